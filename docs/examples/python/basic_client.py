@@ -424,7 +424,7 @@ def example_stealth_browsing():
             print(f"   检测结果:")
             # 解析 JSON 结果
             import json
-            fingerprint = json.loads(eval_response.result.string_value)
+            fingerprint = json.loads(eval_response.result.json_value)
             for key, value in fingerprint.items():
                 print(f"   {key}: {value}")
 
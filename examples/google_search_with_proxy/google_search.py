@@ -229,7 +229,7 @@ class GoogleSearchScraper:
             if press_key_response.HasField('error'):
                 raise Exception(f"提交搜索失败: {press_key_response.error.message}")
         else:
-            result = json.loads(evaluate_response.result.string_value)
+            result = json.loads(evaluate_response.result.json_value)
             print(f"  [DEBUG] 提交方法: {result.get('method')}, 成功: {result.get('success')}")
 
         print(f"  [DEBUG] 等待搜索结果加载...")
@@ -4,10 +4,12 @@
 
 use super::client::CdpClientImpl;
 use super::connection::CdpWebSocketConnection;
+use super::pool::{CdpConnectionPool, CdpPoolConfig};
 use super::traits::*;
 use crate::Error;
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// CDP browser implementation
@@ -17,6 +19,10 @@ pub struct CdpBrowserImpl {
     endpoint: String,
     /// Active connections (target_id -> connection)
     connections: Arc<tokio::sync::Mutex<std::collections::HashMap<String, Arc<dyn CdpConnection>>>>,
+    /// Pool of reusable target connections
+    pool: Arc<CdpConnectionPool>,
+    /// Connection to the browser-level target, opened on first use
+    browser_client: tokio::sync::Mutex<Option<Arc<dyn CdpClient>>>,
 }
 
 impl CdpBrowserImpl {
@@ -25,14 +31,26 @@ impl CdpBrowserImpl {
     /// # Arguments
     /// * `endpoint` - Browser WebSocket endpoint (e.g., "ws://localhost:9222")
     pub fn new<S: Into<String>>(endpoint: S) -> Self {
+        Self::with_pool_config(endpoint, CdpPoolConfig::default())
+    }
+
+    /// Create a new CDP browser controller with a custom connection pool configuration
+    pub fn with_pool_config<S: Into<String>>(endpoint: S, pool_config: CdpPoolConfig) -> Self {
         let endpoint_str = endpoint.into();
         info!("Creating CDP browser controller for endpoint: {}", endpoint_str);
         Self {
             endpoint: endpoint_str,
             connections: Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
+            pool: Arc::new(CdpConnectionPool::new(pool_config)),
+            browser_client: tokio::sync::Mutex::new(None),
         }
     }
 
+    /// Get the connection pool
+    pub fn pool(&self) -> Arc<CdpConnectionPool> {
+        Arc::clone(&self.pool)
+    }
+
     /// Connect to browser and get version info
     async fn connect_browser(&self) -> Result<reqwest::Response, Error> {
         let http_endpoint = self.endpoint.replace("ws://", "http://").replace("wss://", "https://");
@@ -52,6 +70,84 @@ impl CdpBrowserImpl {
             .map_err(|e| Error::internal(format!("Failed to connect to browser: {}", e)))
     }
 
+    /// Resolve the browser-level WebSocket URL from `/json/version`
+    async fn browser_ws_url(&self) -> Result<String, Error> {
+        let version_json: serde_json::Value = self
+            .connect_browser()
+            .await?
+            .json()
+            .await
+            .map_err(|e| Error::internal(format!("Failed to parse version: {}", e)))?;
+        version_json
+            .get("webSocketDebuggerUrl")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| Error::cdp("No webSocketDebuggerUrl in /json/version response"))
+    }
+
+    /// Query `Browser.getVersion` on the browser-level target
+    async fn query_browser_version(&self, ws_url: &str) -> Result<BrowserVersion, Error> {
+        let connection = CdpWebSocketConnection::with_max_message_size(ws_url, self.pool.config().max_message_size).await?;
+        let response = connection
+            .send_command("Browser.getVersion", serde_json::json!({}))
+            .await;
+        let _ = connection.close().await;
+
+        let result = response?
+            .result
+            .ok_or_else(|| Error::cdp("No result in Browser.getVersion response"))?;
+
+        Ok(BrowserVersion::from_cdp_result(&result))
+    }
+
+    /// Check that the endpoint answers CDP commands within `timeout`
+    ///
+    /// Resolves the browser-level WebSocket from `/json/version` and issues
+    /// `Browser.getVersion` on it. Unlike [`CdpBrowser::get_version`] there is
+    /// no fallback to the HTTP metadata: a reachable HTTP endpoint whose
+    /// WebSocket does not work fails the probe.
+    pub async fn probe(&self, timeout: Duration) -> Result<BrowserVersion, Error> {
+        let probe = async {
+            let ws_url = self.browser_ws_url().await?;
+            self.query_browser_version(&ws_url).await
+        };
+
+        tokio::time::timeout(timeout, probe).await.map_err(|_| {
+            Error::timeout(format!(
+                "CDP endpoint {} did not answer within {}ms",
+                self.endpoint,
+                timeout.as_millis()
+            ))
+        })?
+    }
+
+    /// Build version info from the `/json/version` HTTP metadata
+    fn version_from_http_metadata(version_json: &serde_json::Value) -> BrowserVersion {
+        let field = |name: &str| {
+            version_json
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string()
+        };
+
+        // WebKit-Version looks like "537.36 (@<revision>)"
+        let revision = version_json
+            .get("WebKit-Version")
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.split("(@").nth(1))
+            .map(|v| v.trim_end_matches(')').to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        BrowserVersion {
+            protocol_version: field("Protocol-Version"),
+            product: field("Browser"),
+            revision,
+            user_agent: field("User-Agent"),
+            js_version: field("V8-Version"),
+        }
+    }
+
     /// List all targets from browser
     async fn fetch_targets(&self) -> Result<Vec<TargetInfo>, Error> {
         let http_endpoint = self.endpoint.replace("ws://", "http://").replace("wss://", "https://");
@@ -109,8 +205,9 @@ impl CdpBrowser for CdpBrowserImpl {
     async fn create_client(&self, target_url: &str) -> Result<Arc<dyn CdpClient>, Error> {
         info!("Creating CDP client for target: {}", target_url);
 
-        // Connect to target WebSocket
-        let connection = CdpWebSocketConnection::new(target_url).await?;
+        // Borrow a connection from the pool; it is returned when the client is dropped
+        self.pool.ensure_maintenance();
+        let connection = self.pool.acquire(target_url).await?;
 
         // Store connection
         let target_id = target_url
@@ -120,11 +217,12 @@ impl CdpBrowser for CdpBrowserImpl {
             .to_string();
 
         let mut connections = self.connections.lock().await;
-        connections.insert(target_id.clone(), Arc::clone(&connection) as Arc<dyn CdpConnection>);
+        connections.insert(target_id.clone(), connection.inner());
         drop(connections);
 
         // Create client
-        let client = Arc::new(CdpClientImpl::new(connection));
+        let client = Arc::new(CdpClientImpl::new(Arc::new(connection)));
+        client.watch_crashes().await?;
 
         // Enable essential domains only (Page and Runtime are always available)
         // Other domains (Network, DOM, etc.) should be enabled by the caller as needed
@@ -138,6 +236,14 @@ impl CdpBrowser for CdpBrowserImpl {
     async fn close(&self) -> Result<(), Error> {
         info!("CdpBrowser::close: Closing browser at endpoint {}", self.endpoint);
 
+        self.pool.clear().await;
+
+        if let Some(client) = self.browser_client.lock().await.take() {
+            if let Err(e) = client.connection().close().await {
+                warn!("CdpBrowser::close: Failed to close browser-level connection: {}", e);
+            }
+        }
+
         let mut connections = self.connections.lock().await;
         let connection_count = connections.len();
 
@@ -183,6 +289,9 @@ impl CdpBrowser for CdpBrowserImpl {
     }
 
     /// Get browser version
+    ///
+    /// Issues `Browser.getVersion` over the browser-level WebSocket advertised by
+    /// `/json/version`, falling back to the HTTP metadata if that fails.
     async fn get_version(&self) -> Result<BrowserVersion, Error> {
         info!("Getting browser version");
 
@@ -193,28 +302,14 @@ impl CdpBrowser for CdpBrowserImpl {
             .await
             .map_err(|e| Error::internal(format!("Failed to parse version: {}", e)))?;
 
-        Ok(BrowserVersion {
-            protocol_version: version_json
-                .get("Protocol-Version")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string(),
-            product: version_json
-                .get("Browser")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string(),
-            user_agent: version_json
-                .get("User-Agent")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string(),
-            js_version: version_json
-                .get("WebKit-Version")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string(),
-        })
+        if let Some(ws_url) = version_json.get("webSocketDebuggerUrl").and_then(|v| v.as_str()) {
+            match self.query_browser_version(ws_url).await {
+                Ok(version) => return Ok(version),
+                Err(e) => warn!("Browser.getVersion failed, using /json/version metadata: {}", e),
+            }
+        }
+
+        Ok(Self::version_from_http_metadata(&version_json))
     }
 
     /// List all targets (pages, workers, etc.)
@@ -280,6 +375,36 @@ Original error: {}"#,
 
         Ok(ws_url.to_string())
     }
+
+    /// Client on the browser-level target
+    ///
+    /// Opened on first use and reopened if the connection was lost.
+    async fn browser_client(&self) -> Result<Arc<dyn CdpClient>, Error> {
+        let mut slot = self.browser_client.lock().await;
+        if let Some(client) = slot.as_ref().filter(|client| client.connection().is_active()) {
+            return Ok(Arc::clone(client));
+        }
+
+        let ws_url = self.browser_ws_url().await?;
+        info!("Connecting to browser-level target: {}", ws_url);
+        let config = self.pool.config();
+        let connection = CdpWebSocketConnection::with_options(
+            ws_url,
+            config.max_message_size,
+            config.keep_alive,
+            config.max_inflight_cdp_commands,
+        )
+        .await?;
+        let client: Arc<dyn CdpClient> = Arc::new(CdpClientImpl::new(connection));
+        *slot = Some(Arc::clone(&client));
+
+        Ok(client)
+    }
+
+    /// Page targets live at `<endpoint>/devtools/page/<target id>`
+    fn page_ws_url(&self, target_id: &str) -> String {
+        format!("{}/devtools/page/{}", self.endpoint.trim_end_matches('/'), target_id)
+    }
 }
 
 #[cfg(test)]
@@ -292,6 +417,97 @@ mod tests {
         assert_eq!(browser.endpoint, "ws://localhost:9222");
     }
 
+    #[test]
+    fn test_version_from_http_metadata() {
+        let metadata = serde_json::json!({
+            "Browser": "Chrome/120.0.6099.109",
+            "Protocol-Version": "1.3",
+            "User-Agent": "Mozilla/5.0 Chrome/120.0.0.0",
+            "V8-Version": "12.0.267.8",
+            "WebKit-Version": "537.36 (@d46a2e6e16f6a1b7)"
+        });
+
+        let version = CdpBrowserImpl::version_from_http_metadata(&metadata);
+        assert_eq!(version.product, "Chrome/120.0.6099.109");
+        assert_eq!(version.protocol_version, "1.3");
+        assert_eq!(version.revision, "d46a2e6e16f6a1b7");
+        assert_eq!(version.js_version, "12.0.267.8");
+    }
+
+    /// Local endpoint serving `/json/version` over HTTP and the browser target over WebSocket
+    async fn mock_endpoint() -> String {
+        use futures::{SinkExt, StreamExt};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let ws_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}/devtools/browser/mock", ws_listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = ws_listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let response = serde_json::json!({
+                    "id": request["id"],
+                    "result": { "protocolVersion": "1.3", "product": "Chrome/131.0.6778.85" },
+                });
+                if ws.send(Message::Text(response.to_string())).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let http_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("ws://{}", http_listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = http_listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let body = serde_json::json!({ "Browser": "Chrome/131.0.6778.85", "webSocketDebuggerUrl": ws_url }).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        endpoint
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_browser_version() {
+        let browser = CdpBrowserImpl::new(mock_endpoint().await);
+
+        let version = browser.probe(Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(version.product, "Chrome/131.0.6778.85");
+        assert_eq!(version.protocol_version, "1.3");
+    }
+
+    #[tokio::test]
+    async fn test_probe_times_out_on_silent_endpoint() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let browser = CdpBrowserImpl::new(format!("ws://{}", listener.local_addr().unwrap()));
+        tokio::spawn(async move {
+            let _connection = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let started = std::time::Instant::now();
+        let err = browser.probe(Duration::from_millis(200)).await.unwrap_err();
+
+        assert!(matches!(&err, Error::Timeout(message) if message.contains("did not answer within 200ms")));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_page_ws_url() {
+        let browser = CdpBrowserImpl::new("ws://localhost:9222/");
+        assert_eq!(browser.page_ws_url("ABC"), "ws://localhost:9222/devtools/page/ABC");
+    }
+
     #[test]
     fn test_endpoint_conversion() {
         let browser = CdpBrowserImpl::new("wss://remote.example.com:9222");
@@ -7,14 +7,21 @@ use super::types::*;
 use crate::Error;
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tracing::{debug, info};
 
+/// Events Chrome sends when a target's renderer process crashes
+pub const TARGET_CRASHED_EVENTS: [&str; 2] = ["Inspector.targetCrashed", "Target.targetCrashed"];
+
 /// CDP client implementation
 #[derive(Debug, Clone)]
 pub struct CdpClientImpl {
     /// Underlying CDP connection
     connection: Arc<dyn CdpConnection>,
+    /// Set once the target's renderer crashed; commands then fail immediately
+    crashed: Arc<AtomicBool>,
 }
 
 impl CdpClientImpl {
@@ -24,11 +31,77 @@ impl CdpClientImpl {
     /// * `connection` - CDP connection instance
     pub fn new(connection: Arc<dyn CdpConnection>) -> Self {
         info!("Creating CDP client");
-        Self { connection }
+        Self {
+            connection,
+            crashed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Watch for renderer crashes of the client's target
+    ///
+    /// A crashed target never answers, so once a crash event arrives every
+    /// further command fails with `Unavailable` instead of waiting for a reply.
+    pub async fn watch_crashes(&self) -> Result<(), Error> {
+        for event in TARGET_CRASHED_EVENTS {
+            let mut crashes = self.connection.subscribe(event).await?;
+            let crashed = Arc::clone(&self.crashed);
+            tokio::spawn(async move {
+                if crashes.recv().await.is_some() {
+                    tracing::warn!("Target crashed ({})", event);
+                    crashed.store(true, Ordering::SeqCst);
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Whether the client's target crashed
+    pub fn is_crashed(&self) -> bool {
+        self.crashed.load(Ordering::SeqCst)
+    }
+
+    /// `Runtime.evaluate` in `context_id`, or the main world when `None`
+    async fn evaluate_with_params(
+        &self,
+        script: &str,
+        await_promise: bool,
+        context_id: Option<i64>,
+    ) -> Result<EvaluationResult, Error> {
+        debug!("Evaluating script: {}", script);
+
+        let params = EvaluateParams {
+            expression: script.to_string(),
+            await_promise: Some(await_promise),
+            return_by_value: Some(true),
+            context_id,
+        };
+
+        let result = self
+            .call_method(
+                "Runtime.evaluate",
+                serde_json::to_value(params).map_err(|e| Error::cdp(format!("Serialization error: {}", e)))?,
+            )
+            .await?;
+
+        // Thrown exceptions and (with await_promise) rejected promises
+        if let Some(details) = result.get("exceptionDetails") {
+            return Err(Self::exception_error(details));
+        }
+
+        // Parse result - CDP response structure: {"result": {"result": {...}}}
+        let eval_response: crate::cdp::types::EvaluateResponse = serde_json::from_value(result)
+            .map_err(|e| Error::cdp(format!("Failed to parse EvaluateResponse: {}", e)))?;
+        let remote_obj = eval_response.result;
+        debug!("evaluate: parsed RemoteObject: type='{}', value={:?}", remote_obj.r#type, remote_obj.value);
+
+        let eval_result = Self::parse_remote_object(&remote_obj)?;
+        debug!("evaluate: parse_remote_object returned {:?}", eval_result);
+        Ok(eval_result)
     }
 
     /// Parse remote object value to evaluation result
-    fn parse_remote_object(obj: &crate::cdp::types::RemoteObject) -> Result<EvaluationResult, Error> {
+    pub(crate) fn parse_remote_object(obj: &crate::cdp::types::RemoteObject) -> Result<EvaluationResult, Error> {
         let result = match obj.r#type.as_str() {
             "string" => {
                 let value = obj.value
@@ -72,6 +145,61 @@ impl CdpClientImpl {
         debug!("parse_remote_object: returning {:?}", result);
         result
     }
+
+    /// Build a `ScriptExecutionFailed` error from `exceptionDetails`
+    ///
+    /// Carries the exception message, the 1-based line/column where it was thrown
+    /// and the stack trace when one is available. Rejected promises (with
+    /// `awaitPromise`) are reported the same way, using the rejection value.
+    pub(crate) fn exception_error(details: &serde_json::Value) -> Error {
+        let exception = &details["exception"];
+        let description = exception["description"].as_str();
+
+        // Error objects describe themselves as "Error: message\n    at ...";
+        // thrown primitives only carry a value
+        let (message, description_stack) = match description {
+            Some(description) => match description.split_once('\n') {
+                Some((message, stack)) => (message.to_string(), Some(stack.to_string())),
+                None => (description.to_string(), None),
+            },
+            None => match &exception["value"] {
+                serde_json::Value::Null => (details["text"].as_str().unwrap_or("Unknown error").to_string(), None),
+                serde_json::Value::String(value) => (value.clone(), None),
+                value => (value.to_string(), None),
+            },
+        };
+
+        let stack = description_stack.or_else(|| {
+            let frames = details["stackTrace"]["callFrames"].as_array()?;
+            let lines: Vec<String> = frames
+                .iter()
+                .map(|frame| {
+                    let name = frame["functionName"].as_str().filter(|n| !n.is_empty()).unwrap_or("<anonymous>");
+                    format!(
+                        "    at {} ({}:{}:{})",
+                        name,
+                        frame["url"].as_str().unwrap_or(""),
+                        frame["lineNumber"].as_i64().unwrap_or(0) + 1,
+                        frame["columnNumber"].as_i64().unwrap_or(0) + 1
+                    )
+                })
+                .collect();
+            (!lines.is_empty()).then(|| lines.join("\n"))
+        });
+
+        let mut error = format!(
+            "{} (line {}, column {})",
+            message,
+            details["lineNumber"].as_i64().unwrap_or(0) + 1,
+            details["columnNumber"].as_i64().unwrap_or(0) + 1
+        );
+        if let Some(stack) = stack {
+            error.push('\n');
+            error.push_str(&stack);
+        }
+
+        Error::script_execution_failed(error)
+    }
 }
 
 #[async_trait]
@@ -82,12 +210,12 @@ impl CdpClient for CdpClientImpl {
     }
 
     /// Navigate to a URL
-    async fn navigate(&self, url: &str) -> Result<NavigationResult, Error> {
+    async fn navigate(&self, url: &str, referrer: Option<&str>) -> Result<NavigationResult, Error> {
         info!("Navigating to {}", url);
 
         let params = NavigateParams {
             url: url.to_string(),
-            referrer: None,
+            referrer: referrer.map(str::to_string),
             transition_type: None,
         };
 
@@ -146,42 +274,12 @@ impl CdpClient for CdpClientImpl {
 
     /// Evaluate JavaScript in the page
     async fn evaluate(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, Error> {
-        debug!("Evaluating script: {}", script);
-
-        let params = EvaluateParams {
-            expression: script.to_string(),
-            await_promise: Some(await_promise),
-            return_by_value: Some(true),
-            context_id: None,
-        };
-
-        let result = self
-            .call_method(
-                "Runtime.evaluate",
-                serde_json::to_value(params).map_err(|e| Error::cdp(format!("Serialization error: {}", e)))?,
-            )
-            .await?;
-
-        // Check for exception
-        if let Some(exception) = result.get("exceptionDetails") {
-            return Err(Error::script_execution_failed(
-                exception.get("exception")
-                    .and_then(|e| e.get("description"))
-                    .and_then(|d| d.as_str())
-                    .unwrap_or("Unknown error")
-                    .to_string()
-            ));
-        }
-
-        // Parse result - CDP response structure: {"result": {"result": {...}}}
-        let eval_response: crate::cdp::types::EvaluateResponse = serde_json::from_value(result)
-            .map_err(|e| Error::cdp(format!("Failed to parse EvaluateResponse: {}", e)))?;
-        let remote_obj = eval_response.result;
-        debug!("evaluate: parsed RemoteObject: type='{}', value={:?}", remote_obj.r#type, remote_obj.value);
+        self.evaluate_with_params(script, await_promise, None).await
+    }
 
-        let eval_result = Self::parse_remote_object(&remote_obj)?;
-        debug!("evaluate: parse_remote_object returned {:?}", eval_result);
-        Ok(eval_result)
+    /// Evaluate JavaScript in an execution context
+    async fn evaluate_in_context(&self, script: &str, await_promise: bool, context_id: i64) -> Result<EvaluationResult, Error> {
+        self.evaluate_with_params(script, await_promise, Some(context_id)).await
     }
 
     /// Capture a screenshot
@@ -268,6 +366,10 @@ impl CdpClient for CdpClientImpl {
     async fn call_method(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value, Error> {
         debug!("Calling CDP method: {}", method);
 
+        if self.is_crashed() {
+            return Err(Error::unavailable(format!("Target crashed, cannot call {}", method)));
+        }
+
         let response = self.connection().send_command(method, params).await?;
 
         response.result.ok_or_else(|| Error::cdp("No result in response"))
@@ -277,24 +379,145 @@ impl CdpClient for CdpClientImpl {
     async fn subscribe_events(&self, event_type: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
         info!("Subscribing to events: {}", event_type);
 
-        let event_receiver = self.connection.listen_events().await?;
+        self.connection.subscribe(event_type).await
+    }
 
-        // Filter events by type
-        let (tx, rx) = tokio::sync::mpsc::channel(100);
-        let filter_event_type = event_type.to_string();
+    /// Get browser version
+    async fn get_version(&self) -> Result<BrowserVersion, Error> {
+        debug!("Getting browser version");
 
-        tokio::spawn(async move {
-            let mut event_receiver = event_receiver;
-            while let Some(event) = event_receiver.recv().await {
-                if (event.method == filter_event_type || filter_event_type == "*")
-                    && tx.send(event).await.is_err()
-                {
-                    break;
-                }
-            }
+        let result = self.call_method("Browser.getVersion", serde_json::json!({})).await?;
+
+        Ok(BrowserVersion::from_cdp_result(&result))
+    }
+
+    /// Set extra HTTP headers
+    async fn set_extra_http_headers(&self, headers: &HashMap<String, String>) -> Result<(), Error> {
+        info!("Setting {} extra HTTP header(s)", headers.len());
+
+        self.enable_domain("Network").await?;
+
+        let params = serde_json::json!({
+            "headers": headers,
         });
 
-        Ok(rx)
+        let _ = self.call_method("Network.setExtraHTTPHeaders", params).await?;
+
+        Ok(())
+    }
+
+    /// Set CPU throttling rate
+    async fn set_cpu_throttling_rate(&self, rate: f64) -> Result<(), Error> {
+        info!("Setting CPU throttling rate to {}x", rate);
+
+        let _ = self
+            .call_method("Emulation.setCPUThrottlingRate", serde_json::json!({ "rate": rate }))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get DOM storage items
+    async fn get_dom_storage_items(&self, storage_id: &StorageId) -> Result<Vec<(String, String)>, Error> {
+        debug!("Getting DOM storage items for {:?}", storage_id);
+
+        self.enable_domain("DOMStorage").await?;
+
+        let result = self
+            .call_method(
+                "DOMStorage.getDOMStorageItems",
+                serde_json::json!({ "storageId": storage_id }),
+            )
+            .await?;
+
+        // Entries are [key, value] pairs
+        let entries = result
+            .get("entries")
+            .and_then(|v| v.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let key = entry.get(0)?.as_str()?;
+                        let value = entry.get(1)?.as_str()?;
+                        Some((key.to_string(), value.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(entries)
+    }
+
+    /// Set a DOM storage item
+    async fn set_dom_storage_item(&self, storage_id: &StorageId, key: &str, value: &str) -> Result<(), Error> {
+        debug!("Setting DOM storage item '{}' for {:?}", key, storage_id);
+
+        self.enable_domain("DOMStorage").await?;
+
+        let params = serde_json::json!({
+            "storageId": storage_id,
+            "key": key,
+            "value": value,
+        });
+
+        let _ = self.call_method("DOMStorage.setDOMStorageItem", params).await?;
+
+        Ok(())
+    }
+
+    /// Clear a DOM storage area
+    async fn clear_dom_storage(&self, storage_id: &StorageId) -> Result<(), Error> {
+        debug!("Clearing DOM storage for {:?}", storage_id);
+
+        self.enable_domain("DOMStorage").await?;
+
+        let _ = self
+            .call_method("DOMStorage.clear", serde_json::json!({ "storageId": storage_id }))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get all browser cookies
+    async fn get_all_cookies(&self) -> Result<Vec<CookieParam>, Error> {
+        let result = self.call_method("Network.getAllCookies", serde_json::json!({})).await?;
+        serde_json::from_value(result.get("cookies").cloned().unwrap_or_else(|| serde_json::json!([])))
+            .map_err(|e| Error::cdp(format!("Failed to parse cookies: {}", e)))
+    }
+
+    /// Set many cookies in one batch
+    async fn set_cookies(&self, cookies: &[CookieParam]) -> Result<(), Error> {
+        debug!("Setting {} cookies", cookies.len());
+
+        let _ = self
+            .call_method("Network.setCookies", serde_json::json!({ "cookies": cookies }))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Delete all browser cookies
+    async fn clear_browser_cookies(&self) -> Result<(), Error> {
+        let _ = self
+            .call_method("Network.clearBrowserCookies", serde_json::json!({}))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Insert text into the focused element
+    ///
+    /// Fires `beforeinput`/`input` like a paste, so framework-controlled inputs
+    /// (e.g. React) pick up the new value, but no key events.
+    async fn insert_text(&self, text: &str) -> Result<(), Error> {
+        debug!("Inserting {} characters of text", text.chars().count());
+
+        let _ = self
+            .call_method("Input.insertText", serde_json::json!({ "text": text }))
+            .await?;
+
+        Ok(())
     }
 }
 
@@ -344,6 +567,66 @@ mod tests {
         assert!(matches!(result, EvaluationResult::Bool(true)));
     }
 
+    /// Client whose connection answers `Runtime.evaluate` with `response`
+    async fn client_with_evaluate_response(response: serde_json::Value) -> CdpClientImpl {
+        let connection = crate::cdp::mock::MockCdpConnection::new();
+        connection.stub_method("Runtime.evaluate", response).await;
+        CdpClientImpl::new(Arc::new(connection))
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_surfaces_thrown_exception() {
+        let client = client_with_evaluate_response(serde_json::json!({
+            "result": { "type": "object", "subtype": "error" },
+            "exceptionDetails": {
+                "text": "Uncaught",
+                "lineNumber": 2,
+                "columnNumber": 10,
+                "exception": {
+                    "type": "object",
+                    "subtype": "error",
+                    "description": "TypeError: boom\n    at check (<anonymous>:3:11)",
+                },
+            },
+        }))
+        .await;
+
+        let err = client.evaluate("check()", false).await.unwrap_err();
+
+        let Error::ScriptExecutionFailed(message) = err else {
+            panic!("expected ScriptExecutionFailed, got {:?}", err);
+        };
+        assert!(message.starts_with("TypeError: boom (line 3, column 11)"));
+        assert!(message.contains("at check (<anonymous>:3:11)"));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_surfaces_rejected_promise() {
+        let client = client_with_evaluate_response(serde_json::json!({
+            "result": { "type": "string", "value": "denied" },
+            "exceptionDetails": {
+                "text": "Uncaught (in promise)",
+                "lineNumber": 0,
+                "columnNumber": 0,
+                "exception": { "type": "string", "value": "denied" },
+                "stackTrace": {
+                    "callFrames": [
+                        { "functionName": "", "url": "https://example.com/app.js", "lineNumber": 4, "columnNumber": 7 },
+                    ],
+                },
+            },
+        }))
+        .await;
+
+        let err = client.evaluate("Promise.reject('denied')", true).await.unwrap_err();
+
+        let Error::ScriptExecutionFailed(message) = err else {
+            panic!("expected ScriptExecutionFailed, got {:?}", err);
+        };
+        assert!(message.starts_with("denied (line 1, column 1)"));
+        assert!(message.contains("at <anonymous> (https://example.com/app.js:5:8)"));
+    }
+
     #[test]
     fn test_parse_remote_object_null() {
         let obj = crate::cdp::types::RemoteObject {
@@ -3,16 +3,144 @@
 //! This module provides WebSocket-based connection to Chrome DevTools Protocol.
 
 use super::types::*;
+use super::subscriptions::{pattern_domain, DomainTracker, EventRegistry, ALL_EVENTS};
 use super::traits::{CdpConnection, CdpEvent, CdpError as CdpErrorResponse, CdpResponse};
 use crate::Error;
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
-use tokio_tungstenite::{connect_async, tungstenite::Message, WebSocketStream};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::{connect_async_with_config, tungstenite, tungstenite::Message, WebSocketStream};
 use tracing::{debug, error, info, warn};
 
+/// Default limit for a single incoming CDP message (256 MiB)
+///
+/// Full-page screenshots and `Runtime.evaluate` results of large pages can be
+/// tens of megabytes, well above tungstenite's 64 MiB / 16 MiB frame defaults
+/// once base64-encoded.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// Default limit for commands awaiting a response on one connection
+///
+/// High enough that ordinary pages never queue, low enough that a burst of
+/// commands cannot pile hundreds of messages onto a single WebSocket.
+pub const DEFAULT_MAX_INFLIGHT_CDP_COMMANDS: usize = 64;
+
+/// CDP error code reported to pending commands failed by the connection itself
+const CONNECTION_ERROR_CODE: i32 = -32000;
+
+/// Default interval between keep-alive pings
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default time to wait for the pong answering a keep-alive ping
+pub const DEFAULT_PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Event dispatched to subscribers when a connection is found dead
+///
+/// Chrome sends `Inspector.detached` when it drops a debugging session; the
+/// same event is raised locally, with [`CONNECTION_LOST_REASON`] as its
+/// `reason`, when the peer stops answering keep-alive pings.
+pub const DETACHED_EVENT: &str = "Inspector.detached";
+
+/// `reason` of a locally raised [`DETACHED_EVENT`]
+pub const CONNECTION_LOST_REASON: &str = "connection_lost";
+
+/// How long the message loop waits for a message before releasing the stream lock
+const RECEIVE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// WebSocket keep-alive configuration
+///
+/// The message loop sends a ping frame every `ping_interval`. A peer that does
+/// not answer within `pong_timeout` is considered dead: pending commands fail,
+/// the connection is marked inactive, so the connection pool drops it and
+/// opens a new one on the next acquire, and subscribers get a
+/// [`DETACHED_EVENT`] so the page holding it is reported as lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAliveConfig {
+    /// Interval between pings; `None` disables keep-alive
+    pub ping_interval: Option<Duration>,
+    /// Time to wait for the pong answering a ping
+    pub pong_timeout: Duration,
+}
+
+impl Default for KeepAliveConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
+        }
+    }
+}
+
+impl KeepAliveConfig {
+    /// Keep-alive turned off
+    pub fn disabled() -> Self {
+        Self {
+            ping_interval: None,
+            ..Self::default()
+        }
+    }
+}
+
+/// Keep-alive progress of a message loop
+#[derive(Debug)]
+struct KeepAliveState {
+    config: KeepAliveConfig,
+    last_ping: Instant,
+    /// When the unanswered ping was sent
+    awaiting_pong: Option<Instant>,
+}
+
+/// What the message loop must do to keep the connection alive
+#[derive(Debug, PartialEq, Eq)]
+enum KeepAliveAction {
+    None,
+    Ping,
+    /// The last ping went unanswered for longer than the pong timeout
+    PeerDead,
+}
+
+impl KeepAliveState {
+    fn new(config: KeepAliveConfig) -> Self {
+        Self {
+            config,
+            last_ping: Instant::now(),
+            awaiting_pong: None,
+        }
+    }
+
+    /// How long a single receive may wait so pings are not delayed
+    fn poll_interval(&self) -> Duration {
+        self.config
+            .ping_interval
+            .map_or(RECEIVE_POLL_INTERVAL, |interval| interval.min(RECEIVE_POLL_INTERVAL))
+    }
+
+    fn next_action(&self, now: Instant) -> KeepAliveAction {
+        let Some(interval) = self.config.ping_interval else {
+            return KeepAliveAction::None;
+        };
+        match self.awaiting_pong {
+            Some(sent_at) if now.duration_since(sent_at) >= self.config.pong_timeout => KeepAliveAction::PeerDead,
+            Some(_) => KeepAliveAction::None,
+            None if now.duration_since(self.last_ping) >= interval => KeepAliveAction::Ping,
+            None => KeepAliveAction::None,
+        }
+    }
+
+    fn ping_sent(&mut self, now: Instant) {
+        self.last_ping = now;
+        self.awaiting_pong = Some(now);
+    }
+
+    fn pong_received(&mut self) {
+        self.awaiting_pong = None;
+    }
+}
+
 /// CDP timeout configuration
 #[derive(Debug, Clone)]
 struct CdpTimeoutConfig {
@@ -50,6 +178,11 @@ impl CdpTimeoutConfig {
             return tokio::time::Duration::from_secs(self.screenshot_timeout_secs);
         }
 
+        // Heap snapshots of large pages take as long as screenshots
+        if method_lower.contains("heapsnapshot") {
+            return tokio::time::Duration::from_secs(self.screenshot_timeout_secs);
+        }
+
         // Navigation commands
         if method_lower.contains("navigate")
             || method_lower.starts_with("page.navigate")
@@ -88,6 +221,34 @@ struct PendingCommand {
     method: String,
 }
 
+/// Commands awaiting a response, by command ID
+///
+/// A std mutex: it is never held across an await, and `PendingGuard` must be
+/// able to remove entries from `Drop`.
+type PendingCommands = Arc<std::sync::Mutex<std::collections::HashMap<u64, PendingCommand>>>;
+
+/// Lock the pending map, recovering from a poisoned lock (entries stay valid)
+fn lock_pending(
+    pending_commands: &PendingCommands,
+) -> std::sync::MutexGuard<'_, std::collections::HashMap<u64, PendingCommand>> {
+    pending_commands.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Removes a command from the pending map when its caller stops waiting
+///
+/// Covers every exit of `execute`, including the caller's future being
+/// dropped mid-flight, so no entry outlives its command.
+struct PendingGuard<'a> {
+    pending_commands: &'a PendingCommands,
+    id: u64,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        lock_pending(self.pending_commands).remove(&self.id);
+    }
+}
+
 /// CDP WebSocket connection implementation
 #[derive(Debug)]
 pub struct CdpWebSocketConnection {
@@ -100,13 +261,26 @@ pub struct CdpWebSocketConnection {
     /// Next command ID
     next_id: Arc<AtomicU64>,
     /// Pending commands (ID -> response sender)
-    pending_commands: Arc<Mutex<std::collections::HashMap<u64, PendingCommand>>>,
-    /// Event subscribers
-    event_subscribers: Arc<Mutex<Vec<tokio::sync::mpsc::UnboundedSender<CdpEvent>>>>,
+    pending_commands: PendingCommands,
+    /// Event subscribers, routed by event name or domain
+    event_subscribers: Arc<Mutex<EventRegistry>>,
+    /// Subscriber counts of domains enabled on demand
+    ///
+    /// Held while the matching `enable`/`disable` command is in flight so
+    /// toggles of a domain reach the browser in order.
+    domains: Mutex<DomainTracker>,
+    /// Handle used by subscription tasks to unsubscribe
+    self_ref: Weak<Self>,
     /// Is connection active
     is_active: Arc<AtomicBool>,
     /// Timeout configuration
     timeout_config: CdpTimeoutConfig,
+    /// Largest incoming message (and frame) accepted, in bytes
+    max_message_size: usize,
+    /// Keep-alive ping settings
+    keep_alive: KeepAliveConfig,
+    /// Permits for commands awaiting a response; `None` means unlimited
+    in_flight: Option<Semaphore>,
 }
 
 impl CdpWebSocketConnection {
@@ -115,18 +289,45 @@ impl CdpWebSocketConnection {
     /// # Arguments
     /// * `url` - WebSocket URL (e.g., "ws://localhost:9222/devtools/page/ABC123")
     pub async fn new<S: Into<String>>(url: S) -> Result<Arc<Self>, Error> {
+        Self::with_max_message_size(url, DEFAULT_MAX_MESSAGE_SIZE).await
+    }
+
+    /// Create a new CDP WebSocket connection accepting messages up to `max_message_size` bytes
+    ///
+    /// A larger message fails all pending commands with a CDP error and closes
+    /// the connection, since the stream cannot be resynchronized afterwards.
+    pub async fn with_max_message_size<S: Into<String>>(url: S, max_message_size: usize) -> Result<Arc<Self>, Error> {
+        Self::with_options(url, max_message_size, KeepAliveConfig::default(), DEFAULT_MAX_INFLIGHT_CDP_COMMANDS).await
+    }
+
+    /// Create a new CDP WebSocket connection with a message size limit, keep-alive settings
+    /// and a limit on commands awaiting a response
+    ///
+    /// Commands beyond `max_inflight_cdp_commands` queue in call order until a
+    /// running one completes, whichever client sends them. `0` disables the limit.
+    pub async fn with_options<S: Into<String>>(
+        url: S,
+        max_message_size: usize,
+        keep_alive: KeepAliveConfig,
+        max_inflight_cdp_commands: usize,
+    ) -> Result<Arc<Self>, Error> {
         let url = url.into();
         info!("Creating CDP WebSocket connection to {}", url);
 
-        let connection = Arc::new(Self {
+        let connection = Arc::new_cyclic(|self_ref| Self {
             url,
             ws_stream: Arc::new(Mutex::new(None)),
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
             next_id: Arc::new(AtomicU64::new(1)),
-            pending_commands: Arc::new(Mutex::new(std::collections::HashMap::new())),
-            event_subscribers: Arc::new(Mutex::new(Vec::new())),
+            pending_commands: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            event_subscribers: Arc::new(Mutex::new(EventRegistry::default())),
+            domains: Mutex::new(DomainTracker::default()),
+            self_ref: self_ref.clone(),
             is_active: Arc::new(AtomicBool::new(false)),
             timeout_config: CdpTimeoutConfig::default(),
+            max_message_size,
+            keep_alive,
+            in_flight: (max_inflight_cdp_commands > 0).then(|| Semaphore::new(max_inflight_cdp_commands)),
         });
 
         // Connect to WebSocket
@@ -147,7 +348,13 @@ impl CdpWebSocketConnection {
 
         info!("Connecting to WebSocket: {}", self.url);
 
-        match connect_async(&self.url).await {
+        let config = WebSocketConfig {
+            max_message_size: Some(self.max_message_size),
+            max_frame_size: Some(self.max_message_size),
+            ..Default::default()
+        };
+
+        match connect_async_with_config(&self.url, Some(config), false).await {
             Ok((ws_stream, _)) => {
                 let mut stream_guard = self.ws_stream.lock().await;
                 *stream_guard = Some(ws_stream);
@@ -163,9 +370,12 @@ impl CdpWebSocketConnection {
                 // Start message loop - we need to clone the Arcs we need
                 let ws_stream = Arc::clone(&self.ws_stream);
                 let pending_commands = Arc::clone(&self.pending_commands);
+                let loop_pending_commands = Arc::clone(&self.pending_commands);
                 let event_subscribers = Arc::clone(&self.event_subscribers);
                 let is_active = Arc::clone(&self.is_active);
                 let next_id = Arc::clone(&self.next_id);
+                let max_message_size = self.max_message_size;
+                let keep_alive = self.keep_alive;
 
                 info!("Starting message loop for CDP connection");
 
@@ -173,13 +383,19 @@ impl CdpWebSocketConnection {
                     info!("Message loop task started");
                     if let Err(e) = Self::message_loop_with_arcs(
                         ws_stream,
-                        pending_commands,
+                        loop_pending_commands,
                         event_subscribers,
-                        is_active,
+                        Arc::clone(&is_active),
                         next_id,
+                        max_message_size,
+                        keep_alive,
                     ).await {
                         error!("Message loop error: {}", e);
                     }
+
+                    // Nothing will answer the remaining commands once the loop is gone
+                    is_active.store(false, Ordering::SeqCst);
+                    Self::fail_pending_commands(&pending_commands, "CDP connection closed").await;
                     info!("Message loop task exited");
                 });
 
@@ -269,16 +485,51 @@ impl CdpWebSocketConnection {
     /// Message processing loop with Arc parameters (for spawned tasks)
     ///
     /// CRITICAL: Uses try_lock to periodically release the lock and allow send_command to send.
+    ///
+    /// Between receives the loop sends keep-alive pings and ends when a pong is overdue.
+    #[allow(clippy::too_many_arguments)]
     async fn message_loop_with_arcs(
         ws_stream: Arc<Mutex<Option<WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>>>>,
-        pending_commands: Arc<Mutex<std::collections::HashMap<u64, PendingCommand>>>,
-        event_subscribers: Arc<Mutex<Vec<tokio::sync::mpsc::UnboundedSender<CdpEvent>>>>,
+        pending_commands: PendingCommands,
+        event_subscribers: Arc<Mutex<EventRegistry>>,
         is_active: Arc<AtomicBool>,
         _next_id: Arc<AtomicU64>,
+        max_message_size: usize,
+        keep_alive: KeepAliveConfig,
     ) -> Result<(), Error> {
         info!("CDP message loop: Starting message processing loop");
+        let mut keep_alive = KeepAliveState::new(keep_alive);
 
         while is_active.load(Ordering::SeqCst) {
+            match keep_alive.next_action(Instant::now()) {
+                KeepAliveAction::None => {}
+                KeepAliveAction::Ping => {
+                    let mut stream_guard = ws_stream.lock().await;
+                    if let Some(stream) = stream_guard.as_mut() {
+                        debug!("Sending keep-alive ping");
+                        if let Err(e) = stream.send(Message::Ping(Vec::new())).await {
+                            warn!("Failed to send keep-alive ping: {}", e);
+                        }
+                    }
+                    keep_alive.ping_sent(Instant::now());
+                }
+                KeepAliveAction::PeerDead => {
+                    let message = format!(
+                        "CDP connection lost: no pong within {} ms",
+                        keep_alive.config.pong_timeout.as_millis()
+                    );
+                    error!("{}", message);
+                    is_active.store(false, Ordering::SeqCst);
+                    Self::fail_pending_commands(&pending_commands, &message).await;
+                    event_subscribers.lock().await.dispatch(&CdpEvent {
+                        method: DETACHED_EVENT.to_string(),
+                        params: serde_json::json!({ "reason": CONNECTION_LOST_REASON }),
+                        session_id: None,
+                    });
+                    break;
+                }
+            }
+
             // Use try_lock to avoid blocking send_command
             // If lock is available, try to receive a message with short timeout
             let mut stream_guard = match ws_stream.try_lock() {
@@ -303,7 +554,7 @@ impl CdpWebSocketConnection {
 
             // Try to receive with short timeout (100ms) then release lock
             let message_result = tokio::time::timeout(
-                tokio::time::Duration::from_millis(100),
+                keep_alive.poll_interval(),
                 ws_stream_ref.next(),
             ).await;
 
@@ -337,9 +588,21 @@ impl CdpWebSocketConnection {
                                 }
                             }
                         }
+                        Message::Pong(_) => keep_alive.pong_received(),
                         _ => {}
                     }
                 }
+                Ok(Some(Err(tungstenite::Error::Capacity(e)))) => {
+                    // The rest of the oversized message is unread, so the connection is unusable
+                    let message = format!(
+                        "CDP message exceeds max_ws_message_size of {} bytes ({})",
+                        max_message_size, e
+                    );
+                    error!("{}", message);
+                    is_active.store(false, Ordering::SeqCst);
+                    Self::fail_pending_commands(&pending_commands, &message).await;
+                    break;
+                }
                 Ok(Some(Err(e))) => {
                     let error_msg = e.to_string();
                     error!("WebSocket error: {}", error_msg);
@@ -371,11 +634,30 @@ impl CdpWebSocketConnection {
         Ok(())
     }
 
+    /// Answer every pending command with a CDP error carrying `message`
+    async fn fail_pending_commands(
+        pending_commands: &PendingCommands,
+        message: &str,
+    ) {
+        for (id, pending_cmd) in lock_pending(pending_commands).drain() {
+            warn!("Failing pending command {} ({}): {}", id, pending_cmd.method, message);
+            let _ = pending_cmd.sender.send(CdpResponse {
+                id,
+                result: None,
+                error: Some(CdpErrorResponse {
+                    code: CONNECTION_ERROR_CODE,
+                    message: message.to_string(),
+                    data: None,
+                }),
+            });
+        }
+    }
+
     /// Handle incoming WebSocket message with Arc parameters
     async fn handle_message_with_arcs(
         text: &str,
-        pending_commands: &Arc<Mutex<std::collections::HashMap<u64, PendingCommand>>>,
-        event_subscribers: &Arc<Mutex<Vec<tokio::sync::mpsc::UnboundedSender<CdpEvent>>>>,
+        pending_commands: &PendingCommands,
+        event_subscribers: &Arc<Mutex<EventRegistry>>,
     ) -> Result<(), Error> {
         info!("Processing received message: {}", text);
 
@@ -415,12 +697,12 @@ impl CdpWebSocketConnection {
     /// Handle CDP response with Arc parameters
     async fn handle_response_with_arcs(
         response: CdpRpcResponse,
-        pending_commands: &Arc<Mutex<std::collections::HashMap<u64, PendingCommand>>>,
+        pending_commands: &PendingCommands,
     ) -> Result<(), Error> {
         info!("Handling response for command ID: {}", response.id);
-        let mut pending = pending_commands.lock().await;
+        let pending_cmd = lock_pending(pending_commands).remove(&response.id);
 
-        if let Some(pending_cmd) = pending.remove(&response.id) {
+        if let Some(pending_cmd) = pending_cmd {
             info!("Found pending command for ID {}: {}", response.id, pending_cmd.method);
 
             let cdp_response = CdpResponse {
@@ -445,7 +727,7 @@ impl CdpWebSocketConnection {
     /// Handle CDP notification/event with Arc parameters
     async fn handle_notification_with_arcs(
         notification: CdpNotification,
-        event_subscribers: &Arc<Mutex<Vec<tokio::sync::mpsc::UnboundedSender<CdpEvent>>>>,
+        event_subscribers: &Arc<Mutex<EventRegistry>>,
     ) -> Result<(), Error> {
         debug!("Received event: {}", notification.method);
 
@@ -455,20 +737,8 @@ impl CdpWebSocketConnection {
             session_id: notification.session_id,
         };
 
-        // Broadcast to all subscribers
-        let mut subscribers = event_subscribers.lock().await;
-        let mut dead_subscribers = Vec::new();
-
-        for (i, sender) in subscribers.iter().enumerate() {
-            if sender.send(event.clone()).is_err() {
-                dead_subscribers.push(i);
-            }
-        }
-
-        // Remove dead subscribers
-        for i in dead_subscribers.into_iter().rev() {
-            subscribers.remove(i);
-        }
+        // Deliver to matching subscribers only
+        event_subscribers.lock().await.dispatch(&event);
 
         Ok(())
     }
@@ -476,9 +746,9 @@ impl CdpWebSocketConnection {
     /// Handle CDP response
     #[allow(dead_code)]
     async fn handle_response(&self, response: CdpRpcResponse) -> Result<(), Error> {
-        let mut pending = self.pending_commands.lock().await;
+        let pending_cmd = lock_pending(&self.pending_commands).remove(&response.id);
 
-        if let Some(pending_cmd) = pending.remove(&response.id) {
+        if let Some(pending_cmd) = pending_cmd {
             debug!("Received response for command {}: {}", response.id, pending_cmd.method);
 
             let cdp_response = CdpResponse {
@@ -511,53 +781,30 @@ impl CdpWebSocketConnection {
             session_id: notification.session_id,
         };
 
-        // Broadcast to all subscribers
-        let mut subscribers = self.event_subscribers.lock().await;
-        let mut dead_subscribers = Vec::new();
-
-        for (i, sender) in subscribers.iter().enumerate() {
-            if sender.send(event.clone()).is_err() {
-                dead_subscribers.push(i);
-            }
-        }
-
-        // Remove dead subscribers
-        for i in dead_subscribers.into_iter().rev() {
-            subscribers.remove(i);
-        }
-
-        Ok(())
-    }
-
-    /// Send WebSocket message
-    async fn send_message(&self, message: Message) -> Result<(), Error> {
-        let mut stream_guard = self.ws_stream.lock().await;
-        let ws_stream = stream_guard
-            .as_mut()
-            .ok_or_else(|| Error::websocket("WebSocket stream not available"))?;
-
-        info!("WebSocket: Sending message: {:?}", message);
-
-        ws_stream
-            .send(message)
-            .await
-            .map_err(|e| Error::websocket(format!("Failed to send message: {}", e)))?;
-
-        info!("WebSocket: Message sent successfully");
+        // Deliver to matching subscribers only
+        self.event_subscribers.lock().await.dispatch(&event);
 
         Ok(())
     }
-}
 
-#[async_trait]
-impl CdpConnection for CdpWebSocketConnection {
-    /// Send a CDP command and wait for response
-    async fn send_command(&self, method: &str, params: serde_json::Value) -> Result<CdpResponse, Error> {
+    /// Send a CDP command and wait for response, bypassing domain tracking
+    async fn execute(&self, method: &str, params: serde_json::Value) -> Result<CdpResponse, Error> {
         // Check if connection is active
         if !self.is_active.load(Ordering::SeqCst) {
             return Err(Error::websocket("Connection is not active"));
         }
 
+        // Tokio's semaphore is fair: queued commands are sent in call order
+        let _permit = match &self.in_flight {
+            Some(in_flight) => Some(
+                in_flight
+                    .acquire()
+                    .await
+                    .map_err(|_| Error::cdp("CDP command limiter closed"))?,
+            ),
+            None => None,
+        };
+
         // Generate command ID
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
 
@@ -582,16 +829,22 @@ impl CdpConnection for CdpWebSocketConnection {
         // Create response channel
         let (sender, receiver) = tokio::sync::oneshot::channel();
 
-        // Register pending command
-        {
-            let mut pending = self.pending_commands.lock().await;
-            pending.insert(
-                id,
-                PendingCommand {
-                    sender,
-                    method: method.to_string(),
-                },
-            );
+        // Register pending command; the guard removes it on every exit path
+        lock_pending(&self.pending_commands).insert(
+            id,
+            PendingCommand {
+                sender,
+                method: method.to_string(),
+            },
+        );
+        let _pending_guard = PendingGuard {
+            pending_commands: &self.pending_commands,
+            id,
+        };
+
+        // The message loop may have failed all pending commands before we registered
+        if !self.is_active.load(Ordering::SeqCst) {
+            return Err(Error::websocket("Connection is not active"));
         }
 
         // Send request
@@ -615,33 +868,111 @@ impl CdpConnection for CdpWebSocketConnection {
                 Ok(response)
             }
             Ok(Err(_)) => Err(Error::timeout(format!("Command {} response channel closed", id))),
-            Err(_) => {
-                // Clean up pending command
-                let mut pending = self.pending_commands.lock().await;
-                pending.remove(&id);
-                Err(Error::timeout(format!("Command {} timed out", id)))
+            Err(_) => Err(Error::timeout(format!("Command {} timed out", id))),
+        }
+    }
+
+    /// Remove a subscription, disabling its domain when it was the last listener
+    async fn unsubscribe(&self, pattern: &str, id: u64) {
+        if !self.event_subscribers.lock().await.remove(pattern, id) {
+            return;
+        }
+        let Some(domain) = pattern_domain(pattern) else {
+            return;
+        };
+
+        let mut domains = self.domains.lock().await;
+        if domains.unsubscribe(domain) && self.is_active.load(Ordering::SeqCst) {
+            debug!("Last subscriber of {} left, disabling domain", domain);
+            if let Err(e) = self.execute(&format!("{}.disable", domain), serde_json::json!({})).await {
+                warn!("Failed to disable {}: {}", domain, e);
+            }
+        }
+    }
+
+    /// Send WebSocket message
+    async fn send_message(&self, message: Message) -> Result<(), Error> {
+        let mut stream_guard = self.ws_stream.lock().await;
+        let ws_stream = stream_guard
+            .as_mut()
+            .ok_or_else(|| Error::websocket("WebSocket stream not available"))?;
+
+        info!("WebSocket: Sending message: {:?}", message);
+
+        ws_stream
+            .send(message)
+            .await
+            .map_err(|e| Error::websocket(format!("Failed to send message: {}", e)))?;
+
+        info!("WebSocket: Message sent successfully");
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CdpConnection for CdpWebSocketConnection {
+    /// Send a CDP command and wait for response
+    async fn send_command(&self, method: &str, params: serde_json::Value) -> Result<CdpResponse, Error> {
+        // Domains enabled explicitly are never disabled by subscription tracking
+        if let Some((domain, command)) = method.split_once('.') {
+            match command {
+                "enable" => self.domains.lock().await.set_explicit(domain, true),
+                "disable" => self.domains.lock().await.set_explicit(domain, false),
+                _ => {}
             }
         }
+
+        self.execute(method, params).await
     }
 
     /// Subscribe to CDP events
     async fn listen_events(&self) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
-        let (sender, receiver) = tokio::sync::mpsc::channel(100);
+        self.subscribe(ALL_EVENTS).await
+    }
 
-        // Use unbounded channel for event broadcasting
-        let (unbounded_sender, mut unbounded_receiver) = tokio::sync::mpsc::unbounded_channel();
+    /// Subscribe to events matching `pattern`, enabling its domain on first use
+    async fn subscribe(&self, pattern: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
+        if !self.is_active.load(Ordering::SeqCst) {
+            return Err(Error::websocket("Connection is not active"));
+        }
 
-        let mut subscribers = self.event_subscribers.lock().await;
-        subscribers.push(unbounded_sender);
-        drop(subscribers);
+        // Register before enabling so no event emitted right after `enable` is lost
+        let (unbounded_sender, mut unbounded_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let id = self.event_subscribers.lock().await.add(pattern, unbounded_sender);
+        if let Some(domain) = pattern_domain(pattern) {
+            let mut domains = self.domains.lock().await;
+            if domains.subscribe(domain) {
+                if let Err(e) = self.execute(&format!("{}.enable", domain), serde_json::json!({})).await {
+                    domains.unsubscribe(domain);
+                    drop(domains);
+                    self.event_subscribers.lock().await.remove(pattern, id);
+                    return Err(e);
+                }
+            }
+        }
 
-        // Forward events to bounded channel
+        // Forward to a bounded channel; unsubscribe once the receiver is dropped
+        let (sender, receiver) = tokio::sync::mpsc::channel(100);
+        let connection = self.self_ref.clone();
+        let pattern = pattern.to_string();
         tokio::spawn(async move {
-            while let Some(event) = unbounded_receiver.recv().await {
-                if sender.send(event).await.is_err() {
-                    break;
+            loop {
+                tokio::select! {
+                    event = unbounded_receiver.recv() => match event {
+                        Some(event) => {
+                            if sender.send(event).await.is_err() {
+                                break;
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = sender.closed() => break,
                 }
             }
+            if let Some(connection) = connection.upgrade() {
+                connection.unsubscribe(&pattern, id).await;
+            }
         });
 
         Ok(receiver)
@@ -692,4 +1023,303 @@ mod tests {
             assert_eq!(*s, ConnectionState::Connecting);
         }
     }
+
+    #[tokio::test]
+    async fn test_oversized_message_fails_pending_command() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        // Answer the first command normally, the second with a frame over the limit
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let id = request["id"].as_u64().unwrap();
+                let result = if id == 1 {
+                    serde_json::json!({})
+                } else {
+                    serde_json::json!({ "data": "x".repeat(4096) })
+                };
+                let response = serde_json::json!({ "id": id, "result": result });
+                if ws.send(Message::Text(response.to_string())).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let connection = CdpWebSocketConnection::with_max_message_size(url, 1024).await.unwrap();
+        connection.send_command("Page.enable", serde_json::json!({})).await.unwrap();
+
+        let err = connection
+            .send_command("Page.captureScreenshot", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, Error::Cdp(message) if message.contains("max_ws_message_size of 1024 bytes")));
+        assert!(!connection.is_active());
+    }
+
+    /// Local CDP endpoint answering every command; reports command names on the channel
+    async fn recording_server() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (methods_tx, methods_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let _ = methods_tx.send(request["method"].as_str().unwrap().to_string());
+                let response = serde_json::json!({ "id": request["id"], "result": {} });
+                if ws.send(Message::Text(response.to_string())).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        (url, methods_rx)
+    }
+
+    #[tokio::test]
+    async fn test_domain_enabled_on_first_subscribe_and_disabled_on_last_unsubscribe() {
+        let (url, mut methods) = recording_server().await;
+        let connection = CdpWebSocketConnection::new(url).await.unwrap();
+
+        let requests = connection.subscribe("Network.requestWillBeSent").await.unwrap();
+        assert_eq!(methods.recv().await.unwrap(), "Network.enable");
+        let responses = connection.subscribe("Network.responseReceived").await.unwrap();
+        let _all = connection.listen_events().await.unwrap();
+
+        drop(requests);
+        connection.send_command("Page.navigate", serde_json::json!({})).await.unwrap();
+        // Still one Network subscriber left, so no Network.disable before this command
+        assert_eq!(methods.recv().await.unwrap(), "Page.navigate");
+
+        drop(responses);
+        let disabled = tokio::time::timeout(std::time::Duration::from_secs(5), methods.recv()).await.unwrap();
+        assert_eq!(disabled.unwrap(), "Network.disable");
+    }
+
+    /// Local CDP endpoint answering commands in batches of ten, in reverse order
+    ///
+    /// `Test.hang` is never answered and `Test.close` closes the socket.
+    async fn reordering_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let mut batch = Vec::new();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                match request["method"].as_str().unwrap() {
+                    "Test.hang" => continue,
+                    "Test.close" => break,
+                    _ => batch.push(request),
+                }
+                if batch.len() == 10 {
+                    for request in batch.drain(..).rev() {
+                        let response = serde_json::json!({
+                            "id": request["id"],
+                            "result": { "n": request["params"]["n"] },
+                        });
+                        ws.send(Message::Text(response.to_string())).await.unwrap();
+                    }
+                }
+            }
+            let _ = ws.close(None).await;
+        });
+
+        url
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commands_get_matching_responses() {
+        let connection = CdpWebSocketConnection::new(reordering_server().await).await.unwrap();
+        let client = crate::cdp::CdpClientImpl::new(connection.clone());
+
+        let results = futures::future::join_all((0..300).map(|n| {
+            let client = client.clone();
+            async move {
+                use crate::cdp::CdpClient;
+                (n, client.call_method("Test.echo", serde_json::json!({ "n": n })).await)
+            }
+        }))
+        .await;
+
+        for (n, result) in results {
+            assert_eq!(result.unwrap()["n"], n);
+        }
+        assert!(lock_pending(&connection.pending_commands).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_command_leaves_no_pending_entry() {
+        let connection = CdpWebSocketConnection::new(reordering_server().await).await.unwrap();
+
+        let cancelled = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            connection.send_command("Test.hang", serde_json::json!({})),
+        )
+        .await;
+
+        assert!(cancelled.is_err());
+        assert!(lock_pending(&connection.pending_commands).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_commands_fail_when_connection_closes() {
+        let connection = CdpWebSocketConnection::new(reordering_server().await).await.unwrap();
+
+        let hanging = tokio::spawn({
+            let connection = connection.clone();
+            async move { connection.send_command("Test.hang", serde_json::json!({})).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let _ = connection.send_command("Test.close", serde_json::json!({})).await;
+
+        let err = tokio::time::timeout(std::time::Duration::from_secs(5), hanging)
+            .await
+            .expect("pending command should fail promptly")
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(&err, Error::Cdp(message) if message.contains("connection closed")));
+        assert!(!connection.is_active());
+        assert!(lock_pending(&connection.pending_commands).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_limit_queues_commands_in_order() {
+        let url = reordering_server().await;
+        let connection = CdpWebSocketConnection::with_options(url, DEFAULT_MAX_MESSAGE_SIZE, KeepAliveConfig::disabled(), 2)
+            .await
+            .unwrap();
+        let pending_ids = || {
+            let mut ids: Vec<u64> = lock_pending(&connection.pending_commands).keys().copied().collect();
+            ids.sort_unstable();
+            ids
+        };
+        let settle = || tokio::time::sleep(Duration::from_millis(50));
+
+        // Separate clients share the connection's limit
+        let mut commands = Vec::new();
+        for _ in 0..3 {
+            let client = crate::cdp::CdpClientImpl::new(connection.clone());
+            commands.push(tokio::spawn(async move {
+                use crate::cdp::CdpClient;
+                client.call_method("Test.hang", serde_json::json!({})).await
+            }));
+            settle().await;
+        }
+        assert_eq!(pending_ids(), [1, 2]);
+
+        // A finished command lets the oldest queued command through
+        commands.remove(0).abort();
+        settle().await;
+        assert_eq!(pending_ids(), [2, 3]);
+    }
+
+    /// Local endpoint reporting when each ping arrives; `answer_pings` false never reads again
+    async fn ping_server(answer_pings: bool) -> (String, tokio::sync::mpsc::UnboundedReceiver<Instant>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (pings_tx, pings_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            if !answer_pings {
+                // Keep the socket open without ever sending a pong
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                return;
+            }
+            // tungstenite queues the pong and sends it on the next read
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Ping(_) = message {
+                    let _ = pings_tx.send(Instant::now());
+                }
+            }
+        });
+
+        (url, pings_rx)
+    }
+
+    #[tokio::test]
+    async fn test_keep_alive_pings_sent_at_configured_interval() {
+        let (url, mut pings) = ping_server(true).await;
+        let interval = Duration::from_millis(100);
+        let keep_alive = KeepAliveConfig {
+            ping_interval: Some(interval),
+            pong_timeout: Duration::from_secs(5),
+        };
+        let connected_at = Instant::now();
+        let connection = CdpWebSocketConnection::with_options(url, DEFAULT_MAX_MESSAGE_SIZE, keep_alive, DEFAULT_MAX_INFLIGHT_CDP_COMMANDS)
+            .await
+            .unwrap();
+
+        let mut previous = connected_at;
+        for _ in 0..4 {
+            let received = tokio::time::timeout(Duration::from_secs(2), pings.recv())
+                .await
+                .expect("ping not sent")
+                .unwrap();
+            let gap = received.duration_since(previous);
+            assert!(gap >= interval * 3 / 4, "ping after {:?}", gap);
+            assert!(gap < interval * 3, "ping after {:?}", gap);
+            previous = received;
+        }
+        // Every ping was answered, so the connection stays up
+        assert!(connection.is_active());
+
+        let (url, mut pings) = ping_server(true).await;
+        let _quiet = CdpWebSocketConnection::with_options(url, DEFAULT_MAX_MESSAGE_SIZE, KeepAliveConfig::disabled(), DEFAULT_MAX_INFLIGHT_CDP_COMMANDS)
+            .await
+            .unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(300), pings.recv()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_missed_pong_deactivates_connection() {
+        let (url, _pings) = ping_server(false).await;
+        let keep_alive = KeepAliveConfig {
+            ping_interval: Some(Duration::from_millis(50)),
+            pong_timeout: Duration::from_millis(100),
+        };
+        let connection =
+            CdpWebSocketConnection::with_options(url, DEFAULT_MAX_MESSAGE_SIZE, keep_alive, DEFAULT_MAX_INFLIGHT_CDP_COMMANDS)
+                .await
+                .unwrap();
+        let mut events = connection.subscribe(ALL_EVENTS).await.unwrap();
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(5),
+            connection.send_command("Page.enable", serde_json::json!({})),
+        )
+        .await
+        .expect("pending command should fail once the pong is overdue")
+        .unwrap_err();
+        assert!(matches!(&err, Error::Cdp(message) if message.contains("no pong within 100 ms")));
+        assert!(!connection.is_active());
+
+        // Subscribers learn that the connection is gone
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv()).await.unwrap().unwrap();
+        assert_eq!(event.method, DETACHED_EVENT);
+        assert_eq!(event.params["reason"], CONNECTION_LOST_REASON);
+    }
+
+    #[tokio::test]
+    async fn test_explicitly_enabled_domain_stays_enabled() {
+        let (url, mut methods) = recording_server().await;
+        let connection = CdpWebSocketConnection::new(url).await.unwrap();
+
+        connection.send_command("Network.enable", serde_json::json!({})).await.unwrap();
+        assert_eq!(methods.recv().await.unwrap(), "Network.enable");
+
+        drop(connection.subscribe("Network.responseReceived").await.unwrap());
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        connection.send_command("Page.reload", serde_json::json!({})).await.unwrap();
+        assert_eq!(methods.recv().await.unwrap(), "Page.reload");
+    }
 }
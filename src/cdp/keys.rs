@@ -0,0 +1,547 @@
+//! Key names, virtual key codes and key-combination parsing
+//!
+//! Shared by every RPC that presses keys, so "Ctrl+Shift+K" means the same
+//! thing everywhere. A combination is parsed into [`KeyChord`]s, which expand
+//! to the `Input.dispatchKeyEvent` events a physical keyboard would produce:
+//! modifiers go down in order, the key is pressed and released, and the
+//! modifiers are released in reverse.
+//!
+//! Accepted syntax:
+//! - a single key: `a`, `A`, `7`, `/`, `Enter`, `ArrowLeft`, `F5`
+//! - a chord of modifiers and one key joined by `+`: `Ctrl+Shift+K`, `Ctrl++`
+//! - a sequence of chords separated by whitespace: `Ctrl+K Ctrl+C`
+//!
+//! Named keys and modifiers are case-insensitive. The space key is `Space`.
+
+use super::traits::CdpClient;
+use crate::error::{Error, Result};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Modifier state, using the bit values of `Input.dispatchKeyEvent`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers(u32);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const ALT: Modifiers = Modifiers(1);
+    pub const CONTROL: Modifiers = Modifiers(2);
+    pub const META: Modifiers = Modifiers(4);
+    pub const SHIFT: Modifiers = Modifiers(8);
+
+    /// Bit mask sent as the `modifiers` parameter
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Whether every modifier in `other` is set
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Modifiers set in either value
+    pub fn union(self, other: Modifiers) -> Modifiers {
+        Modifiers(self.0 | other.0)
+    }
+
+    /// Modifier for a name such as `Ctrl`, `Shift`, `Alt` or `Cmd`
+    pub fn from_name(name: &str) -> Option<Modifiers> {
+        match name.to_ascii_lowercase().as_str() {
+            "alt" | "option" => Some(Modifiers::ALT),
+            "ctrl" | "control" | "controlormeta" => Some(Modifiers::CONTROL),
+            "meta" | "cmd" | "command" | "super" | "win" => Some(Modifiers::META),
+            "shift" => Some(Modifiers::SHIFT),
+            _ => None,
+        }
+    }
+}
+
+/// Modifiers in the order they are pressed, with the name of their key
+const MODIFIER_KEYS: &[(Modifiers, &str)] = &[
+    (Modifiers::CONTROL, "Control"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::META, "Meta"),
+    (Modifiers::SHIFT, "Shift"),
+];
+
+/// Entry of the named-key table
+struct NamedKey {
+    /// Accepted names, compared case-insensitively; the first is the DOM `key`
+    names: &'static [&'static str],
+    code: &'static str,
+    key_code: u32,
+    text: Option<&'static str>,
+    location: u32,
+}
+
+const fn named(names: &'static [&'static str], code: &'static str, key_code: u32, text: Option<&'static str>) -> NamedKey {
+    NamedKey { names, code, key_code, text, location: 0 }
+}
+
+const fn left(names: &'static [&'static str], code: &'static str, key_code: u32) -> NamedKey {
+    NamedKey { names, code, key_code, text: None, location: 1 }
+}
+
+/// Non-printable keys and the space bar
+const NAMED_KEYS: &[NamedKey] = &[
+    named(&["Enter", "Return"], "Enter", 13, Some("\r")),
+    named(&["Tab"], "Tab", 9, Some("\t")),
+    named(&[" ", "Space", "Spacebar"], "Space", 32, Some(" ")),
+    named(&["Backspace"], "Backspace", 8, None),
+    named(&["Delete", "Del"], "Delete", 46, None),
+    named(&["Escape", "Esc"], "Escape", 27, None),
+    named(&["Insert", "Ins"], "Insert", 45, None),
+    named(&["Home"], "Home", 36, None),
+    named(&["End"], "End", 35, None),
+    named(&["PageUp", "PgUp"], "PageUp", 33, None),
+    named(&["PageDown", "PgDn"], "PageDown", 34, None),
+    named(&["ArrowLeft", "Left"], "ArrowLeft", 37, None),
+    named(&["ArrowUp", "Up"], "ArrowUp", 38, None),
+    named(&["ArrowRight", "Right"], "ArrowRight", 39, None),
+    named(&["ArrowDown", "Down"], "ArrowDown", 40, None),
+    named(&["CapsLock"], "CapsLock", 20, None),
+    named(&["ContextMenu"], "ContextMenu", 93, None),
+    named(&["Pause"], "Pause", 19, None),
+    named(&["PrintScreen"], "PrintScreen", 44, None),
+    named(&["F1"], "F1", 112, None),
+    named(&["F2"], "F2", 113, None),
+    named(&["F3"], "F3", 114, None),
+    named(&["F4"], "F4", 115, None),
+    named(&["F5"], "F5", 116, None),
+    named(&["F6"], "F6", 117, None),
+    named(&["F7"], "F7", 118, None),
+    named(&["F8"], "F8", 119, None),
+    named(&["F9"], "F9", 120, None),
+    named(&["F10"], "F10", 121, None),
+    named(&["F11"], "F11", 122, None),
+    named(&["F12"], "F12", 123, None),
+    left(&["Shift"], "ShiftLeft", 16),
+    left(&["Control", "Ctrl"], "ControlLeft", 17),
+    left(&["Alt", "Option"], "AltLeft", 18),
+    left(&["Meta", "Cmd", "Command", "Super", "Win"], "MetaLeft", 91),
+];
+
+/// Printable non-alphanumeric characters: (unshifted, shifted, code, key code) on a US layout
+const PUNCTUATION: &[(char, char, &str, u32)] = &[
+    (';', ':', "Semicolon", 186),
+    ('=', '+', "Equal", 187),
+    (',', '<', "Comma", 188),
+    ('-', '_', "Minus", 189),
+    ('.', '>', "Period", 190),
+    ('/', '?', "Slash", 191),
+    ('`', '~', "Backquote", 192),
+    ('[', '{', "BracketLeft", 219),
+    ('\\', '|', "Backslash", 220),
+    (']', '}', "BracketRight", 221),
+    ('\'', '"', "Quote", 222),
+];
+
+/// Shifted digit-row characters, indexed by digit
+const SHIFTED_DIGITS: [char; 10] = [')', '!', '@', '#', '$', '%', '^', '&', '*', '('];
+
+/// A physical key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    /// DOM `KeyboardEvent.key`
+    pub key: String,
+    /// DOM `KeyboardEvent.code`
+    pub code: String,
+    /// Windows virtual key code
+    pub key_code: u32,
+    /// Text the key inserts, if any
+    pub text: Option<String>,
+    /// DOM key location (1 for the left-hand modifier keys)
+    pub location: u32,
+}
+
+impl Key {
+    /// Look up a key by name or by the single character it types
+    pub fn from_name(name: &str) -> Option<Key> {
+        let mut chars = name.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            if let Some(key) = Self::from_char(ch) {
+                return Some(key);
+            }
+        }
+
+        NAMED_KEYS
+            .iter()
+            .find(|entry| entry.names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+            .map(|entry| Key {
+                key: entry.names[0].to_string(),
+                code: entry.code.to_string(),
+                key_code: entry.key_code,
+                text: entry.text.map(str::to_string),
+                location: entry.location,
+            })
+    }
+
+    /// Key typing `ch` on a US layout
+    fn from_char(ch: char) -> Option<Key> {
+        let (code, key_code) = if ch.is_ascii_alphabetic() {
+            let upper = ch.to_ascii_uppercase();
+            (format!("Key{}", upper), upper as u32)
+        } else if let Some(digit) = ch.to_digit(10) {
+            (format!("Digit{}", digit), '0' as u32 + digit)
+        } else if let Some(digit) = SHIFTED_DIGITS.iter().position(|&c| c == ch) {
+            (format!("Digit{}", digit), '0' as u32 + digit as u32)
+        } else if let Some(&(_, _, code, key_code)) = PUNCTUATION.iter().find(|p| p.0 == ch || p.1 == ch) {
+            (code.to_string(), key_code)
+        } else {
+            return None;
+        };
+
+        Some(Key {
+            key: ch.to_string(),
+            code,
+            key_code,
+            text: Some(ch.to_string()),
+            location: 0,
+        })
+    }
+}
+
+/// Kind of an `Input.dispatchKeyEvent` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventType {
+    /// Key press that inserts text
+    KeyDown,
+    /// Key press without text (modifiers, navigation keys, shortcuts)
+    RawKeyDown,
+    KeyUp,
+}
+
+impl KeyEventType {
+    fn as_str(self) -> &'static str {
+        match self {
+            KeyEventType::KeyDown => "keyDown",
+            KeyEventType::RawKeyDown => "rawKeyDown",
+            KeyEventType::KeyUp => "keyUp",
+        }
+    }
+}
+
+/// One `Input.dispatchKeyEvent` call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub event_type: KeyEventType,
+    pub key: Key,
+    /// Modifiers held while the event fires
+    pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+    /// Parameters for `Input.dispatchKeyEvent`
+    pub fn to_params(&self) -> Value {
+        let mut params = json!({
+            "type": self.event_type.as_str(),
+            "modifiers": self.modifiers.bits(),
+            "key": self.key.key,
+            "code": self.key.code,
+            "windowsVirtualKeyCode": self.key.key_code,
+        });
+        if self.event_type == KeyEventType::KeyDown {
+            if let Some(text) = &self.key.text {
+                params["text"] = json!(text);
+                params["unmodifiedText"] = json!(text);
+            }
+        }
+        if self.key.location != 0 {
+            params["location"] = json!(self.key.location);
+        }
+        params
+    }
+}
+
+/// A key pressed while holding a set of modifiers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyChord {
+    /// The same chord with `extra` modifiers held as well
+    pub fn with_modifiers(mut self, extra: Modifiers) -> KeyChord {
+        self.modifiers = self.modifiers.union(extra);
+        self
+    }
+
+    /// The key as it is reported with the chord's modifiers held
+    fn effective_key(&self) -> Key {
+        let mut key = self.key.clone();
+        if self.modifiers.contains(Modifiers::SHIFT) && key.key.len() == 1 {
+            key.key = key.key.to_uppercase();
+            key.text = key.text.map(|text| text.to_uppercase());
+        }
+        // Shortcuts do not insert text
+        if [Modifiers::CONTROL, Modifiers::ALT, Modifiers::META]
+            .iter()
+            .any(|&m| self.modifiers.contains(m))
+        {
+            key.text = None;
+        }
+        key
+    }
+
+    /// Modifier key-downs followed by the key-down of the key
+    pub fn press_events(&self) -> Vec<KeyEvent> {
+        let mut held = Modifiers::NONE;
+        let mut events = Vec::new();
+        for &(modifier, name) in MODIFIER_KEYS {
+            if self.modifiers.contains(modifier) {
+                held = held.union(modifier);
+                events.push(KeyEvent {
+                    event_type: KeyEventType::RawKeyDown,
+                    key: Key::from_name(name).expect("modifier keys are in the key table"),
+                    modifiers: held,
+                });
+            }
+        }
+
+        let key = self.effective_key();
+        let event_type = if key.text.is_some() { KeyEventType::KeyDown } else { KeyEventType::RawKeyDown };
+        events.push(KeyEvent { event_type, key, modifiers: held });
+        events
+    }
+
+    /// Key-up of the key followed by the modifier key-ups in reverse order
+    pub fn release_events(&self) -> Vec<KeyEvent> {
+        let mut held = self.modifiers;
+        let mut events = vec![KeyEvent {
+            event_type: KeyEventType::KeyUp,
+            key: self.effective_key(),
+            modifiers: held,
+        }];
+        for &(modifier, name) in MODIFIER_KEYS.iter().rev() {
+            if self.modifiers.contains(modifier) {
+                held = Modifiers(held.bits() & !modifier.bits());
+                events.push(KeyEvent {
+                    event_type: KeyEventType::KeyUp,
+                    key: Key::from_name(name).expect("modifier keys are in the key table"),
+                    modifiers: held,
+                });
+            }
+        }
+        events
+    }
+
+    /// Every event of pressing and releasing the chord
+    pub fn events(&self) -> Vec<KeyEvent> {
+        let mut events = self.press_events();
+        events.extend(self.release_events());
+        events
+    }
+}
+
+/// Parse a single chord such as `Ctrl+Shift+K`
+pub fn parse_key_chord(input: &str) -> Result<KeyChord> {
+    // A trailing "++" (or a lone "+") names the plus key itself
+    let (rest, plus) = match input.strip_suffix("++") {
+        Some(rest) => (rest, true),
+        None if input == "+" => ("", true),
+        None => (input, false),
+    };
+
+    let mut parts: Vec<&str> = if rest.is_empty() && plus { Vec::new() } else { rest.split('+').collect() };
+    if plus {
+        parts.push("+");
+    }
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(Error::configuration(format!("Invalid key combination '{}': empty key name", input)));
+    }
+
+    let (key_name, modifier_names) = parts
+        .split_last()
+        .ok_or_else(|| Error::configuration("Key combination must not be empty"))?;
+
+    let mut modifiers = Modifiers::NONE;
+    for name in modifier_names {
+        let modifier = Modifiers::from_name(name).ok_or_else(|| {
+            Error::configuration(format!("Invalid key combination '{}': '{}' is not a modifier", input, name))
+        })?;
+        modifiers = modifiers.union(modifier);
+    }
+
+    let key = Key::from_name(key_name)
+        .ok_or_else(|| Error::configuration(format!("Invalid key combination '{}': unknown key '{}'", input, key_name)))?;
+
+    Ok(KeyChord { modifiers, key })
+}
+
+/// Parse a whitespace-separated sequence of chords such as `Ctrl+K Ctrl+C`
+pub fn parse_key_sequence(input: &str) -> Result<Vec<KeyChord>> {
+    let chords = input.split_whitespace().map(parse_key_chord).collect::<Result<Vec<_>>>()?;
+    if chords.is_empty() {
+        return Err(Error::configuration("Key combination must not be empty"));
+    }
+    Ok(chords)
+}
+
+/// Parse a key combination into the events that press it
+///
+/// Sequences produce the events of each chord in turn.
+pub fn parse_key_combination(input: &str) -> Result<Vec<KeyEvent>> {
+    Ok(parse_key_sequence(input)?.iter().flat_map(KeyChord::events).collect())
+}
+
+/// Press each chord in turn, holding its key down for `hold`
+pub async fn press_chords(client: &dyn CdpClient, chords: &[KeyChord], hold: Duration) -> Result<()> {
+    for chord in chords {
+        for event in chord.press_events() {
+            client.call_method("Input.dispatchKeyEvent", event.to_params()).await?;
+        }
+        if !hold.is_zero() {
+            tokio::time::sleep(hold).await;
+        }
+        for event in chord.release_events() {
+            client.call_method("Input.dispatchKeyEvent", event.to_params()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Type `text` key by key, waiting `delay` between keystrokes
+///
+/// Characters without a key on a US layout (accented letters, emoji, CJK)
+/// are inserted with `Input.insertText`. A newline presses Enter.
+pub async fn type_text(client: &dyn CdpClient, text: &str, delay: Duration) -> Result<()> {
+    for (i, ch) in text.chars().enumerate() {
+        if i > 0 && !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        let key = match ch {
+            '\n' | '\r' => Key::from_name("Enter"),
+            _ => Key::from_name(&ch.to_string()),
+        };
+        match key {
+            Some(key) => {
+                let chord = KeyChord { modifiers: Modifiers::NONE, key };
+                press_chords(client, std::slice::from_ref(&chord), Duration::ZERO).await?;
+            }
+            None => client.insert_text(&ch.to_string()).await?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(events: &[KeyEvent]) -> Vec<(KeyEventType, &str, u32)> {
+        events
+            .iter()
+            .map(|e| (e.event_type, e.key.key.as_str(), e.modifiers.bits()))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_keys() {
+        let events = parse_key_combination("a").unwrap();
+        assert_eq!(summary(&events), vec![(KeyEventType::KeyDown, "a", 0), (KeyEventType::KeyUp, "a", 0)]);
+        let params = events[0].to_params();
+        assert_eq!(params["code"], "KeyA");
+        assert_eq!(params["windowsVirtualKeyCode"], 65);
+        assert_eq!(params["text"], "a");
+
+        let enter = parse_key_chord("enter").unwrap();
+        assert_eq!(enter.key.key, "Enter");
+        assert_eq!(enter.key.key_code, 13);
+        assert_eq!(enter.events()[0].to_params()["text"], "\r");
+
+        let arrow = parse_key_combination("ArrowLeft").unwrap();
+        assert_eq!(arrow[0].event_type, KeyEventType::RawKeyDown);
+        assert!(arrow[0].to_params().get("text").is_none());
+        assert_eq!(arrow[0].key.key_code, 37);
+
+        assert_eq!(parse_key_chord("?").unwrap().key.code, "Slash");
+        assert_eq!(parse_key_chord("!").unwrap().key.code, "Digit1");
+        assert_eq!(parse_key_chord("F5").unwrap().key.code, "F5");
+        assert_eq!(parse_key_chord("Space").unwrap().key.text.as_deref(), Some(" "));
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let events = parse_key_combination("Ctrl+Shift+K").unwrap();
+        assert_eq!(
+            summary(&events),
+            vec![
+                (KeyEventType::RawKeyDown, "Control", 2),
+                (KeyEventType::RawKeyDown, "Shift", 10),
+                (KeyEventType::RawKeyDown, "K", 10),
+                (KeyEventType::KeyUp, "K", 10),
+                (KeyEventType::KeyUp, "Shift", 2),
+                (KeyEventType::KeyUp, "Control", 0),
+            ]
+        );
+        assert_eq!(events[0].to_params()["location"], 1);
+
+        // Shift alone changes the text; the plus key is named with a trailing "++"
+        let shifted = parse_key_combination("shift+a").unwrap();
+        assert_eq!(shifted[1].to_params()["text"], "A");
+        let plus = parse_key_chord("Cmd++").unwrap();
+        assert_eq!(plus.modifiers, Modifiers::META);
+        assert_eq!(plus.key.code, "Equal");
+
+        let extra = parse_key_chord("a").unwrap().with_modifiers(Modifiers::ALT);
+        assert_eq!(extra.events()[1].modifiers, Modifiers::ALT);
+    }
+
+    #[test]
+    fn test_sequences() {
+        let chords = parse_key_sequence("Ctrl+K  Ctrl+C").unwrap();
+        assert_eq!(chords.len(), 2);
+        assert_eq!(chords[1].key.key, "C");
+        assert_eq!(parse_key_combination("Ctrl+K Ctrl+C").unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_invalid_combinations() {
+        for input in ["", "   ", "Ctrl+", "+A", "Ctrl++A", "A+B", "Ctrl+Foo", "Hyper+A", "é"] {
+            let err = parse_key_combination(input).unwrap_err();
+            assert!(matches!(err, Error::Configuration(_)), "{:?} gave {:?}", input, err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_press_chords_dispatches_events() {
+        let client = crate::cdp::mock::MockCdpClient::new();
+        let chords = parse_key_sequence("Ctrl+A Delete").unwrap();
+
+        press_chords(&client, &chords, Duration::ZERO).await.unwrap();
+
+        let calls = client.recorded_calls("Input.dispatchKeyEvent").await;
+        let keys: Vec<_> = calls.iter().map(|c| (c["type"].as_str().unwrap(), c["key"].as_str().unwrap())).collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("rawKeyDown", "Control"),
+                ("rawKeyDown", "A"),
+                ("keyUp", "A"),
+                ("keyUp", "Control"),
+                ("rawKeyDown", "Delete"),
+                ("keyUp", "Delete"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_type_text_uses_keys_and_falls_back_to_insert_text() {
+        let client = crate::cdp::mock::MockCdpClient::new();
+
+        type_text(&client, "Hi é\n", Duration::ZERO).await.unwrap();
+
+        let calls = client.recorded_calls("Input.dispatchKeyEvent").await;
+        let downs: Vec<_> = calls
+            .iter()
+            .filter(|c| c["type"] == "keyDown")
+            .map(|c| (c["code"].as_str().unwrap(), c["text"].as_str().unwrap()))
+            .collect();
+        assert_eq!(downs, vec![("KeyH", "H"), ("KeyI", "i"), ("Space", " "), ("Enter", "\r")]);
+        let inserted = client.recorded_calls("Input.insertText").await;
+        assert_eq!(inserted.len(), 1);
+        assert_eq!(inserted[0]["text"], "é");
+    }
+}
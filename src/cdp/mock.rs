@@ -3,13 +3,33 @@
 //! This module provides mock implementations of CDP traits for development and testing.
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::cdp::traits::*;
+use crate::cdp::types::{CookieParam, StorageId};
 use crate::Error;
 
+/// Main frame ID reported by the mock connection
+pub const MOCK_FRAME_ID: &str = "mock-main-frame";
+
+/// URL assigned by a `location.href = '...'` script, if any
+pub fn assigned_location(script: &str) -> Option<String> {
+    let (_, rest) = script.split_once("location.href")?;
+    let value = rest.trim_start().strip_prefix('=')?;
+    if value.starts_with('=') {
+        return None;
+    }
+
+    let value = value.trim().trim_end_matches(';').trim_end();
+    ["'", "\"", "`"]
+        .iter()
+        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .map(str::to_string)
+}
+
 /// Mock CDP connection
 #[derive(Debug)]
 pub struct MockCdpConnection {
@@ -17,6 +37,7 @@ pub struct MockCdpConnection {
     id: String,
     is_active: Arc<AtomicBool>,
     next_id: AtomicU64,
+    stubs: Mutex<HashMap<String, serde_json::Value>>,
 }
 
 impl MockCdpConnection {
@@ -26,8 +47,14 @@ impl MockCdpConnection {
             id: uuid::Uuid::new_v4().to_string(),
             is_active: Arc::new(AtomicBool::new(true)),
             next_id: AtomicU64::new(1),
+            stubs: Mutex::new(HashMap::new()),
         }
     }
+
+    /// Respond to `method` with `result` instead of the built-in mock response
+    pub async fn stub_method(&self, method: &str, result: serde_json::Value) {
+        self.stubs.lock().await.insert(method.to_string(), result);
+    }
 }
 
 impl Default for MockCdpConnection {
@@ -45,6 +72,14 @@ impl CdpConnection for MockCdpConnection {
 
         let id = self.next_id.fetch_add(1, Ordering::Relaxed);
 
+        if let Some(result) = self.stubs.lock().await.get(method) {
+            return Ok(CdpResponse {
+                id,
+                result: Some(result.clone()),
+                error: None,
+            });
+        }
+
         // Simulate different responses based on method
         let result = match method {
             "Page.navigate" => Some(serde_json::json!({
@@ -60,9 +95,24 @@ impl CdpConnection for MockCdpConnection {
             "Page.captureScreenshot" => Some(serde_json::json!({
                 "data": "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg=="
             })),
+            "Page.addScriptToEvaluateOnNewDocument" => Some(serde_json::json!({
+                "identifier": id.to_string()
+            })),
             "DOM.getOuterHtml" => Some(serde_json::json!({
                 "outerHtml": "<html><body>Mock HTML</body></html>"
             })),
+            "Page.getFrameTree" => Some(serde_json::json!({
+                "frameTree": {
+                    "frame": { "id": MOCK_FRAME_ID, "url": "about:blank" }
+                }
+            })),
+            "Browser.getVersion" => Some(serde_json::json!({
+                "protocolVersion": "1.3",
+                "product": "Chrome/120.0.0.0",
+                "revision": "@mock-revision",
+                "userAgent": "Mock Chrome/120.0.0.0",
+                "jsVersion": "12.0.0.0"
+            })),
             _ => Some(serde_json::json!({})),
         };
 
@@ -101,12 +151,42 @@ impl CdpConnection for MockCdpConnection {
     }
 }
 
+/// Mock navigation history (entry URLs and current index)
+#[derive(Debug)]
+struct MockHistory {
+    entries: Vec<String>,
+    current: usize,
+}
+
+/// Event type filter and sender of a mock subscription
+type MockEventSubscriber = (String, tokio::sync::mpsc::Sender<CdpEvent>);
+
+/// Mock DOM storage areas and their items in insertion order
+type MockStorage = HashMap<StorageId, Vec<(String, String)>>;
+
 /// Mock CDP client
 #[derive(Debug)]
 pub struct MockCdpClient {
     connection: Arc<MockCdpConnection>,
     url: Arc<Mutex<Option<String>>>,
     content: Arc<Mutex<String>>,
+    history: Arc<Mutex<MockHistory>>,
+    event_subscribers: Arc<Mutex<Vec<MockEventSubscriber>>>,
+    extra_headers: Arc<Mutex<HashMap<String, String>>>,
+    calls: Arc<Mutex<Vec<(String, serde_json::Value)>>>,
+    storage: Arc<Mutex<MockStorage>>,
+    /// Cookie jar, in the order cookies were set
+    cookies: Arc<Mutex<Vec<CookieParam>>>,
+    layout_size: Arc<Mutex<(f64, f64, f64)>>,
+    stubs: Arc<Mutex<HashMap<String, serde_json::Value>>>,
+    stub_events: Arc<Mutex<HashMap<String, Vec<CdpEvent>>>>,
+    delays: Arc<Mutex<HashMap<String, std::time::Duration>>>,
+    /// Error messages returned by the next `call_method` of each method
+    failures: Arc<Mutex<HashMap<String, String>>>,
+    /// Main-world globals set with `set_global`
+    globals: Arc<Mutex<HashMap<String, serde_json::Value>>>,
+    /// Execution context IDs returned by `Page.createIsolatedWorld`
+    isolated_worlds: Arc<Mutex<Vec<i64>>>,
 }
 
 impl MockCdpClient {
@@ -116,34 +196,135 @@ impl MockCdpClient {
             connection: Arc::new(MockCdpConnection::new()),
             url: Arc::new(Mutex::new(None)),
             content: Arc::new(Mutex::new(String::new())),
+            history: Arc::new(Mutex::new(MockHistory {
+                entries: vec!["about:blank".to_string()],
+                current: 0,
+            })),
+            event_subscribers: Arc::new(Mutex::new(Vec::new())),
+            extra_headers: Arc::new(Mutex::new(HashMap::new())),
+            calls: Arc::new(Mutex::new(Vec::new())),
+            storage: Arc::new(Mutex::new(HashMap::new())),
+            cookies: Arc::new(Mutex::new(Vec::new())),
+            layout_size: Arc::new(Mutex::new((1920.0, 1080.0, 1080.0))),
+            stubs: Arc::new(Mutex::new(HashMap::new())),
+            stub_events: Arc::new(Mutex::new(HashMap::new())),
+            delays: Arc::new(Mutex::new(HashMap::new())),
+            failures: Arc::new(Mutex::new(HashMap::new())),
+            globals: Arc::new(Mutex::new(HashMap::new())),
+            isolated_worlds: Arc::new(Mutex::new(Vec::new())),
         }
     }
-}
 
-impl Default for MockCdpClient {
-    fn default() -> Self {
-        Self::new()
+    /// Define a main-world global
+    ///
+    /// Evaluating its name in the main world returns `value`; isolated worlds
+    /// do not see it.
+    pub async fn set_global(&self, name: &str, value: serde_json::Value) {
+        self.globals.lock().await.insert(name.to_string(), value);
     }
-}
 
-#[async_trait]
-impl CdpClient for MockCdpClient {
-    fn connection(&self) -> Arc<dyn CdpConnection> {
-        self.connection.clone()
+    /// Delay the reply to every subsequent `call_method(method, ..)` by `delay`
+    ///
+    /// Simulates commands the browser answers late, e.g. an awaited promise.
+    /// A `Page.navigate` delay also applies to `navigate`.
+    pub async fn delay_method(&self, method: &str, delay: std::time::Duration) {
+        self.delays.lock().await.insert(method.to_string(), delay);
+    }
+
+    /// Return `result` from every subsequent `call_method(method, ..)`
+    pub async fn stub_method(&self, method: &str, result: serde_json::Value) {
+        self.stubs.lock().await.insert(method.to_string(), result);
+    }
+
+    /// Fail the next `call_method(method, ..)` with a CDP error carrying `message`
+    ///
+    /// Later calls of `method` succeed again.
+    pub async fn fail_next_call(&self, method: &str, message: &str) {
+        self.failures.lock().await.insert(method.to_string(), message.to_string());
     }
 
-    async fn navigate(&self, url: &str) -> Result<NavigationResult, Error> {
+    /// Load `url` in the main frame: update history and emit the request, navigation and lifecycle events
+    async fn commit_navigation(&self, url: &str) {
         *self.url.lock().await = Some(url.to_string());
-        Ok(NavigationResult {
-            navigation_id: Some(uuid::Uuid::new_v4().to_string()),
-            url: url.to_string(),
-            status_code: 200,
-        })
+
+        let mut history = self.history.lock().await;
+        let next = history.current + 1;
+        history.entries.truncate(next);
+        history.entries.push(url.to_string());
+        history.current = next;
+        drop(history);
+
+        let headers = self.extra_headers.lock().await.clone();
+        self.emit_event(
+            "Network.requestWillBeSent",
+            serde_json::json!({
+                "frameId": MOCK_FRAME_ID,
+                "type": "Document",
+                "request": { "url": url, "method": "GET", "headers": headers }
+            }),
+        )
+        .await;
+
+        self.commit_document(url).await;
     }
 
-    async fn evaluate(&self, script: &str, _await_promise: bool) -> Result<EvaluationResult, Error> {
+    /// Emit the main-frame navigation and lifecycle events of a new document at `url`
+    async fn commit_document(&self, url: &str) {
+        let loader_id = uuid::Uuid::new_v4().to_string();
+        self.emit_event(
+            "Page.frameNavigated",
+            serde_json::json!({
+                "frame": { "id": MOCK_FRAME_ID, "loaderId": loader_id, "url": url }
+            }),
+        )
+        .await;
+        for name in ["init", "DOMContentLoaded", "load", "networkAlmostIdle", "networkIdle"] {
+            self.emit_event(
+                "Page.lifecycleEvent",
+                serde_json::json!({ "frameId": MOCK_FRAME_ID, "loaderId": loader_id, "name": name }),
+            )
+            .await;
+        }
+    }
+
+    /// Emit the events stubbed for `method` via [`Self::stub_events`]
+    async fn emit_stubbed_events(&self, method: &str) {
+        let events = self.stub_events.lock().await.get(method).cloned().unwrap_or_default();
+        for event in events {
+            self.emit_event(&event.method, event.params).await;
+        }
+    }
+
+    /// Emit `events` (method, params) each time `call_method(method, ..)` is invoked, before it returns
+    ///
+    /// Events stubbed for `Page.navigate` are also emitted by `navigate`, before the navigation commits,
+    /// and events stubbed for `Runtime.evaluate` by `evaluate`, as if logged by the script.
+    pub async fn stub_events(&self, method: &str, events: Vec<(&str, serde_json::Value)>) {
+        let events = events
+            .into_iter()
+            .map(|(event, params)| CdpEvent {
+                method: event.to_string(),
+                params,
+                session_id: None,
+            })
+            .collect();
+        self.stub_events.lock().await.insert(method.to_string(), events);
+    }
+
+    /// Set the content size and viewport height reported by `Page.getLayoutMetrics`
+    pub async fn set_layout_size(&self, width: f64, height: f64, viewport_height: f64) {
+        *self.layout_size.lock().await = (width, height, viewport_height);
+    }
+
+    /// Pattern-based script evaluation shared by all worlds
+    async fn evaluate_script(&self, script: &str) -> Result<EvaluationResult, Error> {
         // Simple mock evaluation for testing
-        if script.contains("document.title") {
+        if let Some(url) = assigned_location(script) {
+            self.commit_navigation(&url).await;
+            Ok(EvaluationResult::String(url))
+        } else if script.contains("document.readyState") {
+            Ok(EvaluationResult::String("complete".to_string()))
+        } else if script.contains("document.title") {
             Ok(EvaluationResult::String("Test Page".to_string()))
         } else if script.contains("+") {
             // Simple arithmetic evaluation for testing
@@ -158,19 +339,147 @@ impl CdpClient for MockCdpClient {
         } else if script.contains("window.location.href") {
             let url = self.url.lock().await.clone().unwrap_or_default();
             Ok(EvaluationResult::String(url))
+        } else if script.contains("window.location.origin") {
+            let url = self.url.lock().await.clone().unwrap_or_default();
+            let origin = reqwest::Url::parse(&url)
+                .map(|u| u.origin().ascii_serialization())
+                .unwrap_or_else(|_| "null".to_string());
+            Ok(EvaluationResult::String(origin))
         } else {
             Ok(EvaluationResult::String("mock result".to_string()))
         }
     }
 
+    /// Render a clipped screenshot as a PNG whose pixel rows encode their page y (mod 256)
+    fn render_clip(clip: &serde_json::Value) -> Result<String, Error> {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+        let number = |key: &str| clip.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let (top, width, height) = (number("y") as u32, number("width") as u32, number("height") as u32);
+        let image = image::RgbaImage::from_fn(width.max(1), height.max(1), |_, row| {
+            let shade = ((top + row) % 256) as u8;
+            image::Rgba([shade, shade, shade, 255])
+        });
+
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut buffer, image::ImageFormat::Png)
+            .map_err(|e| Error::internal(format!("Failed to encode mock screenshot: {}", e)))?;
+        Ok(BASE64.encode(buffer.into_inner()))
+    }
+
+    /// Methods of every recorded call, in call order
+    pub async fn recorded_methods(&self) -> Vec<String> {
+        self.calls.lock().await.iter().map(|(method, _)| method.clone()).collect()
+    }
+
+    /// Parameters of every `call_method` invocation of `method`, in order
+    pub async fn recorded_calls(&self, method: &str) -> Vec<serde_json::Value> {
+        self.calls
+            .lock()
+            .await
+            .iter()
+            .filter(|(m, _)| m == method)
+            .map(|(_, params)| params.clone())
+            .collect()
+    }
+
+    /// Deliver an event to subscribers of `method` (or `*`)
+    pub async fn emit_event(&self, method: &str, params: serde_json::Value) {
+        let event = CdpEvent {
+            method: method.to_string(),
+            params,
+            session_id: None,
+        };
+
+        let mut subscribers = self.event_subscribers.lock().await;
+        subscribers.retain(|(_, tx)| !tx.is_closed());
+        for (event_type, tx) in subscribers.iter() {
+            if event_type == method || event_type == "*" {
+                let _ = tx.try_send(event.clone());
+            }
+        }
+    }
+}
+
+impl Default for MockCdpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CdpClient for MockCdpClient {
+    fn connection(&self) -> Arc<dyn CdpConnection> {
+        self.connection.clone()
+    }
+
+    async fn navigate(&self, url: &str, referrer: Option<&str>) -> Result<NavigationResult, Error> {
+        let mut params = serde_json::json!({ "url": url });
+        if let Some(referrer) = referrer {
+            params["referrer"] = serde_json::json!(referrer);
+        }
+        self.calls.lock().await.push(("Page.navigate".to_string(), params));
+        self.emit_stubbed_events("Page.navigate").await;
+        let delay = self.delays.lock().await.get("Page.navigate").copied();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+        self.commit_navigation(url).await;
+
+        Ok(NavigationResult {
+            navigation_id: Some(uuid::Uuid::new_v4().to_string()),
+            url: url.to_string(),
+            status_code: 200,
+        })
+    }
+
+    async fn evaluate(&self, script: &str, _await_promise: bool) -> Result<EvaluationResult, Error> {
+        self.calls
+            .lock()
+            .await
+            .push(("Runtime.evaluate".to_string(), serde_json::json!({ "expression": script })));
+        self.emit_stubbed_events("Runtime.evaluate").await;
+
+        if let Some(value) = self.globals.lock().await.get(script.trim()) {
+            return Ok(match value {
+                serde_json::Value::Null => EvaluationResult::Null,
+                serde_json::Value::Bool(b) => EvaluationResult::Bool(*b),
+                serde_json::Value::Number(n) => EvaluationResult::Number(n.as_f64().unwrap_or(0.0)),
+                serde_json::Value::String(s) => EvaluationResult::String(s.clone()),
+                other => EvaluationResult::Object(other.clone()),
+            });
+        }
+        self.evaluate_script(script).await
+    }
+
+    async fn evaluate_in_context(&self, script: &str, _await_promise: bool, context_id: i64) -> Result<EvaluationResult, Error> {
+        self.calls.lock().await.push((
+            "Runtime.evaluate".to_string(),
+            serde_json::json!({ "expression": script, "contextId": context_id }),
+        ));
+
+        if !self.isolated_worlds.lock().await.contains(&context_id) {
+            return Err(Error::cdp("Cannot find context with specified id"));
+        }
+        // Main-world globals are `undefined` in an isolated world
+        if self.globals.lock().await.contains_key(script.trim()) {
+            return Ok(EvaluationResult::Null);
+        }
+        self.evaluate_script(script).await
+    }
+
     async fn screenshot(&self, format: ScreenshotFormat) -> Result<Vec<u8>, Error> {
-        // Return a minimal 1x1 PNG image
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
         Ok(match format {
-            ScreenshotFormat::Png => vec![
-                0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D,
-                0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
-                0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x77, 0x53, 0xDE,
-            ],
+            // A small but decodable viewport capture
+            ScreenshotFormat::Png => {
+                let data = Self::render_clip(&serde_json::json!({ "width": 64, "height": 48 }))?;
+                BASE64
+                    .decode(data)
+                    .map_err(|e| Error::internal(format!("Failed to decode mock screenshot: {}", e)))?
+            }
             ScreenshotFormat::Jpeg(_) => vec![0xFF, 0xD8, 0xFF, 0xE0],
             ScreenshotFormat::WebP(_) => vec![0x52, 0x49, 0x46, 0x46],
         })
@@ -186,6 +495,16 @@ impl CdpClient for MockCdpClient {
     }
 
     async fn reload(&self, _ignore_cache: bool) -> Result<(), Error> {
+        let url = self.url.lock().await.clone().unwrap_or_else(|| "about:blank".to_string());
+        self.emit_event(
+            "Network.responseReceived",
+            serde_json::json!({
+                "frameId": MOCK_FRAME_ID,
+                "type": "Document",
+                "response": { "url": url, "status": 200 }
+            }),
+        )
+        .await;
         Ok(())
     }
 
@@ -194,6 +513,68 @@ impl CdpClient for MockCdpClient {
     }
 
     async fn call_method(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value, Error> {
+        self.calls.lock().await.push((method.to_string(), params.clone()));
+        self.emit_stubbed_events(method).await;
+
+        let delay = self.delays.lock().await.get(method).copied();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        if let Some(message) = self.failures.lock().await.remove(method) {
+            return Err(Error::cdp(message));
+        }
+        if let Some(result) = self.stubs.lock().await.get(method) {
+            return Ok(result.clone());
+        }
+
+        match method {
+            "Page.getNavigationHistory" => {
+                let history = self.history.lock().await;
+                let entries: Vec<serde_json::Value> = history
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .map(|(id, url)| serde_json::json!({ "id": id, "url": url, "title": "" }))
+                    .collect();
+                return Ok(serde_json::json!({
+                    "currentIndex": history.current,
+                    "entries": entries,
+                }));
+            }
+            "Page.navigateToHistoryEntry" => {
+                let mut history = self.history.lock().await;
+                let entry_id = params.get("entryId").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+                let url = history
+                    .entries
+                    .get(entry_id)
+                    .cloned()
+                    .ok_or_else(|| Error::cdp("No entry with passed id"))?;
+                history.current = entry_id;
+                drop(history);
+                *self.url.lock().await = Some(url.clone());
+                self.commit_document(&url).await;
+                return Ok(serde_json::json!({}));
+            }
+            "Page.getLayoutMetrics" => {
+                let (width, height, viewport_height) = *self.layout_size.lock().await;
+                return Ok(serde_json::json!({
+                    "cssContentSize": { "x": 0, "y": 0, "width": width, "height": height },
+                    "cssLayoutViewport": { "pageX": 0, "pageY": 0, "clientWidth": width, "clientHeight": viewport_height },
+                }));
+            }
+            "Page.createIsolatedWorld" => {
+                let mut worlds = self.isolated_worlds.lock().await;
+                let context_id = 1000 + worlds.len() as i64;
+                worlds.push(context_id);
+                return Ok(serde_json::json!({ "executionContextId": context_id }));
+            }
+            "Page.captureScreenshot" if params.get("clip").is_some() => {
+                return Ok(serde_json::json!({ "data": Self::render_clip(&params["clip"])? }));
+            }
+            _ => {}
+        }
+
         let response = self.connection.send_command(method, params).await?;
 
         if let Some(error) = response.error {
@@ -207,18 +588,83 @@ impl CdpClient for MockCdpClient {
         }
     }
 
-    async fn subscribe_events(&self, _event_type: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
-        let (_tx, rx) = tokio::sync::mpsc::channel(100);
-        // In a real implementation, we would register the event listener
-        // For mock, just return an empty channel
+    async fn subscribe_events(&self, event_type: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        // Events are delivered via `emit_event`
+        self.event_subscribers.lock().await.push((event_type.to_string(), tx));
         Ok(rx)
     }
+
+    async fn get_version(&self) -> Result<BrowserVersion, Error> {
+        let result = self.call_method("Browser.getVersion", serde_json::json!({})).await?;
+        Ok(BrowserVersion::from_cdp_result(&result))
+    }
+
+    async fn set_extra_http_headers(&self, headers: &HashMap<String, String>) -> Result<(), Error> {
+        *self.extra_headers.lock().await = headers.clone();
+        Ok(())
+    }
+
+    async fn set_cpu_throttling_rate(&self, rate: f64) -> Result<(), Error> {
+        self.call_method("Emulation.setCPUThrottlingRate", serde_json::json!({ "rate": rate }))
+            .await
+            .map(|_| ())
+    }
+
+    async fn get_dom_storage_items(&self, storage_id: &StorageId) -> Result<Vec<(String, String)>, Error> {
+        Ok(self.storage.lock().await.get(storage_id).cloned().unwrap_or_default())
+    }
+
+    async fn set_dom_storage_item(&self, storage_id: &StorageId, key: &str, value: &str) -> Result<(), Error> {
+        let mut storage = self.storage.lock().await;
+        let items = storage.entry(storage_id.clone()).or_default();
+        match items.iter_mut().find(|(k, _)| k == key) {
+            Some(item) => item.1 = value.to_string(),
+            None => items.push((key.to_string(), value.to_string())),
+        }
+        Ok(())
+    }
+
+    async fn clear_dom_storage(&self, storage_id: &StorageId) -> Result<(), Error> {
+        self.storage.lock().await.remove(storage_id);
+        Ok(())
+    }
+
+    async fn insert_text(&self, text: &str) -> Result<(), Error> {
+        self.call_method("Input.insertText", serde_json::json!({ "text": text }))
+            .await
+            .map(|_| ())
+    }
+
+    async fn get_all_cookies(&self) -> Result<Vec<CookieParam>, Error> {
+        Ok(self.cookies.lock().await.clone())
+    }
+
+    async fn set_cookies(&self, cookies: &[CookieParam]) -> Result<(), Error> {
+        self.call_method("Network.setCookies", serde_json::json!({ "cookies": cookies })).await?;
+        let mut jar = self.cookies.lock().await;
+        for cookie in cookies {
+            // Name, domain and path identify a cookie; setting it again overwrites it
+            jar.retain(|c| (&c.name, &c.domain, &c.path) != (&cookie.name, &cookie.domain, &cookie.path));
+            jar.push(cookie.clone());
+        }
+        Ok(())
+    }
+
+    async fn clear_browser_cookies(&self) -> Result<(), Error> {
+        self.call_method("Network.clearBrowserCookies", serde_json::json!({})).await?;
+        self.cookies.lock().await.clear();
+        Ok(())
+    }
 }
 
 /// Mock CDP browser
 #[derive(Debug)]
 pub struct MockCdpBrowser {
     is_active: AtomicBool,
+    clients: Mutex<Vec<(String, Arc<MockCdpClient>)>>,
+    browser_client: Arc<MockCdpClient>,
+    failing_version_checks: AtomicU64,
 }
 
 impl MockCdpBrowser {
@@ -226,8 +672,26 @@ impl MockCdpBrowser {
     pub fn new() -> Self {
         Self {
             is_active: AtomicBool::new(true),
+            clients: Mutex::new(Vec::new()),
+            browser_client: Arc::new(MockCdpClient::new()),
+            failing_version_checks: AtomicU64::new(0),
         }
     }
+
+    /// Fail the next `count` calls to `get_version`, as an unhealthy browser would
+    pub fn fail_version_checks(&self, count: u64) {
+        self.failing_version_checks.store(count, Ordering::SeqCst);
+    }
+
+    /// Clients created so far with the target URL each was created for, in order
+    pub async fn clients(&self) -> Vec<(String, Arc<MockCdpClient>)> {
+        self.clients.lock().await.clone()
+    }
+
+    /// The client returned by `browser_client`, to emit browser-level events in tests
+    pub fn browser_target(&self) -> Arc<MockCdpClient> {
+        Arc::clone(&self.browser_client)
+    }
 }
 
 impl Default for MockCdpBrowser {
@@ -238,12 +702,14 @@ impl Default for MockCdpBrowser {
 
 #[async_trait]
 impl CdpBrowser for MockCdpBrowser {
-    async fn create_client(&self, _target_url: &str) -> Result<Arc<dyn CdpClient>, Error> {
+    async fn create_client(&self, target_url: &str) -> Result<Arc<dyn CdpClient>, Error> {
         if !self.is_active.load(Ordering::Relaxed) {
             return Err(Error::cdp("Browser is closed"));
         }
 
-        Ok(Arc::new(MockCdpClient::new()))
+        let client = Arc::new(MockCdpClient::new());
+        self.clients.lock().await.push((target_url.to_string(), Arc::clone(&client)));
+        Ok(client)
     }
 
     async fn close(&self) -> Result<(), Error> {
@@ -252,9 +718,17 @@ impl CdpBrowser for MockCdpBrowser {
     }
 
     async fn get_version(&self) -> Result<BrowserVersion, Error> {
+        let failing = self
+            .failing_version_checks
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+        if failing.is_ok() {
+            return Err(Error::cdp("Browser.getVersion failed: connection refused"));
+        }
+
         Ok(BrowserVersion {
             protocol_version: "1.3".to_string(),
             product: "Chrome/120.0.0.0".to_string(),
+            revision: "@mock-revision".to_string(),
             user_agent: "Mock Chrome/120.0.0.0".to_string(),
             js_version: "12.0.0.0".to_string(),
         })
@@ -271,10 +745,22 @@ impl CdpBrowser for MockCdpBrowser {
 
         // Return a mock WebSocket URL for testing
         let target_id = uuid::Uuid::new_v4().to_string();
-        let ws_url = format!("ws://localhost:9222/devtools/page/{}", target_id);
+        let ws_url = self.page_ws_url(&target_id);
         tracing::debug!("Mock: Created target {} with URL {} => {}", target_id, url, ws_url);
         Ok(ws_url)
     }
+
+    async fn browser_client(&self) -> Result<Arc<dyn CdpClient>, Error> {
+        if !self.is_active.load(Ordering::Relaxed) {
+            return Err(Error::cdp("Browser is closed"));
+        }
+
+        Ok(self.browser_client.clone())
+    }
+
+    fn page_ws_url(&self, target_id: &str) -> String {
+        format!("ws://localhost:9222/devtools/page/{}", target_id)
+    }
 }
 
 #[cfg(test)]
@@ -299,7 +785,7 @@ mod tests {
         let client = MockCdpClient::new();
 
         let result = client
-            .navigate("https://example.com")
+            .navigate("https://example.com", None)
             .await
             .unwrap();
         assert_eq!(result.url, "https://example.com");
@@ -318,4 +804,16 @@ mod tests {
         let version = browser.get_version().await.unwrap();
         assert_eq!(version.product, "Chrome/120.0.0.0");
     }
+
+    #[tokio::test]
+    async fn test_mock_client_get_version() {
+        let client = MockCdpClient::new();
+
+        let version = client.get_version().await.unwrap();
+        assert_eq!(version.protocol_version, "1.3");
+        assert_eq!(version.product, "Chrome/120.0.0.0");
+        assert_eq!(version.revision, "@mock-revision");
+        assert_eq!(version.user_agent, "Mock Chrome/120.0.0.0");
+        assert_eq!(version.js_version, "12.0.0.0");
+    }
 }
@@ -16,6 +16,9 @@
 //! - `connection`: WebSocket 连接实现
 //! - `client`: CDP 客户端实现
 //! - `browser`: 浏览器级别的操作
+//! - `pool`: CDP 连接池，按端点复用 WebSocket 连接
+//! - `subscriptions`: 事件订阅注册表，按事件前缀路由并按需启用/禁用域
+//! - `keys`: 按键名称表与组合键解析（如 "Ctrl+Shift+K"），生成 `Input.dispatchKeyEvent` 事件
 //! - `mock`: 用于测试的 Mock 实现
 //!
 //! ## 使用示例
@@ -29,7 +32,7 @@
 //! let client = browser.create_client().await?;
 //!
 //! // 导航到页面
-//! let result = client.navigate("https://example.com").await?;
+//! let result = client.navigate("https://example.com", None).await?;
 //! println!("Navigated to: {}", result.url);
 //! # Ok(())
 //! # }
@@ -40,6 +43,9 @@ pub mod types;
 pub mod connection;
 pub mod client;
 pub mod browser;
+pub mod pool;
+pub mod subscriptions;
+pub mod keys;
 pub mod mock;
 
 #[cfg(test)]
@@ -52,9 +58,13 @@ pub use traits::{
 };
 
 // Re-export implementation structs
-pub use connection::CdpWebSocketConnection;
+pub use connection::{
+    CdpWebSocketConnection, KeepAliveConfig, DEFAULT_MAX_INFLIGHT_CDP_COMMANDS, DEFAULT_MAX_MESSAGE_SIZE,
+    DEFAULT_PING_INTERVAL, DEFAULT_PONG_TIMEOUT,
+};
 pub use client::CdpClientImpl;
 pub use browser::CdpBrowserImpl;
+pub use pool::{CdpConnectionPool, CdpPoolConfig, PooledConnection, DEFAULT_ACQUIRE_TIMEOUT};
 
 // Re-export mock for development/testing
 pub use mock::{MockCdpClient, MockCdpBrowser};
@@ -0,0 +1,553 @@
+//! CDP connection pool
+//!
+//! This module provides a bounded pool of CDP connections keyed by WebSocket
+//! endpoint, so that clients created for the same target reuse the existing
+//! WebSocket instead of opening a new one.
+//!
+//! Only connections to browser-level and other long-lived targets go back to
+//! the pool. A page connection carries the page's enabled domains and Fetch
+//! interception, and its target is usually gone once the page is dropped, so
+//! it is closed instead; the pool then only bounds how many are open.
+
+use super::connection::{
+    CdpWebSocketConnection, KeepAliveConfig, DEFAULT_MAX_INFLIGHT_CDP_COMMANDS, DEFAULT_MAX_MESSAGE_SIZE,
+};
+use super::traits::*;
+use crate::Error;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, info, warn};
+
+/// Default time `acquire` waits for a connection when the pool is exhausted
+pub const DEFAULT_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Factory used by the pool to open new connections
+pub type CdpConnector =
+    Arc<dyn Fn(String) -> BoxFuture<'static, Result<Arc<dyn CdpConnection>, Error>> + Send + Sync>;
+
+/// Connection pool configuration
+#[derive(Debug, Clone)]
+pub struct CdpPoolConfig {
+    /// Maximum number of live connections (borrowed + idle)
+    pub max_size: usize,
+    /// How long `acquire` waits for a borrowed connection to be returned
+    pub acquire_timeout: Duration,
+    /// Idle connections older than this are closed
+    pub idle_timeout: Duration,
+    /// Interval between idle eviction / health check passes
+    pub health_check_interval: Duration,
+    /// Timeout for a single health check ping
+    pub health_check_timeout: Duration,
+    /// Largest incoming CDP message accepted by new connections, in bytes
+    pub max_message_size: usize,
+    /// Commands a new connection may have awaiting a response at once (0 = unlimited)
+    pub max_inflight_cdp_commands: usize,
+    /// WebSocket keep-alive pings of new connections
+    ///
+    /// A connection whose peer misses a pong becomes inactive and is replaced
+    /// by a fresh one on the next acquire.
+    pub keep_alive: KeepAliveConfig,
+}
+
+impl Default for CdpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 64,
+            acquire_timeout: DEFAULT_ACQUIRE_TIMEOUT,
+            idle_timeout: Duration::from_secs(300),
+            health_check_interval: Duration::from_secs(30),
+            health_check_timeout: Duration::from_secs(5),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            max_inflight_cdp_commands: DEFAULT_MAX_INFLIGHT_CDP_COMMANDS,
+            keep_alive: KeepAliveConfig::default(),
+        }
+    }
+}
+
+/// Idle connection waiting to be borrowed again
+struct IdleConnection {
+    connection: Arc<dyn CdpConnection>,
+    permit: OwnedSemaphorePermit,
+    idle_since: Instant,
+}
+
+type IdleMap = HashMap<String, Vec<IdleConnection>>;
+
+/// Bounded CDP connection pool
+pub struct CdpConnectionPool {
+    config: CdpPoolConfig,
+    connector: CdpConnector,
+    idle: Arc<Mutex<IdleMap>>,
+    permits: Arc<Semaphore>,
+    maintenance_started: AtomicBool,
+}
+
+impl std::fmt::Debug for CdpConnectionPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CdpConnectionPool")
+            .field("config", &self.config)
+            .field("idle", &self.idle_count())
+            .field("available_permits", &self.permits.available_permits())
+            .finish()
+    }
+}
+
+impl CdpConnectionPool {
+    /// Create a pool that opens `CdpWebSocketConnection`s
+    pub fn new(config: CdpPoolConfig) -> Self {
+        let max_message_size = config.max_message_size;
+        let keep_alive = config.keep_alive;
+        let max_inflight_cdp_commands = config.max_inflight_cdp_commands;
+        let connector: CdpConnector = Arc::new(move |url: String| {
+            Box::pin(async move {
+                let connection =
+                    CdpWebSocketConnection::with_options(url, max_message_size, keep_alive, max_inflight_cdp_commands)
+                        .await?;
+                Ok(connection as Arc<dyn CdpConnection>)
+            })
+        });
+        Self::with_connector(config, connector)
+    }
+
+    /// Create a pool with a custom connection factory
+    pub fn with_connector(config: CdpPoolConfig, connector: CdpConnector) -> Self {
+        let max_size = config.max_size.max(1);
+        Self {
+            config,
+            connector,
+            idle: Arc::new(Mutex::new(HashMap::new())),
+            permits: Arc::new(Semaphore::new(max_size)),
+            maintenance_started: AtomicBool::new(false),
+        }
+    }
+
+    /// Pool configuration
+    pub fn config(&self) -> &CdpPoolConfig {
+        &self.config
+    }
+
+    /// Number of idle connections across all endpoints
+    pub fn idle_count(&self) -> usize {
+        self.idle
+            .lock()
+            .map(|idle| idle.values().map(Vec::len).sum())
+            .unwrap_or(0)
+    }
+
+    /// Borrow a connection for `endpoint`
+    ///
+    /// Reuses a healthy idle connection when one exists, otherwise opens a new
+    /// one. When the pool is full the oldest idle connection is evicted; if
+    /// every connection is borrowed this waits up to `acquire_timeout` for one
+    /// to be returned and fails with [`Error::Unavailable`] otherwise.
+    pub async fn acquire(&self, endpoint: &str) -> Result<PooledConnection, Error> {
+        if let Some(idle) = self.take_idle(endpoint) {
+            debug!("Reusing pooled CDP connection for {}", endpoint);
+            return Ok(self.wrap(endpoint, idle.connection, idle.permit));
+        }
+
+        let permit = match Arc::clone(&self.permits).try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                self.evict_oldest_idle().await;
+                let timeout = self.config.acquire_timeout;
+                tokio::time::timeout(timeout, Arc::clone(&self.permits).acquire_owned())
+                    .await
+                    .map_err(|_| {
+                        Error::unavailable(format!(
+                            "All {} pooled CDP connections stayed in use for {}ms",
+                            self.config.max_size,
+                            timeout.as_millis()
+                        ))
+                    })?
+                    .map_err(|_| Error::cdp("Connection pool is closed"))?
+            }
+        };
+
+        debug!("Opening new pooled CDP connection for {}", endpoint);
+        let connection = (self.connector)(endpoint.to_string()).await?;
+        Ok(self.wrap(endpoint, connection, permit))
+    }
+
+    /// Close idle connections that expired or are no longer active
+    pub async fn evict_idle(&self) -> usize {
+        let idle_timeout = self.config.idle_timeout;
+        let expired = self.drain_idle(|entry| {
+            !entry.connection.is_active() || entry.idle_since.elapsed() >= idle_timeout
+        });
+
+        let count = expired.len();
+        for (_, entry) in expired {
+            let _ = entry.connection.close().await;
+        }
+        if count > 0 {
+            debug!("Evicted {} idle CDP connections", count);
+        }
+        count
+    }
+
+    /// Ping every idle connection with `Browser.getVersion`, dropping failures
+    pub async fn health_check(&self) -> usize {
+        let entries = self.drain_idle(|_| true);
+        let mut unhealthy = 0;
+
+        for (endpoint, entry) in entries {
+            let ping = tokio::time::timeout(
+                self.config.health_check_timeout,
+                entry.connection.send_command("Browser.getVersion", serde_json::json!({})),
+            )
+            .await;
+
+            match ping {
+                Ok(Ok(response)) if response.error.is_none() => {
+                    if let Ok(mut idle) = self.idle.lock() {
+                        idle.entry(endpoint).or_default().push(entry);
+                    }
+                }
+                _ => {
+                    warn!("Dropping unhealthy pooled CDP connection for {}", endpoint);
+                    let _ = entry.connection.close().await;
+                    unhealthy += 1;
+                }
+            }
+        }
+
+        unhealthy
+    }
+
+    /// Close every idle connection
+    pub async fn clear(&self) {
+        for (_, entry) in self.drain_idle(|_| true) {
+            let _ = entry.connection.close().await;
+        }
+    }
+
+    /// Start the background eviction and health check task (once)
+    pub fn ensure_maintenance(self: &Arc<Self>) {
+        if self.maintenance_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let pool = Arc::downgrade(self);
+        let interval = self.config.health_check_interval;
+        info!("Starting CDP connection pool maintenance every {:?}", interval);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(pool) = pool.upgrade() else { break };
+                pool.evict_idle().await;
+                pool.health_check().await;
+            }
+        });
+    }
+
+    fn wrap(
+        &self,
+        endpoint: &str,
+        connection: Arc<dyn CdpConnection>,
+        permit: OwnedSemaphorePermit,
+    ) -> PooledConnection {
+        PooledConnection {
+            endpoint: endpoint.to_string(),
+            connection,
+            permit: Mutex::new(Some(permit)),
+            idle: Arc::downgrade(&self.idle),
+        }
+    }
+
+    fn take_idle(&self, endpoint: &str) -> Option<IdleConnection> {
+        let mut idle = self.idle.lock().ok()?;
+        let entries = idle.get_mut(endpoint)?;
+        while let Some(entry) = entries.pop() {
+            if entry.connection.is_active() {
+                return Some(entry);
+            }
+        }
+        None
+    }
+
+    /// Remove idle entries matching `predicate`, returning them with their endpoint
+    fn drain_idle<F>(&self, predicate: F) -> Vec<(String, IdleConnection)>
+    where
+        F: Fn(&IdleConnection) -> bool,
+    {
+        let mut drained = Vec::new();
+        if let Ok(mut idle) = self.idle.lock() {
+            for (endpoint, entries) in idle.iter_mut() {
+                let (matched, kept): (Vec<_>, Vec<_>) =
+                    std::mem::take(entries).into_iter().partition(|e| predicate(e));
+                *entries = kept;
+                drained.extend(matched.into_iter().map(|e| (endpoint.clone(), e)));
+            }
+            idle.retain(|_, entries| !entries.is_empty());
+        }
+        drained
+    }
+
+    async fn evict_oldest_idle(&self) {
+        let oldest = self.idle.lock().ok().and_then(|mut idle| {
+            let endpoint = idle
+                .iter()
+                .flat_map(|(endpoint, entries)| entries.iter().map(move |e| (endpoint, e.idle_since)))
+                .min_by_key(|(_, since)| *since)
+                .map(|(endpoint, _)| endpoint.clone())?;
+            let entries = idle.get_mut(&endpoint)?;
+            let index = entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, e)| e.idle_since)
+                .map(|(i, _)| i)?;
+            Some(entries.remove(index))
+        });
+
+        if let Some(entry) = oldest {
+            debug!("Pool full, evicting oldest idle CDP connection");
+            let _ = entry.connection.close().await;
+        }
+    }
+}
+
+/// Whether `endpoint` is a page target (`ws://host/devtools/page/<target id>`)
+fn is_page_target(endpoint: &str) -> bool {
+    endpoint.contains("/devtools/page/")
+}
+
+/// Connection borrowed from a [`CdpConnectionPool`]
+///
+/// Returned to the pool when dropped, unless it has been closed or belongs to
+/// a page target, in which case it is closed.
+pub struct PooledConnection {
+    endpoint: String,
+    connection: Arc<dyn CdpConnection>,
+    permit: Mutex<Option<OwnedSemaphorePermit>>,
+    idle: Weak<Mutex<IdleMap>>,
+}
+
+impl PooledConnection {
+    /// Endpoint this connection belongs to
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Underlying connection
+    pub fn inner(&self) -> Arc<dyn CdpConnection> {
+        Arc::clone(&self.connection)
+    }
+}
+
+impl std::fmt::Debug for PooledConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledConnection")
+            .field("endpoint", &self.endpoint)
+            .field("connection", &self.connection)
+            .finish()
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let Some(permit) = self.permit.get_mut().ok().and_then(Option::take) else {
+            return;
+        };
+        if !self.connection.is_active() {
+            return;
+        }
+        if is_page_target(&self.endpoint) {
+            let connection = Arc::clone(&self.connection);
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                runtime.spawn(async move {
+                    if let Err(e) = connection.close().await {
+                        debug!("Failed to close released page connection: {}", e);
+                    }
+                });
+            }
+            return;
+        }
+        if let Some(idle) = self.idle.upgrade() {
+            if let Ok(mut idle) = idle.lock() {
+                idle.entry(self.endpoint.clone()).or_default().push(IdleConnection {
+                    connection: Arc::clone(&self.connection),
+                    permit,
+                    idle_since: Instant::now(),
+                });
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CdpConnection for PooledConnection {
+    async fn send_command(&self, method: &str, params: Value) -> Result<CdpResponse, Error> {
+        self.connection.send_command(method, params).await
+    }
+
+    async fn listen_events(&self) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
+        self.connection.listen_events().await
+    }
+
+    async fn subscribe(&self, pattern: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, Error> {
+        self.connection.subscribe(pattern).await
+    }
+
+    async fn close(&self) -> Result<(), Error> {
+        // A closed connection must not go back to the pool
+        if let Ok(mut permit) = self.permit.lock() {
+            permit.take();
+        }
+        self.connection.close().await
+    }
+
+    fn is_active(&self) -> bool {
+        self.connection.is_active()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpConnection;
+    use std::sync::atomic::AtomicUsize;
+
+    fn counting_pool(config: CdpPoolConfig) -> (CdpConnectionPool, Arc<AtomicUsize>) {
+        let created = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&created);
+        let connector: CdpConnector = Arc::new(move |_url: String| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok(Arc::new(MockCdpConnection::new()) as Arc<dyn CdpConnection>) })
+        });
+        (CdpConnectionPool::with_connector(config, connector), created)
+    }
+
+    #[tokio::test]
+    async fn test_pool_reuses_connections() {
+        let (pool, created) = counting_pool(CdpPoolConfig::default());
+        let endpoint = "ws://localhost:9222/devtools/browser/A";
+
+        let first = pool.acquire(endpoint).await.unwrap();
+        let first_inner = first.inner();
+        drop(first);
+        assert_eq!(pool.idle_count(), 1);
+
+        let second = pool.acquire(endpoint).await.unwrap();
+        assert!(Arc::ptr_eq(&first_inner, &second.inner()));
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.idle_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pool_closes_page_connections_on_release() {
+        let config = CdpPoolConfig {
+            max_size: 1,
+            ..Default::default()
+        };
+        let (pool, created) = counting_pool(config);
+        let endpoint = "ws://localhost:9222/devtools/page/A";
+
+        let first = pool.acquire(endpoint).await.unwrap();
+        let first_inner = first.inner();
+        drop(first);
+        assert_eq!(pool.idle_count(), 0);
+
+        // The permit is back right away, the socket is closed in the background
+        let second = pool.acquire(endpoint).await.unwrap();
+        assert!(!Arc::ptr_eq(&first_inner, &second.inner()));
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        for _ in 0..50 {
+            if !first_inner.is_active() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!first_inner.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_pool_separates_endpoints() {
+        let (pool, created) = counting_pool(CdpPoolConfig::default());
+
+        drop(pool.acquire("ws://a").await.unwrap());
+        let _b = pool.acquire("ws://b").await.unwrap();
+
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.idle_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pool_does_not_reuse_closed_connections() {
+        let (pool, created) = counting_pool(CdpPoolConfig::default());
+
+        let conn = pool.acquire("ws://a").await.unwrap();
+        conn.close().await.unwrap();
+        drop(conn);
+        assert_eq!(pool.idle_count(), 0);
+
+        let conn = pool.acquire("ws://a").await.unwrap();
+        assert!(conn.is_active());
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_max_size_evicts_oldest_idle() {
+        let config = CdpPoolConfig {
+            max_size: 1,
+            ..Default::default()
+        };
+        let (pool, created) = counting_pool(config);
+
+        let a = pool.acquire("ws://a").await.unwrap();
+        let a_inner = a.inner();
+        drop(a);
+
+        let _b = pool.acquire("ws://b").await.unwrap();
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.idle_count(), 0);
+        assert!(!a_inner.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_pool_acquire_times_out_when_exhausted() {
+        let config = CdpPoolConfig {
+            max_size: 1,
+            acquire_timeout: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let (pool, created) = counting_pool(config);
+
+        let held = pool.acquire("ws://a").await.unwrap();
+        let err = pool.acquire("ws://b").await.unwrap_err();
+        assert!(matches!(err, Error::Unavailable(_)), "{:?}", err);
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+
+        drop(held);
+        assert!(pool.acquire("ws://b").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_pool_evicts_expired_idle() {
+        let config = CdpPoolConfig {
+            idle_timeout: Duration::from_millis(0),
+            ..Default::default()
+        };
+        let (pool, _) = counting_pool(config);
+
+        drop(pool.acquire("ws://a").await.unwrap());
+        assert_eq!(pool.evict_idle().await, 1);
+        assert_eq!(pool.idle_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pool_health_check_keeps_healthy() {
+        let (pool, _) = counting_pool(CdpPoolConfig::default());
+
+        drop(pool.acquire("ws://a").await.unwrap());
+        assert_eq!(pool.health_check().await, 0);
+        assert_eq!(pool.idle_count(), 1);
+    }
+}
@@ -0,0 +1,221 @@
+//! CDP event subscription registry
+//!
+//! Routes incoming events only to the subscribers whose pattern matches, and
+//! tracks which domains have listeners so a connection can enable a domain on
+//! the first subscription and disable it again when the last one goes away.
+
+use super::traits::CdpEvent;
+use std::collections::HashMap;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Domains enabled on demand for their subscribers
+///
+/// Limited to domains whose `enable` takes no required parameters and only
+/// turns on event reporting. `Fetch.enable`, for example, would pause every
+/// request and is never enabled implicitly.
+pub const AUTO_ENABLED_DOMAINS: &[&str] = &["Network", "Page", "Runtime", "DOM", "Log"];
+
+/// Pattern matching every event
+pub const ALL_EVENTS: &str = "*";
+
+/// Whether an event `method` matches a subscription `pattern`
+///
+/// A pattern is an event name (`Network.responseReceived`), a domain wildcard
+/// (`Network.*`) or `*` for every event.
+pub fn matches_pattern(pattern: &str, method: &str) -> bool {
+    if pattern == ALL_EVENTS || pattern == method {
+        return true;
+    }
+
+    match pattern.strip_suffix(".*") {
+        Some(domain) => method.split_once('.').is_some_and(|(d, _)| d == domain),
+        None => false,
+    }
+}
+
+/// Domain a pattern listens to, if it is limited to one
+pub fn pattern_domain(pattern: &str) -> Option<&str> {
+    if pattern == ALL_EVENTS {
+        return None;
+    }
+    pattern.split_once('.').map(|(domain, _)| domain).filter(|d| !d.is_empty())
+}
+
+/// Event subscribers keyed by pattern
+#[derive(Debug, Default)]
+pub struct EventRegistry {
+    next_id: u64,
+    subscribers: HashMap<String, Vec<(u64, UnboundedSender<CdpEvent>)>>,
+}
+
+impl EventRegistry {
+    /// Register `sender` for events matching `pattern`, returning its subscription id
+    pub fn add(&mut self, pattern: &str, sender: UnboundedSender<CdpEvent>) -> u64 {
+        self.next_id += 1;
+        self.subscribers
+            .entry(pattern.to_string())
+            .or_default()
+            .push((self.next_id, sender));
+        self.next_id
+    }
+
+    /// Remove a subscription; returns false if it was already gone
+    pub fn remove(&mut self, pattern: &str, id: u64) -> bool {
+        let Some(senders) = self.subscribers.get_mut(pattern) else {
+            return false;
+        };
+        let before = senders.len();
+        senders.retain(|(sid, _)| *sid != id);
+        let removed = senders.len() != before;
+        if senders.is_empty() {
+            self.subscribers.remove(pattern);
+        }
+        removed
+    }
+
+    /// Deliver `event` to every matching subscriber
+    ///
+    /// Only the exact event name, its domain wildcard and `*` are looked up,
+    /// so events nobody listens to cost a few hash lookups.
+    pub fn dispatch(&mut self, event: &CdpEvent) {
+        let domain_pattern = event.method.split_once('.').map(|(domain, _)| format!("{}.*", domain));
+        let keys = [Some(event.method.as_str()), domain_pattern.as_deref(), Some(ALL_EVENTS)];
+
+        for key in keys.into_iter().flatten() {
+            if let Some(senders) = self.subscribers.get_mut(key) {
+                senders.retain(|(_, sender)| sender.send(event.clone()).is_ok());
+                if senders.is_empty() {
+                    self.subscribers.remove(key);
+                }
+            }
+        }
+    }
+
+    /// Number of live subscriptions
+    pub fn len(&self) -> usize {
+        self.subscribers.values().map(Vec::len).sum()
+    }
+
+    /// Whether there are no subscriptions
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty()
+    }
+}
+
+/// Enable state of a single domain
+#[derive(Debug, Default, Clone, Copy)]
+struct DomainState {
+    /// Subscriptions listening to the domain
+    subscribers: usize,
+    /// Enabled by an explicit `<Domain>.enable` command
+    explicit: bool,
+}
+
+/// Reference counts of subscriptions per auto-enabled domain
+///
+/// A domain enabled explicitly through `<Domain>.enable` stays enabled: other
+/// state (e.g. `Network.setUserAgentOverride`) may depend on it.
+#[derive(Debug, Default)]
+pub struct DomainTracker {
+    domains: HashMap<String, DomainState>,
+}
+
+impl DomainTracker {
+    /// Count a new subscriber; returns true if the domain must be enabled now
+    pub fn subscribe(&mut self, domain: &str) -> bool {
+        if !AUTO_ENABLED_DOMAINS.contains(&domain) {
+            return false;
+        }
+        let state = self.domains.entry(domain.to_string()).or_default();
+        state.subscribers += 1;
+        state.subscribers == 1 && !state.explicit
+    }
+
+    /// Drop a subscriber; returns true if the domain must be disabled now
+    pub fn unsubscribe(&mut self, domain: &str) -> bool {
+        let Some(state) = self.domains.get_mut(domain) else {
+            return false;
+        };
+        state.subscribers = state.subscribers.saturating_sub(1);
+        state.subscribers == 0 && !state.explicit
+    }
+
+    /// Record an explicit `<Domain>.enable` / `<Domain>.disable` command
+    pub fn set_explicit(&mut self, domain: &str, enabled: bool) {
+        if AUTO_ENABLED_DOMAINS.contains(&domain) {
+            self.domains.entry(domain.to_string()).or_default().explicit = enabled;
+        }
+    }
+
+    /// Number of subscribers of `domain`
+    pub fn subscribers(&self, domain: &str) -> usize {
+        self.domains.get(domain).map_or(0, |state| state.subscribers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str) -> CdpEvent {
+        CdpEvent {
+            method: method.to_string(),
+            params: serde_json::json!({}),
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("*", "Page.loadEventFired"));
+        assert!(matches_pattern("Network.*", "Network.responseReceived"));
+        assert!(matches_pattern("Network.responseReceived", "Network.responseReceived"));
+        assert!(!matches_pattern("Network.*", "NetworkX.responseReceived"));
+        assert!(!matches_pattern("Network.requestWillBeSent", "Network.responseReceived"));
+        assert_eq!(pattern_domain("Network.*"), Some("Network"));
+        assert_eq!(pattern_domain("*"), None);
+    }
+
+    #[test]
+    fn test_dispatch_routes_by_pattern() {
+        let mut registry = EventRegistry::default();
+        let (network_tx, mut network_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (response_tx, mut response_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (all_tx, mut all_rx) = tokio::sync::mpsc::unbounded_channel();
+        registry.add("Network.*", network_tx);
+        let response_id = registry.add("Network.responseReceived", response_tx);
+        registry.add("*", all_tx);
+
+        registry.dispatch(&event("Page.loadEventFired"));
+        registry.dispatch(&event("Network.responseReceived"));
+
+        assert_eq!(network_rx.try_recv().unwrap().method, "Network.responseReceived");
+        assert!(network_rx.try_recv().is_err());
+        assert_eq!(response_rx.try_recv().unwrap().method, "Network.responseReceived");
+        assert!(response_rx.try_recv().is_err());
+        assert_eq!(all_rx.try_recv().unwrap().method, "Page.loadEventFired");
+        assert_eq!(all_rx.try_recv().unwrap().method, "Network.responseReceived");
+
+        assert!(registry.remove("Network.responseReceived", response_id));
+        drop(network_rx);
+        registry.dispatch(&event("Network.requestWillBeSent"));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_domain_tracker_respects_explicit_enable() {
+        let mut tracker = DomainTracker::default();
+
+        assert!(tracker.subscribe("Network"));
+        assert!(!tracker.subscribe("Network"));
+        assert!(!tracker.unsubscribe("Network"));
+        assert!(tracker.unsubscribe("Network"));
+
+        tracker.set_explicit("Network", true);
+        assert!(!tracker.subscribe("Network"));
+        assert!(!tracker.unsubscribe("Network"));
+
+        // Fetch.enable without patterns would pause every request
+        assert!(!tracker.subscribe("Fetch"));
+    }
+}
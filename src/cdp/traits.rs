@@ -4,6 +4,8 @@
 
 use async_trait::async_trait;
 use serde_json::Value;
+use super::types::{CookieParam, StorageId};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// CDP event representation
@@ -54,6 +56,27 @@ pub trait CdpConnection: Send + Sync + std::fmt::Debug {
     /// Subscribe to CDP events
     async fn listen_events(&self) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, crate::Error>;
 
+    /// Subscribe to events matching `pattern`
+    ///
+    /// `pattern` is an event name (`Network.responseReceived`), a domain
+    /// wildcard (`Network.*`) or `*`. The default implementation filters
+    /// `listen_events`; connections may route events and enable domains on demand.
+    async fn subscribe(&self, pattern: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, crate::Error> {
+        let mut events = self.listen_events().await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let pattern = pattern.to_string();
+
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if super::subscriptions::matches_pattern(&pattern, &event.method) && tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
     /// Close the connection
     async fn close(&self) -> Result<(), crate::Error>;
 
@@ -69,12 +92,20 @@ pub trait CdpClient: Send + Sync + std::fmt::Debug {
     /// Get the underlying connection
     fn connection(&self) -> Arc<dyn CdpConnection>;
 
-    /// Navigate to a URL
-    async fn navigate(&self, url: &str) -> Result<NavigationResult, crate::Error>;
+    /// Navigate to a URL, optionally sending `referrer` as the Referer header
+    async fn navigate(&self, url: &str, referrer: Option<&str>) -> Result<NavigationResult, crate::Error>;
 
     /// Evaluate JavaScript in the page
     async fn evaluate(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, crate::Error>;
 
+    /// Evaluate JavaScript in an execution context, e.g. an isolated world from `Page.createIsolatedWorld`
+    async fn evaluate_in_context(
+        &self,
+        script: &str,
+        await_promise: bool,
+        context_id: i64,
+    ) -> Result<EvaluationResult, crate::Error>;
+
     /// Capture a screenshot
     async fn screenshot(&self, format: ScreenshotFormat) -> Result<Vec<u8>, crate::Error>;
 
@@ -95,6 +126,36 @@ pub trait CdpClient: Send + Sync + std::fmt::Debug {
 
     /// Subscribe to events (returns a receiver)
     async fn subscribe_events(&self, event_type: &str) -> Result<tokio::sync::mpsc::Receiver<CdpEvent>, crate::Error>;
+
+    /// Get browser version via `Browser.getVersion`
+    async fn get_version(&self) -> Result<BrowserVersion, crate::Error>;
+
+    /// Set extra HTTP headers sent with every request (an empty map clears them)
+    async fn set_extra_http_headers(&self, headers: &HashMap<String, String>) -> Result<(), crate::Error>;
+
+    /// Slow down the page's CPU by `rate` (1.0 = no throttling) via `Emulation.setCPUThrottlingRate`
+    async fn set_cpu_throttling_rate(&self, rate: f64) -> Result<(), crate::Error>;
+
+    /// Get all key/value pairs of a DOM storage area
+    async fn get_dom_storage_items(&self, storage_id: &StorageId) -> Result<Vec<(String, String)>, crate::Error>;
+
+    /// Set a single item in a DOM storage area
+    async fn set_dom_storage_item(&self, storage_id: &StorageId, key: &str, value: &str) -> Result<(), crate::Error>;
+
+    /// Remove all items from a DOM storage area
+    async fn clear_dom_storage(&self, storage_id: &StorageId) -> Result<(), crate::Error>;
+
+    /// Insert `text` into the focused element in one step, as if pasted, via `Input.insertText`
+    async fn insert_text(&self, text: &str) -> Result<(), crate::Error>;
+
+    /// Get all browser cookies via `Network.getAllCookies`
+    async fn get_all_cookies(&self) -> Result<Vec<CookieParam>, crate::Error>;
+
+    /// Set many cookies in one `Network.setCookies` call
+    async fn set_cookies(&self, cookies: &[CookieParam]) -> Result<(), crate::Error>;
+
+    /// Delete all browser cookies via `Network.clearBrowserCookies`
+    async fn clear_browser_cookies(&self) -> Result<(), crate::Error>;
 }
 
 /// Navigation result
@@ -155,6 +216,14 @@ pub trait CdpBrowser: Send + Sync + std::fmt::Debug {
     ///
     /// Returns the WebSocket URL of the newly created target.
     async fn create_target(&self, url: &str) -> Result<String, crate::Error>;
+
+    /// Client on the browser-level target, for `Target.*`, `Storage.*` and other browser-wide commands
+    ///
+    /// The connection is shared by all callers and stays open until the browser is closed.
+    async fn browser_client(&self) -> Result<Arc<dyn CdpClient>, crate::Error>;
+
+    /// WebSocket URL of the page target `target_id`
+    fn page_ws_url(&self, target_id: &str) -> String;
 }
 
 /// Browser version information
@@ -164,12 +233,35 @@ pub struct BrowserVersion {
     pub protocol_version: String,
     /// Product name
     pub product: String,
+    /// Product revision
+    pub revision: String,
     /// User agent
     pub user_agent: String,
     /// JavaScript engine version
     pub js_version: String,
 }
 
+impl BrowserVersion {
+    /// Build from a `Browser.getVersion` result
+    pub fn from_cdp_result(result: &Value) -> Self {
+        let field = |name: &str| {
+            result
+                .get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string()
+        };
+
+        Self {
+            protocol_version: field("protocolVersion"),
+            product: field("product"),
+            revision: field("revision"),
+            user_agent: field("userAgent"),
+            js_version: field("jsVersion"),
+        }
+    }
+}
+
 /// Target information (page, worker, etc.)
 #[derive(Debug, Clone)]
 pub struct TargetInfo {
@@ -212,10 +212,102 @@ pub struct GetDocumentResponse {
     pub root: Node,
 }
 
+/// DOM storage identifier (origin and storage type)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StorageId {
+    /// Security origin, e.g. "https://example.com"
+    #[serde(rename = "securityOrigin")]
+    pub security_origin: String,
+    /// `true` for localStorage, `false` for sessionStorage
+    #[serde(rename = "isLocalStorage")]
+    pub is_local_storage: bool,
+}
+
+/// Cookie as sent to `Network.setCookies` and returned by `Network.getCookies`
+///
+/// Chrome reports a session cookie with `session: true` and `expires: -1`;
+/// it is read with `expires` set to `None`, so writing it back keeps it a
+/// session cookie instead of one that expired in 1969.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "ReportedCookie")]
+pub struct CookieParam {
+    /// Cookie name
+    pub name: String,
+    /// Cookie value
+    pub value: String,
+    /// Cookie domain
+    pub domain: String,
+    /// Cookie path
+    pub path: String,
+    /// Only sent over HTTPS
+    #[serde(default)]
+    pub secure: bool,
+    /// Hidden from `document.cookie`
+    #[serde(default)]
+    pub http_only: bool,
+    /// "Strict", "Lax" or "None"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_site: Option<String>,
+    /// Expiry as Unix time in seconds; `None` for a session cookie
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<f64>,
+}
+
+/// Cookie fields as Chrome reports them, including the `session` flag
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportedCookie {
+    name: String,
+    value: String,
+    domain: String,
+    path: String,
+    #[serde(default)]
+    secure: bool,
+    #[serde(default)]
+    http_only: bool,
+    #[serde(default)]
+    same_site: Option<String>,
+    #[serde(default)]
+    expires: Option<f64>,
+    #[serde(default)]
+    session: bool,
+}
+
+impl From<ReportedCookie> for CookieParam {
+    fn from(cookie: ReportedCookie) -> Self {
+        Self {
+            name: cookie.name,
+            value: cookie.value,
+            domain: cookie.domain,
+            path: cookie.path,
+            secure: cookie.secure,
+            http_only: cookie.http_only,
+            same_site: cookie.same_site,
+            expires: cookie.expires.filter(|&expires| !cookie.session && expires > 0.0),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_session_cookie_read_without_expiry() {
+        let cookies: Vec<CookieParam> = serde_json::from_value(serde_json::json!([
+            { "name": "sid", "value": "1", "domain": "example.com", "path": "/", "expires": -1, "session": true },
+            { "name": "odd", "value": "2", "domain": "example.com", "path": "/", "expires": 1.9e9, "session": true },
+            { "name": "zero", "value": "3", "domain": "example.com", "path": "/", "expires": 0 },
+            { "name": "keep", "value": "4", "domain": "example.com", "path": "/", "expires": 1.9e9, "session": false },
+        ]))
+        .unwrap();
+
+        let expiries: Vec<Option<f64>> = cookies.iter().map(|cookie| cookie.expires).collect();
+        assert_eq!(expiries, vec![None, None, None, Some(1.9e9)]);
+        // Written back without `expires`, so Chrome keeps them as session cookies
+        assert!(serde_json::to_value(&cookies[0]).unwrap().get("expires").is_none());
+    }
+
     #[test]
     fn test_cdp_request_serialization() {
         let request = CdpRequest {
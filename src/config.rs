@@ -3,6 +3,7 @@
 use crate::{Error, Result};
 use serde::Deserialize;
 use std::env;
+use std::str::FromStr;
 
 /// Macro for parsing environment variables with type safety and consistent error handling
 macro_rules! parse_env_var {
@@ -27,6 +28,167 @@ macro_rules! parse_env_var {
     };
 }
 
+/// Default navigation timeout in milliseconds, used when a request leaves `timeout` at 0
+pub const DEFAULT_NAVIGATION_TIMEOUT_MS: u64 = 30_000;
+
+/// Upper bound for navigation timeouts in milliseconds (10 minutes)
+///
+/// Larger request values and a larger configured default are clamped to this.
+pub const MAX_NAVIGATION_TIMEOUT_MS: u64 = 600_000;
+
+fn default_navigation_timeout() -> u64 {
+    DEFAULT_NAVIGATION_TIMEOUT_MS
+}
+
+/// Default limit for evaluate/content results in bytes (gRPC clients reject
+/// messages over 4 MiB by default)
+pub const DEFAULT_MAX_EVALUATE_RESULT_BYTES: usize = 4 * 1024 * 1024;
+
+fn default_max_evaluate_result_bytes() -> usize {
+    DEFAULT_MAX_EVALUATE_RESULT_BYTES
+}
+
+/// Default limit for a single CDP WebSocket message in bytes
+pub const DEFAULT_MAX_WS_MESSAGE_SIZE: usize = crate::cdp::DEFAULT_MAX_MESSAGE_SIZE;
+
+fn default_max_ws_message_size() -> usize {
+    DEFAULT_MAX_WS_MESSAGE_SIZE
+}
+
+/// Default limit for CDP commands awaiting a response per page connection
+pub const DEFAULT_MAX_INFLIGHT_CDP_COMMANDS: usize = crate::cdp::DEFAULT_MAX_INFLIGHT_CDP_COMMANDS;
+
+fn default_max_inflight_cdp_commands() -> usize {
+    DEFAULT_MAX_INFLIGHT_CDP_COMMANDS
+}
+
+/// Default limit for concurrent navigations per browser
+pub const DEFAULT_MAX_CONCURRENT_NAVIGATIONS: usize = crate::session::navigation_limit::DEFAULT_MAX_CONCURRENT_NAVIGATIONS;
+
+fn default_max_concurrent_navigations() -> usize {
+    DEFAULT_MAX_CONCURRENT_NAVIGATIONS
+}
+
+/// Default number of console messages kept per page
+pub const DEFAULT_CONSOLE_BUFFER_SIZE: usize = crate::session::console::DEFAULT_CONSOLE_BUFFER_SIZE;
+
+fn default_console_buffer_size() -> usize {
+    DEFAULT_CONSOLE_BUFFER_SIZE
+}
+
+/// Default number of redirects kept in a navigation's redirect chain
+pub const DEFAULT_MAX_REDIRECT_HOPS: usize = crate::session::page::DEFAULT_MAX_REDIRECT_HOPS;
+
+fn default_max_redirect_hops() -> usize {
+    DEFAULT_MAX_REDIRECT_HOPS
+}
+
+/// Default number of health check retries for a new browser
+pub const DEFAULT_BROWSER_HEALTH_CHECK_RETRIES: u32 = 3;
+
+/// Default timeout of a single browser health check in milliseconds
+pub const DEFAULT_BROWSER_HEALTH_CHECK_TIMEOUT_MS: u64 = 5_000;
+
+fn default_browser_health_check_retries() -> u32 {
+    DEFAULT_BROWSER_HEALTH_CHECK_RETRIES
+}
+
+fn default_browser_health_check_timeout() -> u64 {
+    DEFAULT_BROWSER_HEALTH_CHECK_TIMEOUT_MS
+}
+
+/// Default timeout of the startup CDP endpoint probe in milliseconds
+pub const DEFAULT_CDP_PROBE_TIMEOUT_MS: u64 = 5_000;
+
+fn default_cdp_probe_timeout() -> u64 {
+    DEFAULT_CDP_PROBE_TIMEOUT_MS
+}
+
+/// Default interval between CDP WebSocket keep-alive pings in milliseconds
+pub const DEFAULT_CDP_PING_INTERVAL_MS: u64 = crate::cdp::DEFAULT_PING_INTERVAL.as_millis() as u64;
+
+/// Default time to wait for a keep-alive pong in milliseconds
+pub const DEFAULT_CDP_PONG_TIMEOUT_MS: u64 = crate::cdp::DEFAULT_PONG_TIMEOUT.as_millis() as u64;
+
+/// Default time to wait for a pooled CDP connection in milliseconds
+pub const DEFAULT_CDP_POOL_ACQUIRE_TIMEOUT_MS: u64 = crate::cdp::DEFAULT_ACQUIRE_TIMEOUT.as_millis() as u64;
+
+fn default_cdp_ping_interval() -> u64 {
+    DEFAULT_CDP_PING_INTERVAL_MS
+}
+
+fn default_cdp_pong_timeout() -> u64 {
+    DEFAULT_CDP_PONG_TIMEOUT_MS
+}
+
+fn default_cdp_pool_acquire_timeout() -> u64 {
+    DEFAULT_CDP_POOL_ACQUIRE_TIMEOUT_MS
+}
+
+/// Startup probe of the CDP endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CdpProbeMode {
+    /// Do not probe the endpoint
+    Off,
+    /// Probe and log a warning if the endpoint is unreachable
+    #[default]
+    Warn,
+    /// Probe and refuse to start if the endpoint is unreachable
+    Required,
+}
+
+impl FromStr for CdpProbeMode {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "required" => Ok(Self::Required),
+            _ => Err(Error::configuration(format!("Unknown CDP probe mode: {}", value))),
+        }
+    }
+}
+
+/// Default burst size of the per-client rate limit
+pub const DEFAULT_RATE_LIMIT_BURST: u32 = 20;
+
+fn default_rate_limit_burst() -> u32 {
+    DEFAULT_RATE_LIMIT_BURST
+}
+
+/// Default JPEG/WebP screenshot quality, used when a request leaves `quality` at 0
+pub const DEFAULT_SCREENSHOT_QUALITY: u8 = crate::session::screenshot::DEFAULT_QUALITY;
+
+fn default_screenshot_quality() -> u8 {
+    DEFAULT_SCREENSHOT_QUALITY
+}
+
+/// Viewport applied to new pages whose create request has no viewport
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ViewportConfig {
+    /// Viewport width in CSS pixels
+    pub width: u32,
+
+    /// Viewport height in CSS pixels
+    pub height: u32,
+
+    /// Device scale factor
+    pub device_scale_factor: f64,
+}
+
+impl Default for ViewportConfig {
+    fn default() -> Self {
+        Self {
+            width: 1920,
+            height: 1080,
+            device_scale_factor: 1.0,
+        }
+    }
+}
+
 /// Server configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -42,6 +204,20 @@ pub struct Config {
     /// Chrome data directory
     pub chrome_data_dir: Option<String>,
 
+    /// Default flags for browsers the server launches
+    ///
+    /// Launch request args override flags of the same name, and `!--flag`
+    /// removes one.
+    #[serde(default)]
+    pub launch_args: Vec<String>,
+
+    /// Launch browsers with the flags containers need (`--disable-gpu`,
+    /// `--no-sandbox`, `--disable-dev-shm-usage`) ahead of `launch_args`
+    ///
+    /// Off by default so Chrome keeps its sandbox.
+    #[serde(default)]
+    pub container_mode: bool,
+
     /// Maximum concurrent browsers
     pub max_browsers: usize,
 
@@ -54,6 +230,141 @@ pub struct Config {
     /// Default timeout for operations in milliseconds
     pub default_timeout: u64,
 
+    /// Default navigation timeout in milliseconds
+    ///
+    /// Applied to navigate/reload/wait-for-navigation requests whose `timeout` is 0.
+    /// Capped at [`MAX_NAVIGATION_TIMEOUT_MS`].
+    #[serde(default = "default_navigation_timeout")]
+    pub default_navigation_timeout: u64,
+
+    /// Largest evaluate, page content or element HTML result returned, in bytes
+    ///
+    /// Larger results are rejected with `ResourceExhausted`.
+    #[serde(default = "default_max_evaluate_result_bytes")]
+    pub max_evaluate_result_bytes: usize,
+
+    /// Largest CDP WebSocket message accepted from Chrome, in bytes
+    ///
+    /// A larger message (e.g. a huge screenshot) fails the pending commands with
+    /// a CDP error and closes the connection.
+    #[serde(default = "default_max_ws_message_size")]
+    pub max_ws_message_size: usize,
+
+    /// CDP commands a connection may have awaiting a response at once
+    ///
+    /// Further commands wait in call order; `0` disables the limit.
+    #[serde(default = "default_max_inflight_cdp_commands")]
+    pub max_inflight_cdp_commands: usize,
+
+    /// Navigations a browser's pages may run at once
+    ///
+    /// Further navigations wait in call order; `0` disables the limit.
+    #[serde(default = "default_max_concurrent_navigations")]
+    pub max_concurrent_navigations: usize,
+
+    /// Console messages kept per page for `GetConsoleLogs`
+    ///
+    /// Older messages are evicted first; `0` keeps none.
+    #[serde(default = "default_console_buffer_size")]
+    pub console_buffer_size: usize,
+
+    /// Redirects kept in a navigation result's redirect chain
+    ///
+    /// Later redirects are dropped and the result is flagged as truncated;
+    /// the final URL and status code are always returned.
+    #[serde(default = "default_max_redirect_hops")]
+    pub max_redirect_hops: usize,
+
+    /// Health checks retried before a new browser is reported unavailable
+    #[serde(default = "default_browser_health_check_retries")]
+    pub browser_health_check_retries: u32,
+
+    /// Timeout of a single browser health check (`Browser.getVersion`) in milliseconds
+    #[serde(default = "default_browser_health_check_timeout")]
+    pub browser_health_check_timeout: u64,
+
+    /// Whether the CDP endpoint is probed (`Browser.getVersion`) at startup
+    #[serde(default)]
+    pub cdp_probe: CdpProbeMode,
+
+    /// Timeout of the startup CDP endpoint probe in milliseconds
+    #[serde(default = "default_cdp_probe_timeout")]
+    pub cdp_probe_timeout: u64,
+
+    /// Interval between WebSocket pings on CDP connections in milliseconds
+    ///
+    /// `0` disables keep-alive pings.
+    #[serde(default = "default_cdp_ping_interval")]
+    pub cdp_ping_interval: u64,
+
+    /// Time to wait for the pong answering a ping in milliseconds
+    ///
+    /// A connection whose pong is overdue is dropped and replaced, and the
+    /// page using it is reported as crashed. Must be greater than 0.
+    #[serde(default = "default_cdp_pong_timeout")]
+    pub cdp_pong_timeout: u64,
+
+    /// Time to wait for a CDP connection when the pool is exhausted in milliseconds
+    ///
+    /// Requests that time out fail with `UNAVAILABLE`.
+    #[serde(default = "default_cdp_pool_acquire_timeout")]
+    pub cdp_pool_acquire_timeout: u64,
+
+    /// Default viewport for new pages
+    ///
+    /// Request-specified viewports take precedence.
+    #[serde(default)]
+    pub default_viewport: ViewportConfig,
+
+    /// JPEG screenshot quality (1-100) used when a request leaves `quality` at 0
+    #[serde(default = "default_screenshot_quality")]
+    pub screenshot_jpeg_quality: u8,
+
+    /// WebP screenshot quality (1-100) used when a request leaves `quality` at 0
+    #[serde(default = "default_screenshot_quality")]
+    pub screenshot_webp_quality: u8,
+
+    /// Directory screenshots and PDFs may be written to on request
+    ///
+    /// Saving to a path is rejected when unset.
+    pub output_dir: Option<String>,
+
+    /// URL patterns pages may navigate to; empty allows every URL
+    ///
+    /// Patterns use `*` wildcards and match the full URL or its host
+    /// (e.g. `*.example.com`). Disallowed navigations fail with `PermissionDenied`;
+    /// pages also fail disallowed document requests, redirects included. Any
+    /// restriction limits pages to `http` and `https` URLs.
+    #[serde(default)]
+    pub url_allowlist: Vec<String>,
+
+    /// URL patterns pages may not navigate to, checked before the allowlist
+    #[serde(default)]
+    pub url_denylist: Vec<String>,
+
+    /// Reject navigations to hosts resolving to loopback, private or link-local addresses
+    ///
+    /// A best-effort pre-check, not SSRF protection: Chrome resolves the host
+    /// again, so a DNS rebinding host can still reach private addresses.
+    #[serde(default)]
+    pub block_private_networks: bool,
+
+    /// Token required in the `x-admin-token` metadata of admin RPCs (`DumpState`)
+    ///
+    /// Admin RPCs are rejected when unset.
+    pub admin_token: Option<String>,
+
+    /// Requests per second each client may make; `0` disables rate limiting
+    ///
+    /// Clients are identified by peer IP address. Requests over the limit fail
+    /// with `ResourceExhausted`.
+    #[serde(default)]
+    pub rate_limit_per_second: f64,
+
+    /// Requests a client may make at once before the rate limit applies
+    #[serde(default = "default_rate_limit_burst")]
+    pub rate_limit_burst: u32,
+
     /// Enable stealth mode by default
     pub stealth_enabled: bool,
 
@@ -68,10 +379,36 @@ impl Default for Config {
             port: 50051,
             chrome_path: None,
             chrome_data_dir: None,
+            launch_args: Vec::new(),
+            container_mode: false,
             max_browsers: 10,
             max_pages_per_browser: 20,
             session_timeout: 3600,
             default_timeout: 30000,
+            default_navigation_timeout: DEFAULT_NAVIGATION_TIMEOUT_MS,
+            max_evaluate_result_bytes: DEFAULT_MAX_EVALUATE_RESULT_BYTES,
+            max_ws_message_size: DEFAULT_MAX_WS_MESSAGE_SIZE,
+            max_inflight_cdp_commands: DEFAULT_MAX_INFLIGHT_CDP_COMMANDS,
+            max_concurrent_navigations: DEFAULT_MAX_CONCURRENT_NAVIGATIONS,
+            console_buffer_size: DEFAULT_CONSOLE_BUFFER_SIZE,
+            max_redirect_hops: DEFAULT_MAX_REDIRECT_HOPS,
+            browser_health_check_retries: DEFAULT_BROWSER_HEALTH_CHECK_RETRIES,
+            browser_health_check_timeout: DEFAULT_BROWSER_HEALTH_CHECK_TIMEOUT_MS,
+            cdp_probe: CdpProbeMode::default(),
+            cdp_probe_timeout: DEFAULT_CDP_PROBE_TIMEOUT_MS,
+            cdp_ping_interval: DEFAULT_CDP_PING_INTERVAL_MS,
+            cdp_pong_timeout: DEFAULT_CDP_PONG_TIMEOUT_MS,
+            cdp_pool_acquire_timeout: DEFAULT_CDP_POOL_ACQUIRE_TIMEOUT_MS,
+            default_viewport: ViewportConfig::default(),
+            screenshot_jpeg_quality: DEFAULT_SCREENSHOT_QUALITY,
+            screenshot_webp_quality: DEFAULT_SCREENSHOT_QUALITY,
+            output_dir: None,
+            url_allowlist: Vec::new(),
+            url_denylist: Vec::new(),
+            block_private_networks: false,
+            admin_token: None,
+            rate_limit_per_second: 0.0,
+            rate_limit_burst: DEFAULT_RATE_LIMIT_BURST,
             stealth_enabled: true,
             log_level: "info".to_string(),
         }
@@ -88,13 +425,49 @@ impl Config {
         parse_env_var!(config, port, "CHASER_PORT", u16);
         parse_env_var!(opt config, chrome_path, "CHASER_CHROME_PATH");
         parse_env_var!(opt config, chrome_data_dir, "CHASER_DATA_DIR");
+        if let Ok(value) = env::var("CHASER_LAUNCH_ARGS") {
+            config.launch_args = value.split_whitespace().map(str::to_string).collect();
+        }
+        parse_env_var!(config, container_mode, "CHASER_CONTAINER_MODE", bool);
         parse_env_var!(config, max_browsers, "CHASER_MAX_BROWSERS", usize);
         parse_env_var!(config, max_pages_per_browser, "CHASER_MAX_PAGES", usize);
         parse_env_var!(config, session_timeout, "CHASER_SESSION_TIMEOUT", u64);
         parse_env_var!(config, default_timeout, "CHASER_DEFAULT_TIMEOUT", u64);
+        parse_env_var!(config, default_navigation_timeout, "CHASER_NAVIGATION_TIMEOUT", u64);
+        parse_env_var!(config, max_evaluate_result_bytes, "CHASER_MAX_EVALUATE_RESULT_BYTES", usize);
+        parse_env_var!(config, max_ws_message_size, "CHASER_MAX_WS_MESSAGE_SIZE", usize);
+        parse_env_var!(config, max_inflight_cdp_commands, "CHASER_MAX_INFLIGHT_CDP_COMMANDS", usize);
+        parse_env_var!(config, max_concurrent_navigations, "CHASER_MAX_CONCURRENT_NAVIGATIONS", usize);
+        parse_env_var!(config, console_buffer_size, "CHASER_CONSOLE_BUFFER_SIZE", usize);
+        parse_env_var!(config, max_redirect_hops, "CHASER_MAX_REDIRECT_HOPS", usize);
+        parse_env_var!(config, browser_health_check_retries, "CHASER_BROWSER_HEALTH_CHECK_RETRIES", u32);
+        parse_env_var!(config, browser_health_check_timeout, "CHASER_BROWSER_HEALTH_CHECK_TIMEOUT", u64);
+        parse_env_var!(config, cdp_probe, "CHASER_CDP_PROBE", CdpProbeMode);
+        parse_env_var!(config, cdp_probe_timeout, "CHASER_CDP_PROBE_TIMEOUT", u64);
+        parse_env_var!(config, cdp_ping_interval, "CHASER_CDP_PING_INTERVAL", u64);
+        parse_env_var!(config, cdp_pong_timeout, "CHASER_CDP_PONG_TIMEOUT", u64);
+        parse_env_var!(config, cdp_pool_acquire_timeout, "CHASER_CDP_POOL_ACQUIRE_TIMEOUT", u64);
+        let viewport = &mut config.default_viewport;
+        parse_env_var!(viewport, width, "CHASER_VIEWPORT_WIDTH", u32);
+        parse_env_var!(viewport, height, "CHASER_VIEWPORT_HEIGHT", u32);
+        parse_env_var!(viewport, device_scale_factor, "CHASER_DEVICE_SCALE_FACTOR", f64);
+        parse_env_var!(config, screenshot_jpeg_quality, "CHASER_SCREENSHOT_JPEG_QUALITY", u8);
+        parse_env_var!(config, screenshot_webp_quality, "CHASER_SCREENSHOT_WEBP_QUALITY", u8);
+        parse_env_var!(opt config, output_dir, "CHASER_OUTPUT_DIR");
+        if let Ok(value) = env::var("CHASER_URL_ALLOWLIST") {
+            config.url_allowlist = split_patterns(&value);
+        }
+        if let Ok(value) = env::var("CHASER_URL_DENYLIST") {
+            config.url_denylist = split_patterns(&value);
+        }
+        parse_env_var!(config, block_private_networks, "CHASER_BLOCK_PRIVATE_NETWORKS", bool);
+        parse_env_var!(opt config, admin_token, "CHASER_ADMIN_TOKEN");
+        parse_env_var!(config, rate_limit_per_second, "CHASER_RATE_LIMIT_PER_SECOND", f64);
+        parse_env_var!(config, rate_limit_burst, "CHASER_RATE_LIMIT_BURST", u32);
         parse_env_var!(config, stealth_enabled, "CHASER_STEALTH", bool);
         parse_env_var!(config, log_level, "CHASER_LOG_LEVEL");
 
+        config.validate()?;
         Ok(config)
     }
 
@@ -106,6 +479,67 @@ impl Config {
         let config: Config = toml::from_str(&content)
             .map_err(|e| Error::configuration(format!("Failed to parse config: {}", e)))?;
 
+        config.validate()?;
         Ok(config)
     }
+
+    /// Reject values that deserialize but are out of range
+    fn validate(&self) -> Result<()> {
+        use crate::session::screenshot::validate_quality;
+
+        validate_quality("screenshot_jpeg_quality", self.screenshot_jpeg_quality as i64)?;
+        validate_quality("screenshot_webp_quality", self.screenshot_webp_quality as i64)?;
+        if !self.rate_limit_per_second.is_finite() || self.rate_limit_per_second < 0.0 {
+            return Err(Error::configuration(format!(
+                "rate_limit_per_second must be a non-negative number, got {}",
+                self.rate_limit_per_second
+            )));
+        }
+        if self.cdp_pong_timeout == 0 {
+            return Err(Error::configuration("cdp_pong_timeout must be at least 1 ms"));
+        }
+        if self.rate_limit_per_second > 0.0 && self.rate_limit_burst == 0 {
+            return Err(Error::configuration("rate_limit_burst must be at least 1 when rate limiting is enabled"));
+        }
+        Ok(())
+    }
+
+    /// Default screenshot quality of each lossy format
+    pub fn screenshot_quality(&self) -> crate::session::screenshot::DefaultQuality {
+        crate::session::screenshot::DefaultQuality {
+            jpeg: self.screenshot_jpeg_quality,
+            webp: self.screenshot_webp_quality,
+        }
+    }
+
+    /// Default flags for launched browsers, container flags first when enabled
+    pub fn browser_launch_args(&self) -> Vec<String> {
+        let container = if self.container_mode { crate::session::launcher::CONTAINER_LAUNCH_ARGS } else { &[] };
+        container.iter().map(|arg| arg.to_string()).chain(self.launch_args.iter().cloned()).collect()
+    }
+
+    /// Navigation URL policy
+    pub fn url_policy(&self) -> crate::session::url_policy::UrlPolicy {
+        crate::session::url_policy::UrlPolicy {
+            allow: self.url_allowlist.clone(),
+            deny: self.url_denylist.clone(),
+            block_private_networks: self.block_private_networks,
+        }
+    }
+
+    /// Per-client rate limiter, if rate limiting is enabled
+    pub fn rate_limiter(&self) -> Option<crate::services::common::rate_limit::RateLimiter> {
+        (self.rate_limit_per_second > 0.0).then(|| {
+            crate::services::common::rate_limit::RateLimiter::new(self.rate_limit_per_second, self.rate_limit_burst)
+        })
+    }
+}
+
+/// Split a comma or whitespace separated pattern list
+fn split_patterns(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect()
 }
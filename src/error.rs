@@ -65,6 +65,22 @@ pub enum Error {
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    /// A result or request exceeded a configured limit
+    #[error("Resource exhausted: {0}")]
+    ResourceExhausted(String),
+
+    /// A browser or other dependency is not ready to serve requests
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+
+    /// The request is not allowed by server policy
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// The client cancelled the request before the operation finished
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -121,6 +137,26 @@ impl Error {
         Error::Configuration(msg.into())
     }
 
+    /// Create a new resource exhausted error
+    pub fn resource_exhausted<S: Into<String>>(msg: S) -> Self {
+        Error::ResourceExhausted(msg.into())
+    }
+
+    /// Create a new unavailable error
+    pub fn unavailable<S: Into<String>>(msg: S) -> Self {
+        Error::Unavailable(msg.into())
+    }
+
+    /// Create a new permission denied error
+    pub fn permission_denied<S: Into<String>>(msg: S) -> Self {
+        Error::PermissionDenied(msg.into())
+    }
+
+    /// Create a new cancelled error
+    pub fn cancelled<S: Into<String>>(msg: S) -> Self {
+        Error::Cancelled(msg.into())
+    }
+
     /// Create a new internal error
     pub fn internal<S: Into<String>>(msg: S) -> Self {
         Error::Internal(msg.into())
@@ -135,6 +171,10 @@ impl Error {
 /// **NotFound**: Session, browser, page, or element not found
 /// **InvalidArgument**: Configuration errors, invalid parameters
 /// **DeadlineExceeded**: Operation timeouts
+/// **ResourceExhausted**: Results over a configured size limit
+/// **Unavailable**: Browsers that failed their health check
+/// **PermissionDenied**: Requests rejected by server policy
+/// **Cancelled**: Operations stopped because the client went away
 /// **Aborted**: Navigation or script execution failures
 /// **Internal**: All other errors including I/O, network, CDP, WebSocket errors
 impl From<Error> for tonic::Status {
@@ -152,6 +192,18 @@ impl From<Error> for tonic::Status {
             // Timeout errors
             Error::Timeout(_) => tonic::Status::deadline_exceeded(err.to_string()),
 
+            // Limit errors
+            Error::ResourceExhausted(_) => tonic::Status::resource_exhausted(err.to_string()),
+
+            // Retryable availability errors
+            Error::Unavailable(_) => tonic::Status::unavailable(err.to_string()),
+
+            // Policy errors
+            Error::PermissionDenied(_) => tonic::Status::permission_denied(err.to_string()),
+
+            // Client cancellation
+            Error::Cancelled(_) => tonic::Status::cancelled(err.to_string()),
+
             // Operation aborted errors
             Error::NavigationFailed(_) | Error::ScriptExecutionFailed(_) => {
                 tonic::Status::aborted(err.to_string())
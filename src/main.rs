@@ -21,13 +21,13 @@
 //! - `CHASER_CDP_ENDPOINT`: CDP WebSocket 端点（默认: ws://localhost:9222）
 
 use chaser_oxide::{
-    config::Config,
-    cdp::browser::CdpBrowserImpl,
-    cdp::mock::MockCdpClient,
-    session::{SessionManagerImpl, SessionManager},
+    config::{CdpProbeMode, Config},
+    cdp::{browser::CdpBrowserImpl, CdpPoolConfig, KeepAliveConfig},
+    session::{BrowserHealthCheck, SessionManagerImpl, SessionManager},
     services::{
         BrowserServiceGrpc, PageServiceGrpc, ElementGrpcService,
         EventGrpcService, EventDispatcher, ProfileServiceImpl,
+        common::{rate_limit::RateLimitLayer, request_id::RequestIdLayer},
         profile::{ProfileManagerImpl, ProfileServiceGrpc},
     },
     stealth::{
@@ -44,7 +44,9 @@ use chaser_oxide::chaser_oxide::v1::{
     event_service_server::EventServiceServer,
     profile_service_server::ProfileServiceServer as ProfileServer,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tonic::transport::Server;
 use tracing::{error, info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
@@ -73,19 +75,72 @@ fn init_tracing() {
         .expect("setting default subscriber failed");
 }
 
+/// CDP endpoint browsers are created on
+fn cdp_endpoint() -> String {
+    std::env::var("CHASER_CDP_ENDPOINT").unwrap_or_else(|_| "ws://localhost:9222".to_string())
+}
+
+/// Probe the CDP endpoint so a misconfigured endpoint shows up at startup
+///
+/// Logs the connected Chrome version. A failed probe is logged, or fails
+/// startup when the probe is required.
+async fn probe_cdp_endpoint(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    if config.cdp_probe == CdpProbeMode::Off {
+        return Ok(());
+    }
+
+    let endpoint = cdp_endpoint();
+    let browser = CdpBrowserImpl::new(endpoint.clone());
+    match browser.probe(Duration::from_millis(config.cdp_probe_timeout)).await {
+        Ok(version) => {
+            info!("Connected to {} at {} (protocol {})", version.product, endpoint, version.protocol_version);
+            Ok(())
+        }
+        Err(e) if config.cdp_probe == CdpProbeMode::Required => {
+            error!("CDP endpoint {} is unreachable: {}", endpoint, e);
+            Err(e.into())
+        }
+        Err(e) => {
+            warn!("CDP endpoint {} is unreachable, starting anyway: {}", endpoint, e);
+            Ok(())
+        }
+    }
+}
+
 /// Initialize all service dependencies
-fn init_services(_config: &Config) -> ServiceDependencies {
+fn init_services(config: &Config) -> ServiceDependencies {
     // Create CDP browser factory
-    let cdp_endpoint = std::env::var("CHASER_CDP_ENDPOINT")
-        .unwrap_or_else(|_| "ws://localhost:9222".to_string());
+    let cdp_endpoint = cdp_endpoint();
+    let pool_config = CdpPoolConfig {
+        acquire_timeout: Duration::from_millis(config.cdp_pool_acquire_timeout),
+        max_message_size: config.max_ws_message_size,
+        max_inflight_cdp_commands: config.max_inflight_cdp_commands,
+        keep_alive: KeepAliveConfig {
+            ping_interval: (config.cdp_ping_interval > 0).then(|| Duration::from_millis(config.cdp_ping_interval)),
+            pong_timeout: Duration::from_millis(config.cdp_pong_timeout),
+        },
+        ..Default::default()
+    };
 
+    let factory_pool_config = pool_config.clone();
     let cdp_factory = move || {
         let endpoint = cdp_endpoint.clone();
-        Ok(Arc::new(CdpBrowserImpl::new(endpoint)) as Arc<dyn chaser_oxide::cdp::traits::CdpBrowser>)
+        Ok(Arc::new(CdpBrowserImpl::with_pool_config(endpoint, factory_pool_config.clone()))
+            as Arc<dyn chaser_oxide::cdp::traits::CdpBrowser>)
     };
 
     // Create session manager
-    let session_manager_impl = Arc::new(SessionManagerImpl::new(cdp_factory));
+    let health_check = BrowserHealthCheck {
+        retries: config.browser_health_check_retries,
+        timeout: Duration::from_millis(config.browser_health_check_timeout),
+        ..Default::default()
+    };
+    let session_manager_impl = Arc::new(
+        SessionManagerImpl::new(cdp_factory)
+            .with_cdp_pool_config(pool_config)
+            .with_browser_health_check(health_check)
+            .with_url_policy(config.url_policy()),
+    );
     let session_manager: Arc<dyn SessionManager> = session_manager_impl.clone();
     info!("Session manager initialized");
 
@@ -102,11 +157,12 @@ fn init_services(_config: &Config) -> ServiceDependencies {
     let profile_manager = Arc::new(ProfileManagerImpl::new(fingerprint_generator))
         as Arc<dyn chaser_oxide::stealth::traits::ProfileManager>;
 
-    let behavior_simulator = Arc::new(BehaviorSimulatorImpl::new(Arc::new(MockCdpClient::new())))
+    let behavior_simulator = Arc::new(BehaviorSimulatorImpl::with_session_manager(session_manager.clone()))
         as Arc<dyn chaser_oxide::stealth::traits::BehaviorSimulator>;
 
     let stealth_engine = Arc::new(StealthEngineImpl::new(script_injector, behavior_simulator))
         as Arc<dyn chaser_oxide::stealth::traits::StealthEngine>;
+    session_manager_impl.attach_stealth_engine(&stealth_engine);
 
     ServiceDependencies {
         session_manager_impl,
@@ -127,10 +183,27 @@ type GrpcServices = (
 );
 
 /// Create all gRPC service instances
-fn create_grpc_services(deps: &ServiceDependencies) -> GrpcServices {
-    let browser_service = BrowserServiceGrpc::new(deps.session_manager_impl.clone());
-    let page_service = PageServiceGrpc::new(deps.session_manager_impl.clone());
-    let element_service = ElementGrpcService::new(deps.session_manager.clone());
+fn create_grpc_services(config: &Config, deps: &ServiceDependencies) -> GrpcServices {
+    let browser_service = BrowserServiceGrpc::new(deps.session_manager_impl.clone())
+        .with_stealth_engine(deps.stealth_engine.clone())
+        .with_admin_token(config.admin_token.clone())
+        .with_launch_args(config.browser_launch_args())
+        .with_max_concurrent_navigations(config.max_concurrent_navigations)
+        .with_console_buffer_size(config.console_buffer_size)
+        .with_max_redirect_hops(config.max_redirect_hops);
+    let page_service = PageServiceGrpc::new(deps.session_manager_impl.clone())
+        .with_default_navigation_timeout(config.default_navigation_timeout)
+        .with_default_viewport(config.default_viewport)
+        .with_max_evaluate_result_bytes(config.max_evaluate_result_bytes)
+        .with_output_dir(config.output_dir.clone().map(PathBuf::from))
+        .with_screenshot_quality(config.screenshot_quality())
+        .with_url_policy(config.url_policy())
+        .with_stealth_engine(deps.stealth_engine.clone());
+    for store in page_service.page_state() {
+        deps.session_manager_impl.attach_page_state(store);
+    }
+    let element_service = ElementGrpcService::new(deps.session_manager.clone())
+        .with_max_result_bytes(config.max_evaluate_result_bytes);
     let event_service = EventGrpcService::new(deps.event_dispatcher.clone());
 
     let profile_service = ProfileServiceGrpc::new(Arc::new(ProfileServiceImpl::new(
@@ -200,12 +273,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::from_env()?;
     info!("Configuration loaded: host={}, port={}", config.host, config.port);
 
+    // Check the CDP endpoint before accepting requests
+    probe_cdp_endpoint(&config).await?;
+
     // Initialize all service dependencies
     let deps = init_services(&config);
 
     // Create gRPC services
     let (browser_service, page_service, element_service, event_service, profile_service) =
-        create_grpc_services(&deps);
+        create_grpc_services(&config, &deps);
 
     info!("gRPC services initialized");
 
@@ -227,6 +303,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Start gRPC server
     let server = Server::builder()
+        .layer(RequestIdLayer)
+        .layer(RateLimitLayer::new(config.rate_limiter()))
         .add_service(browser_service)
         .add_service(page_service)
         .add_service(element_service)
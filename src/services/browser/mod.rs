@@ -13,6 +13,7 @@
 //! - `GetBrowser`: 获取浏览器详情
 //! - `CloseBrowser`: 关闭指定浏览器
 //! - `ListBrowsers`: 列出所有浏览器
+//! - `DumpState`: 以 JSON 导出服务端会话状态（需要管理令牌）
 //!
 //! ## 使用示例
 //! ```rust,no_run
@@ -29,6 +30,7 @@
 //! ```
 
 pub mod service;
+pub mod state;
 
 #[cfg(test)]
 mod tests;
@@ -6,8 +6,13 @@ use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
-use crate::session::{SessionManager, BrowserOptions};
+use crate::session::{SessionManager, BrowserOptions, ClearDataTypes, DefaultTimeouts, DialogPolicy, PageOptions};
+use crate::session::navigation_limit::DEFAULT_MAX_CONCURRENT_NAVIGATIONS;
+use crate::session::console::DEFAULT_CONSOLE_BUFFER_SIZE;
+use crate::session::page::DEFAULT_MAX_REDIRECT_HOPS;
 use crate::services::traits::{BrowserInfo, BrowserVersion, BrowserStatus, PageInfo};
+use crate::services::common::request_id;
+use crate::stealth::traits::StealthEngine;
 use crate::Error;
 
 // Import generated proto types
@@ -16,16 +21,30 @@ use crate::chaser_oxide::v1::{
     launch_response::Response as LaunchResponseEnum,
     get_pages_response::Response as GetPagesResponseEnum,
     close_response::Response as CloseResponseEnum,
+    close_all_pages_response::Response as CloseAllPagesResponseEnum,
+    clear_browser_data_response::Response as ClearBrowserDataResponseEnum,
     get_version_response::Response as GetVersionResponseEnum,
+    get_browser_version_response::Response as GetBrowserVersionResponseEnum,
     get_status_response::Response as GetStatusResponseEnum,
     connect_response::Response as ConnectResponseEnum,
+    new_tab_response::Response as NewTabResponseEnum,
+    switch_tab_response::Response as SwitchTabResponseEnum,
+    dump_state_response::Response as DumpStateResponseEnum,
     LaunchRequest, LaunchResponse,
     GetPagesRequest, GetPagesResponse, GetPagesResult,
     CloseRequest, CloseResponse,
+    CloseAllPagesRequest, CloseAllPagesResponse, CloseAllPagesResult, PageCloseError,
+    BrowserDataType, ClearBrowserDataRequest, ClearBrowserDataResponse, ClearBrowserDataResult,
     GetVersionRequest, GetVersionResponse,
+    GetBrowserVersionRequest, GetBrowserVersionResponse,
     GetStatusRequest, GetStatusResponse,
     ConnectRequest, ConnectResponse,
+    NewTabRequest, NewTabResponse,
+    SwitchTabRequest, SwitchTabResponse,
+    DumpStateRequest, DumpStateResponse,
+    TabInfo,
     BrowserOptions as ProtoBrowserOptions,
+    browser_options::DialogPolicy as ProtoDialogPolicy,
     PageInfo as ProtoPageInfo,
     BrowserInfo as ProtoBrowserInfo,
     VersionInfo,
@@ -35,10 +54,39 @@ use crate::chaser_oxide::v1::{
     ErrorCode,
 };
 
+/// Metadata key carrying the admin token
+const ADMIN_TOKEN_METADATA: &str = "x-admin-token";
+
 /// Browser service implementation
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Service<S> {
     session_manager: Arc<S>,
+    /// Stealth engine whose applied profiles and scripts are included in state dumps
+    stealth_engine: Option<Arc<dyn StealthEngine>>,
+    /// Token admin RPCs must present; admin RPCs are rejected when `None`
+    admin_token: Option<String>,
+    /// Default flags for launched browsers; request args override them
+    launch_args: Vec<String>,
+    /// Concurrent navigation limit of launched browsers, `0` for none
+    max_concurrent_navigations: usize,
+    /// Console messages kept per page of launched browsers
+    console_buffer_size: usize,
+    /// Redirects kept in navigation results of launched browsers
+    max_redirect_hops: usize,
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for Service<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Service")
+            .field("session_manager", &self.session_manager)
+            .field("stealth_engine", &self.stealth_engine.as_ref().map(|_| "Arc<dyn StealthEngine>"))
+            .field("admin_token", &self.admin_token.as_ref().map(|_| "<redacted>"))
+            .field("launch_args", &self.launch_args)
+            .field("max_concurrent_navigations", &self.max_concurrent_navigations)
+            .field("console_buffer_size", &self.console_buffer_size)
+            .field("max_redirect_hops", &self.max_redirect_hops)
+            .finish()
+    }
 }
 
 impl<S> Service<S>
@@ -47,27 +95,112 @@ where
 {
     /// Create a new browser service
     pub fn new(session_manager: Arc<S>) -> Self {
-        Self { session_manager }
+        Self {
+            session_manager,
+            stealth_engine: None,
+            admin_token: None,
+            launch_args: Vec::new(),
+            max_concurrent_navigations: DEFAULT_MAX_CONCURRENT_NAVIGATIONS,
+            console_buffer_size: DEFAULT_CONSOLE_BUFFER_SIZE,
+            max_redirect_hops: DEFAULT_MAX_REDIRECT_HOPS,
+        }
+    }
+
+    /// Include `stealth_engine`'s per-page state in state dumps
+    pub fn with_stealth_engine(mut self, stealth_engine: Arc<dyn StealthEngine>) -> Self {
+        self.stealth_engine = Some(stealth_engine);
+        self
+    }
+
+    /// Require `token` in the `x-admin-token` metadata of admin RPCs
+    ///
+    /// With `None` (the default) admin RPCs are disabled.
+    pub fn with_admin_token(mut self, token: Option<String>) -> Self {
+        self.admin_token = token.filter(|t| !t.is_empty());
+        self
+    }
+
+    /// Default flags passed to launched browsers
+    pub fn with_launch_args(mut self, launch_args: Vec<String>) -> Self {
+        self.launch_args = launch_args;
+        self
+    }
+
+    /// Navigations a launched browser runs at once; further ones are queued
+    ///
+    /// `0` disables the limit.
+    pub fn with_max_concurrent_navigations(mut self, max_concurrent_navigations: usize) -> Self {
+        self.max_concurrent_navigations = max_concurrent_navigations;
+        self
+    }
+
+    /// Console messages kept per page of launched browsers; `0` keeps none
+    pub fn with_console_buffer_size(mut self, console_buffer_size: usize) -> Self {
+        self.console_buffer_size = console_buffer_size;
+        self
+    }
+
+    /// Redirects kept in navigation results of launched browsers' pages
+    ///
+    /// Later redirects are dropped and the result is flagged as truncated.
+    pub fn with_max_redirect_hops(mut self, max_redirect_hops: usize) -> Self {
+        self.max_redirect_hops = max_redirect_hops;
+        self
+    }
+
+    /// Check the admin token of a request
+    fn authorize_admin<T>(&self, request: &Request<T>) -> Result<(), ProtoError> {
+        let denied = |message: &str| ProtoError {
+            code: ErrorCode::PermissionDenied.into(),
+            message: message.to_string(),
+            details: request_id::error_details(),
+        };
+
+        let expected = self
+            .admin_token
+            .as_deref()
+            .ok_or_else(|| denied("Admin RPCs are disabled; configure an admin token (CHASER_ADMIN_TOKEN)"))?;
+        let presented = request
+            .metadata()
+            .get(ADMIN_TOKEN_METADATA)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        // Compare every byte so the time taken does not reveal the matching prefix
+        let matches = presented.len() == expected.len()
+            && presented.bytes().zip(expected.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0;
+        if matches {
+            Ok(())
+        } else {
+            Err(denied("Invalid admin token"))
+        }
     }
 
     /// Convert Error to ProtoError
     fn error_to_proto(error: Error) -> ProtoError {
         let code = match &error {
-            Error::BrowserNotFound(_) => ErrorCode::NotFound,
+            Error::BrowserNotFound(_) | Error::PageNotFound(_) => ErrorCode::NotFound,
             Error::Configuration(_) => ErrorCode::InvalidArgument,
             Error::Timeout(_) => ErrorCode::Timeout,
+            Error::Unavailable(_) => ErrorCode::Unavailable,
             _ => ErrorCode::Internal,
         };
 
         ProtoError {
             code: code.into(),
             message: error.to_string(),
-            details: std::collections::HashMap::new(),
+            details: request_id::error_details(),
         }
     }
 
     /// Convert proto BrowserOptions to internal BrowserOptions
     pub fn proto_to_browser_options(opts: ProtoBrowserOptions) -> BrowserOptions {
+        let dialog_policy = match opts.dialog_policy() {
+            ProtoDialogPolicy::AcceptAll => DialogPolicy::AcceptAll,
+            ProtoDialogPolicy::DismissAll => DialogPolicy::DismissAll,
+            ProtoDialogPolicy::Manual | ProtoDialogPolicy::Unspecified => DialogPolicy::Manual,
+        };
+
         BrowserOptions {
             headless: opts.headless,
             window_width: opts.window_width.max(0) as u32,
@@ -75,9 +208,22 @@ where
             user_agent: if opts.user_agent.is_empty() { None } else { Some(opts.user_agent) },
             proxy: if opts.proxy_server.is_empty() { None } else { Some(opts.proxy_server) },
             args: opts.args,
+            launch_args: Vec::new(), // Filled from the service configuration at launch
+            max_concurrent_navigations: DEFAULT_MAX_CONCURRENT_NAVIGATIONS,
             executable_path: if opts.executable_path.is_empty() { None } else { Some(opts.executable_path) },
+            user_data_dir: if opts.user_data_dir.is_empty() { None } else { Some(opts.user_data_dir) },
             // Read CDP endpoint from environment variable if set
             cdp_endpoint: std::env::var("CHASER_CDP_ENDPOINT").ok(),
+            block_popups: opts.block_popups,
+            dialog_policy,
+            default_timeouts: DefaultTimeouts {
+                navigation_ms: opts.navigation_timeout.max(0) as u64,
+                command_ms: opts.command_timeout.max(0) as u64,
+                wait_ms: opts.wait_timeout.max(0) as u64,
+            },
+            console_buffer_size: DEFAULT_CONSOLE_BUFFER_SIZE,
+            max_redirect_hops: DEFAULT_MAX_REDIRECT_HOPS,
+            clear_data_on_close: opts.clear_data_on_close,
         }
     }
 
@@ -116,6 +262,90 @@ where
         }
     }
 
+    /// Open a new tab in a browser and describe it
+    async fn open_tab(&self, browser_id: &str, url: &str) -> Result<TabInfo, Error> {
+        let options = PageOptions {
+            default_url: Some(url.to_string()).filter(|u| !u.is_empty()),
+            ..Default::default()
+        };
+        let page = self.session_manager.create_page(browser_id, options).await?;
+        let browser = self.session_manager.get_browser(browser_id).await?;
+
+        Ok(TabInfo {
+            page_id: page.id().to_string(),
+            target_id: browser.target_id(page.id()).await?,
+            browser_id: browser_id.to_string(),
+        })
+    }
+
+    /// Bring a tab to the foreground and describe it
+    async fn activate_tab(&self, browser_id: &str, page_id: &str) -> Result<TabInfo, Error> {
+        let browser = self.session_manager.get_browser(browser_id).await?;
+        browser.activate_page(page_id).await?;
+
+        Ok(TabInfo {
+            page_id: page_id.to_string(),
+            target_id: browser.target_id(page_id).await?,
+            browser_id: browser_id.to_string(),
+        })
+    }
+
+    /// Close every page of a browser, collecting per-page failures
+    ///
+    /// A page that fails to close does not stop the others from closing.
+    async fn close_pages(&self, browser_id: &str) -> Result<CloseAllPagesResult, Error> {
+        let browser = self.session_manager.get_browser(browser_id).await?;
+        let mut result = CloseAllPagesResult::default();
+
+        for page in browser.get_pages().await? {
+            match self.session_manager.close_page(page.id()).await {
+                Ok(()) => result.closed_count += 1,
+                Err(e) => {
+                    error!(browser_id = %browser_id, page_id = %page.id(), error = %e, "Failed to close page");
+                    result.errors.push(PageCloseError {
+                        page_id: page.id().to_string(),
+                        error: Some(Self::error_to_proto(e)),
+                    });
+                }
+            }
+        }
+
+        info!(browser_id = %browser_id, closed = result.closed_count, failed = result.errors.len(), "Closed all pages");
+        Ok(result)
+    }
+
+    /// Data types selected by a `ClearBrowserData` request; none selects all
+    fn clear_data_types(data_types: &[i32]) -> Result<ClearDataTypes, Error> {
+        if data_types.is_empty() {
+            return Ok(ClearDataTypes::all());
+        }
+
+        let mut types = ClearDataTypes::default();
+        for &value in data_types {
+            match BrowserDataType::try_from(value) {
+                Ok(BrowserDataType::Cookies) => types.cookies = true,
+                Ok(BrowserDataType::Cache) => types.cache = true,
+                Ok(BrowserDataType::LocalStorage) => types.local_storage = true,
+                Ok(BrowserDataType::IndexedDb) => types.indexed_db = true,
+                Ok(BrowserDataType::ServiceWorkers) => types.service_workers = true,
+                Ok(BrowserDataType::Unspecified) | Err(_) => {
+                    return Err(Error::configuration(format!("Unsupported browser data type: {}", value)));
+                }
+            }
+        }
+        Ok(types)
+    }
+
+    /// Clear the selected data of a browser
+    async fn clear_data(&self, browser_id: &str, data_types: &[i32]) -> Result<ClearBrowserDataResult, Error> {
+        let types = Self::clear_data_types(data_types)?;
+        let browser = self.session_manager.get_browser(browser_id).await?;
+        let origins = browser.clear_data(types).await?;
+
+        info!(browser_id = %browser_id, origins = origins.len(), "Cleared browser data");
+        Ok(ClearBrowserDataResult { origins })
+    }
+
     /// Convert internal BrowserStatus to proto
     fn browser_status_to_proto(status: BrowserStatus) -> ProtoBrowserStatus {
         ProtoBrowserStatus {
@@ -125,6 +355,8 @@ where
             uptime_seconds: (status.uptime_ms / 1000) as i64,
             memory_usage_bytes: 0, // Will be filled by actual implementation
             active_pages: vec![],
+            navigations_queued: 0,
+            navigation_wait_ms: 0,
         }
     }
 }
@@ -136,7 +368,11 @@ where
 {
     async fn launch(&self, request: Request<LaunchRequest>) -> Result<Response<LaunchResponse>, Status> {
         let req = request.into_inner();
-        let options = Self::proto_to_browser_options(req.options.unwrap_or_default());
+        let mut options = Self::proto_to_browser_options(req.options.unwrap_or_default());
+        options.launch_args = self.launch_args.clone();
+        options.max_concurrent_navigations = self.max_concurrent_navigations;
+        options.console_buffer_size = self.console_buffer_size;
+        options.max_redirect_hops = self.max_redirect_hops;
 
         match self.session_manager.create_browser(options).await {
             Ok(browser_id) => {
@@ -145,9 +381,16 @@ where
                 let browser = self.session_manager.get_browser(&browser_id).await
                     .map_err(|e| Status::from(Error::internal(e.to_string())))?;
 
+                // Version lookup is best-effort; a launched browser is still usable without it
+                let user_agent = browser
+                    .get_version()
+                    .await
+                    .map(|version| version.user_agent)
+                    .unwrap_or_default();
+
                 let info = BrowserInfo {
                     browser_id: browser.id().to_string(),
-                    user_agent: String::new(), // Will be filled by actual implementation
+                    user_agent,
                     cdp_endpoint: String::new(), // Will be filled by actual implementation
                 };
 
@@ -232,6 +475,38 @@ where
         }
     }
 
+    async fn close_all_pages(
+        &self,
+        request: Request<CloseAllPagesRequest>,
+    ) -> Result<Response<CloseAllPagesResponse>, Status> {
+        let req = request.into_inner();
+
+        let response = match self.close_pages(&req.browser_id).await {
+            Ok(result) => CloseAllPagesResponseEnum::Result(result),
+            Err(e) => CloseAllPagesResponseEnum::Error(Self::error_to_proto(e)),
+        };
+
+        Ok(Response::new(CloseAllPagesResponse {
+            response: Some(response),
+        }))
+    }
+
+    async fn clear_browser_data(
+        &self,
+        request: Request<ClearBrowserDataRequest>,
+    ) -> Result<Response<ClearBrowserDataResponse>, Status> {
+        let req = request.into_inner();
+
+        let response = match self.clear_data(&req.browser_id, &req.data_types).await {
+            Ok(result) => ClearBrowserDataResponseEnum::Result(result),
+            Err(e) => ClearBrowserDataResponseEnum::Error(Self::error_to_proto(e)),
+        };
+
+        Ok(Response::new(ClearBrowserDataResponse {
+            response: Some(response),
+        }))
+    }
+
     async fn get_version(&self, _request: Request<GetVersionRequest>) -> Result<Response<GetVersionResponse>, Status> {
         // This is a placeholder - actual implementation would query the browser
         let version = BrowserVersion {
@@ -249,22 +524,57 @@ where
         }))
     }
 
+    async fn get_browser_version(
+        &self,
+        request: Request<GetBrowserVersionRequest>,
+    ) -> Result<Response<GetBrowserVersionResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_browser(&req.browser_id).await {
+            Ok(browser) => match browser.get_version().await {
+                Ok(version) => Ok(Response::new(GetBrowserVersionResponse {
+                    response: Some(GetBrowserVersionResponseEnum::VersionInfo(
+                        Self::browser_version_to_proto(version.into())
+                    )),
+                })),
+                Err(e) => {
+                    error!(browser_id = %req.browser_id, error = %e, "Failed to get browser version");
+                    Ok(Response::new(GetBrowserVersionResponse {
+                        response: Some(GetBrowserVersionResponseEnum::Error(
+                            Self::error_to_proto(e)
+                        )),
+                    }))
+                }
+            },
+            Err(e) => {
+                Ok(Response::new(GetBrowserVersionResponse {
+                    response: Some(GetBrowserVersionResponseEnum::Error(
+                        Self::error_to_proto(e)
+                    )),
+                }))
+            }
+        }
+    }
+
     async fn get_status(&self, request: Request<GetStatusRequest>) -> Result<Response<GetStatusResponse>, Status> {
         let req = request.into_inner();
 
         match self.session_manager.get_browser(&req.browser_id).await {
-            Ok(_browser) => {
+            Ok(browser) => {
                 // This is a placeholder - actual implementation would get real status
                 let status = BrowserStatus {
                     is_active: true,
                     page_count: 1,
                     uptime_ms: 60000,
                 };
+                let navigations = browser.navigation_stats();
 
                 Ok(Response::new(GetStatusResponse {
-                    response: Some(GetStatusResponseEnum::Status(
-                        Self::browser_status_to_proto(status)
-                    )),
+                    response: Some(GetStatusResponseEnum::Status(ProtoBrowserStatus {
+                        navigations_queued: navigations.queued as i64,
+                        navigation_wait_ms: navigations.total_wait_ms as i64,
+                        ..Self::browser_status_to_proto(status)
+                    })),
                 }))
             }
             Err(e) => {
@@ -286,6 +596,55 @@ where
             )),
         }))
     }
+
+    async fn new_tab(&self, request: Request<NewTabRequest>) -> Result<Response<NewTabResponse>, Status> {
+        let req = request.into_inner();
+
+        let response = match self.open_tab(&req.browser_id, &req.url).await {
+            Ok(tab) => {
+                info!("Opened tab {} (target {}) in browser {}", tab.page_id, tab.target_id, tab.browser_id);
+                NewTabResponseEnum::Tab(tab)
+            }
+            Err(e) => {
+                error!("Failed to open tab in browser {}: {}", req.browser_id, e);
+                NewTabResponseEnum::Error(Self::error_to_proto(e))
+            }
+        };
+
+        Ok(Response::new(NewTabResponse {
+            response: Some(response),
+        }))
+    }
+
+    async fn switch_tab(&self, request: Request<SwitchTabRequest>) -> Result<Response<SwitchTabResponse>, Status> {
+        let req = request.into_inner();
+
+        let response = match self.activate_tab(&req.browser_id, &req.page_id).await {
+            Ok(tab) => SwitchTabResponseEnum::Tab(tab),
+            Err(e) => SwitchTabResponseEnum::Error(Self::error_to_proto(e)),
+        };
+
+        Ok(Response::new(SwitchTabResponse {
+            response: Some(response),
+        }))
+    }
+
+    async fn dump_state(&self, request: Request<DumpStateRequest>) -> Result<Response<DumpStateResponse>, Status> {
+        let response = match self.authorize_admin(&request) {
+            Ok(()) => {
+                let state = super::state::dump_state(self.session_manager.as_ref(), self.stealth_engine.as_deref()).await;
+                DumpStateResponseEnum::StateJson(state.to_string())
+            }
+            Err(e) => {
+                error!("Rejected DumpState request: {}", e.message);
+                DumpStateResponseEnum::Error(e)
+            }
+        };
+
+        Ok(Response::new(DumpStateResponse {
+            response: Some(response),
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -318,4 +677,248 @@ mod tests {
         assert_eq!(opts.user_agent, Some("test-agent".to_string()));
         assert_eq!(opts.proxy, Some("http://proxy:8080".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_get_browser_version() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager.create_browser(BrowserOptions::default()).await.unwrap();
+        let service = Service::new(session_manager);
+
+        let response = service
+            .get_browser_version(Request::new(GetBrowserVersionRequest { browser_id }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match response.response {
+            Some(GetBrowserVersionResponseEnum::VersionInfo(info)) => {
+                assert_eq!(info.protocol_version, "1.3");
+                assert_eq!(info.product, "Chrome/120.0.0.0");
+                assert_eq!(info.revision, "@mock-revision");
+                assert_eq!(info.user_agent, "Mock Chrome/120.0.0.0");
+                assert_eq!(info.javascript_version, "12.0.0.0");
+            }
+            other => panic!("Expected version info, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_tab_and_switch_tab() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager.create_browser(BrowserOptions::default()).await.unwrap();
+        let service = Service::new(Arc::clone(&session_manager));
+
+        let mut tabs = Vec::new();
+        for _ in 0..2 {
+            let response = service
+                .new_tab(Request::new(NewTabRequest {
+                    browser_id: browser_id.clone(),
+                    url: String::new(),
+                }))
+                .await
+                .unwrap()
+                .into_inner();
+            match response.response {
+                Some(NewTabResponseEnum::Tab(tab)) => tabs.push(tab),
+                other => panic!("Expected tab, got {:?}", other),
+            }
+        }
+
+        assert_ne!(tabs[0].page_id, tabs[1].page_id);
+        assert_ne!(tabs[0].target_id, tabs[1].target_id);
+        for tab in &tabs {
+            assert!(session_manager.get_page(&tab.page_id).await.is_ok());
+        }
+
+        let response = service
+            .switch_tab(Request::new(SwitchTabRequest {
+                browser_id: browser_id.clone(),
+                page_id: tabs[0].page_id.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(response.response, Some(SwitchTabResponseEnum::Tab(tab)) if tab.target_id == tabs[0].target_id));
+
+        let response = service
+            .switch_tab(Request::new(SwitchTabRequest {
+                browser_id,
+                page_id: "missing".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(response.response, Some(SwitchTabResponseEnum::Error(e)) if e.code == ErrorCode::NotFound as i32));
+    }
+
+    #[tokio::test]
+    async fn test_get_browser_version_unknown_browser() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let service = Service::new(session_manager);
+
+        let response = service
+            .get_browser_version(Request::new(GetBrowserVersionRequest {
+                browser_id: "missing".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match response.response {
+            Some(GetBrowserVersionResponseEnum::Error(err)) => {
+                assert_eq!(err.code, ErrorCode::NotFound as i32);
+            }
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_close_all_pages_keeps_browser_running() {
+        use crate::session::mock::{MockBrowser, MockPage};
+        use crate::session::traits::PageContext;
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser = Arc::new(MockBrowser::new(BrowserOptions::default()));
+        let browser_id = session_manager.register_browser(browser.clone()).await;
+        let mut page_ids = Vec::new();
+        for _ in 0..3 {
+            let page = session_manager.create_page(&browser_id, PageOptions::default()).await.unwrap();
+            page_ids.push(page.id().to_string());
+        }
+        // A page the session manager does not know about fails to close
+        let orphan = Arc::new(MockPage::new(browser_id.clone(), PageOptions::default()));
+        browser.add_page(orphan.clone()).await;
+        let service = Service::new(Arc::clone(&session_manager));
+
+        let response = service
+            .close_all_pages(Request::new(CloseAllPagesRequest { browser_id: browser_id.clone() }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(CloseAllPagesResponseEnum::Result(result)) = response.response else {
+            panic!("Expected result, got {:?}", response.response);
+        };
+        assert_eq!(result.closed_count, 3);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].page_id, orphan.id());
+        assert_eq!(result.errors[0].error.as_ref().unwrap().code, ErrorCode::NotFound as i32);
+        for page_id in &page_ids {
+            assert!(session_manager.get_page(page_id).await.is_err());
+        }
+        assert!(session_manager.get_browser(&browser_id).await.unwrap().is_active());
+
+        let response = service
+            .close_all_pages(Request::new(CloseAllPagesRequest { browser_id: "missing".to_string() }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(response.response, Some(CloseAllPagesResponseEnum::Error(e)) if e.code == ErrorCode::NotFound as i32));
+    }
+
+    #[tokio::test]
+    async fn test_clear_browser_data_clears_all_cookies() {
+        use crate::cdp::mock::MockCdpClient;
+        use crate::session::mock::{MockBrowser, MockPage};
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser = Arc::new(MockBrowser::new(BrowserOptions::default()));
+        let browser_id = session_manager.register_browser(browser.clone()).await;
+        let client = Arc::new(MockCdpClient::new());
+        let page = Arc::new(MockPage::with_cdp_client(browser_id.clone(), PageOptions::default(), client.clone()));
+        browser.add_page(page).await;
+        browser.visited_origins().record("https://example.com/account");
+        let service = Service::new(Arc::clone(&session_manager));
+
+        let response = service
+            .clear_browser_data(Request::new(ClearBrowserDataRequest {
+                browser_id: browser_id.clone(),
+                data_types: vec![],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ClearBrowserDataResponseEnum::Result(result)) = response.response else {
+            panic!("Expected result, got {:?}", response.response);
+        };
+        assert_eq!(result.origins, vec!["https://example.com".to_string()]);
+        let browser_client = browser.browser_client();
+        assert_eq!(browser_client.recorded_calls("Storage.clearCookies").await.len(), 1);
+        let storage_calls = browser_client.recorded_calls("Storage.clearDataForOrigin").await;
+        assert_eq!(storage_calls.len(), 1);
+        assert_eq!(storage_calls[0]["origin"], "https://example.com");
+        assert_eq!(storage_calls[0]["storageTypes"], "local_storage,indexeddb,service_workers");
+        assert_eq!(client.recorded_calls("Network.clearBrowserCache").await.len(), 1);
+
+        let response = service
+            .clear_browser_data(Request::new(ClearBrowserDataRequest {
+                browser_id,
+                data_types: vec![BrowserDataType::Unspecified as i32],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(response.response, Some(ClearBrowserDataResponseEnum::Error(e)) if e.code == ErrorCode::InvalidArgument as i32));
+    }
+
+    fn dump_request(token: Option<&str>) -> Request<DumpStateRequest> {
+        let mut request = Request::new(DumpStateRequest {});
+        if let Some(token) = token {
+            request.metadata_mut().insert(ADMIN_TOKEN_METADATA, token.parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_dump_state_lists_browsers_pages_and_scripts() {
+        use crate::stealth::{BehaviorSimulatorImpl, ScriptInjectorImpl, StealthEngineImpl};
+        use crate::stealth::traits::{BehaviorSimulator, ScriptInjector};
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager.create_browser(BrowserOptions::default()).await.unwrap();
+        let page = session_manager.create_page(&browser_id, PageOptions::default()).await.unwrap();
+
+        let injector = Arc::new(ScriptInjectorImpl::new(session_manager.clone())) as Arc<dyn ScriptInjector>;
+        let simulator = Arc::new(BehaviorSimulatorImpl::with_session_manager(session_manager.clone()))
+            as Arc<dyn BehaviorSimulator>;
+        let engine = Arc::new(StealthEngineImpl::new(injector.clone(), simulator)) as Arc<dyn StealthEngine>;
+        injector.inject_init_script(page.id(), "window.a = 1;").await.unwrap();
+
+        let service = Service::new(session_manager)
+            .with_stealth_engine(engine)
+            .with_admin_token(Some("secret".to_string()));
+        let response = service.dump_state(dump_request(Some("secret"))).await.unwrap().into_inner();
+
+        let Some(DumpStateResponseEnum::StateJson(json)) = response.response else {
+            panic!("Expected state JSON, got {:?}", response.response);
+        };
+        let state: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let browsers = state["browsers"].as_array().unwrap();
+        assert_eq!(browsers.len(), 1);
+        assert_eq!(browsers[0]["browser_id"], browser_id);
+        let pages = browsers[0]["pages"].as_array().unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0]["page_id"], page.id());
+        assert_eq!(pages[0]["url"], "about:blank");
+        assert_eq!(pages[0]["injected_scripts"], 1);
+        assert!(pages[0]["profile"].is_null());
+        assert!(pages[0]["created_at"].as_str().unwrap() <= pages[0]["last_activity"].as_str().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_dump_state_requires_admin_token() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let disabled = Service::new(Arc::clone(&session_manager));
+        let enabled = Service::new(session_manager).with_admin_token(Some("secret".to_string()));
+
+        for (service, token) in [(&disabled, Some("secret")), (&enabled, None), (&enabled, Some("guess"))] {
+            let response = service.dump_state(dump_request(token)).await.unwrap().into_inner();
+            assert!(
+                matches!(response.response, Some(DumpStateResponseEnum::Error(ref e)) if e.code == ErrorCode::PermissionDenied as i32),
+                "token {:?} should be rejected",
+                token
+            );
+        }
+    }
 }
@@ -0,0 +1,82 @@
+//! Server state dump for debugging
+//!
+//! Builds a JSON snapshot of every browser and page from the session manager
+//! and stealth engine registries. Nothing is sent to the browsers, so the dump
+//! works (and is cheap) even when a browser is unresponsive.
+
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+
+use crate::session::{PageContext, SessionManager};
+use crate::stealth::traits::StealthEngine;
+
+/// Snapshot of all browsers, their pages and the stealth state of each page
+pub async fn dump_state<S>(session_manager: &S, stealth_engine: Option<&dyn StealthEngine>) -> Value
+where
+    S: SessionManager + ?Sized,
+{
+    let mut browser_ids = session_manager.list_browsers().await.unwrap_or_default();
+    browser_ids.sort();
+
+    let mut browsers = Vec::with_capacity(browser_ids.len());
+    for browser_id in browser_ids {
+        // The browser may have been closed since it was listed
+        let Ok(browser) = session_manager.get_browser(&browser_id).await else {
+            continue;
+        };
+        let mut pages = browser.get_pages().await.unwrap_or_default();
+        pages.sort_by_key(|page| page.activity().created_at);
+
+        let mut page_states = Vec::with_capacity(pages.len());
+        for page in &pages {
+            page_states.push(page_state(page.as_ref(), stealth_engine).await);
+        }
+
+        browsers.push(json!({
+            "browser_id": browser_id,
+            "active": browser.is_active(),
+            "pages": page_states,
+        }));
+    }
+
+    json!({
+        "generated_at": timestamp(SystemTime::now()),
+        "browsers": browsers,
+    })
+}
+
+/// State of one page
+async fn page_state(page: &dyn PageContext, stealth_engine: Option<&dyn StealthEngine>) -> Value {
+    let activity = page.activity();
+
+    let (profile, injected_scripts) = match stealth_engine {
+        Some(engine) => {
+            let profile = engine.get_active_profile(page.id()).await.ok().flatten().map(|active| {
+                json!({
+                    "profile_id": active.profile_id,
+                    "features": active.features.features,
+                })
+            });
+            let scripts = engine.get_injected_scripts(page.id()).await.map(|s| s.len()).unwrap_or(0);
+            (profile, scripts)
+        }
+        None => (None, 0),
+    };
+
+    json!({
+        "page_id": page.id(),
+        "url": activity.url,
+        "title": activity.title,
+        "active": page.is_active(),
+        "created_at": timestamp(activity.created_at),
+        "last_activity": timestamp(activity.last_activity),
+        "profile": profile,
+        "injected_scripts": injected_scripts,
+    })
+}
+
+/// RFC 3339 timestamp in UTC
+fn timestamp(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339()
+}
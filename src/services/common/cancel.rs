@@ -0,0 +1,92 @@
+//! Propagating client cancellation into long operations
+//!
+//! tonic cancels a call (client disconnect, deadline, stream reset) by
+//! dropping the handler future, so the handler has no chance to clean up what
+//! it started in the browser. [`run_cancellable`] moves the operation onto its
+//! own task and hands it a [`CancelToken`] that fires when the handler is
+//! dropped; the operation selects against the token and cleans up before it
+//! ends.
+
+use std::future::Future;
+
+use tokio::sync::watch;
+use tracing::Instrument;
+
+use super::request_id;
+use crate::session::CancelToken;
+use crate::Error;
+
+/// Cancels the token when the handler future holding it is dropped
+struct CancelOnDrop(watch::Sender<bool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.send_replace(true);
+    }
+}
+
+/// Run `operation` on its own task, cancelling its token if the caller is dropped
+///
+/// The task keeps the request id and tracing span of the caller. Once the
+/// operation has finished, dropping the caller has no effect.
+pub async fn run_cancellable<T, F, Fut>(operation: F) -> Result<T, Error>
+where
+    F: FnOnce(CancelToken) -> Fut,
+    Fut: Future<Output = Result<T, Error>> + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, token) = CancelToken::new();
+    let _cancel_on_drop = CancelOnDrop(sender);
+
+    let future = operation(token);
+    let request_id = request_id::current();
+    let task = tokio::spawn(
+        async move {
+            match request_id {
+                Some(id) => request_id::scope(id, future).await,
+                None => future.await,
+            }
+        }
+        .in_current_span(),
+    );
+
+    task.await
+        .map_err(|e| Error::internal(format!("Operation task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_dropping_caller_cancels_operation() {
+        let cleaned_up = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cleaned_up);
+
+        let call = run_cancellable(move |cancel| async move {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(30)) => Ok(()),
+                _ = cancel.cancelled() => {
+                    flag.store(true, Ordering::SeqCst);
+                    Err(crate::session::cancel::cancelled_error("Sleep"))
+                }
+            }
+        });
+        // The client goes away before the operation finishes
+        assert!(tokio::time::timeout(Duration::from_millis(50), call).await.is_err());
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while !cleaned_up.load(Ordering::SeqCst) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("operation was not cancelled");
+
+        let value = run_cancellable(|cancel| async move { Ok(cancel.is_cancelled()) }).await.unwrap();
+        assert!(!value);
+    }
+}
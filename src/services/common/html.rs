@@ -0,0 +1,454 @@
+//! Reserializing returned HTML
+//!
+//! `GetContent` and `GetHTML` return the browser's markup unchanged unless the
+//! client asks for another [`HtmlFormat`]. The markup is then parsed with
+//! html5ever, the same parsing rules the browser applies, and written back:
+//!
+//! - **Minified**: whitespace runs collapse to one space, and whitespace-only
+//!   text between block-level elements is dropped.
+//! - **Pretty**: one block-level node per line, indented two spaces per
+//!   nesting level. Inline content stays on one line, minified, so no
+//!   whitespace is added between text and inline elements.
+//!
+//! Fragments are parsed in the context their root element needs: table parts
+//! such as `<tr>` inside a table, everything else inside a `<body>`. Markup
+//! whose root element cannot be kept that way (`<html>`, `<head>`, `<body>`)
+//! is returned unchanged.
+//!
+//! The content of `pre`, `textarea`, `script`, `style` and similar elements is
+//! written verbatim in both modes, since their whitespace is significant.
+
+use ego_tree::NodeRef;
+use scraper::{Html, Node};
+
+use crate::chaser_oxide::v1::HtmlFormat;
+
+/// Spaces per nesting level in pretty output
+const INDENT: &str = "  ";
+
+/// Elements without content or end tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+/// Elements whose text is not escaped when serialized
+const RAW_TEXT_ELEMENTS: &[&str] = &[
+    "script", "style", "xmp", "iframe", "noembed", "noframes", "plaintext", "noscript",
+];
+
+/// Elements whose content is never reformatted
+const VERBATIM_ELEMENTS: &[&str] = &[
+    "pre", "textarea", "listing", "script", "style", "xmp", "iframe", "noembed", "noframes", "plaintext",
+    "noscript",
+];
+
+/// Elements around which whitespace is not rendered
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "base", "blockquote", "body", "caption", "col", "colgroup", "dd", "details",
+    "dialog", "div", "dl", "dt", "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4",
+    "h5", "h6", "head", "header", "hgroup", "hr", "html", "li", "link", "main", "meta", "nav", "ol", "optgroup",
+    "option", "p", "pre", "script", "section", "select", "style", "summary", "table", "tbody", "td", "template",
+    "tfoot", "th", "thead", "title", "tr", "ul",
+];
+
+/// Reserialize `html` in the requested format
+///
+/// `fragment` parses the markup as the outer HTML of a single element, in the
+/// context that element needs; otherwise it is parsed as a whole document.
+pub fn format_html(html: &str, format: HtmlFormat, fragment: bool) -> String {
+    if matches!(format, HtmlFormat::Unspecified | HtmlFormat::Raw) {
+        return html.to_string();
+    }
+
+    let leading = leading_tag(html);
+    let context = leading.as_deref().map(fragment_context).unwrap_or_default();
+    let parsed = if fragment {
+        let (open, close): (String, String) = (
+            context.iter().map(|name| format!("<{}>", name)).collect(),
+            context.iter().rev().map(|name| format!("</{}>", name)).collect(),
+        );
+        Html::parse_fragment(&format!("{}{}{}", open, html, close))
+    } else {
+        Html::parse_document(html)
+    };
+
+    let root = if fragment {
+        // Fragments are parsed into a wrapping <html> element that is not part
+        // of the markup, below which sit the context elements added above
+        let Some(root) = parsed.tree.root().first_child() else {
+            return html.to_string();
+        };
+        let Some(root) = context.iter().try_fold(root, |node, name| child_element(node, name)) else {
+            return html.to_string();
+        };
+        // The parser drops start tags that are not allowed in the context
+        let kept = match (&leading, root.children().find(|node| node.value().is_element())) {
+            (Some(tag), Some(node)) => node.value().as_element().is_some_and(|element| element.name() == tag),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        if !kept {
+            return html.to_string();
+        }
+        root
+    } else {
+        parsed.tree.root()
+    };
+
+    let mut out = String::with_capacity(html.len());
+    if format == HtmlFormat::Pretty {
+        write_pretty_children(root, 0, &mut out);
+        out.truncate(out.trim_end().len());
+    } else {
+        write_minified_children(root, &mut out);
+    }
+    out
+}
+
+fn write_minified_children(parent: NodeRef<'_, Node>, out: &mut String) {
+    for child in parent.children() {
+        write_minified_node(child, parent, out);
+    }
+}
+
+fn write_minified_node(node: NodeRef<'_, Node>, parent: NodeRef<'_, Node>, out: &mut String) {
+    match node.value() {
+        Node::Text(text) => {
+            let mut text = collapse_whitespace(text);
+            if at_block_boundary(node.prev_sibling(), parent) {
+                text = text.trim_start().to_string();
+            }
+            if at_block_boundary(node.next_sibling(), parent) {
+                text = text.trim_end().to_string();
+            }
+            escape_text(&text, out);
+        }
+        Node::Element(element) => {
+            write_start_tag(element, out);
+            if is_void(element.name()) {
+                return;
+            }
+            if is_verbatim(element.name()) {
+                write_verbatim_children(node, out);
+            } else {
+                write_minified_children(node, out);
+            }
+            write_end_tag(element.name(), out);
+        }
+        other => write_other(other, out),
+    }
+}
+
+fn write_pretty_children(parent: NodeRef<'_, Node>, depth: usize, out: &mut String) {
+    // Runs of inline nodes are written minified on a single line
+    let mut inline = String::new();
+    for child in parent.children() {
+        if is_inline(child) {
+            write_minified_node(child, parent, &mut inline);
+            continue;
+        }
+        write_pretty_line(&inline, depth, out);
+        inline.clear();
+
+        match child.value() {
+            Node::Element(element) => {
+                let name = element.name();
+                write_indent(depth, out);
+                write_start_tag(element, out);
+                if is_void(name) {
+                    out.push('\n');
+                    continue;
+                }
+
+                if is_verbatim(name) {
+                    write_verbatim_children(child, out);
+                } else if !has_block_descendant(child) {
+                    // Inline content stays on the element's line: <p>Some <b>text</b></p>
+                    let mut content = String::new();
+                    write_minified_children(child, &mut content);
+                    out.push_str(content.trim());
+                } else {
+                    out.push('\n');
+                    write_pretty_children(child, depth + 1, out);
+                    write_indent(depth, out);
+                }
+                write_end_tag(name, out);
+                out.push('\n');
+            }
+            other => {
+                write_indent(depth, out);
+                write_other(other, out);
+                out.push('\n');
+            }
+        }
+    }
+    write_pretty_line(&inline, depth, out);
+}
+
+fn write_pretty_line(content: &str, depth: usize, out: &mut String) {
+    let content = content.trim();
+    if !content.is_empty() {
+        write_indent(depth, out);
+        out.push_str(content);
+        out.push('\n');
+    }
+}
+
+/// Serialize the children of `parent` without changing their whitespace
+fn write_verbatim_children(parent: NodeRef<'_, Node>, out: &mut String) {
+    for child in parent.children() {
+        match child.value() {
+            Node::Text(text) => match parent.value() {
+                Node::Element(element) if RAW_TEXT_ELEMENTS.contains(&element.name()) => out.push_str(text),
+                _ => escape_text(text, out),
+            },
+            Node::Element(element) => {
+                write_start_tag(element, out);
+                if !is_void(element.name()) {
+                    write_verbatim_children(child, out);
+                    write_end_tag(element.name(), out);
+                }
+            }
+            _ => write_other(child.value(), out),
+        }
+    }
+}
+
+/// Whether `node` belongs on a line with its inline siblings in pretty output
+fn is_inline(node: NodeRef<'_, Node>) -> bool {
+    match node.value() {
+        Node::Text(_) | Node::Comment(_) => true,
+        Node::Element(element) => !BLOCK_ELEMENTS.contains(&element.name()) && !has_block_descendant(node),
+        _ => false,
+    }
+}
+
+fn has_block_descendant(node: NodeRef<'_, Node>) -> bool {
+    node.descendants()
+        .skip(1)
+        .any(|node| node.value().as_element().is_some_and(|element| BLOCK_ELEMENTS.contains(&element.name())))
+}
+
+/// Lowercased name of the element `html` starts with, if it starts with one
+fn leading_tag(html: &str) -> Option<String> {
+    let rest = html.trim_start().strip_prefix('<')?;
+    let name: String = rest.chars().take_while(char::is_ascii_alphanumeric).collect();
+    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(name.to_ascii_lowercase())
+}
+
+/// Elements a fragment starting with `tag` has to be parsed inside
+fn fragment_context(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "caption" | "colgroup" | "thead" | "tbody" | "tfoot" => &["table"],
+        "col" => &["table", "colgroup"],
+        "tr" => &["table", "tbody"],
+        "td" | "th" => &["table", "tbody", "tr"],
+        _ => &[],
+    }
+}
+
+fn child_element<'a>(parent: NodeRef<'a, Node>, name: &str) -> Option<NodeRef<'a, Node>> {
+    parent.children().find(|node| node.value().as_element().is_some_and(|element| element.name() == name))
+}
+
+/// Whether whitespace next to a text node is not rendered
+///
+/// `sibling` is the node on that side; without one, the parent's edge counts
+/// when the parent is a block.
+fn at_block_boundary(sibling: Option<NodeRef<'_, Node>>, parent: NodeRef<'_, Node>) -> bool {
+    let node = sibling.unwrap_or(parent);
+    match node.value() {
+        Node::Element(element) => BLOCK_ELEMENTS.contains(&element.name()),
+        Node::Document | Node::Fragment => true,
+        _ => sibling.is_none(),
+    }
+}
+
+fn write_start_tag(element: &scraper::node::Element, out: &mut String) {
+    out.push('<');
+    out.push_str(element.name());
+    for (name, value) in &element.attrs {
+        out.push(' ');
+        if let Some(prefix) = &name.prefix {
+            out.push_str(prefix);
+            out.push(':');
+        }
+        out.push_str(&name.local);
+        out.push_str("=\"");
+        escape_attribute(value, out);
+        out.push('"');
+    }
+    out.push('>');
+}
+
+fn write_end_tag(name: &str, out: &mut String) {
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+/// Doctypes, comments and processing instructions
+fn write_other(node: &Node, out: &mut String) {
+    match node {
+        Node::Doctype(doctype) => {
+            out.push_str("<!DOCTYPE ");
+            out.push_str(doctype.name());
+            out.push('>');
+        }
+        Node::Comment(comment) => {
+            out.push_str("<!--");
+            out.push_str(comment);
+            out.push_str("-->");
+        }
+        Node::ProcessingInstruction(instruction) => {
+            out.push_str("<?");
+            out.push_str(&instruction.target);
+            out.push(' ');
+            out.push_str(&instruction.data);
+            out.push('>');
+        }
+        _ => {}
+    }
+}
+
+fn write_indent(depth: usize, out: &mut String) {
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+fn is_void(name: &str) -> bool {
+    VOID_ELEMENTS.contains(&name)
+}
+
+fn is_verbatim(name: &str) -> bool {
+    VERBATIM_ELEMENTS.contains(&name)
+}
+
+/// Replace every run of HTML whitespace with a single space
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+    collapsed
+}
+
+fn escape_text(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn escape_attribute(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<!DOCTYPE html>\n<html>\n  <head>\n    <title>  Shop  </title>\n  </head>\n  <body>\n    <ul class=\"items\">\n      <li>One <b>bold</b>\n        item</li>\n      <li>Two</li>\n    </ul>\n    <pre>  keep\n  this</pre>\n  </body>\n</html>";
+
+    #[test]
+    fn test_pretty_indents_nested_elements() {
+        let pretty = format_html(PAGE, HtmlFormat::Pretty, false);
+
+        assert_eq!(
+            pretty,
+            [
+                "<!DOCTYPE html>",
+                "<html>",
+                "  <head>",
+                "    <title>Shop</title>",
+                "  </head>",
+                "  <body>",
+                "    <ul class=\"items\">",
+                "      <li>One <b>bold</b> item</li>",
+                "      <li>Two</li>",
+                "    </ul>",
+                "    <pre>  keep\n  this</pre>",
+                "  </body>",
+                "</html>",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_minified_removes_insignificant_whitespace() {
+        let minified = format_html(PAGE, HtmlFormat::Minified, false);
+
+        assert_eq!(
+            minified,
+            "<!DOCTYPE html><html><head><title>Shop</title></head><body><ul class=\"items\">\
+             <li>One <b>bold</b> item</li><li>Two</li></ul><pre>  keep\n  this</pre></body></html>"
+        );
+
+        // Fragments are not wrapped in a document; raw output is untouched
+        let fragment = "<p a=\"1\"  b='x\"y'>\n  Hi &amp; bye\n</p>";
+        assert_eq!(format_html(fragment, HtmlFormat::Minified, true), "<p a=\"1\" b=\"x&quot;y\">Hi &amp; bye</p>");
+        assert_eq!(format_html(fragment, HtmlFormat::Raw, true), fragment);
+    }
+    #[test]
+    fn test_pretty_keeps_inline_content_on_one_line() {
+        let html = "<div>a<b>b</b>\n  <p>Text with <a href=\"/x\">a <i>link</i></a>.</p>\n  c <span>d</span>\n</div>";
+
+        assert_eq!(
+            format_html(html, HtmlFormat::Pretty, true),
+            [
+                "<div>",
+                "  a<b>b</b>",
+                "  <p>Text with <a href=\"/x\">a <i>link</i></a>.</p>",
+                "  c <span>d</span>",
+                "</div>",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_fragment_keeps_table_parts() {
+        assert_eq!(
+            format_html("<tr>\n  <td>a</td>\n  <td>b</td>\n</tr>", HtmlFormat::Minified, true),
+            "<tr><td>a</td><td>b</td></tr>"
+        );
+        assert_eq!(format_html("<td> a </td>", HtmlFormat::Minified, true), "<td>a</td>");
+        assert_eq!(
+            format_html("<thead><tr><th>h</th></tr></thead>", HtmlFormat::Pretty, true),
+            "<thead>\n  <tr>\n    <th>h</th>\n  </tr>\n</thead>"
+        );
+        assert_eq!(format_html("<col span=\"2\">", HtmlFormat::Minified, true), "<col span=\"2\">");
+        assert_eq!(format_html("<caption> c </caption>", HtmlFormat::Minified, true), "<caption>c</caption>");
+
+        // Elements that cannot be parsed as a fragment come back unchanged
+        for html in ["<html><head></head><body>x</body></html>", "<head>\n<title>t</title></head>", "<body> x </body>"] {
+            assert_eq!(format_html(html, HtmlFormat::Minified, true), html);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Result size limits for gRPC services
+
+use crate::Error;
+
+/// Reject a `what` result of `size` bytes when it exceeds `limit`
+pub fn check_result_size(what: &str, size: usize, limit: usize) -> Result<(), Error> {
+    if size > limit {
+        return Err(Error::resource_exhausted(format!(
+            "{} result is {} bytes, exceeding the {} byte limit",
+            what, size, limit
+        )));
+    }
+
+    Ok(())
+}
@@ -3,8 +3,14 @@
 //! This module provides shared functions, macros, and utilities for all gRPC services
 //! to reduce code duplication and ensure consistent error handling.
 
+pub mod cancel;
 pub mod error;
+pub mod html;
+pub mod limits;
+pub mod output;
 pub mod proto;
+pub mod rate_limit;
+pub mod request_id;
 
 pub use error::to_response;
 pub use proto::ServiceResult;
@@ -0,0 +1,90 @@
+//! Server-side output files for gRPC services
+//!
+//! Screenshots and PDFs can be written to disk instead of being returned over
+//! gRPC. Paths are confined to the configured output directory.
+
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+use crate::Error;
+
+/// Resolve `requested` to a file path inside `output_dir`
+///
+/// Relative paths are taken relative to `output_dir`; absolute paths must
+/// already point inside it. `..` components, symlinked directories leading
+/// outside the directory and a symlink as the file itself are rejected.
+/// Missing parent directories are created once the existing part of the path
+/// is known to stay inside.
+///
+/// # Errors
+/// Returns `Configuration` when no output directory is configured or the path
+/// escapes it.
+pub fn resolve_output_path(output_dir: Option<&Path>, requested: &str) -> Result<PathBuf, Error> {
+    let output_dir = output_dir.ok_or_else(|| {
+        Error::configuration("Saving output to a path is disabled; configure an output directory (CHASER_OUTPUT_DIR)")
+    })?;
+    let root = output_dir.canonicalize().map_err(|e| {
+        Error::configuration(format!("Output directory {} is not accessible: {}", output_dir.display(), e))
+    })?;
+
+    let requested_path = Path::new(requested);
+    let relative = if requested_path.is_absolute() {
+        requested_path
+            .strip_prefix(&root)
+            .or_else(|_| requested_path.strip_prefix(output_dir))
+            .map_err(|_| outside_output_dir(requested))?
+    } else {
+        requested_path
+    };
+    if relative.as_os_str().is_empty()
+        || relative.components().any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(outside_output_dir(requested));
+    }
+
+    let path = root.join(relative);
+    let parent = path.parent().ok_or_else(|| outside_output_dir(requested))?;
+
+    // A symlinked directory inside the output dir could point elsewhere, so
+    // check the deepest existing ancestor before creating anything below it
+    let existing = parent
+        .ancestors()
+        .find(|dir| std::fs::symlink_metadata(dir).is_ok())
+        .unwrap_or(&root);
+    if !existing.canonicalize()?.starts_with(&root) {
+        return Err(outside_output_dir(requested));
+    }
+    std::fs::create_dir_all(parent)?;
+    if !parent.canonicalize()?.starts_with(&root) {
+        return Err(outside_output_dir(requested));
+    }
+
+    // Writing through a symlink would land wherever it points
+    if std::fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        return Err(outside_output_dir(requested));
+    }
+
+    Ok(path)
+}
+
+/// Write `data` to `requested` inside `output_dir`, returning the absolute path
+///
+/// An existing file is replaced rather than written through, and the new file
+/// is created exclusively, so a symlink planted after the path was resolved is
+/// never followed.
+pub fn write_output_file(output_dir: Option<&Path>, requested: &str, data: &[u8]) -> Result<PathBuf, Error> {
+    let path = resolve_output_path(output_dir, requested)?;
+    match std::fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(&path)?;
+    file.write_all(data)?;
+
+    Ok(path)
+}
+
+fn outside_output_dir(requested: &str) -> Error {
+    Error::configuration(format!("Output path {} is outside the output directory", requested))
+}
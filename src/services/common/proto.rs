@@ -2,6 +2,8 @@
 //!
 //! Provides macros and types for consistent protobuf message handling.
 
+use crate::cdp::keys::Modifiers;
+use crate::chaser_oxide::v1::KeyModifier;
 use crate::Error;
 
 /// Type alias for service results
@@ -27,6 +29,22 @@ macro_rules! proto_error {
     }};
 }
 
+/// Combine proto `KeyModifier` values into the modifiers held during a key press
+///
+/// The browser runs on Linux, so `ControlOrMeta` means Control.
+pub fn key_modifiers(modifiers: &[i32]) -> Modifiers {
+    modifiers.iter().fold(Modifiers::NONE, |held, &modifier| {
+        let modifier = match KeyModifier::try_from(modifier) {
+            Ok(KeyModifier::Alt) => Modifiers::ALT,
+            Ok(KeyModifier::Control) | Ok(KeyModifier::ControlOrMeta) => Modifiers::CONTROL,
+            Ok(KeyModifier::Meta) => Modifiers::META,
+            Ok(KeyModifier::Shift) => Modifiers::SHIFT,
+            _ => Modifiers::NONE,
+        };
+        held.union(modifier)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,258 @@
+//! Per-client request rate limiting
+//!
+//! [`RateLimitLayer`] gives every client a token bucket that refills at a
+//! fixed rate up to a burst size; a call is rejected with `ResourceExhausted`
+//! when the client's bucket is empty. Clients are identified by peer IP
+//! address: no metadata is authenticated, so a client-chosen value would let
+//! a client pick a fresh bucket on every call. `GetVersion` is never limited so
+//! probes keep working under load.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use http::{Request, Response};
+use tonic::body::BoxBody;
+use tonic::transport::server::TcpConnectInfo;
+use tower::{Layer, Service};
+
+/// Methods that are never rate limited
+const EXEMPT_PATHS: &[&str] = &["/chaser.oxide.v1.BrowserService/GetVersion"];
+
+/// Number of tracked clients above which idle buckets are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Token bucket of one client
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token-bucket rate limiter keyed by client
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Tokens added per second
+    rate: f64,
+    /// Bucket capacity
+    burst: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Allow `rate` requests per second per client, with bursts of up to `burst`
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate,
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `client`, or return how long until one is available
+    pub fn try_acquire(&self, client: &str) -> Result<(), Duration> {
+        self.try_acquire_at(client, Instant::now())
+    }
+
+    fn try_acquire_at(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= PRUNE_THRESHOLD && !buckets.contains_key(client) {
+            // A bucket that has refilled completely behaves like a new one
+            buckets.retain(|_, bucket| self.refilled(bucket, now) < self.burst);
+        }
+
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+
+    /// Tokens in `bucket` at `now`
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.rate).min(self.burst)
+    }
+}
+
+/// Identity used to rate limit a request
+fn client_key<B>(request: &Request<B>) -> String {
+    match request.extensions().get::<TcpConnectInfo>().and_then(TcpConnectInfo::remote_addr) {
+        Some(addr) => format!("peer:{}", addr.ip()),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Tower layer applying a [`RateLimiter`] to every gRPC call
+///
+/// Without a limiter the layer passes every call through.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitLayer {
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: Option<RateLimiter>) -> Self {
+        Self {
+            limiter: limiter.map(Arc::new),
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Service wrapper created by [`RateLimitLayer`]
+#[derive(Debug, Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RateLimitService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        if let Some(limiter) = &self.limiter {
+            let path = request.uri().path();
+            if !EXEMPT_PATHS.contains(&path) {
+                if let Err(retry_after) = limiter.try_acquire(&client_key(&request)) {
+                    tracing::debug!("Rate limit exceeded for {}", path);
+                    let status = tonic::Status::resource_exhausted(format!(
+                        "Rate limit exceeded; retry in {}ms",
+                        retry_after.as_millis().max(1)
+                    ));
+                    return Box::pin(async move { Ok(status.into_http()) });
+                }
+            }
+        }
+
+        Box::pin(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    /// Stand-in for the gRPC services
+    #[derive(Clone)]
+    struct OkService;
+
+    impl Service<Request<()>> for OkService {
+        type Response = Response<BoxBody>;
+        type Error = Infallible;
+        type Future = std::future::Ready<Result<Response<BoxBody>, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: Request<()>) -> Self::Future {
+            std::future::ready(Ok(Response::new(tonic::body::empty_body())))
+        }
+    }
+
+    /// Request for `path` from `peer`, carrying a different `authorization` value on every call
+    fn request(path: &str, peer: &str) -> Request<()> {
+        static CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let call = CALLS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let mut request = Request::builder()
+            .uri(path)
+            .header("authorization", format!("Bearer {}", call))
+            .body(())
+            .unwrap();
+        request.extensions_mut().insert(TcpConnectInfo {
+            local_addr: None,
+            remote_addr: Some(peer.parse().unwrap()),
+        });
+        request
+    }
+
+    fn grpc_code(response: &Response<BoxBody>) -> tonic::Code {
+        tonic::Status::from_header_map(response.headers()).map_or(tonic::Code::Ok, |status| status.code())
+    }
+
+    #[test]
+    fn test_bucket_rejects_beyond_burst_and_refills() {
+        let limiter = RateLimiter::new(2.0, 3);
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.try_acquire_at("a", start).unwrap();
+        }
+        let retry_after = limiter.try_acquire_at("a", start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+        // Other clients have their own bucket
+        limiter.try_acquire_at("b", start).unwrap();
+
+        // One token refills every 500ms
+        limiter.try_acquire_at("a", start + Duration::from_millis(500)).unwrap();
+        assert!(limiter.try_acquire_at("a", start + Duration::from_millis(600)).is_err());
+
+        // After a full refill window the whole burst is available again
+        let later = start + Duration::from_secs(10);
+        for _ in 0..3 {
+            limiter.try_acquire_at("a", later).unwrap();
+        }
+        assert!(limiter.try_acquire_at("a", later).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_layer_limits_by_peer_and_exempts_get_version() {
+        use crate::chaser_oxide::v1::{browser_service_server, page_service_server};
+
+        let mut service = RateLimitLayer::new(Some(RateLimiter::new(1.0, 2))).layer(OkService);
+        let navigate = format!("/{}/Navigate", page_service_server::SERVICE_NAME);
+        let get_version = format!("/{}/GetVersion", browser_service_server::SERVICE_NAME);
+
+        // Changing the authorization metadata does not reset the bucket
+        for _ in 0..2 {
+            let response = service.call(request(&navigate, "10.0.0.1:5000")).await.unwrap();
+            assert_eq!(grpc_code(&response), tonic::Code::Ok);
+        }
+        let response = service.call(request(&navigate, "10.0.0.1:5001")).await.unwrap();
+        assert_eq!(grpc_code(&response), tonic::Code::ResourceExhausted);
+
+        let response = service.call(request(&navigate, "10.0.0.2:5000")).await.unwrap();
+        assert_eq!(grpc_code(&response), tonic::Code::Ok);
+        let response = service.call(request(&get_version, "10.0.0.1:5000")).await.unwrap();
+        assert_eq!(grpc_code(&response), tonic::Code::Ok);
+
+        let mut unlimited = RateLimitLayer::default().layer(OkService);
+        for _ in 0..10 {
+            let response = unlimited.call(request(&navigate, "10.0.0.1:5000")).await.unwrap();
+            assert_eq!(grpc_code(&response), tonic::Code::Ok);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Request-scoped ids for correlating logs across layers
+//!
+//! [`RequestIdLayer`] runs every gRPC call inside a `grpc_request` span with a
+//! `request_id` field, taken from the `x-request-id` metadata or generated.
+//! Session and CDP calls made while handling the request log inside that span,
+//! so every line for one operation carries the same id. The id is echoed back
+//! in the `x-request-id` response metadata and added to in-band error details.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{HeaderValue, Request, Response};
+use tower::{Layer, Service};
+use tracing::Instrument;
+
+/// Metadata key carrying the request id in both directions
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Key of the request id in `Error.details`
+pub const REQUEST_ID_DETAIL: &str = "request_id";
+
+/// Longest client-supplied id that is accepted; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Id of the request being handled by the current task, if any
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Run `future` as part of the request `id`
+///
+/// Used at the gRPC boundary and for work that a handler moves onto another task.
+pub async fn scope<F: Future>(id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(id, future).await
+}
+
+/// Error details identifying the current request (empty outside a request)
+pub fn error_details() -> HashMap<String, String> {
+    current()
+        .map(|id| HashMap::from([(REQUEST_ID_DETAIL.to_string(), id)]))
+        .unwrap_or_default()
+}
+
+/// Client-supplied id, if it is short and printable
+fn client_request_id(value: &HeaderValue) -> Option<String> {
+    let id = value.to_str().ok()?;
+    let valid = !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic());
+    valid.then(|| id.to_string())
+}
+
+/// Tower layer assigning a request id to every gRPC call
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// Service wrapper created by [`RequestIdLayer`]
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestIdService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(client_request_id)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let header = HeaderValue::from_str(&id).expect("request id is a valid header value");
+        // Handlers see the effective id in the request metadata
+        request.headers_mut().insert(REQUEST_ID_HEADER, header.clone());
+
+        let span = tracing::info_span!("grpc_request", request_id = %id, method = %request.uri().path());
+        let future = {
+            let _entered = span.enter();
+            self.inner.call(request)
+        };
+
+        Box::pin(scope(
+            id,
+            async move {
+                let mut response = future.await?;
+                response.headers_mut().insert(REQUEST_ID_HEADER, header);
+                Ok(response)
+            }
+            .instrument(span),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpConnection;
+    use crate::cdp::{CdpClient, CdpClientImpl};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Stand-in for a gRPC service that calls into the CDP layer
+    #[derive(Clone)]
+    struct CdpCallingService;
+
+    impl Service<Request<()>> for CdpCallingService {
+        type Response = Response<()>;
+        type Error = crate::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Response<()>, crate::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: Request<()>) -> Self::Future {
+            Box::pin(async {
+                assert!(current().is_some());
+                let client = CdpClientImpl::new(Arc::new(MockCdpConnection::new()));
+                client.call_method("Browser.getVersion", serde_json::json!({})).await?;
+                Ok(Response::new(()))
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct FieldVisitor {
+        request_id: Option<String>,
+        message: Option<String>,
+    }
+
+    impl Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            match field.name() {
+                "request_id" => self.request_id = Some(format!("{:?}", value)),
+                "message" => self.message = Some(format!("{:?}", value)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Request id of an event's enclosing span
+    struct SpanRequestId(String);
+
+    /// Message of an event and the request id of its enclosing spans
+    type RecordedEvent = (String, Option<String>);
+
+    /// Test layer recording each event's message with the request id of its spans
+    #[derive(Clone, Default)]
+    struct RecordingLayer {
+        events: Arc<Mutex<Vec<RecordedEvent>>>,
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for RecordingLayer
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: LayerContext<'_, S>) {
+            let mut visitor = FieldVisitor::default();
+            attrs.record(&mut visitor);
+            if let (Some(request_id), Some(span)) = (visitor.request_id, ctx.span(id)) {
+                span.extensions_mut().insert(SpanRequestId(request_id));
+            }
+        }
+
+        fn on_event(&self, event: &Event<'_>, ctx: LayerContext<'_, S>) {
+            let mut visitor = FieldVisitor::default();
+            event.record(&mut visitor);
+            let request_id = ctx.event_scope(event).and_then(|scope| {
+                scope
+                    .from_root()
+                    .find_map(|span| span.extensions().get::<SpanRequestId>().map(|id| id.0.clone()))
+            });
+            self.events
+                .lock()
+                .unwrap()
+                .push((visitor.message.unwrap_or_default(), request_id));
+        }
+    }
+
+    async fn call_with(request: Request<()>, recorder: &RecordingLayer) -> Response<()> {
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let mut service = RequestIdLayer.layer(CdpCallingService);
+        service.call(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_id_from_metadata_appears_in_cdp_spans() {
+        let recorder = RecordingLayer::default();
+        let request = Request::builder()
+            .uri("/chaser_oxide.v1.BrowserService/GetVersion")
+            .header(REQUEST_ID_HEADER, "req-42")
+            .body(())
+            .unwrap();
+
+        let response = call_with(request, &recorder).await;
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-42");
+        let events = recorder.events.lock().unwrap();
+        let cdp_call = events
+            .iter()
+            .find(|(message, _)| message.contains("Calling CDP method: Browser.getVersion"))
+            .expect("CDP call was not logged");
+        assert_eq!(cdp_call.1.as_deref(), Some("req-42"));
+        assert!(events.iter().all(|(_, id)| id.as_deref() == Some("req-42")));
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated_when_missing_or_invalid() {
+        let recorder = RecordingLayer::default();
+        let request = Request::builder()
+            .uri("/chaser_oxide.v1.PageService/Navigate")
+            .header(REQUEST_ID_HEADER, "has spaces")
+            .body(())
+            .unwrap();
+
+        let response = call_with(request, &recorder).await;
+
+        let id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_string();
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+        let events = recorder.events.lock().unwrap();
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|(_, event_id)| event_id.as_deref() == Some(id.as_str())));
+    }
+
+    #[tokio::test]
+    async fn test_error_details_carry_request_id() {
+        assert!(error_details().is_empty());
+
+        let details = scope("req-7".to_string(), async { error_details() }).await;
+        assert_eq!(details.get(REQUEST_ID_DETAIL).map(String::as_str), Some("req-7"));
+
+        let proto = scope("req-7".to_string(), async {
+            crate::services::page::response::error_to_proto(crate::Error::timeout("slow"))
+        })
+        .await;
+        assert_eq!(proto.details.get(REQUEST_ID_DETAIL).map(String::as_str), Some("req-7"));
+    }
+}
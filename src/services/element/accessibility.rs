@@ -0,0 +1,228 @@
+//! Element-scoped accessibility queries
+//!
+//! Resolves an element to its DOM backend node and reads the accessibility
+//! subtree rooted at it via `Accessibility.getPartialAXTree`.
+
+use crate::cdp::traits::CdpClient;
+use crate::chaser_oxide::v1::AccessibilityNode;
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Resolve a JavaScript element query to its DOM backend node id
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `element_query` - JavaScript expression evaluating to the element (see `JsBuilder::element_query`)
+///
+/// # Errors
+/// Returns `ElementNotFound` if the expression does not evaluate to a DOM node
+pub async fn resolve_backend_node_id(client: &dyn CdpClient, element_query: &str) -> Result<i64> {
+    let result = client
+        .call_method(
+            "Runtime.evaluate",
+            serde_json::json!({
+                "expression": element_query,
+                "returnByValue": false,
+            }),
+        )
+        .await?;
+
+    if let Some(exception) = result.get("exceptionDetails") {
+        return Err(Error::script_execution_failed(
+            exception
+                .pointer("/exception/description")
+                .and_then(|d| d.as_str())
+                .unwrap_or("Element query failed")
+                .to_string(),
+        ));
+    }
+
+    let object_id = result
+        .pointer("/result/objectId")
+        .and_then(|v| v.as_str())
+        .filter(|_| result.pointer("/result/subtype").and_then(|v| v.as_str()) == Some("node"))
+        .ok_or_else(|| Error::element_not_found(element_query))?;
+
+    let described = client
+        .call_method("DOM.describeNode", serde_json::json!({ "objectId": object_id }))
+        .await;
+
+    // The remote object is only needed to describe the node
+    let _ = client
+        .call_method("Runtime.releaseObject", serde_json::json!({ "objectId": object_id }))
+        .await;
+
+    described?
+        .pointer("/node/backendNodeId")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| Error::cdp("No backendNodeId in DOM.describeNode result"))
+}
+
+/// Get the accessibility subtree rooted at a DOM node
+///
+/// The first returned node is the one for `backend_node_id`; the rest are its
+/// descendants reported by the browser, in breadth-first order.
+pub async fn partial_ax_tree(client: &dyn CdpClient, backend_node_id: i64) -> Result<Vec<AccessibilityNode>> {
+    client.enable_domain("Accessibility").await?;
+
+    let result = client
+        .call_method(
+            "Accessibility.getPartialAXTree",
+            serde_json::json!({
+                "backendNodeId": backend_node_id,
+                "fetchRelatives": true,
+            }),
+        )
+        .await?;
+
+    let nodes: Vec<AccessibilityNode> = result
+        .get("nodes")
+        .and_then(|v| v.as_array())
+        .map(|nodes| nodes.iter().map(parse_ax_node).collect())
+        .unwrap_or_default();
+
+    subtree(nodes, backend_node_id)
+}
+
+/// Keep only the node for `backend_node_id` and its descendants
+///
+/// `fetchRelatives` also returns ancestors and siblings, which are dropped here.
+fn subtree(nodes: Vec<AccessibilityNode>, backend_node_id: i64) -> Result<Vec<AccessibilityNode>> {
+    let root = nodes
+        .iter()
+        .find(|n| n.backend_node_id == backend_node_id)
+        .map(|n| n.node_id.clone())
+        .ok_or_else(|| Error::element_not_found(format!("No accessibility node for backend node {}", backend_node_id)))?;
+
+    let mut by_id: HashMap<String, AccessibilityNode> =
+        nodes.into_iter().map(|n| (n.node_id.clone(), n)).collect();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::from([root]);
+    let mut ordered = Vec::new();
+
+    while let Some(id) = queue.pop_front() {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        if let Some(node) = by_id.remove(&id) {
+            queue.extend(node.child_ids.iter().cloned());
+            ordered.push(node);
+        }
+    }
+
+    Ok(ordered)
+}
+
+/// Convert a CDP `Accessibility.AXNode` into its proto form
+fn parse_ax_node(node: &Value) -> AccessibilityNode {
+    let ax_value = |key: &str| node.get(key).and_then(|v| v.get("value")).map(value_to_string).unwrap_or_default();
+
+    let properties = node
+        .get("properties")
+        .and_then(|v| v.as_array())
+        .map(|props| {
+            props
+                .iter()
+                .filter_map(|p| {
+                    let name = p.get("name")?.as_str()?.to_string();
+                    let value = p.get("value")?.get("value").map(value_to_string).unwrap_or_default();
+                    Some((name, value))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    AccessibilityNode {
+        node_id: node.get("nodeId").map(value_to_string).unwrap_or_default(),
+        role: ax_value("role"),
+        name: ax_value("name"),
+        value: ax_value("value"),
+        ignored: node.get("ignored").and_then(|v| v.as_bool()).unwrap_or(false),
+        properties,
+        child_ids: node
+            .get("childIds")
+            .and_then(|v| v.as_array())
+            .map(|ids| ids.iter().map(value_to_string).collect())
+            .unwrap_or_default(),
+        backend_node_id: node.get("backendDOMNodeId").and_then(|v| v.as_i64()).unwrap_or(0),
+    }
+}
+
+/// Render an AX value as a plain string (strings unquoted, everything else as JSON)
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+
+    fn partial_tree() -> Value {
+        serde_json::json!({
+            "nodes": [
+                { "nodeId": "1", "ignored": false, "role": { "type": "role", "value": "RootWebArea" },
+                  "childIds": ["2"], "backendDOMNodeId": 1 },
+                { "nodeId": "2", "ignored": false, "role": { "type": "role", "value": "button" },
+                  "name": { "type": "computedString", "value": "Submit" },
+                  "properties": [
+                      { "name": "focusable", "value": { "type": "booleanOrUndefined", "value": true } },
+                      { "name": "expanded", "value": { "type": "booleanOrUndefined", "value": false } }
+                  ],
+                  "childIds": ["3"], "backendDOMNodeId": 42 },
+                { "nodeId": "3", "ignored": false, "role": { "type": "role", "value": "StaticText" },
+                  "name": { "type": "computedString", "value": "Submit" },
+                  "childIds": [], "backendDOMNodeId": 43 }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_element_accessibility_subtree() {
+        let client = MockCdpClient::new();
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "node", "objectId": "obj-1" } }),
+            )
+            .await;
+        client
+            .stub_method("DOM.describeNode", serde_json::json!({ "node": { "nodeId": 0, "backendNodeId": 42 } }))
+            .await;
+        client.stub_method("Accessibility.getPartialAXTree", partial_tree()).await;
+
+        let backend_node_id = resolve_backend_node_id(&client, "document.querySelector('button')")
+            .await
+            .unwrap();
+        assert_eq!(backend_node_id, 42);
+        assert_eq!(client.recorded_calls("Runtime.releaseObject").await.len(), 1);
+
+        let nodes = partial_ax_tree(&client, backend_node_id).await.unwrap();
+        let roles: Vec<&str> = nodes.iter().map(|n| n.role.as_str()).collect();
+        assert_eq!(roles, vec!["button", "StaticText"]);
+        assert_eq!(nodes[0].name, "Submit");
+        assert_eq!(nodes[0].properties.get("focusable").map(String::as_str), Some("true"));
+        assert_eq!(nodes[0].child_ids, vec!["3".to_string()]);
+
+        let call = &client.recorded_calls("Accessibility.getPartialAXTree").await[0];
+        assert_eq!(call["backendNodeId"], 42);
+    }
+
+    #[tokio::test]
+    async fn test_unresolved_element_is_not_found() {
+        let client = MockCdpClient::new();
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "null", "value": null } }),
+            )
+            .await;
+
+        let result = resolve_backend_node_id(&client, "document.querySelector('#missing')").await;
+        assert!(matches!(result, Err(Error::ElementNotFound(_))));
+    }
+}
@@ -0,0 +1,51 @@
+//! Element attribute updates
+//!
+//! Sets or removes an attribute through a handle to the resolved element. The
+//! change is a plain DOM mutation: no input, change or other events are
+//! dispatched, so page scripts only notice it through mutation observers or
+//! by reading the attribute.
+
+use super::handle::call_on_element;
+use crate::cdp::traits::CdpClient;
+use crate::error::{Error, Result};
+
+/// Set the attribute `name` to `value`, or remove it when `value` is null
+const SET_ATTRIBUTE_FUNCTION: &str = r#"function(name, value) {
+    if (value === null) {
+        this.removeAttribute(name);
+    } else {
+        this.setAttribute(name, value);
+    }
+}"#;
+
+/// Set an attribute of an element, or remove it when `value` is `None`
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `element_query` - JavaScript expression evaluating to the element (see `JsBuilder::element_query`)
+/// * `name` - Attribute name
+/// * `value` - New value; `None` removes the attribute
+///
+/// # Errors
+/// Returns `Configuration` for an empty name, `ElementNotFound` if the
+/// expression does not evaluate to a DOM node and `ScriptExecutionFailed`
+/// if the browser rejects the name
+pub async fn set_attribute(
+    client: &dyn CdpClient,
+    element_query: &str,
+    name: &str,
+    value: Option<&str>,
+) -> Result<()> {
+    if name.is_empty() {
+        return Err(Error::configuration("Attribute name is required"));
+    }
+
+    call_on_element(
+        client,
+        element_query,
+        SET_ATTRIBUTE_FUNCTION,
+        &[serde_json::json!(name), serde_json::json!(value)],
+    )
+    .await?;
+    Ok(())
+}
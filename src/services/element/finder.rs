@@ -3,6 +3,7 @@
 //! Provides functionality to find DOM elements using various selector strategies.
 
 use crate::error::{Error, Result};
+use crate::services::element::js_utils::PIERCE_QUERY_FUNCTION;
 use crate::services::traits::{ElementInfo, SelectorType};
 use crate::session::traits::PageContext;
 use std::sync::Arc;
@@ -27,7 +28,7 @@ impl ElementFinder {
     /// Returns element information including ID, tag name, and text content.
     ///
     /// # Arguments
-    /// * `selector_type` - Type of selector (CSS, XPath, or Text)
+    /// * `selector_type` - Type of selector (CSS, XPath, Text or Pierce)
     /// * `selector` - Selector string
     ///
     /// # Returns
@@ -50,6 +51,7 @@ impl ElementFinder {
             SelectorType::Css => self.build_css_selector_script(selector, false)?,
             SelectorType::XPath => self.build_xpath_selector_script(selector, false)?,
             SelectorType::Text => self.build_text_selector_script(selector, false)?,
+            SelectorType::Pierce => self.build_pierce_selector_script(selector, false)?,
         };
 
         let result = self.page.evaluate(&script, true).await?;
@@ -71,7 +73,7 @@ impl ElementFinder {
     /// Optionally limits the number of results returned.
     ///
     /// # Arguments
-    /// * `selector_type` - Type of selector (CSS, XPath, or Text)
+    /// * `selector_type` - Type of selector (CSS, XPath, Text or Pierce)
     /// * `selector` - Selector string
     /// * `limit` - Optional maximum number of elements to return
     ///
@@ -99,6 +101,7 @@ impl ElementFinder {
             SelectorType::Css => self.build_css_selector_script(selector, true)?,
             SelectorType::XPath => self.build_xpath_selector_script(selector, true)?,
             SelectorType::Text => self.build_text_selector_script(selector, true)?,
+            SelectorType::Pierce => self.build_pierce_selector_script(selector, true)?,
         };
 
         let result = self.page.evaluate(&script, true).await?;
@@ -124,7 +127,7 @@ impl ElementFinder {
     /// Only retries on ElementNotFound errors; other errors are returned immediately.
     ///
     /// # Arguments
-    /// * `selector_type` - Type of selector (CSS, XPath, or Text)
+    /// * `selector_type` - Type of selector (CSS, XPath, Text or Pierce)
     /// * `selector` - Selector string
     /// * `timeout_ms` - Maximum time to wait in milliseconds
     ///
@@ -341,6 +344,53 @@ impl ElementFinder {
         })
     }
 
+    /// Build shadow-piercing selector script
+    ///
+    /// Generates JavaScript code that matches a CSS selector in the document and
+    /// inside every open shadow root (see `PIERCE_QUERY_FUNCTION`). Elements in
+    /// closed shadow roots are not found.
+    ///
+    /// # Arguments
+    /// * `selector` - CSS selector, optionally a chain of steps separated by `>>>`
+    /// * `multiple` - If true, find all matching elements; otherwise find first match
+    ///
+    /// # Returns
+    /// JavaScript code that returns element information as JSON
+    fn build_pierce_selector_script(&self, selector: &str, multiple: bool) -> Result<String> {
+        let selector_escaped = Self::escape_selector(selector);
+
+        Ok(if multiple {
+            format!(
+                r#"
+                (() => {{
+                    const elements = ({})('{}', true);
+                    return JSON.stringify(elements.map((el, i) => ({{
+                        element_id: el.id || 'pierce-' + i,
+                        tag_name: el.tagName.toLowerCase(),
+                        text_content: el.textContent?.substring(0, 100) || null
+                    }})));
+                }})()
+                "#,
+                PIERCE_QUERY_FUNCTION, selector_escaped
+            )
+        } else {
+            format!(
+                r#"
+                (() => {{
+                    const el = ({})('{}', false);
+                    if (!el) return null;
+                    return JSON.stringify({{
+                        element_id: el.id || 'pierce-single',
+                        tag_name: el.tagName.toLowerCase(),
+                        text_content: el.textContent?.substring(0, 100) || null
+                    }});
+                }})()
+                "#,
+                PIERCE_QUERY_FUNCTION, selector_escaped
+            )
+        })
+    }
+
     /// Escape selector string for safe use in JavaScript
     ///
     /// Escapes backslashes, single quotes, and double quotes to prevent
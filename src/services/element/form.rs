@@ -0,0 +1,70 @@
+//! Element form value queries
+//!
+//! Reads the live `value` of form fields through a handle to the resolved
+//! element, so the field is looked up once and read with a single
+//! `Runtime.callFunctionOn`.
+
+use super::handle::call_on_element;
+use crate::cdp::traits::CdpClient;
+use crate::chaser_oxide::v1::{field_value::Kind as FieldKind, FieldValue};
+use crate::error::{Error, Result};
+
+/// Describe the current value of the element it is called on
+///
+/// Non-form elements report `unsupported` with their tag name.
+const FIELD_VALUE_FUNCTION: &str = r#"function() {
+    const tag = (this.tagName || '').toLowerCase();
+    if (tag === 'select') {
+        return this.multiple
+            ? { kind: 'multi_select', value: this.value, values: Array.from(this.selectedOptions, o => o.value) }
+            : { kind: 'text', value: this.value };
+    }
+    if (tag === 'input' && (this.type === 'checkbox' || this.type === 'radio')) {
+        return { kind: 'checkable', value: this.value, checked: this.checked };
+    }
+    if (tag === 'input' || tag === 'textarea') {
+        return { kind: 'text', value: this.value };
+    }
+    return { kind: 'unsupported', tag };
+}"#;
+
+/// Get the current value of an input, textarea or select
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `element_query` - JavaScript expression evaluating to the element (see `JsBuilder::element_query`)
+///
+/// # Errors
+/// Returns `ElementNotFound` if the expression does not evaluate to a DOM node
+/// and `Configuration` if the element is not a form field
+pub async fn field_value(client: &dyn CdpClient, element_query: &str) -> Result<FieldValue> {
+    let value = call_on_element(client, element_query, FIELD_VALUE_FUNCTION, &[]).await?;
+    parse_field_value(&value)
+}
+
+/// Convert the result of `FIELD_VALUE_FUNCTION`
+fn parse_field_value(value: &serde_json::Value) -> Result<FieldValue> {
+    let kind = match value["kind"].as_str() {
+        Some("text") => FieldKind::Text,
+        Some("checkable") => FieldKind::Checkable,
+        Some("multi_select") => FieldKind::MultiSelect,
+        _ => {
+            return Err(Error::configuration(format!(
+                "<{}> is not a form field; GetValue supports input, textarea and select",
+                value["tag"].as_str().unwrap_or("unknown")
+            )))
+        }
+    };
+
+    Ok(FieldValue {
+        value: value["value"].as_str().unwrap_or_default().to_string(),
+        checked: value["checked"].as_bool().unwrap_or(false),
+        selected_values: value["values"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        kind: kind as i32,
+    })
+}
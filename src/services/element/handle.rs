@@ -0,0 +1,72 @@
+//! Element handles
+//!
+//! Resolves an element query to a remote object and calls a function on it
+//! with `Runtime.callFunctionOn`, so the element is looked up once and
+//! arguments are passed as values rather than spliced into the script.
+
+use crate::cdp::traits::CdpClient;
+use crate::error::{Error, Result};
+
+/// Call `function` with the element as `this` and return the result by value
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `element_query` - JavaScript expression evaluating to the element (see `JsBuilder::element_query`)
+/// * `function` - Function declaration to call
+/// * `arguments` - Values passed to the function
+///
+/// # Errors
+/// Returns `ElementNotFound` if the expression does not evaluate to a DOM node
+/// and `ScriptExecutionFailed` if the function throws
+pub async fn call_on_element(
+    client: &dyn CdpClient,
+    element_query: &str,
+    function: &str,
+    arguments: &[serde_json::Value],
+) -> Result<serde_json::Value> {
+    let resolved = client
+        .call_method(
+            "Runtime.evaluate",
+            serde_json::json!({
+                "expression": element_query,
+                "returnByValue": false,
+            }),
+        )
+        .await?;
+    let object_id = resolved
+        .pointer("/result/objectId")
+        .and_then(|v| v.as_str())
+        .filter(|_| resolved.pointer("/result/subtype").and_then(|v| v.as_str()) == Some("node"))
+        .ok_or_else(|| Error::element_not_found(element_query))?
+        .to_string();
+
+    let arguments: Vec<_> = arguments.iter().map(|value| serde_json::json!({ "value": value })).collect();
+    let called = client
+        .call_method(
+            "Runtime.callFunctionOn",
+            serde_json::json!({
+                "functionDeclaration": function,
+                "objectId": object_id,
+                "arguments": arguments,
+                "returnByValue": true,
+            }),
+        )
+        .await;
+    // Releasing the handle does not affect the result
+    let _ = client
+        .call_method("Runtime.releaseObject", serde_json::json!({ "objectId": object_id }))
+        .await;
+    let called = called?;
+
+    if let Some(exception) = called.get("exceptionDetails") {
+        return Err(Error::script_execution_failed(
+            exception
+                .pointer("/exception/description")
+                .and_then(|d| d.as_str())
+                .unwrap_or("Calling the element function failed")
+                .to_string(),
+        ));
+    }
+
+    Ok(called["result"]["value"].clone())
+}
@@ -2,6 +2,7 @@
 //!
 //! Provides functionality to interact with DOM elements.
 
+use crate::cdp::keys;
 use crate::error::{Error, Result};
 use crate::session::traits::{BoundingBox, ElementRef as SessionElementRef};
 use std::sync::Arc;
@@ -328,47 +329,11 @@ impl ElementInteractor {
         self.element.focus().await?;
 
         // Parse key combination (e.g., "Ctrl+A", "Shift+Enter", "Meta+C")
-        let parts: Vec<&str> = key.split('+').collect();
-        let modifiers = if parts.len() > 1 {
-            &parts[..parts.len() - 1]
-        } else {
-            &[]
-        };
-        let main_key = parts.last().unwrap_or(&key);
-
-        // Build modifier key states
-        let mut ctrl = false;
-        let mut shift = false;
-        let mut alt = false;
-        let mut meta = false;
-
-        for modifier in modifiers {
-            match modifier.to_lowercase().as_str() {
-                "ctrl" | "control" => ctrl = true,
-                "shift" => shift = true,
-                "alt" => alt = true,
-                "meta" | "cmd" | "command" => meta = true,
-                _ => {
-                    debug!("Unknown modifier: {}", modifier);
-                }
-            }
-        }
+        let chords = keys::parse_key_sequence(key)?;
 
-        // In a complete implementation, this would use Input.dispatchKeyEvent
-        // with proper modifier states and key codes
-        debug!(
-            "Dispatching key event: key={}, modifiers=ctrl:{} shift:{} alt:{} meta:{}",
-            main_key, ctrl, shift, alt, meta
-        );
-
-        // Apply delay if specified
-        if let Some(delay) = delay_ms {
-            tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
-        }
-
-        // The actual key press would be handled by the CDP layer
-        // through the ElementRef trait or directly via CDP commands
-        Ok(())
+        // Press the keys as trusted input events, holding each chord for the delay
+        let hold = tokio::time::Duration::from_millis(delay_ms.unwrap_or(0));
+        keys::press_chords(self.element.get_cdp_client().as_ref(), &chords, hold).await
     }
 
     /// Drag and drop element
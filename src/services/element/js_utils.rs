@@ -5,6 +5,33 @@
 
 use crate::error::{Error, Result};
 
+/// Find elements matching a CSS selector across open shadow roots
+///
+/// Called with `(selector, all)`. The selector is a chain of CSS selectors
+/// separated by `>>>`; each step is matched in the light DOM and, recursively,
+/// inside every open shadow root below the previous step's matches. Returns
+/// all matches in document order when `all` is true, otherwise the first
+/// match or `null`. Closed shadow roots are not reachable from page scripts.
+pub const PIERCE_QUERY_FUNCTION: &str = r#"function(selector, all) {
+    const deepQueryAll = (root, css, found) => {
+        for (const el of root.querySelectorAll(css)) found.add(el);
+        for (const el of root.querySelectorAll('*')) {
+            if (el.shadowRoot) deepQueryAll(el.shadowRoot, css, found);
+        }
+    };
+    let scopes = [document];
+    for (const step of selector.split('>>>').map(s => s.trim()).filter(Boolean)) {
+        const found = new Set();
+        for (const scope of scopes) {
+            deepQueryAll(scope, step, found);
+            if (scope.shadowRoot) deepQueryAll(scope.shadowRoot, step, found);
+        }
+        scopes = Array.from(found);
+    }
+    const matches = scopes.filter(el => el !== document);
+    return all ? matches : (matches[0] || null);
+}"#;
+
 /// JavaScript code builder for element operations
 ///
 /// Provides methods to generate JavaScript code snippets for common
@@ -19,7 +46,7 @@ impl JsBuilder {
     /// Create a new JavaScript builder for element operations
     ///
     /// # Arguments
-    /// * `selector_type` - Type of selector (1=CSS, 2=XPath, 3=Text)
+    /// * `selector_type` - Type of selector (1=CSS, 2=XPath, 3=Text, 5=Pierce)
     /// * `selector` - The selector string
     pub fn new(selector_type: i32, selector: String) -> Self {
         Self {
@@ -53,13 +80,14 @@ impl JsBuilder {
     /// Generate JavaScript to query an element
     ///
     /// Creates the appropriate query expression based on selector type.
-    /// Supports CSS selectors (1), XPath (2), and text search (3).
+    /// Supports CSS selectors (1), XPath (2), text search (3) and CSS
+    /// selectors piercing open shadow roots (5).
     ///
     /// # Returns
     /// JavaScript expression that evaluates to the element (or null if not found)
     ///
     /// # Errors
-    /// Returns error if selector_type is invalid (not 1, 2, or 3)
+    /// Returns error if selector_type is invalid (not 1, 2, 3 or 5)
     pub fn element_query(&self) -> Result<String> {
         Ok(match self.selector_type {
             1 => {
@@ -91,6 +119,13 @@ impl JsBuilder {
                     Self::escape_js_str(&self.selector)
                 )
             }
+            5 => {
+                format!(
+                    "({})('{}', false)",
+                    PIERCE_QUERY_FUNCTION,
+                    Self::escape_js_str(&self.selector)
+                )
+            }
             _ => {
                 return Err(Error::internal(format!(
                     "Invalid selector type: {}",
@@ -188,14 +223,22 @@ impl JsBuilder {
 
     /// Build script to get element bounding box
     ///
-    /// Returns the element's position and dimensions as a JSON object.
+    /// Returns the element's viewport position and dimensions as a JSON object,
+    /// along with the scroll offset, device pixel ratio and viewport size
+    /// needed to derive page and device coordinates.
     pub fn get_bounding_box_script(&self) -> Result<String> {
         self.execute_on_element(
-            r#"JSON.stringify({
-                x: el.getBoundingClientRect().x,
-                y: el.getBoundingClientRect().y,
-                width: el.getBoundingClientRect().width,
-                height: el.getBoundingClientRect().height
+            r#"const rect = el.getBoundingClientRect();
+            return JSON.stringify({
+                x: rect.x,
+                y: rect.y,
+                width: rect.width,
+                height: rect.height,
+                scrollX: window.scrollX,
+                scrollY: window.scrollY,
+                devicePixelRatio: window.devicePixelRatio || 1,
+                viewportWidth: window.innerWidth,
+                viewportHeight: window.innerHeight
             })"#,
         )
     }
@@ -239,7 +282,7 @@ impl JsBuilder {
                   reason = 'below viewport';
               }
 
-              JSON.stringify({visible, reason})"#,
+              return JSON.stringify({visible, reason});"#,
         )
     }
 
@@ -318,6 +361,35 @@ impl JsBuilder {
         ))
     }
 
+    /// Build script to focus an element before inserting text into it
+    ///
+    /// With `clear_first` the existing content is selected so the inserted
+    /// text replaces it; otherwise the caret is moved to the end. Returns
+    /// `'focused'`, or `null` when the element does not exist.
+    ///
+    /// # Arguments
+    /// * `clear_first` - If true, select the existing content
+    pub fn focus_for_insert_script(&self, clear_first: bool) -> Result<String> {
+        self.execute_on_element(&format!(
+            r#"el.focus();
+               const clear = {};
+               if (typeof el.select === 'function' && clear) {{
+                   el.select();
+               }} else if (typeof el.setSelectionRange === 'function') {{
+                   try {{ const end = el.value.length; el.setSelectionRange(clear ? 0 : end, end); }} catch (e) {{}}
+               }} else if (el.isContentEditable) {{
+                   const range = document.createRange();
+                   range.selectNodeContents(el);
+                   if (!clear) range.collapse(false);
+                   const selection = window.getSelection();
+                   selection.removeAllRanges();
+                   selection.addRange(range);
+               }}
+               'focused'"#,
+            clear_first
+        ))
+    }
+
     /// Build script to select option in dropdown
     ///
     /// Sets the value property of a select element and triggers change event.
@@ -344,23 +416,6 @@ impl JsBuilder {
             block
         ))
     }
-
-    /// Build script to press key on element
-    ///
-    /// Dispatches keyboard events (keydown, keypress, keyup) for the specified key.
-    ///
-    /// # Arguments
-    /// * `key` - Key to press (e.g., "Enter", "Escape", "Ctrl+A")
-    pub fn press_key_script(&self, key: &str) -> Result<String> {
-        self.execute_on_element(&format!(
-            r#"el.dispatchEvent(new KeyboardEvent('keydown', {{key: '{}', bubbles: true}}));
-               el.dispatchEvent(new KeyboardEvent('keypress', {{key: '{}', bubbles: true}}));
-               el.dispatchEvent(new KeyboardEvent('keyup', {{key: '{}', bubbles: true}})); 'key_pressed'"#,
-            Self::escape_js_str(key),
-            Self::escape_js_str(key),
-            Self::escape_js_str(key)
-        ))
-    }
 }
 
 #[cfg(test)]
@@ -399,6 +454,14 @@ mod tests {
         assert!(query.contains("Submit"));
     }
 
+    #[test]
+    fn test_pierce_query() {
+        let builder = JsBuilder::new(5, "my-widget >>> button[title='Go']".to_string());
+        let query = builder.element_query().unwrap();
+        assert!(query.starts_with(&format!("({})", PIERCE_QUERY_FUNCTION)));
+        assert!(query.ends_with(r"('my-widget >>> button[title=\'Go\']', false)"));
+    }
+
     #[test]
     fn test_click_script() {
         let builder = JsBuilder::new(1, "button".to_string());
@@ -0,0 +1,24 @@
+//! Element markup serialization
+//!
+//! Reads outer HTML with `DOM.getOuterHTML`, the browser's own serializer,
+//! instead of the page's (possibly overridden) `outerHTML` getter.
+
+use crate::cdp::traits::CdpClient;
+use crate::error::{Error, Result};
+
+/// Get the serialized markup of a DOM node, including the node itself
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `backend_node_id` - Node to serialize (see `accessibility::resolve_backend_node_id`)
+pub async fn outer_html(client: &dyn CdpClient, backend_node_id: i64) -> Result<String> {
+    let result = client
+        .call_method("DOM.getOuterHTML", serde_json::json!({ "backendNodeId": backend_node_id }))
+        .await?;
+
+    result
+        .get("outerHTML")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| Error::cdp("No outerHTML in DOM.getOuterHTML result"))
+}
@@ -12,11 +12,19 @@
 //! - `CSS`: CSS 选择器（如 `.class`, `#id`, `[attr=value]`）
 //! - `XPath`: XPath 表达式
 //! - `Text`: 按文本内容查找
+//! - `Pierce`: 穿透开放的 Shadow Root 查找 CSS 选择器，`>>>` 分隔多级（无法进入 closed 模式的 Shadow Root）
 //! - `AriaLabel`: 按 ARIA 标签查找
 //!
 //! ## 模块结构
 //! - `finder`: 元素查找器实现
 //! - `interactor`: 元素交互器实现
+//! - `accessibility`: 元素级可访问性子树查询
+//! - `style`: 元素计算样式查询
+//! - `handle`: 将元素查询解析为远程对象，并通过 `Runtime.callFunctionOn` 在其上调用函数
+//! - `form`: 通过元素句柄读取表单字段的当前值
+//! - `attribute`: 通过元素句柄设置或移除属性（不触发任何事件）
+//! - `markup`: 通过 `DOM.getOuterHTML` 获取浏览器规范序列化的元素 HTML
+//! - `pointer`: 基于 CDP 输入事件的真实点击
 //! - `service`: gRPC 服务实现
 //!
 //! ## RPC 方法
@@ -25,6 +33,10 @@
 //! - `ClickElement`: 点击元素
 //! - `TypeElement`: 在元素中输入文本
 //! - `GetElementInfo`: 获取元素信息
+//! - `GetComputedStyle`: 获取元素的计算样式
+//! - `SetChecked`: 勾选或取消勾选复选框/单选框，并校验最终状态
+//! - `GetValue`: 获取表单字段的当前值（勾选状态、多选列表）
+//! - `SetAttribute`: 设置或移除元素属性，值为空时移除
 //!
 //! ## 使用示例
 //! ```rust,no_run
@@ -44,10 +56,17 @@
 //! # }
 //! ```
 
+pub mod accessibility;
+pub mod attribute;
 pub mod finder;
+pub mod form;
+pub mod handle;
 pub mod interactor;
 pub mod js_utils;
+pub mod markup;
+pub mod pointer;
 pub mod service;
+pub mod style;
 
 #[cfg(test)]
 mod tests;
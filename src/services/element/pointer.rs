@@ -0,0 +1,61 @@
+//! Trusted pointer input on DOM nodes
+//!
+//! Clicks through `Input.dispatchMouseEvent` at the node's on-screen position,
+//! so the page sees the same event sequence (and default actions such as
+//! toggling a checkbox) as for a real user click.
+
+use crate::cdp::traits::CdpClient;
+use crate::error::{Error, Result};
+
+/// Click the center of a DOM node with the left mouse button
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `backend_node_id` - Node to click (see `accessibility::resolve_backend_node_id`)
+///
+/// # Errors
+/// Returns `ElementNotFound` if the node has no layout box (e.g. `display: none`)
+pub async fn click_node(client: &dyn CdpClient, backend_node_id: i64) -> Result<()> {
+    client
+        .call_method(
+            "DOM.scrollIntoViewIfNeeded",
+            serde_json::json!({ "backendNodeId": backend_node_id }),
+        )
+        .await?;
+
+    let box_model = client
+        .call_method("DOM.getBoxModel", serde_json::json!({ "backendNodeId": backend_node_id }))
+        .await
+        .map_err(|e| Error::element_not_found(format!("backend node {} has no box model: {}", backend_node_id, e)))?;
+    let (x, y) = quad_center(&box_model["model"]["content"])
+        .ok_or_else(|| Error::element_not_found(format!("backend node {} has no box model", backend_node_id)))?;
+
+    for event_type in ["mousePressed", "mouseReleased"] {
+        client
+            .call_method(
+                "Input.dispatchMouseEvent",
+                serde_json::json!({
+                    "type": event_type,
+                    "x": x,
+                    "y": y,
+                    "button": "left",
+                    "clickCount": 1,
+                }),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Center of a CDP quad (`[x1, y1, x2, y2, x3, y3, x4, y4]`)
+fn quad_center(quad: &serde_json::Value) -> Option<(f64, f64)> {
+    let points: Vec<f64> = quad.as_array()?.iter().filter_map(|v| v.as_f64()).collect();
+    if points.len() != 8 {
+        return None;
+    }
+
+    let x = points.iter().step_by(2).sum::<f64>() / 4.0;
+    let y = points.iter().skip(1).step_by(2).sum::<f64>() / 4.0;
+    Some((x, y))
+}
@@ -2,11 +2,21 @@
 //!
 //! Provides gRPC server implementation for element interaction operations.
 
+use crate::cdp::keys;
 use crate::error::{Error as ServiceError, Result as ServiceResult};
+use crate::services::element::accessibility;
+use crate::services::element::attribute;
 use crate::services::element::finder::ElementFinder;
+use crate::services::element::form;
 use crate::services::element::js_utils::JsBuilder;
+use crate::services::element::{markup, pointer};
+use crate::services::element::style;
+use crate::services::common::html::format_html;
+use crate::services::common::limits::check_result_size;
+use crate::services::common::proto;
+use crate::services::common::request_id;
 use crate::services::traits::SelectorType;
-use crate::session::traits::{PageContext, SessionManager};
+use crate::session::traits::{DefaultTimeouts, PageContext, SessionManager};
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::{error, info, instrument};
@@ -18,6 +28,7 @@ use crate::chaser_oxide::v1::{
     find_element_response::Response as FindElementResponseEnum,
     find_elements_response::Response as FindElementsResponseEnum,
     wait_for_element_response::Response as WaitForElementResponseEnum,
+    wait_for_element_request::WaitState,
     get_html_response::Response as GetHtmlResponseEnum,
     click_response::Response as ClickResponseEnum,
     type_response::Response as TypeResponseEnum,
@@ -36,6 +47,11 @@ use crate::chaser_oxide::v1::{
     get_properties_response::Response as GetPropertiesResponseEnum,
     press_key_response::Response as PressKeyResponseEnum,
     drag_and_drop_response::Response as DragAndDropResponseEnum,
+    get_element_accessibility_response::Response as GetElementAccessibilityResponseEnum,
+    get_computed_style_response::Response as GetComputedStyleResponseEnum,
+    set_checked_response::Response as SetCheckedResponseEnum,
+    get_value_response::Response as GetValueResponseEnum,
+    set_attribute_response::Response as SetAttributeResponseEnum,
     FindElementRequest, FindElementResponse,
     FindElementsRequest, FindElementsResponse,
     ClickRequest, ClickResponse,
@@ -57,9 +73,15 @@ use crate::chaser_oxide::v1::{
     GetPropertiesRequest, GetPropertiesResponse,
     PressKeyRequest, PressKeyResponse,
     DragAndDropRequest, DragAndDropResponse,
+    GetElementAccessibilityRequest, GetElementAccessibilityResponse,
+    GetComputedStyleRequest, GetComputedStyleResponse,
+    SetCheckedRequest, SetCheckedResponse,
+    GetValueRequest, GetValueResponse,
+    SetAttributeRequest, SetAttributeResponse,
     Empty, ErrorCode,
     AttributeValue, Attributes, TextValue,
     HtmlValue, BoundingBox, VisibilityResult, EnabledResult, ElementProperties,
+    AccessibilityTree, ComputedStyle, SetCheckedResult,
 };
 
 /// Macro for handling simple element operation results (success/empty response)
@@ -78,7 +100,7 @@ macro_rules! handle_simple_op {
                     response: Some($error_ctor(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Response::new(resp)
@@ -91,12 +113,22 @@ macro_rules! handle_simple_op {
 #[derive(Clone)]
 pub struct ElementGrpcService {
     session_manager: Arc<dyn SessionManager>,
+    max_result_bytes: usize,
 }
 
 impl ElementGrpcService {
     /// Create a new ElementService gRPC server
     pub fn new(session_manager: Arc<dyn SessionManager>) -> Self {
-        Self { session_manager }
+        Self {
+            session_manager,
+            max_result_bytes: crate::config::DEFAULT_MAX_EVALUATE_RESULT_BYTES,
+        }
+    }
+
+    /// Limit the size of returned element HTML
+    pub fn with_max_result_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_result_bytes = max_bytes;
+        self
     }
 
     /// Convert to tonic server
@@ -121,6 +153,7 @@ impl ElementGrpcService {
             1 => Ok(SelectorType::Css),
             2 => Ok(SelectorType::XPath),
             3 => Ok(SelectorType::Text),
+            5 => Ok(SelectorType::Pierce),
             _ => Err(ServiceError::internal(format!(
                 "Invalid selector type: {}",
                 selector_type
@@ -139,6 +172,37 @@ impl ElementGrpcService {
         })
     }
 
+    /// Build a bounding box from the output of `get_bounding_box_script`
+    ///
+    /// Page coordinates add the scroll offset to the viewport ones; device
+    /// coordinates scale the viewport ones by the device pixel ratio.
+    fn bounding_box_from_json(bbox: &serde_json::Value) -> BoundingBox {
+        let number = |key: &str| bbox[key].as_f64().unwrap_or(0.0);
+        let (x, y, width, height) = (number("x"), number("y"), number("width"), number("height"));
+        let device_pixel_ratio = bbox["devicePixelRatio"].as_f64().filter(|ratio| *ratio > 0.0).unwrap_or(1.0);
+        let in_viewport = width > 0.0
+            && height > 0.0
+            && x + width > 0.0
+            && y + height > 0.0
+            && x < number("viewportWidth")
+            && y < number("viewportHeight");
+
+        BoundingBox {
+            x,
+            y,
+            width,
+            height,
+            page_x: x + number("scrollX"),
+            page_y: y + number("scrollY"),
+            device_pixel_ratio,
+            device_x: x * device_pixel_ratio,
+            device_y: y * device_pixel_ratio,
+            device_width: width * device_pixel_ratio,
+            device_height: height * device_pixel_ratio,
+            in_viewport,
+        }
+    }
+
     /// Verify that an element exists
     async fn verify_element_exists(
         &self,
@@ -154,6 +218,138 @@ impl ElementGrpcService {
         }
     }
 
+    /// Poll the element's visibility until it is `visible` (or hidden) or `timeout_ms` elapses
+    ///
+    /// An element missing from the DOM counts as hidden. On timeout the error
+    /// carries the last observed state.
+    async fn wait_for_visibility(
+        &self,
+        page: &Arc<dyn PageContext>,
+        selector_type: i32,
+        selector: &str,
+        visible: bool,
+        timeout_ms: u64,
+    ) -> ServiceResult<()> {
+        let script = JsBuilder::new(selector_type, selector.to_string()).is_visible_script()?;
+        let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_millis(timeout_ms);
+        let poll_interval = tokio::time::Duration::from_millis(100);
+
+        loop {
+            let state = self.execute_script(page, &script).await?;
+            let (is_visible, reason) = match serde_json::from_str::<serde_json::Value>(&state) {
+                Ok(v) => (
+                    v["visible"].as_bool().unwrap_or(false),
+                    v["reason"].as_str().unwrap_or("").to_string(),
+                ),
+                Err(_) => (false, "not attached".to_string()),
+            };
+            if is_visible == visible {
+                return Ok(());
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(ServiceError::timeout(format!(
+                    "Element {} not {} within {}ms (last state: {})",
+                    selector,
+                    if visible { "visible" } else { "hidden" },
+                    timeout_ms,
+                    reason
+                )));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Read the input type and `checked` state of an element
+    async fn checked_state(&self, page: &Arc<dyn PageContext>, element_ref: &ElementRef) -> ServiceResult<(String, bool)> {
+        let state = self
+            .execute_on_element(
+                page,
+                element_ref.selector_type,
+                &element_ref.selector,
+                "return JSON.stringify({type: el.type, checked: el.checked});",
+            )
+            .await?;
+        let state: serde_json::Value = serde_json::from_str(&state)
+            .map_err(|_| ServiceError::element_not_found(element_ref.selector.clone()))?;
+
+        Ok((
+            state["type"].as_str().unwrap_or_default().to_string(),
+            state["checked"].as_bool().unwrap_or(false),
+        ))
+    }
+
+    /// Bring a checkbox or radio button into the `checked` state
+    ///
+    /// Clicks through real input events only when the state differs, then
+    /// re-reads the state to make sure the page did not swallow the click.
+    async fn set_checked_state(
+        &self,
+        page: &Arc<dyn PageContext>,
+        element_ref: &ElementRef,
+        checked: bool,
+    ) -> ServiceResult<SetCheckedResult> {
+        let (input_type, current) = self.checked_state(page, element_ref).await?;
+        if input_type != "checkbox" && input_type != "radio" {
+            return Err(ServiceError::configuration(format!(
+                "Element {} is not a checkbox or radio button (type: {})",
+                element_ref.selector, input_type
+            )));
+        }
+        if current == checked {
+            return Ok(SetCheckedResult { checked, changed: false });
+        }
+        if input_type == "radio" && !checked {
+            return Err(ServiceError::configuration(format!(
+                "Radio button {} cannot be unchecked; check another radio in its group instead",
+                element_ref.selector
+            )));
+        }
+
+        let query = JsBuilder::new(element_ref.selector_type, element_ref.selector.clone()).element_query()?;
+        let client = page.get_cdp_client();
+        let backend_node_id = accessibility::resolve_backend_node_id(client.as_ref(), &query).await?;
+        pointer::click_node(client.as_ref(), backend_node_id).await?;
+
+        let (_, actual) = self.checked_state(page, element_ref).await?;
+        if actual != checked {
+            return Err(ServiceError::internal(format!(
+                "Element {} is still {} after clicking",
+                element_ref.selector,
+                if actual { "checked" } else { "unchecked" }
+            )));
+        }
+
+        Ok(SetCheckedResult { checked, changed: true })
+    }
+
+    /// Focus the element and insert `text` in one step via `Input.insertText`
+    ///
+    /// Much faster than per-key typing for long text and, unlike assigning
+    /// `el.value`, fires the input events framework-controlled inputs listen to.
+    async fn insert_text(
+        &self,
+        page: &Arc<dyn PageContext>,
+        element_ref: &ElementRef,
+        text: &str,
+        clear_first: bool,
+    ) -> ServiceResult<()> {
+        let js = JsBuilder::new(element_ref.selector_type, element_ref.selector.clone())
+            .focus_for_insert_script(clear_first)?;
+        if self.execute_script(page, &js).await? != "focused" {
+            return Err(ServiceError::element_not_found(element_ref.selector.clone()));
+        }
+
+        let client = page.get_cdp_client();
+        if clear_first && text.is_empty() {
+            // Inserting nothing would leave the old content selected; delete it instead
+            let delete = keys::parse_key_chord("Delete")?;
+            return keys::press_chords(client.as_ref(), &[delete], std::time::Duration::ZERO).await;
+        }
+
+        client.insert_text(text).await
+    }
+
     /// Execute JavaScript on an element
     async fn execute_on_element(
         &self,
@@ -203,7 +399,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(FindElementResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -252,7 +448,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(FindElementsResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -285,9 +481,13 @@ impl ElementServiceTrait for ElementGrpcService {
         let element_ref = req.element.ok_or_else(|| Status::invalid_argument("Element reference is required"))?;
         let page = self.get_page(&element_ref.page_id).await?;
 
-        let js = JsBuilder::new(element_ref.selector_type, element_ref.selector)
-            .type_text_script(&req.text)?;
-        let result = self.execute_script(&page, &js).await;
+        let result = if req.insert_text {
+            self.insert_text(&page, &element_ref, &req.text, req.clear_first).await
+        } else {
+            let js = JsBuilder::new(element_ref.selector_type, element_ref.selector)
+                .type_text_script(&req.text)?;
+            self.execute_script(&page, &js).await.map(|_| ())
+        };
 
         Ok(handle_simple_op!(result, TypeResponse, "Type", TypeResponseEnum::Success, TypeResponseEnum::Error))
     }
@@ -300,9 +500,13 @@ impl ElementServiceTrait for ElementGrpcService {
         let element_ref = req.element.ok_or_else(|| Status::invalid_argument("Element reference is required"))?;
         let page = self.get_page(&element_ref.page_id).await?;
 
-        let js = JsBuilder::new(element_ref.selector_type, element_ref.selector)
-            .fill_script(&req.value, req.clear_first)?;
-        let result = self.execute_script(&page, &js).await;
+        let result = if req.insert_text {
+            self.insert_text(&page, &element_ref, &req.value, req.clear_first).await
+        } else {
+            let js = JsBuilder::new(element_ref.selector_type, element_ref.selector)
+                .fill_script(&req.value, req.clear_first)?;
+            self.execute_script(&page, &js).await.map(|_| ())
+        };
 
         Ok(handle_simple_op!(result, FillResponse, "Fill", FillResponseEnum::Success, FillResponseEnum::Error))
     }
@@ -329,7 +533,7 @@ impl ElementServiceTrait for ElementGrpcService {
                 response: Some(GetAttributeResponseEnum::Error(ProtoError {
                     code: ErrorCode::ElementNotFound as i32,
                     message: format!("Element not found: {}", element_ref.selector),
-                    details: Default::default(),
+                    details: request_id::error_details(),
                 })),
             }));
         }
@@ -345,7 +549,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(GetAttributeResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 return Ok(Response::new(response));
@@ -395,7 +599,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(GetAttributesResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 return Ok(Response::new(response));
@@ -449,7 +653,7 @@ impl ElementServiceTrait for ElementGrpcService {
                 response: Some(GetTextResponseEnum::Error(ProtoError {
                     code: ErrorCode::ElementNotFound as i32,
                     message: format!("Element not found: {}", element_ref.selector),
-                    details: Default::default(),
+                    details: request_id::error_details(),
                 })),
             }));
         }
@@ -469,7 +673,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(GetTextResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 return Ok(Response::new(response));
@@ -493,41 +697,52 @@ impl ElementServiceTrait for ElementGrpcService {
         info!("GetHTML request received");
 
         let req = request.into_inner();
+        let format = req.format();
         let element_ref = req.element.ok_or_else(|| {
             Status::invalid_argument("Element reference is required")
         })?;
 
         let page = self.get_page(&element_ref.page_id).await?;
 
-        // Execute get HTML using JavaScript
-        let js_code = if req.outer {
-            r#"
-                (() => {
-                    return el.outerHTML || '';
-                })()
-            "#
+        // Outer HTML comes from the browser's serializer; inner HTML has no CDP equivalent
+        let result = if req.outer {
+            let client = page.get_cdp_client();
+            match JsBuilder::new(element_ref.selector_type, element_ref.selector.clone()).element_query() {
+                Ok(query) => match accessibility::resolve_backend_node_id(client.as_ref(), &query).await {
+                    Ok(backend_node_id) => markup::outer_html(client.as_ref(), backend_node_id).await,
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            }
         } else {
-            r#"
+            let js_code = r#"
                 (() => {
                     return el.innerHTML || '';
                 })()
-            "#
+            "#;
+            self.execute_on_element(&page, element_ref.selector_type, &element_ref.selector, js_code).await
         };
 
-        let html = match self.execute_on_element(&page, element_ref.selector_type, &element_ref.selector, js_code).await {
+        let html = match result {
             Ok(v) => v,
             Err(e) => {
                 error!("GetHTML failed: {}", e);
+                let code = match e {
+                    ServiceError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+                    _ => ErrorCode::Internal,
+                };
                 let response = GetHtmlResponse {
                     response: Some(GetHtmlResponseEnum::Error(ProtoError {
-                        code: ErrorCode::ElementNotFound as i32,
+                        code: code as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 return Ok(Response::new(response));
             }
         };
+        let html = format_html(&html, format, true);
+        check_result_size("Element HTML", html.len(), self.max_result_bytes)?;
 
         let response = GetHtmlResponse {
             response: Some(GetHtmlResponseEnum::Html(HtmlValue {
@@ -583,7 +798,7 @@ impl ElementServiceTrait for ElementGrpcService {
                 response: Some(SelectOptionResponseEnum::Error(ProtoError {
                     code: ErrorCode::InvalidArgument as i32,
                     message: "At least one value must be provided".to_string(),
-                    details: Default::default(),
+                    details: request_id::error_details(),
                 })),
             }));
         }
@@ -614,7 +829,7 @@ impl ElementServiceTrait for ElementGrpcService {
                 response: Some(UploadFileResponseEnum::Error(ProtoError {
                     code: ErrorCode::InvalidArgument as i32,
                     message: "At least one file path must be provided".to_string(),
-                    details: Default::default(),
+                    details: request_id::error_details(),
                 })),
             };
             return Ok(Response::new(response));
@@ -647,7 +862,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(UploadFileResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -699,7 +914,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(GetBoundingBoxResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -713,19 +928,14 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(GetBoundingBoxResponseEnum::Error(ProtoError {
                         code: ErrorCode::Unknown as i32,
                         message: format!("Failed to parse bounding box: {}", e),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
         };
 
         Ok(Response::new(GetBoundingBoxResponse {
-            response: Some(GetBoundingBoxResponseEnum::Box(BoundingBox {
-                x: bbox["x"].as_f64().unwrap_or(0.0),
-                y: bbox["y"].as_f64().unwrap_or(0.0),
-                width: bbox["width"].as_f64().unwrap_or(0.0),
-                height: bbox["height"].as_f64().unwrap_or(0.0),
-            })),
+            response: Some(GetBoundingBoxResponseEnum::Box(Self::bounding_box_from_json(&bbox))),
         }))
     }
 
@@ -753,7 +963,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(IsVisibleResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -803,7 +1013,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(IsEnabledResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -839,16 +1049,39 @@ impl ElementServiceTrait for ElementGrpcService {
         let req = request.into_inner();
         let page = self.get_page(&req.page_id).await?;
 
-        let finder = ElementFinder::new(page);
+        let finder = ElementFinder::new(page.clone());
         let selector_type = Self::convert_selector_type(req.selector_type)?;
-        let timeout = if req.timeout > 0 { req.timeout as u64 } else { 30000 };
+        let timeout = DefaultTimeouts::resolve(req.timeout.max(0) as u64, page.default_timeouts().wait_ms, 30000);
 
-        match finder.wait_for_element(selector_type, &req.selector, timeout).await {
-            Ok(element) => {
+        let visible = match WaitState::try_from(req.state) {
+            Ok(WaitState::Visible) => Some(true),
+            Ok(WaitState::Hidden) => Some(false),
+            _ => None,
+        };
+        let result = match visible {
+            Some(visible) => {
+                match self.wait_for_visibility(&page, req.selector_type, &req.selector, visible, timeout).await {
+                    // A hidden element may be gone from the DOM, leaving no id to report
+                    Ok(()) => Ok(finder
+                        .find_element(selector_type, &req.selector)
+                        .await
+                        .map(|element| element.element_id)
+                        .unwrap_or_default()),
+                    Err(e) => Err(e),
+                }
+            }
+            None => finder
+                .wait_for_element(selector_type, &req.selector, timeout)
+                .await
+                .map(|element| element.element_id),
+        };
+
+        match result {
+            Ok(element_id) => {
                 let response = WaitForElementResponse {
                     response: Some(WaitForElementResponseEnum::Element(ElementRef {
                         page_id: req.page_id,
-                        element_id: element.element_id,
+                        element_id,
                         selector_type: req.selector_type,
                         selector: req.selector,
                         index: 0,
@@ -862,7 +1095,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(WaitForElementResponseEnum::Error(ProtoError {
                         code: ErrorCode::Timeout as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -889,7 +1122,7 @@ impl ElementServiceTrait for ElementGrpcService {
                 response: Some(GetPropertiesResponseEnum::Error(ProtoError {
                     code: ErrorCode::InvalidArgument as i32,
                     message: "At least one property name must be provided".to_string(),
-                    details: Default::default(),
+                    details: request_id::error_details(),
                 })),
             };
             return Ok(Response::new(response));
@@ -926,7 +1159,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(GetPropertiesResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: e.to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 };
                 return Ok(Response::new(response));
@@ -977,15 +1210,22 @@ impl ElementServiceTrait for ElementGrpcService {
         })?;
         let page = self.get_page(&element_ref.page_id).await?;
 
-        if req.key.is_empty() {
-            return Ok(Response::new(PressKeyResponse {
-                response: Some(PressKeyResponseEnum::Error(ProtoError {
-                    code: ErrorCode::InvalidArgument as i32,
-                    message: "Key must not be empty".to_string(),
-                    details: Default::default(),
-                })),
-            }));
-        }
+        let extra_modifiers = proto::key_modifiers(&req.modifiers);
+        let chords = match keys::parse_key_sequence(&req.key) {
+            Ok(chords) => chords
+                .into_iter()
+                .map(|chord| chord.with_modifiers(extra_modifiers))
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                return Ok(Response::new(PressKeyResponse {
+                    response: Some(PressKeyResponseEnum::Error(ProtoError {
+                        code: ErrorCode::InvalidArgument as i32,
+                        message: e.to_string(),
+                        details: request_id::error_details(),
+                    })),
+                }));
+            }
+        };
 
         let builder = JsBuilder::new(element_ref.selector_type, element_ref.selector);
 
@@ -997,14 +1237,14 @@ impl ElementServiceTrait for ElementGrpcService {
                 response: Some(PressKeyResponseEnum::Error(ProtoError {
                     code: ErrorCode::ElementNotFound as i32,
                     message: format!("Failed to focus element: {}", e),
-                    details: Default::default(),
+                    details: request_id::error_details(),
                 })),
             }));
         }
 
-        // Then press the key
-        let key_js = builder.press_key_script(&req.key)?;
-        let result = self.execute_script(&page, &key_js).await;
+        // Then press the keys as trusted input events
+        let hold = std::time::Duration::from_millis(req.delay.max(0) as u64);
+        let result = keys::press_chords(page.get_cdp_client().as_ref(), &chords, hold).await;
 
         Ok(handle_simple_op!(result, PressKeyResponse, "PressKey", PressKeyResponseEnum::Success, PressKeyResponseEnum::Error))
     }
@@ -1040,7 +1280,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(DragAndDropResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: format!("Failed to get source element: {}", e),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -1056,7 +1296,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(DragAndDropResponseEnum::Error(ProtoError {
                         code: ErrorCode::ElementNotFound as i32,
                         message: format!("Failed to get target element: {}", e),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -1069,7 +1309,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(DragAndDropResponseEnum::Error(ProtoError {
                         code: ErrorCode::Unknown as i32,
                         message: "Failed to parse source bounding box".to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -1082,7 +1322,7 @@ impl ElementServiceTrait for ElementGrpcService {
                     response: Some(DragAndDropResponseEnum::Error(ProtoError {
                         code: ErrorCode::Unknown as i32,
                         message: "Failed to parse target bounding box".to_string(),
-                        details: Default::default(),
+                        details: request_id::error_details(),
                     })),
                 }));
             }
@@ -1138,11 +1378,209 @@ impl ElementServiceTrait for ElementGrpcService {
 
         Ok(handle_simple_op!(result, DragAndDropResponse, "DragAndDrop", DragAndDropResponseEnum::Success, DragAndDropResponseEnum::Error))
     }
+
+    #[instrument(skip(self, request))]
+    async fn get_element_accessibility(
+        &self,
+        request: Request<GetElementAccessibilityRequest>,
+    ) -> Result<Response<GetElementAccessibilityResponse>, Status> {
+        info!("GetElementAccessibility request received");
+
+        let req = request.into_inner();
+        let element_ref = req.element.ok_or_else(|| {
+            Status::invalid_argument("Element reference is required")
+        })?;
+        let page = self.get_page(&element_ref.page_id).await?;
+
+        let query = JsBuilder::new(element_ref.selector_type, element_ref.selector).element_query()?;
+        let client = page.get_cdp_client();
+
+        let result = match accessibility::resolve_backend_node_id(client.as_ref(), &query).await {
+            Ok(backend_node_id) => accessibility::partial_ax_tree(client.as_ref(), backend_node_id).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(nodes) => Ok(Response::new(GetElementAccessibilityResponse {
+                response: Some(GetElementAccessibilityResponseEnum::Tree(AccessibilityTree { nodes })),
+            })),
+            Err(e) => {
+                error!("GetElementAccessibility failed: {}", e);
+                let code = match e {
+                    ServiceError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+                    _ => ErrorCode::Internal,
+                };
+                Ok(Response::new(GetElementAccessibilityResponse {
+                    response: Some(GetElementAccessibilityResponseEnum::Error(ProtoError {
+                        code: code as i32,
+                        message: e.to_string(),
+                        details: request_id::error_details(),
+                    })),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_computed_style(
+        &self,
+        request: Request<GetComputedStyleRequest>,
+    ) -> Result<Response<GetComputedStyleResponse>, Status> {
+        info!("GetComputedStyle request received");
+
+        let req = request.into_inner();
+        let element_ref = req.element.ok_or_else(|| {
+            Status::invalid_argument("Element reference is required")
+        })?;
+        let page = self.get_page(&element_ref.page_id).await?;
+
+        let query = JsBuilder::new(element_ref.selector_type, element_ref.selector).element_query()?;
+        let client = page.get_cdp_client();
+
+        let result = match accessibility::resolve_backend_node_id(client.as_ref(), &query).await {
+            Ok(backend_node_id) => style::computed_style(client.as_ref(), backend_node_id, &req.properties).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(properties) => Ok(Response::new(GetComputedStyleResponse {
+                response: Some(GetComputedStyleResponseEnum::Style(ComputedStyle { properties })),
+            })),
+            Err(e) => {
+                error!("GetComputedStyle failed: {}", e);
+                let code = match e {
+                    ServiceError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+                    _ => ErrorCode::Internal,
+                };
+                Ok(Response::new(GetComputedStyleResponse {
+                    response: Some(GetComputedStyleResponseEnum::Error(ProtoError {
+                        code: code as i32,
+                        message: e.to_string(),
+                        details: request_id::error_details(),
+                    })),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn set_checked(
+        &self,
+        request: Request<SetCheckedRequest>,
+    ) -> Result<Response<SetCheckedResponse>, Status> {
+        info!("SetChecked request received");
+
+        let req = request.into_inner();
+        let element_ref = req.element.ok_or_else(|| {
+            Status::invalid_argument("Element reference is required")
+        })?;
+        let page = self.get_page(&element_ref.page_id).await?;
+
+        match self.set_checked_state(&page, &element_ref, req.checked).await {
+            Ok(result) => Ok(Response::new(SetCheckedResponse {
+                response: Some(SetCheckedResponseEnum::Result(result)),
+            })),
+            Err(e) => {
+                error!("SetChecked failed: {}", e);
+                let code = match e {
+                    ServiceError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+                    ServiceError::Configuration(_) => ErrorCode::InvalidArgument,
+                    _ => ErrorCode::Internal,
+                };
+                Ok(Response::new(SetCheckedResponse {
+                    response: Some(SetCheckedResponseEnum::Error(ProtoError {
+                        code: code as i32,
+                        message: e.to_string(),
+                        details: request_id::error_details(),
+                    })),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_value(
+        &self,
+        request: Request<GetValueRequest>,
+    ) -> Result<Response<GetValueResponse>, Status> {
+        info!("GetValue request received");
+
+        let req = request.into_inner();
+        let element_ref = req.element.ok_or_else(|| {
+            Status::invalid_argument("Element reference is required")
+        })?;
+        let page = self.get_page(&element_ref.page_id).await?;
+
+        let query = JsBuilder::new(element_ref.selector_type, element_ref.selector).element_query()?;
+        let client = page.get_cdp_client();
+
+        match form::field_value(client.as_ref(), &query).await {
+            Ok(value) => Ok(Response::new(GetValueResponse {
+                response: Some(GetValueResponseEnum::Value(value)),
+            })),
+            Err(e) => {
+                error!("GetValue failed: {}", e);
+                let code = match e {
+                    ServiceError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+                    ServiceError::Configuration(_) => ErrorCode::InvalidArgument,
+                    _ => ErrorCode::Internal,
+                };
+                Ok(Response::new(GetValueResponse {
+                    response: Some(GetValueResponseEnum::Error(ProtoError {
+                        code: code as i32,
+                        message: e.to_string(),
+                        details: request_id::error_details(),
+                    })),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn set_attribute(
+        &self,
+        request: Request<SetAttributeRequest>,
+    ) -> Result<Response<SetAttributeResponse>, Status> {
+        info!("SetAttribute request received");
+
+        let req = request.into_inner();
+        let element_ref = req.element.ok_or_else(|| {
+            Status::invalid_argument("Element reference is required")
+        })?;
+        let page = self.get_page(&element_ref.page_id).await?;
+
+        let query = JsBuilder::new(element_ref.selector_type, element_ref.selector).element_query()?;
+        let client = page.get_cdp_client();
+        let value = Some(req.value.as_str()).filter(|value| !value.is_empty());
+
+        match attribute::set_attribute(client.as_ref(), &query, &req.name, value).await {
+            Ok(()) => Ok(Response::new(SetAttributeResponse {
+                response: Some(SetAttributeResponseEnum::Success(Empty {})),
+            })),
+            Err(e) => {
+                error!("SetAttribute failed: {}", e);
+                let code = match e {
+                    ServiceError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+                    ServiceError::Configuration(_) => ErrorCode::InvalidArgument,
+                    ServiceError::ScriptExecutionFailed(_) => ErrorCode::EvaluationFailed,
+                    _ => ErrorCode::Internal,
+                };
+                Ok(Response::new(SetAttributeResponse {
+                    response: Some(SetAttributeResponseEnum::Error(ProtoError {
+                        code: code as i32,
+                        message: e.to_string(),
+                        details: request_id::error_details(),
+                    })),
+                }))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::chaser_oxide::v1::field_value::Kind as FieldValueKind;
 
     #[tokio::test]
     async fn test_service_creation() {
@@ -1150,4 +1588,682 @@ mod tests {
         // Would require mock session manager
         assert!(true);
     }
+
+    #[tokio::test]
+    async fn test_get_element_accessibility_not_found() {
+        use crate::session::mock::{MockPage, MockSessionManager};
+
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "null", "value": null } }),
+            )
+            .await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client,
+            )))
+            .await;
+        let service = ElementGrpcService::new(manager);
+
+        let response = service
+            .get_element_accessibility(Request::new(GetElementAccessibilityRequest {
+                element: Some(ElementRef {
+                    page_id,
+                    selector_type: 1,
+                    selector: "#missing".to_string(),
+                    ..Default::default()
+                }),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match response.response {
+            Some(GetElementAccessibilityResponseEnum::Error(e)) => {
+                assert_eq!(e.code, ErrorCode::ElementNotFound as i32);
+            }
+            other => panic!("Expected ElementNotFound, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_computed_style_returns_requested_properties() {
+        use crate::session::mock::{MockPage, MockSessionManager};
+
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "node", "objectId": "node-1" } }),
+            )
+            .await;
+        client
+            .stub_method("DOM.describeNode", serde_json::json!({ "node": { "backendNodeId": 5 } }))
+            .await;
+        client
+            .stub_method("DOM.pushNodesByBackendIdsToFrontend", serde_json::json!({ "nodeIds": [12] }))
+            .await;
+        client
+            .stub_method(
+                "CSS.getComputedStyleForNode",
+                serde_json::json!({ "computedStyle": [
+                    { "name": "color", "value": "rgb(0, 0, 0)" },
+                    { "name": "display", "value": "flex" },
+                ]}),
+            )
+            .await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client.clone(),
+            )))
+            .await;
+        let service = ElementGrpcService::new(manager);
+        let element = ElementRef {
+            page_id,
+            selector_type: 1,
+            selector: "#menu".to_string(),
+            ..Default::default()
+        };
+
+        let response = service
+            .get_computed_style(Request::new(GetComputedStyleRequest {
+                element: Some(element.clone()),
+                properties: vec!["display".to_string(), "not-a-property".to_string()],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(GetComputedStyleResponseEnum::Style(style)) = response.response else {
+            panic!("Expected computed style, got {:?}", response.response);
+        };
+        assert_eq!(style.properties.len(), 1);
+        assert_eq!(style.properties[0].name, "display");
+        assert_eq!(style.properties[0].value, "flex");
+        assert_eq!(
+            client.recorded_calls("CSS.getComputedStyleForNode").await[0]["nodeId"],
+            12
+        );
+
+        let response = service
+            .get_computed_style(Request::new(GetComputedStyleRequest {
+                element: Some(element),
+                properties: vec![],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(GetComputedStyleResponseEnum::Style(style)) = response.response else {
+            panic!("Expected computed style, got {:?}", response.response);
+        };
+        assert_eq!(style.properties.len(), 2);
+    }
+
+    /// Page whose element reports the given visibility on successive checks
+    async fn page_with_visibility(states: &[bool]) -> (Arc<crate::session::mock::MockSessionManager>, String) {
+        use crate::session::mock::{MockPage, MockSessionManager};
+        use crate::session::traits::EvaluationResult;
+
+        let page = Arc::new(MockPage::new("browser".to_string(), Default::default()));
+        let results = states
+            .iter()
+            .map(|visible| {
+                let reason = if *visible { "visible" } else { "display: none" };
+                EvaluationResult::String(serde_json::json!({ "visible": visible, "reason": reason }).to_string())
+            })
+            .collect();
+        page.stub_evaluate("getComputedStyle(el)", results).await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager.register_page(page).await;
+        (manager, page_id)
+    }
+
+    #[tokio::test]
+    async fn test_bounding_box_page_coordinates_include_scroll() {
+        use crate::session::mock::{MockPage, MockSessionManager};
+        use crate::session::traits::EvaluationResult;
+
+        // Element 100px below the top of the viewport after scrolling the page down by 1500px
+        let page = Arc::new(MockPage::new("browser".to_string(), Default::default()));
+        let rect = serde_json::json!({
+            "x": 20.0, "y": 100.0, "width": 200.0, "height": 50.0,
+            "scrollX": 0.0, "scrollY": 1500.0, "devicePixelRatio": 2.0,
+            "viewportWidth": 1280.0, "viewportHeight": 720.0,
+        });
+        page.stub_evaluate("getBoundingClientRect", vec![EvaluationResult::String(rect.to_string())]).await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager.register_page(page).await;
+        let service = ElementGrpcService::new(manager);
+
+        let response = service
+            .get_bounding_box(Request::new(GetBoundingBoxRequest {
+                element: Some(ElementRef {
+                    page_id,
+                    selector_type: 1,
+                    selector: "#footer".to_string(),
+                    ..Default::default()
+                }),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(GetBoundingBoxResponseEnum::Box(bbox)) = response.response else {
+            panic!("Expected a bounding box");
+        };
+        assert_eq!((bbox.x, bbox.y), (20.0, 100.0));
+        assert_eq!((bbox.page_x, bbox.page_y), (20.0, 1600.0));
+        assert_ne!(bbox.page_y, bbox.y);
+        assert_eq!(bbox.device_pixel_ratio, 2.0);
+        assert_eq!((bbox.device_x, bbox.device_y, bbox.device_width, bbox.device_height), (40.0, 200.0, 400.0, 100.0));
+        assert!(bbox.in_viewport);
+
+        // Scrolled back to the top, the element sits below the fold
+        let below = ElementGrpcService::bounding_box_from_json(&serde_json::json!({
+            "x": 20.0, "y": 1600.0, "width": 200.0, "height": 50.0,
+            "viewportWidth": 1280.0, "viewportHeight": 720.0,
+        }));
+        assert_eq!(below.page_y, below.y);
+        assert_eq!(below.device_pixel_ratio, 1.0);
+        assert!(!below.in_viewport);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_element_visible_after_delay() {
+        let (manager, page_id) = page_with_visibility(&[false, false, true]).await;
+        let service = ElementGrpcService::new(manager);
+
+        let started = std::time::Instant::now();
+        let response = service
+            .wait_for_element(Request::new(WaitForElementRequest {
+                page_id: page_id.clone(),
+                selector_type: 1,
+                selector: "#banner".to_string(),
+                state: WaitState::Visible as i32,
+                timeout: 5000,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match response.response {
+            Some(WaitForElementResponseEnum::Element(element)) => {
+                assert_eq!(element.page_id, page_id);
+                assert_eq!(element.selector, "#banner");
+            }
+            other => panic!("Expected element, got {:?}", other),
+        }
+        // Two hidden polls before the element shows up
+        assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_element_visible_times_out_with_last_state() {
+        let (manager, page_id) = page_with_visibility(&[false]).await;
+        let service = ElementGrpcService::new(manager);
+
+        let response = service
+            .wait_for_element(Request::new(WaitForElementRequest {
+                page_id,
+                selector_type: 1,
+                selector: "#banner".to_string(),
+                state: WaitState::Visible as i32,
+                timeout: 250,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match response.response {
+            Some(WaitForElementResponseEnum::Error(e)) => {
+                assert_eq!(e.code, ErrorCode::Timeout as i32);
+                assert!(e.message.contains("display: none"));
+            }
+            other => panic!("Expected timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_element_hidden() {
+        let (manager, page_id) = page_with_visibility(&[true, false]).await;
+        let service = ElementGrpcService::new(manager);
+
+        let response = service
+            .wait_for_element(Request::new(WaitForElementRequest {
+                page_id,
+                selector_type: 1,
+                selector: "#spinner".to_string(),
+                state: WaitState::Hidden as i32,
+                timeout: 5000,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(matches!(response.response, Some(WaitForElementResponseEnum::Element(_))));
+    }
+
+    /// Page whose input reports the given `checked` states on successive reads
+    async fn page_with_checkbox(
+        input_type: &str,
+        states: &[bool],
+    ) -> (ElementGrpcService, Arc<crate::cdp::mock::MockCdpClient>, ElementRef) {
+        use crate::session::mock::{MockPage, MockSessionManager};
+        use crate::session::traits::EvaluationResult;
+
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "node", "objectId": "node-1" } }),
+            )
+            .await;
+        client
+            .stub_method("DOM.describeNode", serde_json::json!({ "node": { "backendNodeId": 5 } }))
+            .await;
+        client
+            .stub_method(
+                "DOM.getBoxModel",
+                serde_json::json!({ "model": { "content": [10, 20, 30, 20, 30, 40, 10, 40] } }),
+            )
+            .await;
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let results = states
+            .iter()
+            .map(|checked| {
+                EvaluationResult::String(serde_json::json!({ "type": input_type, "checked": checked }).to_string())
+            })
+            .collect();
+        page.stub_evaluate("el.checked", results).await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager.register_page(page).await;
+        let element = ElementRef {
+            page_id,
+            selector_type: 1,
+            selector: "#terms".to_string(),
+            ..Default::default()
+        };
+        (ElementGrpcService::new(manager), client, element)
+    }
+
+    async fn set_checked(service: &ElementGrpcService, element: ElementRef, checked: bool) -> Option<SetCheckedResponseEnum> {
+        service
+            .set_checked(Request::new(SetCheckedRequest {
+                element: Some(element),
+                checked,
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .response
+    }
+
+    #[tokio::test]
+    async fn test_set_checked_checks_with_real_click() {
+        let (service, client, element) = page_with_checkbox("checkbox", &[false, true]).await;
+
+        let response = set_checked(&service, element, true).await;
+
+        assert_eq!(
+            response,
+            Some(SetCheckedResponseEnum::Result(SetCheckedResult { checked: true, changed: true }))
+        );
+        let events = client.recorded_calls("Input.dispatchMouseEvent").await;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["type"], "mousePressed");
+        assert_eq!(events[0]["x"], 20.0);
+        assert_eq!(events[0]["y"], 30.0);
+        assert_eq!(client.recorded_calls("DOM.getBoxModel").await[0]["backendNodeId"], 5);
+    }
+
+    #[tokio::test]
+    async fn test_set_checked_unchecks() {
+        let (service, client, element) = page_with_checkbox("checkbox", &[true, false]).await;
+
+        let response = set_checked(&service, element, false).await;
+
+        assert_eq!(
+            response,
+            Some(SetCheckedResponseEnum::Result(SetCheckedResult { checked: false, changed: true }))
+        );
+        assert_eq!(client.recorded_calls("Input.dispatchMouseEvent").await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_set_checked_noop_when_already_in_state() {
+        let (service, client, element) = page_with_checkbox("checkbox", &[true]).await;
+
+        let response = set_checked(&service, element, true).await;
+
+        assert_eq!(
+            response,
+            Some(SetCheckedResponseEnum::Result(SetCheckedResult { checked: true, changed: false }))
+        );
+        assert!(client.recorded_calls("Input.dispatchMouseEvent").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_checked_reports_swallowed_click() {
+        let (service, _client, element) = page_with_checkbox("checkbox", &[false, false]).await;
+
+        match set_checked(&service, element, true).await {
+            Some(SetCheckedResponseEnum::Error(e)) => {
+                assert_eq!(e.code, ErrorCode::Internal as i32);
+                assert!(e.message.contains("still unchecked"));
+            }
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_checked_rejects_unchecking_radio() {
+        let (service, client, element) = page_with_checkbox("radio", &[true]).await;
+
+        match set_checked(&service, element, false).await {
+            Some(SetCheckedResponseEnum::Error(e)) => {
+                assert_eq!(e.code, ErrorCode::InvalidArgument as i32);
+                assert!(e.message.contains("cannot be unchecked"));
+            }
+            other => panic!("Expected error, got {:?}", other),
+        }
+        assert!(client.recorded_calls("Input.dispatchMouseEvent").await.is_empty());
+    }
+
+    /// Page whose element resolves to a handle and returns `value` from functions called on it
+    async fn page_with_handle(
+        value: serde_json::Value,
+    ) -> (ElementGrpcService, Arc<crate::cdp::mock::MockCdpClient>, ElementRef) {
+        use crate::session::mock::{MockPage, MockSessionManager};
+
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "node", "objectId": "node-1" } }),
+            )
+            .await;
+        client
+            .stub_method("Runtime.callFunctionOn", serde_json::json!({ "result": { "type": "object", "value": value } }))
+            .await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone())))
+            .await;
+        let element = ElementRef {
+            page_id,
+            selector_type: 1,
+            selector: "#field".to_string(),
+            ..Default::default()
+        };
+        (ElementGrpcService::new(manager), client, element)
+    }
+
+    /// Page whose element reports `value` from the field value function
+    async fn page_with_field(value: serde_json::Value) -> (ElementGrpcService, ElementRef) {
+        let (service, _client, element) = page_with_handle(value).await;
+        (service, element)
+    }
+
+    async fn get_value(service: &ElementGrpcService, element: ElementRef) -> Option<GetValueResponseEnum> {
+        service
+            .get_value(Request::new(GetValueRequest { element: Some(element) }))
+            .await
+            .unwrap()
+            .into_inner()
+            .response
+    }
+
+    #[tokio::test]
+    async fn test_get_value_text_input() {
+        let (service, element) = page_with_field(serde_json::json!({ "kind": "text", "value": "alice@example.com" })).await;
+
+        let Some(GetValueResponseEnum::Value(value)) = get_value(&service, element).await else {
+            panic!("Expected a field value");
+        };
+        assert_eq!(value.kind, FieldValueKind::Text as i32);
+        assert_eq!(value.value, "alice@example.com");
+        assert!(!value.checked);
+        assert!(value.selected_values.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_value_checkbox() {
+        let (service, element) =
+            page_with_field(serde_json::json!({ "kind": "checkable", "value": "on", "checked": true })).await;
+
+        let Some(GetValueResponseEnum::Value(value)) = get_value(&service, element).await else {
+            panic!("Expected a field value");
+        };
+        assert_eq!(value.kind, FieldValueKind::Checkable as i32);
+        assert!(value.checked);
+        assert_eq!(value.value, "on");
+    }
+
+    #[tokio::test]
+    async fn test_get_value_multi_select() {
+        let (service, element) = page_with_field(serde_json::json!({
+            "kind": "multi_select",
+            "value": "red",
+            "values": ["red", "blue"],
+        }))
+        .await;
+
+        let Some(GetValueResponseEnum::Value(value)) = get_value(&service, element).await else {
+            panic!("Expected a field value");
+        };
+        assert_eq!(value.kind, FieldValueKind::MultiSelect as i32);
+        assert_eq!(value.selected_values, vec!["red", "blue"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_value_rejects_non_form_element() {
+        let (service, element) = page_with_field(serde_json::json!({ "kind": "unsupported", "tag": "div" })).await;
+
+        match get_value(&service, element).await {
+            Some(GetValueResponseEnum::Error(e)) => {
+                assert_eq!(e.code, ErrorCode::InvalidArgument as i32);
+                assert!(e.message.contains("<div>"));
+            }
+            other => panic!("Expected an error, got {:?}", other),
+        }
+    }
+
+    async fn set_attribute(
+        service: &ElementGrpcService,
+        element: &ElementRef,
+        name: &str,
+        value: &str,
+    ) -> Option<SetAttributeResponseEnum> {
+        service
+            .set_attribute(Request::new(SetAttributeRequest {
+                element: Some(element.clone()),
+                name: name.to_string(),
+                value: value.to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .response
+    }
+
+    #[tokio::test]
+    async fn test_set_attribute_sets_updates_and_removes() {
+        let (service, client, element) = page_with_handle(serde_json::Value::Null).await;
+
+        for (name, value) in [("data-state", "open"), ("data-state", "closed"), ("data-state", "")] {
+            assert!(matches!(
+                set_attribute(&service, &element, name, value).await,
+                Some(SetAttributeResponseEnum::Success(_))
+            ));
+        }
+
+        let calls = client.recorded_calls("Runtime.callFunctionOn").await;
+        let arguments: Vec<_> = calls.iter().map(|call| call["arguments"].clone()).collect();
+        assert_eq!(
+            arguments,
+            vec![
+                serde_json::json!([{ "value": "data-state" }, { "value": "open" }]),
+                serde_json::json!([{ "value": "data-state" }, { "value": "closed" }]),
+                serde_json::json!([{ "value": "data-state" }, { "value": null }]),
+            ]
+        );
+        assert!(calls.iter().all(|call| call["objectId"] == "node-1"));
+        assert!(client.recorded_calls("Input.dispatchMouseEvent").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_attribute_requires_name() {
+        let (service, _client, element) = page_with_handle(serde_json::Value::Null).await;
+
+        match set_attribute(&service, &element, "", "x").await {
+            Some(SetAttributeResponseEnum::Error(e)) => assert_eq!(e.code, ErrorCode::InvalidArgument as i32),
+            other => panic!("Expected an error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_html_outer_uses_browser_serialization() {
+        let (service, client, element) = page_with_checkbox("checkbox", &[false]).await;
+        let markup = r#"<input id="terms" type="checkbox" required="">"#;
+        client
+            .stub_method("DOM.getOuterHTML", serde_json::json!({ "outerHTML": markup }))
+            .await;
+
+        let response = service
+            .get_html(Request::new(GetHtmlRequest {
+                element: Some(element),
+                outer: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(
+            response.response,
+            Some(GetHtmlResponseEnum::Html(HtmlValue { html: markup.to_string() }))
+        );
+        assert_eq!(client.recorded_calls("DOM.getOuterHTML").await[0]["backendNodeId"], 5);
+    }
+
+    #[tokio::test]
+    async fn test_type_uses_insert_text_when_fast_flag_set() {
+        use crate::session::mock::{MockPage, MockSessionManager};
+        use crate::session::traits::EvaluationResult;
+
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        page.stub_evaluate("'focused'", vec![EvaluationResult::String("focused".to_string())]).await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager.register_page(page.clone()).await;
+        let service = ElementGrpcService::new(manager);
+        let element = ElementRef {
+            page_id,
+            selector_type: 1,
+            selector: "#comment".to_string(),
+            ..Default::default()
+        };
+        let text = "a long comment ".repeat(200);
+
+        let response = service
+            .r#type(Request::new(TypeRequest {
+                element: Some(element.clone()),
+                text: text.clone(),
+                insert_text: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(matches!(response.response, Some(TypeResponseEnum::Success(_))));
+        let inserted = client.recorded_calls("Input.insertText").await;
+        assert_eq!(inserted.len(), 1);
+        assert_eq!(inserted[0]["text"], text);
+        assert!(client.recorded_calls("Input.dispatchKeyEvent").await.is_empty());
+        assert!(!page.evaluated_scripts().await.iter().any(|s| s.contains("el.value =")));
+
+        // Without the flag the default path does not insert text
+        service
+            .r#type(Request::new(TypeRequest {
+                element: Some(element),
+                text: "short".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(client.recorded_calls("Input.insertText").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_press_key_dispatches_parsed_combination() {
+        use crate::session::mock::{MockPage, MockSessionManager};
+
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager.register_page(page).await;
+        let service = ElementGrpcService::new(manager);
+        let element = ElementRef {
+            page_id,
+            selector_type: 1,
+            selector: "#editor".to_string(),
+            ..Default::default()
+        };
+
+        let response = service
+            .press_key(Request::new(PressKeyRequest {
+                element: Some(element.clone()),
+                key: "Ctrl+k".to_string(),
+                modifiers: vec![crate::chaser_oxide::v1::KeyModifier::Shift as i32],
+                delay: 0,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(matches!(response.response, Some(PressKeyResponseEnum::Success(_))));
+        let events = client.recorded_calls("Input.dispatchKeyEvent").await;
+        let keys: Vec<_> = events.iter().map(|e| (e["type"].as_str().unwrap(), e["key"].as_str().unwrap())).collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("rawKeyDown", "Control"),
+                ("rawKeyDown", "Shift"),
+                ("rawKeyDown", "K"),
+                ("keyUp", "K"),
+                ("keyUp", "Shift"),
+                ("keyUp", "Control"),
+            ]
+        );
+        assert_eq!(events[2]["modifiers"], 10);
+        assert_eq!(events[2]["windowsVirtualKeyCode"], 75);
+
+        let response = service
+            .press_key(Request::new(PressKeyRequest {
+                element: Some(element),
+                key: "Ctrl+Nope".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        match response.response {
+            Some(PressKeyResponseEnum::Error(error)) => {
+                assert_eq!(error.code, ErrorCode::InvalidArgument as i32);
+                assert!(error.message.contains("unknown key 'Nope'"));
+            }
+            other => panic!("expected an error, got {:?}", other),
+        }
+        assert_eq!(client.recorded_calls("Input.dispatchKeyEvent").await.len(), 6);
+    }
 }
@@ -0,0 +1,67 @@
+//! Element computed style queries
+//!
+//! Reads resolved CSS values with `CSS.getComputedStyleForNode`, which reports
+//! the same values as `window.getComputedStyle` without running page scripts.
+
+use crate::cdp::traits::CdpClient;
+use crate::chaser_oxide::v1::StyleProperty;
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Get the computed style of a DOM node
+///
+/// # Arguments
+/// * `client` - CDP client of the page
+/// * `backend_node_id` - Node to inspect (see `accessibility::resolve_backend_node_id`)
+/// * `names` - Properties to return, in this order; all properties when empty
+///
+/// Requested names the browser does not know are left out.
+pub async fn computed_style(client: &dyn CdpClient, backend_node_id: i64, names: &[String]) -> Result<Vec<StyleProperty>> {
+    client.enable_domain("DOM").await?;
+    client.enable_domain("CSS").await?;
+
+    // Node ids are only assigned once the document has been requested
+    client
+        .call_method("DOM.getDocument", serde_json::json!({ "depth": 0 }))
+        .await?;
+    let pushed = client
+        .call_method(
+            "DOM.pushNodesByBackendIdsToFrontend",
+            serde_json::json!({ "backendNodeIds": [backend_node_id] }),
+        )
+        .await?;
+    let node_id = pushed
+        .pointer("/nodeIds/0")
+        .and_then(|v| v.as_i64())
+        .filter(|id| *id != 0)
+        .ok_or_else(|| Error::element_not_found(format!("backend node {}", backend_node_id)))?;
+
+    let result = client
+        .call_method("CSS.getComputedStyleForNode", serde_json::json!({ "nodeId": node_id }))
+        .await?;
+    let properties: Vec<StyleProperty> = result
+        .get("computedStyle")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(|p| StyleProperty {
+            name: p["name"].as_str().unwrap_or_default().to_string(),
+            value: p["value"].as_str().unwrap_or_default().to_string(),
+        })
+        .collect();
+
+    if names.is_empty() {
+        return Ok(properties);
+    }
+
+    let mut by_name: HashMap<String, String> = properties.into_iter().map(|p| (p.name, p.value)).collect();
+    Ok(names
+        .iter()
+        .filter_map(|name| {
+            by_name.remove(name).map(|value| StyleProperty {
+                name: name.clone(),
+                value,
+            })
+        })
+        .collect())
+}
@@ -55,6 +55,36 @@ async fn test_element_finder_text_selector() {
     assert!(result.is_ok() || result.is_err());
 }
 
+#[tokio::test]
+async fn test_element_finder_pierce_selector_reaches_shadow_root_button() {
+    use crate::session::traits::EvaluationResult;
+
+    // <my-widget> renders its button inside an open shadow root
+    let page = Arc::new(MockPage::new(
+        "test-browser".to_string(),
+        crate::session::traits::PageOptions::default(),
+    ));
+    page.stub_evaluate(
+        "el.shadowRoot",
+        vec![EvaluationResult::String(
+            r#"{"element_id":"pierce-single","tag_name":"button","text_content":"Buy now"}"#.to_string(),
+        )],
+    )
+    .await;
+
+    let finder = ElementFinder::new(page.clone());
+    let element = finder
+        .find_element(SelectorType::Pierce, "my-widget >>> button.buy")
+        .await
+        .unwrap();
+    assert_eq!(element.tag_name, "button");
+    assert_eq!(element.text_content.as_deref(), Some("Buy now"));
+
+    let scripts = page.evaluated_scripts().await;
+    assert!(scripts[0].contains(super::js_utils::PIERCE_QUERY_FUNCTION));
+    assert!(scripts[0].contains("('my-widget >>> button.buy', false)"));
+}
+
 #[tokio::test]
 async fn test_element_finder_find_multiple() {
     let browser_id = "test-browser".to_string();
@@ -334,10 +364,12 @@ async fn test_element_interactor_scroll_into_view() {
 
 #[tokio::test]
 async fn test_element_interactor_press_key() {
-    let element = Arc::new(MockElement::new(
+    let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+    let element = Arc::new(MockElement::with_cdp_client(
         "test-page".to_string(),
         "input".to_string(),
         None,
+        client.clone(),
     ));
 
     let interactor = ElementInteractor::new(element);
@@ -345,6 +377,15 @@ async fn test_element_interactor_press_key() {
     // Test press key with combination
     let result = interactor.press_key("Ctrl+A", Some(50)).await;
     assert!(result.is_ok());
+
+    // The chord is dispatched on the page's CDP client
+    let calls = client.recorded_calls("Input.dispatchKeyEvent").await;
+    let keys: Vec<_> = calls.iter().map(|c| (c["type"].as_str().unwrap(), c["key"].as_str().unwrap())).collect();
+    assert_eq!(
+        keys,
+        vec![("rawKeyDown", "Control"), ("rawKeyDown", "A"), ("keyUp", "A"), ("keyUp", "Control")]
+    );
+    assert!(interactor.press_key("Ctrl+", None).await.is_err());
 }
 
 #[tokio::test]
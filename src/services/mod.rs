@@ -45,7 +45,7 @@ pub use traits::{
     PageSnapshot, ScreenshotData, NavigationResult, EvaluationResult, WaitCondition,
     SelectorType, ElementInfo, BoundingBox,
     ProfileType, Profile, ProfilePreset, CustomProfileOptions,
-    ProfileOptions, CustomOptions, Viewport, AppliedFeatures,
+    ProfileOptions, CustomOptions, Viewport, AppliedFeatures, ActiveProfile, AppliedFeature,
     Fingerprint, HeadersFingerprint, NavigatorFingerprint, ScreenFingerprint, WebGLFingerprint,
     EventType, Event, PageEvent, ConsoleEvent, NetworkEvent, ConsoleLevel,
 };
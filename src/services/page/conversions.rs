@@ -4,9 +4,11 @@
 
 use crate::session::{
     NavigationOptions, ScreenshotOptions, LoadState, ScreenshotFormat, ClipRegion,
-    EvaluationResult as SessionEvaluationResult,
+    EvaluationResult as SessionEvaluationResult, NavigationResult as SessionNavigationResult,
 };
 use crate::services::traits::EvaluationResult;
+use crate::config::MAX_NAVIGATION_TIMEOUT_MS;
+use crate::Error;
 use crate::chaser_oxide::v1::{
     NavigationOptions as ProtoNavigationOptions,
     navigation_options,
@@ -16,6 +18,7 @@ use crate::chaser_oxide::v1::{
     evaluation_result,
     PageInfo as ProtoPageInfo,
     NavigationResult as ProtoNavigationResult,
+    RedirectHop as ProtoRedirectHop,
     ScreenshotResult,
 };
 
@@ -24,7 +27,13 @@ use crate::chaser_oxide::v1::{
 // ============================================================================
 
 /// 将 proto NavigationOptions 转换为内部 NavigationOptions
-pub fn proto_to_navigation_options(opts: ProtoNavigationOptions) -> NavigationOptions {
+///
+/// `timeout` 为 0 时使用 `default_timeout`；负数返回配置错误；
+/// 超过 [`MAX_NAVIGATION_TIMEOUT_MS`] 的值会被截断到上限。
+pub fn proto_to_navigation_options(
+    opts: ProtoNavigationOptions,
+    default_timeout: u64,
+) -> Result<NavigationOptions, Error> {
     let wait_until = match opts.wait_until() {
         navigation_options::LoadState::Unspecified => LoadState::Load,
         navigation_options::LoadState::Load => LoadState::Load,
@@ -33,10 +42,37 @@ pub fn proto_to_navigation_options(opts: ProtoNavigationOptions) -> NavigationOp
         navigation_options::LoadState::NetworkAlmostIdle => LoadState::NetworkAlmostIdle,
     };
 
-    NavigationOptions {
-        timeout: opts.timeout.max(0) as u64,
+    let timeout = match opts.timeout {
+        t if t < 0 => {
+            return Err(Error::configuration(format!(
+                "Navigation timeout must not be negative, got {}",
+                t
+            )))
+        }
+        0 => default_timeout,
+        t => t as u64,
+    };
+
+    let referer = match opts.referer.as_str() {
+        "" => None,
+        referer => {
+            let parsed = url::Url::parse(referer)
+                .map_err(|e| Error::configuration(format!("Invalid referer {}: {}", referer, e)))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(Error::configuration(format!(
+                    "Referer must be an http(s) URL, got {}",
+                    referer
+                )));
+            }
+            Some(referer.to_string())
+        }
+    };
+
+    Ok(NavigationOptions {
+        timeout: timeout.min(MAX_NAVIGATION_TIMEOUT_MS),
         wait_until,
-    }
+        referer,
+    })
 }
 
 /// 将 proto ScreenshotOptions 转换为内部 ScreenshotOptions
@@ -62,6 +98,8 @@ pub fn proto_to_screenshot_options(opts: ProtoScreenshotOptions) -> ScreenshotOp
         quality: if opts.quality > 0 { Some(opts.quality as u8) } else { None },
         full_page: opts.full_page,
         clip,
+        max_width: if opts.max_width > 0 { Some(opts.max_width as u32) } else { None },
+        max_height: if opts.max_height > 0 { Some(opts.max_height as u32) } else { None },
     }
 }
 
@@ -121,10 +159,11 @@ pub fn evaluation_result_to_proto(result: EvaluationResult) -> ProtoEvaluationRe
         }
         EvaluationResult::Object(v) => {
             tracing::debug!("evaluation_result_to_proto: Object variant with value={:?}", v);
-            // 将 JSON 对象转换为字符串表示
+            // 对象和数组序列化为 JSON，并在 type 中区分数组
+            let value_type = if v.is_array() { "array" } else { "object" };
             ProtoEvaluationResult {
-                response: Some(evaluation_result::Response::StringValue(v.to_string())),
-                r#type: "object".to_string(),
+                response: Some(evaluation_result::Response::JsonValue(v.to_string())),
+                r#type: value_type.to_string(),
                 class_name: String::new(),
             }
         }
@@ -152,6 +191,8 @@ pub fn evaluation_result_from_session(result: SessionEvaluationResult) -> Evalua
 
 /// 将截图数据转换为 ScreenshotResult
 pub fn screenshot_to_proto(data: Vec<u8>, format: ScreenshotFormat) -> ScreenshotResult {
+    let (width, height) = crate::session::screenshot::dimensions(&data).unwrap_or((0, 0));
+
     ScreenshotResult {
         data,
         format: match format {
@@ -159,21 +200,27 @@ pub fn screenshot_to_proto(data: Vec<u8>, format: ScreenshotFormat) -> Screensho
             ScreenshotFormat::Jpeg => "jpeg".to_string(),
             ScreenshotFormat::WebP => "webp".to_string(),
         },
-        width: 1920,  // TODO: 从实际截图获取
-        height: 1080, // TODO: 从实际截图获取
+        width: width as i32,
+        height: height as i32,
+        path: String::new(),
     }
 }
 
 /// 将导航结果转换为 proto NavigationResult
-pub fn navigation_result_to_proto(
-    url: String,
-    status_code: u16,
-    is_loaded: bool,
-) -> ProtoNavigationResult {
+pub fn navigation_result_to_proto(result: SessionNavigationResult) -> ProtoNavigationResult {
     ProtoNavigationResult {
-        url,
-        status_code: status_code as i32,
-        is_loaded,
+        url: result.url,
+        status_code: result.status_code as i32,
+        is_loaded: result.is_loaded,
+        redirect_chain: result
+            .redirect_chain
+            .into_iter()
+            .map(|hop| ProtoRedirectHop {
+                url: hop.url,
+                status_code: hop.status_code as i32,
+            })
+            .collect(),
+        redirect_chain_truncated: result.redirect_chain_truncated,
     }
 }
 
@@ -195,7 +242,7 @@ mod tests {
             ..Default::default()
         };
 
-        let opts = proto_to_navigation_options(proto_opts);
+        let opts = proto_to_navigation_options(proto_opts, 30000).unwrap();
         assert_eq!(opts.timeout, 30000);
         assert!(matches!(opts.wait_until, LoadState::NetworkIdle));
     }
@@ -208,11 +255,56 @@ mod tests {
             ..Default::default()
         };
 
-        let opts = proto_to_navigation_options(proto_opts);
-        assert_eq!(opts.timeout, 0);
+        let opts = proto_to_navigation_options(proto_opts, 45000).unwrap();
+        assert_eq!(opts.timeout, 45000); // Configured default
         assert!(matches!(opts.wait_until, LoadState::Load)); // Default
     }
 
+    #[test]
+    fn test_proto_to_navigation_options_explicit_timeout() {
+        let opts = proto_to_navigation_options(
+            ProtoNavigationOptions { timeout: 5000, ..Default::default() },
+            45000,
+        )
+        .unwrap();
+        assert_eq!(opts.timeout, 5000);
+
+        let opts = proto_to_navigation_options(
+            ProtoNavigationOptions { timeout: i32::MAX, ..Default::default() },
+            45000,
+        )
+        .unwrap();
+        assert_eq!(opts.timeout, MAX_NAVIGATION_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn test_proto_to_navigation_options_referer() {
+        let referer = |referer: &str| {
+            proto_to_navigation_options(
+                ProtoNavigationOptions { referer: referer.to_string(), ..Default::default() },
+                45000,
+            )
+        };
+
+        assert_eq!(referer("").unwrap().referer, None);
+        assert_eq!(
+            referer("https://www.google.com/").unwrap().referer.as_deref(),
+            Some("https://www.google.com/")
+        );
+        for invalid in ["google.com", "javascript:alert(1)"] {
+            assert!(matches!(referer(invalid), Err(Error::Configuration(_))), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_proto_to_navigation_options_negative_timeout() {
+        let result = proto_to_navigation_options(
+            ProtoNavigationOptions { timeout: -1, ..Default::default() },
+            45000,
+        );
+        assert!(matches!(result, Err(Error::Configuration(_))));
+    }
+
     #[test]
     fn test_proto_to_navigation_options_all_load_states() {
         // Test Load state
@@ -220,7 +312,7 @@ mod tests {
             timeout: 10000,
             wait_until: ProtoLoadState::Load as i32,
             ..Default::default()
-        });
+        }, 30000).unwrap();
         assert!(matches!(opts1.wait_until, LoadState::Load));
 
         // Test DomContentLoaded state
@@ -228,7 +320,7 @@ mod tests {
             timeout: 10000,
             wait_until: ProtoLoadState::DomContentLoaded as i32,
             ..Default::default()
-        });
+        }, 30000).unwrap();
         assert!(matches!(opts2.wait_until, LoadState::DOMContentLoaded));
 
         // Test NetworkAlmostIdle state
@@ -236,7 +328,7 @@ mod tests {
             timeout: 10000,
             wait_until: ProtoLoadState::NetworkAlmostIdle as i32,
             ..Default::default()
-        });
+        }, 30000).unwrap();
         assert!(matches!(opts3.wait_until, LoadState::NetworkAlmostIdle));
     }
 
@@ -343,14 +435,25 @@ mod tests {
 
     #[test]
     fn test_evaluation_result_object_conversion() {
-        let json_value = serde_json::json!({"key": "value"});
-        let result = EvaluationResult::Object(json_value);
+        let json_value = serde_json::json!({"key": "value", "nested": {"list": [1, "two", null]}});
+        let result = EvaluationResult::Object(json_value.clone());
+        let proto = evaluation_result_to_proto(result);
+        let Some(evaluation_result::Response::JsonValue(json)) = proto.response else {
+            panic!("objects are returned as json_value");
+        };
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), json_value);
+        assert_eq!(proto.r#type, "object");
+    }
+
+    #[test]
+    fn test_evaluation_result_array_conversion() {
+        let result = EvaluationResult::Object(serde_json::json!([1, 2, 3]));
         let proto = evaluation_result_to_proto(result);
         assert!(matches!(
             proto.response,
-            Some(evaluation_result::Response::StringValue(_))
+            Some(evaluation_result::Response::JsonValue(ref json)) if json == "[1,2,3]"
         ));
-        assert_eq!(proto.r#type, "object");
+        assert_eq!(proto.r#type, "array");
     }
 
     #[test]
@@ -370,9 +473,20 @@ mod tests {
 
     #[test]
     fn test_navigation_result_to_proto() {
-        let result = navigation_result_to_proto("https://example.com".to_string(), 200, true);
+        let result = navigation_result_to_proto(SessionNavigationResult {
+            url: "https://example.com".to_string(),
+            status_code: 200,
+            is_loaded: true,
+            redirect_chain: vec![crate::session::RedirectHop {
+                url: "https://example.com".to_string(),
+                status_code: 200,
+            }],
+            redirect_chain_truncated: false,
+        });
         assert_eq!(result.url, "https://example.com");
         assert_eq!(result.status_code, 200);
         assert_eq!(result.is_loaded, true);
+        assert_eq!(result.redirect_chain.len(), 1);
+        assert_eq!(result.redirect_chain[0].status_code, 200);
     }
 }
@@ -0,0 +1,53 @@
+//! 控制台日志相关的 RPC 方法处理器
+//!
+//! 包括：get_console_logs
+//!
+//! 每个页面在会话层维护最近控制台消息的环形缓冲区，
+//! 不订阅事件流的客户端可在操作后按游标读取新增的消息。
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use crate::session::{ConsoleEntry, SessionManager};
+use crate::chaser_oxide::v1::{
+    get_console_logs_response::Response as GetConsoleLogsResponseEnum,
+    GetConsoleLogsRequest, GetConsoleLogsResponse,
+    ConsoleLogEntry, ConsoleLogs as ProtoConsoleLogs,
+};
+use super::super::response;
+
+/// 实现 PageService trait 中的控制台日志方法
+pub struct ConsoleHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+impl<S> ConsoleHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 返回游标之后缓冲的控制台消息
+    pub async fn get_console_logs(&self, request: Request<GetConsoleLogsRequest>) -> Result<Response<GetConsoleLogsResponse>, Status> {
+        let req = request.into_inner();
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+
+        let logs = page.console_logs(req.cursor);
+        Ok(Response::new(GetConsoleLogsResponse {
+            response: Some(GetConsoleLogsResponseEnum::Logs(ProtoConsoleLogs {
+                entries: logs.entries.into_iter().map(entry_to_proto).collect(),
+                next_cursor: logs.next_cursor,
+                dropped: logs.dropped,
+            })),
+        }))
+    }
+}
+
+fn entry_to_proto(entry: ConsoleEntry) -> ConsoleLogEntry {
+    ConsoleLogEntry {
+        seq: entry.seq,
+        level: entry.level,
+        args: entry.args,
+        timestamp: entry.timestamp,
+        url: entry.url,
+        line: entry.line as i32,
+        column: entry.column as i32,
+    }
+}
@@ -1,17 +1,38 @@
 //! 内容操作相关的 RPC 方法处理器
 //!
-//! 包括：screenshot, get_snapshot, get_content, set_content, get_pdf
+//! 包括：screenshot, screenshot_stream, compare_screenshot, get_snapshot, get_content, set_content, get_pdf
+//!
+//! 截图和 PDF 可通过 `output_path` 保存到配置的输出目录中，此时只返回文件路径。
+//! 较大的截图可通过 `screenshot_stream` 分块返回，避免超出单条 gRPC 消息的大小限制。
+//! `compare_screenshot` 将截图与客户端提供的基准图逐像素比较，用于视觉回归检测。
+//! 截图可先等待页面稳定、临时禁用 CSS 动画，并受捕获超时限制。
+//! PDF 可临时模拟打印媒体和配色方案，使 `@media print` 样式生效，生成后恢复页面原有的媒体覆盖。
 
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
-use crate::session::{SessionManager, EvaluationResult as SessionEvaluationResult};
+use tracing::warn;
+use crate::config::MAX_NAVIGATION_TIMEOUT_MS;
+use crate::session::cancel::cancelled_error;
+use crate::session::screenshot::DefaultQuality;
+use crate::session::{
+    screenshot, ClipRegion, PageContext, ScreenshotFormat, ScreenshotOptions, SessionManager,
+    EvaluationResult as SessionEvaluationResult,
+};
 use crate::chaser_oxide::v1::{
     screenshot_response::Response as ScreenshotResponseEnum,
     get_snapshot_response::Response as GetSnapshotResponseEnum,
     get_content_response::Response as GetContentResponseEnum,
     set_content_response::Response as SetContentResponseEnum,
     get_pdf_response::Response as GetPdfResponseEnum,
+    compare_screenshot_response::Response as CompareScreenshotResponseEnum,
     ScreenshotRequest, ScreenshotResponse,
+    ScreenshotStreamRequest, ScreenshotChunk,
+    CompareScreenshotRequest, CompareScreenshotResponse, CompareScreenshotResult,
     GetSnapshotRequest, GetSnapshotResponse,
     GetContentRequest, GetContentResponse,
     SetContentRequest, SetContentResponse,
@@ -19,30 +40,84 @@ use crate::chaser_oxide::v1::{
     PageSnapshot as ProtoPageSnapshot,
     NodeInfo,
     PageContent,
+    ScreenshotOptions as ProtoScreenshotOptions,
+    MediaFeature,
 };
+use crate::services::common::cancel::run_cancellable;
+use crate::services::common::html::format_html;
+use crate::services::common::limits::check_result_size;
+use crate::services::common::output::write_output_file;
 use super::super::{conversions, response, scripts};
+use super::emulation::{validate_media_feature, MediaOverrides};
+
+/// 请求未指定超时（0）时的截图超时（毫秒），与 CDP 层的截图命令超时一致
+const DEFAULT_SCREENSHOT_TIMEOUT_MS: u64 = 90_000;
+
+/// 截图前等待页面稳定的最长时间（毫秒）
+const MAX_SCREENSHOT_WAIT_MS: u64 = 60_000;
+
+/// 流式截图未指定分块大小（0）时每块的字节数
+const DEFAULT_SCREENSHOT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 流式截图每块的最大字节数，远低于 gRPC 默认的 4MB 消息上限
+const MAX_SCREENSHOT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 发往客户端的分块缓冲数量，客户端读取较慢时形成背压
+const SCREENSHOT_CHUNK_BUFFER: usize = 8;
+
+/// 截图分块的流式响应
+pub type ScreenshotChunkStream = ReceiverStream<Result<ScreenshotChunk, Status>>;
 
 /// 实现 PageService trait 中的内容操作相关方法
 pub struct ContentHandlers<S> {
     pub session_manager: Arc<S>,
+    /// 页面内容的最大字节数
+    pub max_result_bytes: usize,
+    /// 截图和 PDF 允许保存到的目录
+    pub output_dir: Option<PathBuf>,
+    /// 请求未指定质量时 JPEG/WebP 使用的默认质量
+    pub default_quality: DefaultQuality,
+    /// SetMediaFeatures 设置的媒体覆盖，生成 PDF 后据此恢复
+    pub media_overrides: Arc<MediaOverrides>,
 }
 
 impl<S> ContentHandlers<S>
 where
     S: SessionManager + Send + Sync + 'static,
 {
+    /// 转换请求中的截图选项，并为未指定质量（0）的 JPEG/WebP 应用配置的默认质量
+    fn screenshot_options(&self, options: Option<ProtoScreenshotOptions>) -> crate::Result<ScreenshotOptions> {
+        let options = options.unwrap_or_default();
+        if options.quality != 0 {
+            screenshot::validate_quality("quality", options.quality as i64)?;
+        }
+
+        let mut options = conversions::proto_to_screenshot_options(options);
+        options.quality = self.default_quality.resolve(options.format, options.quality);
+        Ok(options)
+    }
+
     /// 截取页面截图
     pub async fn screenshot(&self, request: Request<ScreenshotRequest>) -> Result<Response<ScreenshotResponse>, Status> {
         let req = request.into_inner();
+        let (timeout, wait) = capture_durations(req.timeout, req.wait_ms).map_err(response::error_to_status)?;
+
+        let options = self.screenshot_options(req.options).map_err(response::error_to_status)?;
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
-                let options = conversions::proto_to_screenshot_options(req.options.unwrap_or_default());
                 let format = options.format;
 
-                match page.screenshot(options).await {
+                match capture(page, options, timeout, wait, req.disable_animations).await {
                     Ok(data) => {
-                        let result = conversions::screenshot_to_proto(data, format);
+                        let mut result = conversions::screenshot_to_proto(data, format);
+                        // 保存到服务端路径时只返回路径，不返回图片数据
+                        if !req.output_path.is_empty() {
+                            let path = write_output_file(self.output_dir.as_deref(), &req.output_path, &result.data)
+                                .map_err(response::error_to_status)?;
+                            result.path = path.display().to_string();
+                            result.data = Vec::new();
+                        }
                         Ok(Response::new(ScreenshotResponse {
                             response: Some(ScreenshotResponseEnum::Result(result)),
                         }))
@@ -54,6 +129,106 @@ where
         }
     }
 
+    /// 截取页面截图，按 `chunk_size` 分块流式返回
+    ///
+    /// 截图完成后才开始发送，捕获失败时 RPC 直接返回错误而不是空的流。
+    pub async fn screenshot_stream(&self, request: Request<ScreenshotStreamRequest>) -> Result<Response<ScreenshotChunkStream>, Status> {
+        let req = request.into_inner();
+        let (timeout, wait) = capture_durations(req.timeout, req.wait_ms).map_err(response::error_to_status)?;
+        let chunk_size = match req.chunk_size {
+            n if n < 0 => return Err(Status::invalid_argument("chunk_size must not be negative")),
+            0 => DEFAULT_SCREENSHOT_CHUNK_SIZE,
+            n => (n as usize).min(MAX_SCREENSHOT_CHUNK_SIZE),
+        };
+
+        let options = self.screenshot_options(req.options).map_err(response::error_to_status)?;
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let format = options.format;
+
+                let data = capture(page, options, timeout, wait, req.disable_animations)
+                    .await
+                    .map_err(response::error_to_status)?;
+                let image = conversions::screenshot_to_proto(data, format);
+
+                let (tx, rx) = mpsc::channel(SCREENSHOT_CHUNK_BUFFER);
+                tokio::spawn(async move {
+                    let total_size = image.data.len() as i64;
+                    let mut offset = 0;
+                    for piece in image.data.chunks(chunk_size) {
+                        let chunk = ScreenshotChunk {
+                            data: piece.to_vec(),
+                            offset,
+                            total_size,
+                            format: image.format.clone(),
+                            width: image.width,
+                            height: image.height,
+                        };
+                        // 客户端断开时停止发送
+                        if tx.send(Ok(chunk)).await.is_err() {
+                            return;
+                        }
+                        offset += piece.len() as i64;
+                    }
+                });
+
+                Ok(Response::new(ReceiverStream::new(rx)))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 截取页面或元素截图，并与基准图逐像素比较
+    ///
+    /// 截图总是以无损 PNG 捕获；尺寸与基准图不一致时返回 InvalidArgument。
+    pub async fn compare_screenshot(&self, request: Request<CompareScreenshotRequest>) -> Result<Response<CompareScreenshotResponse>, Status> {
+        let req = request.into_inner();
+
+        if req.baseline.is_empty() {
+            return Err(Status::invalid_argument("baseline is required"));
+        }
+        if !(0..=255).contains(&req.tolerance) {
+            return Err(Status::invalid_argument("tolerance must be between 0 and 255"));
+        }
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let mut options = conversions::proto_to_screenshot_options(req.options.unwrap_or_default());
+                // 有损格式会引入与页面变化无关的差异
+                options.format = ScreenshotFormat::Png;
+                options.quality = None;
+                if let Some(element) = req.element.as_ref() {
+                    options.clip = Some(element_clip(page.as_ref(), &element.element_id).await.map_err(response::error_to_status)?);
+                }
+
+                let timeout = Duration::from_millis(DEFAULT_SCREENSHOT_TIMEOUT_MS);
+                let data = capture(page, options, timeout, Duration::ZERO, false)
+                    .await
+                    .map_err(response::error_to_status)?;
+                let mut diff = screenshot::compare(&data, &req.baseline, req.tolerance as u8, req.include_diff_image)
+                    .map_err(response::error_to_status)?;
+                let diff_image = match diff.diff_image.take() {
+                    Some(image) => screenshot::encode(&image.into(), ScreenshotFormat::Png, None)
+                        .map_err(response::error_to_status)?,
+                    None => Vec::new(),
+                };
+
+                Ok(Response::new(CompareScreenshotResponse {
+                    response: Some(CompareScreenshotResponseEnum::Result(CompareScreenshotResult {
+                        diff_percentage: diff.diff_percentage(),
+                        diff_pixels: diff.diff_pixels as i64,
+                        total_pixels: diff.total_pixels() as i64,
+                        width: diff.width as i32,
+                        height: diff.height as i32,
+                        diff_image,
+                    })),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
     /// 获取页面快照
     pub async fn get_snapshot(&self, request: Request<GetSnapshotRequest>) -> Result<Response<GetSnapshotResponse>, Status> {
         let req = request.into_inner();
@@ -119,8 +294,11 @@ where
             Ok(page) => {
                 match page.get_content().await {
                     Ok(html) => {
+                        let formatted = format_html(&html, req.format(), false);
+                        check_result_size("Page content", formatted.len().max(html.len()), self.max_result_bytes)
+                            .map_err(response::error_to_status)?;
                         let content = PageContent {
-                            html: html.clone(),
+                            html: formatted,
                             text: html, // 占位符 - 实际实现应提取纯文本
                             scripts: vec![],
                             stylesheets: vec![],
@@ -160,23 +338,562 @@ where
         let req = request.into_inner();
 
         match self.session_manager.get_page(&req.page_id).await {
-            Ok(_page) => {
-                // 注意：完整的 CDP Page.printToPDF 实现需要：
-                // 1. CDP 连接支持 Page.printToPDF 命令
-                // 2. PDF 选项处理（landscape, display_header_footer 等）
-                // 3. Base64 编码的 PDF 数据解码
-                //
-                // 目前返回未实现错误
-                let error = response::error_to_proto(crate::Error::internal(
-                    "PDF generation via CDP Page.printToPDF is not yet implemented. \
-                     Please add CDP support for Page.printToPDF command to enable this feature."
-                ));
+            Ok(page) => {
+                let current = self.media_overrides.current(&req.page_id).await;
+                let printed = match pdf_media(&req, &current) {
+                    Ok(media) => print_with_media(page.get_cdp_client().as_ref(), &req, media.map(|m| (m, current))).await,
+                    Err(e) => Err(e),
+                };
+                let result = match printed {
+                    Ok(data) if req.output_path.is_empty() => Ok(GetPdfResponseEnum::PdfData(data)),
+                    // 保存到服务端路径时只返回路径，不返回 PDF 数据
+                    Ok(data) => write_output_file(self.output_dir.as_deref(), &req.output_path, &data)
+                        .map(|path| GetPdfResponseEnum::Path(path.display().to_string())),
+                    Err(e) => Err(e),
+                };
 
                 Ok(Response::new(GetPdfResponse {
-                    response: Some(GetPdfResponseEnum::Error(error)),
+                    response: Some(result.unwrap_or_else(|e| GetPdfResponseEnum::Error(response::error_to_proto(e)))),
                 }))
             }
             Err(e) => Err(response::error_to_status(e)),
         }
     }
 }
+
+/// PDF 需要的媒体模拟：在页面当前的媒体覆盖上切换为打印媒体和/或设置配色方案
+///
+/// 两者都未请求时返回 None，不改变页面的媒体。
+fn pdf_media(req: &GetPdfRequest, current: &serde_json::Value) -> crate::Result<Option<serde_json::Value>> {
+    if !req.emulate_print_media && req.color_scheme.is_empty() {
+        return Ok(None);
+    }
+
+    let mut params = current.clone();
+    if req.emulate_print_media {
+        params["media"] = serde_json::json!("print");
+    }
+    if !req.color_scheme.is_empty() {
+        let scheme = MediaFeature {
+            name: "prefers-color-scheme".to_string(),
+            value: req.color_scheme.clone(),
+        };
+        validate_media_feature(&scheme).map_err(crate::Error::configuration)?;
+
+        let mut features: Vec<_> = params["features"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|f| f["name"] != scheme.name.as_str())
+            .cloned()
+            .collect();
+        features.push(serde_json::json!({ "name": scheme.name, "value": scheme.value }));
+        params["features"] = serde_json::json!(features);
+    }
+
+    Ok(Some(params))
+}
+
+/// 在 `media`（模拟参数，恢复参数）生效期间生成 PDF，之后无论成败都恢复原有的媒体
+async fn print_with_media(
+    client: &dyn crate::cdp::CdpClient,
+    req: &GetPdfRequest,
+    media: Option<(serde_json::Value, serde_json::Value)>,
+) -> crate::Result<Vec<u8>> {
+    let Some((emulated, restore)) = media else {
+        return print_to_pdf(client, req).await;
+    };
+
+    client.call_method("Emulation.setEmulatedMedia", emulated).await?;
+    let result = print_to_pdf(client, req).await;
+    if let Err(e) = client.call_method("Emulation.setEmulatedMedia", restore).await {
+        warn!("Failed to restore emulated media after printing: {}", e);
+    }
+
+    result
+}
+
+/// 通过 Page.printToPDF 生成 PDF；值为 0 或空的选项使用浏览器默认值
+async fn print_to_pdf(client: &dyn crate::cdp::CdpClient, req: &GetPdfRequest) -> crate::Result<Vec<u8>> {
+    let mut params = serde_json::json!({
+        "landscape": req.landscape,
+        "displayHeaderFooter": req.display_header_footer,
+        "printBackground": req.print_background,
+    });
+    for (name, value) in [
+        ("scale", req.scale),
+        ("paperWidth", req.paper_width),
+        ("paperHeight", req.paper_height),
+        ("marginTop", req.margin_top),
+        ("marginBottom", req.margin_bottom),
+        ("marginLeft", req.margin_left),
+        ("marginRight", req.margin_right),
+    ] {
+        if value > 0.0 {
+            params[name] = serde_json::json!(value);
+        }
+    }
+    if !req.page_ranges.is_empty() {
+        params["pageRanges"] = serde_json::json!(req.page_ranges);
+    }
+
+    let result = client.call_method("Page.printToPDF", params).await?;
+    let data = result["data"]
+        .as_str()
+        .ok_or_else(|| crate::Error::cdp("Page.printToPDF returned no data"))?;
+
+    BASE64
+        .decode(data)
+        .map_err(|e| crate::Error::cdp(format!("Failed to decode PDF data: {}", e)))
+}
+
+/// 校验并换算截图的捕获超时和等待时间（毫秒）
+fn capture_durations(timeout_ms: i32, wait_ms: i32) -> crate::Result<(Duration, Duration)> {
+    if timeout_ms < 0 || wait_ms < 0 {
+        return Err(crate::Error::configuration("timeout and wait_ms must not be negative"));
+    }
+    let timeout = match timeout_ms as u64 {
+        0 => DEFAULT_SCREENSHOT_TIMEOUT_MS,
+        ms => ms.min(MAX_NAVIGATION_TIMEOUT_MS),
+    };
+    let wait = (wait_ms as u64).min(MAX_SCREENSHOT_WAIT_MS);
+
+    Ok((Duration::from_millis(timeout), Duration::from_millis(wait)))
+}
+
+/// 计算元素在页面中的区域（CSS 像素），用作截图的裁剪区域
+async fn element_clip(page: &dyn PageContext, element_id: &str) -> crate::Result<ClipRegion> {
+    let selector = format!("[data-element-id=\"{}\"]", element_id);
+    let script = format!(
+        "(() => {{ const el = document.querySelector({}); if (!el) return null; \
+         const r = el.getBoundingClientRect(); \
+         return {{ x: r.x + window.scrollX, y: r.y + window.scrollY, width: r.width, height: r.height }}; }})()",
+        serde_json::json!(selector)
+    );
+
+    match page.evaluate(&script, false).await? {
+        SessionEvaluationResult::Object(rect) => {
+            let value = |key: &str| rect.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            if value("width") <= 0.0 || value("height") <= 0.0 {
+                return Err(crate::Error::configuration(format!("Element {} has no visible area", element_id)));
+            }
+            Ok(ClipRegion {
+                x: value("x"),
+                y: value("y"),
+                width: value("width"),
+                height: value("height"),
+                scale: 1.0,
+            })
+        }
+        _ => Err(crate::Error::element_not_found(element_id)),
+    }
+}
+
+/// 截图：可选地禁用动画并等待页面稳定，捕获本身受 `timeout` 限制
+///
+/// 捕获在独立任务中进行，客户端取消请求时不再继续等待和分块捕获。
+/// 注入的样式在捕获结束后（包括失败、超时或取消）都会被移除。
+async fn capture(
+    page: Arc<dyn PageContext>,
+    options: ScreenshotOptions,
+    timeout: Duration,
+    wait: Duration,
+    disable_animations: bool,
+) -> crate::Result<Vec<u8>> {
+    run_cancellable(move |cancel| async move {
+        if disable_animations {
+            page.evaluate(scripts::FREEZE_ANIMATIONS_SCRIPT, false).await?;
+        }
+
+        let captured = async {
+            tokio::time::sleep(wait).await;
+            tokio::time::timeout(timeout, page.screenshot(options))
+                .await
+                .map_err(|_| crate::Error::timeout(format!("Screenshot not captured within {}ms", timeout.as_millis())))?
+        };
+        let result = tokio::select! {
+            result = captured => result,
+            _ = cancel.cancelled() => Err(cancelled_error("Screenshot")),
+        };
+
+        if disable_animations {
+            if let Err(e) = page.evaluate(scripts::UNFREEZE_ANIMATIONS_SCRIPT, false).await {
+                warn!("Failed to remove animation freeze style: {}", e);
+            }
+        }
+
+        result
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+    use uuid::Uuid;
+
+    async fn handlers_with_output_dir() -> (ContentHandlers<MockSessionManager>, Arc<MockCdpClient>, String, PathBuf) {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client.clone(),
+            )))
+            .await;
+        let output_dir = std::env::temp_dir().join(format!("chaser-oxide-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&output_dir).unwrap();
+
+        let handlers = ContentHandlers {
+            session_manager: manager,
+            max_result_bytes: usize::MAX,
+            output_dir: Some(output_dir.clone()),
+            default_quality: DefaultQuality::default(),
+            media_overrides: Arc::new(MediaOverrides::default()),
+        };
+        (handlers, client, page_id, output_dir)
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_and_pdf_saved_to_output_dir() {
+        let (handlers, client, page_id, output_dir) = handlers_with_output_dir().await;
+
+        let response = handlers
+            .screenshot(Request::new(ScreenshotRequest {
+                page_id: page_id.clone(),
+                options: None,
+                output_path: "shots/home.png".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ScreenshotResponseEnum::Result(result)) = response.response else {
+            panic!("expected a screenshot result");
+        };
+        assert!(result.data.is_empty());
+        let path = output_dir.canonicalize().unwrap().join("shots/home.png");
+        assert_eq!(result.path, path.display().to_string());
+        assert_eq!(&std::fs::read(&path).unwrap()[..4], b"\x89PNG");
+
+        client
+            .stub_method("Page.printToPDF", serde_json::json!({ "data": BASE64.encode(b"%PDF-1.7") }))
+            .await;
+        let response = handlers
+            .get_pdf(Request::new(GetPdfRequest {
+                page_id,
+                landscape: true,
+                output_path: "report.pdf".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(GetPdfResponseEnum::Path(path)) = response.response else {
+            panic!("expected a PDF path, got {:?}", response.response);
+        };
+        assert_eq!(std::fs::read(&path).unwrap(), b"%PDF-1.7");
+        let params = &client.recorded_calls("Page.printToPDF").await[0];
+        assert_eq!(params["landscape"], true);
+        assert!(params.get("scale").is_none());
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_output_path_traversal_rejected() {
+        let (handlers, client, page_id, output_dir) = handlers_with_output_dir().await;
+        let outside = output_dir.parent().unwrap().join("escaped.png");
+
+        for output_path in ["../escaped.png", "shots/../../escaped.png", outside.to_str().unwrap()] {
+            let status = handlers
+                .screenshot(Request::new(ScreenshotRequest {
+                    page_id: page_id.clone(),
+                    options: None,
+                    output_path: output_path.to_string(),
+                    ..Default::default()
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument, "{}", output_path);
+        }
+        assert!(!outside.exists());
+
+        client
+            .stub_method("Page.printToPDF", serde_json::json!({ "data": BASE64.encode(b"%PDF-1.7") }))
+            .await;
+        let response = handlers
+            .get_pdf(Request::new(GetPdfRequest {
+                page_id,
+                output_path: "../escaped.pdf".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        match response.response {
+            Some(GetPdfResponseEnum::Error(e)) => assert!(e.message.contains("outside the output directory")),
+            other => panic!("expected an error, got {:?}", other),
+        }
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    // 测试：输出目录中的符号链接（文件或目录）指向目录外时拒绝保存，且不在目录外创建任何内容
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_path_symlinks_rejected() {
+        let (handlers, _client, page_id, output_dir) = handlers_with_output_dir().await;
+        let outside = std::env::temp_dir().join(format!("chaser-oxide-outside-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&outside).unwrap();
+        let target = outside.join("target.png");
+        std::fs::write(&target, b"original").unwrap();
+        std::os::unix::fs::symlink(&target, output_dir.join("link.png")).unwrap();
+        std::os::unix::fs::symlink(&outside, output_dir.join("linked")).unwrap();
+
+        for output_path in ["link.png", "linked/shot.png", "linked/new/shot.png"] {
+            let status = handlers
+                .screenshot(Request::new(ScreenshotRequest {
+                    page_id: page_id.clone(),
+                    options: None,
+                    output_path: output_path.to_string(),
+                    ..Default::default()
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument, "{}", output_path);
+        }
+        assert_eq!(std::fs::read(&target).unwrap(), b"original");
+        assert!(!outside.join("shot.png").exists());
+        assert!(!outside.join("new").exists());
+
+        // 目录内的普通文件可以被覆盖
+        std::fs::write(output_dir.join("existing.png"), b"old").unwrap();
+        handlers
+            .screenshot(Request::new(ScreenshotRequest {
+                page_id,
+                options: None,
+                output_path: "existing.png".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(&std::fs::read(output_dir.join("existing.png")).unwrap()[..4], b"\x89PNG");
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+        std::fs::remove_dir_all(&outside).unwrap();
+    }
+
+    // 测试：生成 PDF 期间模拟打印媒体和深色配色，之后恢复 SetMediaFeatures 设置的覆盖
+    #[tokio::test]
+    async fn test_pdf_emulates_print_media_and_restores_override() {
+        let (handlers, client, page_id, output_dir) = handlers_with_output_dir().await;
+        client
+            .stub_method("Page.printToPDF", serde_json::json!({ "data": BASE64.encode(b"%PDF-1.7") }))
+            .await;
+        let reduced_motion = serde_json::json!({
+            "media": "",
+            "features": [{ "name": "prefers-reduced-motion", "value": "reduce" }],
+        });
+        handlers.media_overrides.set(&page_id, Some(reduced_motion.clone())).await;
+
+        let response = handlers
+            .get_pdf(Request::new(GetPdfRequest {
+                page_id: page_id.clone(),
+                emulate_print_media: true,
+                color_scheme: "dark".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(response.response, Some(GetPdfResponseEnum::PdfData(_))));
+
+        let methods: Vec<_> = client
+            .recorded_methods()
+            .await
+            .into_iter()
+            .filter(|m| m == "Emulation.setEmulatedMedia" || m == "Page.printToPDF")
+            .collect();
+        assert_eq!(methods, vec!["Emulation.setEmulatedMedia", "Page.printToPDF", "Emulation.setEmulatedMedia"]);
+        let media = client.recorded_calls("Emulation.setEmulatedMedia").await;
+        assert_eq!(
+            media[0],
+            serde_json::json!({
+                "media": "print",
+                "features": [
+                    { "name": "prefers-reduced-motion", "value": "reduce" },
+                    { "name": "prefers-color-scheme", "value": "dark" },
+                ],
+            })
+        );
+        assert_eq!(media[1], reduced_motion);
+
+        // 未请求模拟时不改变媒体；无效的配色方案被拒绝
+        handlers
+            .get_pdf(Request::new(GetPdfRequest { page_id: page_id.clone(), ..Default::default() }))
+            .await
+            .unwrap();
+        let response = handlers
+            .get_pdf(Request::new(GetPdfRequest {
+                page_id,
+                color_scheme: "purple".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(GetPdfResponseEnum::Error(error)) = response.response else {
+            panic!("expected an error for an invalid color scheme");
+        };
+        assert!(error.message.contains("purple"));
+        assert_eq!(client.recorded_calls("Emulation.setEmulatedMedia").await.len(), 2);
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    // 测试：禁用动画时先注入临时样式，截图后再移除
+    #[tokio::test]
+    async fn test_screenshot_freezes_animations_during_capture() {
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::new("browser".to_string(), Default::default()));
+        let page_id = manager.register_page(page.clone()).await;
+        let handlers = ContentHandlers {
+            session_manager: manager,
+            max_result_bytes: usize::MAX,
+            output_dir: None,
+            default_quality: DefaultQuality::default(),
+            media_overrides: Arc::new(MediaOverrides::default()),
+        };
+
+        let response = handlers
+            .screenshot(Request::new(ScreenshotRequest {
+                page_id: page_id.clone(),
+                wait_ms: 10,
+                disable_animations: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ScreenshotResponseEnum::Result(result)) = response.response else {
+            panic!("expected a screenshot result");
+        };
+        assert!(!result.data.is_empty());
+        assert_eq!(
+            page.evaluated_scripts().await,
+            vec![scripts::FREEZE_ANIMATIONS_SCRIPT, scripts::UNFREEZE_ANIMATIONS_SCRIPT]
+        );
+
+        let status = handlers
+            .screenshot(Request::new(ScreenshotRequest {
+                page_id,
+                timeout: -1,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    // 测试：质量为 0 的 JPEG 请求使用配置的默认质量，PNG 不带质量，超出范围的质量被拒绝
+    #[tokio::test]
+    async fn test_zero_quality_jpeg_uses_configured_default() {
+        use crate::chaser_oxide::v1::screenshot_options::Format;
+
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::new("browser".to_string(), Default::default())))
+            .await;
+        let handlers = ContentHandlers {
+            session_manager: manager,
+            max_result_bytes: usize::MAX,
+            output_dir: None,
+            default_quality: DefaultQuality { jpeg: 72, webp: 55 },
+            media_overrides: Arc::new(MediaOverrides::default()),
+        };
+        let request = |format: Format, quality: i32| ProtoScreenshotOptions {
+            format: format as i32,
+            quality,
+            ..Default::default()
+        };
+
+        let options = handlers.screenshot_options(Some(request(Format::Jpeg, 0))).unwrap();
+        assert_eq!(options.quality, Some(72));
+        let options = handlers.screenshot_options(Some(request(Format::Webp, 0))).unwrap();
+        assert_eq!(options.quality, Some(55));
+        let options = handlers.screenshot_options(Some(request(Format::Jpeg, 90))).unwrap();
+        assert_eq!(options.quality, Some(90));
+        let options = handlers.screenshot_options(Some(request(Format::Png, 0))).unwrap();
+        assert_eq!(options.quality, None);
+
+        let status = handlers
+            .screenshot(Request::new(ScreenshotRequest {
+                page_id,
+                options: Some(request(Format::Jpeg, 101)),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    // 测试：流式截图按分块大小拆分，按顺序拼接后与原图逐字节一致
+    #[tokio::test]
+    async fn test_screenshot_stream_chunks_reassemble_to_source_image() {
+        use tokio_stream::StreamExt;
+
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::new("browser".to_string(), Default::default()));
+        let page_id = manager.register_page(page.clone()).await;
+        let handlers = ContentHandlers {
+            session_manager: manager,
+            max_result_bytes: usize::MAX,
+            output_dir: None,
+            default_quality: DefaultQuality::default(),
+            media_overrides: Arc::new(MediaOverrides::default()),
+        };
+        let source = page.screenshot(ScreenshotOptions::default()).await.unwrap();
+
+        let mut stream = handlers
+            .screenshot_stream(Request::new(ScreenshotStreamRequest {
+                page_id: page_id.clone(),
+                chunk_size: 4,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            chunks.push(chunk.unwrap());
+        }
+        assert_eq!(chunks.len(), source.len().div_ceil(4));
+        assert!(chunks.iter().all(|c| c.data.len() <= 4 && c.total_size == source.len() as i64));
+        assert_eq!(chunks[0].format, "png");
+
+        let mut reassembled = Vec::new();
+        for chunk in &chunks {
+            assert_eq!(chunk.offset, reassembled.len() as i64);
+            reassembled.extend_from_slice(&chunk.data);
+        }
+        assert_eq!(reassembled, source);
+
+        let status = handlers
+            .screenshot_stream(Request::new(ScreenshotStreamRequest {
+                page_id,
+                chunk_size: -1,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
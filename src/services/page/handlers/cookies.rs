@@ -1,20 +1,26 @@
 //! Cookie 管理相关的 RPC 方法处理器
 //!
-//! 包括：get_cookies, set_cookies, clear_cookies
+//! 包括：get_cookies, set_cookies, clear_cookies, replace_cookies
 
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
+use crate::cdp::traits::CdpClient;
+use crate::cdp::types::CookieParam;
 use crate::session::{SessionManager, EvaluationResult as SessionEvaluationResult};
+use crate::Error;
 use crate::chaser_oxide::v1::{
     get_cookies_response::Response as GetCookiesResponseEnum,
     set_cookies_response::Response as SetCookiesResponseEnum,
     clear_cookies_response::Response as ClearCookiesResponseEnum,
+    replace_cookies_response::Response as ReplaceCookiesResponseEnum,
     GetCookiesRequest, GetCookiesResponse,
     SetCookiesRequest, SetCookiesResponse,
     ClearCookiesRequest, ClearCookiesResponse,
+    ReplaceCookiesRequest, ReplaceCookiesResponse,
     Cookie,
     Cookies,
     Empty,
+    SameSite,
 };
 use super::super::{response, scripts};
 
@@ -47,7 +53,7 @@ where
                                     http_only: obj.get("http_only").and_then(|v| v.as_bool()).unwrap_or(false),
                                     secure: obj.get("secure").and_then(|v| v.as_bool()).unwrap_or(false),
                                     session: obj.get("session").and_then(|v| v.as_bool()).unwrap_or(false),
-                                    same_site: obj.get("same_site").and_then(|v| v.as_str()).unwrap_or("Lax").to_string(),
+                                    same_site: same_site_from_cdp(obj.get("same_site").and_then(|v| v.as_str())) as i32,
                                 })
                             }).collect();
 
@@ -76,12 +82,23 @@ where
     pub async fn set_cookies(&self, request: Request<SetCookiesRequest>) -> Result<Response<SetCookiesResponse>, Status> {
         let req = request.into_inner();
 
+        // 先校验全部 sameSite，避免只写入一部分 cookie
+        let same_sites = req
+            .cookies
+            .iter()
+            .map(|cookie| same_site_to_cdp(cookie.same_site))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(response::error_to_status)?;
+
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
                 // 使用 document.cookie 设置每个 cookie
-                for cookie in req.cookies {
-                    let cookie_string = format!("{}={}; Domain={}; Path={}",
-                        cookie.name, cookie.value, cookie.domain, cookie.path);
+                for (cookie, same_site) in req.cookies.into_iter().zip(same_sites) {
+                    let mut cookie_string = format!("{}={}; Domain={}; Path={}; SameSite={}",
+                        cookie.name, cookie.value, cookie.domain, cookie.path, same_site);
+                    if cookie.secure {
+                        cookie_string.push_str("; Secure");
+                    }
 
                     let script = format!("document.cookie = {}", serde_json::json!(cookie_string));
 
@@ -119,4 +136,243 @@ where
             Err(e) => Err(response::error_to_status(e)),
         }
     }
+
+    /// 用给定的 cookie 替换整个 Cookie 存储
+    ///
+    /// 先校验全部 cookie，再清空并通过一次 `Network.setCookies` 批量写入，
+    /// 完成后存储中恰好是请求中的 cookie。写入失败时恢复原有的 cookie 并返回错误
+    pub async fn replace_cookies(&self, request: Request<ReplaceCookiesRequest>) -> Result<Response<ReplaceCookiesResponse>, Status> {
+        let req = request.into_inner();
+
+        let cookies = req
+            .cookies
+            .into_iter()
+            .map(cookie_to_cdp)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(response::error_to_status)?;
+
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+        let client = page.get_cdp_client();
+        let previous = client.get_all_cookies().await.map_err(response::error_to_status)?;
+        client.clear_browser_cookies().await.map_err(response::error_to_status)?;
+        if !cookies.is_empty() {
+            if let Err(e) = client.set_cookies(&cookies).await {
+                restore_cookies(client.as_ref(), &previous).await;
+                return Err(response::error_to_status(e));
+            }
+        }
+
+        Ok(Response::new(ReplaceCookiesResponse {
+            response: Some(ReplaceCookiesResponseEnum::Success(Empty {})),
+        }))
+    }
+}
+
+/// 替换失败后写回原有的 cookie，恢复失败时只记录警告
+///
+/// 读出的会话 cookie 不带过期时间（见 [`CookieParam`]），写回后仍是会话 cookie。
+async fn restore_cookies(client: &dyn CdpClient, previous: &[CookieParam]) {
+    let restored = async {
+        client.clear_browser_cookies().await?;
+        if !previous.is_empty() {
+            client.set_cookies(previous).await?;
+        }
+        Ok::<_, Error>(())
+    };
+    if let Err(e) = restored.await {
+        tracing::warn!("Failed to restore cookies after a failed replace: {}", e);
+    }
+}
+
+/// 校验 cookie 并转换为 `Network.setCookies` 的参数
+///
+/// SameSite=None 的 cookie 必须设置 Secure，否则浏览器会拒绝写入。
+fn cookie_to_cdp(cookie: Cookie) -> Result<CookieParam, Error> {
+    if cookie.name.is_empty() {
+        return Err(Error::configuration("Cookie name must not be empty"));
+    }
+    if cookie.domain.is_empty() {
+        return Err(Error::configuration(format!("Cookie {} has no domain", cookie.name)));
+    }
+    let same_site = same_site_to_cdp(cookie.same_site)?;
+    if same_site == "None" && !cookie.secure {
+        return Err(Error::configuration(format!(
+            "Cookie {} with SameSite=None must be secure",
+            cookie.name
+        )));
+    }
+
+    Ok(CookieParam {
+        path: if cookie.path.is_empty() { "/".to_string() } else { cookie.path },
+        name: cookie.name,
+        value: cookie.value,
+        domain: cookie.domain,
+        secure: cookie.secure,
+        http_only: cookie.http_only,
+        same_site: Some(same_site.to_string()),
+        expires: (cookie.expires > 0).then_some(cookie.expires as f64),
+    })
+}
+
+/// 将 CDP 的 sameSite（Strict/Lax/None）转换为 proto 枚举，缺失或无法识别时按 Lax 处理
+fn same_site_from_cdp(value: Option<&str>) -> SameSite {
+    match value {
+        Some("Strict") => SameSite::Strict,
+        Some("None") => SameSite::None,
+        _ => SameSite::Lax,
+    }
+}
+
+/// 将 proto 枚举转换为 CDP 的 sameSite，未指定时默认为 Lax，未知取值返回错误
+fn same_site_to_cdp(value: i32) -> Result<&'static str, Error> {
+    match SameSite::try_from(value) {
+        Ok(SameSite::Unspecified) | Ok(SameSite::Lax) => Ok("Lax"),
+        Ok(SameSite::Strict) => Ok("Strict"),
+        Ok(SameSite::None) => Ok("None"),
+        Err(_) => Err(Error::configuration(format!("Unknown cookie sameSite value: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    async fn handlers_with_page() -> (CookieHandlers<MockSessionManager>, String, Arc<MockPage>) {
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::new("browser".to_string(), Default::default()));
+        let page_id = manager.register_page(Arc::clone(&page)).await;
+        (CookieHandlers { session_manager: manager }, page_id, page)
+    }
+
+    #[test]
+    fn test_same_site_round_trips_each_value() {
+        for same_site in [SameSite::Strict, SameSite::Lax, SameSite::None] {
+            let cdp = same_site_to_cdp(same_site as i32).unwrap();
+            assert_eq!(same_site_from_cdp(Some(cdp)), same_site);
+        }
+        assert_eq!(same_site_to_cdp(SameSite::Unspecified as i32).unwrap(), "Lax");
+        assert_eq!(same_site_from_cdp(None), SameSite::Lax);
+        assert!(same_site_to_cdp(42).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replace_cookies_leaves_exactly_the_given_jar() {
+        let client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let page_id = manager.register_page(page).await;
+        let handlers = CookieHandlers { session_manager: manager };
+
+        let stale = CookieParam {
+            name: "stale".to_string(),
+            value: "old".to_string(),
+            domain: "example.com".to_string(),
+            path: "/".to_string(),
+            secure: false,
+            http_only: false,
+            same_site: None,
+            expires: None,
+        };
+        client.set_cookies(&[stale]).await.unwrap();
+
+        let cookies = vec![
+            Cookie {
+                name: "session".to_string(),
+                value: "abc".to_string(),
+                domain: "example.com".to_string(),
+                path: "/".to_string(),
+                http_only: true,
+                secure: true,
+                same_site: SameSite::Strict as i32,
+                ..Default::default()
+            },
+            Cookie {
+                name: "tracking".to_string(),
+                value: "1".to_string(),
+                domain: ".example.org".to_string(),
+                expires: 1_900_000_000,
+                secure: true,
+                same_site: SameSite::None as i32,
+                ..Default::default()
+            },
+        ];
+        handlers
+            .replace_cookies(Request::new(ReplaceCookiesRequest { page_id: page_id.clone(), cookies: cookies.clone() }))
+            .await
+            .unwrap();
+
+        let expected: Vec<CookieParam> = cookies.into_iter().map(|c| cookie_to_cdp(c).unwrap()).collect();
+        assert_eq!(client.get_all_cookies().await.unwrap(), expected);
+        assert_eq!(expected[1].path, "/");
+        assert_eq!(expected[1].expires, Some(1_900_000_000.0));
+        assert_eq!(client.recorded_calls("Network.setCookies").await.len(), 2);
+
+        // One invalid cookie rejects the whole request and leaves the jar untouched
+        let status = handlers
+            .replace_cookies(Request::new(ReplaceCookiesRequest {
+                page_id: page_id.clone(),
+                cookies: vec![Cookie {
+                    name: "insecure".to_string(),
+                    domain: "example.com".to_string(),
+                    same_site: SameSite::None as i32,
+                    ..Default::default()
+                }],
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(client.get_all_cookies().await.unwrap(), expected);
+
+        // 浏览器拒绝写入时恢复原有的 cookie
+        client.fail_next_call("Network.setCookies", "Invalid cookie fields").await;
+        let status = handlers
+            .replace_cookies(Request::new(ReplaceCookiesRequest {
+                page_id,
+                cookies: vec![Cookie {
+                    name: "other".to_string(),
+                    domain: "example.net".to_string(),
+                    ..Default::default()
+                }],
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert_eq!(client.get_all_cookies().await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_set_cookies_writes_same_site_and_rejects_unknown_values() {
+        let (handlers, page_id, page) = handlers_with_page().await;
+        let cookie = |name: &str, same_site: i32| Cookie {
+            name: name.to_string(),
+            value: "1".to_string(),
+            domain: "example.com".to_string(),
+            path: "/".to_string(),
+            secure: true,
+            same_site,
+            ..Default::default()
+        };
+
+        handlers
+            .set_cookies(Request::new(SetCookiesRequest {
+                page_id: page_id.clone(),
+                cookies: vec![cookie("a", SameSite::Strict as i32), cookie("b", SameSite::Unspecified as i32)],
+            }))
+            .await
+            .unwrap();
+        let scripts = page.evaluated_scripts().await;
+        assert!(scripts[0].contains("SameSite=Strict; Secure"));
+        assert!(scripts[1].contains("SameSite=Lax; Secure"));
+
+        let status = handlers
+            .set_cookies(Request::new(SetCookiesRequest {
+                page_id,
+                cookies: vec![cookie("c", SameSite::None as i32), cookie("d", 42)],
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(page.evaluated_scripts().await.len(), 2);
+    }
 }
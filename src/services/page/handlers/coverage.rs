@@ -0,0 +1,342 @@
+//! 代码覆盖率相关的 RPC 方法处理器
+//!
+//! 包括：start_coverage, stop_coverage
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use serde_json::Value;
+use tonic::{Request, Response, Status};
+use crate::cdp::traits::CdpClient;
+use crate::session::SessionManager;
+use crate::chaser_oxide::v1::{
+    start_coverage_response::Response as StartCoverageResponseEnum,
+    stop_coverage_response::Response as StopCoverageResponseEnum,
+    StartCoverageRequest, StartCoverageResponse,
+    StopCoverageRequest, StopCoverageResponse,
+    CoverageRange, CoverageReport, ScriptCoverage, StyleSheetCoverage,
+    Empty,
+};
+use super::super::response;
+
+/// 实现 PageService trait 中的覆盖率相关方法
+pub struct CoverageHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+impl<S> CoverageHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 开始收集 JS/CSS 覆盖率
+    pub async fn start_coverage(&self, request: Request<StartCoverageRequest>) -> Result<Response<StartCoverageResponse>, Status> {
+        let req = request.into_inner();
+        // 两者都未指定时同时收集
+        let (js, css) = if req.js || req.css { (req.js, req.css) } else { (true, true) };
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+
+                if js {
+                    start_js_coverage(client.as_ref()).await.map_err(response::error_to_status)?;
+                }
+                if css {
+                    start_css_coverage(client.as_ref()).await.map_err(response::error_to_status)?;
+                }
+
+                Ok(Response::new(StartCoverageResponse {
+                    response: Some(StartCoverageResponseEnum::Success(Empty {})),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 停止收集覆盖率并返回已使用的字节区间
+    pub async fn stop_coverage(&self, request: Request<StopCoverageRequest>) -> Result<Response<StopCoverageResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+
+                // 未启动的类型在停止时会返回错误，视为未收集
+                let scripts = stop_js_coverage(client.as_ref()).await.ok();
+                let style_sheets = stop_css_coverage(client.as_ref()).await.ok();
+
+                if scripts.is_none() && style_sheets.is_none() {
+                    return Err(Status::failed_precondition("Coverage collection was not started"));
+                }
+
+                Ok(Response::new(StopCoverageResponse {
+                    response: Some(StopCoverageResponseEnum::Report(CoverageReport {
+                        scripts: scripts.unwrap_or_default(),
+                        style_sheets: style_sheets.unwrap_or_default(),
+                    })),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
+
+/// 启用 Profiler 并开始精确覆盖率收集
+async fn start_js_coverage(client: &dyn CdpClient) -> crate::Result<()> {
+    client.enable_domain("Profiler").await?;
+    client
+        .call_method(
+            "Profiler.startPreciseCoverage",
+            serde_json::json!({ "callCount": false, "detailed": true }),
+        )
+        .await?;
+    Ok(())
+}
+
+/// 启用 CSS 并开始规则使用跟踪（CSS 域依赖 DOM 域）
+async fn start_css_coverage(client: &dyn CdpClient) -> crate::Result<()> {
+    client.enable_domain("DOM").await?;
+    client.enable_domain("CSS").await?;
+    client
+        .call_method("CSS.startRuleUsageTracking", serde_json::json!({}))
+        .await?;
+    Ok(())
+}
+
+/// 获取 JS 覆盖率并关闭 Profiler
+async fn stop_js_coverage(client: &dyn CdpClient) -> crate::Result<Vec<ScriptCoverage>> {
+    let taken = client
+        .call_method("Profiler.takePreciseCoverage", serde_json::json!({}))
+        .await;
+
+    // 无论结果如何都停止收集，避免持续开销
+    let _ = client.call_method("Profiler.stopPreciseCoverage", serde_json::json!({})).await;
+    let _ = client.call_method("Profiler.disable", serde_json::json!({})).await;
+
+    Ok(taken?
+        .get("result")
+        .and_then(|v| v.as_array())
+        .map(|scripts| scripts.iter().map(script_coverage).collect())
+        .unwrap_or_default())
+}
+
+/// 获取 CSS 规则使用情况并关闭 CSS 域
+async fn stop_css_coverage(client: &dyn CdpClient) -> crate::Result<Vec<StyleSheetCoverage>> {
+    let stopped = client
+        .call_method("CSS.stopRuleUsageTracking", serde_json::json!({}))
+        .await;
+
+    let result = match stopped {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = client.call_method("CSS.disable", serde_json::json!({})).await;
+            return Err(e);
+        }
+    };
+
+    // 按样式表分组已使用的规则区间
+    let mut used: BTreeMap<String, Vec<(i64, i64)>> = BTreeMap::new();
+    for rule in result.get("ruleUsage").and_then(|v| v.as_array()).into_iter().flatten() {
+        let Some(sheet) = rule.get("styleSheetId").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let ranges = used.entry(sheet.to_string()).or_default();
+        if rule.get("used").and_then(|v| v.as_bool()).unwrap_or(false) {
+            let start = rule.get("startOffset").and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
+            let end = rule.get("endOffset").and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
+            ranges.push((start, end));
+        }
+    }
+
+    let mut style_sheets = Vec::with_capacity(used.len());
+    for (style_sheet_id, ranges) in used {
+        // 样式表文本仅用于计算总字节数，获取失败时为 0
+        let total_bytes = client
+            .call_method("CSS.getStyleSheetText", serde_json::json!({ "styleSheetId": style_sheet_id }))
+            .await
+            .ok()
+            .and_then(|r| r.get("text").and_then(|t| t.as_str()).map(|t| t.len() as i32))
+            .unwrap_or(0);
+        let ranges = merge_ranges(ranges);
+
+        style_sheets.push(StyleSheetCoverage {
+            style_sheet_id,
+            total_bytes,
+            used_bytes: used_bytes(&ranges),
+            ranges,
+        });
+    }
+
+    let _ = client.call_method("CSS.disable", serde_json::json!({})).await;
+
+    Ok(style_sheets)
+}
+
+/// 将 Profiler.ScriptCoverage 转换为 proto
+fn script_coverage(script: &Value) -> ScriptCoverage {
+    // (start, end, count)
+    let ranges: Vec<(i64, i64, i64)> = script
+        .get("functions")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .flat_map(|f| f.get("ranges").and_then(|v| v.as_array()).into_iter().flatten())
+        .map(|r| {
+            let field = |key: &str| r.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+            (field("startOffset"), field("endOffset"), field("count"))
+        })
+        .collect();
+
+    // 最外层区间覆盖整个脚本
+    let total_bytes = ranges.iter().map(|r| r.1).max().unwrap_or(0) as i32;
+    let ranges = disjoint_used_ranges(ranges);
+
+    ScriptCoverage {
+        script_id: script.get("scriptId").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        url: script.get("url").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        total_bytes,
+        used_bytes: used_bytes(&ranges),
+        ranges,
+    }
+}
+
+/// 将嵌套的块覆盖区间展开为互不重叠的已执行区间
+///
+/// 内层区间的计数覆盖外层区间，因此未执行的内层块会从已执行的外层块中剔除。
+fn disjoint_used_ranges(ranges: Vec<(i64, i64, i64)>) -> Vec<CoverageRange> {
+    // (offset, is_start, length, count)
+    let mut points: Vec<(i64, bool, i64, i64)> = Vec::with_capacity(ranges.len() * 2);
+    for (start, end, count) in ranges {
+        points.push((start, true, end - start, count));
+        points.push((end, false, end - start, count));
+    }
+
+    // 同一位置：先结束后开始；开始时长区间在前，结束时短区间在前
+    points.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.cmp(&b.1))
+            .then_with(|| if a.1 { b.2.cmp(&a.2) } else { a.2.cmp(&b.2) })
+    });
+
+    let mut counts: Vec<i64> = Vec::new();
+    let mut result: Vec<CoverageRange> = Vec::new();
+    let mut last_offset = 0;
+
+    for (offset, is_start, _, count) in points {
+        if counts.last().is_some_and(|c| *c > 0) && last_offset < offset {
+            match result.last_mut() {
+                Some(last) if last.end as i64 == last_offset => last.end = offset as i32,
+                _ => result.push(CoverageRange { start: last_offset as i32, end: offset as i32 }),
+            }
+        }
+        last_offset = offset;
+
+        if is_start {
+            counts.push(count);
+        } else {
+            counts.pop();
+        }
+    }
+
+    result
+}
+
+/// 排序并合并重叠或相邻的区间
+fn merge_ranges(mut ranges: Vec<(i64, i64)>) -> Vec<CoverageRange> {
+    ranges.sort();
+
+    let mut merged: Vec<CoverageRange> = Vec::new();
+    for (start, end) in ranges.into_iter().filter(|(s, e)| e > s) {
+        match merged.last_mut() {
+            Some(last) if start as i32 <= last.end => last.end = last.end.max(end as i32),
+            _ => merged.push(CoverageRange { start: start as i32, end: end as i32 }),
+        }
+    }
+
+    merged
+}
+
+/// 计算区间覆盖的总字节数
+fn used_bytes(ranges: &[CoverageRange]) -> i32 {
+    ranges.iter().map(|r| r.end - r.start).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(ranges: &[CoverageRange]) -> Vec<(i32, i32)> {
+        ranges.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn test_disjoint_used_ranges_excludes_unexecuted_blocks() {
+        // 脚本 0..100 已执行，其中 20..40 未执行，30..35 又被执行
+        let ranges = disjoint_used_ranges(vec![(0, 100, 1), (20, 40, 0), (30, 35, 2)]);
+        assert_eq!(pairs(&ranges), vec![(0, 20), (30, 35), (40, 100)]);
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let ranges = merge_ranges(vec![(50, 60), (0, 10), (5, 20), (20, 30), (70, 70)]);
+        assert_eq!(pairs(&ranges), vec![(0, 30), (50, 60)]);
+    }
+
+    #[tokio::test]
+    async fn test_coverage_report_from_mock() {
+        let client = crate::cdp::mock::MockCdpClient::new();
+        client
+            .stub_method(
+                "Profiler.takePreciseCoverage",
+                serde_json::json!({
+                    "result": [{
+                        "scriptId": "17",
+                        "url": "https://example.com/app.js",
+                        "functions": [
+                            { "functionName": "", "isBlockCoverage": true,
+                              "ranges": [{ "startOffset": 0, "endOffset": 200, "count": 1 }] },
+                            { "functionName": "unused", "isBlockCoverage": false,
+                              "ranges": [{ "startOffset": 120, "endOffset": 180, "count": 0 }] }
+                        ]
+                    }]
+                }),
+            )
+            .await;
+        client
+            .stub_method(
+                "CSS.stopRuleUsageTracking",
+                serde_json::json!({
+                    "ruleUsage": [
+                        { "styleSheetId": "sheet-1", "startOffset": 0, "endOffset": 40, "used": true },
+                        { "styleSheetId": "sheet-1", "startOffset": 40, "endOffset": 90, "used": false },
+                        { "styleSheetId": "sheet-1", "startOffset": 90, "endOffset": 100, "used": true }
+                    ]
+                }),
+            )
+            .await;
+        client
+            .stub_method("CSS.getStyleSheetText", serde_json::json!({ "text": "x".repeat(100) }))
+            .await;
+
+        start_js_coverage(&client).await.unwrap();
+        start_css_coverage(&client).await.unwrap();
+
+        let scripts = stop_js_coverage(&client).await.unwrap();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].url, "https://example.com/app.js");
+        assert_eq!(scripts[0].total_bytes, 200);
+        assert_eq!(scripts[0].used_bytes, 140);
+        assert_eq!(pairs(&scripts[0].ranges), vec![(0, 120), (180, 200)]);
+
+        let sheets = stop_css_coverage(&client).await.unwrap();
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(sheets[0].style_sheet_id, "sheet-1");
+        assert_eq!(sheets[0].total_bytes, 100);
+        assert_eq!(sheets[0].used_bytes, 50);
+        assert_eq!(pairs(&sheets[0].ranges), vec![(0, 40), (90, 100)]);
+
+        // 停止后关闭相关域
+        assert_eq!(client.recorded_calls("Profiler.stopPreciseCoverage").await.len(), 1);
+        assert_eq!(client.recorded_calls("Profiler.disable").await.len(), 1);
+        assert_eq!(client.recorded_calls("CSS.disable").await.len(), 1);
+    }
+}
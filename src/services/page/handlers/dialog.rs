@@ -0,0 +1,51 @@
+//! 对话框相关的 RPC 方法处理器
+//!
+//! 包括：handle_dialog
+//!
+//! 对话框的默认处理方式由浏览器的 `dialog_policy` 决定；手动模式下对话框
+//! 会保持打开，直到调用 HandleDialog 或超时后被自动关闭。
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use crate::session::SessionManager;
+use crate::chaser_oxide::v1::{
+    handle_dialog_response::Response as HandleDialogResponseEnum,
+    HandleDialogRequest, HandleDialogResponse,
+    Empty,
+};
+use super::super::response;
+
+/// 实现 PageService trait 中的对话框相关方法
+pub struct DialogHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+impl<S> DialogHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 接受或关闭页面上打开的对话框
+    pub async fn handle_dialog(&self, request: Request<HandleDialogRequest>) -> Result<Response<HandleDialogResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let params = serde_json::json!({
+                    "accept": req.accept,
+                    "promptText": req.prompt_text,
+                });
+
+                // 没有打开的对话框时 CDP 返回错误
+                match page.get_cdp_client().call_method("Page.handleJavaScriptDialog", params).await {
+                    Ok(_) => Ok(Response::new(HandleDialogResponse {
+                        response: Some(HandleDialogResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Ok(Response::new(HandleDialogResponse {
+                        response: Some(HandleDialogResponseEnum::Error(response::error_to_proto(e))),
+                    })),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
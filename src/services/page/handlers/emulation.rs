@@ -1,16 +1,23 @@
 //! 设备模拟相关的 RPC 方法处理器
 //!
-//! 包括：emulate_device, set_viewport, set_geolocation, bring_to_front
+//! 包括：emulate_device, set_cpu_throttling, set_media_features, set_viewport, set_geolocation, bring_to_front
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::Mutex;
 use tonic::{Request, Response, Status};
-use crate::session::SessionManager;
+use crate::session::{PageStateStore, SessionManager};
 use crate::chaser_oxide::v1::{
     emulate_device_response::Response as EmulateDeviceResponseEnum,
     set_viewport_response::Response as SetViewportResponseEnum,
     set_geolocation_response::Response as SetGeolocationResponseEnum,
     bring_to_front_response::Response as BringToFrontResponseEnum,
+    set_cpu_throttling_request::Preset as ThrottlingPresetType,
+    set_cpu_throttling_response::Response as SetCpuThrottlingResponseEnum,
+    set_media_features_response::Response as SetMediaFeaturesResponseEnum,
     EmulateDeviceRequest, EmulateDeviceResponse,
+    SetCpuThrottlingRequest, SetCpuThrottlingResponse,
+    SetMediaFeaturesRequest, SetMediaFeaturesResponse, MediaFeature,
     SetViewportRequest, SetViewportResponse,
     SetGeolocationRequest, SetGeolocationResponse,
     BringToFrontRequest, BringToFrontResponse,
@@ -108,9 +115,100 @@ impl DevicePreset {
     }
 }
 
+/// 性能节流预设配置
+#[derive(Debug, Clone)]
+pub struct ThrottlingPreset {
+    pub name: &'static str,
+    /// CPU 减速倍数
+    pub cpu_rate: f64,
+    /// 网络往返延迟（毫秒）
+    pub latency_ms: f64,
+    /// 下行吞吐量（字节/秒）
+    pub download_throughput: f64,
+    /// 上行吞吐量（字节/秒）
+    pub upload_throughput: f64,
+}
+
+impl ThrottlingPreset {
+    /// 中端移动设备（与 Lighthouse 移动端默认节流一致）
+    pub const MID_TIER_MOBILE: Self = Self {
+        name: "Mid-tier Mobile",
+        cpu_rate: 4.0,
+        latency_ms: 150.0,
+        download_throughput: 1.6 * 1024.0 * 1024.0 / 8.0,
+        upload_throughput: 750.0 * 1024.0 / 8.0,
+    };
+}
+
+/// 可模拟的媒体类型
+const MEDIA_TYPES: &[&str] = &["screen", "print"];
+
+/// 可模拟的媒体特性及其取值
+const MEDIA_FEATURES: &[(&str, &[&str])] = &[
+    ("prefers-color-scheme", &["light", "dark"]),
+    ("prefers-reduced-motion", &["reduce", "no-preference"]),
+    ("prefers-reduced-transparency", &["reduce", "no-preference"]),
+    ("prefers-contrast", &["more", "less", "custom", "no-preference"]),
+    ("forced-colors", &["active", "none"]),
+    ("color-gamut", &["srgb", "p3", "rec2020"]),
+];
+
+/// SetMediaFeatures 设置的媒体覆盖：页面 ID -> `Emulation.setEmulatedMedia` 参数
+///
+/// GetPdf 临时模拟打印媒体后据此恢复页面原有的覆盖。
+#[derive(Debug, Default)]
+pub struct MediaOverrides {
+    overrides: Mutex<HashMap<String, serde_json::Value>>,
+}
+
+impl MediaOverrides {
+    pub(super) async fn set(&self, page_id: &str, params: Option<serde_json::Value>) {
+        let mut overrides = self.overrides.lock().await;
+        match params {
+            Some(params) => overrides.insert(page_id.to_string(), params),
+            None => overrides.remove(page_id),
+        };
+    }
+
+    /// 页面当前的媒体覆盖；未覆盖时为恢复真实媒体的参数
+    pub async fn current(&self, page_id: &str) -> serde_json::Value {
+        self.overrides
+            .lock()
+            .await
+            .get(page_id)
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({ "media": "", "features": [] }))
+    }
+}
+
+#[async_trait::async_trait]
+impl PageStateStore for MediaOverrides {
+    /// 页面关闭后丢弃其媒体覆盖
+    async fn release_page(&self, page_id: &str) {
+        self.overrides.lock().await.remove(page_id);
+    }
+}
+
+/// 校验媒体特性名称和取值，空值表示取消该特性的覆盖
+pub(super) fn validate_media_feature(feature: &MediaFeature) -> Result<(), String> {
+    let Some((_, values)) = MEDIA_FEATURES.iter().find(|(name, _)| *name == feature.name) else {
+        return Err(format!("Unknown media feature '{}'", feature.name));
+    };
+    if !feature.value.is_empty() && !values.contains(&feature.value.as_str()) {
+        return Err(format!(
+            "Invalid value '{}' for media feature '{}', expected one of: {}",
+            feature.value,
+            feature.name,
+            values.join(", ")
+        ));
+    }
+    Ok(())
+}
+
 /// 实现 PageService trait 中的设备模拟相关方法
 pub struct EmulationHandlers<S> {
     pub session_manager: Arc<S>,
+    pub media_overrides: Arc<MediaOverrides>,
 }
 
 impl<S> EmulationHandlers<S>
@@ -124,24 +222,24 @@ where
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
                 // 定义设备预设
-                let (width, height, device_scale_factor, user_agent) = match req.device {
+                let (width, height, device_scale_factor, mobile, user_agent) = match req.device {
                     Some(device) => {
                         match device {
                             crate::chaser_oxide::v1::emulate_device_request::Device::DeviceType(device_type) => {
                                 if let Some(preset) = DevicePreset::from_device_type(device_type) {
-                                    (preset.width, preset.height, preset.device_scale_factor, preset.user_agent)
+                                    (preset.width, preset.height, preset.device_scale_factor, preset.mobile, preset.user_agent)
                                 } else {
-                                    (DevicePreset::DESKTOP.width, DevicePreset::DESKTOP.height, DevicePreset::DESKTOP.device_scale_factor, None)
+                                    (DevicePreset::DESKTOP.width, DevicePreset::DESKTOP.height, DevicePreset::DESKTOP.device_scale_factor, false, None)
                                 }
                             }
                             crate::chaser_oxide::v1::emulate_device_request::Device::Viewport(viewport) => {
-                                (viewport.width.max(0) as u32, viewport.height.max(0) as u32, viewport.device_scale_factor, None)
+                                (viewport.width.max(0) as u32, viewport.height.max(0) as u32, viewport.device_scale_factor, viewport.mobile, None)
                             }
                         }
                     }
                     None => {
                         // 默认为桌面
-                        (DevicePreset::DESKTOP.width, DevicePreset::DESKTOP.height, DevicePreset::DESKTOP.device_scale_factor, None)
+                        (DevicePreset::DESKTOP.width, DevicePreset::DESKTOP.height, DevicePreset::DESKTOP.device_scale_factor, false, None)
                     }
                 };
 
@@ -154,6 +252,11 @@ where
                             let _ = page.evaluate(&script, false).await;
                         }
 
+                        // 移动设备启用触摸事件模拟
+                        page.set_touch_emulation(mobile)
+                            .await
+                            .map_err(response::error_to_status)?;
+
                         Ok(Response::new(EmulateDeviceResponse {
                             response: Some(EmulateDeviceResponseEnum::Success(Empty {})),
                         }))
@@ -165,6 +268,102 @@ where
         }
     }
 
+    /// 设置 CPU 节流倍率，预设同时应用网络节流
+    pub async fn set_cpu_throttling(&self, request: Request<SetCpuThrottlingRequest>) -> Result<Response<SetCpuThrottlingResponse>, Status> {
+        let req = request.into_inner();
+
+        let preset = match req.preset() {
+            ThrottlingPresetType::Unspecified => None,
+            ThrottlingPresetType::MidTierMobile => Some(ThrottlingPreset::MID_TIER_MOBILE),
+        };
+        let rate = preset.as_ref().map_or(req.rate, |p| p.cpu_rate);
+        if !rate.is_finite() || rate < 1.0 {
+            return Err(Status::invalid_argument(format!(
+                "CPU throttling rate must be >= 1.0 (1.0 = no throttling), got {}",
+                rate
+            )));
+        }
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+                client
+                    .set_cpu_throttling_rate(rate)
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                if let Some(preset) = preset {
+                    client.enable_domain("Network").await.map_err(response::error_to_status)?;
+                    client
+                        .call_method(
+                            "Network.emulateNetworkConditions",
+                            serde_json::json!({
+                                "offline": false,
+                                "latency": preset.latency_ms,
+                                "downloadThroughput": preset.download_throughput,
+                                "uploadThroughput": preset.upload_throughput,
+                            }),
+                        )
+                        .await
+                        .map_err(response::error_to_status)?;
+                }
+
+                Ok(Response::new(SetCpuThrottlingResponse {
+                    response: Some(SetCpuThrottlingResponseEnum::Success(Empty {})),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 覆盖 CSS 媒体类型和媒体特性（如深色模式、减少动画）
+    pub async fn set_media_features(&self, request: Request<SetMediaFeaturesRequest>) -> Result<Response<SetMediaFeaturesResponse>, Status> {
+        let req = request.into_inner();
+
+        // 空的媒体类型和特性列表会恢复真实的媒体
+        let (media, features) = if req.clear {
+            (String::new(), Vec::new())
+        } else {
+            if !req.media_type.is_empty() && !MEDIA_TYPES.contains(&req.media_type.as_str()) {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid media type '{}', expected one of: {}",
+                    req.media_type,
+                    MEDIA_TYPES.join(", ")
+                )));
+            }
+            for feature in &req.features {
+                validate_media_feature(feature).map_err(Status::invalid_argument)?;
+            }
+            (req.media_type, req.features)
+        };
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let features: Vec<_> = features
+                    .iter()
+                    .map(|f| serde_json::json!({ "name": f.name, "value": f.value }))
+                    .collect();
+                let params = serde_json::json!({ "media": media, "features": features });
+
+                match page
+                    .get_cdp_client()
+                    .call_method("Emulation.setEmulatedMedia", params.clone())
+                    .await
+                {
+                    Ok(_) => {
+                        let overridden = !media.is_empty() || !features.is_empty();
+                        self.media_overrides.set(&req.page_id, overridden.then_some(params)).await;
+                        Ok(Response::new(SetMediaFeaturesResponse {
+                            response: Some(SetMediaFeaturesResponseEnum::Success(Empty {})),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
     /// 设置视口
     pub async fn set_viewport(&self, request: Request<SetViewportRequest>) -> Result<Response<SetViewportResponse>, Status> {
         let req = request.into_inner();
@@ -234,3 +433,156 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    async fn handlers_with(client: Arc<MockCdpClient>) -> (EmulationHandlers<MockSessionManager>, String) {
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client,
+            )))
+            .await;
+        let handlers = EmulationHandlers {
+            session_manager: manager,
+            media_overrides: Arc::new(MediaOverrides::default()),
+        };
+        (handlers, page_id)
+    }
+
+    #[tokio::test]
+    async fn test_set_cpu_throttling_issues_rate() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        handlers
+            .set_cpu_throttling(Request::new(SetCpuThrottlingRequest {
+                page_id,
+                rate: 2.5,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let calls = client.recorded_calls("Emulation.setCPUThrottlingRate").await;
+        assert_eq!(calls, vec![serde_json::json!({ "rate": 2.5 })]);
+        assert!(client.recorded_calls("Network.emulateNetworkConditions").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mid_tier_mobile_preset_throttles_cpu_and_network() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        handlers
+            .set_cpu_throttling(Request::new(SetCpuThrottlingRequest {
+                page_id,
+                preset: ThrottlingPresetType::MidTierMobile as i32,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let calls = client.recorded_calls("Emulation.setCPUThrottlingRate").await;
+        assert_eq!(calls, vec![serde_json::json!({ "rate": 4.0 })]);
+        let network = client.recorded_calls("Network.emulateNetworkConditions").await;
+        assert_eq!(network.len(), 1);
+        assert_eq!(network[0]["latency"], 150.0);
+    }
+
+    #[tokio::test]
+    async fn test_set_media_features_dark_mode() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        handlers
+            .set_media_features(Request::new(SetMediaFeaturesRequest {
+                page_id: page_id.clone(),
+                features: vec![MediaFeature {
+                    name: "prefers-color-scheme".to_string(),
+                    value: "dark".to_string(),
+                }],
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        handlers
+            .set_media_features(Request::new(SetMediaFeaturesRequest {
+                page_id: page_id.clone(),
+                clear: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let calls = client.recorded_calls("Emulation.setEmulatedMedia").await;
+        assert_eq!(
+            calls,
+            vec![
+                serde_json::json!({
+                    "media": "",
+                    "features": [{ "name": "prefers-color-scheme", "value": "dark" }]
+                }),
+                serde_json::json!({ "media": "", "features": [] }),
+            ]
+        );
+
+        let status = handlers
+            .set_media_features(Request::new(SetMediaFeaturesRequest {
+                page_id,
+                features: vec![MediaFeature {
+                    name: "prefers-color-scheme".to_string(),
+                    value: "purple".to_string(),
+                }],
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_closed_page_drops_media_overrides() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+        let reset = serde_json::json!({ "media": "", "features": [] });
+
+        handlers
+            .set_media_features(Request::new(SetMediaFeaturesRequest {
+                page_id: page_id.clone(),
+                media_type: "print".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_ne!(handlers.media_overrides.current(&page_id).await, reset);
+
+        // 测试：页面关闭后媒体覆盖被丢弃
+        handlers.media_overrides.release_page(&page_id).await;
+        assert_eq!(handlers.media_overrides.current(&page_id).await, reset);
+    }
+
+    #[tokio::test]
+    async fn test_set_cpu_throttling_rejects_rate_below_one() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        let status = handlers
+            .set_cpu_throttling(Request::new(SetCpuThrottlingRequest {
+                page_id,
+                rate: 0.5,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(client.recorded_calls("Emulation.setCPUThrottlingRate").await.is_empty());
+    }
+}
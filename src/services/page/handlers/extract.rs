@@ -0,0 +1,146 @@
+//! 内嵌 JSON 提取相关的 RPC 方法处理器
+//!
+//! 包括：extract_json
+//!
+//! 在页面中读取匹配的 `<script>` 文本（默认为 `application/ld+json`），
+//! 在服务端解析 JSON 并按 JSONPath 提取值。无法解析的脚本会被跳过并记录警告。
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::warn;
+use crate::session::{EvaluationResult as SessionEvaluationResult, SessionManager};
+use crate::chaser_oxide::v1::{
+    extract_json_response::Response as ExtractJsonResponseEnum,
+    ExtractJsonRequest, ExtractJsonResponse, ExtractJsonResult,
+};
+use crate::services::common::limits::check_result_size;
+use crate::Error;
+use super::super::json_path::JsonPath;
+use super::super::{response, scripts};
+
+/// 未指定 script_type 和 selector 时读取的脚本类型
+const DEFAULT_SCRIPT_TYPE: &str = "application/ld+json";
+
+/// 实现 PageService trait 中的内嵌 JSON 提取方法
+pub struct ExtractHandlers<S> {
+    pub session_manager: Arc<S>,
+    pub max_result_bytes: usize,
+}
+
+impl<S> ExtractHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 解析匹配脚本中的 JSON 并返回 JSONPath 选中的值
+    pub async fn extract_json(&self, request: Request<ExtractJsonRequest>) -> Result<Response<ExtractJsonResponse>, Status> {
+        let req = request.into_inner();
+        let path = match req.path.trim() {
+            "" => None,
+            path => Some(JsonPath::parse(path).map_err(response::error_to_status)?),
+        };
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+
+        let script_type = if req.script_type.is_empty() { DEFAULT_SCRIPT_TYPE } else { req.script_type.as_str() };
+        let script = format!(
+            "({})({}, {})",
+            scripts::SCRIPT_TEXTS_SCRIPT,
+            serde_json::json!(req.selector),
+            serde_json::json!(script_type)
+        );
+        let texts: Vec<String> = match page.evaluate(&script, false).await.map_err(response::error_to_status)? {
+            SessionEvaluationResult::String(json) => serde_json::from_str(&json)
+                .map_err(|e| response::error_to_status(Error::internal(format!("Invalid script text list: {}", e))))?,
+            other => {
+                return Err(response::error_to_status(Error::internal(format!(
+                    "Unexpected script text list: {:?}",
+                    other
+                ))))
+            }
+        };
+
+        let mut values = Vec::new();
+        let mut skipped = 0;
+        let mut total_bytes = 0;
+        for (index, text) in texts.iter().enumerate() {
+            let document: serde_json::Value = match serde_json::from_str(text) {
+                Ok(document) => document,
+                Err(e) => {
+                    warn!(page_id = %req.page_id, index, error = %e, "Skipping script with malformed JSON");
+                    skipped += 1;
+                    continue;
+                }
+            };
+
+            let matched = match &path {
+                Some(path) => path.select(&document).into_iter().map(|value| value.to_string()).collect(),
+                None => vec![document.to_string()],
+            };
+            for value in matched {
+                total_bytes += value.len();
+                check_result_size("Extracted JSON", total_bytes, self.max_result_bytes)
+                    .map_err(response::error_to_status)?;
+                values.push(value);
+            }
+        }
+
+        Ok(Response::new(ExtractJsonResponse {
+            response: Some(ExtractJsonResponseEnum::Result(ExtractJsonResult {
+                values,
+                scripts_matched: texts.len() as i32,
+                scripts_skipped: skipped,
+            })),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    // 测试：从 ld+json 中提取字段，跳过格式错误的脚本
+    #[tokio::test]
+    async fn test_extract_json_reads_ld_json_field() {
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::new("browser".to_string(), Default::default()));
+        let texts = serde_json::json!([
+            r#"{"@type": "Product", "name": "Widget", "offers": {"@type": "Offer", "price": "19.99"}}"#,
+            r#"{"@type": "Product", "offers": "#,
+            r#"{"@type": "BreadcrumbList", "itemListElement": []}"#,
+        ]);
+        page.stub_evaluate("script[type=", vec![SessionEvaluationResult::String(texts.to_string())])
+            .await;
+        let page_id = manager.register_page(Arc::clone(&page)).await;
+        let handlers = ExtractHandlers {
+            session_manager: manager,
+            max_result_bytes: 1024,
+        };
+
+        let response = handlers
+            .extract_json(Request::new(ExtractJsonRequest {
+                page_id: page_id.clone(),
+                path: "$.offers.price".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(ExtractJsonResponseEnum::Result(result)) = response.response else {
+            panic!("expected extracted values");
+        };
+        assert_eq!(result.values, vec![r#""19.99""#.to_string()]);
+        assert_eq!(result.scripts_matched, 3);
+        assert_eq!(result.scripts_skipped, 1);
+        assert!(page.evaluated_scripts().await[0].contains(r#""application/ld+json""#));
+
+        let status = handlers
+            .extract_json(Request::new(ExtractJsonRequest {
+                page_id,
+                path: "offers.price".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,127 @@
+//! 框架相关的 RPC 方法处理器
+//!
+//! 包括：get_frames
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use crate::session::SessionManager;
+use crate::chaser_oxide::v1::{
+    get_frames_response::Response as GetFramesResponseEnum,
+    GetFramesRequest, GetFramesResponse,
+    FrameInfo, FrameList,
+};
+use super::super::response;
+
+/// 实现 PageService trait 中的框架相关方法
+pub struct FrameHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+impl<S> FrameHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 列出页面的全部框架
+    pub async fn get_frames(&self, request: Request<GetFramesRequest>) -> Result<Response<GetFramesResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                match page.get_cdp_client().call_method("Page.getFrameTree", serde_json::json!({})).await {
+                    Ok(result) => {
+                        let tree = &result["frameTree"];
+                        let main_origin = tree["frame"]["securityOrigin"].as_str().unwrap_or_default();
+                        let mut frames = Vec::new();
+                        flatten_frame_tree(tree, main_origin, &mut frames);
+
+                        Ok(Response::new(GetFramesResponse {
+                            response: Some(GetFramesResponseEnum::Frames(FrameList { frames })),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
+
+/// 深度优先展开 `Page.getFrameTree` 返回的框架树
+fn flatten_frame_tree(node: &serde_json::Value, main_origin: &str, frames: &mut Vec<FrameInfo>) {
+    let frame = &node["frame"];
+    if frame.is_null() {
+        return;
+    }
+
+    let field = |key: &str| frame[key].as_str().unwrap_or_default().to_string();
+    let security_origin = field("securityOrigin");
+    frames.push(FrameInfo {
+        frame_id: field("id"),
+        parent_id: field("parentId"),
+        url: field("url"),
+        name: field("name"),
+        cross_origin: security_origin != main_origin,
+        security_origin,
+    });
+
+    for child in node["childFrames"].as_array().into_iter().flatten() {
+        flatten_frame_tree(child, main_origin, frames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    #[tokio::test]
+    async fn test_get_frames_flattens_tree() {
+        let client = Arc::new(MockCdpClient::new());
+        client
+            .stub_method(
+                "Page.getFrameTree",
+                serde_json::json!({ "frameTree": {
+                    "frame": {
+                        "id": "main",
+                        "url": "https://example.com/",
+                        "securityOrigin": "https://example.com",
+                    },
+                    "childFrames": [{
+                        "frame": {
+                            "id": "ad",
+                            "parentId": "main",
+                            "name": "banner",
+                            "url": "https://ads.example.net/slot",
+                            "securityOrigin": "https://ads.example.net",
+                        },
+                    }],
+                }}),
+            )
+            .await;
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client)))
+            .await;
+        let handlers = FrameHandlers { session_manager: manager };
+
+        let response = handlers
+            .get_frames(Request::new(GetFramesRequest { page_id }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(GetFramesResponseEnum::Frames(list)) = response.response else {
+            panic!("expected a frame list");
+        };
+        assert_eq!(list.frames.len(), 2);
+        assert_eq!(list.frames[0].frame_id, "main");
+        assert!(list.frames[0].parent_id.is_empty());
+        assert!(!list.frames[0].cross_origin);
+        assert_eq!(list.frames[1].frame_id, "ad");
+        assert_eq!(list.frames[1].parent_id, "main");
+        assert_eq!(list.frames[1].name, "banner");
+        assert_eq!(list.frames[1].security_origin, "https://ads.example.net");
+        assert!(list.frames[1].cross_origin);
+    }
+}
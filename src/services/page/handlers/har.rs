@@ -0,0 +1,141 @@
+//! HAR 录制相关的 RPC 方法处理器
+//!
+//! 包括：start_har_recording, export_har
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use crate::session::SessionManager;
+use crate::chaser_oxide::v1::{
+    start_har_recording_response::Response as StartHarRecordingResponseEnum,
+    export_har_response::Response as ExportHarResponseEnum,
+    StartHarRecordingRequest, StartHarRecordingResponse,
+    ExportHarRequest, ExportHarResponse,
+    Empty,
+};
+use crate::services::common::limits::check_result_size;
+use super::super::har::HarRecorder;
+use super::super::response;
+
+/// 实现 PageService trait 中的 HAR 录制方法
+pub struct HarHandlers<S> {
+    pub session_manager: Arc<S>,
+    pub recorder: Arc<HarRecorder>,
+    /// 导出 HAR 的最大字节数
+    pub max_result_bytes: usize,
+}
+
+impl<S> HarHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 开始录制页面的网络活动
+    pub async fn start_har_recording(&self, request: Request<StartHarRecordingRequest>) -> Result<Response<StartHarRecordingResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                self.recorder
+                    .start(&req.page_id, page.get_cdp_client(), req.include_bodies)
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                Ok(Response::new(StartHarRecordingResponse {
+                    response: Some(StartHarRecordingResponseEnum::Success(Empty {})),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 导出已录制的 HAR
+    pub async fn export_har(&self, request: Request<ExportHarRequest>) -> Result<Response<ExportHarResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(_page) => {
+                let har = self
+                    .recorder
+                    .export(&req.page_id, req.stop)
+                    .await
+                    .ok_or_else(|| Status::failed_precondition("HAR recording was not started"))?;
+                let har_json = har.to_string();
+                check_result_size("HAR", har_json.len(), self.max_result_bytes).map_err(response::error_to_status)?;
+
+                Ok(Response::new(ExportHarResponse {
+                    response: Some(ExportHarResponseEnum::HarJson(har_json)),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    // 测试：录制期间的网络事件出现在导出的 HAR 中
+    #[tokio::test]
+    async fn test_export_har_after_recording() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone())))
+            .await;
+        client
+            .stub_method("Network.getResponseBody", serde_json::json!({ "body": "aGk=", "base64Encoded": true }))
+            .await;
+        let handlers = HarHandlers {
+            session_manager: manager,
+            recorder: Arc::new(HarRecorder::default()),
+            max_result_bytes: usize::MAX,
+        };
+        let export = |stop| ExportHarRequest { page_id: page_id.clone(), stop };
+
+        let status = handlers.export_har(Request::new(export(false))).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        handlers
+            .start_har_recording(Request::new(StartHarRecordingRequest {
+                page_id: page_id.clone(),
+                include_bodies: true,
+            }))
+            .await
+            .unwrap();
+        client
+            .emit_event("Network.requestWillBeSent", serde_json::json!({
+                "requestId": "7", "timestamp": 5.0, "wallTime": 1_700_000_000.0,
+                "request": { "url": "https://example.com/logo.png", "method": "GET", "headers": {} },
+            }))
+            .await;
+        client
+            .emit_event("Network.loadingFinished", serde_json::json!({
+                "requestId": "7", "timestamp": 5.2, "encodedDataLength": 2.0,
+            }))
+            .await;
+
+        let mut entries = Vec::new();
+        for _ in 0..50 {
+            let Some(ExportHarResponseEnum::HarJson(json)) =
+                handlers.export_har(Request::new(export(false))).await.unwrap().into_inner().response
+            else {
+                panic!("expected HAR JSON");
+            };
+            let har: serde_json::Value = serde_json::from_str(&json).unwrap();
+            entries = har["log"]["entries"].as_array().cloned().unwrap_or_default();
+            if entries.first().is_some_and(|e| !e["response"]["content"]["text"].is_null()) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["request"]["url"], "https://example.com/logo.png");
+        assert_eq!(entries[0]["response"]["content"]["encoding"], "base64");
+
+        handlers.export_har(Request::new(export(true))).await.unwrap();
+        let status = handlers.export_har(Request::new(export(false))).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+}
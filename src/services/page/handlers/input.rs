@@ -0,0 +1,817 @@
+//! 输入相关的 RPC 方法处理器
+//!
+//! 包括：click_at, tap, swipe, replay_mouse_trace, simulate_idle, scroll_to_bottom, page_type, page_press_key
+
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::{Request, Response, Status};
+use crate::cdp::keys;
+use crate::cdp::traits::CdpClient;
+use crate::session::{DefaultTimeouts, PageContext, SessionManager, EvaluationResult as SessionEvaluationResult};
+use crate::stealth::{BehaviorSimulator, BehaviorSimulatorImpl, ClickOptions, MouseButton, TracePoint};
+use crate::chaser_oxide::v1::{
+    click_at_response::Response as ClickAtResponseEnum,
+    tap_request::Target as TapTarget,
+    tap_response::Response as TapResponseEnum,
+    swipe_response::Response as SwipeResponseEnum,
+    replay_mouse_trace_response::Response as ReplayMouseTraceResponseEnum,
+    simulate_idle_response::Response as SimulateIdleResponseEnum,
+    scroll_to_bottom_response::Response as ScrollToBottomResponseEnum,
+    page_type_response::Response as PageTypeResponseEnum,
+    page_press_key_response::Response as PagePressKeyResponseEnum,
+    ClickAtRequest, ClickAtResponse,
+    TapRequest, TapResponse,
+    SwipeRequest, SwipeResponse,
+    ReplayMouseTraceRequest, ReplayMouseTraceResponse,
+    SimulateIdleRequest, SimulateIdleResponse,
+    ScrollToBottomRequest, ScrollToBottomResponse, ScrollToBottomResult,
+    PageTypeRequest, PageTypeResponse,
+    PagePressKeyRequest, PagePressKeyResponse,
+    Empty, MouseButton as ProtoMouseButton, TouchPoint,
+};
+use crate::services::common::proto;
+use super::super::{response, scripts};
+
+/// 滑动未指定步数时的 touchMove 事件数
+const DEFAULT_SWIPE_STEPS: u32 = 10;
+
+/// 滑动未指定时长时的持续时间（毫秒）
+const DEFAULT_SWIPE_DURATION_MS: u64 = 300;
+
+/// 滚动到底部未指定次数时的最大滚动步数
+const DEFAULT_MAX_SCROLLS: u32 = 50;
+
+/// 每次滚动后等待新内容加载的时间（毫秒）
+const DEFAULT_SCROLL_SETTLE_MS: u64 = 1000;
+
+/// 请求和浏览器都未指定超时时，滚动到底部的总时长上限（毫秒）
+const DEFAULT_SCROLL_TIMEOUT_MS: u64 = 60_000;
+
+/// 一格滚轮滚动的像素数
+const WHEEL_NOTCH_PX: f64 = 100.0;
+
+/// 连续滚轮事件之间的间隔
+const WHEEL_NOTCH_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 实现 PageService trait 中的输入相关方法
+pub struct InputHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+impl<S> InputHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 在视口坐标处点击，经由行为模拟器移动鼠标后按下/释放
+    pub async fn click_at(&self, request: Request<ClickAtRequest>) -> Result<Response<ClickAtResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+                let (width, height) = viewport_size(client.as_ref())
+                    .await
+                    .map_err(response::error_to_status)?;
+                if !within_viewport(req.x, req.y, width, height) {
+                    return Err(Status::invalid_argument(format!(
+                        "Point ({}, {}) is outside the {}x{} viewport",
+                        req.x, req.y, width, height
+                    )));
+                }
+
+                let button = match req.button() {
+                    ProtoMouseButton::Unspecified | ProtoMouseButton::Left => MouseButton::Left,
+                    ProtoMouseButton::Right => MouseButton::Right,
+                    ProtoMouseButton::Middle => MouseButton::Middle,
+                    ProtoMouseButton::Back => MouseButton::Back,
+                    ProtoMouseButton::Forward => MouseButton::Forward,
+                };
+                let click_count = req.click_count.max(1) as u32;
+
+                let simulator = BehaviorSimulatorImpl::new(client);
+                match simulator
+                    .simulate_click_at(page.id(), (req.x, req.y), button, click_count, ClickOptions::default())
+                    .await
+                {
+                    Ok(_) => Ok(Response::new(ClickAtResponse {
+                        response: Some(ClickAtResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 在元素中心或视口坐标处轻触（touchStart/touchEnd）
+    pub async fn tap(&self, request: Request<TapRequest>) -> Result<Response<TapResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                if !page.touch_emulation_enabled() {
+                    return Err(touch_disabled());
+                }
+
+                let (x, y) = match req.target {
+                    Some(TapTarget::Point(point)) => (point.x, point.y),
+                    Some(TapTarget::Element(element)) => element_center(page.as_ref(), &element.element_id)
+                        .await
+                        .map_err(response::error_to_status)?,
+                    None => return Err(Status::invalid_argument("element or point is required")),
+                };
+
+                let client = page.get_cdp_client();
+                let result = async {
+                    dispatch_touch(client.as_ref(), "touchStart", Some((x, y))).await?;
+                    dispatch_touch(client.as_ref(), "touchEnd", None).await
+                }
+                .await;
+
+                match result {
+                    Ok(_) => Ok(Response::new(TapResponse {
+                        response: Some(TapResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 从起点滑动到终点，中间以一系列 touchMove 事件移动
+    pub async fn swipe(&self, request: Request<SwipeRequest>) -> Result<Response<SwipeResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                if !page.touch_emulation_enabled() {
+                    return Err(touch_disabled());
+                }
+
+                let point = |p: Option<TouchPoint>| p.map(|p| (p.x, p.y));
+                let (Some(start), Some(end)) = (point(req.start), point(req.end)) else {
+                    return Err(Status::invalid_argument("start and end are required"));
+                };
+                let steps = match req.steps {
+                    s if s > 0 => s as u32,
+                    _ => DEFAULT_SWIPE_STEPS,
+                };
+                let duration = match req.duration_ms {
+                    d if d > 0 => d as u64,
+                    _ => DEFAULT_SWIPE_DURATION_MS,
+                };
+                let step_delay = Duration::from_millis(duration) / steps;
+
+                let client = page.get_cdp_client();
+                let result = async {
+                    dispatch_touch(client.as_ref(), "touchStart", Some(start)).await?;
+                    for i in 1..=steps {
+                        tokio::time::sleep(step_delay).await;
+                        let t = i as f64 / steps as f64;
+                        let point = (start.0 + (end.0 - start.0) * t, start.1 + (end.1 - start.1) * t);
+                        dispatch_touch(client.as_ref(), "touchMove", Some(point)).await?;
+                    }
+                    dispatch_touch(client.as_ref(), "touchEnd", None).await
+                }
+                .await;
+
+                match result {
+                    Ok(_) => Ok(Response::new(SwipeResponse {
+                        response: Some(SwipeResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 按录制的时间间隔回放鼠标轨迹
+    pub async fn replay_mouse_trace(
+        &self,
+        request: Request<ReplayMouseTraceRequest>,
+    ) -> Result<Response<ReplayMouseTraceResponse>, Status> {
+        let req = request.into_inner();
+
+        if req.points.iter().any(|p| p.t_ms < 0) {
+            return Err(Status::invalid_argument("t_ms must not be negative"));
+        }
+        let trace: Vec<TracePoint> = req
+            .points
+            .iter()
+            .map(|p| TracePoint { x: p.x, y: p.y, t_ms: p.t_ms as u64 })
+            .collect();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let simulator = BehaviorSimulatorImpl::new(page.get_cdp_client());
+                match simulator.replay_mouse_trace(page.id(), &trace).await {
+                    Ok(_) => Ok(Response::new(ReplayMouseTraceResponse {
+                        response: Some(ReplayMouseTraceResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 在给定时长内模拟用户阅读时的小幅鼠标移动和偶尔滚动
+    pub async fn simulate_idle(&self, request: Request<SimulateIdleRequest>) -> Result<Response<SimulateIdleResponse>, Status> {
+        let req = request.into_inner();
+
+        if req.duration_ms < 0 {
+            return Err(Status::invalid_argument("duration_ms must not be negative"));
+        }
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let simulator = BehaviorSimulatorImpl::new(page.get_cdp_client());
+                match simulator.simulate_idle(page.id(), req.duration_ms as u64).await {
+                    Ok(_) => Ok(Response::new(SimulateIdleResponse {
+                        response: Some(SimulateIdleResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 用滚轮反复将页面或可滚动元素滚动到底部，直到高度不再增长
+    ///
+    /// 每一步滚动约一个可视高度，之后等待 `settle_ms` 让懒加载内容出现；
+    /// 到达底部且等待后高度未增长即结束。达到最大步数或超时时停止并返回已完成的步数。
+    pub async fn scroll_to_bottom(&self, request: Request<ScrollToBottomRequest>) -> Result<Response<ScrollToBottomResponse>, Status> {
+        let req = request.into_inner();
+
+        if req.max_scrolls < 0 || req.settle_ms < 0 || req.timeout < 0 {
+            return Err(Status::invalid_argument("max_scrolls, settle_ms and timeout must not be negative"));
+        }
+        let max_scrolls = match req.max_scrolls {
+            0 => DEFAULT_MAX_SCROLLS,
+            n => n as u32,
+        };
+        let settle = Duration::from_millis(match req.settle_ms {
+            0 => DEFAULT_SCROLL_SETTLE_MS,
+            ms => ms as u64,
+        });
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let timeout_ms = DefaultTimeouts::resolve(
+                    req.timeout as u64,
+                    page.default_timeouts().wait_ms,
+                    DEFAULT_SCROLL_TIMEOUT_MS,
+                );
+                let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+
+                match scroll_until_stable(page.as_ref(), &req.selector, max_scrolls, settle, deadline).await {
+                    Ok(result) => Ok(Response::new(ScrollToBottomResponse {
+                        response: Some(ScrollToBottomResponseEnum::Result(result)),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 向当前获得焦点的元素输入文本，无需指定元素
+    pub async fn page_type(&self, request: Request<PageTypeRequest>) -> Result<Response<PageTypeResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+                let result = if req.insert_text {
+                    client.insert_text(&req.text).await
+                } else {
+                    let delay = Duration::from_millis(req.delay.max(0) as u64);
+                    keys::type_text(client.as_ref(), &req.text, delay).await
+                };
+
+                match result {
+                    Ok(_) => Ok(Response::new(PageTypeResponse {
+                        response: Some(PageTypeResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 在当前获得焦点的元素上按下组合键（如 "Ctrl+Shift+K"），无需指定元素
+    pub async fn page_press_key(
+        &self,
+        request: Request<PagePressKeyRequest>,
+    ) -> Result<Response<PagePressKeyResponse>, Status> {
+        let req = request.into_inner();
+
+        let extra_modifiers = proto::key_modifiers(&req.modifiers);
+        let chords: Vec<_> = keys::parse_key_sequence(&req.key)
+            .map_err(response::error_to_status)?
+            .into_iter()
+            .map(|chord| chord.with_modifiers(extra_modifiers))
+            .collect();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let hold = Duration::from_millis(req.delay.max(0) as u64);
+                match keys::press_chords(page.get_cdp_client().as_ref(), &chords, hold).await {
+                    Ok(_) => Ok(Response::new(PagePressKeyResponse {
+                        response: Some(PagePressKeyResponseEnum::Success(Empty {})),
+                    })),
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
+
+/// 滚动目标的滚动状态
+#[derive(Debug, Clone, Copy)]
+struct ScrollMetrics {
+    scroll_height: f64,
+    client_height: f64,
+    scroll_top: f64,
+    /// 滚轮事件落点（视口坐标）
+    x: f64,
+    y: f64,
+}
+
+impl ScrollMetrics {
+    fn at_bottom(&self) -> bool {
+        // 缩放时 scrollTop 可能是小数，留 1 像素余量
+        self.scroll_top + self.client_height >= self.scroll_height - 1.0
+    }
+}
+
+/// 读取滚动目标的滚动状态；`selector` 为空时为整个页面
+async fn scroll_metrics(page: &dyn PageContext, selector: &str) -> crate::Result<ScrollMetrics> {
+    let script = format!("({})({})", scripts::SCROLL_METRICS_SCRIPT, serde_json::json!(selector));
+    match page.evaluate(&script, false).await? {
+        SessionEvaluationResult::Object(metrics) => {
+            let value = |key: &str| metrics.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            Ok(ScrollMetrics {
+                scroll_height: value("scrollHeight"),
+                client_height: value("clientHeight"),
+                scroll_top: value("scrollTop"),
+                x: value("x"),
+                y: value("y"),
+            })
+        }
+        _ => Err(crate::Error::element_not_found(selector)),
+    }
+}
+
+/// 滚动直到到达底部且高度稳定，或达到步数上限、截止时间
+async fn scroll_until_stable(
+    page: &dyn PageContext,
+    selector: &str,
+    max_scrolls: u32,
+    settle: Duration,
+    deadline: tokio::time::Instant,
+) -> crate::Result<ScrollToBottomResult> {
+    let client = page.get_cdp_client();
+    let mut scroll_count = 0;
+
+    loop {
+        let before = scroll_metrics(page, selector).await?;
+        let stopped = |reached_bottom| ScrollToBottomResult {
+            scroll_count: scroll_count as i32,
+            scroll_height: before.scroll_height,
+            reached_bottom,
+        };
+        if tokio::time::Instant::now() >= deadline {
+            return Ok(stopped(false));
+        }
+
+        if before.at_bottom() {
+            // 已到底部：等待懒加载，高度不再增长即结束
+            tokio::time::sleep_until((tokio::time::Instant::now() + settle).min(deadline)).await;
+            let after = scroll_metrics(page, selector).await?;
+            if after.scroll_height <= before.scroll_height {
+                return Ok(ScrollToBottomResult {
+                    scroll_count: scroll_count as i32,
+                    scroll_height: after.scroll_height,
+                    reached_bottom: true,
+                });
+            }
+            continue;
+        }
+        if scroll_count >= max_scrolls {
+            return Ok(stopped(false));
+        }
+
+        wheel_scroll(client.as_ref(), before.x, before.y, before.client_height).await?;
+        scroll_count += 1;
+        tokio::time::sleep_until((tokio::time::Instant::now() + settle).min(deadline)).await;
+    }
+}
+
+/// 在 (x, y) 处以若干格滚轮事件向下滚动约 `distance` 像素
+async fn wheel_scroll(client: &dyn CdpClient, x: f64, y: f64, distance: f64) -> crate::Result<()> {
+    let notches = (distance / WHEEL_NOTCH_PX).ceil().max(1.0) as u32;
+    for notch in 0..notches {
+        if notch > 0 {
+            tokio::time::sleep(WHEEL_NOTCH_INTERVAL).await;
+        }
+        client
+            .call_method(
+                "Input.dispatchMouseEvent",
+                serde_json::json!({
+                    "type": "mouseWheel", "x": x, "y": y, "deltaX": 0, "deltaY": WHEEL_NOTCH_PX,
+                }),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// 触摸手势只在启用触摸模拟时有效，否则页面收不到一致的触摸能力
+fn touch_disabled() -> Status {
+    Status::failed_precondition("Touch emulation is not enabled; emulate a mobile device first")
+}
+
+/// 发送一个触摸事件，`point` 为 None 时不带触摸点（touchEnd）
+async fn dispatch_touch(client: &dyn CdpClient, event_type: &str, point: Option<(f64, f64)>) -> crate::Result<()> {
+    let touch_points: Vec<_> = point
+        .map(|(x, y)| serde_json::json!({ "x": x, "y": y }))
+        .into_iter()
+        .collect();
+    client
+        .call_method(
+            "Input.dispatchTouchEvent",
+            serde_json::json!({ "type": event_type, "touchPoints": touch_points }),
+        )
+        .await?;
+    Ok(())
+}
+
+/// 计算元素中心的视口坐标
+pub(super) async fn element_center(page: &dyn PageContext, element_id: &str) -> crate::Result<(f64, f64)> {
+    let selector = format!("[data-element-id=\"{}\"]", element_id);
+    let script = format!(
+        "(() => {{ const el = document.querySelector({}); if (!el) return null; \
+         const r = el.getBoundingClientRect(); return {{ x: r.x + r.width / 2, y: r.y + r.height / 2 }}; }})()",
+        serde_json::json!(selector)
+    );
+
+    match page.evaluate(&script, false).await? {
+        SessionEvaluationResult::Object(center) => {
+            let coord = |key: &str| center.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            Ok((coord("x"), coord("y")))
+        }
+        _ => Err(crate::Error::element_not_found(element_id)),
+    }
+}
+
+/// 读取当前布局视口的大小（CSS 像素）
+async fn viewport_size(client: &dyn CdpClient) -> crate::Result<(f64, f64)> {
+    let metrics = client
+        .call_method("Page.getLayoutMetrics", serde_json::json!({}))
+        .await?;
+    let viewport = &metrics["cssLayoutViewport"];
+    let size = |key: &str| viewport.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+    Ok((size("clientWidth"), size("clientHeight")))
+}
+
+/// 判断坐标是否位于视口内
+fn within_viewport(x: f64, y: f64, width: f64, height: f64) -> bool {
+    (0.0..width).contains(&x) && (0.0..height).contains(&y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    async fn handlers_with(client: Arc<MockCdpClient>) -> (InputHandlers<MockSessionManager>, String) {
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client,
+            )))
+            .await;
+        (InputHandlers { session_manager: manager }, page_id)
+    }
+
+    #[tokio::test]
+    async fn test_click_at_presses_and_releases_at_point() {
+        let client = Arc::new(MockCdpClient::new());
+        client.set_layout_size(800.0, 2000.0, 600.0).await;
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        handlers
+            .click_at(Request::new(ClickAtRequest {
+                page_id,
+                x: 120.5,
+                y: 340.0,
+                button: ProtoMouseButton::Right as i32,
+                click_count: 2,
+            }))
+            .await
+            .unwrap();
+
+        let events = client.recorded_calls("Input.dispatchMouseEvent").await;
+        let clicks: Vec<_> = events.iter().filter(|e| e["type"] != "mouseMoved").collect();
+        assert_eq!(clicks.len(), 4);
+        for (event, (kind, count)) in clicks.iter().zip([
+            ("mousePressed", 1),
+            ("mouseReleased", 1),
+            ("mousePressed", 2),
+            ("mouseReleased", 2),
+        ]) {
+            assert_eq!(event["type"], kind);
+            assert_eq!(event["x"], 120.5);
+            assert_eq!(event["y"], 340.0);
+            assert_eq!(event["button"], "right");
+            assert_eq!(event["clickCount"], count);
+        }
+
+        // 按下前先沿路径移动到目标点
+        let last_move = events.iter().rev().find(|e| e["type"] == "mouseMoved").unwrap();
+        assert_eq!(last_move["x"], 120.5);
+        assert_eq!(last_move["y"], 340.0);
+    }
+
+    #[tokio::test]
+    async fn test_click_at_rejects_point_outside_viewport() {
+        let client = Arc::new(MockCdpClient::new());
+        client.set_layout_size(800.0, 2000.0, 600.0).await;
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        let status = handlers
+            .click_at(Request::new(ClickAtRequest {
+                page_id,
+                x: 100.0,
+                y: 700.0,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(client.recorded_calls("Input.dispatchMouseEvent").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tap_and_swipe_dispatch_touch_sequence() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+        let page = handlers.session_manager.get_page(&page_id).await.unwrap();
+        page.set_touch_emulation(true).await.unwrap();
+
+        handlers
+            .tap(Request::new(TapRequest {
+                page_id: page_id.clone(),
+                target: Some(TapTarget::Point(TouchPoint { x: 50.0, y: 80.0 })),
+            }))
+            .await
+            .unwrap();
+        handlers
+            .swipe(Request::new(SwipeRequest {
+                page_id,
+                start: Some(TouchPoint { x: 100.0, y: 500.0 }),
+                end: Some(TouchPoint { x: 100.0, y: 100.0 }),
+                steps: 4,
+                duration_ms: 40,
+            }))
+            .await
+            .unwrap();
+
+        let events = client.recorded_calls("Input.dispatchTouchEvent").await;
+        let sequence: Vec<_> = events
+            .iter()
+            .map(|e| {
+                let point = e["touchPoints"].get(0).map(|p| (p["x"].as_f64().unwrap(), p["y"].as_f64().unwrap()));
+                (e["type"].as_str().unwrap(), point)
+            })
+            .collect();
+        assert_eq!(
+            sequence,
+            vec![
+                ("touchStart", Some((50.0, 80.0))),
+                ("touchEnd", None),
+                ("touchStart", Some((100.0, 500.0))),
+                ("touchMove", Some((100.0, 400.0))),
+                ("touchMove", Some((100.0, 300.0))),
+                ("touchMove", Some((100.0, 200.0))),
+                ("touchMove", Some((100.0, 100.0))),
+                ("touchEnd", None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tap_requires_touch_emulation() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        let status = handlers
+            .tap(Request::new(TapRequest {
+                page_id,
+                target: Some(TapTarget::Point(TouchPoint { x: 10.0, y: 10.0 })),
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert!(client.recorded_calls("Input.dispatchTouchEvent").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_mouse_trace_dispatches_points_in_order() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+        let trace = [(10.0, 20.0, 1000), (15.0, 22.0, 1016), (30.0, 25.0, 1016), (42.5, 31.0, 1050)];
+
+        let started = std::time::Instant::now();
+        handlers
+            .replay_mouse_trace(Request::new(ReplayMouseTraceRequest {
+                page_id,
+                points: trace
+                    .iter()
+                    .map(|&(x, y, t_ms)| crate::chaser_oxide::v1::MouseTracePoint { x, y, t_ms })
+                    .collect(),
+            }))
+            .await
+            .unwrap();
+
+        // 时间戳相对于首个点，整体耗时至少为轨迹跨度
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        let events = client.recorded_calls("Input.dispatchMouseEvent").await;
+        let points: Vec<_> = events
+            .iter()
+            .map(|e| {
+                assert_eq!(e["type"], "mouseMoved");
+                (e["x"].as_f64().unwrap(), e["y"].as_f64().unwrap())
+            })
+            .collect();
+        assert_eq!(points, vec![(10.0, 20.0), (15.0, 22.0), (30.0, 25.0), (42.5, 31.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_mouse_trace_rejects_invalid_traces() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+        let request = |times: &[i64]| {
+            Request::new(ReplayMouseTraceRequest {
+                page_id: page_id.clone(),
+                points: times
+                    .iter()
+                    .map(|&t_ms| crate::chaser_oxide::v1::MouseTracePoint { x: 1.0, y: 1.0, t_ms })
+                    .collect(),
+            })
+        };
+
+        for times in [&[][..], &[0, 20, 10][..], &[-5, 0][..], &[0, 60_001][..]] {
+            let status = handlers.replay_mouse_trace(request(times)).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument, "{:?}", times);
+        }
+        assert!(client.recorded_calls("Input.dispatchMouseEvent").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_idle_moves_mouse_within_viewport() {
+        let client = Arc::new(MockCdpClient::new());
+        client.set_layout_size(400.0, 3000.0, 300.0).await;
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        handlers
+            .simulate_idle(Request::new(SimulateIdleRequest { page_id, duration_ms: 100 }))
+            .await
+            .unwrap();
+
+        let events = client.recorded_calls("Input.dispatchMouseEvent").await;
+        let moves: Vec<_> = events.iter().filter(|e| e["type"] == "mouseMoved").collect();
+        assert!(!moves.is_empty());
+        for event in events {
+            let (x, y) = (event["x"].as_f64().unwrap(), event["y"].as_f64().unwrap());
+            assert!(within_viewport(x, y, 400.0, 300.0), "({}, {}) left the viewport", x, y);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_keyboard_dispatches_to_page_client() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        handlers
+            .page_type(Request::new(PageTypeRequest {
+                page_id: page_id.clone(),
+                text: "ok".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        handlers
+            .page_press_key(Request::new(PagePressKeyRequest {
+                page_id: page_id.clone(),
+                key: "Ctrl+Enter".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let events = client.recorded_calls("Input.dispatchKeyEvent").await;
+        let sequence: Vec<_> = events
+            .iter()
+            .map(|e| (e["type"].as_str().unwrap(), e["key"].as_str().unwrap(), e["modifiers"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(
+            sequence,
+            vec![
+                ("keyDown", "o", 0),
+                ("keyUp", "o", 0),
+                ("keyDown", "k", 0),
+                ("keyUp", "k", 0),
+                ("rawKeyDown", "Control", 2),
+                ("rawKeyDown", "Enter", 2),
+                ("keyUp", "Enter", 2),
+                ("keyUp", "Control", 0),
+            ]
+        );
+
+        handlers
+            .page_type(Request::new(PageTypeRequest {
+                page_id: page_id.clone(),
+                text: "pasted".to_string(),
+                insert_text: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(client.recorded_calls("Input.insertText").await[0]["text"], "pasted");
+        assert_eq!(client.recorded_calls("Input.dispatchKeyEvent").await.len(), 8);
+
+        let status = handlers
+            .page_press_key(Request::new(PagePressKeyRequest {
+                page_id,
+                key: "Shift+".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    // 测试：页面高度增长后趋于稳定时，滚动到底部并返回滚动步数
+    #[tokio::test]
+    async fn test_scroll_to_bottom_until_height_stabilizes() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let metrics = |height: f64, top: f64| {
+            SessionEvaluationResult::Object(serde_json::json!({
+                "scrollHeight": height, "clientHeight": 800.0, "scrollTop": top, "x": 640.0, "y": 400.0,
+            }))
+        };
+        // 每次到达底部后加载一屏新内容，两次后不再增长
+        page.stub_evaluate(
+            "scrollHeight",
+            vec![
+                metrics(1600.0, 0.0),
+                metrics(2400.0, 800.0),
+                metrics(2400.0, 1600.0),
+                metrics(3200.0, 1600.0),
+                metrics(3200.0, 1600.0),
+                metrics(3200.0, 2400.0),
+                metrics(3200.0, 2400.0),
+            ],
+        )
+        .await;
+        let page_id = manager.register_page(page).await;
+        let handlers = InputHandlers { session_manager: manager };
+
+        let response = handlers
+            .scroll_to_bottom(Request::new(ScrollToBottomRequest {
+                page_id,
+                settle_ms: 10,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ScrollToBottomResponseEnum::Result(result)) = response.response else {
+            panic!("expected a scroll result");
+        };
+        assert_eq!(result.scroll_count, 3);
+        assert_eq!(result.scroll_height, 3200.0);
+        assert!(result.reached_bottom);
+
+        // 每步滚动一屏，以 100 像素一格的滚轮事件发出
+        let wheels = client.recorded_calls("Input.dispatchMouseEvent").await;
+        assert_eq!(wheels.len(), 24);
+        assert!(wheels.iter().all(|e| e["type"] == "mouseWheel" && e["deltaY"] == 100.0 && e["x"] == 640.0));
+    }
+}
@@ -8,7 +8,18 @@ mod script;
 mod emulation;
 mod network;
 mod cookies;
+mod storage;
+mod coverage;
+mod profiling;
 mod wait;
+mod input;
+mod reset;
+mod frames;
+mod dialog;
+mod har;
+mod style;
+mod console;
+mod extract;
 
 pub use navigation::*;
 pub use content::*;
@@ -16,4 +27,15 @@ pub use script::*;
 pub use emulation::*;
 pub use network::*;
 pub use cookies::*;
+pub use storage::*;
+pub use coverage::*;
+pub use profiling::*;
 pub use wait::*;
+pub use input::*;
+pub use reset::*;
+pub use frames::*;
+pub use dialog::*;
+pub use har::*;
+pub use style::*;
+pub use console::*;
+pub use extract::*;
@@ -1,56 +1,106 @@
 //! 导航相关的 RPC 方法处理器
 //!
-//! 包括：create_page, navigate, reload, go_back, go_forward, close_page
+//! 包括：create_page, navigate, reload, go_back, go_forward, wait_for_navigation, wait_for_load_state, click_and_wait, close_page
 
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
-use tracing::{error, info};
-use crate::session::{SessionManager, PageOptions, NavigationOptions};
+use tracing::{error, info, warn};
+use crate::config::ViewportConfig;
+use crate::services::common::cancel::run_cancellable;
+use crate::session::cancel::stop_loading_on_cancel;
+use crate::session::url_policy::UrlPolicy;
+use crate::session::{
+    wait_for_load_state, DefaultTimeouts, EvaluationResult, NavigationOptions, NavigationResult, NavigationWatch,
+    PageContext, PageOptions, SessionManager,
+};
+use crate::stealth::{BehaviorSimulator, BehaviorSimulatorImpl, ClickOptions, MouseButton};
 use crate::chaser_oxide::v1::{
     create_page_response::Response as CreatePageResponseEnum,
     navigate_response::Response as NavigateResponseEnum,
     reload_response::Response as ReloadResponseEnum,
     go_back_response::Response as GoBackResponseEnum,
     go_forward_response::Response as GoForwardResponseEnum,
+    wait_for_navigation_response::Response as WaitForNavigationResponseEnum,
+    wait_for_load_state_response::Response as WaitForLoadStateResponseEnum,
     close_page_response::Response as ClosePageResponseEnum,
+    click_and_wait_response::Response as ClickAndWaitResponseEnum,
     CreatePageRequest, CreatePageResponse,
     NavigateRequest, NavigateResponse,
     ReloadRequest, ReloadResponse,
     GoBackRequest, GoBackResponse,
     GoForwardRequest, GoForwardResponse,
+    WaitForNavigationRequest, WaitForNavigationResponse,
+    WaitForLoadStateRequest, WaitForLoadStateResponse,
     ClosePageRequest, ClosePageResponse,
+    ClickAndWaitRequest, ClickAndWaitResponse, ClickAndWaitResult,
     PageInfo as ProtoPageInfo,
-    NavigationResult as ProtoNavigationResult,
     Empty,
 };
 use super::super::{conversions, response};
+use super::input::element_center;
 
 /// 实现 PageService trait 中的导航相关方法
 ///
 /// 这些方法应该被 impl<S> PageService for Service<S> 块使用
 pub struct NavigationHandlers<S> {
     pub session_manager: Arc<S>,
+    /// 请求未指定超时（0）时使用的导航超时（毫秒）
+    pub default_timeout: u64,
+    /// 请求未指定视口时使用的默认视口
+    pub default_viewport: ViewportConfig,
+    /// 导航目标 URL 的允许/拒绝规则
+    pub url_policy: Arc<UrlPolicy>,
 }
 
 impl<S> NavigationHandlers<S>
 where
     S: SessionManager + Send + Sync + 'static,
 {
+    /// 请求未指定超时时的导航超时：优先使用页面从浏览器继承的超时
+    fn default_timeout_for(&self, page: &dyn PageContext) -> u64 {
+        DefaultTimeouts::resolve(0, page.default_timeouts().navigation_ms, self.default_timeout)
+    }
+
     /// 创建新页面
     pub async fn create_page(&self, request: Request<CreatePageRequest>) -> Result<Response<CreatePageResponse>, Status> {
         let req = request.into_inner();
+        // 初始 URL 不被允许时不创建页面
+        if !req.url.is_empty() {
+            self.url_policy.check(&req.url).await.map_err(response::error_to_status)?;
+        }
 
-        // 从请求构建 PageOptions
-        let mut page_options = PageOptions::default();
+        // 从请求构建 PageOptions，未指定视口时使用服务端默认视口
+        let mut page_options = PageOptions {
+            locale: Some(req.locale).filter(|locale| !locale.is_empty()),
+            accept_language: Some(req.accept_language).filter(|value| !value.is_empty()),
+            ..Default::default()
+        };
 
-        if let Some(viewport) = req.viewport {
-            page_options.viewport_width = viewport.width.max(0) as u32;
-            page_options.viewport_height = viewport.height.max(0) as u32;
-            page_options.device_scale_factor = viewport.device_scale_factor;
+        match req.viewport {
+            Some(viewport) => {
+                page_options.viewport_width = viewport.width.max(0) as u32;
+                page_options.viewport_height = viewport.height.max(0) as u32;
+                page_options.device_scale_factor = viewport.device_scale_factor;
+            }
+            None => {
+                page_options.viewport_width = self.default_viewport.width;
+                page_options.viewport_height = self.default_viewport.height;
+                page_options.device_scale_factor = self.default_viewport.device_scale_factor;
+            }
         }
+        let (width, height, scale) = (
+            page_options.viewport_width,
+            page_options.viewport_height,
+            page_options.device_scale_factor,
+        );
 
         match self.session_manager.create_page(&req.browser_id, page_options).await {
             Ok(page) => {
+                // 页面已创建，视口设置失败不影响返回结果
+                if let Err(e) = page.set_viewport(width, height, scale).await {
+                    warn!(page_id = %page.id(), error = %e, "Failed to apply viewport");
+                }
+
                 info!(
                     page_id = %page.id(),
                     browser_id = %req.browser_id,
@@ -95,17 +145,22 @@ where
     /// 导航到指定 URL
     pub async fn navigate(&self, request: Request<NavigateRequest>) -> Result<Response<NavigateResponse>, Status> {
         let req = request.into_inner();
+        self.url_policy.check(&req.url).await.map_err(response::error_to_status)?;
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
-                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default());
-                match page.navigate(&req.url, options).await {
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                // 客户端取消请求时停止浏览器中的加载
+                let url = req.url;
+                let navigated = run_cancellable(move |cancel| async move {
+                    let client = page.get_cdp_client();
+                    stop_loading_on_cancel(client.as_ref(), &cancel, page.navigate(&url, options)).await
+                })
+                .await;
+                match navigated {
                     Ok(result) => {
-                        let nav_result = conversions::navigation_result_to_proto(
-                            result.url,
-                            result.status_code,
-                            result.is_loaded,
-                        );
+                        let nav_result = conversions::navigation_result_to_proto(result);
                         Ok(Response::new(NavigateResponse {
                             response: Some(NavigateResponseEnum::Result(nav_result)),
                         }))
@@ -123,15 +178,19 @@ where
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
-                match page.reload(false).await {
-                    Ok(_) => {
-                        let result = ProtoNavigationResult {
-                            url: String::new(),
-                            status_code: 200,
-                            is_loaded: true,
-                        };
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                let ignore_cache = req.ignore_cache;
+                let reloaded = run_cancellable(move |cancel| async move {
+                    let client = page.get_cdp_client();
+                    stop_loading_on_cancel(client.as_ref(), &cancel, page.reload(ignore_cache, options)).await
+                })
+                .await;
+                match reloaded {
+                    Ok(result) => {
+                        let nav_result = conversions::navigation_result_to_proto(result);
                         Ok(Response::new(ReloadResponse {
-                            response: Some(ReloadResponseEnum::Result(result)),
+                            response: Some(ReloadResponseEnum::Result(nav_result)),
                         }))
                     }
                     Err(e) => Err(response::error_to_status(e)),
@@ -147,15 +206,19 @@ where
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
-                match page.go_back().await {
-                    Ok(_) => {
-                        let result = ProtoNavigationResult {
-                            url: String::new(),
-                            status_code: 200,
-                            is_loaded: true,
-                        };
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                let delta = req.delta.max(1) as u32;
+                let moved = run_cancellable(move |cancel| async move {
+                    let client = page.get_cdp_client();
+                    stop_loading_on_cancel(client.as_ref(), &cancel, page.go_back(delta, options)).await
+                })
+                .await;
+                match moved {
+                    Ok(result) => {
+                        let nav_result = conversions::navigation_result_to_proto(result);
                         Ok(Response::new(GoBackResponse {
-                            response: Some(GoBackResponseEnum::Result(result)),
+                            response: Some(GoBackResponseEnum::Result(nav_result)),
                         }))
                     }
                     Err(e) => Err(response::error_to_status(e)),
@@ -171,15 +234,42 @@ where
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
-                match page.go_forward().await {
-                    Ok(_) => {
-                        let result = ProtoNavigationResult {
-                            url: String::new(),
-                            status_code: 200,
-                            is_loaded: true,
-                        };
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                let delta = req.delta.max(1) as u32;
+                let moved = run_cancellable(move |cancel| async move {
+                    let client = page.get_cdp_client();
+                    stop_loading_on_cancel(client.as_ref(), &cancel, page.go_forward(delta, options)).await
+                })
+                .await;
+                match moved {
+                    Ok(result) => {
+                        let nav_result = conversions::navigation_result_to_proto(result);
                         Ok(Response::new(GoForwardResponse {
-                            response: Some(GoForwardResponseEnum::Result(result)),
+                            response: Some(GoForwardResponseEnum::Result(nav_result)),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 等待下一次主框架导航完成
+    pub async fn wait_for_navigation(&self, request: Request<WaitForNavigationRequest>) -> Result<Response<WaitForNavigationResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                let url_pattern = Some(req.url_pattern.as_str()).filter(|p| !p.is_empty());
+                match page.wait_for_navigation(url_pattern, options).await {
+                    Ok(result) => {
+                        let nav_result = conversions::navigation_result_to_proto(result);
+                        Ok(Response::new(WaitForNavigationResponse {
+                            response: Some(WaitForNavigationResponseEnum::Result(nav_result)),
                         }))
                     }
                     Err(e) => Err(response::error_to_status(e)),
@@ -189,6 +279,87 @@ where
         }
     }
 
+    /// 等待当前文档达到指定的加载状态，不触发导航
+    ///
+    /// 已达到时立即返回，适用于 set_content、前进/后退等改变加载状态的操作之后
+    pub async fn wait_for_load_state(&self, request: Request<WaitForLoadStateRequest>) -> Result<Response<WaitForLoadStateResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                wait_for_load_state(page.get_cdp_client().as_ref(), options.wait_until, options.timeout)
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                Ok(Response::new(WaitForLoadStateResponse {
+                    response: Some(WaitForLoadStateResponseEnum::Success(Empty {})),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 点击元素并等待其触发的导航
+    ///
+    /// 先订阅导航事件再点击，避免导航在单独的等待请求之前就已开始；
+    /// 超时仍未导航时返回当前页面状态，navigated 为 false
+    pub async fn click_and_wait(&self, request: Request<ClickAndWaitRequest>) -> Result<Response<ClickAndWaitResponse>, Status> {
+        let req = request.into_inner();
+        let Some(element) = req.element else {
+            return Err(Status::invalid_argument("element is required"));
+        };
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let options = conversions::proto_to_navigation_options(req.options.unwrap_or_default(), self.default_timeout_for(page.as_ref()))
+                    .map_err(response::error_to_status)?;
+                let result = self
+                    .click_and_wait_inner(page.as_ref(), &element.element_id, options)
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                Ok(Response::new(ClickAndWaitResponse {
+                    response: Some(ClickAndWaitResponseEnum::Result(result)),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    async fn click_and_wait_inner(
+        &self,
+        page: &dyn PageContext,
+        element_id: &str,
+        options: NavigationOptions,
+    ) -> crate::Result<ClickAndWaitResult> {
+        let selector = serde_json::json!(format!("[data-element-id=\"{}\"]", element_id));
+        page.evaluate(
+            &format!("document.querySelector({})?.scrollIntoView({{ block: 'center', inline: 'center' }})", selector),
+            false,
+        )
+        .await?;
+        let center = element_center(page, element_id).await?;
+
+        let client = page.get_cdp_client();
+        let watch = NavigationWatch::start(client.as_ref()).await?;
+        BehaviorSimulatorImpl::new(client)
+            .simulate_click_at(page.id(), center, MouseButton::Left, 1, ClickOptions::default())
+            .await?;
+
+        let (navigation, navigated) = match watch.wait(None, options).await {
+            Ok(navigation) => (navigation, true),
+            Err(crate::Error::Timeout(_)) => (current_document(page).await?, false),
+            Err(e) => return Err(e),
+        };
+
+        Ok(ClickAndWaitResult {
+            navigation: Some(conversions::navigation_result_to_proto(navigation)),
+            navigated,
+        })
+    }
+
     /// 关闭页面
     pub async fn close_page(&self, request: Request<ClosePageRequest>) -> Result<Response<ClosePageResponse>, Status> {
         let req = request.into_inner();
@@ -203,3 +374,322 @@ where
         }
     }
 }
+
+/// 未发生导航时的当前文档状态；状态码未知，记为 0
+async fn current_document(page: &dyn PageContext) -> crate::Result<NavigationResult> {
+    let state = page
+        .evaluate("({ url: window.location.href, readyState: document.readyState })", false)
+        .await?;
+    let EvaluationResult::Object(state) = state else {
+        return Err(crate::Error::internal("Unexpected document state result"));
+    };
+    let field = |key: &str| state.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+    Ok(NavigationResult {
+        url: field("url"),
+        status_code: 0,
+        is_loaded: field("readyState") == "complete",
+        redirect_chain: Vec::new(),
+        redirect_chain_truncated: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chaser_oxide::v1::Viewport;
+    use crate::session::BrowserContext;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockBrowser, MockPage, MockSessionManager};
+
+    async fn handlers_with_browser() -> (NavigationHandlers<MockSessionManager>, Arc<MockBrowser>) {
+        let manager = Arc::new(MockSessionManager::new());
+        let browser = Arc::new(MockBrowser::new(Default::default()));
+        manager.register_browser(browser.clone()).await;
+        let handlers = NavigationHandlers {
+            session_manager: manager,
+            default_timeout: 30000,
+            default_viewport: ViewportConfig {
+                width: 1366,
+                height: 768,
+                device_scale_factor: 2.0,
+            },
+            url_policy: Arc::default(),
+        };
+        (handlers, browser)
+    }
+
+    #[tokio::test]
+    async fn test_create_page_applies_default_viewport() {
+        let (handlers, browser) = handlers_with_browser().await;
+
+        handlers
+            .create_page(Request::new(CreatePageRequest {
+                browser_id: browser.id().to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let pages = browser.pages().await;
+        assert_eq!(pages[0].viewport().await, (1366, 768, 2.0));
+    }
+
+    #[tokio::test]
+    async fn test_create_page_prefers_requested_viewport() {
+        let (handlers, browser) = handlers_with_browser().await;
+
+        handlers
+            .create_page(Request::new(CreatePageRequest {
+                browser_id: browser.id().to_string(),
+                viewport: Some(Viewport {
+                    width: 800,
+                    height: 600,
+                    device_scale_factor: 1.0,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+
+        let pages = browser.pages().await;
+        assert_eq!(pages[0].viewport().await, (800, 600, 1.0));
+    }
+
+    /// 页面上有一个按钮（中心位于 (50, 20)）的处理器
+    async fn handlers_with_button() -> (NavigationHandlers<MockSessionManager>, String, Arc<MockCdpClient>, Arc<MockPage>) {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        page.stub_evaluate(
+            "getBoundingClientRect",
+            vec![EvaluationResult::Object(serde_json::json!({ "x": 50.0, "y": 20.0 }))],
+        )
+        .await;
+        let page_id = manager.register_page(page.clone()).await;
+        let handlers = NavigationHandlers {
+            session_manager: manager,
+            default_timeout: 30000,
+            default_viewport: ViewportConfig::default(),
+            url_policy: Arc::default(),
+        };
+        (handlers, page_id, client, page)
+    }
+
+    fn click_request(page_id: &str, timeout: i32) -> ClickAndWaitRequest {
+        ClickAndWaitRequest {
+            page_id: page_id.to_string(),
+            element: Some(crate::chaser_oxide::v1::ElementRef {
+                page_id: page_id.to_string(),
+                element_id: "link".to_string(),
+                ..Default::default()
+            }),
+            options: Some(crate::chaser_oxide::v1::NavigationOptions {
+                timeout,
+                ..Default::default()
+            }),
+        }
+    }
+
+    // 测试：按钮的点击处理器设置 location 时，返回新文档
+    #[tokio::test]
+    async fn test_click_and_wait_returns_triggered_navigation() {
+        let (handlers, page_id, client, _page) = handlers_with_button().await;
+
+        // 模拟页面：鼠标释放后开始导航到 /next
+        let page_client = client.clone();
+        tokio::spawn(async move {
+            loop {
+                let clicks = page_client.recorded_calls("Input.dispatchMouseEvent").await;
+                if clicks.iter().any(|c| c["type"] == "mouseReleased") {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            page_client
+                .emit_event(
+                    "Page.frameNavigated",
+                    serde_json::json!({ "frame": { "id": "main", "loaderId": "next", "url": "https://example.com/next" } }),
+                )
+                .await;
+            page_client
+                .emit_event(
+                    "Network.responseReceived",
+                    serde_json::json!({
+                        "type": "Document",
+                        "frameId": "main",
+                        "response": { "url": "https://example.com/next", "status": 201 },
+                    }),
+                )
+                .await;
+            page_client
+                .emit_event("Page.lifecycleEvent", serde_json::json!({ "loaderId": "next", "name": "load" }))
+                .await;
+        });
+
+        let result = handlers
+            .click_and_wait(Request::new(click_request(&page_id, 5000)))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ClickAndWaitResponseEnum::Result(result)) = result.response else {
+            panic!("expected a click result");
+        };
+        assert!(result.navigated);
+        let navigation = result.navigation.unwrap();
+        assert_eq!(navigation.url, "https://example.com/next");
+        assert_eq!(navigation.status_code, 201);
+
+        let clicks = client.recorded_calls("Input.dispatchMouseEvent").await;
+        let press = clicks.iter().find(|c| c["type"] == "mousePressed").unwrap();
+        assert_eq!((press["x"].as_f64(), press["y"].as_f64()), (Some(50.0), Some(20.0)));
+    }
+
+    // 测试：点击未触发导航时，超时后返回当前文档并标记 navigated = false
+    #[tokio::test]
+    async fn test_click_and_wait_without_navigation() {
+        let (handlers, page_id, _client, page) = handlers_with_button().await;
+        page.stub_evaluate(
+            "document.readyState",
+            vec![EvaluationResult::Object(
+                serde_json::json!({ "url": "https://example.com/", "readyState": "complete" }),
+            )],
+        )
+        .await;
+
+        let result = handlers
+            .click_and_wait(Request::new(click_request(&page_id, 100)))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ClickAndWaitResponseEnum::Result(result)) = result.response else {
+            panic!("expected a click result");
+        };
+        assert!(!result.navigated);
+        let navigation = result.navigation.unwrap();
+        assert_eq!(navigation.url, "https://example.com/");
+        assert_eq!(navigation.status_code, 0);
+        assert!(navigation.is_loaded);
+    }
+
+    fn load_state_request(page_id: &str, state: crate::chaser_oxide::v1::navigation_options::LoadState, timeout: i32) -> Request<WaitForLoadStateRequest> {
+        Request::new(WaitForLoadStateRequest {
+            page_id: page_id.to_string(),
+            options: Some(crate::chaser_oxide::v1::NavigationOptions {
+                timeout,
+                wait_until: state as i32,
+                ..Default::default()
+            }),
+        })
+    }
+
+    // 测试：页面已加载完成时立即返回
+    #[tokio::test]
+    async fn test_wait_for_load_state_resolves_immediately_when_loaded() {
+        use crate::chaser_oxide::v1::navigation_options::LoadState as ProtoLoadState;
+
+        let (handlers, page_id, client, _page) = handlers_with_button().await;
+
+        let started = std::time::Instant::now();
+        let response = handlers
+            .wait_for_load_state(load_state_request(&page_id, ProtoLoadState::Load, 5000))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(matches!(response.response, Some(WaitForLoadStateResponseEnum::Success(_))));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(client.recorded_calls("Page.setLifecycleEventsEnabled").await[0]["enabled"], true);
+        assert!(client.recorded_calls("Page.navigate").await.is_empty());
+    }
+
+    // 测试：等待网络空闲时，收到主框架的生命周期事件后才返回，子框架事件被忽略
+    #[tokio::test]
+    async fn test_wait_for_load_state_resolves_after_lifecycle_event() {
+        use crate::cdp::mock::MOCK_FRAME_ID;
+        use crate::chaser_oxide::v1::navigation_options::LoadState as ProtoLoadState;
+
+        let (handlers, page_id, client, _page) = handlers_with_button().await;
+        let events = client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            events
+                .emit_event("Page.lifecycleEvent", serde_json::json!({ "frameId": "child", "name": "networkIdle" }))
+                .await;
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            events
+                .emit_event("Page.lifecycleEvent", serde_json::json!({ "frameId": MOCK_FRAME_ID, "name": "networkIdle" }))
+                .await;
+        });
+
+        let started = std::time::Instant::now();
+        let response = handlers
+            .wait_for_load_state(load_state_request(&page_id, ProtoLoadState::NetworkIdle, 5000))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(matches!(response.response, Some(WaitForLoadStateResponseEnum::Success(_))));
+        assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+
+        let status = handlers
+            .wait_for_load_state(load_state_request(&page_id, ProtoLoadState::NetworkIdle, 100))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+        assert!(status.message().contains("networkIdle"));
+    }
+
+    // 测试：导航前按 URL 策略检查目标，被拒绝的 URL 不会发起导航
+    #[tokio::test]
+    async fn test_navigate_enforces_url_policy() {
+        let (mut handlers, page_id, _client, _page) = handlers_with_button().await;
+        handlers.url_policy = Arc::new(UrlPolicy {
+            allow: vec!["*.example.com".to_string(), "http://10.0.0.5/*".to_string()],
+            deny: vec!["https://*/admin/*".to_string()],
+            block_private_networks: true,
+        });
+        let navigate = |url: &str| {
+            Request::new(NavigateRequest {
+                page_id: page_id.clone(),
+                url: url.to_string(),
+                ..Default::default()
+            })
+        };
+
+        // 不匹配任何允许规则
+        let status = handlers.navigate(navigate("http://93.184.216.34/")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let status = handlers.navigate(navigate("https://www.example.com/admin/users")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert!(status.message().contains("denied pattern"));
+
+        // 即使匹配允许规则，私有地址仍被拒绝
+        let status = handlers.navigate(navigate("http://10.0.0.5/metrics")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert!(status.message().contains("private networks"));
+
+        let status = handlers.navigate(navigate("not a url")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        handlers.url_policy = Arc::new(UrlPolicy {
+            allow: vec!["93.184.216.*".to_string()],
+            block_private_networks: true,
+            ..Default::default()
+        });
+        // 允许的 IP 字面量无需解析主机名
+        let response = handlers
+            .navigate(navigate("http://93.184.216.34/"))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(NavigateResponseEnum::Result(result)) = response.response else {
+            panic!("expected a navigation result");
+        };
+        assert_eq!(result.url, "http://93.184.216.34/");
+    }
+}
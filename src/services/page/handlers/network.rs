@@ -1,21 +1,83 @@
 //! 网络相关的 RPC 方法处理器
 //!
-//! 包括：set_offline_mode, set_cache_enabled, override_permissions
+//! 包括：set_offline_mode, set_cache_enabled, set_extra_headers, set_http_credentials, block_urls, override_permissions
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
-use crate::session::SessionManager;
+use crate::session::{HttpCredentials, SessionManager};
 use crate::chaser_oxide::v1::{
     set_offline_mode_response::Response as SetOfflineModeResponseEnum,
     set_cache_enabled_response::Response as SetCacheEnabledResponseEnum,
+    set_extra_headers_response::Response as SetExtraHeadersResponseEnum,
+    set_http_credentials_response::Response as SetHttpCredentialsResponseEnum,
+    block_urls_response::Response as BlockUrlsResponseEnum,
     override_permissions_response::Response as OverridePermissionsResponseEnum,
     SetOfflineModeRequest, SetOfflineModeResponse,
     SetCacheEnabledRequest, SetCacheEnabledResponse,
+    SetExtraHeadersRequest, SetExtraHeadersResponse,
+    SetHttpCredentialsRequest, SetHttpCredentialsResponse,
+    BlockUrlsRequest, BlockUrlsResponse,
     OverridePermissionsRequest, OverridePermissionsResponse,
     Empty,
 };
 use super::super::{response, scripts};
 
+/// 禁止由调用方设置的请求头（Fetch 规范 forbidden request-header）
+const FORBIDDEN_HEADERS: &[&str] = &[
+    "accept-charset",
+    "accept-encoding",
+    "access-control-request-headers",
+    "access-control-request-method",
+    "connection",
+    "content-length",
+    "cookie",
+    "cookie2",
+    "date",
+    "dnt",
+    "expect",
+    "host",
+    "keep-alive",
+    "origin",
+    "referer",
+    "set-cookie",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "via",
+];
+
+/// 禁止的请求头前缀
+const FORBIDDEN_HEADER_PREFIXES: &[&str] = &["proxy-", "sec-"];
+
+/// 校验额外请求头
+///
+/// 头名称必须是 RFC 7230 token，且不能是规范禁止的请求头；
+/// 头值不能包含换行符。
+pub(crate) fn validate_extra_headers(headers: &HashMap<String, String>) -> crate::Result<()> {
+    for (name, value) in headers {
+        let is_token = !name.is_empty()
+            && name.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+        if !is_token {
+            return Err(crate::Error::configuration(format!("Invalid header name: {:?}", name)));
+        }
+
+        let lower = name.to_ascii_lowercase();
+        if FORBIDDEN_HEADERS.contains(&lower.as_str())
+            || FORBIDDEN_HEADER_PREFIXES.iter().any(|p| lower.starts_with(p))
+        {
+            return Err(crate::Error::configuration(format!("Forbidden header: {}", name)));
+        }
+
+        if value.contains(['\r', '\n', '\0']) {
+            return Err(crate::Error::configuration(format!("Invalid value for header: {}", name)));
+        }
+    }
+
+    Ok(())
+}
+
 /// 实现 PageService trait 中的网络相关方法
 pub struct NetworkHandlers<S> {
     pub session_manager: Arc<S>,
@@ -67,6 +129,88 @@ where
         }
     }
 
+    /// 设置额外请求头，空表清除之前设置的请求头
+    pub async fn set_extra_headers(&self, request: Request<SetExtraHeadersRequest>) -> Result<Response<SetExtraHeadersResponse>, Status> {
+        let req = request.into_inner();
+
+        if let Err(e) = validate_extra_headers(&req.headers) {
+            return Err(response::error_to_status(e));
+        }
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                match page.get_cdp_client().set_extra_http_headers(&req.headers).await {
+                    Ok(_) => {
+                        Ok(Response::new(SetExtraHeadersResponse {
+                            response: Some(SetExtraHeadersResponseEnum::Success(Empty {})),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 设置 HTTP 认证凭据，未提供凭据时清除
+    pub async fn set_http_credentials(&self, request: Request<SetHttpCredentialsRequest>) -> Result<Response<SetHttpCredentialsResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let credentials = req.credentials.map(|c| HttpCredentials {
+                    username: c.username,
+                    password: c.password,
+                });
+
+                match page.set_http_credentials(credentials).await {
+                    Ok(_) => {
+                        Ok(Response::new(SetHttpCredentialsResponse {
+                            response: Some(SetHttpCredentialsResponseEnum::Success(Empty {})),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 屏蔽匹配任一模式的请求，新列表替换旧列表，空列表清除屏蔽
+    ///
+    /// 使用 `Network.setBlockedURLs`，被屏蔽的请求直接失败，比 Fetch 拦截开销小。
+    pub async fn block_urls(&self, request: Request<BlockUrlsRequest>) -> Result<Response<BlockUrlsResponse>, Status> {
+        let req = request.into_inner();
+
+        if req.patterns.iter().any(|p| p.trim().is_empty()) {
+            return Err(response::error_to_status(crate::Error::configuration("URL patterns must not be empty")));
+        }
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+                let result = async {
+                    // 屏蔽列表只在 Network 域启用时生效
+                    client.enable_domain("Network").await?;
+                    client
+                        .call_method("Network.setBlockedURLs", serde_json::json!({ "urls": req.patterns }))
+                        .await
+                }
+                .await;
+
+                match result {
+                    Ok(_) => {
+                        Ok(Response::new(BlockUrlsResponse {
+                            response: Some(BlockUrlsResponseEnum::Success(Empty {})),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
     /// 覆盖权限
     pub async fn override_permissions(&self, request: Request<OverridePermissionsRequest>) -> Result<Response<OverridePermissionsResponse>, Status> {
         let req = request.into_inner();
@@ -89,3 +233,87 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_validate_extra_headers_accepts_custom_headers() {
+        assert!(validate_extra_headers(&headers(&[
+            ("Authorization", "Bearer token"),
+            ("X-Custom-Header", "value"),
+            ("User-Agent", "Custom/1.0"),
+        ]))
+        .is_ok());
+        assert!(validate_extra_headers(&HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_extra_headers_rejects_invalid_names() {
+        assert!(validate_extra_headers(&headers(&[("", "v")])).is_err());
+        assert!(validate_extra_headers(&headers(&[("Bad Header", "v")])).is_err());
+        assert!(validate_extra_headers(&headers(&[("Bad:Header", "v")])).is_err());
+        assert!(validate_extra_headers(&headers(&[("X-Header", "a\r\nInjected: 1")])).is_err());
+    }
+
+    #[test]
+    fn test_validate_extra_headers_rejects_forbidden_headers() {
+        for name in ["Host", "cookie", "Content-Length", "Proxy-Authorization", "Sec-Fetch-Mode"] {
+            let result = validate_extra_headers(&headers(&[(name, "v")]));
+            assert!(matches!(result, Err(crate::Error::Configuration(_))), "{} should be rejected", name);
+        }
+    }
+
+    // 测试：屏蔽列表下发到 Network.setBlockedURLs，空列表清除
+    #[tokio::test]
+    async fn test_block_urls_applies_and_clears_patterns() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client.clone(),
+            )))
+            .await;
+        let handlers = NetworkHandlers { session_manager: manager };
+
+        let patterns = vec!["*google-analytics.com*".to_string(), "*.png".to_string()];
+        handlers
+            .block_urls(Request::new(BlockUrlsRequest { page_id: page_id.clone(), patterns: patterns.clone() }))
+            .await
+            .unwrap();
+        handlers
+            .block_urls(Request::new(BlockUrlsRequest { page_id, patterns: Vec::new() }))
+            .await
+            .unwrap();
+
+        let calls = client.recorded_calls("Network.setBlockedURLs").await;
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0]["urls"], serde_json::json!(patterns));
+        assert_eq!(calls[1]["urls"], serde_json::json!([]));
+    }
+
+    // 测试：空白模式被拒绝
+    #[tokio::test]
+    async fn test_block_urls_rejects_empty_pattern() {
+        let handlers = NetworkHandlers { session_manager: Arc::new(MockSessionManager::new()) };
+
+        let status = handlers
+            .block_urls(Request::new(BlockUrlsRequest {
+                page_id: "page".to_string(),
+                patterns: vec!["*.js".to_string(), " ".to_string()],
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,215 @@
+//! 内存分析相关的 RPC 方法处理器
+//!
+//! 包括：take_heap_snapshot, get_heap_usage
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use crate::cdp::traits::{CdpClient, CdpEvent};
+use crate::session::SessionManager;
+use crate::chaser_oxide::v1::{
+    get_heap_usage_response::Response as GetHeapUsageResponseEnum,
+    TakeHeapSnapshotRequest, HeapSnapshotChunk,
+    GetHeapUsageRequest, GetHeapUsageResponse, HeapUsage,
+};
+use super::super::response;
+
+/// 堆快照分块的流式响应
+pub type HeapSnapshotStream = ReceiverStream<Result<HeapSnapshotChunk, Status>>;
+
+/// 快照命令返回后，等待剩余分块到达的空闲时间
+const CHUNK_DRAIN_IDLE: Duration = Duration::from_millis(200);
+
+/// 发往客户端的分块缓冲数量，客户端读取较慢时形成背压
+const CHUNK_BUFFER: usize = 16;
+
+/// 实现 PageService trait 中的内存分析相关方法
+pub struct ProfilingHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+impl<S> ProfilingHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 获取 JS 堆快照，按 CDP 分块流式返回，避免在内存中缓存整个快照
+    pub async fn take_heap_snapshot(&self, request: Request<TakeHeapSnapshotRequest>) -> Result<Response<HeapSnapshotStream>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let client = page.get_cdp_client();
+
+                // 先订阅分块事件，确保不会错过第一个分块
+                let chunks = client
+                    .subscribe_events("HeapProfiler.addHeapSnapshotChunk")
+                    .await
+                    .map_err(response::error_to_status)?;
+                client
+                    .enable_domain("HeapProfiler")
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                let (tx, rx) = mpsc::channel(CHUNK_BUFFER);
+                tokio::spawn(async move {
+                    if let Err(status) = stream_heap_snapshot(client.as_ref(), chunks, &tx).await {
+                        let _ = tx.send(Err(status)).await;
+                    }
+
+                    let _ = client.call_method("HeapProfiler.disable", serde_json::json!({})).await;
+                });
+
+                Ok(Response::new(ReceiverStream::new(rx)))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 获取 JS 堆使用情况
+    pub async fn get_heap_usage(&self, request: Request<GetHeapUsageRequest>) -> Result<Response<GetHeapUsageResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let result = page
+                    .get_cdp_client()
+                    .call_method("Runtime.getHeapUsage", serde_json::json!({}))
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                let size = |key: &str| result.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
+
+                Ok(Response::new(GetHeapUsageResponse {
+                    response: Some(GetHeapUsageResponseEnum::Usage(HeapUsage {
+                        used_size: size("usedSize"),
+                        total_size: size("totalSize"),
+                    })),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
+
+/// 执行 HeapProfiler.takeHeapSnapshot，同时将分块事件转发给客户端
+///
+/// 分块事件与命令响应经由不同的通道到达，命令返回后继续转发，
+/// 直到 [`CHUNK_DRAIN_IDLE`] 内没有新的分块。客户端断开时提前结束。
+async fn stream_heap_snapshot(
+    client: &dyn CdpClient,
+    mut chunks: mpsc::Receiver<CdpEvent>,
+    tx: &mpsc::Sender<Result<HeapSnapshotChunk, Status>>,
+) -> Result<(), Status> {
+    let snapshot = client.call_method(
+        "HeapProfiler.takeHeapSnapshot",
+        serde_json::json!({ "reportProgress": false }),
+    );
+    tokio::pin!(snapshot);
+
+    let mut finished = false;
+    let mut events_open = true;
+
+    loop {
+        tokio::select! {
+            result = &mut snapshot, if !finished => {
+                result.map_err(response::error_to_status)?;
+                finished = true;
+            }
+            event = chunks.recv(), if events_open => match event {
+                Some(event) => {
+                    let data = event
+                        .params
+                        .get("chunk")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    if tx.send(Ok(HeapSnapshotChunk { data })).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                None => events_open = false,
+            },
+            _ = tokio::time::sleep(CHUNK_DRAIN_IDLE), if finished => return Ok(()),
+        }
+
+        if finished && !events_open {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+    use tokio_stream::StreamExt;
+
+    async fn handlers_with(client: Arc<MockCdpClient>) -> (ProfilingHandlers<MockSessionManager>, String) {
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client,
+            )))
+            .await;
+        (ProfilingHandlers { session_manager: manager }, page_id)
+    }
+
+    #[tokio::test]
+    async fn test_heap_snapshot_chunks_are_streamed_in_order() {
+        let parts = ["{\"snapshot\":", "{\"node_count\":3},", "\"nodes\":[1,2,3]}"];
+        let client = Arc::new(MockCdpClient::new());
+        client
+            .stub_events(
+                "HeapProfiler.takeHeapSnapshot",
+                parts
+                    .iter()
+                    .map(|chunk| ("HeapProfiler.addHeapSnapshotChunk", serde_json::json!({ "chunk": chunk })))
+                    .collect(),
+            )
+            .await;
+        let (handlers, page_id) = handlers_with(Arc::clone(&client)).await;
+
+        let mut stream = handlers
+            .take_heap_snapshot(Request::new(TakeHeapSnapshotRequest { page_id }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let mut received = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            received.push(chunk.unwrap().data);
+        }
+
+        assert_eq!(received.len(), parts.len());
+        assert_eq!(received.concat(), parts.concat());
+        assert_eq!(client.recorded_calls("HeapProfiler.disable").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_heap_usage() {
+        let client = Arc::new(MockCdpClient::new());
+        client
+            .stub_method("Runtime.getHeapUsage", serde_json::json!({ "usedSize": 1048576.0, "totalSize": 4194304.0 }))
+            .await;
+        let (handlers, page_id) = handlers_with(client).await;
+
+        let response = handlers
+            .get_heap_usage(Request::new(GetHeapUsageRequest { page_id }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match response.response {
+            Some(GetHeapUsageResponseEnum::Usage(usage)) => {
+                assert_eq!(usage.used_size, 1_048_576);
+                assert_eq!(usage.total_size, 4_194_304);
+            }
+            other => panic!("Expected heap usage, got {:?}", other),
+        }
+    }
+}
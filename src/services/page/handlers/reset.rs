@@ -0,0 +1,204 @@
+//! 页面重置相关的 RPC 方法处理器
+//!
+//! 包括：reset_page
+//!
+//! 清空 Cookie、Web Storage、注入的初始化脚本以及 UA/地理位置/时区覆盖，
+//! 最后导航到 about:blank，便于复用同一个页面而无需重新创建。
+//! UA 恢复为浏览器启动时配置的值，而不是 Chrome 自带的默认值。
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use crate::session::{DefaultTimeouts, NavigationOptions, PageContext, SessionManager};
+use crate::stealth::StealthEngine;
+use crate::chaser_oxide::v1::{
+    reset_page_response::Response as ResetPageResponseEnum,
+    ResetPageRequest, ResetPageResponse, ResetPageResult,
+    StorageType,
+};
+use super::super::response;
+use super::storage::storage_id;
+
+/// 重置时清除的覆盖项
+const OVERRIDES: &[&str] = &["user_agent", "geolocation", "timezone"];
+
+/// 实现 PageService trait 中的页面重置方法
+pub struct ResetHandlers<S> {
+    pub session_manager: Arc<S>,
+    pub stealth_engine: Option<Arc<dyn StealthEngine>>,
+    pub default_timeout: u64,
+}
+
+impl<S> ResetHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 重置页面状态并导航到 about:blank
+    pub async fn reset_page(&self, request: Request<ResetPageRequest>) -> Result<Response<ResetPageResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let result = self.reset(&req.page_id, page.as_ref()).await.map_err(response::error_to_status)?;
+
+                Ok(Response::new(ResetPageResponse {
+                    response: Some(ResetPageResponseEnum::Result(result)),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 依次清理各项状态；存储需要页面的 origin，因此必须在导航之前完成
+    async fn reset(&self, page_id: &str, page: &dyn PageContext) -> crate::Result<ResetPageResult> {
+        let client = page.get_cdp_client();
+
+        // Cookie：通过 CDP 删除，HttpOnly Cookie 也会被清除
+        let cookies = client.call_method("Network.getCookies", serde_json::json!({})).await?;
+        let cookies = cookies["cookies"].as_array().cloned().unwrap_or_default();
+        for cookie in &cookies {
+            client
+                .call_method(
+                    "Network.deleteCookies",
+                    serde_json::json!({
+                        "name": cookie["name"],
+                        "domain": cookie["domain"],
+                        "path": cookie["path"],
+                    }),
+                )
+                .await?;
+        }
+
+        // Web Storage：没有 origin 的页面（如 about:blank）无需清理
+        let mut storage_cleared = Vec::new();
+        for (name, storage_type) in [("local", StorageType::Local), ("session", StorageType::Session)] {
+            if let Ok(id) = storage_id(page, String::new(), storage_type as i32).await {
+                client.clear_dom_storage(&id).await?;
+                storage_cleared.push(name.to_string());
+            }
+        }
+
+        // 注入的初始化脚本
+        let mut scripts_removed = 0;
+        if let Some(engine) = &self.stealth_engine {
+            if let Some(active) = engine.get_active_profile(page_id).await? {
+                scripts_removed = active.script_ids.values().map(Vec::len).sum::<usize>() as i32;
+                engine.remove_all(page_id).await?;
+            }
+        }
+
+        // UA 恢复为浏览器配置的值（未配置时为空，即 Chrome 默认）；地理位置 / 时区空值表示恢复默认
+        let browser = self.session_manager.get_browser(page.browser_id()).await?;
+        let user_agent = browser.user_agent().unwrap_or_default();
+        client
+            .call_method("Network.setUserAgentOverride", serde_json::json!({ "userAgent": user_agent }))
+            .await?;
+        client
+            .call_method("Emulation.clearGeolocationOverride", serde_json::json!({}))
+            .await?;
+        client
+            .call_method("Emulation.setTimezoneOverride", serde_json::json!({ "timezoneId": "" }))
+            .await?;
+
+        let options = NavigationOptions {
+            timeout: DefaultTimeouts::resolve(0, page.default_timeouts().navigation_ms, self.default_timeout),
+            ..Default::default()
+        };
+        page.navigate("about:blank", options).await?;
+
+        Ok(ResetPageResult {
+            cookies_cleared: cookies.len() as i32,
+            storage_cleared,
+            scripts_removed,
+            overrides_cleared: OVERRIDES.iter().map(|name| name.to_string()).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::cdp::types::StorageId;
+    use crate::cdp::CdpClient;
+    use crate::services::{Profile, ProfileType};
+    use crate::session::mock::{MockBrowser, MockPage, MockSessionManager};
+    use crate::session::BrowserOptions;
+    use crate::stealth::{
+        BehaviorSimulator, BehaviorSimulatorImpl, FingerprintGenerator, FingerprintGeneratorImpl, ScriptInjector,
+        ScriptInjectorImpl, StealthEngineImpl,
+    };
+
+    #[tokio::test]
+    async fn test_reset_page_removes_scripts_and_cookies() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let browser = Arc::new(MockBrowser::new(BrowserOptions {
+            user_agent: Some("ConfiguredAgent/1.0".to_string()),
+            ..Default::default()
+        }));
+        let browser_id = manager.register_browser(browser).await;
+        let page = Arc::new(MockPage::with_cdp_client(browser_id, Default::default(), client.clone()));
+        let page_id = manager.register_page(page.clone()).await;
+
+        let injector = Arc::new(ScriptInjectorImpl::new(manager.clone())) as Arc<dyn ScriptInjector>;
+        let simulator = Arc::new(BehaviorSimulatorImpl::new(client.clone())) as Arc<dyn BehaviorSimulator>;
+        let engine = Arc::new(StealthEngineImpl::new(injector.clone(), simulator));
+        let profile = Profile {
+            profile_id: "profile".to_string(),
+            profile_type: ProfileType::Windows,
+            fingerprint: FingerprintGeneratorImpl::with_seed(7).generate_windows().await.unwrap(),
+        };
+        engine.apply_profile(&page_id, &profile).await.unwrap();
+        let injected = injector.get_injected_scripts(&page_id).await.unwrap().len();
+        assert!(injected > 0);
+
+        page.navigate("https://example.com/", NavigationOptions::default()).await.unwrap();
+        let local = StorageId {
+            security_origin: "https://example.com".to_string(),
+            is_local_storage: true,
+        };
+        client.set_dom_storage_item(&local, "token", "abc").await.unwrap();
+        client
+            .stub_method(
+                "Network.getCookies",
+                serde_json::json!({ "cookies": [
+                    { "name": "sid", "domain": "example.com", "path": "/" },
+                    { "name": "theme", "domain": "example.com", "path": "/" },
+                ]}),
+            )
+            .await;
+
+        let handlers = ResetHandlers {
+            session_manager: manager,
+            stealth_engine: Some(engine.clone() as Arc<dyn StealthEngine>),
+            default_timeout: 30000,
+        };
+        let response = handlers
+            .reset_page(Request::new(ResetPageRequest { page_id: page_id.clone() }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(ResetPageResponseEnum::Result(result)) = response.response else {
+            panic!("expected a reset result");
+        };
+        assert_eq!(result.cookies_cleared, 2);
+        assert_eq!(result.scripts_removed as usize, injected);
+        assert_eq!(result.storage_cleared, vec!["local", "session"]);
+
+        let deleted = client.recorded_calls("Network.deleteCookies").await;
+        assert_eq!(deleted.len(), 2);
+        assert_eq!(deleted[0]["name"], "sid");
+        assert_eq!(client.recorded_calls("Page.removeScriptToEvaluateOnNewDocument").await.len(), injected);
+        assert!(injector.get_injected_scripts(&page_id).await.is_err());
+        assert!(engine.get_active_profile(&page_id).await.unwrap().is_none());
+        assert!(client.get_dom_storage_items(&local).await.unwrap().is_empty());
+        // 恢复浏览器配置的 UA，而不是清空覆盖
+        let overrides = client.recorded_calls("Network.setUserAgentOverride").await;
+        assert_eq!(overrides.last().unwrap()["userAgent"], "ConfiguredAgent/1.0");
+        assert!(matches!(
+            page.evaluate("window.location.origin", false).await.unwrap(),
+            crate::session::EvaluationResult::String(origin) if origin == "null"
+        ));
+    }
+}
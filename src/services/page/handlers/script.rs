@@ -4,7 +4,12 @@
 
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
-use crate::session::{SessionManager, EvaluationResult as SessionEvaluationResult};
+use crate::cdp::traits::EvaluationResult as CdpEvaluationResult;
+use crate::cdp::types::RemoteObject;
+use crate::cdp::CdpClientImpl;
+use crate::services::common::limits::check_result_size;
+use crate::services::element::js_utils::JsBuilder;
+use crate::session::{DefaultTimeouts, PageContext, SessionManager, EvaluationResult as SessionEvaluationResult};
 use crate::chaser_oxide::v1::{
     evaluate_response::Response as EvaluateResponseEnum,
     evaluate_on_element_response::Response as EvaluateOnElementResponseEnum,
@@ -15,6 +20,7 @@ use crate::chaser_oxide::v1::{
     AddInitScriptRequest, AddInitScriptResponse,
     GetMetricsRequest, GetMetricsResponse,
     Metrics,
+    ElementRef,
     Empty,
 };
 use super::super::{conversions, response};
@@ -22,6 +28,8 @@ use super::super::{conversions, response};
 /// 实现 PageService trait 中的脚本执行相关方法
 pub struct ScriptHandlers<S> {
     pub session_manager: Arc<S>,
+    /// 脚本执行结果的最大字节数
+    pub max_result_bytes: usize,
 }
 
 impl<S> ScriptHandlers<S>
@@ -29,13 +37,35 @@ where
     S: SessionManager + Send + Sync + 'static,
 {
     /// 在页面中执行 JavaScript 代码
+    ///
+    /// `isolated_world` 为 true 时在隔离环境中执行，页面脚本的全局变量不可见。
     pub async fn evaluate(&self, request: Request<EvaluateRequest>) -> Result<Response<EvaluateResponse>, Status> {
         let req = request.into_inner();
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
-                match page.evaluate(&req.expression, req.await_promise).await {
+                let evaluate = async {
+                    if req.isolated_world {
+                        page.evaluate_isolated(&req.expression, req.await_promise).await
+                    } else {
+                        page.evaluate(&req.expression, req.await_promise).await
+                    }
+                };
+                // 请求未指定超时时使用页面从浏览器继承的命令超时；都为 0 则不额外限时
+                let timeout_ms = DefaultTimeouts::resolve(req.timeout.max(0) as u64, page.default_timeouts().command_ms, 0);
+                let evaluated = if timeout_ms > 0 {
+                    tokio::time::timeout(tokio::time::Duration::from_millis(timeout_ms), evaluate)
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(crate::Error::timeout(format!("Evaluate did not finish within {}ms", timeout_ms)))
+                        })
+                } else {
+                    evaluate.await
+                };
+                match evaluated {
                     Ok(result) => {
+                        check_result_size("Evaluate", result_size(&result), self.max_result_bytes)
+                            .map_err(response::error_to_status)?;
                         let converted = conversions::evaluation_result_from_session(result);
                         Ok(Response::new(EvaluateResponse {
                             response: Some(EvaluateResponseEnum::Result(
@@ -55,25 +85,16 @@ where
         let req = request.into_inner();
 
         let element_ref = req.element.ok_or_else(|| Status::invalid_argument("element is required"))?;
+        if element_ref.selector.is_empty() {
+            return Err(Status::invalid_argument("element.selector is required"));
+        }
 
         match self.session_manager.get_page(&element_ref.page_id).await {
             Ok(page) => {
-                // 构建在元素上执行的脚本
-                let script = format!(
-                    r#"
-                    (() => {{
-                        const element = document.querySelector(`[data-element-id="{}"]`);
-                        if (!element) {{ return null; }}
-
-                        return {};
-                    }})()
-                    "#,
-                    element_ref.element_id,
-                    req.expression
-                );
-
-                match page.evaluate(&script, req.await_promise).await {
+                match call_on_element(page.as_ref(), &element_ref, &req.expression, req.await_promise, req.isolated_world).await {
                     Ok(result) => {
+                        check_result_size("Evaluate", result_size(&result), self.max_result_bytes)
+                            .map_err(response::error_to_status)?;
                         let converted = conversions::evaluation_result_from_session(result);
                         Ok(Response::new(EvaluateOnElementResponse {
                             response: Some(EvaluateOnElementResponseEnum::Result(
@@ -156,3 +177,223 @@ where
         }
     }
 }
+
+/// 执行结果序列化后的大致字节数
+fn result_size(result: &SessionEvaluationResult) -> usize {
+    match result {
+        SessionEvaluationResult::String(s) => s.len(),
+        SessionEvaluationResult::Object(v) => v.to_string().len(),
+        _ => 0,
+    }
+}
+
+/// 以 `this`（以及参数 `element`）为元素执行用户提供的函数体
+///
+/// 元素先解析为 RemoteObject，再通过 `Runtime.callFunctionOn` 执行，
+/// 避免把选择器或元素标识拼接进脚本。`isolated_world` 为 true 时元素在
+/// 隔离环境中解析，函数随之在该环境中执行。
+async fn call_on_element(
+    page: &dyn PageContext,
+    element_ref: &ElementRef,
+    body: &str,
+    await_promise: bool,
+    isolated_world: bool,
+) -> crate::Result<SessionEvaluationResult> {
+    let client = page.get_cdp_client();
+    let query = JsBuilder::new(element_ref.selector_type, element_ref.selector.clone()).element_query()?;
+
+    let mut params = serde_json::json!({ "expression": query, "returnByValue": false });
+    if isolated_world {
+        params["contextId"] = serde_json::json!(page.isolated_world().await?);
+    }
+    let resolved = client.call_method("Runtime.evaluate", params).await?;
+    let object_id = match resolved["result"]["objectId"].as_str() {
+        Some(id) if resolved.get("exceptionDetails").is_none() => id.to_string(),
+        _ => return Err(crate::Error::element_not_found(&element_ref.selector)),
+    };
+
+    let called = client
+        .call_method(
+            "Runtime.callFunctionOn",
+            serde_json::json!({
+                "functionDeclaration": format!("function(element) {{\n{}\n}}", body),
+                "objectId": object_id,
+                "arguments": [{ "objectId": object_id }],
+                "returnByValue": true,
+                "awaitPromise": await_promise,
+            }),
+        )
+        .await;
+    // 释放句柄失败不影响结果
+    let _ = client
+        .call_method("Runtime.releaseObject", serde_json::json!({ "objectId": object_id }))
+        .await;
+    let called = called?;
+
+    if let Some(details) = called.get("exceptionDetails") {
+        return Err(CdpClientImpl::exception_error(details));
+    }
+
+    let remote_object: RemoteObject = serde_json::from_value(called["result"].clone())
+        .map_err(|e| crate::Error::cdp(format!("Failed to parse RemoteObject: {}", e)))?;
+
+    Ok(match CdpClientImpl::parse_remote_object(&remote_object)? {
+        CdpEvaluationResult::String(s) => SessionEvaluationResult::String(s),
+        CdpEvaluationResult::Number(n) => SessionEvaluationResult::Number(n),
+        CdpEvaluationResult::Bool(b) => SessionEvaluationResult::Bool(b),
+        CdpEvaluationResult::Null => SessionEvaluationResult::Null,
+        CdpEvaluationResult::Object(v) => SessionEvaluationResult::Object(v),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::chaser_oxide::v1::evaluation_result::Response as ValueEnum;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    async fn handlers_with(client: Arc<MockCdpClient>) -> (ScriptHandlers<MockSessionManager>, String) {
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client,
+            )))
+            .await;
+        (
+            ScriptHandlers {
+                session_manager: manager,
+                max_result_bytes: crate::config::DEFAULT_MAX_EVALUATE_RESULT_BYTES,
+            },
+            page_id,
+        )
+    }
+
+    /// 元素解析为 `node-1`，函数调用返回 `result`
+    async fn evaluate_with_result(
+        result: serde_json::Value,
+    ) -> (Result<Response<EvaluateOnElementResponse>, Status>, Arc<MockCdpClient>) {
+        let client = Arc::new(MockCdpClient::new());
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "node", "objectId": "node-1" } }),
+            )
+            .await;
+        client
+            .stub_method("Runtime.callFunctionOn", serde_json::json!({ "result": result }))
+            .await;
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+
+        let response = handlers
+            .evaluate_on_element(Request::new(EvaluateOnElementRequest {
+                element: Some(ElementRef {
+                    page_id,
+                    selector_type: 1,
+                    selector: "#title\"]".to_string(),
+                    ..Default::default()
+                }),
+                expression: "return this.textContent;".to_string(),
+                ..Default::default()
+            }))
+            .await;
+        (response, client)
+    }
+
+    fn result_value(response: EvaluateOnElementResponse) -> ValueEnum {
+        match response.response {
+            Some(EvaluateOnElementResponseEnum::Result(result)) => result.response.unwrap(),
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_on_element_returns_string() {
+        let (response, client) = evaluate_with_result(serde_json::json!({ "type": "string", "value": "Hello" })).await;
+
+        let value = result_value(response.unwrap().into_inner());
+        assert!(matches!(value, ValueEnum::StringValue(s) if s == "Hello"));
+
+        let calls = client.recorded_calls("Runtime.callFunctionOn").await;
+        assert_eq!(calls[0]["objectId"], "node-1");
+        assert_eq!(calls[0]["arguments"], serde_json::json!([{ "objectId": "node-1" }]));
+        assert!(calls[0]["functionDeclaration"].as_str().unwrap().contains("return this.textContent;"));
+        // 选择器只出现在解析元素的查询中，不会拼接进用户函数
+        assert!(!calls[0]["functionDeclaration"].as_str().unwrap().contains("#title"));
+        assert_eq!(client.recorded_calls("Runtime.releaseObject").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_on_element_returns_number() {
+        let (response, _) = evaluate_with_result(serde_json::json!({ "type": "number", "value": 42.5 })).await;
+
+        let value = result_value(response.unwrap().into_inner());
+        assert!(matches!(value, ValueEnum::DoubleValue(n) if n == 42.5));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_on_element_returns_object() {
+        let (response, _) = evaluate_with_result(serde_json::json!({
+            "type": "object",
+            "value": { "tag": "H1", "width": 120 },
+        }))
+        .await;
+
+        let value = result_value(response.unwrap().into_inner());
+        let ValueEnum::JsonValue(json) = value else {
+            panic!("objects are returned as json_value");
+        };
+        let object: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(object, serde_json::json!({ "tag": "H1", "width": 120 }));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_on_element_missing_element() {
+        let client = Arc::new(MockCdpClient::new());
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "subtype": "null", "value": null } }),
+            )
+            .await;
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+
+        let status = handlers
+            .evaluate_on_element(Request::new(EvaluateOnElementRequest {
+                element: Some(ElementRef {
+                    page_id,
+                    selector_type: 1,
+                    selector: "#missing".to_string(),
+                    ..Default::default()
+                }),
+                expression: "return 1;".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+
+        assert!(status.message().contains("#missing"));
+        assert!(client.recorded_calls("Runtime.callFunctionOn").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_rejects_oversized_result() {
+        let (mut handlers, page_id) = handlers_with(Arc::new(MockCdpClient::new())).await;
+        handlers.max_result_bytes = 1024;
+
+        // 模拟页面把无法识别的脚本原样返回
+        let status = handlers
+            .evaluate(Request::new(EvaluateRequest {
+                page_id,
+                expression: "x".repeat(4096),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert!(status.message().contains("4096 bytes"));
+    }
+}
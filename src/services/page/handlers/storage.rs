@@ -0,0 +1,126 @@
+//! Web Storage 相关的 RPC 方法处理器
+//!
+//! 包括：get_storage, set_storage, clear_storage
+
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use crate::cdp::types::StorageId;
+use crate::session::{PageContext, SessionManager, EvaluationResult as SessionEvaluationResult};
+use crate::chaser_oxide::v1::{
+    get_storage_response::Response as GetStorageResponseEnum,
+    set_storage_response::Response as SetStorageResponseEnum,
+    clear_storage_response::Response as ClearStorageResponseEnum,
+    GetStorageRequest, GetStorageResponse,
+    SetStorageRequest, SetStorageResponse,
+    ClearStorageRequest, ClearStorageResponse,
+    StorageItem, StorageItems, StorageType,
+    Empty,
+};
+use super::super::response;
+
+/// 实现 PageService trait 中的 Web Storage 相关方法
+pub struct StorageHandlers<S> {
+    pub session_manager: Arc<S>,
+}
+
+/// 构造存储标识，未指定 origin 时使用页面当前的 origin
+pub(crate) async fn storage_id(page: &dyn PageContext, origin: String, storage_type: i32) -> crate::Result<StorageId> {
+    let security_origin = if origin.is_empty() {
+        match page.evaluate("window.location.origin", false).await? {
+            SessionEvaluationResult::String(origin) if !origin.is_empty() && origin != "null" => origin,
+            _ => {
+                return Err(crate::Error::configuration(
+                    "Page has no origin; specify the storage origin explicitly",
+                ))
+            }
+        }
+    } else {
+        origin
+    };
+
+    Ok(StorageId {
+        security_origin,
+        is_local_storage: storage_type != StorageType::Session as i32,
+    })
+}
+
+impl<S> StorageHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 获取存储中的全部键值对
+    pub async fn get_storage(&self, request: Request<GetStorageRequest>) -> Result<Response<GetStorageResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let storage_id = storage_id(page.as_ref(), req.origin, req.storage_type)
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                match page.get_cdp_client().get_dom_storage_items(&storage_id).await {
+                    Ok(entries) => {
+                        let items = entries
+                            .into_iter()
+                            .map(|(key, value)| StorageItem { key, value })
+                            .collect();
+
+                        Ok(Response::new(GetStorageResponse {
+                            response: Some(GetStorageResponseEnum::Items(StorageItems { items })),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 批量写入存储
+    pub async fn set_storage(&self, request: Request<SetStorageRequest>) -> Result<Response<SetStorageResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let storage_id = storage_id(page.as_ref(), req.origin, req.storage_type)
+                    .await
+                    .map_err(response::error_to_status)?;
+                let client = page.get_cdp_client();
+
+                for item in &req.items {
+                    if let Err(e) = client.set_dom_storage_item(&storage_id, &item.key, &item.value).await {
+                        return Err(response::error_to_status(e));
+                    }
+                }
+
+                Ok(Response::new(SetStorageResponse {
+                    response: Some(SetStorageResponseEnum::Success(Empty {})),
+                }))
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+
+    /// 清空存储
+    pub async fn clear_storage(&self, request: Request<ClearStorageRequest>) -> Result<Response<ClearStorageResponse>, Status> {
+        let req = request.into_inner();
+
+        match self.session_manager.get_page(&req.page_id).await {
+            Ok(page) => {
+                let storage_id = storage_id(page.as_ref(), req.origin, req.storage_type)
+                    .await
+                    .map_err(response::error_to_status)?;
+
+                match page.get_cdp_client().clear_dom_storage(&storage_id).await {
+                    Ok(_) => {
+                        Ok(Response::new(ClearStorageResponse {
+                            response: Some(ClearStorageResponseEnum::Success(Empty {})),
+                        }))
+                    }
+                    Err(e) => Err(response::error_to_status(e)),
+                }
+            }
+            Err(e) => Err(response::error_to_status(e)),
+        }
+    }
+}
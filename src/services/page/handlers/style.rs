@@ -0,0 +1,232 @@
+//! 样式注入相关的 RPC 方法处理器
+//!
+//! 包括：inject_style, remove_style
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+use crate::session::{PageStateStore, SessionManager};
+use crate::chaser_oxide::v1::{
+    inject_style_response::Response as InjectStyleResponseEnum,
+    remove_style_response::Response as RemoveStyleResponseEnum,
+    InjectStyleRequest, InjectStyleResponse,
+    RemoveStyleRequest, RemoveStyleResponse,
+    Empty,
+};
+use super::super::{response, scripts};
+
+/// InjectStyle 注入的样式：页面 ID -> 样式 ID -> 新文档脚本的 CDP identifier
+#[derive(Debug, Default)]
+pub struct InjectedStyles {
+    styles: Mutex<HashMap<String, HashMap<String, String>>>,
+}
+
+impl InjectedStyles {
+    async fn insert(&self, page_id: &str, style_id: String, identifier: String) {
+        self.styles
+            .lock()
+            .await
+            .entry(page_id.to_string())
+            .or_default()
+            .insert(style_id, identifier);
+    }
+
+    /// 移除样式记录，返回其新文档脚本的 identifier
+    async fn remove(&self, page_id: &str, style_id: &str) -> Option<String> {
+        let mut styles = self.styles.lock().await;
+        let page_styles = styles.get_mut(page_id)?;
+        let identifier = page_styles.remove(style_id);
+        if page_styles.is_empty() {
+            styles.remove(page_id);
+        }
+        identifier
+    }
+
+    /// 页面当前注入的样式 ID
+    pub async fn style_ids(&self, page_id: &str) -> Vec<String> {
+        self.styles
+            .lock()
+            .await
+            .get(page_id)
+            .map(|styles| styles.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl PageStateStore for InjectedStyles {
+    /// 页面关闭后丢弃其样式记录
+    async fn release_page(&self, page_id: &str) {
+        self.styles.lock().await.remove(page_id);
+    }
+}
+
+/// 实现 PageService trait 中的样式注入方法
+pub struct StyleHandlers<S> {
+    pub session_manager: Arc<S>,
+    pub styles: Arc<InjectedStyles>,
+}
+
+impl<S> StyleHandlers<S>
+where
+    S: SessionManager + Send + Sync + 'static,
+{
+    /// 向当前文档注入样式，并登记为新文档脚本使其在导航后仍然生效
+    pub async fn inject_style(&self, request: Request<InjectStyleRequest>) -> Result<Response<InjectStyleResponse>, Status> {
+        let req = request.into_inner();
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+
+        let style_id = Uuid::new_v4().to_string();
+        // 样式 ID 和 CSS 都以 JSON 字符串字面量传入，不会破坏脚本
+        let script = format!(
+            "({})({}, {})",
+            scripts::INJECT_STYLE_SCRIPT,
+            serde_json::json!(style_id),
+            serde_json::json!(req.css)
+        );
+
+        let added = page
+            .get_cdp_client()
+            .call_method("Page.addScriptToEvaluateOnNewDocument", serde_json::json!({ "source": script }))
+            .await
+            .map_err(response::error_to_status)?;
+        let identifier = added["identifier"].as_str().unwrap_or_default().to_string();
+
+        // 注入当前文档失败时撤销新文档脚本，避免留下无法移除的样式
+        if let Err(e) = page.evaluate(&script, false).await {
+            if !identifier.is_empty() {
+                let removed = page
+                    .get_cdp_client()
+                    .call_method(
+                        "Page.removeScriptToEvaluateOnNewDocument",
+                        serde_json::json!({ "identifier": identifier }),
+                    )
+                    .await;
+                if let Err(remove_error) = removed {
+                    tracing::warn!("Failed to remove style script {}: {}", identifier, remove_error);
+                }
+            }
+            return Err(response::error_to_status(e));
+        }
+        self.styles.insert(&req.page_id, style_id.clone(), identifier).await;
+
+        Ok(Response::new(InjectStyleResponse {
+            response: Some(InjectStyleResponseEnum::StyleId(style_id)),
+        }))
+    }
+
+    /// 移除 inject_style 注入的样式，包括已注入当前文档的 `<style>`
+    pub async fn remove_style(&self, request: Request<RemoveStyleRequest>) -> Result<Response<RemoveStyleResponse>, Status> {
+        let req = request.into_inner();
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+
+        let identifier = self
+            .styles
+            .remove(&req.page_id, &req.style_id)
+            .await
+            .ok_or_else(|| Status::not_found(format!("Style not found: {}", req.style_id)))?;
+
+        if !identifier.is_empty() {
+            page.get_cdp_client()
+                .call_method(
+                    "Page.removeScriptToEvaluateOnNewDocument",
+                    serde_json::json!({ "identifier": identifier }),
+                )
+                .await
+                .map_err(response::error_to_status)?;
+        }
+        let script = format!("({})({})", scripts::REMOVE_STYLE_SCRIPT, serde_json::json!(req.style_id));
+        page.evaluate(&script, false).await.map_err(response::error_to_status)?;
+
+        Ok(Response::new(RemoveStyleResponse {
+            response: Some(RemoveStyleResponseEnum::Success(Empty {})),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::session::mock::{MockPage, MockSessionManager};
+
+    #[tokio::test]
+    async fn test_inject_and_remove_style_tracks_handle() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let page_id = manager.register_page(Arc::clone(&page)).await;
+        let handlers = StyleHandlers {
+            session_manager: manager,
+            styles: Arc::new(InjectedStyles::default()),
+        };
+
+        let css = "#banner { display: none; } /* it's \"hidden\" </style> */";
+        let response = handlers
+            .inject_style(Request::new(InjectStyleRequest {
+                page_id: page_id.clone(),
+                css: css.to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let Some(InjectStyleResponseEnum::StyleId(style_id)) = response.response else {
+            panic!("expected a style id");
+        };
+        assert_eq!(handlers.styles.style_ids(&page_id).await, vec![style_id.clone()]);
+
+        let added = client.recorded_calls("Page.addScriptToEvaluateOnNewDocument").await;
+        assert_eq!(added.len(), 1);
+        let source = added[0]["source"].as_str().unwrap();
+        assert!(source.contains(&serde_json::json!(css).to_string()));
+        assert!(page.evaluated_scripts().await.iter().any(|script| script == source));
+
+        handlers
+            .remove_style(Request::new(RemoveStyleRequest {
+                page_id: page_id.clone(),
+                style_id: style_id.clone(),
+            }))
+            .await
+            .unwrap();
+        assert!(handlers.styles.style_ids(&page_id).await.is_empty());
+        let removed = client.recorded_calls("Page.removeScriptToEvaluateOnNewDocument").await;
+        assert_eq!(removed.len(), 1);
+        assert!(!removed[0]["identifier"].as_str().unwrap().is_empty());
+        assert!(page.evaluated_scripts().await.last().unwrap().contains(&style_id));
+
+        let status = handlers
+            .remove_style(Request::new(RemoveStyleRequest { page_id, style_id }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_failed_injection_removes_script_and_closed_page_drops_styles() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let page_id = manager.register_page(Arc::clone(&page)).await;
+        let handlers = StyleHandlers {
+            session_manager: manager,
+            styles: Arc::new(InjectedStyles::default()),
+        };
+        let inject = |css: &str| InjectStyleRequest { page_id: page_id.clone(), css: css.to_string() };
+
+        // 测试：注入当前文档失败时撤销新文档脚本，不登记样式
+        page.fail_evaluate(".broken").await;
+        assert!(handlers.inject_style(Request::new(inject(".broken {}"))).await.is_err());
+        assert!(handlers.styles.style_ids(&page_id).await.is_empty());
+        let removed = client.recorded_calls("Page.removeScriptToEvaluateOnNewDocument").await;
+        assert_eq!(removed.len(), 1);
+        assert!(!removed[0]["identifier"].as_str().unwrap().is_empty());
+
+        // 测试：页面关闭后样式记录被丢弃
+        handlers.inject_style(Request::new(inject(".ok {}"))).await.unwrap();
+        assert_eq!(handlers.styles.style_ids(&page_id).await.len(), 1);
+        handlers.styles.release_page(&page_id).await;
+        assert!(handlers.styles.style_ids(&page_id).await.is_empty());
+    }
+}
@@ -1,17 +1,43 @@
 //! 等待条件相关的 RPC 方法处理器
 //!
-//! 包括：wait_for
+//! 包括：wait_for, wait_for_request, wait_for_response
+//!
+//! 等待在独立任务中进行：客户端取消请求或等待超时后，页面中的等待脚本会被通知停止。
+//! 网络请求和响应的等待基于页面的 `Network` 事件订阅，匹配到第一个请求即返回。
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use regex::Regex;
+use serde_json::Value;
+use tokio::sync::mpsc;
 use tonic::{Request, Response, Status};
-use crate::session::{SessionManager, EvaluationResult as SessionEvaluationResult};
+use crate::cdp::traits::CdpEvent;
+use crate::cdp::CdpClientImpl;
+use crate::services::common::cancel::run_cancellable;
+use crate::session::cancel::cancelled_error;
+use crate::session::page::url_matches;
+use crate::session::{CancelToken, DefaultTimeouts, PageContext, SessionManager};
 use crate::chaser_oxide::v1::{
     wait_for_response::Response as WaitForResponseEnum,
+    wait_for_network_request_response::Response as WaitForNetworkRequestResponseEnum,
+    wait_for_network_response_response::Response as WaitForNetworkResponseResponseEnum,
     WaitForRequest, WaitForResponse,
+    WaitForNetworkRequestRequest, WaitForNetworkRequestResponse,
+    WaitForNetworkResponseRequest, WaitForNetworkResponseResponse,
+    NetworkMatcher, NetworkRequestInfo, NetworkResponseInfo,
     Empty,
 };
+use crate::Error;
 use super::super::{response, scripts};
 
+/// 等待选择器时的兜底轮询间隔（毫秒）
+///
+/// 节点出现主要由 `MutationObserver` 感知，轮询只处理观察不到的变化。
+const SELECTOR_FALLBACK_POLL_MS: u64 = 500;
+
+/// 请求和浏览器都未指定超时时，等待网络请求的超时（毫秒）
+const DEFAULT_NETWORK_WAIT_TIMEOUT_MS: u64 = 30_000;
+
 /// 实现 PageService trait 中的等待条件相关方法
 pub struct WaitForHandlers<S> {
     pub session_manager: Arc<S>,
@@ -27,36 +53,45 @@ where
 
         match self.session_manager.get_page(&req.page_id).await {
             Ok(page) => {
+                // 选择器和 URL 等待使用页面从浏览器继承的等待超时；为 0 时不限时
+                let wait_timeout_ms = page.default_timeouts().wait_ms;
                 // 根据请求等待条件
-                let wait_result = match req.wait_condition {
-                    Some(condition) => {
-                        match condition {
-                            crate::chaser_oxide::v1::wait_for_request::WaitCondition::Selector(selector) => {
-                                // 等待选择器出现
-                                let script = format!("{}('{}')", scripts::WAIT_FOR_SELECTOR_SCRIPT, selector);
-                                page.evaluate(&script, false).await
-                            }
-                            crate::chaser_oxide::v1::wait_for_request::WaitCondition::Timeout(timeout_ms) => {
-                                // 等待指定的超时时间
-                                tokio::time::sleep(tokio::time::Duration::from_millis(timeout_ms as u64)).await;
-                                Ok(SessionEvaluationResult::Bool(true))
-                            }
-                            crate::chaser_oxide::v1::wait_for_request::WaitCondition::NavigationUrl(url) => {
-                                // 等待导航到指定 URL
-                                let script = format!("{}('{}')", scripts::WAIT_FOR_URL_SCRIPT, url);
-                                page.evaluate(&script, false).await
+                let wait_result = run_cancellable(move |cancel| async move {
+                    match req.wait_condition {
+                        Some(condition) => {
+                            match condition {
+                                crate::chaser_oxide::v1::wait_for_request::WaitCondition::Selector(selector) => {
+                                    // 等待选择器出现
+                                    call_waiter(
+                                        page.as_ref(),
+                                        scripts::WAIT_FOR_SELECTOR_SCRIPT,
+                                        vec![selector.into(), SELECTOR_FALLBACK_POLL_MS.into()],
+                                        wait_timeout_ms,
+                                        &cancel,
+                                    )
+                                    .await
+                                }
+                                crate::chaser_oxide::v1::wait_for_request::WaitCondition::Timeout(timeout_ms) => {
+                                    // 等待指定的超时时间
+                                    sleep_unless_cancelled(timeout_ms as u64, &cancel).await
+                                }
+                                crate::chaser_oxide::v1::wait_for_request::WaitCondition::NavigationUrl(url) => {
+                                    // 等待导航到指定 URL
+                                    call_waiter(page.as_ref(), scripts::WAIT_FOR_URL_SCRIPT, vec![url.into()], wait_timeout_ms, &cancel)
+                                        .await
+                                }
                             }
                         }
+                        None => {
+                            // 默认：等待短时间
+                            sleep_unless_cancelled(1000, &cancel).await
+                        }
                     }
-                    None => {
-                        // 默认：等待短时间
-                        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
-                        Ok(SessionEvaluationResult::Bool(true))
-                    }
-                };
+                })
+                .await;
 
                 match wait_result {
-                    Ok(_) => {
+                    Ok(()) => {
                         Ok(Response::new(WaitForResponse {
                             response: Some(WaitForResponseEnum::Success(Empty {})),
                         }))
@@ -67,4 +102,495 @@ where
             Err(e) => Err(response::error_to_status(e)),
         }
     }
+
+    /// 等待 URL（及方法）匹配的网络请求发出
+    pub async fn wait_for_network_request(&self, request: Request<WaitForNetworkRequestRequest>) -> Result<Response<WaitForNetworkRequestResponse>, Status> {
+        let req = request.into_inner();
+        let matcher = RequestMatcher::from_proto(req.matcher.unwrap_or_default()).map_err(response::error_to_status)?;
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+        let timeout_ms = network_wait_timeout(req.timeout, page.as_ref());
+
+        // 在返回前订阅，不漏掉调用方随后触发的请求
+        let client = page.get_cdp_client();
+        let mut requests = client.subscribe_events("Network.requestWillBeSent").await.map_err(response::error_to_status)?;
+
+        let info = run_cancellable(move |cancel| async move {
+            let wait = async move {
+                while let Some(event) = requests.recv().await {
+                    let info = request_info(&event.params);
+                    if matcher.matches(&info.url, &info.method) {
+                        return Ok(info);
+                    }
+                }
+                Err(Error::cdp("Network event stream closed"))
+            };
+            tokio::select! {
+                result = with_wait_timeout(wait, timeout_ms) => result,
+                _ = cancel.cancelled() => Err(cancelled_error("Wait")),
+            }
+        })
+        .await
+        .map_err(response::error_to_status)?;
+
+        Ok(Response::new(WaitForNetworkRequestResponse {
+            response: Some(WaitForNetworkRequestResponseEnum::Result(info)),
+        }))
+    }
+
+    /// 等待 URL（及方法）匹配的网络请求收到响应
+    pub async fn wait_for_network_response(&self, request: Request<WaitForNetworkResponseRequest>) -> Result<Response<WaitForNetworkResponseResponse>, Status> {
+        let req = request.into_inner();
+        let matcher = RequestMatcher::from_proto(req.matcher.unwrap_or_default()).map_err(response::error_to_status)?;
+        let page = self.session_manager.get_page(&req.page_id).await.map_err(response::error_to_status)?;
+        let timeout_ms = network_wait_timeout(req.timeout, page.as_ref());
+
+        // 响应事件不含请求方法，需同时订阅请求事件
+        let client = page.get_cdp_client();
+        let requests = client.subscribe_events("Network.requestWillBeSent").await.map_err(response::error_to_status)?;
+        let responses = client.subscribe_events("Network.responseReceived").await.map_err(response::error_to_status)?;
+
+        let info = run_cancellable(move |cancel| async move {
+            tokio::select! {
+                result = with_wait_timeout(next_response(requests, responses, &matcher), timeout_ms) => result,
+                _ = cancel.cancelled() => Err(cancelled_error("Wait")),
+            }
+        })
+        .await
+        .map_err(response::error_to_status)?;
+
+        Ok(Response::new(WaitForNetworkResponseResponse {
+            response: Some(WaitForNetworkResponseResponseEnum::Result(info)),
+        }))
+    }
+}
+
+/// 网络请求的匹配条件
+#[derive(Debug)]
+struct RequestMatcher {
+    url_pattern: Option<String>,
+    url_regex: Option<Regex>,
+    method: Option<String>,
+}
+
+impl RequestMatcher {
+    fn from_proto(matcher: NetworkMatcher) -> crate::Result<Self> {
+        if !matcher.url_pattern.is_empty() && !matcher.url_regex.is_empty() {
+            return Err(Error::configuration("Set only one of url_pattern and url_regex"));
+        }
+        let url_regex = match matcher.url_regex.as_str() {
+            "" => None,
+            pattern => Some(
+                Regex::new(pattern).map_err(|e| Error::configuration(format!("Invalid url_regex: {}", e)))?,
+            ),
+        };
+
+        Ok(Self {
+            url_pattern: Some(matcher.url_pattern).filter(|pattern| !pattern.is_empty()),
+            url_regex,
+            method: Some(matcher.method).filter(|method| !method.is_empty()),
+        })
+    }
+
+    fn matches(&self, url: &str, method: &str) -> bool {
+        let url_matched = match &self.url_regex {
+            Some(regex) => regex.is_match(url),
+            None => url_matches(self.url_pattern.as_deref(), url),
+        };
+        let method_matched = match &self.method {
+            Some(expected) => expected.eq_ignore_ascii_case(method),
+            None => true,
+        };
+        url_matched && method_matched
+    }
+}
+
+/// 请求指定的超时，否则为浏览器的默认等待超时，都为 0 时为 30 秒
+fn network_wait_timeout(requested_ms: i32, page: &dyn PageContext) -> u64 {
+    DefaultTimeouts::resolve(
+        requested_ms.max(0) as u64,
+        page.default_timeouts().wait_ms,
+        DEFAULT_NETWORK_WAIT_TIMEOUT_MS,
+    )
+}
+
+/// 返回第一个匹配请求的响应
+async fn next_response(
+    mut requests: mpsc::Receiver<CdpEvent>,
+    mut responses: mpsc::Receiver<CdpEvent>,
+    matcher: &RequestMatcher,
+) -> crate::Result<NetworkResponseInfo> {
+    // requestId -> 请求方法
+    let mut methods: HashMap<String, String> = HashMap::new();
+    loop {
+        tokio::select! {
+            // 同一连接按顺序分发事件：响应到达时其请求事件已在请求通道中，先处理请求才能查到方法
+            biased;
+            Some(event) = requests.recv() => {
+                let params = &event.params;
+                methods.insert(string_field(&params["requestId"]), string_field(&params["request"]["method"]));
+            }
+            Some(event) = responses.recv() => {
+                let method = methods.remove(event.params["requestId"].as_str().unwrap_or_default()).unwrap_or_default();
+                let info = response_info(&event.params, method);
+                if matcher.matches(&info.url, &info.method) {
+                    return Ok(info);
+                }
+            }
+            else => return Err(Error::cdp("Network event stream closed")),
+        }
+    }
+}
+
+/// 由 `Network.requestWillBeSent` 参数构造请求信息
+fn request_info(params: &Value) -> NetworkRequestInfo {
+    let request = &params["request"];
+    NetworkRequestInfo {
+        request_id: string_field(&params["requestId"]),
+        url: string_field(&request["url"]),
+        method: string_field(&request["method"]),
+        headers: header_map(&request["headers"]),
+        post_data: string_field(&request["postData"]),
+        resource_type: string_field(&params["type"]),
+    }
+}
+
+/// 由 `Network.responseReceived` 参数构造响应信息
+fn response_info(params: &Value, method: String) -> NetworkResponseInfo {
+    let response = &params["response"];
+    NetworkResponseInfo {
+        request_id: string_field(&params["requestId"]),
+        url: string_field(&response["url"]),
+        method,
+        status: response["status"].as_i64().unwrap_or_default() as i32,
+        status_text: string_field(&response["statusText"]),
+        headers: header_map(&response["headers"]),
+        mime_type: string_field(&response["mimeType"]),
+        resource_type: string_field(&params["type"]),
+    }
+}
+
+/// CDP 头对象转换为名值映射
+fn header_map(headers: &Value) -> HashMap<String, String> {
+    headers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| (name.clone(), string_field(value)))
+        .collect()
+}
+
+fn string_field(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+/// 等待 `ms` 毫秒，请求取消时提前结束
+async fn sleep_unless_cancelled(ms: u64, cancel: &CancelToken) -> crate::Result<()> {
+    tokio::select! {
+        _ = tokio::time::sleep(tokio::time::Duration::from_millis(ms)) => Ok(()),
+        _ = cancel.cancelled() => Err(cancelled_error("Wait")),
+    }
+}
+
+/// 在 `timeout_ms` 内等待 `waiter` 完成；`timeout_ms` 为 0 时不限时
+async fn with_wait_timeout<T>(
+    waiter: impl std::future::Future<Output = crate::Result<T>>,
+    timeout_ms: u64,
+) -> crate::Result<T> {
+    if timeout_ms == 0 {
+        return waiter.await;
+    }
+    tokio::time::timeout(tokio::time::Duration::from_millis(timeout_ms), waiter)
+        .await
+        .unwrap_or_else(|_| Err(crate::Error::timeout(format!("Wait condition not met within {}ms", timeout_ms))))
+}
+
+/// 以新建的控制对象为 `this` 执行等待脚本，并等待其返回的 Promise
+///
+/// 参数通过 `Runtime.callFunctionOn` 的 `arguments` 传入，
+/// 选择器或 URL 中的引号等字符不会被当作脚本解析。
+/// 超时或请求取消时调用控制对象上的 `cancel()`，停止页面中的监听和轮询。
+async fn call_waiter(
+    page: &dyn PageContext,
+    function: &str,
+    args: Vec<serde_json::Value>,
+    timeout_ms: u64,
+    cancel: &CancelToken,
+) -> crate::Result<()> {
+    let client = page.get_cdp_client();
+
+    // 控制对象只由本次等待持有，不在页面全局对象上留下痕迹
+    let control = client
+        .call_method(
+            "Runtime.evaluate",
+            serde_json::json!({ "expression": "({})", "returnByValue": false }),
+        )
+        .await?;
+    let object_id = control["result"]["objectId"]
+        .as_str()
+        .ok_or_else(|| crate::Error::cdp("Failed to create the waiter control object"))?
+        .to_string();
+
+    let arguments: Vec<serde_json::Value> = args.into_iter().map(|value| serde_json::json!({ "value": value })).collect();
+    let call = client.call_method(
+        "Runtime.callFunctionOn",
+        serde_json::json!({
+            "functionDeclaration": function,
+            "objectId": object_id,
+            "arguments": arguments,
+            "returnByValue": true,
+            "awaitPromise": true,
+        }),
+    );
+    let called = tokio::select! {
+        called = with_wait_timeout(call, timeout_ms) => called,
+        _ = cancel.cancelled() => Err(cancelled_error("Wait")),
+    };
+    if matches!(called, Err(crate::Error::Timeout(_) | crate::Error::Cancelled(_))) {
+        // 页面中的等待仍在进行，通知其停止；失败不影响结果
+        let _ = client
+            .call_method(
+                "Runtime.callFunctionOn",
+                serde_json::json!({ "functionDeclaration": scripts::CANCEL_WAITER_SCRIPT, "objectId": object_id }),
+            )
+            .await;
+    }
+    // 释放句柄失败不影响结果
+    let _ = client
+        .call_method("Runtime.releaseObject", serde_json::json!({ "objectId": object_id }))
+        .await;
+    let called = called?;
+
+    match called.get("exceptionDetails") {
+        Some(details) => Err(CdpClientImpl::exception_error(details)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use crate::chaser_oxide::v1::wait_for_request::WaitCondition;
+    use crate::session::mock::{MockPage, MockSessionManager};
+    use std::time::{Duration, Instant};
+
+    async fn handlers_with(client: Arc<MockCdpClient>) -> (WaitForHandlers<MockSessionManager>, String) {
+        client
+            .stub_method(
+                "Runtime.evaluate",
+                serde_json::json!({ "result": { "type": "object", "className": "Object", "objectId": "control-1" } }),
+            )
+            .await;
+        client
+            .stub_method(
+                "Runtime.callFunctionOn",
+                serde_json::json!({ "result": { "type": "boolean", "value": true } }),
+            )
+            .await;
+
+        let manager = Arc::new(MockSessionManager::new());
+        let page_id = manager
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser".to_string(),
+                Default::default(),
+                client,
+            )))
+            .await;
+        (WaitForHandlers { session_manager: manager }, page_id)
+    }
+
+    fn wait_request(page_id: &str, condition: WaitCondition) -> Request<WaitForRequest> {
+        Request::new(WaitForRequest {
+            page_id: page_id.to_string(),
+            wait_condition: Some(condition),
+        })
+    }
+
+    // 测试：含引号的选择器作为参数传入，不拼接进脚本
+    #[tokio::test]
+    async fn test_wait_for_selector_with_quotes_is_passed_as_argument() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+        let selector = r#"a[title="it's"]'); alert(1); ('"#;
+
+        let response = handlers
+            .wait_for(wait_request(&page_id, WaitCondition::Selector(selector.to_string())))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(matches!(response.response, Some(WaitForResponseEnum::Success(_))));
+
+        let calls = client.recorded_calls("Runtime.callFunctionOn").await;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["objectId"], "control-1");
+        assert_eq!(calls[0]["awaitPromise"], true);
+        assert_eq!(calls[0]["arguments"][0]["value"], selector);
+        assert_eq!(calls[0]["arguments"][1]["value"], SELECTOR_FALLBACK_POLL_MS);
+        let function = calls[0]["functionDeclaration"].as_str().unwrap();
+        assert!(function.contains("MutationObserver"));
+        assert!(!function.contains("it's"));
+        assert_eq!(client.recorded_calls("Runtime.releaseObject").await.len(), 1);
+    }
+
+    // 测试：元素稍后才加入页面时，等待到 Promise resolve 才返回
+    #[tokio::test]
+    async fn test_wait_for_selector_resolves_when_element_added_later() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+        // 浏览器在元素插入后才返回 awaitPromise 的结果
+        client.delay_method("Runtime.callFunctionOn", Duration::from_millis(150)).await;
+
+        let started = Instant::now();
+        let response = handlers
+            .wait_for(wait_request(&page_id, WaitCondition::Selector("#late".to_string())))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        assert!(matches!(response.response, Some(WaitForResponseEnum::Success(_))));
+        assert_eq!(client.recorded_calls("Runtime.callFunctionOn").await.len(), 1);
+    }
+
+    // 测试：无效选择器在页面中抛出异常时返回错误
+    #[tokio::test]
+    async fn test_wait_for_invalid_selector_returns_error() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+        client
+            .stub_method(
+                "Runtime.callFunctionOn",
+                serde_json::json!({
+                    "result": { "type": "object", "subtype": "error" },
+                    "exceptionDetails": {
+                        "text": "Uncaught",
+                        "exception": { "description": "SyntaxError: '##' is not a valid selector" },
+                    },
+                }),
+            )
+            .await;
+
+        let status = handlers
+            .wait_for(wait_request(&page_id, WaitCondition::Selector("##".to_string())))
+            .await
+            .unwrap_err();
+
+        assert!(status.message().contains("not a valid selector"));
+        assert_eq!(client.recorded_calls("Runtime.releaseObject").await.len(), 1);
+    }
+
+    // 测试：页面中的 fetch 收到响应后，WaitForNetworkResponse 返回其状态；URL 或方法不匹配的响应被跳过
+    #[tokio::test]
+    async fn test_wait_for_response_resolves_with_fetch_status() {
+        let client = Arc::new(MockCdpClient::new());
+        let manager = Arc::new(MockSessionManager::new());
+        let page = Arc::new(MockPage::with_cdp_client("browser".to_string(), Default::default(), client.clone()));
+        let page_id = manager.register_page(page.clone()).await;
+        let handlers = WaitForHandlers { session_manager: manager };
+
+        let wait = handlers.wait_for_network_response(Request::new(WaitForNetworkResponseRequest {
+            page_id,
+            matcher: Some(NetworkMatcher {
+                url_pattern: "*/api/items*".to_string(),
+                method: "post".to_string(),
+                ..Default::default()
+            }),
+            timeout: 5000,
+        }));
+        // 模拟浏览器：页面脚本发起 fetch 后依次发出网络事件
+        let browser = async {
+            page.evaluate("fetch('/api/items', { method: 'POST' })", true).await.unwrap();
+            let requests = [
+                ("1", "https://shop.test/app.js", "GET", 200),
+                ("2", "https://shop.test/api/items", "GET", 304),
+                ("3", "https://shop.test/api/items?page=1", "POST", 201),
+            ];
+            for (id, url, method, _) in requests {
+                client
+                    .emit_event("Network.requestWillBeSent", serde_json::json!({
+                        "requestId": id, "type": "Fetch", "request": { "url": url, "method": method, "headers": {} },
+                    }))
+                    .await;
+            }
+            for (id, url, _, status) in requests {
+                client
+                    .emit_event("Network.responseReceived", serde_json::json!({
+                        "requestId": id, "type": "Fetch",
+                        "response": {
+                            "url": url, "status": status, "statusText": "Created",
+                            "headers": { "content-type": "application/json" }, "mimeType": "application/json",
+                        },
+                    }))
+                    .await;
+            }
+        };
+        let (response, ()) = tokio::join!(wait, browser);
+
+        let Some(WaitForNetworkResponseResponseEnum::Result(info)) = response.unwrap().into_inner().response else {
+            panic!("expected a response");
+        };
+        assert_eq!(info.request_id, "3");
+        assert_eq!(info.status, 201);
+        assert_eq!(info.method, "POST");
+        assert_eq!(info.headers.get("content-type").map(String::as_str), Some("application/json"));
+    }
+
+    // 测试：没有匹配的请求时超时；无效的正则返回参数错误
+    #[tokio::test]
+    async fn test_wait_for_request_times_out_and_rejects_invalid_regex() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+        let wait_request = |matcher: NetworkMatcher| {
+            Request::new(WaitForNetworkRequestRequest {
+                page_id: page_id.clone(),
+                matcher: Some(matcher),
+                timeout: 50,
+            })
+        };
+
+        let status = handlers
+            .wait_for_network_request(wait_request(NetworkMatcher {
+                url_regex: r"/api/\d+$".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+
+        let status = handlers
+            .wait_for_network_request(wait_request(NetworkMatcher {
+                url_regex: "(".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    // 测试：客户端取消等待后，页面中的等待脚本被通知停止
+    #[tokio::test]
+    async fn test_cancelled_wait_stops_page_waiter() {
+        let client = Arc::new(MockCdpClient::new());
+        let (handlers, page_id) = handlers_with(client.clone()).await;
+        // 元素一直不出现，浏览器不返回 awaitPromise 的结果
+        client.delay_method("Runtime.callFunctionOn", Duration::from_secs(60)).await;
+
+        // 客户端断开时 tonic 丢弃处理器的 future
+        let wait = handlers.wait_for(wait_request(&page_id, WaitCondition::Selector("#never".to_string())));
+        assert!(tokio::time::timeout(Duration::from_millis(100), wait).await.is_err());
+
+        let calls = tokio::time::timeout(Duration::from_secs(1), async {
+            loop {
+                let calls = client.recorded_calls("Runtime.callFunctionOn").await;
+                if calls.len() >= 2 {
+                    return calls;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("page waiter was not cancelled");
+        assert_eq!(calls[1]["functionDeclaration"], scripts::CANCEL_WAITER_SCRIPT);
+        assert_eq!(calls[1]["objectId"], "control-1");
+    }
 }
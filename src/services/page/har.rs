@@ -0,0 +1,475 @@
+//! HAR 1.2 录制
+//!
+//! 订阅页面的 `Network` 事件，按请求累积请求、响应、时间和大小信息，
+//! 导出时转换为 HAR 1.2 结构。所有时间均来自 `Network` 事件的时间戳，
+//! 启用 `include_bodies` 时在请求完成后通过 `Network.getResponseBody` 获取响应体。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use crate::cdp::traits::{CdpClient, CdpEvent};
+
+/// 录制的 `Network` 事件
+const HAR_EVENTS: [&str; 4] = [
+    "Network.requestWillBeSent",
+    "Network.responseReceived",
+    "Network.loadingFinished",
+    "Network.loadingFailed",
+];
+
+/// 单个请求的累积状态
+#[derive(Debug, Default)]
+struct PendingEntry {
+    /// CDP `Request` 对象
+    request: Value,
+    /// 请求开始的墙上时间（秒）
+    wall_time: f64,
+    /// 请求开始的单调时间戳（秒）
+    started_at: f64,
+    /// CDP `Response` 对象
+    response: Option<Value>,
+    /// 收到响应头的时间戳（秒）
+    response_at: Option<f64>,
+    /// 加载结束（完成、失败或被重定向）的时间戳（秒）
+    finished_at: Option<f64>,
+    /// 实际传输的字节数
+    encoded_length: Option<f64>,
+    /// 失败原因
+    error: Option<String>,
+    /// 响应体及其是否为 base64 编码
+    body: Option<(String, bool)>,
+    resource_type: String,
+}
+
+/// 按事件顺序累积的 HAR 日志
+#[derive(Debug, Default)]
+pub struct HarLog {
+    entries: Vec<PendingEntry>,
+    /// requestId -> 当前（最后一次重定向后）条目的下标
+    active: HashMap<String, usize>,
+}
+
+impl HarLog {
+    /// 记录一个 `Network` 事件，其他事件会被忽略
+    pub fn record(&mut self, event: &CdpEvent) {
+        let params = &event.params;
+        let Some(request_id) = params["requestId"].as_str() else {
+            return;
+        };
+        let timestamp = params["timestamp"].as_f64();
+
+        match event.method.as_str() {
+            "Network.requestWillBeSent" => {
+                // 同一个 requestId 再次出现表示重定向，上一跳以 redirectResponse 结束
+                if let (Some(index), Some(redirect)) = (self.active.get(request_id), params.get("redirectResponse")) {
+                    let entry = &mut self.entries[*index];
+                    entry.response = Some(redirect.clone());
+                    entry.response_at = timestamp;
+                    entry.finished_at = timestamp;
+                }
+
+                self.active.insert(request_id.to_string(), self.entries.len());
+                self.entries.push(PendingEntry {
+                    request: params["request"].clone(),
+                    wall_time: params["wallTime"].as_f64().unwrap_or_default(),
+                    started_at: timestamp.unwrap_or_default(),
+                    resource_type: params["type"].as_str().unwrap_or_default().to_string(),
+                    ..Default::default()
+                });
+            }
+            "Network.responseReceived" => {
+                if let Some(entry) = self.entry_mut(request_id) {
+                    entry.response = Some(params["response"].clone());
+                    entry.response_at = timestamp;
+                }
+            }
+            "Network.loadingFinished" => {
+                if let Some(entry) = self.entry_mut(request_id) {
+                    entry.finished_at = timestamp;
+                    entry.encoded_length = params["encodedDataLength"].as_f64();
+                }
+            }
+            "Network.loadingFailed" => {
+                if let Some(entry) = self.entry_mut(request_id) {
+                    entry.finished_at = timestamp;
+                    entry.error = params["errorText"].as_str().map(str::to_string);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 设置请求的响应体
+    pub fn set_body(&mut self, request_id: &str, body: String, base64_encoded: bool) {
+        if let Some(entry) = self.entry_mut(request_id) {
+            entry.body = Some((body, base64_encoded));
+        }
+    }
+
+    fn entry_mut(&mut self, request_id: &str) -> Option<&mut PendingEntry> {
+        let index = *self.active.get(request_id)?;
+        self.entries.get_mut(index)
+    }
+
+    /// 转换为 HAR 1.2 JSON
+    pub fn to_har(&self) -> Value {
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+                "pages": [],
+                "entries": self.entries.iter().map(har_entry).collect::<Vec<_>>(),
+            }
+        })
+    }
+}
+
+/// 将 CDP 头对象转换为 HAR 名值对列表
+fn har_headers(headers: &Value) -> Vec<Value> {
+    headers
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(name, value)| {
+            // CDP 用换行合并同名头
+            value
+                .as_str()
+                .unwrap_or_default()
+                .split('\n')
+                .map(|v| json!({ "name": name, "value": v }))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn har_entry(entry: &PendingEntry) -> Value {
+    let request = &entry.request;
+    let url = request["url"].as_str().unwrap_or_default();
+    let query_string: Vec<Value> = reqwest::Url::parse(url)
+        .map(|u| u.query_pairs().map(|(name, value)| json!({ "name": name, "value": value })).collect())
+        .unwrap_or_default();
+    let started = chrono::DateTime::from_timestamp_millis((entry.wall_time * 1000.0) as i64).unwrap_or_default();
+
+    let mut har_request = json!({
+        "method": request["method"].as_str().unwrap_or("GET"),
+        "url": url,
+        "httpVersion": entry.response.as_ref().and_then(|r| r["protocol"].as_str()).unwrap_or("HTTP/1.1"),
+        "cookies": [],
+        "headers": har_headers(&request["headers"]),
+        "queryString": query_string,
+        "headersSize": -1,
+        "bodySize": request["postData"].as_str().map_or(0, str::len),
+    });
+    if let Some(post_data) = request["postData"].as_str() {
+        let mime_type = request["headers"]
+            .as_object()
+            .and_then(|h| h.iter().find(|(name, _)| name.eq_ignore_ascii_case("content-type")))
+            .and_then(|(_, v)| v.as_str())
+            .unwrap_or_default();
+        har_request["postData"] = json!({ "mimeType": mime_type, "text": post_data });
+    }
+
+    let empty = json!({});
+    let response = entry.response.as_ref().unwrap_or(&empty);
+    let mut content = json!({
+        "size": entry.body.as_ref().map_or_else(
+            || response["encodedDataLength"].as_f64().unwrap_or(0.0),
+            |(body, _)| body.len() as f64,
+        ),
+        "mimeType": response["mimeType"].as_str().unwrap_or_default(),
+    });
+    if let Some((body, base64_encoded)) = &entry.body {
+        content["text"] = json!(body);
+        if *base64_encoded {
+            content["encoding"] = json!("base64");
+        }
+    }
+    let headers = har_headers(&response["headers"]);
+    let redirect_url = headers
+        .iter()
+        .find(|h| h["name"].as_str().is_some_and(|n| n.eq_ignore_ascii_case("location")))
+        .and_then(|h| h["value"].as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let timings = har_timings(entry);
+    let time: f64 = ["blocked", "dns", "connect", "send", "wait", "receive"]
+        .iter()
+        .map(|phase| timings[*phase].as_f64().unwrap_or(0.0).max(0.0))
+        .sum();
+
+    let mut har_entry = json!({
+        "startedDateTime": started.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "time": time,
+        "request": har_request,
+        "response": {
+            "status": response["status"].as_i64().unwrap_or(0),
+            "statusText": response["statusText"].as_str().unwrap_or_default(),
+            "httpVersion": response["protocol"].as_str().unwrap_or("HTTP/1.1"),
+            "cookies": [],
+            "headers": headers,
+            "content": content,
+            "redirectURL": redirect_url,
+            "headersSize": -1,
+            "bodySize": entry.encoded_length.unwrap_or(-1.0),
+        },
+        "cache": {},
+        "timings": timings,
+        "_resourceType": entry.resource_type,
+    });
+    if let Some(ip) = response["remoteIPAddress"].as_str() {
+        har_entry["serverIPAddress"] = json!(ip);
+    }
+    if let Some(error) = &entry.error {
+        har_entry["_error"] = json!(error);
+    }
+
+    har_entry
+}
+
+/// 由 `Response.timing` 和事件时间戳计算 HAR 各阶段耗时（毫秒，-1 表示不适用）
+fn har_timings(entry: &PendingEntry) -> Value {
+    let phase = |start: f64, end: f64| if start >= 0.0 { end - start } else { -1.0 };
+    let timing = entry.response.as_ref().map(|r| &r["timing"]).filter(|t| t.is_object());
+
+    let Some(t) = timing else {
+        // 没有详细时间（如缓存或重定向）时只区分等待和接收
+        let wait = entry.response_at.map_or(-1.0, |at| (at - entry.started_at) * 1000.0);
+        let receive = match (entry.response_at, entry.finished_at) {
+            (Some(response_at), Some(finished_at)) => (finished_at - response_at) * 1000.0,
+            _ => -1.0,
+        };
+        return json!({
+            "blocked": -1, "dns": -1, "connect": -1, "ssl": -1,
+            "send": 0, "wait": wait, "receive": receive,
+        });
+    };
+
+    let get = |key: &str| t[key].as_f64().unwrap_or(-1.0);
+    let request_time = t["requestTime"].as_f64().unwrap_or(entry.started_at);
+    // requestTime 之前的排队时间加上第一个阶段开始前的时间
+    let queued = ((request_time - entry.started_at) * 1000.0).max(0.0);
+    let first_phase = [get("dnsStart"), get("connectStart"), get("sendStart")]
+        .into_iter()
+        .find(|v| *v >= 0.0)
+        .unwrap_or(0.0);
+    let receive = entry
+        .finished_at
+        .map_or(0.0, |end| ((end - request_time) * 1000.0 - get("receiveHeadersEnd")).max(0.0));
+
+    json!({
+        "blocked": queued + first_phase,
+        "dns": phase(get("dnsStart"), get("dnsEnd")),
+        "connect": phase(get("connectStart"), get("connectEnd")),
+        "ssl": phase(get("sslStart"), get("sslEnd")),
+        "send": get("sendEnd") - get("sendStart"),
+        "wait": get("receiveHeadersEnd") - get("sendEnd"),
+        "receive": receive,
+    })
+}
+
+/// 单个页面的录制
+#[derive(Debug)]
+struct Recording {
+    log: Arc<Mutex<HarLog>>,
+    task: JoinHandle<()>,
+}
+
+/// 所有页面的 HAR 录制，按页面 ID 索引
+#[derive(Debug, Default)]
+pub struct HarRecorder {
+    recordings: Mutex<HashMap<String, Recording>>,
+}
+
+impl HarRecorder {
+    /// 开始录制页面的网络活动；已在录制时重新开始
+    pub async fn start(&self, page_id: &str, client: Arc<dyn CdpClient>, include_bodies: bool) -> crate::Result<()> {
+        let mut receivers = Vec::with_capacity(HAR_EVENTS.len());
+        // 订阅时连接会按需启用 Network 域，最后一个订阅者退出后再禁用
+        for event in HAR_EVENTS {
+            receivers.push(client.subscribe_events(event).await?);
+        }
+
+        // 合并各事件的接收端，保持每类事件内的顺序
+        let (tx, mut events) = tokio::sync::mpsc::channel::<CdpEvent>(256);
+        for mut receiver in receivers {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                while let Some(event) = receiver.recv().await {
+                    if tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        let log = Arc::new(Mutex::new(HarLog::default()));
+        let task_log = Arc::clone(&log);
+        let task = tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                task_log.lock().await.record(&event);
+
+                if include_bodies && event.method == "Network.loadingFinished" {
+                    let request_id = event.params["requestId"].as_str().unwrap_or_default().to_string();
+                    match client
+                        .call_method("Network.getResponseBody", json!({ "requestId": request_id }))
+                        .await
+                    {
+                        Ok(body) => task_log.lock().await.set_body(
+                            &request_id,
+                            body["body"].as_str().unwrap_or_default().to_string(),
+                            body["base64Encoded"].as_bool().unwrap_or(false),
+                        ),
+                        Err(e) => tracing::debug!("No response body for {}: {}", request_id, e),
+                    }
+                }
+            }
+        });
+
+        if let Some(previous) = self.recordings.lock().await.insert(page_id.to_string(), Recording { log, task }) {
+            previous.task.abort();
+        }
+
+        Ok(())
+    }
+
+    /// 导出页面的 HAR；`stop` 为 true 时同时结束录制。未在录制时返回 None
+    pub async fn export(&self, page_id: &str, stop: bool) -> Option<Value> {
+        let mut recordings = self.recordings.lock().await;
+        let har = recordings.get(page_id)?.log.lock().await.to_har();
+
+        if stop {
+            if let Some(recording) = recordings.remove(page_id) {
+                recording.task.abort();
+            }
+        }
+
+        Some(har)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str, params: Value) -> CdpEvent {
+        CdpEvent {
+            method: method.to_string(),
+            params,
+            session_id: None,
+        }
+    }
+
+    // 测试：由请求、响应、完成和失败事件构建 HAR
+    #[test]
+    fn test_har_from_network_events() {
+        let mut log = HarLog::default();
+        log.record(&event("Network.requestWillBeSent", json!({
+            "requestId": "1",
+            "timestamp": 100.0,
+            "wallTime": 1_700_000_000.0,
+            "type": "Document",
+            "request": {
+                "url": "https://example.com/search?q=rust",
+                "method": "GET",
+                "headers": { "Accept": "text/html" },
+            },
+        })));
+        log.record(&event("Network.requestWillBeSent", json!({
+            "requestId": "2",
+            "timestamp": 100.5,
+            "wallTime": 1_700_000_000.5,
+            "type": "XHR",
+            "request": {
+                "url": "https://example.com/api",
+                "method": "POST",
+                "headers": { "Content-Type": "application/json" },
+                "postData": "{\"a\":1}",
+            },
+        })));
+        log.record(&event("Network.responseReceived", json!({
+            "requestId": "1",
+            "timestamp": 100.2,
+            "response": {
+                "url": "https://example.com/search?q=rust",
+                "status": 200,
+                "statusText": "OK",
+                "protocol": "h2",
+                "mimeType": "text/html",
+                "headers": { "Content-Type": "text/html" },
+                "remoteIPAddress": "93.184.216.34",
+                "timing": {
+                    "requestTime": 100.01,
+                    "dnsStart": 0.0, "dnsEnd": 10.0,
+                    "connectStart": 10.0, "connectEnd": 50.0,
+                    "sslStart": 20.0, "sslEnd": 50.0,
+                    "sendStart": 50.0, "sendEnd": 51.0,
+                    "receiveHeadersEnd": 150.0,
+                },
+            },
+        })));
+        log.record(&event("Network.loadingFinished", json!({
+            "requestId": "1",
+            "timestamp": 100.31,
+            "encodedDataLength": 1234.0,
+        })));
+        log.set_body("1", "<html></html>".to_string(), false);
+        log.record(&event("Network.loadingFailed", json!({
+            "requestId": "2",
+            "timestamp": 100.6,
+            "errorText": "net::ERR_CONNECTION_REFUSED",
+        })));
+
+        let har = log.to_har();
+        assert_eq!(har["log"]["version"], "1.2");
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let page = &entries[0];
+        assert_eq!(page["startedDateTime"], "2023-11-14T22:13:20.000Z");
+        assert_eq!(page["request"]["queryString"][0]["name"], "q");
+        assert_eq!(page["response"]["status"], 200);
+        assert_eq!(page["response"]["httpVersion"], "h2");
+        assert_eq!(page["response"]["bodySize"], 1234.0);
+        assert_eq!(page["response"]["content"]["text"], "<html></html>");
+        assert_eq!(page["serverIPAddress"], "93.184.216.34");
+        let timings = &page["timings"];
+        assert!((timings["blocked"].as_f64().unwrap() - 10.0).abs() < 1e-6);
+        assert_eq!(timings["dns"], 10.0);
+        assert_eq!(timings["connect"], 40.0);
+        assert_eq!(timings["ssl"], 30.0);
+        assert_eq!(timings["wait"], 99.0);
+        assert!((timings["receive"].as_f64().unwrap() - 150.0).abs() < 1e-6);
+        assert!((page["time"].as_f64().unwrap() - 310.0).abs() < 1e-6);
+
+        let failed = &entries[1];
+        assert_eq!(failed["request"]["method"], "POST");
+        assert_eq!(failed["request"]["postData"]["mimeType"], "application/json");
+        assert_eq!(failed["response"]["status"], 0);
+        assert_eq!(failed["_error"], "net::ERR_CONNECTION_REFUSED");
+    }
+
+    // 测试：重定向的每一跳各成一个条目
+    #[test]
+    fn test_har_splits_redirects() {
+        let mut log = HarLog::default();
+        log.record(&event("Network.requestWillBeSent", json!({
+            "requestId": "1", "timestamp": 1.0, "wallTime": 1.0,
+            "request": { "url": "http://example.com/", "method": "GET", "headers": {} },
+        })));
+        log.record(&event("Network.requestWillBeSent", json!({
+            "requestId": "1", "timestamp": 1.1, "wallTime": 1.1,
+            "request": { "url": "https://example.com/", "method": "GET", "headers": {} },
+            "redirectResponse": { "status": 301, "headers": { "Location": "https://example.com/" } },
+        })));
+
+        let har = log.to_har();
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["response"]["status"], 301);
+        assert_eq!(entries[0]["response"]["redirectURL"], "https://example.com/");
+        assert_eq!(entries[1]["request"]["url"], "https://example.com/");
+    }
+}
@@ -0,0 +1,221 @@
+//! # JSONPath 查询
+//!
+//! `ExtractJson` 使用的 JSONPath 子集，支持：
+//! - 根节点 `$`
+//! - 成员访问 `.name`、`['name']`、`["name"]`
+//! - 数组下标 `[0]`，负数从末尾计数（`[-1]` 为最后一个元素）
+//! - 通配符 `.*`、`[*]`
+//! - 递归下降 `..name`、`..*`、`..[0]`
+//!
+//! 不支持过滤表达式、切片和并集。
+
+use serde_json::Value;
+
+use crate::Error;
+
+/// 路径中的一段
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// 作用于当前节点的直接子节点
+    Child(Selector),
+    /// 作用于当前节点及其所有后代
+    Descendant(Selector),
+}
+
+/// 子节点选择方式
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// 解析后的 JSONPath 查询
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// 解析 JSONPath 表达式
+    ///
+    /// # 错误
+    /// 表达式不以 `$` 开头或包含不支持的语法时返回 `Configuration` 错误
+    pub fn parse(path: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::configuration(format!("Invalid JSONPath {:?}: {}", path, reason));
+
+        let rest = path.trim().strip_prefix('$').ok_or_else(|| invalid("must start with $"))?;
+        let chars: Vec<char> = rest.chars().collect();
+        let mut segments = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let descendant = chars[i..].starts_with(&['.', '.']);
+            let selector = if descendant || chars[i] == '.' {
+                i += if descendant { 2 } else { 1 };
+                if descendant && chars.get(i) == Some(&'[') {
+                    parse_bracket(&chars, &mut i).map_err(|reason| invalid(&reason))?
+                } else {
+                    let start = i;
+                    while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                        i += 1;
+                    }
+                    match chars[start..i].iter().collect::<String>() {
+                        name if name.is_empty() => return Err(invalid("expected a member name after '.'")),
+                        name if name == "*" => Selector::Wildcard,
+                        name => Selector::Name(name),
+                    }
+                }
+            } else if chars[i] == '[' {
+                parse_bracket(&chars, &mut i).map_err(|reason| invalid(&reason))?
+            } else {
+                return Err(invalid(&format!("unexpected character {:?}", chars[i])));
+            };
+
+            segments.push(if descendant { Segment::Descendant(selector) } else { Segment::Child(selector) });
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// 返回文档中匹配的所有值，按文档顺序排列
+    pub fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![root];
+        for segment in &self.segments {
+            let mut next = Vec::new();
+            for value in current {
+                match segment {
+                    Segment::Child(selector) => apply(selector, value, &mut next),
+                    Segment::Descendant(selector) => {
+                        let mut nodes = Vec::new();
+                        collect_descendants(value, &mut nodes);
+                        for node in nodes {
+                            apply(selector, node, &mut next);
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+/// 解析 `[...]` 选择器，`i` 指向 `[`，返回时指向 `]` 之后
+fn parse_bracket(chars: &[char], i: &mut usize) -> Result<Selector, String> {
+    *i += 1;
+    let selector = match chars.get(*i) {
+        Some('*') => {
+            *i += 1;
+            Selector::Wildcard
+        }
+        Some(&quote) if quote == '\'' || quote == '"' => {
+            *i += 1;
+            let mut name = String::new();
+            loop {
+                match chars.get(*i) {
+                    Some('\\') => {
+                        name.extend(chars.get(*i + 1));
+                        *i += 2;
+                    }
+                    Some(&c) if c == quote => {
+                        *i += 1;
+                        break;
+                    }
+                    Some(&c) => {
+                        name.push(c);
+                        *i += 1;
+                    }
+                    None => return Err("unterminated string in brackets".to_string()),
+                }
+            }
+            Selector::Name(name)
+        }
+        _ => {
+            let start = *i;
+            while *i < chars.len() && chars[*i] != ']' {
+                *i += 1;
+            }
+            let index: String = chars[start..*i].iter().collect();
+            let index = index
+                .trim()
+                .parse()
+                .map_err(|_| format!("expected an index, '*' or a quoted name in brackets, got {:?}", index))?;
+            Selector::Index(index)
+        }
+    };
+
+    if chars.get(*i) != Some(&']') {
+        return Err("expected ']'".to_string());
+    }
+    *i += 1;
+    Ok(selector)
+}
+
+/// 将选择器应用到单个节点
+fn apply<'a>(selector: &Selector, value: &'a Value, out: &mut Vec<&'a Value>) {
+    match (selector, value) {
+        (Selector::Name(name), Value::Object(map)) => out.extend(map.get(name)),
+        (Selector::Index(index), Value::Array(items)) => {
+            let index = if *index < 0 { items.len() as i64 + index } else { *index };
+            if index >= 0 {
+                out.extend(items.get(index as usize));
+            }
+        }
+        (Selector::Wildcard, Value::Object(map)) => out.extend(map.values()),
+        (Selector::Wildcard, Value::Array(items)) => out.extend(items),
+        _ => {}
+    }
+}
+
+/// 先序收集节点自身及其所有后代
+fn collect_descendants<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(value);
+    match value {
+        Value::Object(map) => map.values().for_each(|child| collect_descendants(child, out)),
+        Value::Array(items) => items.iter().for_each(|child| collect_descendants(child, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // 测试：支持的语法选出预期的值
+    #[test]
+    fn test_select_supported_syntax() {
+        let doc = json!({
+            "name": "Widget",
+            "offers": [
+                { "price": "9.99", "seller": { "name": "A" } },
+                { "price": "12.50", "seller": { "name": "B" } }
+            ],
+            "odd key": true
+        });
+        let select = |path: &str| -> Vec<Value> {
+            JsonPath::parse(path).unwrap().select(&doc).into_iter().cloned().collect()
+        };
+
+        assert_eq!(select("$"), vec![doc.clone()]);
+        assert_eq!(select("$.name"), vec![json!("Widget")]);
+        assert_eq!(select("$.offers[0].price"), vec![json!("9.99")]);
+        assert_eq!(select("$.offers[-1].seller.name"), vec![json!("B")]);
+        assert_eq!(select("$.offers[*].price"), vec![json!("9.99"), json!("12.50")]);
+        assert_eq!(select("$['odd key']"), vec![json!(true)]);
+        assert_eq!(select("$..name"), vec![json!("Widget"), json!("A"), json!("B")]);
+        assert_eq!(select("$.offers.*.seller[\"name\"]"), vec![json!("A"), json!("B")]);
+        assert!(select("$.missing.price").is_empty());
+        assert!(select("$.offers[5]").is_empty());
+    }
+
+    // 测试：无效表达式返回配置错误
+    #[test]
+    fn test_parse_rejects_invalid_paths() {
+        for path in ["name", "$.", "$[", "$['open", "$[1:2]", "$.a b[?(@.x)]", "$x"] {
+            let err = JsonPath::parse(path).unwrap_err();
+            assert!(matches!(err, Error::Configuration(_)), "{} was accepted", path);
+        }
+    }
+}
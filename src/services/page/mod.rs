@@ -26,6 +26,8 @@
 //! - [`conversions`][]: 类型转换（proto <-> 内部）
 //! - [`response`][]: 响应构建辅助函数
 //! - [`scripts`]: JavaScript 脚本常量
+//! - [`har`]: 网络活动的 HAR 1.2 录制
+//! - [`json_path`]: `ExtractJson` 使用的 JSONPath 子集
 //!
 //! ## 使用示例
 //! ```rust,no_run
@@ -46,6 +48,8 @@ pub mod conversions;
 pub mod response;
 pub mod scripts;
 pub mod handlers;
+pub mod har;
+pub mod json_path;
 
 // 测试模块
 #[cfg(test)]
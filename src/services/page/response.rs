@@ -6,6 +6,7 @@
 
 use tonic::{Response, Status};
 use crate::Error;
+use crate::services::common::request_id;
 use crate::chaser_oxide::v1::{
     ErrorCode, Error as ProtoError,
     Empty,
@@ -19,13 +20,17 @@ pub fn error_to_proto(error: Error) -> ProtoError {
         Error::NavigationFailed(_) => ErrorCode::NavigationFailed,
         Error::ScriptExecutionFailed(_) => ErrorCode::EvaluationFailed,
         Error::Configuration(_) => ErrorCode::InvalidArgument,
+        Error::ResourceExhausted(_) => ErrorCode::ResourceExhausted,
+        Error::Unavailable(_) => ErrorCode::Unavailable,
+        Error::PermissionDenied(_) => ErrorCode::PermissionDenied,
+        Error::Cancelled(_) => ErrorCode::Aborted,
         _ => ErrorCode::Internal,
     };
 
     ProtoError {
         code: code.into(),
         message: error.to_string(),
-        details: std::collections::HashMap::new(),
+        details: request_id::error_details(),
     }
 }
 
@@ -38,6 +43,10 @@ pub fn error_to_status(error: Error) -> Status {
         ErrorCode::NavigationFailed => tonic::Code::Aborted,
         ErrorCode::EvaluationFailed => tonic::Code::Internal,
         ErrorCode::InvalidArgument => tonic::Code::InvalidArgument,
+        ErrorCode::ResourceExhausted => tonic::Code::ResourceExhausted,
+        ErrorCode::Unavailable => tonic::Code::Unavailable,
+        ErrorCode::PermissionDenied => tonic::Code::PermissionDenied,
+        ErrorCode::Aborted => tonic::Code::Cancelled,
         _ => tonic::Code::Internal,
     };
 
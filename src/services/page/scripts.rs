@@ -86,47 +86,76 @@ pub const CLEAR_COOKIES_SCRIPT: &str = r#"
 
 /// 等待选择器出现脚本
 ///
-/// 轮询检查指定的 CSS 选择器是否出现在页面中
+/// 通过 `MutationObserver` 监听 DOM 变化，节点出现后立即 resolve；
+/// 另以低频定时器兜底，覆盖观察不到的变化（如 `document.documentElement` 被替换）。
+/// 选择器无效时 reject。需通过 `Runtime.callFunctionOn` 以参数传入，不要拼接进脚本。
+/// 在控制对象（`this`）上注册 `cancel()`，请求取消或超时后调用以停止监听。
 ///
 /// # 参数
 /// - `selector`: CSS 选择器字符串
+/// - `fallbackMs`: 兜底轮询间隔（毫秒）
 pub const WAIT_FOR_SELECTOR_SCRIPT: &str = r#"
-((selector) => {
-    return new Promise((resolve) => {
+function (selector, fallbackMs) {
+    return new Promise((resolve, reject) => {
+        let observer = null;
+        let timer = null;
+        const finish = (settle, value) => {
+            if (observer) observer.disconnect();
+            if (timer) clearInterval(timer);
+            settle(value);
+        };
         const check = () => {
-            const element = document.querySelector(selector);
-            if (element) {
-                resolve(true);
-            } else {
-                setTimeout(check, 100);
+            try {
+                if (document.querySelector(selector)) {
+                    finish(resolve, true);
+                    return true;
+                }
+            } catch (e) {
+                finish(reject, e);
+                return true;
             }
+            return false;
         };
-        check();
+        if (check()) return;
+        this.cancel = () => finish(resolve, false);
+        observer = new MutationObserver(check);
+        observer.observe(document, { childList: true, subtree: true, attributes: true });
+        timer = setInterval(check, fallbackMs);
     });
-})
+}
 "#;
 
 /// 等待导航到指定 URL 脚本
 ///
-/// 轮询检查当前页面 URL 是否等于目标 URL
+/// 轮询检查当前页面 URL 是否等于目标 URL。
+/// 需通过 `Runtime.callFunctionOn` 以参数传入，不要拼接进脚本。
+/// 在控制对象（`this`）上注册 `cancel()`，请求取消或超时后调用以停止轮询。
 ///
 /// # 参数
 /// - `url`: 目标 URL 字符串
 pub const WAIT_FOR_URL_SCRIPT: &str = r#"
-((url) => {
+function (url) {
     return new Promise((resolve) => {
+        let timer = null;
+        this.cancel = () => {
+            clearTimeout(timer);
+            resolve(false);
+        };
         const check = () => {
             if (window.location.href === url) {
                 resolve(true);
             } else {
-                setTimeout(check, 100);
+                timer = setTimeout(check, 100);
             }
         };
         check();
     });
-})
+}
 "#;
 
+/// 取消 `WAIT_FOR_SELECTOR_SCRIPT` / `WAIT_FOR_URL_SCRIPT` 的等待，在控制对象上调用
+pub const CANCEL_WAITER_SCRIPT: &str = "function () { if (this.cancel) this.cancel(); }";
+
 /// 获取性能指标脚本
 ///
 /// 使用 Performance API 获取页面加载和渲染性能指标
@@ -290,6 +319,64 @@ pub const GET_URL_SCRIPT: &str = "window.location.href";
 /// 窗口聚焦脚本
 pub const WINDOW_FOCUS_SCRIPT: &str = "window.focus()";
 
+/// 截图前注入的临时样式，停止 CSS 动画和过渡，使截图不落在过渡中间
+pub const FREEZE_ANIMATIONS_SCRIPT: &str = r#"
+(() => {
+    const style = document.createElement('style');
+    style.setAttribute('data-chaser-freeze-animations', '');
+    style.textContent = '*, *::before, *::after { animation: none !important; transition: none !important; }';
+    (document.head || document.documentElement).appendChild(style);
+    return true;
+})()
+"#;
+
+/// 移除 `FREEZE_ANIMATIONS_SCRIPT` 注入的样式
+pub const UNFREEZE_ANIMATIONS_SCRIPT: &str =
+    "document.querySelectorAll('style[data-chaser-freeze-animations]').forEach(style => style.remove())";
+
+/// 注入带标记的 `<style>`，文档尚未解析时等到 DOMContentLoaded
+///
+/// 同一标记的样式只注入一次。
+///
+/// # 参数
+/// - `id`: 样式 ID，写入 `data-chaser-style`
+/// - `css`: 样式表内容
+pub const INJECT_STYLE_SCRIPT: &str = r#"
+((id, css) => {
+    const apply = () => {
+        const existing = Array.from(document.querySelectorAll('style[data-chaser-style]'));
+        if (existing.some(style => style.dataset.chaserStyle === id)) {
+            return;
+        }
+        const style = document.createElement('style');
+        style.dataset.chaserStyle = id;
+        style.textContent = css;
+        (document.head || document.documentElement).appendChild(style);
+    };
+    if (document.documentElement) {
+        apply();
+    } else {
+        document.addEventListener('DOMContentLoaded', apply, { once: true });
+    }
+    return true;
+})
+"#;
+
+/// 移除 `INJECT_STYLE_SCRIPT` 注入的样式
+///
+/// # 参数
+/// - `id`: 样式 ID
+pub const REMOVE_STYLE_SCRIPT: &str = r#"
+((id) => {
+    document.querySelectorAll('style[data-chaser-style]').forEach(style => {
+        if (style.dataset.chaserStyle === id) {
+            style.remove();
+        }
+    });
+    return true;
+})
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -324,3 +411,38 @@ mod tests {
         assert!(GET_METRICS_SCRIPT.contains("performance.timing"));
     }
 }
+
+/// 读取匹配元素的文本内容，返回 JSON 数组字符串
+///
+/// # 参数
+/// - `selector`: CSS 选择器，为空时按 `type` 匹配 `<script>`
+/// - `type`: `<script>` 的 type 属性
+pub const SCRIPT_TEXTS_SCRIPT: &str = r#"
+((selector, type) => {
+    const query = selector || `script[type="${CSS.escape(type)}"]`;
+    return JSON.stringify(Array.from(document.querySelectorAll(query), el => el.textContent || ''));
+})
+"#;
+
+/// 读取滚动目标的滚动状态，以及滚轮事件应落在的视口坐标
+///
+/// 返回 `{ scrollHeight, clientHeight, scrollTop, x, y }`，元素不存在时返回 null。
+///
+/// # 参数
+/// - `selector`: 可滚动元素的 CSS 选择器，为空时滚动整个页面
+pub const SCROLL_METRICS_SCRIPT: &str = r#"
+((selector) => {
+    const el = selector ? document.querySelector(selector) : (document.scrollingElement || document.documentElement);
+    if (!el) return null;
+    const rect = selector ? el.getBoundingClientRect() : { left: 0, top: 0, right: innerWidth, bottom: innerHeight };
+    const left = Math.max(rect.left, 0), right = Math.min(rect.right, innerWidth);
+    const top = Math.max(rect.top, 0), bottom = Math.min(rect.bottom, innerHeight);
+    return {
+        scrollHeight: el.scrollHeight,
+        clientHeight: el.clientHeight,
+        scrollTop: el.scrollTop,
+        x: (left + right) / 2,
+        y: (top + bottom) / 2,
+    };
+})
+"#;
@@ -24,10 +24,17 @@
 //! # }
 //! ```
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
-use crate::session::SessionManager;
+use crate::config::{
+    ViewportConfig, DEFAULT_MAX_EVALUATE_RESULT_BYTES, DEFAULT_NAVIGATION_TIMEOUT_MS, MAX_NAVIGATION_TIMEOUT_MS,
+};
+use crate::session::screenshot::DefaultQuality;
+use crate::session::url_policy::UrlPolicy;
+use crate::session::{PageStateStore, SessionManager};
+use crate::stealth::StealthEngine;
 use crate::chaser_oxide::v1::page_service_server::PageService;
 
 // 导入公共模块（来自父模块）
@@ -36,8 +43,12 @@ use super::handlers;
 // 导入处理器
 use handlers::{
     NavigationHandlers, ContentHandlers, ScriptHandlers,
-    EmulationHandlers, NetworkHandlers, CookieHandlers, WaitForHandlers,
+    EmulationHandlers, NetworkHandlers, CookieHandlers, StorageHandlers, CoverageHandlers,
+    ProfilingHandlers, HeapSnapshotStream, ScreenshotChunkStream, WaitForHandlers, InputHandlers, ResetHandlers,
+    FrameHandlers, DialogHandlers, HarHandlers, StyleHandlers, InjectedStyles, ConsoleHandlers, MediaOverrides,
+    ExtractHandlers,
 };
+use super::har::HarRecorder;
 
 /// Page service implementation
 ///
@@ -46,10 +57,48 @@ use handlers::{
 /// # 类型参数
 ///
 /// * `S` - SessionManager 类型，必须实现 `SessionManager` trait
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Service<S> {
     /// Session manager 实例
     session_manager: Arc<S>,
+    /// 默认导航超时（毫秒）
+    default_navigation_timeout: u64,
+    /// 隐身引擎，用于重置页面时移除注入的脚本
+    stealth_engine: Option<Arc<dyn StealthEngine>>,
+    /// 创建页面请求未指定视口时使用的默认视口
+    default_viewport: ViewportConfig,
+    /// 导航目标 URL 的允许/拒绝规则
+    url_policy: Arc<UrlPolicy>,
+    /// 脚本执行结果和页面内容的最大字节数
+    max_result_bytes: usize,
+    /// 截图和 PDF 允许保存到的目录；为 None 时拒绝保存到路径
+    output_dir: Option<PathBuf>,
+    /// 截图请求未指定质量时 JPEG/WebP 使用的默认质量
+    screenshot_quality: DefaultQuality,
+    /// 各页面的 HAR 录制
+    har_recorder: Arc<HarRecorder>,
+    /// InjectStyle 注入的样式
+    injected_styles: Arc<InjectedStyles>,
+    /// SetMediaFeatures 设置的媒体覆盖
+    media_overrides: Arc<MediaOverrides>,
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for Service<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Service")
+            .field("session_manager", &self.session_manager)
+            .field("default_navigation_timeout", &self.default_navigation_timeout)
+            .field("stealth_engine", &self.stealth_engine.as_ref().map(|_| "Arc<dyn StealthEngine>"))
+            .field("default_viewport", &self.default_viewport)
+            .field("url_policy", &self.url_policy)
+            .field("max_result_bytes", &self.max_result_bytes)
+            .field("output_dir", &self.output_dir)
+            .field("screenshot_quality", &self.screenshot_quality)
+            .field("har_recorder", &self.har_recorder)
+            .field("injected_styles", &self.injected_styles)
+            .field("media_overrides", &self.media_overrides)
+            .finish()
+    }
 }
 
 impl<S> Service<S>
@@ -62,13 +111,80 @@ where
     ///
     /// * `session_manager` - 会话管理器实例
     pub fn new(session_manager: Arc<S>) -> Self {
-        Self { session_manager }
+        Self {
+            session_manager,
+            default_navigation_timeout: DEFAULT_NAVIGATION_TIMEOUT_MS,
+            stealth_engine: None,
+            default_viewport: ViewportConfig::default(),
+            url_policy: Arc::new(UrlPolicy::default()),
+            max_result_bytes: DEFAULT_MAX_EVALUATE_RESULT_BYTES,
+            output_dir: None,
+            screenshot_quality: DefaultQuality::default(),
+            har_recorder: Arc::new(HarRecorder::default()),
+            injected_styles: Arc::new(InjectedStyles::default()),
+            media_overrides: Arc::new(MediaOverrides::default()),
+        }
+    }
+
+    /// 需要在页面关闭时释放的按页面状态，交给会话管理器登记
+    pub fn page_state(&self) -> Vec<Arc<dyn PageStateStore>> {
+        vec![self.injected_styles.clone(), self.media_overrides.clone()]
+    }
+
+    /// 设置截图和 PDF 请求通过 `output_path` 保存文件时允许写入的目录
+    pub fn with_output_dir(mut self, output_dir: Option<PathBuf>) -> Self {
+        self.output_dir = output_dir;
+        self
+    }
+
+    /// 设置截图请求 `quality` 为 0 时 JPEG/WebP 使用的默认质量
+    pub fn with_screenshot_quality(mut self, quality: DefaultQuality) -> Self {
+        self.screenshot_quality = quality;
+        self
+    }
+
+    /// 设置脚本执行结果和页面内容的最大字节数
+    pub fn with_max_evaluate_result_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_result_bytes = max_bytes;
+        self
+    }
+
+    /// 设置创建页面时的默认视口
+    pub fn with_default_viewport(mut self, viewport: ViewportConfig) -> Self {
+        self.default_viewport = viewport;
+        self
+    }
+
+    /// 设置导航目标 URL 的允许/拒绝规则，对 CreatePage 和 Navigate 生效
+    pub fn with_url_policy(mut self, url_policy: UrlPolicy) -> Self {
+        self.url_policy = Arc::new(url_policy);
+        self
+    }
+
+    /// 设置隐身引擎，ResetPage 会通过它移除页面上注入的脚本
+    pub fn with_stealth_engine(mut self, stealth_engine: Arc<dyn StealthEngine>) -> Self {
+        self.stealth_engine = Some(stealth_engine);
+        self
+    }
+
+    /// 设置导航请求 `timeout` 为 0 时使用的默认超时（毫秒）
+    ///
+    /// 0 表示恢复内置默认值，超过 [`MAX_NAVIGATION_TIMEOUT_MS`] 的值会被截断。
+    pub fn with_default_navigation_timeout(mut self, timeout_ms: u64) -> Self {
+        self.default_navigation_timeout = match timeout_ms {
+            0 => DEFAULT_NAVIGATION_TIMEOUT_MS,
+            ms => ms.min(MAX_NAVIGATION_TIMEOUT_MS),
+        };
+        self
     }
 
     /// 获取导航处理器
     fn navigation(&self) -> NavigationHandlers<S> {
         NavigationHandlers {
             session_manager: Arc::clone(&self.session_manager),
+            default_timeout: self.default_navigation_timeout,
+            default_viewport: self.default_viewport,
+            url_policy: Arc::clone(&self.url_policy),
         }
     }
 
@@ -76,6 +192,10 @@ where
     fn content(&self) -> ContentHandlers<S> {
         ContentHandlers {
             session_manager: Arc::clone(&self.session_manager),
+            max_result_bytes: self.max_result_bytes,
+            output_dir: self.output_dir.clone(),
+            default_quality: self.screenshot_quality,
+            media_overrides: Arc::clone(&self.media_overrides),
         }
     }
 
@@ -83,6 +203,7 @@ where
     fn script(&self) -> ScriptHandlers<S> {
         ScriptHandlers {
             session_manager: Arc::clone(&self.session_manager),
+            max_result_bytes: self.max_result_bytes,
         }
     }
 
@@ -90,6 +211,7 @@ where
     fn emulation(&self) -> EmulationHandlers<S> {
         EmulationHandlers {
             session_manager: Arc::clone(&self.session_manager),
+            media_overrides: Arc::clone(&self.media_overrides),
         }
     }
 
@@ -107,12 +229,95 @@ where
         }
     }
 
+    /// 获取 Web Storage 处理器
+    fn storage(&self) -> StorageHandlers<S> {
+        StorageHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
+    /// 获取代码覆盖率处理器
+    fn coverage(&self) -> CoverageHandlers<S> {
+        CoverageHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
+    /// 获取内存分析处理器
+    fn profiling(&self) -> ProfilingHandlers<S> {
+        ProfilingHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
     /// 获取等待处理器
     fn wait(&self) -> WaitForHandlers<S> {
         WaitForHandlers {
             session_manager: Arc::clone(&self.session_manager),
         }
     }
+
+    /// 获取输入处理器
+    fn input(&self) -> InputHandlers<S> {
+        InputHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
+    /// 获取框架处理器
+    fn frames(&self) -> FrameHandlers<S> {
+        FrameHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
+    /// 获取 HAR 录制处理器
+    fn har(&self) -> HarHandlers<S> {
+        HarHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+            recorder: Arc::clone(&self.har_recorder),
+            max_result_bytes: self.max_result_bytes,
+        }
+    }
+
+    /// 获取样式注入处理器
+    fn style(&self) -> StyleHandlers<S> {
+        StyleHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+            styles: Arc::clone(&self.injected_styles),
+        }
+    }
+
+    /// 获取控制台日志处理器
+    fn console(&self) -> ConsoleHandlers<S> {
+        ConsoleHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
+    /// 获取内嵌 JSON 提取处理器
+    fn extract(&self) -> ExtractHandlers<S> {
+        ExtractHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+            max_result_bytes: self.max_result_bytes,
+        }
+    }
+
+    /// 获取对话框处理器
+    fn dialog(&self) -> DialogHandlers<S> {
+        DialogHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+        }
+    }
+
+    /// 获取页面重置处理器
+    fn reset(&self) -> ResetHandlers<S> {
+        ResetHandlers {
+            session_manager: Arc::clone(&self.session_manager),
+            stealth_engine: self.stealth_engine.clone(),
+            default_timeout: self.default_navigation_timeout,
+        }
+    }
 }
 
 /// 实现 PageService trait
@@ -139,6 +344,16 @@ where
         self.content().screenshot(request).await
     }
 
+    type ScreenshotStreamStream = ScreenshotChunkStream;
+
+    async fn screenshot_stream(&self, request: Request<crate::chaser_oxide::v1::ScreenshotStreamRequest>) -> Result<Response<Self::ScreenshotStreamStream>, Status> {
+        self.content().screenshot_stream(request).await
+    }
+
+    async fn compare_screenshot(&self, request: Request<crate::chaser_oxide::v1::CompareScreenshotRequest>) -> Result<Response<crate::chaser_oxide::v1::CompareScreenshotResponse>, Status> {
+        self.content().compare_screenshot(request).await
+    }
+
     async fn evaluate(&self, request: Request<crate::chaser_oxide::v1::EvaluateRequest>) -> Result<Response<crate::chaser_oxide::v1::EvaluateResponse>, Status> {
         self.script().evaluate(request).await
     }
@@ -167,6 +382,18 @@ where
         self.navigation().go_forward(request).await
     }
 
+    async fn wait_for_navigation(&self, request: Request<crate::chaser_oxide::v1::WaitForNavigationRequest>) -> Result<Response<crate::chaser_oxide::v1::WaitForNavigationResponse>, Status> {
+        self.navigation().wait_for_navigation(request).await
+    }
+
+    async fn wait_for_load_state(&self, request: Request<crate::chaser_oxide::v1::WaitForLoadStateRequest>) -> Result<Response<crate::chaser_oxide::v1::WaitForLoadStateResponse>, Status> {
+        self.navigation().wait_for_load_state(request).await
+    }
+
+    async fn click_and_wait(&self, request: Request<crate::chaser_oxide::v1::ClickAndWaitRequest>) -> Result<Response<crate::chaser_oxide::v1::ClickAndWaitResponse>, Status> {
+        self.navigation().click_and_wait(request).await
+    }
+
     async fn set_viewport(&self, request: Request<crate::chaser_oxide::v1::SetViewportRequest>) -> Result<Response<crate::chaser_oxide::v1::SetViewportResponse>, Status> {
         self.emulation().set_viewport(request).await
     }
@@ -175,6 +402,14 @@ where
         self.emulation().emulate_device(request).await
     }
 
+    async fn set_cpu_throttling(&self, request: Request<crate::chaser_oxide::v1::SetCpuThrottlingRequest>) -> Result<Response<crate::chaser_oxide::v1::SetCpuThrottlingResponse>, Status> {
+        self.emulation().set_cpu_throttling(request).await
+    }
+
+    async fn set_media_features(&self, request: Request<crate::chaser_oxide::v1::SetMediaFeaturesRequest>) -> Result<Response<crate::chaser_oxide::v1::SetMediaFeaturesResponse>, Status> {
+        self.emulation().set_media_features(request).await
+    }
+
     async fn bring_to_front(&self, request: Request<crate::chaser_oxide::v1::BringToFrontRequest>) -> Result<Response<crate::chaser_oxide::v1::BringToFrontResponse>, Status> {
         self.emulation().bring_to_front(request).await
     }
@@ -191,6 +426,14 @@ where
         self.wait().wait_for(request).await
     }
 
+    async fn wait_for_network_request(&self, request: Request<crate::chaser_oxide::v1::WaitForNetworkRequestRequest>) -> Result<Response<crate::chaser_oxide::v1::WaitForNetworkRequestResponse>, Status> {
+        self.wait().wait_for_network_request(request).await
+    }
+
+    async fn wait_for_network_response(&self, request: Request<crate::chaser_oxide::v1::WaitForNetworkResponseRequest>) -> Result<Response<crate::chaser_oxide::v1::WaitForNetworkResponseResponse>, Status> {
+        self.wait().wait_for_network_response(request).await
+    }
+
     async fn get_pdf(&self, request: Request<crate::chaser_oxide::v1::GetPdfRequest>) -> Result<Response<crate::chaser_oxide::v1::GetPdfResponse>, Status> {
         self.content().get_pdf(request).await
     }
@@ -211,6 +454,108 @@ where
         self.cookies().clear_cookies(request).await
     }
 
+    async fn replace_cookies(&self, request: Request<crate::chaser_oxide::v1::ReplaceCookiesRequest>) -> Result<Response<crate::chaser_oxide::v1::ReplaceCookiesResponse>, Status> {
+        self.cookies().replace_cookies(request).await
+    }
+
+    async fn get_storage(&self, request: Request<crate::chaser_oxide::v1::GetStorageRequest>) -> Result<Response<crate::chaser_oxide::v1::GetStorageResponse>, Status> {
+        self.storage().get_storage(request).await
+    }
+
+    async fn set_storage(&self, request: Request<crate::chaser_oxide::v1::SetStorageRequest>) -> Result<Response<crate::chaser_oxide::v1::SetStorageResponse>, Status> {
+        self.storage().set_storage(request).await
+    }
+
+    async fn clear_storage(&self, request: Request<crate::chaser_oxide::v1::ClearStorageRequest>) -> Result<Response<crate::chaser_oxide::v1::ClearStorageResponse>, Status> {
+        self.storage().clear_storage(request).await
+    }
+
+    async fn start_coverage(&self, request: Request<crate::chaser_oxide::v1::StartCoverageRequest>) -> Result<Response<crate::chaser_oxide::v1::StartCoverageResponse>, Status> {
+        self.coverage().start_coverage(request).await
+    }
+
+    async fn stop_coverage(&self, request: Request<crate::chaser_oxide::v1::StopCoverageRequest>) -> Result<Response<crate::chaser_oxide::v1::StopCoverageResponse>, Status> {
+        self.coverage().stop_coverage(request).await
+    }
+
+    type TakeHeapSnapshotStream = HeapSnapshotStream;
+
+    async fn take_heap_snapshot(&self, request: Request<crate::chaser_oxide::v1::TakeHeapSnapshotRequest>) -> Result<Response<Self::TakeHeapSnapshotStream>, Status> {
+        self.profiling().take_heap_snapshot(request).await
+    }
+
+    async fn get_heap_usage(&self, request: Request<crate::chaser_oxide::v1::GetHeapUsageRequest>) -> Result<Response<crate::chaser_oxide::v1::GetHeapUsageResponse>, Status> {
+        self.profiling().get_heap_usage(request).await
+    }
+
+    async fn click_at(&self, request: Request<crate::chaser_oxide::v1::ClickAtRequest>) -> Result<Response<crate::chaser_oxide::v1::ClickAtResponse>, Status> {
+        self.input().click_at(request).await
+    }
+
+    async fn tap(&self, request: Request<crate::chaser_oxide::v1::TapRequest>) -> Result<Response<crate::chaser_oxide::v1::TapResponse>, Status> {
+        self.input().tap(request).await
+    }
+
+    async fn swipe(&self, request: Request<crate::chaser_oxide::v1::SwipeRequest>) -> Result<Response<crate::chaser_oxide::v1::SwipeResponse>, Status> {
+        self.input().swipe(request).await
+    }
+
+    async fn replay_mouse_trace(&self, request: Request<crate::chaser_oxide::v1::ReplayMouseTraceRequest>) -> Result<Response<crate::chaser_oxide::v1::ReplayMouseTraceResponse>, Status> {
+        self.input().replay_mouse_trace(request).await
+    }
+
+    async fn simulate_idle(&self, request: Request<crate::chaser_oxide::v1::SimulateIdleRequest>) -> Result<Response<crate::chaser_oxide::v1::SimulateIdleResponse>, Status> {
+        self.input().simulate_idle(request).await
+    }
+
+    async fn scroll_to_bottom(&self, request: Request<crate::chaser_oxide::v1::ScrollToBottomRequest>) -> Result<Response<crate::chaser_oxide::v1::ScrollToBottomResponse>, Status> {
+        self.input().scroll_to_bottom(request).await
+    }
+
+    async fn page_type(&self, request: Request<crate::chaser_oxide::v1::PageTypeRequest>) -> Result<Response<crate::chaser_oxide::v1::PageTypeResponse>, Status> {
+        self.input().page_type(request).await
+    }
+
+    async fn page_press_key(&self, request: Request<crate::chaser_oxide::v1::PagePressKeyRequest>) -> Result<Response<crate::chaser_oxide::v1::PagePressKeyResponse>, Status> {
+        self.input().page_press_key(request).await
+    }
+
+    async fn reset_page(&self, request: Request<crate::chaser_oxide::v1::ResetPageRequest>) -> Result<Response<crate::chaser_oxide::v1::ResetPageResponse>, Status> {
+        self.reset().reset_page(request).await
+    }
+
+    async fn get_frames(&self, request: Request<crate::chaser_oxide::v1::GetFramesRequest>) -> Result<Response<crate::chaser_oxide::v1::GetFramesResponse>, Status> {
+        self.frames().get_frames(request).await
+    }
+
+    async fn get_console_logs(&self, request: Request<crate::chaser_oxide::v1::GetConsoleLogsRequest>) -> Result<Response<crate::chaser_oxide::v1::GetConsoleLogsResponse>, Status> {
+        self.console().get_console_logs(request).await
+    }
+
+    async fn extract_json(&self, request: Request<crate::chaser_oxide::v1::ExtractJsonRequest>) -> Result<Response<crate::chaser_oxide::v1::ExtractJsonResponse>, Status> {
+        self.extract().extract_json(request).await
+    }
+
+    async fn handle_dialog(&self, request: Request<crate::chaser_oxide::v1::HandleDialogRequest>) -> Result<Response<crate::chaser_oxide::v1::HandleDialogResponse>, Status> {
+        self.dialog().handle_dialog(request).await
+    }
+
+    async fn start_har_recording(&self, request: Request<crate::chaser_oxide::v1::StartHarRecordingRequest>) -> Result<Response<crate::chaser_oxide::v1::StartHarRecordingResponse>, Status> {
+        self.har().start_har_recording(request).await
+    }
+
+    async fn export_har(&self, request: Request<crate::chaser_oxide::v1::ExportHarRequest>) -> Result<Response<crate::chaser_oxide::v1::ExportHarResponse>, Status> {
+        self.har().export_har(request).await
+    }
+
+    async fn inject_style(&self, request: Request<crate::chaser_oxide::v1::InjectStyleRequest>) -> Result<Response<crate::chaser_oxide::v1::InjectStyleResponse>, Status> {
+        self.style().inject_style(request).await
+    }
+
+    async fn remove_style(&self, request: Request<crate::chaser_oxide::v1::RemoveStyleRequest>) -> Result<Response<crate::chaser_oxide::v1::RemoveStyleResponse>, Status> {
+        self.style().remove_style(request).await
+    }
+
     async fn override_permissions(&self, request: Request<crate::chaser_oxide::v1::OverridePermissionsRequest>) -> Result<Response<crate::chaser_oxide::v1::OverridePermissionsResponse>, Status> {
         self.network().override_permissions(request).await
     }
@@ -226,6 +571,18 @@ where
     async fn set_cache_enabled(&self, request: Request<crate::chaser_oxide::v1::SetCacheEnabledRequest>) -> Result<Response<crate::chaser_oxide::v1::SetCacheEnabledResponse>, Status> {
         self.network().set_cache_enabled(request).await
     }
+
+    async fn set_extra_headers(&self, request: Request<crate::chaser_oxide::v1::SetExtraHeadersRequest>) -> Result<Response<crate::chaser_oxide::v1::SetExtraHeadersResponse>, Status> {
+        self.network().set_extra_headers(request).await
+    }
+
+    async fn set_http_credentials(&self, request: Request<crate::chaser_oxide::v1::SetHttpCredentialsRequest>) -> Result<Response<crate::chaser_oxide::v1::SetHttpCredentialsResponse>, Status> {
+        self.network().set_http_credentials(request).await
+    }
+
+    async fn block_urls(&self, request: Request<crate::chaser_oxide::v1::BlockUrlsRequest>) -> Result<Response<crate::chaser_oxide::v1::BlockUrlsResponse>, Status> {
+        self.network().block_urls(request).await
+    }
 }
 
 // ============================================================================
@@ -245,6 +602,24 @@ mod tests {
         assert!(true, "PageService created successfully");
     }
 
+    // 测试：默认导航超时
+    #[tokio::test]
+    async fn test_default_navigation_timeout() {
+        let session_manager = Arc::new(MockSessionManager::new());
+
+        let service = Service::new(Arc::clone(&session_manager));
+        assert_eq!(service.navigation().default_timeout, DEFAULT_NAVIGATION_TIMEOUT_MS);
+
+        let service = Service::new(Arc::clone(&session_manager)).with_default_navigation_timeout(5000);
+        assert_eq!(service.navigation().default_timeout, 5000);
+
+        let service = Service::new(Arc::clone(&session_manager)).with_default_navigation_timeout(0);
+        assert_eq!(service.navigation().default_timeout, DEFAULT_NAVIGATION_TIMEOUT_MS);
+
+        let service = Service::new(session_manager).with_default_navigation_timeout(u64::MAX);
+        assert_eq!(service.navigation().default_timeout, MAX_NAVIGATION_TIMEOUT_MS);
+    }
+
     // 测试：处理器访问
     #[tokio::test]
     async fn test_handler_access() {
@@ -258,8 +633,266 @@ mod tests {
         let _emulation = service.emulation();
         let _network = service.network();
         let _cookies = service.cookies();
+        let _storage = service.storage();
+        let _coverage = service.coverage();
+        let _profiling = service.profiling();
         let _wait = service.wait();
+        let _input = service.input();
+        let _reset = service.reset();
+        let _frames = service.frames();
+        let _dialog = service.dialog();
+        let _har = service.har();
 
         assert!(true);
     }
+
+    // 测试：没有历史记录时后退返回错误
+    #[tokio::test]
+    async fn test_go_back_without_history() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager
+            .create_browser(crate::session::BrowserOptions::default())
+            .await
+            .unwrap();
+        let page = session_manager
+            .create_page(&browser_id, crate::session::PageOptions::default())
+            .await
+            .unwrap();
+        let service = Service::new(session_manager);
+
+        let status = service
+            .go_back(Request::new(crate::chaser_oxide::v1::GoBackRequest {
+                page_id: page.id().to_string(),
+                options: None,
+                delta: 1,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Aborted);
+    }
+
+    // 测试：按 delta 前进后退并返回真实 URL
+    #[tokio::test]
+    async fn test_go_back_and_forward_with_delta() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager
+            .create_browser(crate::session::BrowserOptions::default())
+            .await
+            .unwrap();
+        let page = session_manager
+            .create_page(&browser_id, crate::session::PageOptions::default())
+            .await
+            .unwrap();
+        for url in ["https://a.example", "https://b.example"] {
+            page.navigate(url, crate::session::NavigationOptions::default()).await.unwrap();
+        }
+        let service = Service::new(session_manager);
+
+        let response = service
+            .go_back(Request::new(crate::chaser_oxide::v1::GoBackRequest {
+                page_id: page.id().to_string(),
+                options: None,
+                delta: 2,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        match response.response {
+            Some(crate::chaser_oxide::v1::go_back_response::Response::Result(result)) => {
+                assert_eq!(result.url, "about:blank");
+                assert!(result.is_loaded);
+            }
+            other => panic!("Expected navigation result, got {:?}", other),
+        }
+
+        let response = service
+            .go_forward(Request::new(crate::chaser_oxide::v1::GoForwardRequest {
+                page_id: page.id().to_string(),
+                options: None,
+                delta: 0,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        match response.response {
+            Some(crate::chaser_oxide::v1::go_forward_response::Response::Result(result)) => {
+                assert_eq!(result.url, "https://a.example");
+            }
+            other => panic!("Expected navigation result, got {:?}", other),
+        }
+    }
+
+    // 测试：额外请求头应用到后续导航请求，空表清除
+    #[tokio::test]
+    async fn test_wait_for_navigation_resolves_after_location_change() {
+        use crate::chaser_oxide::v1::{
+            wait_for_navigation_response, EvaluateRequest, WaitForNavigationRequest,
+        };
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager
+            .create_browser(crate::session::BrowserOptions::default())
+            .await
+            .unwrap();
+        let page = session_manager
+            .create_page(&browser_id, crate::session::PageOptions::default())
+            .await
+            .unwrap();
+        let page_id = page.id().to_string();
+        let service = Service::new(session_manager);
+
+        let evaluate = |url: &str| EvaluateRequest {
+            page_id: page_id.clone(),
+            expression: format!("location.href = '{}'", url),
+            ..Default::default()
+        };
+
+        let (waited, _, _) = tokio::join!(
+            service.wait_for_navigation(Request::new(WaitForNavigationRequest {
+                page_id: page_id.clone(),
+                options: None,
+                url_pattern: "https://example.com/checkout*".to_string(),
+            })),
+            async {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+                service.evaluate(Request::new(evaluate("https://example.com/cart"))).await
+            },
+            async {
+                tokio::time::sleep(tokio::time::Duration::from_millis(40)).await;
+                service.evaluate(Request::new(evaluate("https://example.com/checkout?step=1"))).await
+            },
+        );
+
+        match waited.unwrap().into_inner().response {
+            Some(wait_for_navigation_response::Response::Result(result)) => {
+                assert_eq!(result.url, "https://example.com/checkout?step=1");
+            }
+            other => panic!("Expected navigation result, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_extra_headers_applied_to_navigation() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager
+            .create_browser(crate::session::BrowserOptions::default())
+            .await
+            .unwrap();
+        let page = session_manager
+            .create_page(&browser_id, crate::session::PageOptions::default())
+            .await
+            .unwrap();
+        let client = page.get_cdp_client();
+        let mut requests = client.subscribe_events("Network.requestWillBeSent").await.unwrap();
+        let service = Service::new(session_manager);
+
+        let headers: std::collections::HashMap<String, String> =
+            [("X-Auth-Token".to_string(), "secret".to_string())].into_iter().collect();
+        service
+            .set_extra_headers(Request::new(crate::chaser_oxide::v1::SetExtraHeadersRequest {
+                page_id: page.id().to_string(),
+                headers,
+            }))
+            .await
+            .unwrap();
+
+        client.navigate("https://example.com", None).await.unwrap();
+        let event = requests.recv().await.unwrap();
+        assert_eq!(event.params["request"]["headers"]["X-Auth-Token"], "secret");
+
+        service
+            .set_extra_headers(Request::new(crate::chaser_oxide::v1::SetExtraHeadersRequest {
+                page_id: page.id().to_string(),
+                headers: Default::default(),
+            }))
+            .await
+            .unwrap();
+
+        client.navigate("https://example.com/next", None).await.unwrap();
+        let event = requests.recv().await.unwrap();
+        assert!(event.params["request"]["headers"].as_object().unwrap().is_empty());
+    }
+
+    // 测试：禁止的请求头被拒绝
+    #[tokio::test]
+    async fn test_set_extra_headers_rejects_forbidden_header() {
+        let session_manager = Arc::new(MockSessionManager::new());
+        let service = Service::new(session_manager);
+
+        let headers: std::collections::HashMap<String, String> =
+            [("Host".to_string(), "evil.example".to_string())].into_iter().collect();
+        let status = service
+            .set_extra_headers(Request::new(crate::chaser_oxide::v1::SetExtraHeadersRequest {
+                page_id: "any".to_string(),
+                headers,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    // 测试：写入存储后读取，并按存储类型隔离
+    #[tokio::test]
+    async fn test_set_and_get_storage() {
+        use crate::chaser_oxide::v1::{
+            get_storage_response, ClearStorageRequest, GetStorageRequest, SetStorageRequest,
+            StorageItem, StorageType,
+        };
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let browser_id = session_manager
+            .create_browser(crate::session::BrowserOptions::default())
+            .await
+            .unwrap();
+        let page = session_manager
+            .create_page(&browser_id, crate::session::PageOptions::default())
+            .await
+            .unwrap();
+        page.navigate("https://example.com/login", crate::session::NavigationOptions::default())
+            .await
+            .unwrap();
+        let service = Service::new(session_manager);
+
+        service
+            .set_storage(Request::new(SetStorageRequest {
+                page_id: page.id().to_string(),
+                origin: String::new(),
+                storage_type: StorageType::Local as i32,
+                items: vec![
+                    StorageItem { key: "token".to_string(), value: "abc".to_string() },
+                    StorageItem { key: "theme".to_string(), value: "dark".to_string() },
+                ],
+            }))
+            .await
+            .unwrap();
+
+        let get = |storage_type: StorageType| GetStorageRequest {
+            page_id: page.id().to_string(),
+            origin: "https://example.com".to_string(),
+            storage_type: storage_type as i32,
+        };
+
+        let response = service.get_storage(Request::new(get(StorageType::Local))).await.unwrap().into_inner();
+        match response.response {
+            Some(get_storage_response::Response::Items(items)) => {
+                let pairs: Vec<(&str, &str)> = items.items.iter().map(|i| (i.key.as_str(), i.value.as_str())).collect();
+                assert_eq!(pairs, vec![("token", "abc"), ("theme", "dark")]);
+            }
+            other => panic!("Expected storage items, got {:?}", other),
+        }
+
+        let response = service.get_storage(Request::new(get(StorageType::Session))).await.unwrap().into_inner();
+        assert!(matches!(response.response, Some(get_storage_response::Response::Items(items)) if items.items.is_empty()));
+
+        service
+            .clear_storage(Request::new(ClearStorageRequest {
+                page_id: page.id().to_string(),
+                origin: "https://example.com".to_string(),
+                storage_type: StorageType::Local as i32,
+            }))
+            .await
+            .unwrap();
+        let response = service.get_storage(Request::new(get(StorageType::Local))).await.unwrap().into_inner();
+        assert!(matches!(response.response, Some(get_storage_response::Response::Items(items)) if items.items.is_empty()));
+    }
 }
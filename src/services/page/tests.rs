@@ -23,7 +23,7 @@ mod tests {
             ..Default::default()
         };
 
-        let opts = crate::services::page::conversions::proto_to_navigation_options(proto_opts);
+        let opts = crate::services::page::conversions::proto_to_navigation_options(proto_opts, 30000).unwrap();
         assert_eq!(opts.timeout, 30000);
     }
 
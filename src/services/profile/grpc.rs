@@ -10,6 +10,7 @@ use rand::seq::SliceRandom;
 use crate::services::profile::service::ProfileServiceImpl;
 use crate::services::traits as services;
 use crate::services::traits::ProfileService; // Import trait to use methods
+use crate::services::common::request_id;
 
 // Import generated proto types
 use crate::chaser_oxide::v1::{
@@ -18,12 +19,14 @@ use crate::chaser_oxide::v1::{
     apply_profile_response::Response as ApplyProfileResponseEnum,
     get_presets_response::Response as GetPresetsResponseEnum,
     get_active_profile_response::Response as GetActiveProfileResponseEnum,
+    remove_stealth_feature_response::Response as RemoveStealthFeatureResponseEnum,
     create_custom_profile_response::Response as CreateCustomProfileResponseEnum,
     randomize_profile_response::Response as RandomizeProfileResponseEnum,
     CreateProfileRequest, CreateProfileResponse,
     ApplyProfileRequest, ApplyProfileResponse,
     GetPresetsRequest, GetPresetsResponse,
     GetActiveProfileRequest, GetActiveProfileResponse,
+    RemoveStealthFeatureRequest, RemoveStealthFeatureResponse,
     CreateCustomProfileRequest, CreateCustomProfileResponse,
     RandomizeProfileRequest, RandomizeProfileResponse,
     ProfileType as ProtoProfileType,
@@ -40,6 +43,8 @@ use crate::chaser_oxide::v1::{
     ProfileOptions as ProtoProfileOptions,
     CustomProfileOptions as ProtoCustomProfileOptions,
     ApplyProfileResult as ProtoApplyProfileResult,
+    AppliedFeature as ProtoAppliedFeature,
+    RemoveStealthFeatureResult as ProtoRemoveStealthFeatureResult,
     PresetProfiles as ProtoPresetProfiles,
     Error as ProtoError,
     ErrorCode,
@@ -57,21 +62,24 @@ impl ProfileServiceGrpc {
         Self { inner: service }
     }
 
-    /// Convert Error to Status
-    fn error_to_status(error: crate::Error) -> Status {
-        let code = match &error {
+    /// Map Error to a proto error code
+    fn error_code(error: &crate::Error) -> ErrorCode {
+        match error {
             crate::Error::SessionNotFound(_) => ErrorCode::NotFound,
             crate::Error::BrowserNotFound(_) => ErrorCode::NotFound,
             crate::Error::PageNotFound(_) => ErrorCode::NotFound,
             crate::Error::ElementNotFound(_) => ErrorCode::NotFound,
             crate::Error::Configuration(_) => ErrorCode::InvalidArgument,
             _ => ErrorCode::Internal,
-        };
+        }
+    }
 
+    /// Convert Error to Status
+    fn error_to_status(error: crate::Error) -> Status {
         let proto_error = ProtoError {
-            code: code.into(),
+            code: Self::error_code(&error).into(),
             message: error.to_string(),
-            details: std::collections::HashMap::new(),
+            details: request_id::error_details(),
         };
 
         Status::unknown(proto_error.message)
@@ -197,14 +205,18 @@ impl ProfileServiceGrpc {
             inject_canvas: options.inject_canvas,
             inject_audio: options.inject_audio,
             neutralize_utility_world: false, // Not in internal struct
-            use_isolated_world: false,       // Not in internal struct
+            use_isolated_world: options.use_isolated_world,
             randomize_metrics: false,        // Not in internal struct
             prevent_detection: false,        // Not in internal struct
         }
     }
 
     /// Convert proto CustomProfileOptions to internal
-    fn proto_to_custom_options(opts: ProtoCustomProfileOptions, template: i32) -> services::CustomProfileOptions {
+    fn proto_to_custom_options(
+        opts: ProtoCustomProfileOptions,
+        template: i32,
+        profile_options: Option<ProtoProfileOptions>,
+    ) -> services::CustomProfileOptions {
         services::CustomProfileOptions {
             profile_name: String::new(), // Not provided in proto
             template: Self::proto_to_profile_type(template),
@@ -227,6 +239,7 @@ impl ProfileServiceGrpc {
                 inject_webgl: true,
                 inject_canvas: true,
                 inject_audio: true,
+                use_isolated_world: profile_options.is_some_and(|o| o.use_isolated_world),
             },
         }
     }
@@ -258,7 +271,7 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(CreateProfileResponseEnum::Error(ProtoError {
                         code: ErrorCode::Internal.into(),
                         message: e.to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -294,7 +307,7 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(ApplyProfileResponseEnum::Error(ProtoError {
                         code: ErrorCode::Internal.into(),
                         message: error.message().to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -359,7 +372,7 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(GetPresetsResponseEnum::Error(ProtoError {
                         code: ErrorCode::Internal.into(),
                         message: error.message().to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -377,9 +390,17 @@ impl ProtoProfileService for ProfileServiceGrpc {
         let req = request.into_inner();
 
         match self.inner.get_active_profile(&req.page_id).await {
-            Ok(Some(profile)) => {
+            Ok(Some(active)) => {
                 let response = GetActiveProfileResponse {
-                    response: Some(GetActiveProfileResponseEnum::Profile(Self::profile_to_proto(profile))),
+                    response: Some(GetActiveProfileResponseEnum::Profile(Self::profile_to_proto(active.profile))),
+                    applied_features: active
+                        .features
+                        .into_iter()
+                        .map(|feature| ProtoAppliedFeature {
+                            name: feature.name,
+                            script_ids: feature.script_ids,
+                        })
+                        .collect(),
                 };
                 Ok(Response::new(response))
             }
@@ -388,8 +409,9 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(GetActiveProfileResponseEnum::Error(ProtoError {
                         code: ErrorCode::NotFound.into(),
                         message: "No active profile found".to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
                     })),
+                    applied_features: Vec::new(),
                 };
                 Ok(Response::new(response))
             }
@@ -399,7 +421,40 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(GetActiveProfileResponseEnum::Error(ProtoError {
                         code: ErrorCode::Internal.into(),
                         message: error.message().to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
+                    })),
+                    applied_features: Vec::new(),
+                };
+                Ok(Response::new(response))
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn remove_stealth_feature(
+        &self,
+        request: Request<RemoveStealthFeatureRequest>,
+    ) -> Result<Response<RemoveStealthFeatureResponse>, Status> {
+        info!("RemoveStealthFeature request received");
+
+        let req = request.into_inner();
+
+        match self.inner.remove_stealth_feature(&req.page_id, &req.feature).await {
+            Ok(features) => {
+                let response = RemoveStealthFeatureResponse {
+                    response: Some(RemoveStealthFeatureResponseEnum::Result(ProtoRemoveStealthFeatureResult {
+                        page_id: req.page_id,
+                        applied_features: features.features,
+                    })),
+                };
+                Ok(Response::new(response))
+            }
+            Err(e) => {
+                let response = RemoveStealthFeatureResponse {
+                    response: Some(RemoveStealthFeatureResponseEnum::Error(ProtoError {
+                        code: Self::error_code(&e).into(),
+                        message: e.to_string(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -418,7 +473,7 @@ impl ProtoProfileService for ProfileServiceGrpc {
 
         // Convert proto options to internal options
         let opts = req.options.unwrap_or_default();
-        let custom_options = Self::proto_to_custom_options(opts, req.template);
+        let custom_options = Self::proto_to_custom_options(opts, req.template, req.profile_options);
 
         match self.inner.create_custom_profile(custom_options).await {
             Ok(profile) => {
@@ -433,7 +488,7 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(CreateCustomProfileResponseEnum::Error(ProtoError {
                         code: ErrorCode::Internal.into(),
                         message: error.message().to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -528,7 +583,7 @@ impl ProtoProfileService for ProfileServiceGrpc {
                     response: Some(RandomizeProfileResponseEnum::Error(ProtoError {
                         code: ErrorCode::Internal.into(),
                         message: error.message().to_string(),
-                        details: std::collections::HashMap::new(),
+                        details: request_id::error_details(),
                     })),
                 };
                 Ok(Response::new(response))
@@ -103,11 +103,39 @@ impl services::ProfileService for ProfileServiceImpl {
     /// Get active profile
     async fn get_active_profile(
         &self,
-        _page_id: &str,
-    ) -> Result<Option<services::Profile>, Error> {
-        // For now, return None
-        // In a real implementation, we would track active profiles per page
-        Ok(None)
+        page_id: &str,
+    ) -> Result<Option<services::ActiveProfile>, Error> {
+        let Some(mut active) = self.stealth_engine.get_active_profile(page_id).await? else {
+            return Ok(None);
+        };
+
+        let profile = self.profile_manager.get_profile(&active.profile_id).await?;
+
+        // Report features in the order they were applied
+        let features = active
+            .features
+            .features
+            .into_iter()
+            .map(|name| services::AppliedFeature {
+                script_ids: active.script_ids.remove(&name).unwrap_or_default(),
+                name,
+            })
+            .collect();
+
+        Ok(Some(services::ActiveProfile { profile, features }))
+    }
+
+    /// Remove one stealth feature from a page
+    async fn remove_stealth_feature(
+        &self,
+        page_id: &str,
+        feature: &str,
+    ) -> Result<services::AppliedFeatures, Error> {
+        let remaining = self.stealth_engine.remove_feature(page_id, feature).await?;
+
+        Ok(services::AppliedFeatures {
+            features: remaining.features,
+        })
     }
 
     /// Create custom profile
@@ -116,7 +144,8 @@ impl services::ProfileService for ProfileServiceImpl {
         options: services::CustomProfileOptions,
     ) -> Result<services::Profile, Error> {
         let generator = FingerprintGeneratorImpl::new();
-        let fingerprint = generator.generate_custom(&options.options).await?;
+        let mut fingerprint = generator.generate_custom(&options.options).await?;
+        fingerprint.options.use_isolated_world = options.profile_options.use_isolated_world;
 
         let profile = services::Profile {
             profile_id: uuid::Uuid::new_v4().to_string(),
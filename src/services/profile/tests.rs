@@ -72,6 +72,7 @@ mod tests {
                 inject_webgl: true,
                 inject_canvas: true,
                 inject_audio: true,
+                use_isolated_world: false,
             },
         };
 
@@ -88,6 +89,7 @@ mod tests {
             inject_webgl: true,
             inject_canvas: true,
             inject_audio: true,
+            use_isolated_world: false,
         };
 
         assert!(options.inject_navigator);
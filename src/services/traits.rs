@@ -51,6 +51,18 @@ pub struct BrowserVersion {
     pub js_version: String,
 }
 
+impl From<crate::cdp::traits::BrowserVersion> for BrowserVersion {
+    fn from(version: crate::cdp::traits::BrowserVersion) -> Self {
+        Self {
+            protocol_version: version.protocol_version,
+            product: version.product,
+            revision: version.revision,
+            user_agent: version.user_agent,
+            js_version: version.js_version,
+        }
+    }
+}
+
 /// Browser status
 #[derive(Debug, Clone)]
 pub struct BrowserStatus {
@@ -254,6 +266,8 @@ pub enum SelectorType {
     Css,
     XPath,
     Text,
+    /// CSS selector matched inside open shadow roots; `>>>` separates steps
+    Pierce,
 }
 
 /// Element information
@@ -292,7 +306,10 @@ pub trait ProfileService: Send + Sync {
     async fn get_presets(&self) -> Result<Vec<ProfilePreset>, crate::Error>;
 
     /// Get active profile
-    async fn get_active_profile(&self, page_id: &str) -> Result<Option<Profile>, crate::Error>;
+    async fn get_active_profile(&self, page_id: &str) -> Result<Option<ActiveProfile>, crate::Error>;
+
+    /// Remove one stealth feature from a page
+    async fn remove_stealth_feature(&self, page_id: &str, feature: &str) -> Result<AppliedFeatures, crate::Error>;
 
     /// Create custom profile
     async fn create_custom_profile(
@@ -373,6 +390,20 @@ pub struct AppliedFeatures {
     pub features: Vec<String>,
 }
 
+/// Profile applied to a page with the features it injected
+#[derive(Debug, Clone)]
+pub struct ActiveProfile {
+    pub profile: Profile,
+    pub features: Vec<AppliedFeature>,
+}
+
+/// One applied stealth feature and its injected scripts
+#[derive(Debug, Clone)]
+pub struct AppliedFeature {
+    pub name: String,
+    pub script_ids: Vec<String>,
+}
+
 /// Profile preset
 #[derive(Debug, Clone)]
 pub struct ProfilePreset {
@@ -414,6 +445,8 @@ pub struct ProfileOptions {
     pub inject_webgl: bool,
     pub inject_canvas: bool,
     pub inject_audio: bool,
+    /// Run the injected scripts in an isolated world instead of the main world
+    pub use_isolated_world: bool,
 }
 
 // ============================================================================
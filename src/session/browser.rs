@@ -4,11 +4,17 @@
 
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
-use crate::cdp::traits::CdpBrowser;
-use crate::session::traits::{BrowserContext, BrowserOptions, PageContext, PageOptions};
+use crate::cdp::traits::{BrowserVersion, CdpBrowser, CdpClient};
+use crate::session::browser_data::{ClearDataTypes, VisitedOrigins};
+use crate::session::launcher::ChromeProcess;
+use crate::session::navigation_limit::{NavigationLimiter, NavigationStats};
+use crate::session::page::PageContextImpl;
+use crate::session::traits::{BrowserContext, BrowserOptions, DefaultTimeouts, DialogPolicy, PageContext, PageOptions, TargetEvent};
+use crate::session::url_policy::UrlPolicy;
 use crate::Error;
 
 /// Browser context implementation
@@ -19,24 +25,377 @@ pub struct BrowserContextImpl {
     cdp_browser: Arc<dyn CdpBrowser>,
     pages: Arc<RwLock<HashMap<String, Arc<dyn PageContext>>>>,
     is_active: Arc<RwLock<bool>>,
+    target_events: tokio::sync::broadcast::Sender<TargetEvent>,
+    /// `targetInfo` of every `Target.targetInfoChanged`, for the pages to pick theirs from
+    target_info: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// Chrome process owned by this browser, when launched rather than attached to
+    process: std::sync::Mutex<Option<ChromeProcess>>,
+    /// Shared by all pages so a burst of navigations cannot overwhelm the renderer
+    navigations: Arc<NavigationLimiter>,
+    /// Set once target discovery runs on the browser-level connection
+    target_watch_started: AtomicBool,
+    /// Navigation rules enforced on every page's document requests
+    url_policy: Arc<UrlPolicy>,
+    /// Origins whose storage `clear_data` clears
+    visited_origins: VisitedOrigins,
 }
 
 impl BrowserContextImpl {
     /// Create a new browser context
     pub fn new(options: BrowserOptions, cdp_browser: Arc<dyn CdpBrowser>) -> Self {
+        let navigations = Arc::new(NavigationLimiter::new(options.max_concurrent_navigations));
         Self {
             id: Uuid::new_v4().to_string(),
             options,
             cdp_browser,
             pages: Arc::new(RwLock::new(HashMap::new())),
             is_active: Arc::new(RwLock::new(true)),
+            target_events: tokio::sync::broadcast::channel(64).0,
+            target_info: tokio::sync::broadcast::channel(256).0,
+            process: std::sync::Mutex::new(None),
+            navigations,
+            target_watch_started: AtomicBool::new(false),
+            url_policy: Arc::default(),
+            visited_origins: VisitedOrigins::default(),
         }
     }
 
+    /// Attach a launched Chrome process, stopped when the browser closes
+    pub fn with_process(self, process: ChromeProcess) -> Self {
+        *self.process.lock().unwrap_or_else(|e| e.into_inner()) = Some(process);
+        self
+    }
+
+    /// Enforce `policy` on the document requests of every page, popups included
+    pub fn with_url_policy(mut self, policy: Arc<UrlPolicy>) -> Self {
+        self.url_policy = policy;
+        self
+    }
+
+    /// Popup handling state shared with the target discovery task
+    fn targets(&self) -> TargetWatcher {
+        TargetWatcher {
+            browser_id: self.id.clone(),
+            cdp_browser: Arc::clone(&self.cdp_browser),
+            pages: Arc::clone(&self.pages),
+            target_events: self.target_events.clone(),
+            target_info: self.target_info.clone(),
+            user_agent: self.options.user_agent.clone(),
+            block: self.options.block_popups,
+            dialog_policy: self.options.dialog_policy,
+            navigations: Arc::clone(&self.navigations),
+            default_timeouts: self.options.default_timeouts,
+            console_buffer_size: self.options.console_buffer_size,
+            max_redirect_hops: self.options.max_redirect_hops,
+            url_policy: Arc::clone(&self.url_policy),
+            visited_origins: self.visited_origins.clone(),
+        }
+    }
+
+    /// Start watching the browser's targets for popups, once per browser
+    async fn watch_targets(&self) -> Result<(), Error> {
+        if self.target_watch_started.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        if let Err(e) = self.targets().watch().await {
+            self.target_watch_started.store(false, Ordering::SeqCst);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Find a page and the target ID it is keyed by
+    fn find_page(&self, page_id: &str) -> Result<(String, Arc<dyn PageContext>), Error> {
+        self.pages
+            .read()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
+            .iter()
+            .find(|(_, page)| page.id() == page_id)
+            .map(|(target_id, page)| (target_id.clone(), Arc::clone(page)))
+            .ok_or_else(|| Error::page_not_found(page_id))
+    }
+
     /// Get browser options
     pub fn options(&self) -> &BrowserOptions {
         &self.options
     }
+
+    /// Connection of an open page, or of a blank target opened for the purpose
+    ///
+    /// Returns the target ID of the opened target, to be closed once done.
+    async fn page_client(&self) -> Result<(Arc<dyn CdpClient>, Option<String>), Error> {
+        let open = self
+            .pages
+            .read()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
+            .values()
+            .find(|page| page.is_active())
+            .map(|page| page.get_cdp_client());
+        if let Some(client) = open {
+            return Ok((client, None));
+        }
+
+        let ws_url = self.cdp_browser.create_target("about:blank").await?;
+        let target_id = ws_url.rsplit('/').next().unwrap_or_default().to_string();
+        let client = self.cdp_browser.create_client(&ws_url).await?;
+        Ok((client, Some(target_id)))
+    }
+}
+
+/// Record the origins a new page's frames load, for clearing its data later
+///
+/// Failure leaves later origins of the page uncleared, so it is logged but
+/// does not fail page creation.
+async fn track_origins(page: &PageContextImpl, origins: &VisitedOrigins) {
+    if let Err(e) = origins.track(page.get_cdp_client().as_ref()).await {
+        tracing::warn!("Failed to track visited origins of page {}: {}", page.id(), e);
+    }
+}
+
+/// Override the User-Agent of a target's session, if one is configured
+async fn apply_user_agent(client: &dyn CdpClient, user_agent: Option<&str>) -> Result<(), Error> {
+    if let Some(user_agent) = user_agent.filter(|ua| !ua.is_empty()) {
+        client.enable_domain("Network").await?;
+        client
+            .call_method("Network.setUserAgentOverride", serde_json::json!({ "userAgent": user_agent }))
+            .await?;
+
+        tracing::debug!("User-Agent set at page creation: {}", user_agent);
+    }
+
+    Ok(())
+}
+
+/// Check a BCP-47 language tag such as `ja`, `ja-JP` or `zh-Hant-TW`
+///
+/// Only the shape is checked: a 2-3 or 5-8 letter primary language followed by
+/// subtags of 1-8 letters or digits.
+fn validate_language_tag(tag: &str) -> Result<(), Error> {
+    let invalid = || Error::configuration(format!("Invalid language tag: '{}'", tag));
+    let mut subtags = tag.split('-');
+
+    let primary = subtags.next().unwrap_or_default();
+    if !matches!(primary.len(), 2..=3 | 5..=8) || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(invalid());
+    }
+    if subtags.any(|subtag| !(1..=8).contains(&subtag.len()) || !subtag.chars().all(|c| c.is_ascii_alphanumeric())) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// Check an `Accept-Language` value: language tags (or `*`) with optional `;q=` weights
+fn validate_accept_language(value: &str) -> Result<(), Error> {
+    for entry in value.split(',') {
+        let mut parts = entry.trim().split(';');
+        let tag = parts.next().unwrap_or_default().trim();
+        if tag != "*" {
+            validate_language_tag(tag)?;
+        }
+        for param in parts {
+            let weight = param.trim().strip_prefix("q=").and_then(|q| q.parse::<f32>().ok());
+            if !weight.is_some_and(|q| (0.0..=1.0).contains(&q)) {
+                return Err(Error::configuration(format!("Invalid Accept-Language weight: '{}'", entry.trim())));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check a new page's locale options before its target is created
+fn validate_locale(options: &PageOptions) -> Result<(), Error> {
+    if let Some(locale) = &options.locale {
+        validate_language_tag(locale)?;
+    }
+    if let Some(accept_language) = &options.accept_language {
+        validate_accept_language(accept_language)?;
+    }
+    Ok(())
+}
+
+/// Override a new page's locale and `Accept-Language`, if configured
+async fn apply_locale(client: &dyn CdpClient, options: &PageOptions) -> Result<(), Error> {
+    if let Some(locale) = &options.locale {
+        client
+            .call_method("Emulation.setLocaleOverride", serde_json::json!({ "locale": locale }))
+            .await?;
+    }
+    if let Some(accept_language) = options.accept_language.as_ref().or(options.locale.as_ref()) {
+        let headers = HashMap::from([("Accept-Language".to_string(), accept_language.clone())]);
+        client.set_extra_http_headers(&headers).await?;
+    }
+
+    Ok(())
+}
+
+/// Fail a new page's document requests that the URL policy rejects
+///
+/// An unprotected page would let clients reach URLs the policy forbids, so
+/// failure closes the page and fails its creation.
+async fn enforce_url_policy(page: &PageContextImpl) -> Result<(), Error> {
+    if let Err(e) = page.enforce_url_policy().await {
+        if let Err(close_error) = page.close().await {
+            tracing::warn!("Failed to close unprotected page {}: {}", page.id(), close_error);
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Answer a new page's dialogs per the browser's dialog policy
+///
+/// Failure only leaves dialogs unanswered, so it does not fail page creation.
+async fn start_dialog_handler(page: &PageContextImpl, policy: DialogPolicy) {
+    if let Err(e) = page.start_dialog_handler(policy).await {
+        tracing::warn!("Failed to start dialog handler for page {}: {}", page.id(), e);
+    }
+}
+
+/// Buffer a new page's console messages for `GetConsoleLogs`
+///
+/// Failure only leaves the buffer empty, so it does not fail page creation.
+async fn start_console_log(page: &PageContextImpl) {
+    if let Err(e) = page.start_console_log().await {
+        tracing::warn!("Failed to buffer console messages of page {}: {}", page.id(), e);
+    }
+}
+
+/// Flag a new page as crashed and announce it when its renderer crashes
+///
+/// Without the listener a crashed page is only noticed when commands stop
+/// answering, so failure is logged but does not fail page creation.
+async fn start_crash_handler(
+    page: &PageContextImpl,
+    target_id: &str,
+    target_events: tokio::sync::broadcast::Sender<TargetEvent>,
+) {
+    if let Err(e) = page.start_crash_handler(target_id, target_events).await {
+        tracing::warn!("Failed to watch for crashes of page {}: {}", page.id(), e);
+    }
+}
+
+/// Watches a browser's targets on its browser-level connection and registers
+/// the popups its pages open (`window.open`, `target=_blank`) as pages of the browser
+///
+/// Discovery (`Target.setDiscoverTargets`) reports targets without attaching
+/// to them, so no target is ever left paused. A popup is a page target whose
+/// `openerId` is one of the browser's pages; with `block` set it is closed instead.
+/// The same discovery feeds `Target.targetInfoChanged` to the pages' URL and title.
+#[derive(Clone)]
+struct TargetWatcher {
+    browser_id: String,
+    cdp_browser: Arc<dyn CdpBrowser>,
+    pages: Arc<RwLock<HashMap<String, Arc<dyn PageContext>>>>,
+    target_events: tokio::sync::broadcast::Sender<TargetEvent>,
+    target_info: tokio::sync::broadcast::Sender<serde_json::Value>,
+    user_agent: Option<String>,
+    block: bool,
+    dialog_policy: DialogPolicy,
+    navigations: Arc<NavigationLimiter>,
+    default_timeouts: DefaultTimeouts,
+    console_buffer_size: usize,
+    max_redirect_hops: usize,
+    url_policy: Arc<UrlPolicy>,
+    visited_origins: VisitedOrigins,
+}
+
+impl TargetWatcher {
+    /// Enable target discovery and handle new and changed targets in the background
+    async fn watch(&self) -> Result<(), Error> {
+        let client = self.cdp_browser.browser_client().await?;
+        let mut created = client.subscribe_events("Target.targetCreated").await?;
+        let mut changed = client.subscribe_events("Target.targetInfoChanged").await?;
+        client
+            .call_method("Target.setDiscoverTargets", serde_json::json!({ "discover": true }))
+            .await?;
+
+        let target_info = self.target_info.clone();
+        tokio::spawn(async move {
+            while let Some(event) = changed.recv().await {
+                // No page listening is fine
+                let _ = target_info.send(event.params["targetInfo"].clone());
+            }
+        });
+
+        let watcher = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = created.recv().await {
+                if let Err(e) = watcher.on_created(client.as_ref(), &event.params).await {
+                    tracing::warn!("Failed to handle popup target: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Whether `target_id` is registered as a page of the browser
+    fn is_page(&self, target_id: &str) -> Result<bool, Error> {
+        Ok(self
+            .pages
+            .read()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
+            .contains_key(target_id))
+    }
+
+    /// Handle `Target.targetCreated`, registering or closing popups of the browser's pages
+    async fn on_created(&self, browser: &dyn CdpClient, params: &serde_json::Value) -> Result<(), Error> {
+        let field = |name: &str| params["targetInfo"][name].as_str().unwrap_or_default().to_string();
+        let (target_id, opener_id) = (field("targetId"), field("openerId"));
+        if field("type") != "page" || target_id.is_empty() || opener_id.is_empty() {
+            return Ok(());
+        }
+        if !self.is_page(&opener_id)? || self.is_page(&target_id)? {
+            return Ok(());
+        }
+
+        if self.block {
+            tracing::info!("Blocking popup {} in browser {}", target_id, self.browser_id);
+            browser
+                .call_method("Target.closeTarget", serde_json::json!({ "targetId": target_id }))
+                .await?;
+            return Ok(());
+        }
+
+        // Popups are not paused, so one may already have loaded a URL the policy rejects
+        if let Err(e) = self.url_policy.check(&field("url")).await {
+            tracing::warn!("Closing popup {} in browser {}: {}", target_id, self.browser_id, e);
+            browser
+                .call_method("Target.closeTarget", serde_json::json!({ "targetId": target_id }))
+                .await?;
+            return Ok(());
+        }
+
+        self.visited_origins.record(&field("url"));
+        let client = self.cdp_browser.create_client(&self.cdp_browser.page_ws_url(&target_id)).await?;
+        apply_user_agent(client.as_ref(), self.user_agent.as_deref()).await?;
+
+        let page = PageContextImpl::new(self.browser_id.clone(), PageOptions::default(), client)
+            .with_navigation_limiter(Arc::clone(&self.navigations))
+            .with_default_timeouts(self.default_timeouts)
+            .with_console_buffer_size(self.console_buffer_size)
+            .with_max_redirect_hops(self.max_redirect_hops)
+            .with_url_policy(Arc::clone(&self.url_policy));
+        enforce_url_policy(&page).await?;
+        track_origins(&page, &self.visited_origins).await;
+        start_dialog_handler(&page, self.dialog_policy).await;
+        start_console_log(&page).await;
+        page.track_target_info(&target_id, self.target_info.subscribe());
+        start_crash_handler(&page, &target_id, self.target_events.clone()).await;
+        let page: Arc<dyn PageContext> = Arc::new(page);
+        self.pages
+            .write()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
+            .insert(target_id.clone(), Arc::clone(&page));
+
+        tracing::info!("Attached to popup {} of {} as page {}", target_id, opener_id, page.id());
+        let _ = self.target_events.send(TargetEvent::Created {
+            target_id,
+            page_id: page.id().to_string(),
+        });
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -55,10 +414,19 @@ impl BrowserContext for BrowserContextImpl {
             return Err(Error::browser_not_found(&self.id));
         }
 
-        // Determine the URL for the new page
+        validate_locale(&options)?;
+
+        // Attach to (or block) popups opened by the browser's pages
+        if let Err(e) = self.watch_targets().await {
+            tracing::warn!("Failed to watch for popups of browser {}: {}", self.id, e);
+        }
+
+        // Determine the URL for the new page; it loads before the page can intercept requests
         let default_url = options.default_url.as_deref().unwrap_or("about:blank");
+        self.url_policy.check(default_url).await?;
 
         // Create a new target and get its WebSocket URL using CDP Target.createTarget
+        self.visited_origins.record(default_url);
         let ws_url = self.cdp_browser.create_target(default_url).await?;
 
         // Create CDP client with the WebSocket URL
@@ -66,21 +434,8 @@ impl BrowserContext for BrowserContextImpl {
 
         // Set User-Agent at CDP level if provided in browser options
         // This must be done BEFORE any navigation to ensure correct UA is used
-        if let Some(user_agent) = &self.options.user_agent {
-            if !user_agent.is_empty() {
-                // Enable Network domain first
-                cdp_client.enable_domain("Network").await?;
-
-                // Set User-Agent override using Network.setUserAgentOverride
-                let params = serde_json::json!({
-                    "userAgent": user_agent
-                });
-
-                cdp_client.call_method("Network.setUserAgentOverride", params).await?;
-
-                tracing::debug!("User-Agent set at page creation: {}", user_agent);
-            }
-        }
+        apply_user_agent(cdp_client.as_ref(), self.options.user_agent.as_deref()).await?;
+        apply_locale(cdp_client.as_ref(), &options).await?;
 
         // Extract target_id from ws_url for use as page key
         let target_id = ws_url
@@ -89,11 +444,19 @@ impl BrowserContext for BrowserContextImpl {
             .unwrap_or("unknown");
 
         // Create page context
-        let page = Arc::new(crate::session::page::PageContextImpl::new(
-            self.id.clone(),
-            options,
-            cdp_client,
-        ));
+        let page = PageContextImpl::new(self.id.clone(), options, cdp_client)
+            .with_navigation_limiter(Arc::clone(&self.navigations))
+            .with_default_timeouts(self.options.default_timeouts)
+            .with_console_buffer_size(self.options.console_buffer_size)
+            .with_max_redirect_hops(self.options.max_redirect_hops)
+            .with_url_policy(Arc::clone(&self.url_policy));
+        enforce_url_policy(&page).await?;
+        track_origins(&page, &self.visited_origins).await;
+        start_dialog_handler(&page, self.options.dialog_policy).await;
+        start_console_log(&page).await;
+        page.track_target_info(target_id, self.target_info.subscribe());
+        start_crash_handler(&page, target_id, self.target_events.clone()).await;
+        let page = Arc::new(page);
 
         // Store page using target_id as the key
         self.pages
@@ -101,6 +464,12 @@ impl BrowserContext for BrowserContextImpl {
             .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
             .insert(target_id.to_string(), page.clone());
 
+        // No subscribers is fine
+        let _ = self.target_events.send(TargetEvent::Created {
+            target_id: target_id.to_string(),
+            page_id: page.id().to_string(),
+        });
+
         Ok(page)
     }
 
@@ -120,9 +489,71 @@ impl BrowserContext for BrowserContextImpl {
         Ok(pages.values().cloned().collect())
     }
 
+    async fn target_id(&self, page_id: &str) -> Result<String, Error> {
+        self.find_page(page_id).map(|(target_id, _)| target_id)
+    }
+
+    async fn activate_page(&self, page_id: &str) -> Result<(), Error> {
+        let (target_id, page) = self.find_page(page_id)?;
+
+        page.get_cdp_client()
+            .call_method("Target.activateTarget", serde_json::json!({ "targetId": target_id }))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn close_page(&self, page_id: &str) -> Result<(), Error> {
+        // Closed pages stay registered (inactive) like pages closed directly
+        let (target_id, page) = self.find_page(page_id)?;
+        page.close().await?;
+
+        let _ = self.target_events.send(TargetEvent::Destroyed {
+            target_id,
+            page_id: page_id.to_string(),
+        });
+
+        Ok(())
+    }
+
+    fn subscribe_targets(&self) -> tokio::sync::broadcast::Receiver<TargetEvent> {
+        self.target_events.subscribe()
+    }
+
+    async fn clear_data(&self, types: ClearDataTypes) -> Result<Vec<String>, Error> {
+        let browser = self.cdp_browser.browser_client().await?;
+        let origins = self.visited_origins.snapshot();
+        if !types.cache {
+            return crate::session::browser_data::clear_browser_data(browser.as_ref(), None, &origins, types).await;
+        }
+
+        // The HTTP cache can only be cleared through a page
+        let (page, opened) = self.page_client().await?;
+        let result =
+            crate::session::browser_data::clear_browser_data(browser.as_ref(), Some(page.as_ref()), &origins, types)
+                .await;
+        if let Some(target_id) = opened {
+            let _ = page.connection().close().await;
+            if let Err(e) = browser
+                .call_method("Target.closeTarget", serde_json::json!({ "targetId": target_id }))
+                .await
+            {
+                tracing::warn!("Failed to close target {} used to clear the cache: {}", target_id, e);
+            }
+        }
+        result
+    }
+
     async fn close(&self) -> Result<(), Error> {
         tracing::info!("BrowserContext::close: Closing browser {}", self.id);
 
+        // Clear data while a page can still carry the cache command
+        if self.options.clear_data_on_close {
+            if let Err(e) = self.clear_data(ClearDataTypes::all()).await {
+                tracing::warn!("BrowserContext::close: Failed to clear data of browser {}: {}", self.id, e);
+            }
+        }
+
         // Close all pages - collect pages first to avoid holding lock across await
         let pages_to_close: Vec<Arc<dyn PageContext>> = self
             .pages
@@ -175,6 +606,18 @@ impl BrowserContext for BrowserContextImpl {
             }
         }
 
+        // Stop a launched Chrome; dropping it releases its user data dir
+        let process = self
+            .process
+            .lock()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
+            .take();
+        if let Some(process) = process {
+            if let Err(e) = process.shutdown().await {
+                tracing::warn!("BrowserContext::close: Failed to stop Chrome process: {}", e);
+            }
+        }
+
         // Mark as inactive
         *self
             .is_active
@@ -191,6 +634,26 @@ impl BrowserContext for BrowserContextImpl {
             .map(|active| *active)
             .unwrap_or(false)
     }
+
+    fn navigation_stats(&self) -> NavigationStats {
+        self.navigations.stats()
+    }
+
+    fn default_timeouts(&self) -> DefaultTimeouts {
+        self.options.default_timeouts
+    }
+
+    fn user_agent(&self) -> Option<String> {
+        self.options.user_agent.clone().filter(|ua| !ua.is_empty())
+    }
+
+    async fn get_version(&self) -> Result<BrowserVersion, Error> {
+        if !self.is_active() {
+            return Err(Error::browser_not_found(&self.id));
+        }
+
+        self.cdp_browser.get_version().await
+    }
 }
 
 #[cfg(test)]
@@ -220,6 +683,404 @@ mod tests {
         assert!(page.is_active());
     }
 
+    #[tokio::test]
+    async fn test_new_tabs_are_independent_targets() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser);
+        let mut events = browser.subscribe_targets();
+
+        let first = browser.create_page(PageOptions::default()).await.unwrap();
+        let second = browser.create_page(PageOptions::default()).await.unwrap();
+
+        let first_target = browser.target_id(first.id()).await.unwrap();
+        let second_target = browser.target_id(second.id()).await.unwrap();
+        assert_ne!(first_target, second_target);
+        assert_eq!(
+            events.recv().await.unwrap(),
+            TargetEvent::Created { target_id: first_target.clone(), page_id: first.id().to_string() }
+        );
+        assert_eq!(
+            events.recv().await.unwrap(),
+            TargetEvent::Created { target_id: second_target.clone(), page_id: second.id().to_string() }
+        );
+
+        // Each tab has its own CDP session
+        first
+            .navigate("https://example.com/first", crate::session::NavigationOptions::default())
+            .await
+            .unwrap();
+        let url = |page: Arc<dyn PageContext>| async move {
+            match page.evaluate("window.location.href", false).await.unwrap() {
+                crate::session::EvaluationResult::String(url) => url,
+                other => panic!("Expected string, got {:?}", other),
+            }
+        };
+        assert_eq!(url(first.clone()).await, "https://example.com/first");
+        assert_ne!(url(second.clone()).await, "https://example.com/first");
+
+        browser.activate_page(second.id()).await.unwrap();
+
+        browser.close_page(first.id()).await.unwrap();
+        assert_eq!(
+            events.recv().await.unwrap(),
+            TargetEvent::Destroyed { target_id: first_target, page_id: first.id().to_string() }
+        );
+        assert!(!first.is_active());
+        assert!(second.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_renderer_crash_flags_page() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser.clone());
+        let page = browser.create_page(PageOptions::default()).await.unwrap();
+        let target_id = browser.target_id(page.id()).await.unwrap();
+        let mut events = browser.subscribe_targets();
+        assert!(!page.is_crashed());
+
+        let (_, client) = cdp_browser.clients().await.remove(0);
+        client.emit_event("Inspector.targetCrashed", serde_json::json!({})).await;
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event, TargetEvent::Crashed { target_id, page_id: page.id().to_string() });
+        assert!(page.is_crashed());
+
+        // A second crash event is not announced again
+        client.emit_event("Target.targetCrashed", serde_json::json!({})).await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_target_info_changes_reach_matching_page() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser.clone());
+        let first = browser.create_page(PageOptions::default()).await.unwrap();
+        let second = browser.create_page(PageOptions::default()).await.unwrap();
+        let first_target = browser.target_id(first.id()).await.unwrap();
+
+        // Discovery runs once, on the browser-level connection only
+        let browser_client = cdp_browser.browser_target();
+        assert_eq!(browser_client.recorded_calls("Target.setDiscoverTargets").await.len(), 1);
+        for (_, client) in cdp_browser.clients().await {
+            assert!(client.recorded_calls("Target.setDiscoverTargets").await.is_empty());
+        }
+
+        browser_client
+            .emit_event(
+                "Target.targetInfoChanged",
+                serde_json::json!({
+                    "targetInfo": { "targetId": first_target, "url": "https://example.com/next", "title": "Next" }
+                }),
+            )
+            .await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert_eq!(first.activity().url, "https://example.com/next");
+        assert_eq!(first.activity().title, "Next");
+        assert_ne!(second.activity().url, "https://example.com/next");
+    }
+
+    #[tokio::test]
+    async fn test_lost_connection_flags_page_crashed() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser.clone());
+        let page = browser.create_page(PageOptions::default()).await.unwrap();
+        let target_id = browser.target_id(page.id()).await.unwrap();
+        let mut events = browser.subscribe_targets();
+        let (_, client) = cdp_browser.clients().await.remove(0);
+
+        // A detach for another reason is not a crash
+        client.emit_event("Inspector.detached", serde_json::json!({ "reason": "target_closed" })).await;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!page.is_crashed());
+
+        client.emit_event("Inspector.detached", serde_json::json!({ "reason": "connection_lost" })).await;
+        let event = tokio::time::timeout(std::time::Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event, TargetEvent::Crashed { target_id, page_id: page.id().to_string() });
+        assert!(page.is_crashed());
+    }
+
+    #[tokio::test]
+    async fn test_navigations_beyond_limit_wait_for_a_slot() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let options = BrowserOptions { max_concurrent_navigations: 1, ..Default::default() };
+        let browser = BrowserContextImpl::new(options, cdp_browser.clone());
+        let first = browser.create_page(PageOptions::default()).await.unwrap();
+        let second = browser.create_page(PageOptions::default()).await.unwrap();
+        let delay = std::time::Duration::from_millis(200);
+        for (_, client) in cdp_browser.clients().await {
+            client.delay_method("Page.navigate", delay).await;
+        }
+
+        let navigate = |page: Arc<dyn PageContext>, url: &'static str| {
+            tokio::spawn(async move {
+                page.navigate(url, crate::session::NavigationOptions::default()).await.unwrap();
+                std::time::Instant::now()
+            })
+        };
+        let started = std::time::Instant::now();
+        let first_done = navigate(first, "https://example.com/first");
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        let second_done = navigate(second, "https://example.com/second");
+
+        let (first_done, second_done) = (first_done.await.unwrap(), second_done.await.unwrap());
+        assert!(second_done >= first_done);
+        assert!(second_done - started >= delay * 2);
+
+        let stats = browser.navigation_stats();
+        assert_eq!(stats.navigations, 2);
+        assert_eq!(stats.queued, 1);
+        assert!(stats.total_wait_ms >= 100);
+    }
+
+    #[tokio::test]
+    async fn test_page_inherits_browser_navigation_timeout() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let timeouts = DefaultTimeouts { navigation_ms: 100, command_ms: 0, wait_ms: 0 };
+        let options = BrowserOptions { default_timeouts: timeouts, ..Default::default() };
+        let browser = BrowserContextImpl::new(options, cdp_browser);
+        let page = browser.create_page(PageOptions::default()).await.unwrap();
+        assert_eq!(browser.default_timeouts(), timeouts);
+        assert_eq!(page.default_timeouts(), timeouts);
+
+        // No timeout in the request: the browser's 100ms applies instead of the 30s default
+        let options = crate::session::NavigationOptions { timeout: 0, ..Default::default() };
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            page.wait_for_navigation(None, options),
+        )
+        .await
+        .expect("inherited timeout should fire long before the server default");
+        match result {
+            Err(Error::Timeout(message)) => assert!(message.contains("100ms"), "{}", message),
+            other => panic!("Expected timeout, got {:?}", other),
+        }
+    }
+
+    /// `Target.targetCreated` for a target opened by `opener_id`
+    fn target_created(target_id: &str, target_type: &str, opener_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "targetInfo": {
+                "targetId": target_id,
+                "type": target_type,
+                "openerId": opener_id,
+                "url": "https://example.com/popup",
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_popup_is_registered_as_page() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser.clone());
+        let mut events = browser.subscribe_targets();
+
+        let opener = browser.create_page(PageOptions::default()).await.unwrap();
+        let opener_target = browser.target_id(opener.id()).await.unwrap();
+        assert!(matches!(events.recv().await.unwrap(), TargetEvent::Created { .. }));
+
+        // Discovery runs once, on the browser-level connection, and attaches to nothing
+        browser.create_page(PageOptions::default()).await.unwrap();
+        assert!(matches!(events.recv().await.unwrap(), TargetEvent::Created { .. }));
+        let browser_client = cdp_browser.browser_target();
+        assert_eq!(browser_client.recorded_calls("Target.setDiscoverTargets").await.len(), 1);
+        for (_, client) in cdp_browser.clients().await {
+            assert!(client.recorded_calls("Target.setAutoAttach").await.is_empty());
+        }
+
+        // Workers and targets opened outside the browser's pages are left alone
+        browser_client
+            .emit_event("Target.targetCreated", target_created("worker-target", "service_worker", &opener_target))
+            .await;
+        browser_client
+            .emit_event("Target.targetCreated", target_created("foreign-target", "page", "foreign-opener"))
+            .await;
+        browser_client
+            .emit_event("Target.targetCreated", target_created("popup-target", "page", &opener_target))
+            .await;
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let popup_id = match event {
+            TargetEvent::Created { target_id, page_id } => {
+                assert_eq!(target_id, "popup-target");
+                page_id
+            }
+            other => panic!("Expected popup creation, got {:?}", other),
+        };
+
+        assert_ne!(popup_id, opener.id());
+        assert_eq!(browser.get_pages().await.unwrap().len(), 3);
+        assert_eq!(browser.target_id(&popup_id).await.unwrap(), "popup-target");
+
+        // The popup gets its own connection
+        let clients = cdp_browser.clients().await;
+        assert_eq!(clients.len(), 3);
+        assert!(clients[2].0.ends_with("/devtools/page/popup-target"));
+    }
+
+    #[tokio::test]
+    async fn test_page_locale_sets_accept_language_on_navigation() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser.clone());
+        let options = PageOptions {
+            locale: Some("ja-JP".to_string()),
+            ..Default::default()
+        };
+        let page = browser.create_page(options).await.unwrap();
+
+        let (_, client) = cdp_browser.clients().await.remove(0);
+        let locale = client.recorded_calls("Emulation.setLocaleOverride").await;
+        assert_eq!(locale[0]["locale"], "ja-JP");
+
+        let mut requests = client.subscribe_events("Network.requestWillBeSent").await.unwrap();
+        page.navigate("https://example.jp/", crate::session::NavigationOptions::default())
+            .await
+            .unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.params["request"]["headers"]["Accept-Language"], "ja-JP");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_language_tags_are_rejected() {
+        let browser = BrowserContextImpl::new(
+            BrowserOptions::default(),
+            Arc::new(crate::cdp::mock::MockCdpBrowser::new()),
+        );
+
+        for (locale, accept_language) in [
+            (Some("ja_JP"), None),
+            (Some("j"), None),
+            (None, Some("en-US,en;q=2")),
+            (None, Some("en-US;")),
+        ] {
+            let options = PageOptions {
+                locale: locale.map(str::to_string),
+                accept_language: accept_language.map(str::to_string),
+                ..Default::default()
+            };
+            assert!(matches!(browser.create_page(options).await, Err(Error::Configuration(_))));
+        }
+
+        assert!(validate_accept_language("de-CH, de;q=0.9, en;q=0.8, *;q=0.5").is_ok());
+        assert!(validate_language_tag("zh-Hant-TW").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_blocked_popup_is_closed() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let options = BrowserOptions {
+            block_popups: true,
+            ..Default::default()
+        };
+        let browser = BrowserContextImpl::new(options, cdp_browser.clone());
+        let opener = browser.create_page(PageOptions::default()).await.unwrap();
+        let opener_target = browser.target_id(opener.id()).await.unwrap();
+
+        let browser_client = cdp_browser.browser_target();
+        browser_client
+            .emit_event("Target.targetCreated", target_created("popup-target", "page", &opener_target))
+            .await;
+
+        let mut closed = Vec::new();
+        for _ in 0..50 {
+            closed = browser_client.recorded_calls("Target.closeTarget").await;
+            if !closed.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(closed[0]["targetId"], "popup-target");
+        assert_eq!(browser.get_pages().await.unwrap().len(), 1);
+    }
+
+    /// Wait for the first `Page.handleJavaScriptDialog` call on `client`
+    async fn dialog_answer(client: &crate::cdp::mock::MockCdpClient) -> Option<serde_json::Value> {
+        for _ in 0..50 {
+            if let Some(answer) = client.recorded_calls("Page.handleJavaScriptDialog").await.pop() {
+                return Some(answer);
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        None
+    }
+
+    #[tokio::test]
+    async fn test_alert_dismissed_under_dismiss_all() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let options = BrowserOptions {
+            dialog_policy: DialogPolicy::DismissAll,
+            ..Default::default()
+        };
+        let browser = BrowserContextImpl::new(options, cdp_browser.clone());
+        browser.create_page(PageOptions::default()).await.unwrap();
+
+        let (_, client) = cdp_browser.clients().await.remove(0);
+        client
+            .emit_event(
+                "Page.javascriptDialogOpening",
+                serde_json::json!({ "type": "alert", "message": "Hello", "url": "https://example.com/" }),
+            )
+            .await;
+
+        let answer = dialog_answer(&client).await.expect("alert was not answered");
+        assert_eq!(answer["accept"], false);
+    }
+
+    #[tokio::test]
+    async fn test_manual_dialog_left_open() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let options = BrowserOptions {
+            dialog_policy: DialogPolicy::Manual,
+            ..Default::default()
+        };
+        let browser = BrowserContextImpl::new(options, cdp_browser.clone());
+        browser.create_page(PageOptions::default()).await.unwrap();
+
+        let (_, client) = cdp_browser.clients().await.remove(0);
+        client
+            .emit_event(
+                "Page.javascriptDialogOpening",
+                serde_json::json!({ "type": "confirm", "message": "Leave?", "url": "https://example.com/" }),
+            )
+            .await;
+
+        // Waits for HandleDialog, however long it takes
+        assert!(dialog_answer(&client).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dialog_handler_stops_on_page_close() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let options = BrowserOptions {
+            dialog_policy: DialogPolicy::AcceptAll,
+            ..Default::default()
+        };
+        let browser = BrowserContextImpl::new(options, cdp_browser.clone());
+        let page = browser.create_page(PageOptions::default()).await.unwrap();
+        let (_, client) = cdp_browser.clients().await.remove(0);
+
+        browser.close_page(page.id()).await.unwrap();
+        client
+            .emit_event(
+                "Page.javascriptDialogOpening",
+                serde_json::json!({ "type": "alert", "message": "Bye", "url": "https://example.com/" }),
+            )
+            .await;
+
+        assert!(dialog_answer(&client).await.is_none());
+    }
+
     #[tokio::test]
     async fn test_browser_get_pages() {
         let options = BrowserOptions::default();
@@ -251,4 +1112,64 @@ mod tests {
         let result = browser.create_page(PageOptions::default()).await;
         assert!(result.is_err());
     }
+    #[tokio::test]
+    async fn test_close_clears_data_when_configured() {
+        for clear_data_on_close in [true, false] {
+            let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+            let options = BrowserOptions { clear_data_on_close, ..Default::default() };
+            let browser = BrowserContextImpl::new(options, cdp_browser.clone());
+            browser
+                .create_page(PageOptions { default_url: Some("https://example.com/login".to_string()), ..Default::default() })
+                .await
+                .unwrap();
+            let (_, client) = cdp_browser.clients().await.remove(0);
+
+            browser.close().await.unwrap();
+
+            let browser_target = cdp_browser.browser_target();
+            let cleared = browser_target.recorded_calls("Storage.clearDataForOrigin").await;
+            let cookies_cleared = browser_target.recorded_calls("Storage.clearCookies").await;
+            let cache_cleared = client.recorded_calls("Network.clearBrowserCache").await;
+            if clear_data_on_close {
+                assert_eq!(cleared.len(), 1);
+                assert_eq!(cleared[0]["origin"], "https://example.com");
+                assert_eq!(cookies_cleared.len(), 1);
+                assert_eq!(cache_cleared.len(), 1);
+            } else {
+                assert!(cleared.is_empty());
+                assert!(cookies_cleared.is_empty());
+                assert!(cache_cleared.is_empty());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clear_data_without_open_pages_covers_visited_origins() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        let browser = BrowserContextImpl::new(BrowserOptions::default(), cdp_browser.clone());
+        let page = browser.create_page(PageOptions::default()).await.unwrap();
+        let (_, client) = cdp_browser.clients().await.remove(0);
+        // Origins are recorded from every frame the page loads, iframes included
+        for url in ["https://shop.example/cart", "https://cdn.example:8443/frame", "about:blank"] {
+            client
+                .emit_event("Page.frameNavigated", serde_json::json!({ "frame": { "id": "f", "url": url } }))
+                .await;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        browser.close_page(page.id()).await.unwrap();
+
+        let origins = browser.clear_data(ClearDataTypes::all()).await.unwrap();
+
+        assert_eq!(origins, vec!["https://cdn.example:8443".to_string(), "https://shop.example".to_string()]);
+        let browser_target = cdp_browser.browser_target();
+        assert_eq!(browser_target.recorded_calls("Storage.clearDataForOrigin").await.len(), 2);
+        assert_eq!(browser_target.recorded_calls("Storage.clearCookies").await.len(), 1);
+        // The cache is cleared through a blank target opened for it and closed afterwards
+        let clients = cdp_browser.clients().await;
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[1].1.recorded_calls("Network.clearBrowserCache").await.len(), 1);
+        let closed = browser_target.recorded_calls("Target.closeTarget").await;
+        assert_eq!(closed.len(), 1);
+        assert!(clients[1].0.ends_with(closed[0]["targetId"].as_str().unwrap()));
+    }
 }
@@ -0,0 +1,141 @@
+//! Clearing a browser's stored data
+//!
+//! Cookies and the HTTP cache are cleared browser-wide. Origin-scoped storage
+//! (localStorage, IndexedDB, service workers) is cleared with
+//! `Storage.clearDataForOrigin` for every origin the browser's pages have
+//! loaded a document from, recorded by [`VisitedOrigins`] as frames navigate.
+//!
+//! Cookie and storage commands go through the browser-level connection, so
+//! data can be cleared with no page open. Only the HTTP cache needs a page
+//! connection (`Network.clearBrowserCache`).
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+use url::Url;
+
+use crate::cdp::traits::CdpClient;
+use crate::Error;
+
+/// Kinds of data to clear
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClearDataTypes {
+    pub cookies: bool,
+    pub cache: bool,
+    pub local_storage: bool,
+    pub indexed_db: bool,
+    pub service_workers: bool,
+}
+
+impl ClearDataTypes {
+    /// Every kind of data
+    pub fn all() -> Self {
+        Self {
+            cookies: true,
+            cache: true,
+            local_storage: true,
+            indexed_db: true,
+            service_workers: true,
+        }
+    }
+
+    /// `storageTypes` for `Storage.clearDataForOrigin`, empty if no origin storage is cleared
+    fn origin_storage_types(&self) -> String {
+        [
+            (self.local_storage, "local_storage"),
+            (self.indexed_db, "indexeddb"),
+            (self.service_workers, "service_workers"),
+        ]
+        .iter()
+        .filter(|(selected, _)| *selected)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// Origins a browser's pages have loaded documents from, in any frame
+///
+/// Kept for the browser's lifetime, so origins of closed pages are cleared too.
+#[derive(Debug, Clone, Default)]
+pub struct VisitedOrigins {
+    origins: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl VisitedOrigins {
+    /// Record the origin of `url`; opaque origins (`about:blank`, `data:`) are skipped
+    pub fn record(&self, url: &str) {
+        let Ok(url) = Url::parse(url) else {
+            return;
+        };
+        let origin = url.origin();
+        if origin.is_tuple() {
+            self.origins
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(origin.ascii_serialization());
+        }
+    }
+
+    /// Record the origin of every frame `client`'s page navigates
+    ///
+    /// The background task ends when the connection closes.
+    pub async fn track(&self, client: &dyn CdpClient) -> Result<(), Error> {
+        let mut navigated = client.subscribe_events("Page.frameNavigated").await?;
+        client.enable_domain("Page").await?;
+
+        let origins = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = navigated.recv().await {
+                if let Some(url) = event.params["frame"]["url"].as_str() {
+                    origins.record(url);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Origins recorded so far
+    pub fn snapshot(&self) -> BTreeSet<String> {
+        self.origins.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Clear the selected data of a browser
+///
+/// `browser` is the browser-level connection. `page` is any of the browser's
+/// page connections, needed only to clear the HTTP cache; without one the
+/// cache is left as is. Returns the origins whose storage was cleared.
+pub async fn clear_browser_data(
+    browser: &dyn CdpClient,
+    page: Option<&dyn CdpClient>,
+    origins: &BTreeSet<String>,
+    types: ClearDataTypes,
+) -> Result<Vec<String>, Error> {
+    let storage_types = types.origin_storage_types();
+    if !storage_types.is_empty() {
+        for origin in origins {
+            browser
+                .call_method(
+                    "Storage.clearDataForOrigin",
+                    serde_json::json!({ "origin": origin, "storageTypes": storage_types }),
+                )
+                .await?;
+        }
+    }
+    if types.cache {
+        match page {
+            Some(page) => {
+                page.call_method("Network.clearBrowserCache", serde_json::json!({})).await?;
+            }
+            None => tracing::warn!("No page connection to clear the HTTP cache through"),
+        }
+    }
+    if types.cookies {
+        browser.call_method("Storage.clearCookies", serde_json::json!({})).await?;
+    }
+
+    tracing::info!("Cleared browser data ({:?}) for {} origin(s)", types, origins.len());
+    Ok(if storage_types.is_empty() { Vec::new() } else { origins.iter().cloned().collect() })
+}
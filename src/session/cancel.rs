@@ -0,0 +1,97 @@
+//! Cancellation of long page operations
+//!
+//! A [`CancelToken`] fires when the client that asked for an operation goes
+//! away. Long operations race their work against [`CancelToken::cancelled`]
+//! with `tokio::select!` and undo what the browser would otherwise keep
+//! doing: a page load is stopped, an in-page waiter is torn down.
+
+use tokio::sync::watch;
+
+use crate::cdp::traits::CdpClient;
+use crate::Error;
+
+/// Signal that the caller of an operation has gone away
+#[derive(Debug, Clone)]
+pub struct CancelToken {
+    cancelled: watch::Receiver<bool>,
+}
+
+impl CancelToken {
+    /// Create a token and the sender that cancels it
+    ///
+    /// Dropping the sender without sending `true` leaves the token uncancelled.
+    pub fn new() -> (watch::Sender<bool>, Self) {
+        let (sender, cancelled) = watch::channel(false);
+        (sender, Self { cancelled })
+    }
+
+    /// A token that is never cancelled
+    pub fn never() -> Self {
+        Self::new().1
+    }
+
+    /// Whether the operation has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Resolve once the operation is cancelled; never resolves otherwise
+    pub async fn cancelled(&self) {
+        let mut cancelled = self.cancelled.clone();
+        let sender_dropped = cancelled.wait_for(|cancelled| *cancelled).await.is_err();
+        if sender_dropped {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// Error returned by operations that stopped because they were cancelled
+pub fn cancelled_error(operation: &str) -> Error {
+    Error::cancelled(format!("{} cancelled by the client", operation))
+}
+
+/// Run a page load, stopping it in the browser if `cancel` fires first
+pub async fn stop_loading_on_cancel<T>(
+    client: &dyn CdpClient,
+    cancel: &CancelToken,
+    load: impl std::future::Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    tokio::select! {
+        result = load => result,
+        _ = cancel.cancelled() => {
+            if let Err(e) = client.call_method("Page.stopLoading", serde_json::json!({})).await {
+                tracing::warn!("Failed to stop cancelled page load: {}", e);
+            }
+            Err(cancelled_error("Navigation"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::mock::MockCdpClient;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancelled_load_is_stopped() {
+        let client = MockCdpClient::new();
+        let (sender, cancel) = CancelToken::new();
+
+        let load = stop_loading_on_cancel(&client, &cancel, async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(())
+        });
+        sender.send_replace(true);
+        let err = tokio::time::timeout(Duration::from_secs(1), load).await.unwrap().unwrap_err();
+
+        assert!(matches!(err, Error::Cancelled(_)));
+        assert!(cancel.is_cancelled());
+        assert_eq!(client.recorded_calls("Page.stopLoading").await.len(), 1);
+
+        // A dropped sender does not cancel
+        let (sender, cancel) = CancelToken::new();
+        drop(sender);
+        assert!(tokio::time::timeout(Duration::from_millis(50), cancel.cancelled()).await.is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Per-page console message buffer
+//!
+//! Keeps the most recent `Runtime.consoleAPICalled` messages of a page in a
+//! bounded ring buffer, so clients without an event stream can still read
+//! what a page logged after an action. Entries are numbered in order; reading
+//! from a cursor returns the entries logged since.
+
+use std::collections::VecDeque;
+
+/// Default number of console messages kept per page
+pub const DEFAULT_CONSOLE_BUFFER_SIZE: usize = 1000;
+
+/// A buffered console message
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleEntry {
+    /// Position in the page's console output, starting at 1
+    pub seq: u64,
+    /// Console call type (`log`, `info`, `warning`, `error`, `debug`, ...)
+    pub level: String,
+    /// Arguments rendered as text
+    pub args: Vec<String>,
+    /// Milliseconds since the epoch
+    pub timestamp: f64,
+    /// Script URL of the calling frame
+    pub url: String,
+    /// Zero-based line of the calling frame
+    pub line: i64,
+    /// Zero-based column of the calling frame
+    pub column: i64,
+}
+
+/// Console messages read from a cursor
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsoleLogs {
+    /// Entries after the cursor, oldest first
+    pub entries: Vec<ConsoleEntry>,
+    /// Cursor that continues after the last entry
+    pub next_cursor: u64,
+    /// Messages after the cursor that were evicted before being read
+    pub dropped: u64,
+}
+
+/// Ring buffer of a page's most recent console messages
+#[derive(Debug)]
+pub struct ConsoleBuffer {
+    entries: VecDeque<ConsoleEntry>,
+    capacity: usize,
+    /// Sequence number of the last recorded message
+    last_seq: u64,
+}
+
+impl ConsoleBuffer {
+    /// Keep at most `capacity` messages; `0` keeps none
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(DEFAULT_CONSOLE_BUFFER_SIZE)),
+            capacity,
+            last_seq: 0,
+        }
+    }
+
+    /// Record a `Runtime.consoleAPICalled` event, evicting the oldest message when full
+    pub fn record(&mut self, params: &serde_json::Value) {
+        self.last_seq += 1;
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        let frame = params.pointer("/stackTrace/callFrames/0");
+        let frame_field = |name: &str| frame.and_then(|f| f.get(name));
+        self.entries.push_back(ConsoleEntry {
+            seq: self.last_seq,
+            level: params["type"].as_str().unwrap_or("log").to_string(),
+            args: params["args"].as_array().into_iter().flatten().map(arg_text).collect(),
+            timestamp: params["timestamp"].as_f64().unwrap_or_default(),
+            url: frame_field("url").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            line: frame_field("lineNumber").and_then(|v| v.as_i64()).unwrap_or_default(),
+            column: frame_field("columnNumber").and_then(|v| v.as_i64()).unwrap_or_default(),
+        });
+    }
+
+    /// Messages logged after `cursor`; `0` returns everything still buffered
+    pub fn since(&self, cursor: u64) -> ConsoleLogs {
+        let oldest = self.entries.front().map_or(self.last_seq + 1, |entry| entry.seq);
+        ConsoleLogs {
+            entries: self.entries.iter().filter(|entry| entry.seq > cursor).cloned().collect(),
+            next_cursor: self.last_seq.max(cursor),
+            dropped: oldest.saturating_sub(cursor + 1),
+        }
+    }
+}
+
+impl Default for ConsoleBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONSOLE_BUFFER_SIZE)
+    }
+}
+
+/// Render a console argument (a CDP `RemoteObject`) as text
+///
+/// Strings are kept as-is, other primitives use their JSON form and objects
+/// fall back to the description Chrome provides.
+fn arg_text(arg: &serde_json::Value) -> String {
+    match arg.get("value") {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+        None => arg["unserializableValue"]
+            .as_str()
+            .or_else(|| arg["description"].as_str())
+            .or_else(|| arg["type"].as_str())
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(text: &str) -> serde_json::Value {
+        serde_json::json!({ "type": "log", "args": [{ "type": "string", "value": text }], "timestamp": 1.0 })
+    }
+
+    #[test]
+    fn test_buffer_evicts_oldest_and_reports_dropped() {
+        let mut buffer = ConsoleBuffer::new(2);
+        for text in ["a", "b", "c"] {
+            buffer.record(&message(text));
+        }
+
+        let logs = buffer.since(0);
+        let texts: Vec<_> = logs.entries.iter().map(|e| e.args[0].as_str()).collect();
+        assert_eq!(texts, vec!["b", "c"]);
+        assert_eq!(logs.next_cursor, 3);
+        assert_eq!(logs.dropped, 1);
+
+        let logs = buffer.since(2);
+        assert_eq!(logs.entries.len(), 1);
+        assert_eq!(logs.dropped, 0);
+        assert!(buffer.since(logs.next_cursor).entries.is_empty());
+    }
+}
@@ -52,6 +52,10 @@ impl ElementRef for ElementRefImpl {
         &self.page_id
     }
 
+    fn get_cdp_client(&self) -> Arc<dyn CdpClient> {
+        Arc::clone(&self.cdp_client)
+    }
+
     async fn get_text(&self) -> Result<String, Error> {
         let result = self
             .execute_dom_command(
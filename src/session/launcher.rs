@@ -0,0 +1,366 @@
+//! Chrome process launcher
+//!
+//! Starts a local Chrome with its own user data directory and discovers the
+//! DevTools endpoint from the `DevToolsActivePort` file Chrome writes there.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::session::traits::BrowserOptions;
+use crate::Error;
+
+/// How long to wait for Chrome to report its DevTools port
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Poll interval while waiting for `DevToolsActivePort`
+const LAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// User data directories held by running browsers of this process
+fn dirs_in_use() -> &'static Mutex<HashSet<PathBuf>> {
+    static DIRS: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+    DIRS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Exclusive claim on a user data directory, released on drop
+///
+/// Chrome refuses to share a profile between processes (the second one hands
+/// its window to the first and exits), so two browsers must never use the same dir.
+#[derive(Debug)]
+pub struct UserDataDirLock {
+    path: PathBuf,
+}
+
+impl UserDataDirLock {
+    /// Claim `path`, failing if another browser already uses it
+    pub fn acquire(path: &Path) -> Result<Self, Error> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut dirs = dirs_in_use()
+            .lock()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?;
+
+        if !dirs.insert(path.clone()) {
+            return Err(Error::configuration(format!(
+                "User data dir {} is already in use by another browser",
+                path.display()
+            )));
+        }
+
+        Ok(Self { path })
+    }
+}
+
+impl Drop for UserDataDirLock {
+    fn drop(&mut self) {
+        if let Ok(mut dirs) = dirs_in_use().lock() {
+            dirs.remove(&self.path);
+        }
+    }
+}
+
+/// Launch flags for running Chrome in a container: no GPU, no sandbox (needs
+/// user namespaces) and no reliance on a small `/dev/shm`
+///
+/// Only used when the server is configured for containers; otherwise Chrome
+/// keeps its sandbox.
+pub const CONTAINER_LAUNCH_ARGS: &[&str] = &["--disable-gpu", "--no-sandbox", "--disable-dev-shm-usage"];
+
+/// Flags the server relies on to reach the browser; launch arguments cannot override them
+const REQUIRED_FLAGS: &[&str] = &["--remote-debugging-port", "--user-data-dir"];
+
+/// Prefix of a launch argument that removes an earlier flag (`!--disable-gpu`)
+pub const REMOVE_FLAG_PREFIX: &str = "!";
+
+/// Name of a command line flag (`--window-size=800,600` -> `--window-size`), `None` for non-flags
+fn flag_name(arg: &str) -> Option<&str> {
+    arg.starts_with('-').then(|| arg.split('=').next().unwrap_or(arg))
+}
+
+/// Command line arguments for launching Chrome with `options`
+///
+/// `options.launch_args` then `options.args` are applied over the flags derived
+/// from `options`; a later flag replaces an earlier one of the same name, so
+/// request args win over the configured defaults. An argument of the form
+/// `!--flag` removes `--flag` instead, whatever its value.
+pub fn launch_args(options: &BrowserOptions, user_data_dir: &Path) -> Vec<String> {
+    let mut args = vec![
+        "--remote-debugging-port=0".to_string(),
+        format!("--user-data-dir={}", user_data_dir.display()),
+    ];
+
+    let mut overridable = vec![
+        "--no-first-run".to_string(),
+        "--no-default-browser-check".to_string(),
+        format!("--window-size={},{}", options.window_width, options.window_height),
+    ];
+    if options.headless {
+        overridable.push("--headless=new".to_string());
+    }
+    if let Some(proxy) = &options.proxy {
+        overridable.push(format!("--proxy-server={}", proxy));
+    }
+    overridable.extend(options.launch_args.iter().cloned());
+    overridable.extend(options.args.iter().cloned());
+
+    for arg in overridable {
+        if let Some(removed) = arg.strip_prefix(REMOVE_FLAG_PREFIX) {
+            match flag_name(removed) {
+                Some(name) if !REQUIRED_FLAGS.contains(&name) => {
+                    args.retain(|existing| flag_name(existing) != Some(name));
+                }
+                _ => tracing::warn!("Ignoring launch argument {}: only optional flags can be removed", arg),
+            }
+            continue;
+        }
+
+        let name = flag_name(&arg);
+        if name.is_some_and(|name| REQUIRED_FLAGS.contains(&name)) {
+            tracing::warn!("Ignoring launch argument {}: the server sets it", arg);
+            continue;
+        }
+        match args.iter_mut().find(|existing| name.is_some() && flag_name(existing) == name) {
+            Some(existing) => *existing = arg,
+            None => args.push(arg),
+        }
+    }
+    args.push("about:blank".to_string());
+
+    args
+}
+
+/// A Chrome process started by the server
+///
+/// The process is killed on drop; a temporary user data dir is removed with it.
+#[derive(Debug)]
+pub struct ChromeProcess {
+    child: tokio::sync::Mutex<tokio::process::Child>,
+    endpoint: String,
+    user_data_dir: PathBuf,
+    temporary: bool,
+    _lock: UserDataDirLock,
+}
+
+impl ChromeProcess {
+    /// Launch `executable` with `options`
+    ///
+    /// Uses `options.user_data_dir` when set, otherwise a fresh temporary directory.
+    pub async fn launch(executable: &str, options: &BrowserOptions) -> Result<Self, Error> {
+        let (user_data_dir, temporary) = match &options.user_data_dir {
+            Some(dir) => (PathBuf::from(dir), false),
+            None => (std::env::temp_dir().join(format!("chaser-oxide-{}", Uuid::new_v4())), true),
+        };
+        std::fs::create_dir_all(&user_data_dir).map_err(|e| {
+            Error::configuration(format!(
+                "Failed to create user data dir {}: {}",
+                user_data_dir.display(),
+                e
+            ))
+        })?;
+        let lock = UserDataDirLock::acquire(&user_data_dir)?;
+
+        // A stale port file from a previous run would point at a dead browser
+        let port_file = user_data_dir.join("DevToolsActivePort");
+        let _ = std::fs::remove_file(&port_file);
+
+        let child = tokio::process::Command::new(executable)
+            .args(launch_args(options, &user_data_dir))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| Error::internal(format!("Failed to launch {}: {}", executable, e)))?;
+
+        let mut process = Self {
+            child: tokio::sync::Mutex::new(child),
+            endpoint: String::new(),
+            user_data_dir,
+            temporary,
+            _lock: lock,
+        };
+        process.endpoint = process.wait_for_endpoint(&port_file).await?;
+        tracing::info!("Launched Chrome at {} (user data dir {})", process.endpoint, process.user_data_dir.display());
+
+        Ok(process)
+    }
+
+    /// DevTools endpoint of the launched browser (e.g. `ws://127.0.0.1:38211`)
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// User data directory the browser runs with
+    pub fn user_data_dir(&self) -> &Path {
+        &self.user_data_dir
+    }
+
+    /// Kill the browser process and wait for it to exit
+    pub async fn shutdown(&self) -> Result<(), Error> {
+        self.child
+            .lock()
+            .await
+            .kill()
+            .await
+            .map_err(|e| Error::internal(format!("Failed to stop Chrome: {}", e)))
+    }
+
+    /// Wait until Chrome writes its DevTools port, failing if it exits first
+    async fn wait_for_endpoint(&self, port_file: &Path) -> Result<String, Error> {
+        let deadline = tokio::time::Instant::now() + LAUNCH_TIMEOUT;
+
+        loop {
+            if let Ok(content) = std::fs::read_to_string(port_file) {
+                if let Some(port) = content.lines().next().and_then(|line| line.trim().parse::<u16>().ok()) {
+                    return Ok(format!("ws://127.0.0.1:{}", port));
+                }
+            }
+
+            if let Ok(Some(status)) = self.child.lock().await.try_wait() {
+                return Err(Error::internal(format!("Chrome exited during startup: {}", status)));
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Error::timeout(format!(
+                    "Chrome did not report a DevTools port within {}s",
+                    LAUNCH_TIMEOUT.as_secs()
+                )));
+            }
+
+            tokio::time::sleep(LAUNCH_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Drop for ChromeProcess {
+    fn drop(&mut self) {
+        let _ = self.child.get_mut().start_kill();
+        if self.temporary {
+            let _ = std::fs::remove_dir_all(&self.user_data_dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_args_pass_user_data_dir() {
+        let options = BrowserOptions {
+            user_data_dir: Some("/tmp/chaser-profile".to_string()),
+            args: vec!["--lang=de-DE".to_string()],
+            ..Default::default()
+        };
+
+        let args = launch_args(&options, Path::new("/tmp/chaser-profile"));
+
+        assert!(args.contains(&"--user-data-dir=/tmp/chaser-profile".to_string()));
+        assert!(args.contains(&"--remote-debugging-port=0".to_string()));
+        assert!(args.contains(&"--headless=new".to_string()));
+        assert!(args.contains(&"--lang=de-DE".to_string()));
+    }
+
+    #[test]
+    fn test_launch_args_apply_defaults_and_let_request_args_win() {
+        let options = BrowserOptions {
+            launch_args: CONTAINER_LAUNCH_ARGS.iter().map(|arg| arg.to_string()).collect(),
+            args: vec![
+                "--window-size=800,600".to_string(),
+                "--headless=old".to_string(),
+                "--disable-gpu".to_string(),
+                "--remote-debugging-port=9222".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let args = launch_args(&options, Path::new("/tmp/chaser-profile"));
+
+        for default in CONTAINER_LAUNCH_ARGS {
+            assert_eq!(args.iter().filter(|arg| arg == default).count(), 1, "{} missing or duplicated", default);
+        }
+        assert!(args.contains(&"--window-size=800,600".to_string()));
+        assert!(!args.contains(&"--window-size=1920,1080".to_string()));
+        assert!(args.contains(&"--headless=old".to_string()));
+        assert!(!args.contains(&"--headless=new".to_string()));
+        assert!(args.contains(&"--remote-debugging-port=0".to_string()));
+        assert!(!args.contains(&"--remote-debugging-port=9222".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("about:blank"));
+    }
+
+    #[test]
+    fn test_launch_args_keep_sandbox_unless_configured_and_allow_removing_flags() {
+        let args = launch_args(&BrowserOptions::default(), Path::new("/tmp/chaser-profile"));
+        assert!(!args.contains(&"--no-sandbox".to_string()));
+
+        let options = BrowserOptions {
+            launch_args: CONTAINER_LAUNCH_ARGS.iter().map(|arg| arg.to_string()).collect(),
+            args: vec![
+                "!--no-sandbox".to_string(),
+                "!--window-size".to_string(),
+                "!--user-data-dir".to_string(),
+                "!--remote-debugging-port".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let args = launch_args(&options, Path::new("/tmp/chaser-profile"));
+
+        assert!(!args.contains(&"--no-sandbox".to_string()));
+        assert!(!args.iter().any(|arg| arg.starts_with("--window-size")));
+        assert!(!args.iter().any(|arg| arg.starts_with('!')));
+        assert!(args.contains(&"--disable-gpu".to_string()));
+        assert!(args.contains(&"--user-data-dir=/tmp/chaser-profile".to_string()));
+        assert!(args.contains(&"--remote-debugging-port=0".to_string()));
+    }
+
+    #[test]
+    fn test_user_data_dir_cannot_be_shared() {
+        let dir = std::env::temp_dir().join(format!("chaser-oxide-test-{}", Uuid::new_v4()));
+
+        let first = UserDataDirLock::acquire(&dir).unwrap();
+        assert!(matches!(UserDataDirLock::acquire(&dir), Err(Error::Configuration(_))));
+
+        drop(first);
+        assert!(UserDataDirLock::acquire(&dir).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_launch_passes_user_data_dir_to_chrome() {
+        // A stand-in "browser" that records its arguments and reports a port like Chrome does
+        let dir = std::env::temp_dir().join(format!("chaser-oxide-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("fake-chrome.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nfor arg in \"$@\"; do case \"$arg\" in --user-data-dir=*) profile=\"${arg#--user-data-dir=}\";; esac; done\n\
+             echo \"$@\" > \"$profile/args\"\nprintf '9333\\n/devtools/browser/fake\\n' > \"$profile/DevToolsActivePort\"\nexec sleep 30\n",
+        )
+        .unwrap();
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let profile = dir.join("profile");
+        let options = BrowserOptions {
+            user_data_dir: Some(profile.display().to_string()),
+            ..Default::default()
+        };
+
+        let process = ChromeProcess::launch(script.to_str().unwrap(), &options).await.unwrap();
+
+        assert_eq!(process.endpoint(), "ws://127.0.0.1:9333");
+        let args = std::fs::read_to_string(profile.join("args")).unwrap();
+        assert!(args.contains(&format!("--user-data-dir={}", profile.display())));
+        assert!(matches!(
+            ChromeProcess::launch(script.to_str().unwrap(), &options).await,
+            Err(Error::Configuration(_))
+        ));
+
+        process.shutdown().await.unwrap();
+        drop(process);
+        // A persistent profile survives the browser
+        assert!(profile.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
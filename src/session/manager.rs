@@ -4,18 +4,56 @@
 
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, Weak};
+use std::time::Duration;
 
+use crate::cdp::pool::CdpPoolConfig;
 use crate::cdp::traits::CdpBrowser;
+use crate::session::launcher::ChromeProcess;
 use crate::session::traits::{
-    BrowserContext, BrowserOptions, PageContext, PageOptions, SessionManager,
+    BrowserContext, BrowserOptions, PageContext, PageOptions, PageStateStore, SessionManager,
 };
+use crate::session::url_policy::UrlPolicy;
+use crate::stealth::traits::StealthEngine;
 use crate::Error;
 
+/// Health check a new browser must pass before `create_browser` returns it
+#[derive(Debug, Clone)]
+pub struct BrowserHealthCheck {
+    /// Checks run after the first one fails
+    pub retries: u32,
+    /// Timeout of a single `Browser.getVersion` check
+    pub timeout: Duration,
+    /// Delay before the first retry; doubled before each further retry
+    pub initial_backoff: Duration,
+}
+
+impl Default for BrowserHealthCheck {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            timeout: Duration::from_secs(5),
+            initial_backoff: Duration::from_millis(250),
+        }
+    }
+}
+
 /// Session manager implementation
 pub struct SessionManagerImpl {
     browsers: Arc<RwLock<HashMap<String, Arc<dyn BrowserContext>>>>,
     cdp_browser_factory: Arc<dyn Fn() -> Result<Arc<dyn CdpBrowser>, Error> + Send + Sync>,
+    /// Connection settings for browsers launched by the manager
+    cdp_pool_config: CdpPoolConfig,
+    /// Check run on every new browser
+    health_check: BrowserHealthCheck,
+    /// Navigation rules every page enforces on its document requests
+    url_policy: Arc<UrlPolicy>,
+    /// Stealth engine whose per-page state is dropped when a page closes
+    ///
+    /// Weak because the engine's script injector holds the session manager.
+    stealth_engine: RwLock<Option<Weak<dyn StealthEngine>>>,
+    /// Service-side page state dropped when a page closes
+    page_state: RwLock<Vec<Arc<dyn PageStateStore>>>,
 }
 
 impl SessionManagerImpl {
@@ -27,6 +65,131 @@ impl SessionManagerImpl {
         Self {
             browsers: Arc::new(RwLock::new(HashMap::new())),
             cdp_browser_factory: Arc::new(factory),
+            cdp_pool_config: CdpPoolConfig::default(),
+            health_check: BrowserHealthCheck::default(),
+            url_policy: Arc::default(),
+            stealth_engine: RwLock::new(None),
+            page_state: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Use `config` for the CDP connections of launched browsers
+    ///
+    /// Attached browsers are configured by the factory instead.
+    pub fn with_cdp_pool_config(mut self, config: CdpPoolConfig) -> Self {
+        self.cdp_pool_config = config;
+        self
+    }
+
+    /// Have every page of new browsers enforce `policy` on its document requests
+    pub fn with_url_policy(mut self, policy: UrlPolicy) -> Self {
+        self.url_policy = Arc::new(policy);
+        self
+    }
+
+    /// Verify new browsers with `check` before handing out their id
+    pub fn with_browser_health_check(mut self, check: BrowserHealthCheck) -> Self {
+        self.health_check = check;
+        self
+    }
+
+    /// Wait until `browser` answers `Browser.getVersion`, retrying with backoff
+    ///
+    /// # Errors
+    /// Returns `Unavailable` with the last failure once all retries are used up.
+    async fn verify_health(&self, browser: &dyn BrowserContext) -> Result<(), Error> {
+        let check = &self.health_check;
+        let mut backoff = check.initial_backoff;
+        let mut attempt = 1;
+
+        loop {
+            let failure = match tokio::time::timeout(check.timeout, browser.get_version()).await {
+                Ok(Ok(_)) => return Ok(()),
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => format!("no response within {}ms", check.timeout.as_millis()),
+            };
+            tracing::warn!(
+                "Health check {}/{} of browser {} failed: {}",
+                attempt,
+                check.retries + 1,
+                browser.id(),
+                failure
+            );
+
+            if attempt > check.retries {
+                return Err(Error::unavailable(format!(
+                    "Browser failed its health check after {} attempt(s): {}",
+                    attempt, failure
+                )));
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+            attempt += 1;
+        }
+    }
+
+    /// Drop `engine`'s injected scripts and active profile of every page the manager closes
+    ///
+    /// Set after construction since the engine itself depends on the manager.
+    pub fn attach_stealth_engine(&self, engine: &Arc<dyn StealthEngine>) {
+        if let Ok(mut slot) = self.stealth_engine.write() {
+            *slot = Some(Arc::downgrade(engine));
+        }
+    }
+
+    /// Release `store`'s entries of every page the manager closes
+    pub fn attach_page_state(&self, store: Arc<dyn PageStateStore>) {
+        if let Ok(mut stores) = self.page_state.write() {
+            stores.push(store);
+        }
+    }
+
+    /// Find a page by ID, whether or not it crashed
+    async fn find_page(&self, page_id: &str) -> Result<Arc<dyn PageContext>, Error> {
+        // Search through all browsers to find the page
+        // Collect browser Arcs first to avoid holding lock across await
+        let browser_refs: Vec<Arc<dyn BrowserContext>> = self
+            .browsers
+            .read()
+            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
+            .values()
+            .cloned()
+            .collect();
+        // Lock guard dropped here
+
+        for browser in browser_refs {
+            if let Ok(pages) = browser.get_pages().await {
+                for page in pages {
+                    if page.id() == page_id {
+                        return Ok(page);
+                    }
+                }
+            }
+        }
+
+        Err(Error::page_not_found(page_id))
+    }
+
+    /// Remove the stealth and service state of a page that is about to close
+    ///
+    /// Runs while the page still exists so its init scripts can be unregistered;
+    /// failures are logged since the page is going away regardless.
+    async fn release_page_state(&self, page_id: &str) {
+        let engine = self
+            .stealth_engine
+            .read()
+            .ok()
+            .and_then(|slot| slot.as_ref().and_then(Weak::upgrade));
+        if let Some(engine) = engine {
+            if let Err(e) = engine.remove_all(page_id).await {
+                tracing::warn!("Failed to remove injected scripts of page {}: {}", page_id, e);
+            }
+        }
+
+        let stores = self.page_state.read().map(|stores| stores.clone()).unwrap_or_default();
+        for store in stores {
+            store.release_page(page_id).await;
         }
     }
 
@@ -46,14 +209,35 @@ impl Default for SessionManagerImpl {
 #[async_trait]
 impl SessionManager for SessionManagerImpl {
     async fn create_browser(&self, options: BrowserOptions) -> Result<String, Error> {
-        // Create CDP browser
-        let cdp_browser = (self.cdp_browser_factory)()?;
+        // Launch our own Chrome when an executable is given, otherwise attach via the factory
+        let browser = match &options.executable_path {
+            Some(executable) => {
+                let process = ChromeProcess::launch(executable, &options).await?;
+                let cdp_browser: Arc<dyn CdpBrowser> =
+                    Arc::new(crate::cdp::browser::CdpBrowserImpl::with_pool_config(
+                        process.endpoint(),
+                        self.cdp_pool_config.clone(),
+                    ));
+                crate::session::browser::BrowserContextImpl::new(options.clone(), cdp_browser)
+                    .with_process(process)
+            }
+            None => {
+                if options.user_data_dir.is_some() {
+                    tracing::warn!("user_data_dir is ignored when attaching to an existing browser");
+                }
+                let cdp_browser = (self.cdp_browser_factory)()?;
+                crate::session::browser::BrowserContextImpl::new(options.clone(), cdp_browser)
+            }
+        };
+        let browser = Arc::new(browser.with_url_policy(Arc::clone(&self.url_policy)));
 
-        // Create browser context
-        let browser = Arc::new(crate::session::browser::BrowserContextImpl::new(
-            options.clone(),
-            cdp_browser,
-        ));
+        // Only hand out browsers whose CDP endpoint actually answers
+        if let Err(e) = self.verify_health(browser.as_ref()).await {
+            if let Err(close_error) = browser.close().await {
+                tracing::warn!("Failed to close unhealthy browser {}: {}", browser.id(), close_error);
+            }
+            return Err(e);
+        }
 
         // Store browser
         let browser_id = browser.id().to_string();
@@ -78,6 +262,10 @@ impl SessionManager for SessionManagerImpl {
         // Get browser
         let browser = self.get_browser(browser_id).await?;
 
+        for page in browser.get_pages().await.unwrap_or_default() {
+            self.release_page_state(page.id()).await;
+        }
+
         // Close browser
         browser.close().await?;
 
@@ -108,33 +296,25 @@ impl SessionManager for SessionManagerImpl {
     }
 
     async fn get_page(&self, page_id: &str) -> Result<Arc<dyn PageContext>, Error> {
-        // Search through all browsers to find the page
-        // Collect browser Arcs first to avoid holding lock across await
-        let browser_refs: Vec<Arc<dyn BrowserContext>> = self
-            .browsers
-            .read()
-            .map_err(|e| Error::internal(format!("Lock error: {}", e)))?
-            .values()
-            .cloned()
-            .collect();
-        // Lock guard dropped here
-
-        for browser in browser_refs {
-            if let Ok(pages) = browser.get_pages().await {
-                for page in pages {
-                    if page.id() == page_id {
-                        return Ok(page);
-                    }
-                }
-            }
+        let page = self.find_page(page_id).await?;
+        if page.is_crashed() {
+            return Err(Error::unavailable(format!("Page {} crashed; close it and create a new page", page_id)));
         }
 
-        Err(Error::page_not_found(page_id))
+        page.touch();
+        Ok(page)
     }
 
     async fn close_page(&self, page_id: &str) -> Result<(), Error> {
-        let page = self.get_page(page_id).await?;
-        page.close().await
+        // Crashed pages can still be closed
+        let page = self.find_page(page_id).await?;
+        self.release_page_state(page_id).await;
+
+        // Let the owning browser stop tracking the target and emit its event
+        match self.get_browser(page.browser_id()).await {
+            Ok(browser) => browser.close_page(page_id).await,
+            Err(_) => page.close().await,
+        }
     }
 
     async fn cleanup(&self) -> Result<(), Error> {
@@ -271,6 +451,62 @@ mod tests {
         assert!(!retrieved_page.is_active());
     }
 
+    #[tokio::test]
+    async fn test_close_page_drops_injected_scripts() {
+        use crate::stealth::{
+            BehaviorSimulator, BehaviorSimulatorImpl, ScriptInjector, ScriptInjectorImpl, StealthEngineImpl,
+        };
+
+        let manager = Arc::new(SessionManagerImpl::mock());
+        let browser_id = manager.create_browser(BrowserOptions::default()).await.unwrap();
+        let page = manager.create_page(&browser_id, PageOptions::default()).await.unwrap();
+        let page_id = page.id().to_string();
+
+        let injector = Arc::new(ScriptInjectorImpl::new(manager.clone())) as Arc<dyn ScriptInjector>;
+        let simulator = Arc::new(BehaviorSimulatorImpl::with_session_manager(manager.clone()))
+            as Arc<dyn BehaviorSimulator>;
+        let engine = Arc::new(StealthEngineImpl::new(injector.clone(), simulator)) as Arc<dyn StealthEngine>;
+        manager.attach_stealth_engine(&engine);
+
+        injector.inject_init_script(&page_id, "window.a = 1;").await.unwrap();
+        injector.inject_init_script(&page_id, "window.b = 2;").await.unwrap();
+        assert_eq!(injector.get_injected_scripts(&page_id).await.unwrap().len(), 2);
+
+        manager.close_page(&page_id).await.unwrap();
+
+        assert!(matches!(
+            injector.get_injected_scripts(&page_id).await,
+            Err(Error::PageNotFound(_))
+        ));
+        assert!(engine.get_active_profile(&page_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_close_page_releases_page_state() {
+        #[derive(Default)]
+        struct Released(std::sync::Mutex<Vec<String>>);
+
+        #[async_trait]
+        impl PageStateStore for Released {
+            async fn release_page(&self, page_id: &str) {
+                self.0.lock().unwrap().push(page_id.to_string());
+            }
+        }
+
+        let manager = SessionManagerImpl::mock();
+        let released = Arc::new(Released::default());
+        manager.attach_page_state(released.clone());
+        let browser_id = manager.create_browser(BrowserOptions::default()).await.unwrap();
+        let first = manager.create_page(&browser_id, PageOptions::default()).await.unwrap();
+        let second = manager.create_page(&browser_id, PageOptions::default()).await.unwrap();
+
+        manager.close_page(first.id()).await.unwrap();
+        assert_eq!(*released.0.lock().unwrap(), vec![first.id().to_string()]);
+
+        manager.close_browser(&browser_id).await.unwrap();
+        assert!(released.0.lock().unwrap().contains(&second.id().to_string()));
+    }
+
     #[tokio::test]
     async fn test_cleanup() {
         let manager = SessionManagerImpl::mock();
@@ -326,4 +562,40 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), Error::PageNotFound(_)));
     }
+
+    /// Manager whose browsers share `cdp_browser`, with fast health check retries
+    fn manager_with(cdp_browser: Arc<crate::cdp::mock::MockCdpBrowser>, retries: u32) -> SessionManagerImpl {
+        SessionManagerImpl::new(move || Ok(cdp_browser.clone() as Arc<dyn CdpBrowser>)).with_browser_health_check(
+            BrowserHealthCheck {
+                retries,
+                timeout: Duration::from_secs(1),
+                initial_backoff: Duration::from_millis(10),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_browser_retries_failed_health_check() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        cdp_browser.fail_version_checks(1);
+        let manager = manager_with(cdp_browser.clone(), 2);
+
+        let browser_id = manager.create_browser(BrowserOptions::default()).await.unwrap();
+
+        assert!(manager.get_browser(&browser_id).await.unwrap().is_active());
+        assert!(cdp_browser.get_version().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_browser_unavailable_after_retries() {
+        let cdp_browser = Arc::new(crate::cdp::mock::MockCdpBrowser::new());
+        cdp_browser.fail_version_checks(3);
+        let manager = manager_with(cdp_browser.clone(), 2);
+
+        let err = manager.create_browser(BrowserOptions::default()).await.unwrap_err();
+
+        assert!(matches!(&err, Error::Unavailable(message) if message.contains("3 attempt(s)")));
+        assert_eq!(manager.session_count(), 0);
+        assert_eq!(tonic::Status::from(err).code(), tonic::Code::Unavailable);
+    }
 }
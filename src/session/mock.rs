@@ -10,11 +10,14 @@ use uuid::Uuid;
 
 use super::traits::{
     BrowserContext, BrowserOptions, PageContext, PageOptions, ElementRef,
-    NavigationOptions, NavigationResult, EvaluationResult, BoundingBox,
-    ScreenshotOptions, SessionManager,
+    NavigationOptions, NavigationResult, RedirectHop, EvaluationResult, BoundingBox,
+    ScreenshotOptions, SessionManager, HttpCredentials, PageActivity, TargetEvent,
 };
 use crate::Error;
 
+/// Execution context ID reported for a mock page's isolated world
+pub const MOCK_ISOLATED_WORLD: i64 = 1;
+
 /// Mock session manager
 #[derive(Debug, Clone)]
 pub struct MockSessionManager {
@@ -100,6 +103,7 @@ impl SessionManager for MockSessionManager {
             .await
             .get(page_id)
             .cloned()
+            .inspect(|page| page.touch())
             .ok_or_else(|| crate::Error::PageNotFound(page_id.to_string()))
     }
 
@@ -132,11 +136,13 @@ impl SessionManager for MockSessionManager {
 #[derive(Debug)]
 pub struct MockBrowser {
     id: String,
-    #[allow(dead_code)]
     options: BrowserOptions,
     pages: Arc<RwLock<Vec<Arc<MockPage>>>>,
     is_active: Arc<RwLock<bool>>,
     created_at: std::time::Instant,
+    target_events: tokio::sync::broadcast::Sender<TargetEvent>,
+    browser_client: Arc<crate::cdp::mock::MockCdpClient>,
+    visited_origins: crate::session::browser_data::VisitedOrigins,
 }
 
 impl MockBrowser {
@@ -148,9 +154,22 @@ impl MockBrowser {
             pages: Arc::new(RwLock::new(Vec::new())),
             is_active: Arc::new(RwLock::new(true)),
             created_at: std::time::Instant::now(),
+            target_events: tokio::sync::broadcast::channel(64).0,
+            browser_client: Arc::new(crate::cdp::mock::MockCdpClient::new()),
+            visited_origins: Default::default(),
         }
     }
 
+    /// Browser-level connection that browser-wide commands are sent to
+    pub fn browser_client(&self) -> Arc<crate::cdp::mock::MockCdpClient> {
+        Arc::clone(&self.browser_client)
+    }
+
+    /// Origins cleared by `clear_data`
+    pub fn visited_origins(&self) -> &crate::session::browser_data::VisitedOrigins {
+        &self.visited_origins
+    }
+
     /// Add a page to this browser
     pub async fn add_page(&self, page: Arc<MockPage>) {
         self.pages.write().await.push(page);
@@ -161,10 +180,20 @@ impl MockBrowser {
         self.created_at.elapsed().as_millis() as u64
     }
 
+    /// Target ID reported for a mock page
+    fn mock_target_id(page_id: &str) -> String {
+        format!("mock-target-{}", page_id)
+    }
+
     /// Get page count
     pub async fn page_count(&self) -> usize {
         self.pages.read().await.len()
     }
+
+    /// Pages created in this browser
+    pub async fn pages(&self) -> Vec<Arc<MockPage>> {
+        self.pages.read().await.clone()
+    }
 }
 
 #[async_trait]
@@ -179,6 +208,10 @@ impl BrowserContext for MockBrowser {
             options,
         ));
         self.add_page(page.clone()).await;
+        let _ = self.target_events.send(TargetEvent::Created {
+            target_id: Self::mock_target_id(page.id()),
+            page_id: page.id().to_string(),
+        });
         Ok(page)
     }
 
@@ -187,6 +220,51 @@ impl BrowserContext for MockBrowser {
         Ok(pages.iter().map(|p| p.clone() as Arc<dyn PageContext>).collect())
     }
 
+    async fn target_id(&self, page_id: &str) -> Result<String, Error> {
+        if self.pages.read().await.iter().any(|p| p.id() == page_id) {
+            Ok(Self::mock_target_id(page_id))
+        } else {
+            Err(Error::page_not_found(page_id))
+        }
+    }
+
+    async fn activate_page(&self, page_id: &str) -> Result<(), Error> {
+        self.target_id(page_id).await.map(|_| ())
+    }
+
+    async fn close_page(&self, page_id: &str) -> Result<(), Error> {
+        let page = self
+            .pages
+            .read()
+            .await
+            .iter()
+            .find(|p| p.id() == page_id)
+            .cloned()
+            .ok_or_else(|| Error::page_not_found(page_id))?;
+
+        page.close().await?;
+        let _ = self.target_events.send(TargetEvent::Destroyed {
+            target_id: Self::mock_target_id(page_id),
+            page_id: page_id.to_string(),
+        });
+        Ok(())
+    }
+
+    fn subscribe_targets(&self) -> tokio::sync::broadcast::Receiver<TargetEvent> {
+        self.target_events.subscribe()
+    }
+
+    async fn clear_data(&self, types: crate::session::browser_data::ClearDataTypes) -> Result<Vec<String>, Error> {
+        let page = self.pages.read().await.first().map(|page| page.get_cdp_client());
+        crate::session::browser_data::clear_browser_data(
+            self.browser_client.as_ref(),
+            page.as_deref(),
+            &self.visited_origins.snapshot(),
+            types,
+        )
+        .await
+    }
+
     async fn close(&self) -> Result<(), Error> {
         *self.is_active.write().await = false;
         Ok(())
@@ -200,8 +278,20 @@ impl BrowserContext for MockBrowser {
             .map(|active| *active)
             .unwrap_or(false)
     }
+
+    fn user_agent(&self) -> Option<String> {
+        self.options.user_agent.clone()
+    }
+
+    async fn get_version(&self) -> Result<crate::cdp::traits::BrowserVersion, Error> {
+        let cdp_browser = crate::cdp::mock::MockCdpBrowser::new();
+        crate::cdp::traits::CdpBrowser::get_version(&cdp_browser).await
+    }
 }
 
+/// Script substring and the queued results returned for matching evaluations
+type EvaluateStub = (String, std::collections::VecDeque<EvaluationResult>);
+
 /// Mock page context
 #[derive(Debug)]
 pub struct MockPage {
@@ -214,12 +304,30 @@ pub struct MockPage {
     content: Arc<RwLock<String>>,
     is_active: Arc<RwLock<bool>>,
     viewport: Arc<RwLock<(u32, u32, f64)>>,
+    screen: Arc<RwLock<(u32, u32)>>,
+    history: Arc<RwLock<(Vec<String>, usize)>>,
+    http_credentials: Arc<RwLock<Option<HttpCredentials>>>,
+    navigations: tokio::sync::broadcast::Sender<String>,
+    touch_enabled: Arc<std::sync::atomic::AtomicBool>,
+    evaluate_stubs: Arc<RwLock<Vec<EvaluateStub>>>,
+    evaluate_failures: Arc<RwLock<Vec<String>>>,
+    evaluated_scripts: Arc<RwLock<Vec<String>>>,
+    activity: Arc<std::sync::Mutex<PageActivity>>,
     cdp_client: Arc<dyn crate::cdp::traits::CdpClient>,
 }
 
 impl MockPage {
     /// Create a new mock page
     pub fn new(browser_id: String, options: PageOptions) -> Self {
+        Self::with_cdp_client(browser_id, options, Arc::new(crate::cdp::mock::MockCdpClient::new()))
+    }
+
+    /// Create a mock page backed by the given CDP client
+    pub fn with_cdp_client(
+        browser_id: String,
+        options: PageOptions,
+        cdp_client: Arc<dyn crate::cdp::traits::CdpClient>,
+    ) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             browser_id,
@@ -229,15 +337,85 @@ impl MockPage {
             content: Arc::new(RwLock::new(String::new())),
             is_active: Arc::new(RwLock::new(true)),
             viewport: Arc::new(RwLock::new((1920, 1080, 1.0))),
-            cdp_client: Arc::new(crate::cdp::mock::MockCdpClient::new()),
+            screen: Arc::new(RwLock::new((1920, 1080))),
+            history: Arc::new(RwLock::new((vec!["about:blank".to_string()], 0))),
+            http_credentials: Arc::new(RwLock::new(None)),
+            navigations: tokio::sync::broadcast::channel(16).0,
+            touch_enabled: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            evaluate_stubs: Arc::new(RwLock::new(Vec::new())),
+            evaluate_failures: Arc::new(RwLock::new(Vec::new())),
+            evaluated_scripts: Arc::new(RwLock::new(Vec::new())),
+            activity: Arc::new(std::sync::Mutex::new(PageActivity::new("about:blank"))),
+            cdp_client,
         }
     }
 
+    /// Move `offset` entries through the mock history
+    async fn move_in_history(&self, offset: i64) -> Result<NavigationResult, Error> {
+        let mut history = self.history.write().await;
+        let target = history.1 as i64 + offset;
+        if target < 0 || target >= history.0.len() as i64 {
+            return Err(Error::navigation_failed(format!(
+                "Cannot move {} entries in history of {} entries",
+                offset,
+                history.0.len()
+            )));
+        }
+
+        history.1 = target as usize;
+        let url = history.0[history.1].clone();
+        drop(history);
+
+        *self.url.write().await = url.clone();
+        Ok(NavigationResult {
+            url,
+            status_code: 200,
+            is_loaded: true,
+            redirect_chain: Vec::new(),
+            redirect_chain_truncated: false,
+        })
+    }
+
+    /// Answer scripts containing `needle` with `results`, one per call
+    ///
+    /// The last result keeps being returned once the others are used up.
+    pub async fn stub_evaluate(&self, needle: &str, results: Vec<EvaluationResult>) {
+        self.evaluate_stubs
+            .write()
+            .await
+            .push((needle.to_string(), results.into_iter().collect()));
+    }
+
+    /// Fail every evaluated script containing `needle`
+    pub async fn fail_evaluate(&self, needle: &str) {
+        self.evaluate_failures.write().await.push(needle.to_string());
+    }
+
+    /// Scripts passed to `evaluate`, in call order
+    pub async fn evaluated_scripts(&self) -> Vec<String> {
+        self.evaluated_scripts.read().await.clone()
+    }
+
+    /// Current viewport as (width, height, device scale factor)
+    pub async fn viewport(&self) -> (u32, u32, f64) {
+        *self.viewport.read().await
+    }
+
+    /// Current `window.screen` size as (width, height)
+    pub async fn screen_size(&self) -> (u32, u32) {
+        *self.screen.read().await
+    }
+
     /// Set URL (for testing)
     pub async fn set_url(&self, url: String) {
         *self.url.write().await = url;
     }
 
+    /// Get the current HTTP credentials (for testing)
+    pub async fn http_credentials(&self) -> Option<HttpCredentials> {
+        self.http_credentials.read().await.clone()
+    }
+
     /// Set title (for testing)
     pub async fn set_title(&self, title: String) {
         *self.title.write().await = title;
@@ -261,10 +439,50 @@ impl PageContext for MockPage {
 
     async fn navigate(&self, url: &str, _options: NavigationOptions) -> Result<NavigationResult, Error> {
         *self.url.write().await = url.to_string();
+
+        let mut history = self.history.write().await;
+        let next = history.1 + 1;
+        history.0.truncate(next);
+        history.0.push(url.to_string());
+        history.1 = next;
+        drop(history);
+
+        let _ = self.navigations.send(url.to_string());
+
         Ok(NavigationResult {
             url: url.to_string(),
             status_code: 200,
             is_loaded: true,
+            redirect_chain: vec![RedirectHop { url: url.to_string(), status_code: 200 }],
+            redirect_chain_truncated: false,
+        })
+    }
+
+    async fn wait_for_navigation(&self, url_pattern: Option<&str>, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        let mut navigations = self.navigations.subscribe();
+        let wait = async {
+            loop {
+                match navigations.recv().await {
+                    Ok(url) if crate::session::page::url_matches(url_pattern, &url) => return Ok(url),
+                    Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                        return Err(Error::page_not_found(&self.id));
+                    }
+                }
+            }
+        };
+
+        let timeout_ms = if options.timeout == 0 { 30_000 } else { options.timeout };
+        let url = tokio::time::timeout(tokio::time::Duration::from_millis(timeout_ms), wait)
+            .await
+            .map_err(|_| Error::timeout("No matching navigation"))??;
+
+        Ok(NavigationResult {
+            url,
+            status_code: 200,
+            is_loaded: true,
+            redirect_chain: Vec::new(),
+            redirect_chain_truncated: false,
         })
     }
 
@@ -277,22 +495,49 @@ impl PageContext for MockPage {
         Ok(())
     }
 
-    async fn reload(&self, _ignore_cache: bool) -> Result<(), Error> {
-        Ok(())
+    async fn reload(&self, _ignore_cache: bool, _options: NavigationOptions) -> Result<NavigationResult, Error> {
+        Ok(NavigationResult {
+            url: self.url.read().await.clone(),
+            status_code: 200,
+            is_loaded: true,
+            redirect_chain: Vec::new(),
+            redirect_chain_truncated: false,
+        })
     }
 
-    async fn go_back(&self) -> Result<(), Error> {
-        Ok(())
+    async fn go_back(&self, delta: u32, _options: NavigationOptions) -> Result<NavigationResult, Error> {
+        self.move_in_history(-(delta.max(1) as i64)).await
     }
 
-    async fn go_forward(&self) -> Result<(), Error> {
-        Ok(())
+    async fn go_forward(&self, delta: u32, _options: NavigationOptions) -> Result<NavigationResult, Error> {
+        self.move_in_history(delta.max(1) as i64).await
     }
 
     async fn evaluate(&self, script: &str, _await_promise: bool) -> Result<EvaluationResult, Error> {
+        self.evaluated_scripts.write().await.push(script.to_string());
+
+        if self.evaluate_failures.read().await.iter().any(|needle| script.contains(needle.as_str())) {
+            return Err(Error::script_execution_failed("Evaluation failed"));
+        }
+
+        for (needle, results) in self.evaluate_stubs.write().await.iter_mut() {
+            if script.contains(needle.as_str()) {
+                let result = if results.len() > 1 { results.pop_front() } else { results.front().cloned() };
+                return Ok(result.unwrap_or(EvaluationResult::Null));
+            }
+        }
+
         // Simple mock: handle basic cases for testing
-        if script == "document.title" {
+        if let Some(url) = crate::cdp::mock::assigned_location(script) {
+            self.navigate(&url, NavigationOptions::default()).await?;
+            Ok(EvaluationResult::String(url))
+        } else if script == "document.title" {
             Ok(EvaluationResult::String("Test Page".to_string()))
+        } else if script == "window.location.origin" {
+            let origin = reqwest::Url::parse(&self.url.read().await)
+                .map(|u| u.origin().ascii_serialization())
+                .unwrap_or_else(|_| "null".to_string());
+            Ok(EvaluationResult::String(origin))
         } else if script.contains("+") {
             // Simple arithmetic evaluation
             let parts: Vec<&str> = script.split('+').collect();
@@ -308,6 +553,15 @@ impl PageContext for MockPage {
         }
     }
 
+    async fn isolated_world(&self) -> Result<i64, Error> {
+        Ok(MOCK_ISOLATED_WORLD)
+    }
+
+    /// Mock pages have a single world, so this is `evaluate`
+    async fn evaluate_isolated(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, Error> {
+        self.evaluate(script, await_promise).await
+    }
+
     async fn screenshot(&self, _options: ScreenshotOptions) -> Result<Vec<u8>, Error> {
         // Return a minimal 1x1 PNG
         Ok(vec![
@@ -323,6 +577,27 @@ impl PageContext for MockPage {
 
     async fn set_viewport(&self, width: u32, height: u32, device_scale_factor: f64) -> Result<(), Error> {
         *self.viewport.write().await = (width, height, device_scale_factor);
+        *self.screen.write().await = (width, height);
+        Ok(())
+    }
+
+    async fn set_screen_size(&self, width: u32, height: u32) -> Result<(), Error> {
+        let (viewport_width, viewport_height, _) = *self.viewport.read().await;
+        *self.screen.write().await = (width.max(viewport_width), height.max(viewport_height));
+        Ok(())
+    }
+
+    async fn set_touch_emulation(&self, enabled: bool) -> Result<(), Error> {
+        self.touch_enabled.store(enabled, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn touch_emulation_enabled(&self) -> bool {
+        self.touch_enabled.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    async fn set_http_credentials(&self, credentials: Option<HttpCredentials>) -> Result<(), Error> {
+        *self.http_credentials.write().await = credentials;
         Ok(())
     }
 
@@ -340,6 +615,21 @@ impl PageContext for MockPage {
             .unwrap_or(false)
     }
 
+    fn activity(&self) -> PageActivity {
+        let mut activity = self.activity.lock().unwrap().clone();
+        if let Ok(url) = self.url.try_read() {
+            activity.url = url.clone();
+        }
+        if let Ok(title) = self.title.try_read() {
+            activity.title = title.clone();
+        }
+        activity
+    }
+
+    fn touch(&self) {
+        self.activity.lock().unwrap().last_activity = std::time::SystemTime::now();
+    }
+
     fn get_cdp_client(&self) -> Arc<dyn crate::cdp::traits::CdpClient> {
         self.cdp_client.clone()
     }
@@ -352,16 +642,28 @@ pub struct MockElement {
     page_id: String,
     tag_name: String,
     text_content: Option<String>,
+    cdp_client: Arc<dyn crate::cdp::traits::CdpClient>,
 }
 
 impl MockElement {
     /// Create a new mock element
     pub fn new(page_id: String, tag_name: String, text_content: Option<String>) -> Self {
+        Self::with_cdp_client(page_id, tag_name, text_content, Arc::new(crate::cdp::mock::MockCdpClient::new()))
+    }
+
+    /// Create a new mock element whose page uses `cdp_client`
+    pub fn with_cdp_client(
+        page_id: String,
+        tag_name: String,
+        text_content: Option<String>,
+        cdp_client: Arc<dyn crate::cdp::traits::CdpClient>,
+    ) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             page_id,
             tag_name,
             text_content,
+            cdp_client,
         }
     }
 }
@@ -376,6 +678,10 @@ impl ElementRef for MockElement {
         &self.page_id
     }
 
+    fn get_cdp_client(&self) -> Arc<dyn crate::cdp::traits::CdpClient> {
+        Arc::clone(&self.cdp_client)
+    }
+
     async fn get_text(&self) -> Result<String, Error> {
         Ok(self.text_content.clone().unwrap_or_default())
     }
@@ -459,6 +765,7 @@ mod tests {
         let result = page.navigate("https://example.com", NavigationOptions {
             timeout: 30000,
             wait_until: super::super::traits::LoadState::Load,
+            referer: None,
         }).await.unwrap();
         assert_eq!(result.url, "https://example.com");
 
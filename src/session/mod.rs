@@ -19,6 +19,12 @@
 //! - `manager`: 会话管理器实现
 //! - `browser`: 浏览器上下文实现
 //! - `page`: 页面上下文实现
+//! - `browser_data`: 清除浏览器的 Cookie、缓存和按源存储的数据
+//! - `cancel`: 客户端取消时中止长时间运行的页面操作
+//! - `launcher`: 启动本地 Chrome 进程并管理其用户数据目录
+//! - `navigation_limit`: 限制单个浏览器内同时进行的导航数
+//! - `screenshot`: 截图后处理（格式转换、质量、缩放、拼接）
+//! - `url_policy`: 导航 URL 的允许/拒绝规则
 //! - `element`: 元素引用实现
 //! - `mock`: 用于测试的 Mock 实现
 //!
@@ -45,6 +51,13 @@ pub mod traits;
 pub mod manager;
 pub mod browser;
 pub mod page;
+pub mod browser_data;
+pub mod cancel;
+pub mod console;
+pub mod launcher;
+pub mod navigation_limit;
+pub mod screenshot;
+pub mod url_policy;
 pub mod element;
 pub mod mock;
 
@@ -52,17 +65,21 @@ pub mod mock;
 pub mod tests;
 
 pub use traits::{
-    SessionManager, BrowserContext, PageContext, ElementRef,
-    BrowserOptions, PageOptions, ScreenshotOptions, NavigationOptions,
-    LoadState, ScreenshotFormat, ClipRegion,
-    NavigationResult, EvaluationResult, BoundingBox,
+    SessionManager, BrowserContext, PageContext, ElementRef, PageStateStore,
+    BrowserOptions, DefaultTimeouts, DialogPolicy, PageOptions, ScreenshotOptions, NavigationOptions,
+    LoadState, ScreenshotFormat, ClipRegion, HttpCredentials, PageActivity, TargetEvent,
+    NavigationResult, RedirectHop, EvaluationResult, BoundingBox,
 };
 
 // Re-export implementation structs
-pub use manager::SessionManagerImpl;
+pub use manager::{BrowserHealthCheck, SessionManagerImpl};
 pub use browser::BrowserContextImpl;
-pub use page::PageContextImpl;
+pub use page::{wait_for_load_state, NavigationWatch, PageContextImpl};
 pub use element::ElementRefImpl;
+pub use navigation_limit::{NavigationLimiter, NavigationStats};
+pub use console::{ConsoleEntry, ConsoleLogs};
+pub use browser_data::ClearDataTypes;
+pub use cancel::CancelToken;
 
 // Re-export mock implementations for testing
 #[cfg(test)]
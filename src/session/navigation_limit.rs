@@ -0,0 +1,98 @@
+//! Per-browser limit on concurrent navigations
+//!
+//! Every page of a browser shares one renderer budget, so navigations beyond
+//! the limit queue until a running one completes. Time spent queueing is
+//! recorded for the browser's status.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::Error;
+
+/// Default limit for concurrent navigations in one browser
+pub const DEFAULT_MAX_CONCURRENT_NAVIGATIONS: usize = 8;
+
+/// Navigation counters of a browser
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NavigationStats {
+    /// Navigations started
+    pub navigations: u64,
+    /// Navigations that had to wait for a free slot
+    pub queued: u64,
+    /// Total time navigations spent waiting, in milliseconds
+    pub total_wait_ms: u64,
+    /// Longest time a single navigation waited, in milliseconds
+    pub max_wait_ms: u64,
+}
+
+/// Semaphore shared by the pages of one browser
+#[derive(Debug)]
+pub struct NavigationLimiter {
+    /// `None` means unlimited
+    slots: Option<Semaphore>,
+    navigations: AtomicU64,
+    queued: AtomicU64,
+    total_wait_ms: AtomicU64,
+    max_wait_ms: AtomicU64,
+}
+
+impl NavigationLimiter {
+    /// Allow at most `max_concurrent` navigations at once; `0` disables the limit
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            slots: (max_concurrent > 0).then(|| Semaphore::new(max_concurrent)),
+            navigations: AtomicU64::new(0),
+            queued: AtomicU64::new(0),
+            total_wait_ms: AtomicU64::new(0),
+            max_wait_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Wait for a navigation slot; the navigation runs while the permit is held
+    ///
+    /// Tokio's semaphore is fair, so queued navigations start in call order.
+    pub async fn acquire(&self) -> Result<Option<SemaphorePermit<'_>>, Error> {
+        self.navigations.fetch_add(1, Ordering::Relaxed);
+        let Some(slots) = &self.slots else {
+            return Ok(None);
+        };
+
+        if let Ok(permit) = slots.try_acquire() {
+            return Ok(Some(permit));
+        }
+
+        let started = Instant::now();
+        let permit = slots
+            .acquire()
+            .await
+            .map_err(|_| Error::internal("Navigation limiter closed"))?;
+        let waited_ms = started.elapsed().as_millis() as u64;
+
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_ms.fetch_add(waited_ms, Ordering::Relaxed);
+        self.max_wait_ms.fetch_max(waited_ms, Ordering::Relaxed);
+        if waited_ms > 0 {
+            tracing::debug!("Navigation waited {}ms for a free slot", waited_ms);
+        }
+
+        Ok(Some(permit))
+    }
+
+    /// Counters since the browser started
+    pub fn stats(&self) -> NavigationStats {
+        NavigationStats {
+            navigations: self.navigations.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            total_wait_ms: self.total_wait_ms.load(Ordering::Relaxed),
+            max_wait_ms: self.max_wait_ms.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for NavigationLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_NAVIGATIONS)
+    }
+}
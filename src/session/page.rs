@@ -3,16 +3,224 @@
 //! Manages page lifecycle and operations.
 
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::cdp::traits::CdpClient;
+use crate::cdp::client::TARGET_CRASHED_EVENTS;
+use crate::cdp::connection::{CONNECTION_LOST_REASON, DETACHED_EVENT};
+use crate::cdp::traits::{CdpClient, CdpEvent};
+use crate::session::console::{ConsoleBuffer, ConsoleLogs};
+use crate::session::navigation_limit::NavigationLimiter;
+use crate::session::screenshot;
 use crate::session::traits::{
-    EvaluationResult, LoadState, NavigationOptions, NavigationResult,
-    PageContext, ScreenshotOptions,
+    DefaultTimeouts, DialogPolicy, EvaluationResult, HttpCredentials, LoadState, NavigationOptions, NavigationResult,
+    PageActivity, PageContext, RedirectHop, ScreenshotFormat, ScreenshotOptions, TargetEvent,
 };
+use crate::session::url_policy::UrlPolicy;
 use crate::Error;
 
+/// `wait_for_navigation` timeout used when the options give none
+const DEFAULT_NAVIGATION_TIMEOUT_MS: u64 = 30_000;
+
+/// Redirects recorded per navigation by default; later ones are dropped from the chain
+pub const DEFAULT_MAX_REDIRECT_HOPS: usize = 20;
+
+/// Name of the isolated world created for `evaluate_isolated` and isolated stealth scripts
+pub const ISOLATED_WORLD_NAME: &str = "chaser_isolated_world";
+
+/// Touch points reported to the page while touch emulation is enabled
+const MAX_TOUCH_POINTS: u32 = 5;
+
+/// Tallest page captured with a single `Page.captureScreenshot` call;
+/// taller pages are captured in viewport-height tiles and stitched
+const MAX_SINGLE_CAPTURE_HEIGHT: f64 = 16384.0;
+
+/// Hide fixed/sticky elements so they are not repeated in every tile
+const HIDE_FIXED_ELEMENTS_SCRIPT: &str = r#"(() => {
+    let hidden = 0;
+    for (const el of document.querySelectorAll('body *')) {
+        const position = getComputedStyle(el).position;
+        if (position === 'fixed' || position === 'sticky') {
+            el.setAttribute('data-chaser-visibility', el.style.visibility);
+            el.style.visibility = 'hidden';
+            hidden++;
+        }
+    }
+    return hidden;
+})()"#;
+
+/// Restore elements hidden by `HIDE_FIXED_ELEMENTS_SCRIPT`
+const RESTORE_FIXED_ELEMENTS_SCRIPT: &str = r#"(() => {
+    for (const el of document.querySelectorAll('[data-chaser-visibility]')) {
+        el.style.visibility = el.getAttribute('data-chaser-visibility');
+        el.removeAttribute('data-chaser-visibility');
+    }
+})()"#;
+
+/// Report `window.screen` as `{width}`x`{height}` in every frame
+///
+/// Defined on `Screen.prototype` and configurable, so a later call can
+/// replace it and profile overrides on the `screen` instance still apply.
+fn screen_size_script(width: u32, height: u32) -> String {
+    format!(
+        r#"(() => {{
+    const size = {{ width: {width}, height: {height}, availWidth: {width}, availHeight: {height} }};
+    for (const [name, value] of Object.entries(size)) {{
+        try {{
+            Object.defineProperty(Screen.prototype, name, {{ get: () => value, configurable: true }});
+        }} catch (e) {{}}
+    }}
+}})();"#
+    )
+}
+
+/// Device metrics last sent with `Emulation.setDeviceMetricsOverride`
+#[derive(Debug, Default)]
+struct DeviceMetrics {
+    /// Width, height and device scale factor from `set_viewport`
+    viewport: Option<(u32, u32, f64)>,
+    /// Screen size from `set_screen_size`
+    screen: Option<(u32, u32)>,
+    /// CDP identifier of the `window.screen` init script
+    screen_script: Option<String>,
+}
+
+/// Subscriptions watching for the next main-frame navigation
+///
+/// Created before the action that triggers the navigation (e.g. a click), so
+/// a navigation that starts right away is not missed.
+pub struct NavigationWatch {
+    navigated: tokio::sync::mpsc::Receiver<CdpEvent>,
+    lifecycle: tokio::sync::mpsc::Receiver<CdpEvent>,
+    responses: tokio::sync::mpsc::Receiver<CdpEvent>,
+}
+
+impl NavigationWatch {
+    /// Subscribe to navigation events and enable lifecycle reporting
+    pub async fn start(client: &dyn CdpClient) -> Result<Self, Error> {
+        // Subscribe before enabling lifecycle events so the first ones are not missed
+        let navigated = client.subscribe_events("Page.frameNavigated").await?;
+        let lifecycle = client.subscribe_events("Page.lifecycleEvent").await?;
+        let responses = client.subscribe_events("Network.responseReceived").await?;
+        client.enable_domain("Page").await?;
+        client
+            .call_method("Page.setLifecycleEventsEnabled", serde_json::json!({ "enabled": true }))
+            .await?;
+
+        Ok(Self { navigated, lifecycle, responses })
+    }
+
+    /// Wait for a navigation matching `url_pattern` to reach `options.wait_until`
+    ///
+    /// # Errors
+    /// Returns `Timeout` if none does within `options.timeout`.
+    pub async fn wait(mut self, url_pattern: Option<&str>, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        let target = options.wait_until.lifecycle_event();
+        let timeout_ms = if options.timeout == 0 {
+            DEFAULT_NAVIGATION_TIMEOUT_MS
+        } else {
+            options.timeout
+        };
+
+        let (frame_id, url) = tokio::time::timeout(
+            tokio::time::Duration::from_millis(timeout_ms),
+            PageContextImpl::next_navigation(self.navigated, self.lifecycle, url_pattern, target),
+        )
+        .await
+        .map_err(|_| {
+            Error::timeout(format!(
+                "No navigation{} reached '{}' within {}ms",
+                url_pattern.map(|p| format!(" matching '{}'", p)).unwrap_or_default(),
+                target,
+                timeout_ms
+            ))
+        })??;
+
+        let status_code = PageContextImpl::main_frame_response(&mut self.responses, Some(&frame_id))
+            .map(|(_, status)| status)
+            .unwrap_or(200);
+
+        Ok(NavigationResult {
+            url,
+            status_code,
+            is_loaded: true,
+            redirect_chain: Vec::new(),
+            redirect_chain_truncated: false,
+        })
+    }
+}
+
+/// Wait until the current document reaches `state`, without navigating
+///
+/// Resolves at once if the state was already reached: load and
+/// DOMContentLoaded are read from `document.readyState`, and enabling
+/// lifecycle events makes Chrome replay the events the current document has
+/// already fired (including network idle).
+///
+/// # Errors
+/// Returns `Timeout` if the state is not reached within `timeout_ms`
+/// (0 = the default navigation timeout).
+pub async fn wait_for_load_state(client: &dyn CdpClient, state: LoadState, timeout_ms: u64) -> Result<(), Error> {
+    let target = state.lifecycle_event();
+    let timeout_ms = if timeout_ms == 0 { DEFAULT_NAVIGATION_TIMEOUT_MS } else { timeout_ms };
+
+    let wait = async {
+        // Subscribe before enabling lifecycle events so the replayed ones are not missed
+        let mut lifecycle = client.subscribe_events("Page.lifecycleEvent").await?;
+        client.enable_domain("Page").await?;
+        client
+            .call_method("Page.setLifecycleEventsEnabled", serde_json::json!({ "enabled": true }))
+            .await?;
+
+        if matches!(state, LoadState::Load | LoadState::DOMContentLoaded) {
+            let ready = match client.evaluate("document.readyState", false).await? {
+                crate::cdp::traits::EvaluationResult::String(ready) => ready,
+                _ => String::new(),
+            };
+            let reached = match state {
+                LoadState::Load => ready == "complete",
+                _ => ready == "interactive" || ready == "complete",
+            };
+            if reached {
+                return Ok(());
+            }
+        }
+
+        let main_frame_id = client
+            .call_method("Page.getFrameTree", serde_json::json!({}))
+            .await
+            .ok()
+            .and_then(|tree| tree.pointer("/frameTree/frame/id").and_then(|v| v.as_str()).map(str::to_string));
+
+        while let Some(event) = lifecycle.recv().await {
+            let frame_id = event.params.get("frameId").and_then(|v| v.as_str());
+            if main_frame_id.is_some() && frame_id != main_frame_id.as_deref() {
+                continue;
+            }
+            if event.params.get("name").and_then(|v| v.as_str()) == Some(target) {
+                return Ok(());
+            }
+        }
+
+        Err(Error::cdp("Lifecycle event stream closed"))
+    };
+
+    tokio::time::timeout(tokio::time::Duration::from_millis(timeout_ms), wait)
+        .await
+        .map_err(|_| Error::timeout(format!("Page did not reach '{}' within {}ms", target, timeout_ms)))?
+}
+
+/// Running Fetch event loop of a page
+#[derive(Debug)]
+struct FetchInterception {
+    task: tokio::task::JoinHandle<()>,
+    /// Whether every request is paused so auth challenges reach the loop
+    handles_auth: bool,
+}
+
 /// Page context implementation
 #[derive(Debug)]
 pub struct PageContextImpl {
@@ -21,6 +229,33 @@ pub struct PageContextImpl {
     options: crate::session::traits::PageOptions,
     cdp_client: Arc<dyn CdpClient>,
     is_active: Arc<tokio::sync::RwLock<bool>>,
+    http_credentials: Arc<tokio::sync::RwLock<Option<HttpCredentials>>>,
+    /// Fetch event loop for auth challenges and URL policy checks, running until close
+    fetch_handler: tokio::sync::Mutex<Option<FetchInterception>>,
+    /// Set once credentials were first given; every request is intercepted from then on
+    handle_auth: AtomicBool,
+    /// Navigation rules enforced on document requests; `None` when unrestricted
+    url_policy: Option<Arc<UrlPolicy>>,
+    /// Task answering dialogs under an automatic dialog policy, stopped on close
+    dialog_handler: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    touch_enabled: AtomicBool,
+    /// Set by the crash listener when the renderer crashes
+    crashed: Arc<AtomicBool>,
+    /// Concurrent navigation limit shared with the browser's other pages
+    navigation_limiter: Option<Arc<NavigationLimiter>>,
+    /// Timeouts inherited from the browser
+    default_timeouts: DefaultTimeouts,
+    /// Viewport and screen size, kept together so `window.screen` never lags the viewport
+    device_metrics: tokio::sync::Mutex<DeviceMetrics>,
+    /// Isolated world execution context per frame ID
+    isolated_worlds: Arc<tokio::sync::Mutex<HashMap<String, i64>>>,
+    isolated_world_watch_started: AtomicBool,
+    /// Shared with the target info listener
+    activity: Arc<std::sync::Mutex<PageActivity>>,
+    /// Recent console messages, fed by the console listener
+    console: Arc<std::sync::Mutex<ConsoleBuffer>>,
+    /// Redirects kept in a navigation's redirect chain
+    max_redirect_hops: usize,
 }
 
 impl PageContextImpl {
@@ -30,162 +265,552 @@ impl PageContextImpl {
         options: crate::session::traits::PageOptions,
         cdp_client: Arc<dyn CdpClient>,
     ) -> Self {
+        let url = options.default_url.clone().unwrap_or_else(|| "about:blank".to_string());
         Self {
             id: Uuid::new_v4().to_string(),
             browser_id,
             options,
             cdp_client,
             is_active: Arc::new(tokio::sync::RwLock::new(true)),
+            http_credentials: Arc::new(tokio::sync::RwLock::new(None)),
+            fetch_handler: tokio::sync::Mutex::new(None),
+            handle_auth: AtomicBool::new(false),
+            url_policy: None,
+            dialog_handler: std::sync::Mutex::new(None),
+            touch_enabled: AtomicBool::new(false),
+            crashed: Arc::new(AtomicBool::new(false)),
+            navigation_limiter: None,
+            default_timeouts: DefaultTimeouts::default(),
+            device_metrics: tokio::sync::Mutex::new(DeviceMetrics::default()),
+            isolated_worlds: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            isolated_world_watch_started: AtomicBool::new(false),
+            activity: Arc::new(std::sync::Mutex::new(PageActivity::new(url))),
+            console: Arc::new(std::sync::Mutex::new(ConsoleBuffer::default())),
+            max_redirect_hops: DEFAULT_MAX_REDIRECT_HOPS,
         }
     }
 
-    /// Convert screenshot options
-    fn convert_screenshot_format(format: crate::session::traits::ScreenshotFormat) -> crate::cdp::traits::ScreenshotFormat {
-        match format {
-            crate::session::traits::ScreenshotFormat::Png => crate::cdp::traits::ScreenshotFormat::Png,
-            crate::session::traits::ScreenshotFormat::Jpeg => crate::cdp::traits::ScreenshotFormat::Jpeg(100),
-            crate::session::traits::ScreenshotFormat::WebP => crate::cdp::traits::ScreenshotFormat::WebP(100),
-        }
+    /// Queue navigations behind `limiter`, shared by the pages of one browser
+    pub fn with_navigation_limiter(mut self, limiter: Arc<NavigationLimiter>) -> Self {
+        self.navigation_limiter = Some(limiter);
+        self
     }
-}
 
-#[async_trait]
-impl PageContext for PageContextImpl {
-    fn id(&self) -> &str {
-        &self.id
+    /// Inherit the browser's default timeouts
+    pub fn with_default_timeouts(mut self, timeouts: DefaultTimeouts) -> Self {
+        self.default_timeouts = timeouts;
+        self
     }
 
-    fn browser_id(&self) -> &str {
-        &self.browser_id
+    /// Keep at most `size` console messages; `0` keeps none
+    pub fn with_console_buffer_size(mut self, size: usize) -> Self {
+        self.console = Arc::new(std::sync::Mutex::new(ConsoleBuffer::new(size)));
+        self
     }
 
-    async fn navigate(&self, url: &str, options: NavigationOptions) -> Result<NavigationResult, Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
-        }
+    /// Check the page's document requests against `policy` once [`Self::enforce_url_policy`] runs
+    pub fn with_url_policy(mut self, policy: Arc<UrlPolicy>) -> Self {
+        self.url_policy = Some(policy).filter(|policy| !policy.is_unrestricted());
+        self
+    }
 
-        // Navigate using CDP
-        let nav_result = self.cdp_client.navigate(url).await?;
+    /// Keep at most `max_hops` redirects in navigation results; later ones are dropped
+    pub fn with_max_redirect_hops(mut self, max_hops: usize) -> Self {
+        self.max_redirect_hops = max_hops;
+        self
+    }
 
-        // Wait for load state if specified
-        match options.wait_until {
-            LoadState::Load => {
-                // In real implementation, wait for load event
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-            }
-            LoadState::DOMContentLoaded => {
-                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    /// Buffer the page's console messages from `Runtime.consoleAPICalled`
+    pub async fn start_console_log(&self) -> Result<(), Error> {
+        let mut calls = self.cdp_client.subscribe_events("Runtime.consoleAPICalled").await?;
+        self.cdp_client.enable_domain("Runtime").await?;
+
+        let console = Arc::clone(&self.console);
+        tokio::spawn(async move {
+            while let Some(event) = calls.recv().await {
+                console.lock().unwrap_or_else(|e| e.into_inner()).record(&event.params);
             }
-            LoadState::NetworkIdle | LoadState::NetworkAlmostIdle => {
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        });
+
+        Ok(())
+    }
+
+    /// Keep the recorded URL and title in sync with the page's target
+    ///
+    /// `changes` carries the `targetInfo` of every `Target.targetInfoChanged`
+    /// the browser reports; those of `target_id` are applied, so reading the
+    /// page's activity never needs a round trip to the browser. Tracking
+    /// stops once the page is dropped.
+    pub fn track_target_info(&self, target_id: &str, mut changes: tokio::sync::broadcast::Receiver<serde_json::Value>) {
+        let target_id = target_id.to_string();
+        let activity = Arc::downgrade(&self.activity);
+        tokio::spawn(async move {
+            loop {
+                let info = match changes.recv().await {
+                    Ok(info) => info,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                if info["targetId"].as_str() != Some(target_id.as_str()) {
+                    continue;
+                }
+                let Some(activity) = activity.upgrade() else {
+                    break;
+                };
+                let mut activity = activity.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(url) = info["url"].as_str() {
+                    activity.url = url.to_string();
+                }
+                if let Some(title) = info["title"].as_str() {
+                    activity.title = title.to_string();
+                }
             }
+        });
+    }
+
+    /// Flag the page as crashed when its renderer crashes
+    ///
+    /// Sends `TargetEvent::Crashed` for `target_id` on `target_events` once,
+    /// on the first of `Inspector.targetCrashed` / `Target.targetCrashed`, or
+    /// when the connection is lost (`Inspector.detached` with reason
+    /// `connection_lost`).
+    pub async fn start_crash_handler(
+        &self,
+        target_id: &str,
+        target_events: tokio::sync::broadcast::Sender<TargetEvent>,
+    ) -> Result<(), Error> {
+        let (tx, mut crashes) = tokio::sync::mpsc::channel(1);
+        for event in TARGET_CRASHED_EVENTS {
+            let mut events = self.cdp_client.subscribe_events(event).await?;
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if events.recv().await.is_some() {
+                    let _ = tx.send(()).await;
+                }
+            });
         }
+        // A connection that stopped answering leaves the page just as unusable
+        let mut detached = self.cdp_client.subscribe_events(DETACHED_EVENT).await?;
+        tokio::spawn(async move {
+            while let Some(event) = detached.recv().await {
+                if event.params["reason"] == CONNECTION_LOST_REASON {
+                    let _ = tx.send(()).await;
+                    break;
+                }
+            }
+        });
 
-        Ok(NavigationResult {
-            url: nav_result.url,
-            status_code: 200,
-            is_loaded: true,
-        })
+        let crashed = Arc::clone(&self.crashed);
+        let (target_id, page_id) = (target_id.to_string(), self.id.clone());
+        tokio::spawn(async move {
+            if crashes.recv().await.is_some() && !crashed.swap(true, Ordering::SeqCst) {
+                tracing::warn!("Page {} (target {}) crashed", page_id, target_id);
+                // No subscribers is fine
+                let _ = target_events.send(TargetEvent::Crashed { target_id, page_id });
+            }
+        });
+
+        Ok(())
     }
 
-    async fn get_content(&self) -> Result<String, Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Forget isolated worlds whose execution context was destroyed
+    ///
+    /// Runs once per page; navigations clear every context of the frame.
+    async fn watch_isolated_worlds(&self) -> Result<(), Error> {
+        if self.isolated_world_watch_started.swap(true, Ordering::SeqCst) {
+            return Ok(());
         }
 
-        self.cdp_client.get_content().await
+        let subscriptions = async {
+            let destroyed = self.cdp_client.subscribe_events("Runtime.executionContextDestroyed").await?;
+            let cleared = self.cdp_client.subscribe_events("Runtime.executionContextsCleared").await?;
+            Ok::<_, Error>((destroyed, cleared))
+        };
+        let (mut destroyed, mut cleared) = match subscriptions.await {
+            Ok(events) => events,
+            Err(e) => {
+                self.isolated_world_watch_started.store(false, Ordering::SeqCst);
+                return Err(e);
+            }
+        };
+
+        let worlds = Arc::clone(&self.isolated_worlds);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    Some(event) = destroyed.recv() => {
+                        let context_id = event.params["executionContextId"].as_i64();
+                        worlds.lock().await.retain(|_, id| Some(*id) != context_id);
+                    }
+                    Some(_) = cleared.recv() => worlds.lock().await.clear(),
+                    else => break,
+                }
+            }
+        });
+
+        Ok(())
     }
 
-    async fn set_content(&self, html: &str) -> Result<(), Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Intercept requests via the Fetch domain to answer auth challenges and enforce the URL policy
+    ///
+    /// Chrome only raises `Fetch.authRequired` for requests matched by a
+    /// `Fetch.enable` pattern, and a challenge can come from any resource, so
+    /// once credentials are set every request is paused, costing one extra
+    /// `Fetch.continueRequest` round trip each. Before that only document
+    /// requests are paused, and only when a URL policy is enforced. Both share
+    /// one `Fetch.enable`, since each call replaces the previous patterns.
+    ///
+    /// Interception runs until the page closes; challenges are answered with
+    /// the current credentials, or cancelled once they are cleared.
+    async fn start_fetch_handler(&self) -> Result<(), Error> {
+        let mut handler = self.fetch_handler.lock().await;
+        let handle_auth = self.handle_auth.load(Ordering::SeqCst);
+        if handler.as_ref().is_some_and(|running| running.handles_auth || !handle_auth) {
+            return Ok(());
         }
 
-        self.cdp_client.set_content(html).await
-    }
+        let pattern = if handle_auth {
+            serde_json::json!({ "urlPattern": "*" })
+        } else {
+            serde_json::json!({ "urlPattern": "*", "resourceType": "Document" })
+        };
+        let enable = serde_json::json!({ "handleAuthRequests": handle_auth, "patterns": [pattern] });
 
-    async fn reload(&self, ignore_cache: bool) -> Result<(), Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+        // Already intercepting documents: widen the patterns, the event loop stays
+        if let Some(running) = handler.as_mut() {
+            self.cdp_client.call_method("Fetch.enable", enable).await?;
+            running.handles_auth = handle_auth;
+            return Ok(());
+        }
+
+        let (mut auth_events, mut paused_events) = async {
+            let auth_events = self.cdp_client.subscribe_events("Fetch.authRequired").await?;
+            let paused_events = self.cdp_client.subscribe_events("Fetch.requestPaused").await?;
+            self.cdp_client.call_method("Fetch.enable", enable).await?;
+            Ok::<_, Error>((auth_events, paused_events))
         }
+        .await?;
 
-        self.cdp_client.reload(ignore_cache).await
+        let client = Arc::clone(&self.cdp_client);
+        let credentials = Arc::clone(&self.http_credentials);
+        let policy = self.url_policy.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    Some(event) = auth_events.recv() => event,
+                    Some(event) = paused_events.recv() => event,
+                    else => break,
+                };
+                // Policy checks may resolve DNS, so one slow host must not hold up other requests
+                let (client, credentials, policy) = (Arc::clone(&client), Arc::clone(&credentials), policy.clone());
+                tokio::spawn(async move {
+                    let current = credentials.read().await.clone();
+                    if let Err(e) =
+                        Self::handle_fetch_event(client.as_ref(), &event, current.as_ref(), policy.as_deref()).await
+                    {
+                        tracing::warn!("Failed to handle {}: {}", event.method, e);
+                    }
+                });
+            }
+        });
+        *handler = Some(FetchInterception { task, handles_auth: handle_auth });
+
+        Ok(())
     }
 
-    async fn go_back(&self) -> Result<(), Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Stop request interception when the page closes
+    async fn stop_fetch_handler(&self) -> Result<(), Error> {
+        let Some(handler) = self.fetch_handler.lock().await.take() else {
+            return Ok(());
+        };
+        handler.task.abort();
+        self.cdp_client.call_method("Fetch.disable", serde_json::json!({})).await?;
+        Ok(())
+    }
+
+    /// Enforce the page's URL policy on every document request
+    ///
+    /// Covers navigations the server never sees: redirects, frames, links,
+    /// scripts setting `location`, reloads and popups. Requests the policy
+    /// rejects fail with `net::ERR_BLOCKED_BY_CLIENT`. Does nothing without a
+    /// restrictive policy.
+    pub async fn enforce_url_policy(&self) -> Result<(), Error> {
+        if self.url_policy.is_none() {
+            return Ok(());
+        }
+        self.start_fetch_handler().await
+    }
+
+    /// Answer a single Fetch domain event
+    async fn handle_fetch_event(
+        client: &dyn CdpClient,
+        event: &CdpEvent,
+        credentials: Option<&HttpCredentials>,
+        policy: Option<&UrlPolicy>,
+    ) -> Result<(), Error> {
+        let request_id = event
+            .params
+            .get("requestId")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::cdp(format!("{} without requestId", event.method)))?;
+
+        match event.method.as_str() {
+            "Fetch.authRequired" => {
+                let challenge_response = match credentials {
+                    Some(c) => serde_json::json!({
+                        "response": "ProvideCredentials",
+                        "username": c.username,
+                        "password": c.password,
+                    }),
+                    None => serde_json::json!({ "response": "CancelAuth" }),
+                };
+                client
+                    .call_method(
+                        "Fetch.continueWithAuth",
+                        serde_json::json!({
+                            "requestId": request_id,
+                            "authChallengeResponse": challenge_response,
+                        }),
+                    )
+                    .await?;
+            }
+            "Fetch.requestPaused" => {
+                let rejected = match policy {
+                    Some(policy) if event.params["resourceType"] == "Document" => {
+                        let url = event.params["request"]["url"].as_str().unwrap_or_default();
+                        policy.check(url).await.err()
+                    }
+                    _ => None,
+                };
+                match rejected {
+                    Some(reason) => {
+                        tracing::warn!("Blocked document request: {}", reason);
+                        client
+                            .call_method(
+                                "Fetch.failRequest",
+                                serde_json::json!({ "requestId": request_id, "errorReason": "BlockedByClient" }),
+                            )
+                            .await?;
+                    }
+                    None => {
+                        client
+                            .call_method("Fetch.continueRequest", serde_json::json!({ "requestId": request_id }))
+                            .await?;
+                    }
+                }
+            }
+            _ => {}
         }
 
-        // Use CDP to navigate back
-        self.cdp_client
-            .call_method(
-                "Page.navigate",
-                serde_json::json!({ "url": "javascript:history.back()" }),
-            )
-            .await?;
         Ok(())
     }
 
-    async fn go_forward(&self) -> Result<(), Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Answer JavaScript dialogs according to `policy` until the page closes
+    ///
+    /// Under [`DialogPolicy::Manual`] nothing is answered: a dialog stays open
+    /// until the client handles it (`Page.handleJavaScriptDialog`).
+    pub async fn start_dialog_handler(&self, policy: DialogPolicy) -> Result<(), Error> {
+        let accept = match policy {
+            DialogPolicy::AcceptAll => true,
+            DialogPolicy::DismissAll => false,
+            DialogPolicy::Manual => return Ok(()),
+        };
+        let mut opened = self.cdp_client.subscribe_events("Page.javascriptDialogOpening").await?;
+        self.cdp_client.enable_domain("Page").await?;
+
+        // A weak reference lets the connection close with the page; the
+        // subscription then ends and so does the task
+        let client = Arc::downgrade(&self.cdp_client);
+        let task = tokio::spawn(async move {
+            while let Some(event) = opened.recv().await {
+                let Some(client) = client.upgrade() else {
+                    break;
+                };
+                let default_prompt = event.params["defaultPrompt"].as_str().unwrap_or_default();
+                if let Err(e) = Self::answer_dialog(client.as_ref(), accept, default_prompt).await {
+                    tracing::warn!("Failed to answer dialog: {}", e);
+                }
+            }
+        });
+        if let Some(previous) = self.dialog_handler.lock().unwrap_or_else(|e| e.into_inner()).replace(task) {
+            previous.abort();
         }
 
-        // Use CDP to navigate forward
-        self.cdp_client
+        Ok(())
+    }
+
+    /// Accept or dismiss the open dialog
+    async fn answer_dialog(client: &dyn CdpClient, accept: bool, prompt_text: &str) -> Result<(), Error> {
+        client
             .call_method(
-                "Page.navigate",
-                serde_json::json!({ "url": "javascript:history.forward()" }),
+                "Page.handleJavaScriptDialog",
+                serde_json::json!({ "accept": accept, "promptText": prompt_text }),
             )
             .await?;
+
         Ok(())
     }
 
-    async fn evaluate(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Wait for the requested load state
+    async fn wait_for_load_state(wait_until: &LoadState) {
+        match wait_until {
+            LoadState::Load => {
+                // In real implementation, wait for load event
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            }
+            LoadState::DOMContentLoaded => {
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            }
+            LoadState::NetworkIdle | LoadState::NetworkAlmostIdle => {
+                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+            }
         }
+    }
 
-        let result = self.cdp_client.evaluate(script, await_promise).await?;
-        tracing::debug!("PageContext::evaluate: CDP returned {:?}", result);
+    /// Get the main frame ID via `Page.getFrameTree`
+    async fn main_frame_id(&self) -> Option<String> {
+        let tree = self
+            .cdp_client
+            .call_method("Page.getFrameTree", serde_json::json!({}))
+            .await
+            .ok()?;
 
-        let session_result = match result {
-            crate::cdp::traits::EvaluationResult::String(s) => EvaluationResult::String(s),
-            crate::cdp::traits::EvaluationResult::Number(n) => EvaluationResult::Number(n),
-            crate::cdp::traits::EvaluationResult::Bool(b) => EvaluationResult::Bool(b),
-            crate::cdp::traits::EvaluationResult::Null => EvaluationResult::Null,
-            crate::cdp::traits::EvaluationResult::Object(v) => EvaluationResult::Object(v),
-        };
-        tracing::debug!("PageContext::evaluate: returning {:?}", session_result);
-        Ok(session_result)
+        tree.pointer("/frameTree/frame/id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
     }
 
-    async fn screenshot(&self, options: ScreenshotOptions) -> Result<Vec<u8>, Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Find the main-frame document response among received network events
+    fn main_frame_response(
+        events: &mut tokio::sync::mpsc::Receiver<crate::cdp::traits::CdpEvent>,
+        main_frame_id: Option<&str>,
+    ) -> Option<(String, u16)> {
+        let mut found = None;
+
+        while let Ok(event) = events.try_recv() {
+            let params = &event.params;
+            if params.get("type").and_then(|v| v.as_str()) != Some("Document") {
+                continue;
+            }
+            let frame_id = params.get("frameId").and_then(|v| v.as_str());
+            if main_frame_id.is_some() && frame_id != main_frame_id {
+                continue;
+            }
+
+            let url = params.pointer("/response/url").and_then(|v| v.as_str());
+            let status = params.pointer("/response/status").and_then(|v| v.as_u64());
+            if let (Some(url), Some(status)) = (url, status) {
+                // Keep the last one so redirects resolve to the final response
+                found = Some((url.to_string(), status as u16));
+            }
         }
 
-        let format = Self::convert_screenshot_format(options.format);
-        self.cdp_client.screenshot(format).await
+        found
     }
 
-    async fn set_viewport(&self, width: u32, height: u32, device_scale_factor: f64) -> Result<(), Error> {
-        let active = *self.is_active.read().await;
-        if !active {
-            return Err(Error::page_not_found(&self.id));
+    /// Collect the main-frame redirects among received `Network.requestWillBeSent` events
+    ///
+    /// Each redirected request carries the response that caused it in `redirectResponse`.
+    /// Only the first `max_hops` redirects are kept; the flag reports whether any were dropped.
+    fn redirect_hops(
+        events: &mut tokio::sync::mpsc::Receiver<CdpEvent>,
+        main_frame_id: Option<&str>,
+        max_hops: usize,
+    ) -> (Vec<RedirectHop>, bool) {
+        let mut hops = Vec::new();
+        let mut truncated = false;
+
+        while let Ok(event) = events.try_recv() {
+            let params = &event.params;
+            if params.get("type").and_then(|v| v.as_str()) != Some("Document") {
+                continue;
+            }
+            let frame_id = params.get("frameId").and_then(|v| v.as_str());
+            if main_frame_id.is_some() && frame_id != main_frame_id {
+                continue;
+            }
+
+            let url = params.pointer("/redirectResponse/url").and_then(|v| v.as_str());
+            let status = params.pointer("/redirectResponse/status").and_then(|v| v.as_u64());
+            if let (Some(url), Some(status)) = (url, status) {
+                // Keep draining so later navigations do not see these events
+                if hops.len() == max_hops {
+                    truncated = true;
+                    continue;
+                }
+                hops.push(RedirectHop {
+                    url: url.to_string(),
+                    status_code: status as u16,
+                });
+            }
+        }
+
+        if truncated {
+            tracing::debug!("Redirect chain truncated to {} hops", max_hops);
+        }
+        (hops, truncated)
+    }
+
+    /// Follow main-frame navigations until one whose URL matches `url_pattern`
+    /// fires the `target` lifecycle event, returning its frame id and URL
+    async fn next_navigation(
+        mut navigated: tokio::sync::mpsc::Receiver<CdpEvent>,
+        mut lifecycle: tokio::sync::mpsc::Receiver<CdpEvent>,
+        url_pattern: Option<&str>,
+        target: &str,
+    ) -> Result<(String, String), Error> {
+        // The two subscriptions are not ordered relative to each other, so remember
+        // lifecycle events per loader in case they arrive before their navigation
+        let mut reached: HashMap<String, HashSet<String>> = HashMap::new();
+        // (frame id, loader id, url) of the matching navigation in progress
+        let mut pending: Option<(String, String, String)> = None;
+
+        loop {
+            tokio::select! {
+                Some(event) = navigated.recv() => {
+                    let frame = event.params.get("frame").cloned().unwrap_or_default();
+                    let field = |key: &str| frame.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+                    if frame.get("parentId").is_none() {
+                        let (frame_id, loader_id, url) = (field("id"), field("loaderId"), field("url"));
+                        // A later non-matching navigation replaces the pending document
+                        pending = None;
+                        if url_matches(url_pattern, &url) {
+                            if reached.get(&loader_id).is_some_and(|names| names.contains(target)) {
+                                return Ok((frame_id, url));
+                            }
+                            pending = Some((frame_id, loader_id, url));
+                        }
+                    }
+                }
+                Some(event) = lifecycle.recv() => {
+                    let field = |key: &str| event.params.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                    let (loader_id, name) = (field("loaderId"), field("name"));
+
+                    if let Some((frame_id, pending_loader, url)) = &pending {
+                        if *pending_loader == loader_id && name == target {
+                            return Ok((frame_id.clone(), url.clone()));
+                        }
+                    }
+                    reached.entry(loader_id).or_default().insert(name);
+                }
+                else => return Err(Error::cdp("Event stream closed while waiting for navigation")),
+            }
         }
+    }
 
-        // Ignore result
-        let _ = self.cdp_client
+    /// Send the viewport and screen size, then make `window.screen` report the same size
+    ///
+    /// The screen is never reported smaller than the viewport. Without a
+    /// viewport override, width and height 0 keep the window's own size.
+    async fn apply_device_metrics(&self, metrics: &mut DeviceMetrics) -> Result<(), Error> {
+        let (width, height, device_scale_factor) = metrics.viewport.unwrap_or((0, 0, 0.0));
+        let (screen_width, screen_height) = metrics
+            .screen
+            .map(|(w, h)| (w.max(width), h.max(height)))
+            .unwrap_or((width, height));
+
+        self.cdp_client
             .call_method(
                 "Emulation.setDeviceMetricsOverride",
                 serde_json::json!({
@@ -193,66 +818,790 @@ impl PageContext for PageContextImpl {
                     "height": height,
                     "deviceScaleFactor": device_scale_factor,
                     "mobile": self.options.is_mobile,
+                    "screenWidth": screen_width,
+                    "screenHeight": screen_height,
                 }),
             )
             .await?;
 
+        // Replace the previous size so documents loaded later do not see a stale screen
+        if let Some(identifier) = metrics.screen_script.take() {
+            self.cdp_client
+                .call_method(
+                    "Page.removeScriptToEvaluateOnNewDocument",
+                    serde_json::json!({ "identifier": identifier }),
+                )
+                .await?;
+        }
+        let script = screen_size_script(screen_width, screen_height);
+        let added = self
+            .cdp_client
+            .call_method("Page.addScriptToEvaluateOnNewDocument", serde_json::json!({ "source": script }))
+            .await?;
+        metrics.screen_script = added["identifier"].as_str().map(str::to_string);
+        self.cdp_client.evaluate(&script, false).await?;
+
         Ok(())
     }
 
-    async fn close(&self) -> Result<(), Error> {
-        tracing::info!("PageContext::close: Closing page {}", self.id);
+    /// Wait for a navigation slot of the browser; navigations run while it is held
+    async fn navigation_slot(&self) -> Result<Option<tokio::sync::SemaphorePermit<'_>>, Error> {
+        match &self.navigation_limiter {
+            Some(limiter) => limiter.acquire().await,
+            None => Ok(None),
+        }
+    }
 
-        // Check if page is still active before attempting close
+    /// Convert a CDP evaluation result to the session type
+    fn session_result(result: crate::cdp::traits::EvaluationResult) -> EvaluationResult {
+        match result {
+            crate::cdp::traits::EvaluationResult::String(s) => EvaluationResult::String(s),
+            crate::cdp::traits::EvaluationResult::Number(n) => EvaluationResult::Number(n),
+            crate::cdp::traits::EvaluationResult::Bool(b) => EvaluationResult::Bool(b),
+            crate::cdp::traits::EvaluationResult::Null => EvaluationResult::Null,
+            crate::cdp::traits::EvaluationResult::Object(v) => EvaluationResult::Object(v),
+        }
+    }
+
+    /// Move `offset` entries through the navigation history
+    ///
+    /// Uses `Page.getNavigationHistory` to check bounds before calling
+    /// `Page.navigateToHistoryEntry`. A new document is waited for until it
+    /// reaches `options.wait_until`; a same-document entry (fragment or
+    /// `history.pushState`) completes when the main frame reports it.
+    ///
+    /// # Errors
+    /// Returns `Timeout` if the navigation does not complete within `options.timeout`.
+    async fn navigate_history(&self, offset: i64, options: NavigationOptions) -> Result<NavigationResult, Error> {
         let active = *self.is_active.read().await;
         if !active {
-            tracing::warn!("PageContext::close: Page {} is already inactive", self.id);
-            return Ok(());
+            return Err(Error::page_not_found(&self.id));
         }
 
-        // Try to close the page via CDP - Page.close command will close the page in the browser
-        tracing::debug!("PageContext::close: Sending Page.close CDP command for page {}", self.id);
-        let close_result = self
+        let _slot = self.navigation_slot().await?;
+        let history = self
             .cdp_client
-            .call_method("Page.close", serde_json::json!({}))
-            .await;
+            .call_method("Page.getNavigationHistory", serde_json::json!({}))
+            .await?;
 
-        match &close_result {
-            Ok(_) => {
-                tracing::info!("PageContext::close: Page.close CDP command succeeded for page {}", self.id);
-            }
-            Err(e) => {
-                tracing::warn!("PageContext::close: Page.close CDP command failed for page {}: {}", self.id, e);
-                tracing::warn!("PageContext::close: The page may not be closed in the browser");
-            }
-        }
+        let current_index = history
+            .get("currentIndex")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        let entries = history
+            .get("entries")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
 
-        // Mark as inactive regardless of CDP result
-        // This ensures the page context is removed from browser's management
-        tracing::debug!("PageContext::close: Marking page {} as inactive", self.id);
-        *self.is_active.write().await = false;
+        let target_index = current_index + offset;
+        if target_index < 0 || target_index >= entries.len() as i64 {
+            let direction = if offset < 0 { "back" } else { "forward" };
+            return Err(Error::navigation_failed(format!(
+                "Cannot go {} {} entries: history has {} entries at index {}",
+                direction,
+                offset.abs(),
+                entries.len(),
+                current_index
+            )));
+        }
 
-        tracing::info!("PageContext::close: Page {} close completed", self.id);
-        Ok(())
-    }
+        let entry = &entries[target_index as usize];
+        let entry_id = entry
+            .get("id")
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| Error::cdp("History entry is missing an id"))?;
+        let timeout_ms = DefaultTimeouts::resolve(
+            options.timeout,
+            self.default_timeouts.navigation_ms,
+            DEFAULT_NAVIGATION_TIMEOUT_MS,
+        );
 
-    fn is_active(&self) -> bool {
-        // Use try_read to avoid blocking in sync context
-        self.is_active
-            .try_read()
+        // Subscribe before navigating so the commit and the main-frame response are not missed
+        let mut requests = self
+            .cdp_client
+            .subscribe_events("Network.requestWillBeSent")
+            .await?;
+        let mut responses = self
+            .cdp_client
+            .subscribe_events("Network.responseReceived")
+            .await?;
+        let mut committed = self.cdp_client.subscribe_events("Page.frameNavigated").await?;
+        let mut same_document = self
+            .cdp_client
+            .subscribe_events("Page.navigatedWithinDocument")
+            .await?;
+        let main_frame_id = self.main_frame_id().await;
+
+        self.cdp_client
+            .call_method(
+                "Page.navigateToHistoryEntry",
+                serde_json::json!({ "entryId": entry_id }),
+            )
+            .await?;
+
+        let wait = async {
+            let new_document = loop {
+                tokio::select! {
+                    Some(event) = committed.recv() => {
+                        if event.params.pointer("/frame/parentId").is_none() {
+                            break true;
+                        }
+                    }
+                    Some(event) = same_document.recv() => {
+                        let frame_id = event.params.get("frameId").and_then(|v| v.as_str());
+                        if main_frame_id.is_none() || frame_id == main_frame_id.as_deref() {
+                            break false;
+                        }
+                    }
+                    else => return Err(Error::cdp("Event stream closed while waiting for history navigation")),
+                }
+            };
+            if new_document {
+                wait_for_load_state(self.cdp_client.as_ref(), options.wait_until, timeout_ms).await?;
+            }
+            Ok(())
+        };
+        tokio::time::timeout(tokio::time::Duration::from_millis(timeout_ms), wait)
+            .await
+            .map_err(|_| {
+                Error::timeout(format!(
+                    "History navigation did not reach '{}' within {}ms",
+                    options.wait_until.lifecycle_event(),
+                    timeout_ms
+                ))
+            })??;
+
+        // Same-document and back/forward cache navigations load nothing from the network
+        let (url, status_code) = Self::main_frame_response(&mut responses, main_frame_id.as_deref())
+            .unwrap_or_else(|| {
+                let url = entry.get("url").and_then(|v| v.as_str()).unwrap_or_default();
+                (url.to_string(), 200)
+            });
+        let (mut redirect_chain, redirect_chain_truncated) =
+            Self::redirect_hops(&mut requests, main_frame_id.as_deref(), self.max_redirect_hops);
+        redirect_chain.push(RedirectHop {
+            url: url.clone(),
+            status_code,
+        });
+
+        Ok(NavigationResult {
+            url,
+            status_code,
+            is_loaded: true,
+            redirect_chain,
+            redirect_chain_truncated,
+        })
+    }
+
+    /// Poll `document.readyState` until the page has loaded
+    async fn wait_for_ready_state(&self) -> bool {
+        let max_attempts = 50; // 5 seconds (50 * 100ms)
+
+        for _ in 0..max_attempts {
+            if let Ok(crate::cdp::traits::EvaluationResult::String(state)) =
+                self.cdp_client.evaluate("document.readyState", false).await
+            {
+                if state == "complete" {
+                    return true;
+                }
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+
+        false
+    }
+
+    /// Content size and layout viewport height from `Page.getLayoutMetrics`
+    async fn layout_metrics(&self) -> Result<(f64, f64, f64), Error> {
+        let metrics = self
+            .cdp_client
+            .call_method("Page.getLayoutMetrics", serde_json::json!({}))
+            .await?;
+
+        // css* variants are in CSS pixels; older browsers only report the legacy fields
+        let content = metrics
+            .get("cssContentSize")
+            .or_else(|| metrics.get("contentSize"))
+            .ok_or_else(|| Error::cdp("No content size in layout metrics"))?;
+        let viewport = metrics
+            .get("cssLayoutViewport")
+            .or_else(|| metrics.get("layoutViewport"))
+            .ok_or_else(|| Error::cdp("No layout viewport in layout metrics"))?;
+
+        let number = |value: &serde_json::Value, key: &str| value.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        Ok((
+            number(content, "width"),
+            number(content, "height"),
+            number(viewport, "clientHeight"),
+        ))
+    }
+
+    /// Capture a clipped region as PNG with `Page.captureScreenshot`
+    async fn capture_region(&self, clip: serde_json::Value, capture_beyond_viewport: bool) -> Result<Vec<u8>, Error> {
+        let params = serde_json::json!({
+            "format": "png",
+            "clip": clip,
+            "captureBeyondViewport": capture_beyond_viewport,
+        });
+
+        let result = self.cdp_client.call_method("Page.captureScreenshot", params).await?;
+        let data = result
+            .get("data")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::cdp("No data in screenshot result"))?;
+
+        BASE64
+            .decode(data)
+            .map_err(|e| Error::cdp(format!("Failed to decode screenshot: {}", e)))
+    }
+
+    /// Capture the whole scrollable page as PNG
+    async fn full_page_screenshot(&self) -> Result<Vec<u8>, Error> {
+        let (width, height, viewport_height) = self.layout_metrics().await?;
+
+        if height <= MAX_SINGLE_CAPTURE_HEIGHT {
+            let clip = serde_json::json!({ "x": 0, "y": 0, "width": width, "height": height, "scale": 1 });
+            return self.capture_region(clip, true).await;
+        }
+
+        if viewport_height < 1.0 {
+            return Err(Error::cdp("Layout viewport has no height"));
+        }
+
+        tracing::debug!(
+            "Page height {} exceeds {}, stitching {}px tiles",
+            height, MAX_SINGLE_CAPTURE_HEIGHT, viewport_height
+        );
+
+        let original_scroll = match self.cdp_client.evaluate("window.scrollY", false).await? {
+            crate::cdp::traits::EvaluationResult::Number(y) => y,
+            _ => 0.0,
+        };
+
+        let tiles = self.capture_tiles(width, height, viewport_height).await;
+
+        // Restore the page even if a tile failed
+        self.cdp_client.evaluate(RESTORE_FIXED_ELEMENTS_SCRIPT, false).await?;
+        self.cdp_client
+            .evaluate(&format!("window.scrollTo(0, {})", original_scroll), false)
+            .await?;
+
+        let stitched = screenshot::stitch_tiles(&tiles?)?;
+        screenshot::encode(&image::DynamicImage::ImageRgba8(stitched), ScreenshotFormat::Png, None)
+    }
+
+    /// Scroll through the page capturing one PNG tile per viewport
+    ///
+    /// Fixed/sticky elements are kept in the first tile and hidden afterwards.
+    async fn capture_tiles(&self, width: f64, height: f64, viewport_height: f64) -> Result<Vec<Vec<u8>>, Error> {
+        let mut tiles = Vec::new();
+        let mut y = 0.0;
+
+        while y < height {
+            if tiles.len() == 1 {
+                self.cdp_client.evaluate(HIDE_FIXED_ELEMENTS_SCRIPT, false).await?;
+            }
+
+            self.cdp_client
+                .evaluate(&format!("window.scrollTo(0, {})", y), false)
+                .await?;
+
+            let tile_height = viewport_height.min(height - y);
+            let clip = serde_json::json!({ "x": 0, "y": y, "width": width, "height": tile_height, "scale": 1 });
+            tiles.push(self.capture_region(clip, false).await?);
+
+            y += tile_height;
+        }
+
+        Ok(tiles)
+    }
+}
+
+#[async_trait]
+impl PageContext for PageContextImpl {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn browser_id(&self) -> &str {
+        &self.browser_id
+    }
+
+    async fn navigate(&self, url: &str, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let _slot = self.navigation_slot().await?;
+
+        // Subscribe before navigating so redirects and the final response are not missed;
+        // the subscription enables the Network domain
+        let mut requests = self
+            .cdp_client
+            .subscribe_events("Network.requestWillBeSent")
+            .await?;
+        let mut responses = self
+            .cdp_client
+            .subscribe_events("Network.responseReceived")
+            .await?;
+        let main_frame_id = self.main_frame_id().await;
+
+        // Navigate using CDP
+        let nav_result = self.cdp_client.navigate(url, options.referer.as_deref()).await?;
+
+        // Wait for load state if specified
+        Self::wait_for_load_state(&options.wait_until).await;
+
+        let (url, status_code) = Self::main_frame_response(&mut responses, main_frame_id.as_deref())
+            .unwrap_or((nav_result.url, 200));
+        let (mut redirect_chain, redirect_chain_truncated) =
+            Self::redirect_hops(&mut requests, main_frame_id.as_deref(), self.max_redirect_hops);
+        redirect_chain.push(RedirectHop {
+            url: url.clone(),
+            status_code,
+        });
+
+        Ok(NavigationResult {
+            url,
+            status_code,
+            is_loaded: true,
+            redirect_chain,
+            redirect_chain_truncated,
+        })
+    }
+
+    async fn get_content(&self) -> Result<String, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        self.cdp_client.get_content().await
+    }
+
+    async fn set_content(&self, html: &str) -> Result<(), Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        self.cdp_client.set_content(html).await
+    }
+
+    async fn reload(&self, ignore_cache: bool, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let _slot = self.navigation_slot().await?;
+
+        // Subscribe before reloading so redirects and the main-frame response are not missed
+        let mut requests = self
+            .cdp_client
+            .subscribe_events("Network.requestWillBeSent")
+            .await?;
+        let mut events = self
+            .cdp_client
+            .subscribe_events("Network.responseReceived")
+            .await?;
+        let main_frame_id = self.main_frame_id().await;
+
+        self.cdp_client.reload(ignore_cache).await?;
+
+        Self::wait_for_load_state(&options.wait_until).await;
+        let is_loaded = self.wait_for_ready_state().await;
+
+        let (url, status_code) =
+            match Self::main_frame_response(&mut events, main_frame_id.as_deref()) {
+                Some(response) => response,
+                None => {
+                    let url = match self.cdp_client.evaluate("window.location.href", false).await? {
+                        crate::cdp::traits::EvaluationResult::String(url) => url,
+                        _ => String::new(),
+                    };
+                    (url, 200)
+                }
+            };
+
+        let (mut redirect_chain, redirect_chain_truncated) =
+            Self::redirect_hops(&mut requests, main_frame_id.as_deref(), self.max_redirect_hops);
+        redirect_chain.push(RedirectHop {
+            url: url.clone(),
+            status_code,
+        });
+
+        Ok(NavigationResult {
+            url,
+            status_code,
+            is_loaded,
+            redirect_chain,
+            redirect_chain_truncated,
+        })
+    }
+
+    async fn go_back(&self, delta: u32, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        self.navigate_history(-(delta.max(1) as i64), options).await
+    }
+
+    async fn go_forward(&self, delta: u32, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        self.navigate_history(delta.max(1) as i64, options).await
+    }
+
+    async fn wait_for_navigation(&self, url_pattern: Option<&str>, options: NavigationOptions) -> Result<NavigationResult, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let options = NavigationOptions {
+            timeout: DefaultTimeouts::resolve(options.timeout, self.default_timeouts.navigation_ms, 0),
+            ..options
+        };
+        NavigationWatch::start(self.cdp_client.as_ref())
+            .await?
+            .wait(url_pattern, options)
+            .await
+    }
+
+    async fn evaluate(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let result = self.cdp_client.evaluate(script, await_promise).await?;
+        tracing::debug!("PageContext::evaluate: CDP returned {:?}", result);
+
+        let session_result = Self::session_result(result);
+        tracing::debug!("PageContext::evaluate: returning {:?}", session_result);
+        Ok(session_result)
+    }
+
+    async fn isolated_world(&self) -> Result<i64, Error> {
+        let frame_id = self
+            .main_frame_id()
+            .await
+            .ok_or_else(|| Error::cdp("Page has no main frame"))?;
+        self.watch_isolated_worlds().await?;
+
+        let mut worlds = self.isolated_worlds.lock().await;
+        if let Some(context_id) = worlds.get(&frame_id) {
+            return Ok(*context_id);
+        }
+
+        let created = self
+            .cdp_client
+            .call_method(
+                "Page.createIsolatedWorld",
+                serde_json::json!({
+                    "frameId": frame_id,
+                    "worldName": ISOLATED_WORLD_NAME,
+                    "grantUniveralAccess": true,
+                }),
+            )
+            .await?;
+        let context_id = created["executionContextId"]
+            .as_i64()
+            .ok_or_else(|| Error::cdp("No executionContextId in Page.createIsolatedWorld response"))?;
+        worlds.insert(frame_id, context_id);
+
+        Ok(context_id)
+    }
+
+    async fn evaluate_isolated(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let context_id = self.isolated_world().await?;
+        let result = self.cdp_client.evaluate_in_context(script, await_promise, context_id).await?;
+        Ok(Self::session_result(result))
+    }
+
+    async fn screenshot(&self, options: ScreenshotOptions) -> Result<Vec<u8>, Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        // Capture losslessly, then convert to the requested format/quality/size
+        let png = if options.full_page {
+            self.full_page_screenshot().await?
+        } else {
+            self.cdp_client.screenshot(crate::cdp::traits::ScreenshotFormat::Png).await?
+        };
+
+        screenshot::process(png, &options)
+    }
+
+    async fn set_viewport(&self, width: u32, height: u32, device_scale_factor: f64) -> Result<(), Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let mut metrics = self.device_metrics.lock().await;
+        metrics.viewport = Some((width, height, device_scale_factor));
+        self.apply_device_metrics(&mut metrics).await
+    }
+
+    async fn set_screen_size(&self, width: u32, height: u32) -> Result<(), Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let mut metrics = self.device_metrics.lock().await;
+        metrics.screen = Some((width, height));
+        self.apply_device_metrics(&mut metrics).await
+    }
+
+    async fn set_touch_emulation(&self, enabled: bool) -> Result<(), Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let mut params = serde_json::json!({ "enabled": enabled });
+        if enabled {
+            params["maxTouchPoints"] = serde_json::json!(MAX_TOUCH_POINTS);
+        }
+        self.cdp_client
+            .call_method("Emulation.setTouchEmulationEnabled", params)
+            .await?;
+        self.touch_enabled.store(enabled, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    fn touch_emulation_enabled(&self) -> bool {
+        self.touch_enabled.load(Ordering::SeqCst)
+    }
+
+    async fn set_http_credentials(&self, credentials: Option<HttpCredentials>) -> Result<(), Error> {
+        let active = *self.is_active.read().await;
+        if !active {
+            return Err(Error::page_not_found(&self.id));
+        }
+
+        let has_credentials = credentials.is_some();
+        *self.http_credentials.write().await = credentials;
+
+        // Nothing to answer yet; once started, cleared credentials cancel challenges
+        if has_credentials {
+            self.handle_auth.store(true, Ordering::SeqCst);
+            self.start_fetch_handler().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<(), Error> {
+        tracing::info!("PageContext::close: Closing page {}", self.id);
+
+        // Check if page is still active before attempting close
+        let active = *self.is_active.read().await;
+        if !active {
+            tracing::warn!("PageContext::close: Page {} is already inactive", self.id);
+            return Ok(());
+        }
+
+        if let Err(e) = self.stop_fetch_handler().await {
+            tracing::warn!("PageContext::close: Failed to disable Fetch for page {}: {}", self.id, e);
+        }
+        if let Some(task) = self.dialog_handler.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            task.abort();
+        }
+
+        // Try to close the page via CDP - Page.close command will close the page in the browser
+        tracing::debug!("PageContext::close: Sending Page.close CDP command for page {}", self.id);
+        let close_result = self
+            .cdp_client
+            .call_method("Page.close", serde_json::json!({}))
+            .await;
+
+        match &close_result {
+            Ok(_) => {
+                tracing::info!("PageContext::close: Page.close CDP command succeeded for page {}", self.id);
+            }
+            Err(e) => {
+                tracing::warn!("PageContext::close: Page.close CDP command failed for page {}: {}", self.id, e);
+                tracing::warn!("PageContext::close: The page may not be closed in the browser");
+            }
+        }
+
+        // Mark as inactive regardless of CDP result
+        // This ensures the page context is removed from browser's management
+        tracing::debug!("PageContext::close: Marking page {} as inactive", self.id);
+        *self.is_active.write().await = false;
+
+        tracing::info!("PageContext::close: Page {} close completed", self.id);
+        Ok(())
+    }
+
+    fn is_active(&self) -> bool {
+        // Use try_read to avoid blocking in sync context
+        self.is_active
+            .try_read()
             .ok()
             .map(|active| *active)
             .unwrap_or(false)
     }
 
-    fn get_cdp_client(&self) -> Arc<dyn crate::cdp::traits::CdpClient> {
-        self.cdp_client.clone()
-    }
-}
+    fn default_timeouts(&self) -> DefaultTimeouts {
+        self.default_timeouts
+    }
+
+    fn console_logs(&self, cursor: u64) -> ConsoleLogs {
+        self.console.lock().unwrap_or_else(|e| e.into_inner()).since(cursor)
+    }
+
+    fn is_crashed(&self) -> bool {
+        self.crashed.load(Ordering::SeqCst)
+    }
+
+    fn activity(&self) -> PageActivity {
+        self.activity.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn touch(&self) {
+        self.activity.lock().unwrap_or_else(|e| e.into_inner()).last_activity = std::time::SystemTime::now();
+    }
+
+    fn get_cdp_client(&self) -> Arc<dyn crate::cdp::traits::CdpClient> {
+        self.cdp_client.clone()
+    }
+}
+
+/// Match `url` against a pattern in which `*` matches any sequence of characters
+///
+/// A missing or empty pattern matches every URL.
+pub fn url_matches(pattern: Option<&str>, url: &str) -> bool {
+    let pattern = match pattern {
+        Some(pattern) if !pattern.is_empty() => pattern,
+        _ => return true,
+    };
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if parts.len() == 1 {
+        return url == pattern;
+    }
+    if !url.starts_with(first) || !url[first.len()..].ends_with(last) {
+        return false;
+    }
+
+    // Middle parts must appear in order between the prefix and suffix
+    let mut rest = &url[first.len()..url.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_isolated_world_does_not_see_main_world_globals() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        cdp_client.set_global("window.__pageSecret", serde_json::json!("main world")).await;
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+
+        let main = page.evaluate("window.__pageSecret", false).await.unwrap();
+        assert!(matches!(main, EvaluationResult::String(s) if s == "main world"));
+        let isolated = page.evaluate_isolated("window.__pageSecret", false).await.unwrap();
+        assert!(matches!(isolated, EvaluationResult::Null));
+
+        // One world per frame, reused until its contexts are cleared
+        page.evaluate_isolated("1 + 1", false).await.unwrap();
+        let created = cdp_client.recorded_calls("Page.createIsolatedWorld").await;
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0]["frameId"], crate::cdp::mock::MOCK_FRAME_ID);
+        assert_eq!(created[0]["worldName"], ISOLATED_WORLD_NAME);
+
+        cdp_client
+            .emit_event("Runtime.executionContextsCleared", serde_json::json!({}))
+            .await;
+        for _ in 0..50 {
+            if page.isolated_worlds.lock().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        page.evaluate_isolated("1 + 1", false).await.unwrap();
+        assert_eq!(cdp_client.recorded_calls("Page.createIsolatedWorld").await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_console_messages_logged_during_evaluate_are_buffered() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let console_call = |level: &str, text: &str| {
+            serde_json::json!({
+                "type": level,
+                "args": [{ "type": "string", "value": text }, { "type": "number", "value": 42 }],
+                "timestamp": 1_700_000_000_000.0,
+                "stackTrace": { "callFrames": [{ "url": "https://example.com/app.js", "lineNumber": 3, "columnNumber": 8 }] },
+            })
+        };
+        cdp_client
+            .stub_events(
+                "Runtime.evaluate",
+                vec![
+                    ("Runtime.consoleAPICalled", console_call("log", "saved")),
+                    ("Runtime.consoleAPICalled", console_call("warning", "slow")),
+                ],
+            )
+            .await;
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+        page.start_console_log().await.unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        page.evaluate("console.log('saved', 42); console.warn('slow', 42)", false).await.unwrap();
+        for _ in 0..50 {
+            if page.console_logs(0).entries.len() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let logs = page.console_logs(0);
+        assert_eq!(logs.entries.len(), 2);
+        assert_eq!(logs.entries[0].level, "log");
+        assert_eq!(logs.entries[0].args, vec!["saved", "42"]);
+        assert_eq!(logs.entries[0].url, "https://example.com/app.js");
+        assert_eq!((logs.entries[0].line, logs.entries[0].column), (3, 8));
+        assert_eq!(logs.entries[1].level, "warning");
+        assert_eq!(logs.next_cursor, 2);
+
+        // Reading from the returned cursor yields only newer messages
+        assert!(page.console_logs(logs.next_cursor).entries.is_empty());
+        page.evaluate("console.log('again')", false).await.unwrap();
+        for _ in 0..50 {
+            if !page.console_logs(logs.next_cursor).entries.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let newer = page.console_logs(logs.next_cursor);
+        assert_eq!(newer.entries.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![3, 4]);
+    }
 
     #[tokio::test]
     async fn test_page_creation() {
@@ -267,6 +1616,110 @@ mod tests {
         assert_eq!(page.browser_id(), "test-browser");
     }
 
+    #[test]
+    fn test_url_matches() {
+        assert!(url_matches(None, "https://example.com/"));
+        assert!(url_matches(Some(""), "https://example.com/"));
+        assert!(url_matches(Some("https://example.com/"), "https://example.com/"));
+        assert!(url_matches(Some("*/checkout*"), "https://shop.test/checkout?step=2"));
+        assert!(url_matches(Some("https://*.test/*/done"), "https://a.test/orders/1/done"));
+        assert!(!url_matches(Some("https://*.test/*/done"), "https://a.test/done"));
+        assert!(!url_matches(Some("*/checkout"), "https://shop.test/checkout?step=2"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_navigation_after_location_change() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        let (waited, _) = tokio::join!(
+            page.wait_for_navigation(
+                Some("*/done"),
+                NavigationOptions {
+                    timeout: 5000,
+                    wait_until: LoadState::NetworkIdle,
+                    ..Default::default()
+                },
+            ),
+            async {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+                page.evaluate("location.href = 'https://example.com/pending'", false).await.unwrap();
+                page.evaluate("location.href = 'https://example.com/done'", false).await.unwrap();
+            },
+        );
+
+        assert_eq!(waited.unwrap().url, "https://example.com/done");
+    }
+
+    #[tokio::test]
+    async fn test_set_viewport_updates_window_screen() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+        cdp_client
+            .stub_method("Page.addScriptToEvaluateOnNewDocument", serde_json::json!({ "identifier": "screen-1" }))
+            .await;
+
+        page.set_viewport(1280, 720, 2.0).await.unwrap();
+        page.set_viewport(1440, 900, 1.0).await.unwrap();
+
+        let metrics = cdp_client.recorded_calls("Emulation.setDeviceMetricsOverride").await;
+        let last = metrics.last().unwrap();
+        assert_eq!((last["width"].as_u64(), last["height"].as_u64()), (Some(1440), Some(900)));
+        assert_eq!((last["screenWidth"].as_u64(), last["screenHeight"].as_u64()), (Some(1440), Some(900)));
+
+        // window.screen.width follows the latest viewport in the current and in new documents
+        let added = cdp_client.recorded_calls("Page.addScriptToEvaluateOnNewDocument").await;
+        let source = added.last().unwrap()["source"].as_str().unwrap().to_string();
+        assert_eq!(source, screen_size_script(1440, 900));
+        assert!(source.contains("width: 1440"));
+        let removed = cdp_client.recorded_calls("Page.removeScriptToEvaluateOnNewDocument").await;
+        assert_eq!(removed.len(), 1);
+        // The first call's script is removed by the identifier the browser returned for it
+        assert_eq!(removed[0]["identifier"], "screen-1");
+        let evaluated = cdp_client.recorded_calls("Runtime.evaluate").await;
+        assert_eq!(evaluated.last().unwrap()["expression"].as_str(), Some(source.as_str()));
+
+        // A larger profile screen is kept; a smaller one is raised to the viewport
+        page.set_screen_size(2560, 1440).await.unwrap();
+        page.set_viewport(1280, 720, 1.0).await.unwrap();
+        let last = cdp_client.recorded_calls("Emulation.setDeviceMetricsOverride").await.pop().unwrap();
+        assert_eq!((last["screenWidth"].as_u64(), last["screenHeight"].as_u64()), (Some(2560), Some(1440)));
+        page.set_screen_size(800, 600).await.unwrap();
+        let last = cdp_client.recorded_calls("Emulation.setDeviceMetricsOverride").await.pop().unwrap();
+        assert_eq!((last["screenWidth"].as_u64(), last["screenHeight"].as_u64()), (Some(1280), Some(720)));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_navigation_timeout() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        let result = page
+            .wait_for_navigation(
+                None,
+                NavigationOptions {
+                    timeout: 50,
+                    wait_until: LoadState::Load,
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+
     #[tokio::test]
     async fn test_page_navigate() {
         let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
@@ -282,6 +1735,7 @@ mod tests {
                 NavigationOptions {
                     timeout: 30000,
                     wait_until: LoadState::Load,
+                    ..Default::default()
                 },
             )
             .await
@@ -290,6 +1744,157 @@ mod tests {
         assert_eq!(result.url, "https://example.com");
     }
 
+    #[tokio::test]
+    async fn test_page_navigate_forwards_referer() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+
+        let options = NavigationOptions {
+            referer: Some("https://www.google.com/".to_string()),
+            ..Default::default()
+        };
+        page.navigate("https://example.com/a", options).await.unwrap();
+        page.navigate("https://example.com/b", NavigationOptions::default()).await.unwrap();
+
+        let calls = cdp_client.recorded_calls("Page.navigate").await;
+        assert_eq!(calls[0]["referrer"], "https://www.google.com/");
+        assert!(calls[1].get("referrer").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_page_navigate_records_redirect_chain() {
+        use crate::cdp::mock::MOCK_FRAME_ID;
+
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let document = |extra: serde_json::Value| {
+            let mut params = serde_json::json!({ "frameId": MOCK_FRAME_ID, "type": "Document" });
+            params.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            params
+        };
+        cdp_client
+            .stub_events(
+                "Page.navigate",
+                vec![
+                    ("Network.requestWillBeSent", document(serde_json::json!({
+                        "request": { "url": "http://example.com/old" }
+                    }))),
+                    ("Network.requestWillBeSent", document(serde_json::json!({
+                        "request": { "url": "https://example.com/new" },
+                        "redirectResponse": { "url": "http://example.com/old", "status": 302 }
+                    }))),
+                    ("Network.responseReceived", document(serde_json::json!({
+                        "response": { "url": "https://example.com/new", "status": 200 }
+                    }))),
+                ],
+            )
+            .await;
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        let result = page
+            .navigate("http://example.com/old", NavigationOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.url, "https://example.com/new");
+        assert_eq!(result.status_code, 200);
+        assert_eq!(
+            result.redirect_chain,
+            vec![
+                RedirectHop { url: "http://example.com/old".to_string(), status_code: 302 },
+                RedirectHop { url: "https://example.com/new".to_string(), status_code: 200 },
+            ]
+        );
+        assert!(!result.redirect_chain_truncated);
+    }
+
+    #[tokio::test]
+    async fn test_go_back_reports_main_frame_status_and_honours_timeout() {
+        use crate::cdp::mock::MOCK_FRAME_ID;
+
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+        for url in ["https://a.example/", "https://b.example/"] {
+            page.navigate(url, NavigationOptions::default()).await.unwrap();
+        }
+        cdp_client
+            .stub_events(
+                "Page.navigateToHistoryEntry",
+                vec![("Network.responseReceived", serde_json::json!({
+                    "frameId": MOCK_FRAME_ID,
+                    "type": "Document",
+                    "response": { "url": "https://a.example/", "status": 404 },
+                }))],
+            )
+            .await;
+
+        let result = page.go_back(1, NavigationOptions::default()).await.unwrap();
+        assert_eq!(result.url, "https://a.example/");
+        assert_eq!(result.status_code, 404);
+        assert_eq!(result.redirect_chain.last().unwrap().status_code, 404);
+
+        // A history navigation that never commits fails after the caller's timeout
+        cdp_client.stub_method("Page.navigateToHistoryEntry", serde_json::json!({})).await;
+        let options = NavigationOptions { timeout: 100, ..Default::default() };
+        let started = std::time::Instant::now();
+        let err = page.go_forward(1, options).await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "{:?}", err);
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_page_navigate_truncates_redirect_chain_beyond_cap() {
+        use crate::cdp::mock::MOCK_FRAME_ID;
+
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let hop_url = |i: usize| format!("https://example.com/hop/{}?{}", i, "x".repeat(2048));
+        let mut events: Vec<(&str, serde_json::Value)> = (1..=10)
+            .map(|i| {
+                ("Network.requestWillBeSent", serde_json::json!({
+                    "frameId": MOCK_FRAME_ID,
+                    "type": "Document",
+                    "request": { "url": hop_url(i) },
+                    "redirectResponse": { "url": hop_url(i - 1), "status": 301 },
+                }))
+            })
+            .collect();
+        events.push(("Network.responseReceived", serde_json::json!({
+            "frameId": MOCK_FRAME_ID,
+            "type": "Document",
+            "response": { "url": "https://example.com/final", "status": 200 },
+        })));
+        cdp_client.stub_events("Page.navigate", events).await;
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        )
+        .with_max_redirect_hops(3);
+
+        let result = page
+            .navigate(&hop_url(0), NavigationOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.url, "https://example.com/final");
+        assert_eq!(result.status_code, 200);
+        assert!(result.redirect_chain_truncated);
+        let urls: Vec<_> = result.redirect_chain.iter().map(|hop| hop.url.clone()).collect();
+        assert_eq!(urls, vec![hop_url(0), hop_url(1), hop_url(2), "https://example.com/final".to_string()]);
+        assert_eq!(result.redirect_chain.last().unwrap().status_code, 200);
+    }
+
     #[tokio::test]
     async fn test_page_evaluate() {
         let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
@@ -318,6 +1923,8 @@ mod tests {
                 quality: None,
                 full_page: false,
                 clip: None,
+                max_width: None,
+                max_height: None,
             })
             .await
             .unwrap();
@@ -338,4 +1945,307 @@ mod tests {
         page.close().await.unwrap();
         assert!(!page.is_active());
     }
+
+    #[tokio::test]
+    async fn test_page_reload_returns_current_url() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        page.navigate("https://example.com/article", NavigationOptions::default())
+            .await
+            .unwrap();
+
+        let result = page.reload(true, NavigationOptions::default()).await.unwrap();
+        assert_eq!(result.url, "https://example.com/article");
+        assert_eq!(result.status_code, 200);
+        assert!(result.is_loaded);
+    }
+
+    #[test]
+    fn test_main_frame_response_ignores_subframes() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let event = |frame: &str, kind: &str, url: &str, status: u16| crate::cdp::traits::CdpEvent {
+            method: "Network.responseReceived".to_string(),
+            params: serde_json::json!({
+                "frameId": frame,
+                "type": kind,
+                "response": { "url": url, "status": status }
+            }),
+            session_id: None,
+        };
+        tx.try_send(event("main", "Document", "https://example.com/", 404)).unwrap();
+        tx.try_send(event("child", "Document", "https://ads.example/", 200)).unwrap();
+        tx.try_send(event("main", "Script", "https://example.com/app.js", 200)).unwrap();
+
+        let response = PageContextImpl::main_frame_response(&mut rx, Some("main"));
+        assert_eq!(response, Some(("https://example.com/".to_string(), 404)));
+    }
+
+    #[tokio::test]
+    async fn test_page_go_back_without_history() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        let result = page.go_back(1, NavigationOptions::default()).await;
+        assert!(matches!(result, Err(Error::NavigationFailed(_))));
+
+        let result = page.go_forward(1, NavigationOptions::default()).await;
+        assert!(matches!(result, Err(Error::NavigationFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_page_history_navigation() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        for url in ["https://a.example", "https://b.example", "https://c.example"] {
+            page.navigate(url, NavigationOptions::default()).await.unwrap();
+        }
+
+        let result = page.go_back(2, NavigationOptions::default()).await.unwrap();
+        assert_eq!(result.url, "https://a.example");
+        assert!(result.is_loaded);
+
+        // Only two entries ahead of the current one
+        assert!(matches!(page.go_forward(3, NavigationOptions::default()).await, Err(Error::NavigationFailed(_))));
+
+        let result = page.go_forward(1, NavigationOptions::default()).await.unwrap();
+        assert_eq!(result.url, "https://b.example");
+    }
+
+    #[tokio::test]
+    async fn test_page_http_credentials_answer_auth_challenge() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+
+        page.set_http_credentials(Some(HttpCredentials {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        }))
+        .await
+        .unwrap();
+
+        let enable = cdp_client.recorded_calls("Fetch.enable").await;
+        assert_eq!(enable.len(), 1);
+        assert_eq!(enable[0]["handleAuthRequests"], true);
+
+        let auth_responses = |expected: usize| {
+            let cdp_client = cdp_client.clone();
+            async move {
+                for _ in 0..50 {
+                    let calls = cdp_client.recorded_calls("Fetch.continueWithAuth").await;
+                    if calls.len() >= expected {
+                        return calls;
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+                }
+                panic!("Fetch.authRequired was not answered");
+            }
+        };
+
+        cdp_client
+            .emit_event("Fetch.authRequired", serde_json::json!({ "requestId": "auth-1" }))
+            .await;
+        let calls = auth_responses(1).await;
+        assert_eq!(calls[0]["requestId"], "auth-1");
+        assert_eq!(calls[0]["authChallengeResponse"]["response"], "ProvideCredentials");
+        assert_eq!(calls[0]["authChallengeResponse"]["username"], "user");
+        assert_eq!(calls[0]["authChallengeResponse"]["password"], "pass");
+
+        // Setting credentials again does not re-enable Fetch
+        page.set_http_credentials(Some(HttpCredentials {
+            username: "other".to_string(),
+            password: "secret".to_string(),
+        }))
+        .await
+        .unwrap();
+        assert_eq!(cdp_client.recorded_calls("Fetch.enable").await.len(), 1);
+
+        // Cleared credentials cancel the challenge instead of blocking
+        page.set_http_credentials(None).await.unwrap();
+        cdp_client
+            .emit_event("Fetch.authRequired", serde_json::json!({ "requestId": "auth-2" }))
+            .await;
+        let calls = auth_responses(2).await;
+        assert_eq!(calls[1]["requestId"], "auth-2");
+        assert_eq!(calls[1]["authChallengeResponse"]["response"], "CancelAuth");
+        assert!(cdp_client.recorded_calls("Fetch.disable").await.is_empty());
+
+        // Fetch is only turned off when the page closes
+        page.close().await.unwrap();
+        assert_eq!(cdp_client.recorded_calls("Fetch.disable").await.len(), 1);
+        assert_eq!(cdp_client.recorded_calls("Fetch.enable").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_url_policy_fails_rejected_document_requests() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let policy = UrlPolicy {
+            deny: vec!["*.internal".to_string()],
+            ..Default::default()
+        };
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        )
+        .with_url_policy(Arc::new(policy));
+        page.enforce_url_policy().await.unwrap();
+
+        let enable = cdp_client.recorded_calls("Fetch.enable").await;
+        assert_eq!(enable.len(), 1);
+        assert_eq!(enable[0]["patterns"][0]["resourceType"], "Document");
+        assert_eq!(enable[0]["handleAuthRequests"], false);
+
+        let paused = |id: &str, resource_type: &str, url: &str| {
+            serde_json::json!({ "requestId": id, "resourceType": resource_type, "request": { "url": url } })
+        };
+        // A redirect to a denied host arrives as a new paused request
+        for (id, resource_type, url) in [
+            ("redirect", "Document", "http://metadata.internal/"),
+            ("allowed", "Document", "https://example.com/"),
+            ("image", "Image", "http://cdn.internal/a.png"),
+        ] {
+            cdp_client.emit_event("Fetch.requestPaused", paused(id, resource_type, url)).await;
+        }
+
+        let answered = |method: &'static str| {
+            let cdp_client = cdp_client.clone();
+            async move { cdp_client.recorded_calls(method).await }
+        };
+        for _ in 0..50 {
+            if answered("Fetch.failRequest").await.len() + answered("Fetch.continueRequest").await.len() == 3 {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+        let failed = answered("Fetch.failRequest").await;
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0]["requestId"], "redirect");
+        assert_eq!(failed[0]["errorReason"], "BlockedByClient");
+        let mut continued: Vec<String> = answered("Fetch.continueRequest")
+            .await
+            .iter()
+            .map(|call| call["requestId"].as_str().unwrap().to_string())
+            .collect();
+        continued.sort();
+        assert_eq!(continued, ["allowed", "image"]);
+
+        // Credentials widen the same interception to every request
+        page.set_http_credentials(Some(HttpCredentials {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        }))
+        .await
+        .unwrap();
+        let enable = cdp_client.recorded_calls("Fetch.enable").await;
+        assert_eq!(enable.len(), 2);
+        assert_eq!(enable[1]["handleAuthRequests"], true);
+        assert!(enable[1]["patterns"][0].get("resourceType").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_full_page_screenshot_single_capture() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        cdp_client.set_layout_size(40.0, 3000.0, 500.0).await;
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+
+        let data = page
+            .screenshot(ScreenshotOptions { full_page: true, ..Default::default() })
+            .await
+            .unwrap();
+
+        let image = image::load_from_memory(&data).unwrap();
+        assert_eq!((image.width(), image.height()), (40, 3000));
+
+        let captures = cdp_client.recorded_calls("Page.captureScreenshot").await;
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0]["captureBeyondViewport"], true);
+    }
+
+    #[tokio::test]
+    async fn test_full_page_screenshot_stitches_tall_page() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        // Taller than a single capture allows; the last tile is partial
+        cdp_client.set_layout_size(8.0, 20_100.0, 1000.0).await;
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client.clone(),
+        );
+
+        let data = page
+            .screenshot(ScreenshotOptions { full_page: true, ..Default::default() })
+            .await
+            .unwrap();
+
+        let captures = cdp_client.recorded_calls("Page.captureScreenshot").await;
+        assert_eq!(captures.len(), 21);
+        assert_eq!(captures[20]["clip"]["y"], 20_000.0);
+        assert_eq!(captures[20]["clip"]["height"], 100.0);
+
+        // Every row of the stitched image comes from the tile covering it
+        let image = image::load_from_memory_with_format(&data, image::ImageFormat::Png)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!((image.width(), image.height()), (8, 20_100));
+        for y in [0u32, 999, 1000, 12_345, 20_099] {
+            assert_eq!(image.get_pixel(3, y)[0], (y % 256) as u8, "row {}", y);
+        }
+
+        // Fixed/sticky elements are hidden after the first tile and restored afterwards
+        let scripts: Vec<String> = cdp_client
+            .recorded_calls("Runtime.evaluate")
+            .await
+            .iter()
+            .filter_map(|call| call["expression"].as_str().map(str::to_string))
+            .collect();
+        let hide = scripts.iter().position(|s| s == HIDE_FIXED_ELEMENTS_SCRIPT).unwrap();
+        let restore = scripts.iter().position(|s| s == RESTORE_FIXED_ELEMENTS_SCRIPT).unwrap();
+        assert!(hide < restore);
+        assert_eq!(scripts.iter().filter(|s| *s == HIDE_FIXED_ELEMENTS_SCRIPT).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_converts_and_resizes() {
+        let cdp_client = Arc::new(crate::cdp::mock::MockCdpClient::new());
+        let page = PageContextImpl::new(
+            "test-browser".to_string(),
+            crate::session::traits::PageOptions::default(),
+            cdp_client,
+        );
+
+        let data = page
+            .screenshot(ScreenshotOptions {
+                format: ScreenshotFormat::Jpeg,
+                quality: Some(60),
+                max_width: Some(32),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(&data[..3], &[0xFF, 0xD8, 0xFF]);
+        assert_eq!(screenshot::dimensions(&data), Some((32, 24)));
+    }
 }
@@ -0,0 +1,344 @@
+//! Screenshot post-processing
+//!
+//! Screenshots are captured losslessly as PNG and re-encoded here, so the
+//! requested format, quality and size limits apply regardless of what the
+//! browser supports natively. Captures can also be compared pixel-wise
+//! against a baseline image for visual regression checks.
+
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+
+use crate::session::traits::{ScreenshotFormat, ScreenshotOptions};
+use crate::Error;
+
+/// Quality used for JPEG/WebP when none is requested
+pub const DEFAULT_QUALITY: u8 = 100;
+
+/// Default quality of each lossy format, applied when a request leaves quality unset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultQuality {
+    /// JPEG quality (1-100)
+    pub jpeg: u8,
+    /// WebP quality (1-100)
+    pub webp: u8,
+}
+
+impl Default for DefaultQuality {
+    fn default() -> Self {
+        Self {
+            jpeg: DEFAULT_QUALITY,
+            webp: DEFAULT_QUALITY,
+        }
+    }
+}
+
+impl DefaultQuality {
+    /// Quality to encode `format` with: `requested` if set, otherwise the format's default
+    ///
+    /// PNG is lossless and never has a quality.
+    pub fn resolve(&self, format: ScreenshotFormat, requested: Option<u8>) -> Option<u8> {
+        match format {
+            ScreenshotFormat::Png => None,
+            ScreenshotFormat::Jpeg => Some(requested.unwrap_or(self.jpeg)),
+            ScreenshotFormat::WebP => Some(requested.unwrap_or(self.webp)),
+        }
+    }
+}
+
+/// Check that a JPEG/WebP quality is within 1-100
+pub fn validate_quality(name: &str, quality: i64) -> Result<(), Error> {
+    if !(1..=100).contains(&quality) {
+        return Err(Error::configuration(format!("{} must be between 1 and 100, got {}", name, quality)));
+    }
+    Ok(())
+}
+
+/// Re-encode a captured PNG according to `options`
+///
+/// PNG output stays lossless; the captured bytes are returned untouched when
+/// no conversion or resizing is needed.
+pub fn process(png: Vec<u8>, options: &ScreenshotOptions) -> Result<Vec<u8>, Error> {
+    let resize = options.max_width.is_some() || options.max_height.is_some();
+    if matches!(options.format, ScreenshotFormat::Png) && !resize {
+        return Ok(png);
+    }
+
+    let image = decode(&png)?;
+    let image = fit_within(image, options.max_width, options.max_height);
+    encode(&image, options.format, options.quality)
+}
+
+/// Decode a PNG screenshot
+pub fn decode(png: &[u8]) -> Result<DynamicImage, Error> {
+    image::load_from_memory_with_format(png, ImageFormat::Png)
+        .map_err(|e| Error::internal(format!("Failed to decode screenshot: {}", e)))
+}
+
+/// Shrink an image to fit within the given bounds, preserving aspect ratio
+///
+/// Images already within bounds are returned unchanged; images are never enlarged.
+pub fn fit_within(image: DynamicImage, max_width: Option<u32>, max_height: Option<u32>) -> DynamicImage {
+    let max_width = max_width.filter(|w| *w > 0).unwrap_or(u32::MAX);
+    let max_height = max_height.filter(|h| *h > 0).unwrap_or(u32::MAX);
+
+    if image.width() <= max_width && image.height() <= max_height {
+        return image;
+    }
+
+    image.resize(
+        max_width.min(image.width()),
+        max_height.min(image.height()),
+        image::imageops::FilterType::Lanczos3,
+    )
+}
+
+/// Encode an image in the requested format
+///
+/// `quality` (1-100) applies to JPEG and WebP and defaults to [`DEFAULT_QUALITY`].
+pub fn encode(image: &DynamicImage, format: ScreenshotFormat, quality: Option<u8>) -> Result<Vec<u8>, Error> {
+    let quality = quality.unwrap_or(DEFAULT_QUALITY).clamp(1, 100);
+    let encode_error = |e: image::ImageError| Error::internal(format!("Failed to encode screenshot: {}", e));
+    let mut buffer = std::io::Cursor::new(Vec::new());
+
+    match format {
+        ScreenshotFormat::Png => {
+            image.write_to(&mut buffer, ImageFormat::Png).map_err(encode_error)?;
+        }
+        ScreenshotFormat::Jpeg => {
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, quality)
+                .encode_image(&image.to_rgb8())
+                .map_err(encode_error)?;
+        }
+        ScreenshotFormat::WebP => {
+            // The `image` crate only encodes lossless WebP
+            let rgba = image.to_rgba8();
+            let encoded = webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height()).encode(quality as f32);
+            return Ok(encoded.to_vec());
+        }
+    }
+
+    Ok(buffer.into_inner())
+}
+
+/// Composite PNG tiles top to bottom into one image
+pub fn stitch_tiles(tiles: &[Vec<u8>]) -> Result<RgbaImage, Error> {
+    let decoded = tiles
+        .iter()
+        .map(|tile| decode(tile).map(|image| image.to_rgba8()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let width = decoded.iter().map(|tile| tile.width()).max().unwrap_or(0);
+    let height = decoded.iter().map(|tile| tile.height()).sum();
+    let mut canvas = RgbaImage::new(width, height);
+
+    let mut offset = 0i64;
+    for tile in &decoded {
+        image::imageops::replace(&mut canvas, tile, 0, offset);
+        offset += tile.height() as i64;
+    }
+
+    Ok(canvas)
+}
+
+/// Result of comparing a screenshot with a baseline image
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    /// Width of both images in pixels
+    pub width: u32,
+    /// Height of both images in pixels
+    pub height: u32,
+    /// Pixels differing by more than the tolerance in any channel
+    pub diff_pixels: u64,
+    /// Changed pixels in red over a faded copy of the screenshot, if requested
+    pub diff_image: Option<RgbaImage>,
+}
+
+impl ImageDiff {
+    /// Number of pixels compared
+    pub fn total_pixels(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+
+    /// Changed pixels as a percentage (0-100) of all pixels
+    pub fn diff_percentage(&self) -> f64 {
+        match self.total_pixels() {
+            0 => 0.0,
+            total => self.diff_pixels as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+/// Colour marking changed pixels in the diff image
+const DIFF_HIGHLIGHT: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+/// Compare a PNG screenshot pixel by pixel with an encoded baseline image
+///
+/// A pixel counts as changed when any RGBA channel differs by more than
+/// `tolerance`. The baseline may be PNG, JPEG or WebP; images of different
+/// sizes are rejected with a configuration error.
+pub fn compare(screenshot: &[u8], baseline: &[u8], tolerance: u8, with_diff_image: bool) -> Result<ImageDiff, Error> {
+    let actual = decode(screenshot)?.to_rgba8();
+    let expected = image::load_from_memory(baseline)
+        .map_err(|e| Error::configuration(format!("Failed to decode baseline image: {}", e)))?
+        .to_rgba8();
+
+    if actual.dimensions() != expected.dimensions() {
+        return Err(Error::configuration(format!(
+            "Baseline image is {}x{} but the screenshot is {}x{}",
+            expected.width(),
+            expected.height(),
+            actual.width(),
+            actual.height()
+        )));
+    }
+
+    let mut diff_image = with_diff_image.then(|| RgbaImage::new(actual.width(), actual.height()));
+    let mut diff_pixels = 0;
+    for ((x, y, a), e) in actual.enumerate_pixels().zip(expected.pixels()) {
+        let changed = a.0.iter().zip(e.0.iter()).any(|(a, e)| a.abs_diff(*e) > tolerance);
+        if changed {
+            diff_pixels += 1;
+        }
+        if let Some(diff) = diff_image.as_mut() {
+            diff.put_pixel(x, y, if changed { DIFF_HIGHLIGHT } else { faded(a) });
+        }
+    }
+
+    Ok(ImageDiff {
+        width: actual.width(),
+        height: actual.height(),
+        diff_pixels,
+        diff_image,
+    })
+}
+
+/// Light grey version of a pixel, used as the diff image background
+fn faded(pixel: &Rgba<u8>) -> Rgba<u8> {
+    let [r, g, b, _] = pixel.0;
+    let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    let light = (255 - (255 - luma) / 3) as u8;
+    Rgba([light, light, light, 255])
+}
+
+/// Read the pixel dimensions of an encoded screenshot without decoding it
+pub fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    image::ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic noisy image so lossy encoders have detail to discard
+    fn sample_png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, y| {
+            let n = x.wrapping_mul(7919) ^ y.wrapping_mul(104_729) ^ (x * y);
+            image::Rgba([(n % 251) as u8, (x * 255 / width) as u8, (y * 255 / height) as u8, 255])
+        });
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut buffer, ImageFormat::Png).unwrap();
+        buffer.into_inner()
+    }
+
+    fn options(format: ScreenshotFormat, quality: Option<u8>) -> ScreenshotOptions {
+        ScreenshotOptions {
+            format,
+            quality,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_png_passthrough_is_lossless() {
+        let png = sample_png(32, 16);
+        let output = process(png.clone(), &options(ScreenshotFormat::Png, Some(10))).unwrap();
+        assert_eq!(output, png);
+    }
+
+    #[test]
+    fn test_jpeg_quality_affects_size() {
+        let png = sample_png(200, 150);
+        let low = process(png.clone(), &options(ScreenshotFormat::Jpeg, Some(50))).unwrap();
+        let high = process(png, &options(ScreenshotFormat::Jpeg, Some(95))).unwrap();
+
+        assert_eq!(&low[..3], &[0xFF, 0xD8, 0xFF]);
+        assert!(low.len() < high.len(), "q50 = {} bytes, q95 = {} bytes", low.len(), high.len());
+    }
+
+    #[test]
+    fn test_webp_quality_affects_size() {
+        let png = sample_png(200, 150);
+        let low = process(png.clone(), &options(ScreenshotFormat::WebP, Some(20))).unwrap();
+        let high = process(png, &options(ScreenshotFormat::WebP, Some(95))).unwrap();
+
+        assert_eq!(&low[..4], b"RIFF");
+        assert_eq!(&low[8..12], b"WEBP");
+        assert!(low.len() < high.len());
+    }
+
+    #[test]
+    fn test_resize_preserves_aspect_ratio() {
+        let png = sample_png(400, 200);
+        let output = process(
+            png,
+            &ScreenshotOptions {
+                max_width: Some(100),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(dimensions(&output), Some((100, 50)));
+    }
+
+    #[test]
+    fn test_resize_never_enlarges() {
+        let image = decode(&sample_png(40, 20)).unwrap();
+        let image = fit_within(image, Some(400), Some(400));
+        assert_eq!((image.width(), image.height()), (40, 20));
+    }
+
+    #[test]
+    fn test_compare_identical_and_slightly_different_images() {
+        let png = sample_png(40, 20);
+
+        let identical = compare(&png, &png, 0, true).unwrap();
+        assert_eq!(identical.diff_pixels, 0);
+        assert_eq!(identical.diff_percentage(), 0.0);
+        let diff_image = identical.diff_image.unwrap();
+        assert!(diff_image.pixels().all(|p| *p != DIFF_HIGHLIGHT));
+
+        // Change 8 of 800 pixels: 4 clearly, 4 by a single step
+        let mut changed = decode(&png).unwrap().to_rgba8();
+        for x in 0..4 {
+            changed.put_pixel(x, 0, Rgba([255, 255, 255, 0]));
+            let p = changed.get_pixel_mut(x, 10);
+            p.0[0] = p.0[0].wrapping_add(1);
+        }
+        let mut baseline = std::io::Cursor::new(Vec::new());
+        changed.write_to(&mut baseline, ImageFormat::Png).unwrap();
+        let baseline = baseline.into_inner();
+
+        let diff = compare(&png, &baseline, 0, true).unwrap();
+        assert_eq!((diff.width, diff.height, diff.total_pixels()), (40, 20, 800));
+        assert_eq!(diff.diff_pixels, 8);
+        assert!((diff.diff_percentage() - 1.0).abs() < 1e-9);
+        let diff_image = diff.diff_image.unwrap();
+        assert_eq!(*diff_image.get_pixel(0, 10), DIFF_HIGHLIGHT);
+        assert_ne!(*diff_image.get_pixel(5, 10), DIFF_HIGHLIGHT);
+
+        // A small tolerance ignores the single-step changes
+        let tolerant = compare(&png, &baseline, 2, false).unwrap();
+        assert!(tolerant.diff_image.is_none());
+        assert_eq!(tolerant.diff_pixels, 4);
+    }
+
+    #[test]
+    fn test_compare_rejects_size_mismatch() {
+        let err = compare(&sample_png(40, 20), &sample_png(20, 40), 0, false).unwrap_err();
+        assert!(matches!(&err, Error::Configuration(m) if m.contains("Baseline image is 20x40 but the screenshot is 40x20")));
+    }
+}
@@ -114,6 +114,7 @@ async fn test_page_navigation() {
             NavigationOptions {
                 timeout: 30000,
                 wait_until: LoadState::Load,
+                referer: None,
             },
         )
         .await
@@ -208,6 +209,8 @@ async fn test_page_screenshot() {
             quality: None,
             full_page: false,
             clip: None,
+            max_width: None,
+            max_height: None,
         })
         .await
         .expect("Failed to take screenshot");
@@ -258,10 +261,21 @@ async fn test_page_navigation_history() {
         .expect("Failed to navigate");
 
     // Go back
-    page.go_back().await.expect("Failed to go back");
+    let result = page
+        .go_back(1, NavigationOptions::default())
+        .await
+        .expect("Failed to go back");
+    assert_eq!(result.url, "https://example.com");
 
     // Go forward
-    page.go_forward().await.expect("Failed to go forward");
+    let result = page
+        .go_forward(1, NavigationOptions::default())
+        .await
+        .expect("Failed to go forward");
+    assert_eq!(result.url, "https://example.org");
+
+    // Nothing left to go forward to
+    assert!(page.go_forward(1, NavigationOptions::default()).await.is_err());
 }
 
 #[tokio::test]
@@ -279,10 +293,10 @@ async fn test_page_reload() {
         .expect("Failed to create page");
 
     // Reload without cache
-    page.reload(true).await.expect("Failed to reload");
+    page.reload(true, NavigationOptions::default()).await.expect("Failed to reload");
 
     // Reload with cache
-    page.reload(false).await.expect("Failed to reload");
+    page.reload(false, NavigationOptions::default()).await.expect("Failed to reload");
 }
 
 #[tokio::test]
@@ -5,6 +5,8 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 
+use crate::session::console::ConsoleLogs;
+
 /// Browser options for launching a browser
 #[derive(Debug, Clone)]
 pub struct BrowserOptions {
@@ -19,11 +21,40 @@ pub struct BrowserOptions {
     /// Proxy server
     pub proxy: Option<String>,
     /// Additional arguments to pass to Chrome
+    ///
+    /// Override [`launch_args`](Self::launch_args) flags of the same name;
+    /// `!--flag` removes a default flag.
     pub args: Vec<String>,
-    /// Chrome executable path
+    /// Default flags for launched browsers, typically from the server configuration
+    pub launch_args: Vec<String>,
+    /// Chrome executable path; when set, the server launches its own Chrome instead of attaching to `cdp_endpoint`
     pub executable_path: Option<String>,
+    /// Chrome profile directory for launched browsers (`--user-data-dir`)
+    ///
+    /// Persists cookies and storage across launches. When unset, a temporary
+    /// directory is used and removed when the browser closes.
+    pub user_data_dir: Option<String>,
     /// CDP endpoint (e.g., "ws://localhost:9222" or from CHASER_CDP_ENDPOINT env var)
     pub cdp_endpoint: Option<String>,
+    /// Close popups (`window.open`, `target=_blank`) instead of attaching to them
+    pub block_popups: bool,
+    /// How JavaScript dialogs on this browser's pages are answered
+    pub dialog_policy: DialogPolicy,
+    /// Navigations allowed to run at once across the browser's pages; more are queued
+    ///
+    /// `0` disables the limit.
+    pub max_concurrent_navigations: usize,
+    /// Timeouts the browser's pages fall back to when a request sets none
+    pub default_timeouts: DefaultTimeouts,
+    /// Console messages kept per page for `GetConsoleLogs`; `0` keeps none
+    pub console_buffer_size: usize,
+    /// Redirects kept in a navigation's redirect chain; later ones are dropped
+    pub max_redirect_hops: usize,
+    /// Clear cookies, cache and origin storage when the browser is closed
+    ///
+    /// Keeps an attached browser that is shared between clients from handing
+    /// one client's data to the next.
+    pub clear_data_on_close: bool,
 }
 
 impl Default for BrowserOptions {
@@ -35,12 +66,53 @@ impl Default for BrowserOptions {
             user_agent: None,
             proxy: None,
             args: vec![],
+            launch_args: Vec::new(),
             executable_path: None,
+            user_data_dir: None,
             cdp_endpoint: None,
+            block_popups: false,
+            dialog_policy: DialogPolicy::default(),
+            max_concurrent_navigations: crate::session::navigation_limit::DEFAULT_MAX_CONCURRENT_NAVIGATIONS,
+            default_timeouts: DefaultTimeouts::default(),
+            console_buffer_size: crate::session::console::DEFAULT_CONSOLE_BUFFER_SIZE,
+            max_redirect_hops: crate::session::page::DEFAULT_MAX_REDIRECT_HOPS,
+            clear_data_on_close: false,
         }
     }
 }
 
+/// Per-browser default timeouts, in milliseconds, inherited by its pages
+///
+/// A request's own timeout wins; `0` here defers to the server-wide default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultTimeouts {
+    /// Navigations (`Navigate`, `Reload`, `WaitForNavigation`, ...)
+    pub navigation_ms: u64,
+    /// Single commands such as `Evaluate`
+    pub command_ms: u64,
+    /// Waits for a selector, URL or element state
+    pub wait_ms: u64,
+}
+
+impl DefaultTimeouts {
+    /// `requested` when set, else this browser's default, else `fallback`
+    pub fn resolve(requested: u64, inherited: u64, fallback: u64) -> u64 {
+        [requested, inherited].into_iter().find(|&t| t > 0).unwrap_or(fallback)
+    }
+}
+
+/// Default handling of JavaScript dialogs (`alert`, `confirm`, `prompt`, `beforeunload`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DialogPolicy {
+    /// Accept every dialog; prompts get their default text
+    AcceptAll,
+    /// Dismiss every dialog
+    DismissAll,
+    /// Leave dialogs open until `HandleDialog` answers them
+    #[default]
+    Manual,
+}
+
 /// Page options for creating a new page
 #[derive(Debug, Clone)]
 pub struct PageOptions {
@@ -54,6 +126,10 @@ pub struct PageOptions {
     pub device_scale_factor: f64,
     /// Mobile emulation
     pub is_mobile: bool,
+    /// BCP-47 locale (e.g. `ja-JP`) for `Intl` and `navigator.language`
+    pub locale: Option<String>,
+    /// `Accept-Language` header sent with every request; defaults to `locale`
+    pub accept_language: Option<String>,
 }
 
 impl Default for PageOptions {
@@ -64,6 +140,8 @@ impl Default for PageOptions {
             viewport_height: 1080,
             device_scale_factor: 1.0,
             is_mobile: false,
+            locale: None,
+            accept_language: None,
         }
     }
 }
@@ -79,6 +157,10 @@ pub struct ScreenshotOptions {
     pub full_page: bool,
     /// Clip region
     pub clip: Option<ClipRegion>,
+    /// Maximum output width in pixels (downscaled preserving aspect ratio)
+    pub max_width: Option<u32>,
+    /// Maximum output height in pixels (downscaled preserving aspect ratio)
+    pub max_height: Option<u32>,
 }
 
 impl Default for ScreenshotOptions {
@@ -88,6 +170,8 @@ impl Default for ScreenshotOptions {
             quality: None,
             full_page: false,
             clip: None,
+            max_width: None,
+            max_height: None,
         }
     }
 }
@@ -118,6 +202,8 @@ pub struct NavigationOptions {
     pub timeout: u64,
     /// Wait until condition
     pub wait_until: LoadState,
+    /// Referer sent with the navigation request; `None` keeps Chrome's default
+    pub referer: Option<String>,
 }
 
 impl Default for NavigationOptions {
@@ -125,10 +211,44 @@ impl Default for NavigationOptions {
         Self {
             timeout: 30000,
             wait_until: LoadState::Load,
+            referer: None,
         }
     }
 }
 
+/// Server-side record of a page, readable without contacting the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageActivity {
+    /// When the page was opened
+    pub created_at: std::time::SystemTime,
+    /// Last time a request used the page
+    pub last_activity: std::time::SystemTime,
+    /// Main-frame URL last reported by the browser
+    pub url: String,
+    /// Document title last reported by the browser
+    pub title: String,
+}
+
+impl PageActivity {
+    /// Record for a page opened now at `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        let now = std::time::SystemTime::now();
+        Self {
+            created_at: now,
+            last_activity: now,
+            url: url.into(),
+            title: String::new(),
+        }
+    }
+}
+
+/// HTTP Basic auth credentials
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpCredentials {
+    pub username: String,
+    pub password: String,
+}
+
 /// Page load state
 #[derive(Debug, Clone, Copy)]
 pub enum LoadState {
@@ -138,6 +258,29 @@ pub enum LoadState {
     NetworkAlmostIdle,
 }
 
+impl LoadState {
+    /// Name of the `Page.lifecycleEvent` that marks this state
+    pub fn lifecycle_event(&self) -> &'static str {
+        match self {
+            LoadState::Load => "load",
+            LoadState::DOMContentLoaded => "DOMContentLoaded",
+            LoadState::NetworkIdle => "networkIdle",
+            LoadState::NetworkAlmostIdle => "networkAlmostIdle",
+        }
+    }
+}
+
+/// Target lifecycle event of a browser context
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetEvent {
+    /// A page target was created and attached to
+    Created { target_id: String, page_id: String },
+    /// A page target was closed
+    Destroyed { target_id: String, page_id: String },
+    /// A page target's renderer crashed; the page stays registered until closed
+    Crashed { target_id: String, page_id: String },
+}
+
 /// Browser context trait
 ///
 /// Represents a running browser instance.
@@ -152,11 +295,46 @@ pub trait BrowserContext: Send + Sync + std::fmt::Debug {
     /// Get all pages
     async fn get_pages(&self) -> Result<Vec<Arc<dyn PageContext>>, crate::Error>;
 
+    /// Get the CDP target ID of a page
+    async fn target_id(&self, page_id: &str) -> Result<String, crate::Error>;
+
+    /// Bring a page's tab to the foreground
+    async fn activate_page(&self, page_id: &str) -> Result<(), crate::Error>;
+
+    /// Close a page and emit its `TargetEvent::Destroyed`
+    async fn close_page(&self, page_id: &str) -> Result<(), crate::Error>;
+
+    /// Subscribe to target created/destroyed/crashed events
+    fn subscribe_targets(&self) -> tokio::sync::broadcast::Receiver<TargetEvent>;
+
     /// Close the browser
     async fn close(&self) -> Result<(), crate::Error>;
 
+    /// Clear the selected cookies, cache and origin storage of the browser
+    ///
+    /// Works with no page open. Returns the origins whose storage was cleared.
+    async fn clear_data(&self, types: crate::session::browser_data::ClearDataTypes) -> Result<Vec<String>, crate::Error>;
+
     /// Check if browser is active
     fn is_active(&self) -> bool;
+
+    /// Navigation counters, including time spent waiting for a navigation slot
+    fn navigation_stats(&self) -> crate::session::navigation_limit::NavigationStats {
+        Default::default()
+    }
+
+    /// Timeouts inherited by the browser's pages
+    fn default_timeouts(&self) -> DefaultTimeouts {
+        DefaultTimeouts::default()
+    }
+
+    /// User-Agent the browser's pages are created with, if one is configured
+    fn user_agent(&self) -> Option<String> {
+        None
+    }
+
+    /// Get browser version information
+    async fn get_version(&self) -> Result<crate::cdp::traits::BrowserVersion, crate::Error>;
 }
 
 /// Page context trait
@@ -179,30 +357,96 @@ pub trait PageContext: Send + Sync + std::fmt::Debug {
     /// Set page content
     async fn set_content(&self, html: &str) -> Result<(), crate::Error>;
 
-    /// Reload page
-    async fn reload(&self, ignore_cache: bool) -> Result<(), crate::Error>;
+    /// Reload page, returning the final URL and main-frame HTTP status
+    async fn reload(&self, ignore_cache: bool, options: NavigationOptions) -> Result<NavigationResult, crate::Error>;
+
+    /// Go back `delta` entries in history and wait for `options.wait_until`
+    ///
+    /// Fails with `NavigationFailed` when there are not enough entries to go back to.
+    async fn go_back(&self, delta: u32, options: NavigationOptions) -> Result<NavigationResult, crate::Error>;
 
-    /// Go back in history
-    async fn go_back(&self) -> Result<(), crate::Error>;
+    /// Go forward `delta` entries in history and wait for `options.wait_until`
+    ///
+    /// Fails with `NavigationFailed` when there are not enough entries to go forward to.
+    async fn go_forward(&self, delta: u32, options: NavigationOptions) -> Result<NavigationResult, crate::Error>;
 
-    /// Go forward in history
-    async fn go_forward(&self) -> Result<(), crate::Error>;
+    /// Wait for the next main-frame navigation to reach `options.wait_until`
+    ///
+    /// Navigations whose URL does not match `url_pattern` (`*` matches any
+    /// sequence of characters) are ignored. Fails with `Timeout` if no matching
+    /// navigation completes within `options.timeout` milliseconds.
+    async fn wait_for_navigation(&self, url_pattern: Option<&str>, options: NavigationOptions) -> Result<NavigationResult, crate::Error>;
 
     /// Evaluate JavaScript
     async fn evaluate(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, crate::Error>;
 
+    /// Execution context ID of the page's isolated world in the main frame
+    ///
+    /// Created with `Page.createIsolatedWorld` on first use and reused until
+    /// the frame's execution contexts are destroyed, e.g. by a navigation.
+    async fn isolated_world(&self) -> Result<i64, crate::Error>;
+
+    /// Evaluate JavaScript in the page's isolated world
+    ///
+    /// The isolated world shares the DOM but not JavaScript globals with page
+    /// scripts, so evaluation can neither clash with nor be observed by them.
+    async fn evaluate_isolated(&self, script: &str, await_promise: bool) -> Result<EvaluationResult, crate::Error>;
+
     /// Capture screenshot
     async fn screenshot(&self, options: ScreenshotOptions) -> Result<Vec<u8>, crate::Error>;
 
     /// Set viewport size
+    ///
+    /// `window.screen` is resized along with it; it keeps a larger size set
+    /// with [`set_screen_size`](Self::set_screen_size).
     async fn set_viewport(&self, width: u32, height: u32, device_scale_factor: f64) -> Result<(), crate::Error>;
 
+    /// Set the `window.screen` size reported to page scripts, keeping the viewport
+    ///
+    /// The screen is never reported smaller than the viewport.
+    async fn set_screen_size(&self, width: u32, height: u32) -> Result<(), crate::Error>;
+
+    /// Enable or disable touch event emulation
+    async fn set_touch_emulation(&self, enabled: bool) -> Result<(), crate::Error>;
+
+    /// Check if touch event emulation is enabled
+    fn touch_emulation_enabled(&self) -> bool;
+
+    /// Set credentials used to answer HTTP auth challenges
+    ///
+    /// `None` clears them; challenges are then cancelled instead of blocking on the native dialog.
+    async fn set_http_credentials(&self, credentials: Option<HttpCredentials>) -> Result<(), crate::Error>;
+
     /// Close the page
     async fn close(&self) -> Result<(), crate::Error>;
 
     /// Check if page is active
     fn is_active(&self) -> bool;
 
+    /// Check if the page's renderer crashed
+    fn is_crashed(&self) -> bool {
+        false
+    }
+
+    /// Timeouts inherited from the page's browser, used when a request sets none
+    fn default_timeouts(&self) -> DefaultTimeouts {
+        DefaultTimeouts::default()
+    }
+
+    /// Buffered console messages logged after `cursor` (`0` for all)
+    fn console_logs(&self, cursor: u64) -> ConsoleLogs {
+        ConsoleLogs {
+            next_cursor: cursor,
+            ..Default::default()
+        }
+    }
+
+    /// Creation time, last activity, URL and title as last recorded by the server
+    fn activity(&self) -> PageActivity;
+
+    /// Record that a request used the page
+    fn touch(&self);
+
     /// Get the CDP client for this page
     fn get_cdp_client(&self) -> Arc<dyn crate::cdp::traits::CdpClient>;
 }
@@ -218,6 +462,9 @@ pub trait ElementRef: Send + Sync {
     /// Get parent page ID
     fn page_id(&self) -> &str;
 
+    /// Get the CDP client of the element's page
+    fn get_cdp_client(&self) -> Arc<dyn crate::cdp::traits::CdpClient>;
+
     /// Get element text
     async fn get_text(&self) -> Result<String, crate::Error>;
 
@@ -262,11 +509,26 @@ pub struct BoundingBox {
 }
 
 /// Navigation result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct NavigationResult {
     pub url: String,
     pub status_code: u16,
     pub is_loaded: bool,
+    /// Main-frame document responses in order, ending with the final one
+    ///
+    /// Empty when the navigation was not observed over the network (e.g. history navigation).
+    pub redirect_chain: Vec<RedirectHop>,
+    /// Redirects beyond the page's cap were left out of `redirect_chain`
+    ///
+    /// `url` and `status_code` still describe the final response.
+    pub redirect_chain_truncated: bool,
+}
+
+/// One main-frame document response of a navigation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status_code: u16,
 }
 
 /// JavaScript evaluation result
@@ -279,6 +541,16 @@ pub enum EvaluationResult {
     Object(serde_json::Value),
 }
 
+/// Per-page state kept outside the session layer
+///
+/// Attached to the session manager, which releases a page's state when the
+/// page closes.
+#[async_trait]
+pub trait PageStateStore: Send + Sync {
+    /// Drop everything kept for `page_id`
+    async fn release_page(&self, page_id: &str);
+}
+
 /// Session manager trait
 ///
 /// Manages all browser, page, and element sessions.
@@ -0,0 +1,221 @@
+//! URL policy for navigations
+//!
+//! Restricts which URLs pages may load documents from. Patterns use `*`
+//! wildcards and are matched against both the full URL and its host, so
+//! `*.example.com` and `https://example.com/admin/*` both work. With
+//! `block_private_networks`, the host is resolved and rejected if any of its
+//! addresses is loopback, private or link-local. A restrictive policy only
+//! allows `http` and `https` URLs (and `about:blank`), so `data:`,
+//! `javascript:` and `file:` URLs cannot be used to get around it.
+//!
+//! Besides the checks before CreatePage and Navigate, every page intercepts
+//! its document requests with the Fetch domain and fails the ones the policy
+//! rejects (see `PageContextImpl::enforce_url_policy`). That covers
+//! redirects, frames, reloads, popups and scripts setting `location`.
+//! Subresources (images, scripts, XHR) are not checked.
+//!
+//! `block_private_networks` is not SSRF protection. Chrome resolves the host
+//! again on its own, so a DNS name that answers with a public address to the
+//! server and a private one to the browser (DNS rebinding) gets through. Run
+//! the browser in a network namespace or behind a proxy that enforces the
+//! restriction when untrusted clients pick the URLs.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use url::{Host, Url};
+
+use crate::session::page::url_matches;
+use crate::Error;
+
+/// Allow/deny rules for navigation URLs
+///
+/// The default policy allows every URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UrlPolicy {
+    /// When non-empty, only URLs matching one of these patterns are allowed
+    pub allow: Vec<String>,
+    /// URLs matching any of these patterns are rejected, even if allowed
+    pub deny: Vec<String>,
+    /// Reject hosts that resolve to loopback, private or link-local addresses
+    ///
+    /// Only the server's own resolution is checked, so DNS rebinding bypasses it.
+    pub block_private_networks: bool,
+}
+
+impl UrlPolicy {
+    /// Whether the policy allows every URL
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty() && !self.block_private_networks
+    }
+
+    /// Check a navigation URL against the policy
+    ///
+    /// # Errors
+    /// Returns `Configuration` for URLs that cannot be parsed and
+    /// `PermissionDenied` for URLs the policy rejects
+    pub async fn check(&self, url: &str) -> Result<(), Error> {
+        if self.is_unrestricted() {
+            return Ok(());
+        }
+        // Every page starts on about:blank, so it is never treated as a navigation target
+        if url == "about:blank" {
+            return Ok(());
+        }
+
+        let parsed = Url::parse(url).map_err(|e| Error::configuration(format!("Invalid URL {}: {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(Error::permission_denied(format!(
+                "{} uses scheme '{}'; only http and https are allowed",
+                url,
+                parsed.scheme()
+            )));
+        }
+        let host = parsed.host_str().unwrap_or_default();
+        let matches = |pattern: &String| {
+            url_matches(Some(pattern), parsed.as_str()) || (!host.is_empty() && url_matches(Some(pattern), host))
+        };
+
+        if let Some(pattern) = self.deny.iter().find(|pattern| matches(pattern)) {
+            return Err(Error::permission_denied(format!("{} matches denied pattern {}", url, pattern)));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(matches) {
+            return Err(Error::permission_denied(format!("{} does not match any allowed pattern", url)));
+        }
+        if self.block_private_networks {
+            check_public(&parsed).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reject URLs whose host is or resolves to a non-public address
+async fn check_public(url: &Url) -> Result<(), Error> {
+    let denied = |reason: &str| Error::permission_denied(format!("{} {}; private networks are blocked", url, reason));
+
+    let addresses = match url.host() {
+        Some(Host::Ipv4(ip)) => vec![IpAddr::V4(ip)],
+        Some(Host::Ipv6(ip)) => vec![IpAddr::V6(ip)],
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            if domain == "localhost" || domain.ends_with(".localhost") {
+                return Err(denied("points to localhost"));
+            }
+            let port = url.port_or_known_default().unwrap_or(80);
+            let addresses: Vec<IpAddr> = tokio::net::lookup_host((domain.as_str(), port))
+                .await
+                .map_err(|e| Error::navigation_failed(format!("Cannot resolve {}: {}", domain, e)))?
+                .map(|addr| addr.ip())
+                .collect();
+            addresses
+        }
+        None => return Err(denied("has no host")),
+    };
+
+    match addresses.into_iter().find(|ip| is_private(*ip)) {
+        Some(ip) => Err(denied(&format!("resolves to {}", ip))),
+        None => Ok(()),
+    }
+}
+
+/// Loopback, private, link-local, shared and unspecified addresses
+///
+/// IPv6 addresses that carry an IPv4 address are judged by that address.
+fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_v4(ip),
+        IpAddr::V6(ip) => match embedded_v4(ip) {
+            Some(embedded) => is_private_v4(embedded),
+            None => is_private_v6(ip),
+        },
+    }
+}
+
+/// The IPv4 address in an IPv4-mapped (`::ffff:a.b.c.d`), IPv4-compatible
+/// (`::a.b.c.d`), NAT64 (`64:ff9b::/96`) or 6to4 (`2002::/16`) address
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let v4 = |high: u16, low: u16| Ipv4Addr::from((u32::from(high) << 16) | u32::from(low));
+    match segments {
+        [0x0064, 0xff9b, 0, 0, 0, 0, high, low] => Some(v4(high, low)),
+        [0x2002, high, low, ..] => Some(v4(high, low)),
+        _ => ip.to_ipv4(),
+    }
+}
+
+fn is_private_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        // "This network" (0.0.0.0/8), which Linux routes to the local host
+        || a == 0
+        // Carrier-grade NAT (100.64.0.0/10)
+        || (a == 100 && (b & 0xc0) == 64)
+}
+
+fn is_private_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    ip.is_loopback()
+        || ip.is_unspecified()
+        // Unique local (fc00::/7)
+        || (first & 0xfe00) == 0xfc00
+        // Link-local (fe80::/10)
+        || (first & 0xffc0) == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_private_addresses_are_blocked() {
+        let policy = UrlPolicy {
+            block_private_networks: true,
+            ..Default::default()
+        };
+
+        for url in [
+            "http://127.0.0.1:8080/",
+            "http://10.1.2.3/",
+            "http://192.168.0.1/admin",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/",
+            "http://[::ffff:10.0.0.1]/",
+            "http://2130706433/",
+            "http://localhost:3000/",
+            "http://0.1.2.3/",
+            "http://[::a9fe:a9fe]/",
+            "http://[64:ff9b::a9fe:a9fe]/",
+            "http://[2002:a9fe:a9fe::1]/",
+            "file:///etc/passwd",
+            "data:text/html,<meta http-equiv=refresh content='0;url=http://169.254.169.254/'>",
+            "javascript:location='http://169.254.169.254/'",
+        ] {
+            let err = policy.check(url).await.unwrap_err();
+            assert!(matches!(err, Error::PermissionDenied(_)), "{} was not blocked: {:?}", url, err);
+        }
+        policy.check("https://93.184.216.34/").await.unwrap();
+        policy.check("https://[64:ff9b::5db8:d822]/").await.unwrap();
+        policy.check("https://[2606:2800:220:1::]/").await.unwrap();
+        policy.check("about:blank").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restrictive_policy_only_allows_http() {
+        let policy = UrlPolicy {
+            deny: vec!["*.internal".to_string()],
+            ..Default::default()
+        };
+
+        for url in ["data:text/html,hi", "javascript:alert(1)", "file:///etc/passwd", "ftp://example.com/"] {
+            let err = policy.check(url).await.unwrap_err();
+            assert!(matches!(err, Error::PermissionDenied(_)), "{} was not blocked: {:?}", url, err);
+        }
+        policy.check("https://example.com/").await.unwrap();
+
+        // The default policy leaves every URL alone
+        UrlPolicy::default().check("data:text/html,hi").await.unwrap();
+    }
+}
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use rand::Rng;
 use bezier_rs::Bezier;
 
-use crate::{Error, cdp::CdpClient};
+use crate::{Error, cdp::CdpClient, session::SessionManager};
 use super::traits::*;
 
 /// Typing action for pre-generated typing sequences
@@ -19,36 +19,104 @@ enum TypingAction {
     Delay(u32),
 }
 
+/// Where the simulator gets the CDP client for a page
+enum ClientSource {
+    /// One client for every call, for callers that already resolved the page
+    Fixed(Arc<dyn CdpClient>),
+    /// Look the page up at call time
+    Session(Arc<dyn SessionManager>),
+}
+
 /// Behavior simulator implementation
 pub struct BehaviorSimulatorImpl {
-    /// CDP client
-    cdp_client: Arc<dyn CdpClient>,
+    /// CDP client source
+    clients: ClientSource,
 }
 
 impl BehaviorSimulatorImpl {
-    /// Create a new behavior simulator
+    /// Create a behavior simulator that drives a single page's CDP client
     pub fn new(cdp_client: Arc<dyn CdpClient>) -> Self {
-        Self { cdp_client }
+        Self { clients: ClientSource::Fixed(cdp_client) }
+    }
+
+    /// Create a behavior simulator that dispatches input to whichever page
+    /// the call names, resolved through the session manager
+    pub fn with_session_manager(session_manager: Arc<dyn SessionManager>) -> Self {
+        Self { clients: ClientSource::Session(session_manager) }
+    }
+
+    /// Get CDP client for page
+    async fn get_cdp_client(&self, page_id: &str) -> Result<Arc<dyn CdpClient>, Error> {
+        match &self.clients {
+            ClientSource::Fixed(client) => Ok(client.clone()),
+            ClientSource::Session(session_manager) => {
+                Ok(session_manager.get_page(page_id).await?.get_cdp_client())
+            }
+        }
     }
 
     /// Generate Bezier curve path for mouse movement
+    ///
+    /// With an overshoot, the main curve ends past the target and a short
+    /// correction curve brings the cursor back onto it.
     fn generate_bezier_path(
         start: (f64, f64),
         end: (f64, f64),
         options: &MouseMoveOptions,
     ) -> Vec<(f64, f64)> {
         let (dx, dy) = (end.0 - start.0, end.1 - start.1);
-        let deviation = options.deviation;
+        let distance = dx.hypot(dy);
+        let overshoot = options.points >= 4
+            && distance > 0.0
+            && options.overshoot_magnitude > 0.0
+            && rand::random::<f64>() < options.overshoot_probability;
+
+        if !overshoot {
+            return Self::bezier_segment(start, end, options.curvature, options.deviation, options.points);
+        }
+
+        let past = (
+            end.0 + dx * options.overshoot_magnitude,
+            end.1 + dy * options.overshoot_magnitude,
+        );
+        let correction_points = (options.points / 5).max(2);
+        let mut path = Self::bezier_segment(
+            start,
+            past,
+            options.curvature,
+            options.deviation,
+            options.points - correction_points,
+        );
+        // The correction is a small, nearly straight move back
+        let correction = Self::bezier_segment(past, end, 0.0, options.deviation * 0.1, correction_points);
+        path.extend(correction.into_iter().skip(1));
+        path
+    }
+
+    /// Sample `points` steps of one cubic Bezier from `start` to `end`
+    ///
+    /// Control points sit a quarter of the way in from each end, pushed
+    /// sideways by `curvature` and jittered by `deviation`.
+    fn bezier_segment(
+        start: (f64, f64),
+        end: (f64, f64),
+        curvature: f64,
+        deviation: f64,
+        points: u32,
+    ) -> Vec<(f64, f64)> {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        // Perpendicular offset: the unit normal scaled by curvature * distance
+        let (bend_x, bend_y) = (-dy * curvature, dx * curvature);
 
         // Generate control points with random deviation
         let cp1 = (
-            start.0 + dx * 0.25 + (rand::random::<f64>() - 0.5) * deviation,
-            start.1 + dy * 0.25 + (rand::random::<f64>() - 0.5) * deviation,
+            start.0 + dx * 0.25 + bend_x + (rand::random::<f64>() - 0.5) * deviation,
+            start.1 + dy * 0.25 + bend_y + (rand::random::<f64>() - 0.5) * deviation,
         );
 
         let cp2 = (
-            end.0 - dx * 0.25 + (rand::random::<f64>() - 0.5) * deviation,
-            end.1 - dy * 0.25 + (rand::random::<f64>() - 0.5) * deviation,
+            end.0 - dx * 0.25 + bend_x + (rand::random::<f64>() - 0.5) * deviation,
+            end.1 - dy * 0.25 + bend_y + (rand::random::<f64>() - 0.5) * deviation,
         );
 
         let bezier = Bezier::from_cubic_coordinates(
@@ -58,9 +126,9 @@ impl BehaviorSimulatorImpl {
             end.0, end.1,
         );
 
-        (0..=options.points)
+        (0..=points)
             .map(|i| {
-                let t = i as f64 / options.points as f64;
+                let t = i as f64 / points as f64;
                 let point = bezier.evaluate(bezier_rs::TValue::Euclidean(t));
                 (point[0], point[1])
             })
@@ -80,12 +148,13 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
     /// Simulate mouse movement using Bezier curves
     async fn simulate_mouse_move(
         &self,
-        _page_id: &str,
+        page_id: &str,
         start: (f64, f64),
         end: (f64, f64),
         options: MouseMoveOptions,
     ) -> Result<(), Error> {
         let path = Self::generate_bezier_path(start, end, &options);
+        let cdp_client = self.get_cdp_client(page_id).await?;
 
         let duration = Duration::from_millis(options.duration_ms);
         let step_delay = duration / path.len() as u32;
@@ -97,7 +166,7 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
                 "type": "mouseMoved"
             });
 
-            self.cdp_client
+            cdp_client
                 .call_method("Input.dispatchMouseEvent", params)
                 .await?;
 
@@ -115,9 +184,11 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
         text: &str,
         options: TypingOptions,
     ) -> Result<(), Error> {
+        let cdp_client = self.get_cdp_client(page_id).await?;
+
         // Focus element
         let focus_params = serde_json::json!({ "objectId": element_id });
-        self.cdp_client.call_method("DOM.focus", focus_params).await?;
+        cdp_client.call_method("DOM.focus", focus_params).await?;
 
         // Pre-generate typing actions to avoid Send issues
         let typing_actions: Vec<Vec<TypingAction>> = text
@@ -150,10 +221,10 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
             for action in actions {
                 match action {
                     TypingAction::TypeChar(c) | TypingAction::WrongChar(c) => {
-                        self.type_char(page_id, c).await?;
+                        Self::type_char(cdp_client.as_ref(), c).await?;
                     }
                     TypingAction::Backspace => {
-                        self.type_char(page_id, '\u{08}').await?;
+                        Self::type_char(cdp_client.as_ref(), '\u{08}').await?;
                     }
                     TypingAction::Delay(ms) => {
                         tokio::time::sleep(Duration::from_millis(ms as u64)).await;
@@ -173,7 +244,8 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
         options: ClickOptions,
     ) -> Result<(), Error> {
         // Get and extract center position
-        let result = self.cdp_client.call_method("DOM.getBoxModel", serde_json::json!({ "objectId": element_id })).await?;
+        let cdp_client = self.get_cdp_client(page_id).await?;
+        let result = cdp_client.call_method("DOM.getBoxModel", serde_json::json!({ "objectId": element_id })).await?;
         let quad = result.get("model")
             .and_then(|m| m.get("content"))
             .and_then(|c| c.as_array())
@@ -182,19 +254,35 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
         let x = (quad[0].as_f64().unwrap_or(0.0) + quad[4].as_f64().unwrap_or(0.0)) / 2.0;
         let y = (quad[1].as_f64().unwrap_or(0.0) + quad[5].as_f64().unwrap_or(0.0)) / 2.0;
 
-        // Move, press, hold, release
+        self.simulate_click_at(page_id, (x, y), MouseButton::Left, 1, options).await
+    }
+
+    /// Simulate a human-like click at viewport coordinates
+    async fn simulate_click_at(
+        &self,
+        page_id: &str,
+        (x, y): (f64, f64),
+        button: MouseButton,
+        click_count: u32,
+        options: ClickOptions,
+    ) -> Result<(), Error> {
+        // Move, then press, hold, release for each click
         tokio::time::sleep(Duration::from_millis(options.delay_before_ms)).await;
         self.simulate_mouse_move(page_id, (0.0, 0.0), (x, y), MouseMoveOptions {
             duration_ms: options.movement_duration_ms,
             deviation: 20.0,
             points: 10,
+            ..Default::default()
         }).await?;
 
-        for (event_type, delay) in [("mousePressed", options.hold_duration_ms), ("mouseReleased", 0)] {
-            self.cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
-                "x": x, "y": y, "type": event_type, "button": "left", "clickCount": 1
-            })).await?;
-            if delay > 0 { tokio::time::sleep(Duration::from_millis(delay)).await; }
+        let cdp_client = self.get_cdp_client(page_id).await?;
+        for count in 1..=click_count.max(1) {
+            for (event_type, delay) in [("mousePressed", options.hold_duration_ms), ("mouseReleased", 0)] {
+                cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
+                    "x": x, "y": y, "type": event_type, "button": button.as_cdp(), "clickCount": count
+                })).await?;
+                if delay > 0 { tokio::time::sleep(Duration::from_millis(delay)).await; }
+            }
         }
 
         Ok(())
@@ -203,7 +291,7 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
     /// Simulate scroll behavior
     async fn simulate_scroll(
         &self,
-        _page_id: &str,
+        page_id: &str,
         target_y: f64,
         options: ScrollOptions,
     ) -> Result<(), Error> {
@@ -213,7 +301,8 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
             .collect();
 
         // Get current scroll position
-        let result = self.cdp_client.call_method("Page.getLayoutMetrics", serde_json::json!({})).await?;
+        let cdp_client = self.get_cdp_client(page_id).await?;
+        let result = cdp_client.call_method("Page.getLayoutMetrics", serde_json::json!({})).await?;
         let current_y = result.get("cssLayoutViewport")
             .and_then(|v| v.get("pageY"))
             .and_then(|v| v.as_f64())
@@ -234,7 +323,7 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
             };
 
             let scroll_y = current_y + step_distance * (i as f64 + 1.0) * multiplier;
-            self.cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
+            cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
                 "x": 0.0, "y": scroll_y, "type": "mouseWheel"
             })).await?;
 
@@ -244,6 +333,96 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
         Ok(())
     }
 
+    /// Replay a recorded mouse trace
+    async fn replay_mouse_trace(&self, page_id: &str, trace: &[TracePoint]) -> Result<(), Error> {
+        let (Some(first), Some(last)) = (trace.first(), trace.last()) else {
+            return Err(Error::configuration("Mouse trace is empty"));
+        };
+        if let Some(pair) = trace.windows(2).find(|pair| pair[1].t_ms < pair[0].t_ms) {
+            return Err(Error::configuration(format!(
+                "Mouse trace timestamps must not decrease ({}ms after {}ms)",
+                pair[1].t_ms, pair[0].t_ms
+            )));
+        }
+        if last.t_ms - first.t_ms > MAX_MOUSE_TRACE_DURATION_MS {
+            return Err(Error::configuration(format!(
+                "Mouse trace spans {}ms, more than the {}ms limit",
+                last.t_ms - first.t_ms,
+                MAX_MOUSE_TRACE_DURATION_MS
+            )));
+        }
+
+        let cdp_client = self.get_cdp_client(page_id).await?;
+
+        // Schedule against the start time so dispatch latency does not accumulate
+        let start = tokio::time::Instant::now();
+        for point in trace {
+            tokio::time::sleep_until(start + Duration::from_millis(point.t_ms - first.t_ms)).await;
+            cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
+                "x": point.x, "y": point.y, "type": "mouseMoved"
+            })).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Simulate a human idling on the page
+    async fn simulate_idle(&self, page_id: &str, duration_ms: u64) -> Result<(), Error> {
+        if duration_ms > MAX_IDLE_DURATION_MS {
+            return Err(Error::configuration(format!(
+                "Idle duration {}ms exceeds the {}ms limit",
+                duration_ms, MAX_IDLE_DURATION_MS
+            )));
+        }
+        if duration_ms == 0 {
+            return Ok(());
+        }
+
+        let cdp_client = self.get_cdp_client(page_id).await?;
+        let metrics = cdp_client.call_method("Page.getLayoutMetrics", serde_json::json!({})).await?;
+        let size = |key: &str| {
+            metrics["cssLayoutViewport"].get(key).and_then(|v| v.as_f64()).unwrap_or(0.0).max(1.0)
+        };
+        let (width, height) = (size("clientWidth"), size("clientHeight"));
+        let clamp = |(x, y): (f64, f64)| (x.clamp(0.0, width - 1.0), y.clamp(0.0, height - 1.0));
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(duration_ms);
+        let mut position = clamp((
+            width * (0.3 + rand::random::<f64>() * 0.4),
+            height * (0.3 + rand::random::<f64>() * 0.4),
+        ));
+        let mut first = true;
+
+        while first || tokio::time::Instant::now() < deadline {
+            // Always start with a move; afterwards scroll about one time in five
+            if !first && rand::random::<f64>() < 0.2 {
+                let delta = (rand::random::<f64>() * 150.0 + 50.0) * if rand::random() { 1.0 } else { -1.0 };
+                cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
+                    "x": position.0, "y": position.1, "type": "mouseWheel", "deltaX": 0, "deltaY": delta
+                })).await?;
+            } else {
+                let target = clamp((
+                    position.0 + (rand::random::<f64>() - 0.5) * 60.0,
+                    position.1 + (rand::random::<f64>() - 0.5) * 60.0,
+                ));
+                let path = Self::bezier_segment(position, target, 0.0, 5.0, 4);
+                for (x, y) in path.into_iter().skip(1).map(clamp) {
+                    cdp_client.call_method("Input.dispatchMouseEvent", serde_json::json!({
+                        "x": x, "y": y, "type": "mouseMoved"
+                    })).await?;
+                    tokio::time::sleep(Duration::from_millis(15)).await;
+                }
+                position = target;
+            }
+            first = false;
+
+            let pause = Duration::from_millis(200 + (rand::random::<f64>() * 600.0) as u64);
+            tokio::time::sleep_until((tokio::time::Instant::now() + pause).min(deadline)).await;
+        }
+
+        Ok(())
+    }
+
     /// Add random delay
     async fn random_delay(&self, min_ms: u64, max_ms: u64) -> Result<(), Error> {
         // Generate random value before await to avoid Send issue
@@ -255,9 +434,9 @@ impl BehaviorSimulator for BehaviorSimulatorImpl {
 
 impl BehaviorSimulatorImpl {
     /// Type a single character
-    async fn type_char(&self, _page_id: &str, ch: char) -> Result<(), Error> {
+    async fn type_char(cdp_client: &dyn CdpClient, ch: char) -> Result<(), Error> {
         for event_type in ["keyDown", "keyUp"] {
-            self.cdp_client.call_method("Input.dispatchKeyEvent", serde_json::json!({
+            cdp_client.call_method("Input.dispatchKeyEvent", serde_json::json!({
                 "type": event_type, "key": ch.to_string()
             })).await?;
         }
@@ -2,7 +2,7 @@
 //!
 //! Core engine for managing browser fingerprinting evasion and stealth capabilities.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use async_trait::async_trait;
@@ -18,8 +18,10 @@ pub struct StealthEngineImpl {
     /// Behavior simulator
     #[allow(dead_code)]
     simulator: Arc<dyn BehaviorSimulator>,
-    /// Applied features tracking
-    applied_features: Arc<RwLock<HashMap<String, AppliedFeatures>>>,
+    /// Applied profile tracking per page
+    active_profiles: Arc<RwLock<HashMap<String, ActiveProfile>>>,
+    /// Pages whose profile scripts run in an isolated world
+    isolated_pages: Arc<RwLock<HashSet<String>>>,
 }
 
 impl StealthEngineImpl {
@@ -31,7 +33,17 @@ impl StealthEngineImpl {
         Self {
             injector,
             simulator,
-            applied_features: Arc::new(RwLock::new(HashMap::new())),
+            active_profiles: Arc::new(RwLock::new(HashMap::new())),
+            isolated_pages: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Inject a profile script into the world the page's profile asks for
+    async fn inject_script(&self, page_id: &str, script: &str) -> Result<String, Error> {
+        if self.isolated_pages.read().await.contains(page_id) {
+            self.injector.inject_isolated_init_script(page_id, script).await
+        } else {
+            self.injector.inject_init_script(page_id, script).await
         }
     }
 }
@@ -45,46 +57,72 @@ impl StealthEngine for StealthEngineImpl {
         profile: &services::Profile,
     ) -> Result<AppliedFeatures, Error> {
         let mut applied = Vec::new();
+        let mut script_ids = HashMap::new();
+
+        {
+            let mut isolated = self.isolated_pages.write().await;
+            if profile.fingerprint.options.use_isolated_world {
+                isolated.insert(page_id.to_string());
+            } else {
+                isolated.remove(page_id);
+            }
+        }
 
         // Set User-Agent at CDP protocol level FIRST (before any other operations)
         self.set_user_agent(page_id, &profile.fingerprint.headers.user_agent).await?;
         applied.push("user_agent".to_string());
+        script_ids.insert("user_agent".to_string(), Vec::new());
 
         // Apply navigator overrides
         if profile.fingerprint.options.inject_navigator {
-            self.inject_navigator(page_id, &profile.fingerprint.navigator).await?;
+            let script_id = self.inject_navigator(page_id, &profile.fingerprint.navigator).await?;
             applied.push("navigator".to_string());
+            script_ids.insert("navigator".to_string(), vec![script_id]);
         }
 
         // Apply screen overrides
         if profile.fingerprint.options.inject_screen {
-            self.inject_screen(page_id, &profile.fingerprint.screen).await?;
+            let script_id = self.inject_screen(page_id, &profile.fingerprint.screen).await?;
+            // Report the same screen through the device metrics so it stays coherent with the viewport
+            let screen = &profile.fingerprint.screen;
+            self.injector.set_screen_size(page_id, screen.width, screen.height).await?;
             applied.push("screen".to_string());
+            script_ids.insert("screen".to_string(), vec![script_id]);
         }
 
         // Apply WebGL protection
         if profile.fingerprint.options.inject_webgl {
-            self.inject_webgl(page_id, &profile.fingerprint.webgl).await?;
+            let script_id = self.inject_webgl(page_id, &profile.fingerprint.webgl).await?;
             applied.push("webgl".to_string());
+            script_ids.insert("webgl".to_string(), vec![script_id]);
         }
 
         // Apply canvas protection
         if profile.fingerprint.options.inject_canvas {
-            self.inject_canvas(page_id).await?;
+            let script_id = self.inject_canvas(page_id).await?;
             applied.push("canvas".to_string());
+            script_ids.insert("canvas".to_string(), vec![script_id]);
         }
 
         // Apply audio protection
         if profile.fingerprint.options.inject_audio {
-            self.inject_audio(page_id).await?;
+            let script_id = self.inject_audio(page_id).await?;
             applied.push("audio".to_string());
+            script_ids.insert("audio".to_string(), vec![script_id]);
         }
 
         let features = AppliedFeatures { features: applied };
 
-        // Track applied features
-        let mut tracker = self.applied_features.write().await;
-        tracker.insert(page_id.to_string(), features.clone());
+        // Track the applied profile
+        let mut tracker = self.active_profiles.write().await;
+        tracker.insert(
+            page_id.to_string(),
+            ActiveProfile {
+                profile_id: profile.profile_id.clone(),
+                features: features.clone(),
+                script_ids,
+            },
+        );
 
         Ok(features)
     }
@@ -99,7 +137,7 @@ impl StealthEngine for StealthEngineImpl {
         &self,
         page_id: &str,
         fingerprint: &services::NavigatorFingerprint,
-    ) -> Result<(), Error> {
+    ) -> Result<String, Error> {
         tracing::debug!(
             "[P5-DEBUG] Injecting navigator for page {}: platform={}, vendor={}, cores={}, memory={:?}, lang={}",
             page_id,
@@ -139,7 +177,7 @@ impl StealthEngine for StealthEngineImpl {
             fingerprint.platform, fingerprint.vendor, fingerprint.hardware_concurrency, device_memory, fingerprint.language
         );
 
-        self.injector.inject_init_script(page_id, &script).await
+        self.inject_script(page_id, &script).await
     }
 
     /// Inject screen overrides
@@ -147,7 +185,7 @@ impl StealthEngine for StealthEngineImpl {
         &self,
         page_id: &str,
         fingerprint: &services::ScreenFingerprint,
-    ) -> Result<(), Error> {
+    ) -> Result<String, Error> {
         let avail_height = fingerprint.height - 40;
         let script = format!(
             r#"(function() {{
@@ -163,7 +201,7 @@ impl StealthEngine for StealthEngineImpl {
             fingerprint.pixel_depth, fingerprint.width, avail_height
         );
 
-        self.injector.inject_init_script(page_id, &script).await
+        self.inject_script(page_id, &script).await
     }
 
     /// Inject WebGL protection
@@ -171,7 +209,7 @@ impl StealthEngine for StealthEngineImpl {
         &self,
         page_id: &str,
         fingerprint: &services::WebGLFingerprint,
-    ) -> Result<(), Error> {
+    ) -> Result<String, Error> {
         let script = format!(
             r#"(function() {{
                 const getParameter = WebGLRenderingContext.prototype.getParameter;
@@ -189,11 +227,11 @@ impl StealthEngine for StealthEngineImpl {
             fingerprint.vendor, fingerprint.renderer
         );
 
-        self.injector.inject_init_script(page_id, &script).await
+        self.inject_script(page_id, &script).await
     }
 
     /// Inject canvas protection
-    async fn inject_canvas(&self, page_id: &str) -> Result<(), Error> {
+    async fn inject_canvas(&self, page_id: &str) -> Result<String, Error> {
         let script = r#"(function() {
             const addNoise = (data) => {
                 for (let i = 0; i < data.length; i += 4) {
@@ -222,11 +260,11 @@ impl StealthEngine for StealthEngineImpl {
             };
         })();"#;
 
-        self.injector.inject_init_script(page_id, script).await
+        self.inject_script(page_id, script).await
     }
 
     /// Inject audio protection
-    async fn inject_audio(&self, page_id: &str) -> Result<(), Error> {
+    async fn inject_audio(&self, page_id: &str) -> Result<String, Error> {
         let script = r#"(function() {
             const originalGetChannelData = AudioBuffer.prototype.getChannelData;
             AudioBuffer.prototype.getChannelData = function() {
@@ -238,25 +276,66 @@ impl StealthEngine for StealthEngineImpl {
             };
         })();"#;
 
-        self.injector.inject_init_script(page_id, script).await
+        self.inject_script(page_id, script).await
     }
 
     /// Get applied features
     async fn get_applied_features(&self, page_id: &str) -> Result<AppliedFeatures, Error> {
-        let tracker = self.applied_features.read().await;
+        let tracker = self.active_profiles.read().await;
         tracker
             .get(page_id)
-            .cloned()
+            .map(|active| active.features.clone())
             .ok_or_else(|| Error::PageNotFound(format!("No features applied for page: {}", page_id)))
     }
 
+    /// Remove one applied feature
+    async fn remove_feature(&self, page_id: &str, feature: &str) -> Result<AppliedFeatures, Error> {
+        let mut tracker = self.active_profiles.write().await;
+        let active = tracker
+            .get_mut(page_id)
+            .filter(|active| active.features.features.iter().any(|f| f == feature))
+            .ok_or_else(|| {
+                Error::configuration(format!("Feature {} is not applied to page {}", feature, page_id))
+            })?;
+
+        // The User-Agent override lives in the network layer, not in a script
+        if feature == "user_agent" {
+            return Err(Error::configuration("user_agent cannot be removed individually"));
+        }
+
+        for script_id in active.script_ids.get(feature).cloned().unwrap_or_default() {
+            self.injector.remove_script(page_id, &script_id).await?;
+        }
+        active.script_ids.remove(feature);
+        active.features.features.retain(|f| f != feature);
+
+        Ok(active.features.clone())
+    }
+
+    /// Get the profile applied to a page
+    async fn get_active_profile(&self, page_id: &str) -> Result<Option<ActiveProfile>, Error> {
+        let tracker = self.active_profiles.read().await;
+        Ok(tracker.get(page_id).cloned())
+    }
+
+    /// Get injected scripts from the injector's registry
+    async fn get_injected_scripts(&self, page_id: &str) -> Result<Vec<InjectedScript>, Error> {
+        match self.injector.get_injected_scripts(page_id).await {
+            Ok(scripts) => Ok(scripts),
+            Err(Error::PageNotFound(_)) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Remove all injections
     async fn remove_all(&self, page_id: &str) -> Result<(), Error> {
-        self.injector.clear_all(page_id).await?;
+        let result = self.injector.clear_all(page_id).await;
 
-        let mut tracker = self.applied_features.write().await;
+        // Forget the profile even if the page could not be reached
+        let mut tracker = self.active_profiles.write().await;
         tracker.remove(page_id);
+        self.isolated_pages.write().await.remove(page_id);
 
-        Ok(())
+        result
     }
 }
@@ -3,7 +3,10 @@
 //! Generates realistic browser fingerprints for various platforms.
 
 use async_trait::async_trait;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::sync::{Mutex, MutexGuard};
 
 use crate::Error;
 use super::super::services::traits as services;
@@ -53,11 +56,17 @@ pub const WEBGL_RENDERERS: &[&str] = &[
 ];
 
 /// Fingerprint generator implementation
-pub struct FingerprintGeneratorImpl;
+///
+/// All random choices come from one RNG, so a generator created with
+/// [`FingerprintGeneratorImpl::with_seed`] yields the same sequence of fingerprints
+/// for the same seed (with the same `rand` version).
+pub struct FingerprintGeneratorImpl {
+    rng: Mutex<StdRng>,
+}
 
 impl Default for FingerprintGeneratorImpl {
     fn default() -> Self {
-        Self
+        Self::new()
     }
 }
 
@@ -76,25 +85,45 @@ pub struct FingerprintConfig<'a> {
 }
 
 impl FingerprintGeneratorImpl {
-    /// Create a new fingerprint generator
+    /// Create a new fingerprint generator seeded from system entropy
     pub fn new() -> Self {
-        Self
+        Self {
+            rng: Mutex::new(StdRng::from_entropy()),
+        }
+    }
+
+    /// Create a deterministic fingerprint generator
+    ///
+    /// Generators with the same seed produce identical fingerprints (UA,
+    /// resolution, locale, hardware and WebGL) call for call.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Lock the generator's RNG
+    fn rng(&self) -> MutexGuard<'_, StdRng> {
+        self.rng.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Pick one item from a non-empty list
+    fn pick<T: Copy>(rng: &mut impl Rng, items: &[T]) -> T {
+        *items.choose(rng).expect("choice list is not empty")
     }
 
     /// Generate random hardware concurrency from predefined values
-    fn generate_hardware_concurrency() -> u32 {
-        let options = [4, 6, 8, 12, 16, 24, 32];
-        options[rand::random::<usize>() % options.len()]
+    fn generate_hardware_concurrency(rng: &mut impl Rng) -> u32 {
+        Self::pick(rng, &[4, 6, 8, 12, 16, 24, 32])
     }
 
     /// Generate random device memory from predefined values
-    fn generate_device_memory() -> Option<u32> {
-        let options = [4, 8, 16, 32];
-        Some(options[rand::random::<usize>() % options.len()])
+    fn generate_device_memory(rng: &mut impl Rng) -> Option<u32> {
+        Some(Self::pick(rng, &[4, 8, 16, 32]))
     }
 
     /// Generate random user agent from platform-specific list
-    fn random_user_agent(profile_type: services::ProfileType) -> &'static str {
+    fn random_user_agent(rng: &mut impl Rng, profile_type: services::ProfileType) -> &'static str {
         let agents = match profile_type {
             services::ProfileType::Windows => WINDOWS_USER_AGENTS,
             services::ProfileType::MacOS => MACOS_USER_AGENTS,
@@ -103,17 +132,17 @@ impl FingerprintGeneratorImpl {
             services::ProfileType::IOS => IOS_USER_AGENTS,
             services::ProfileType::Custom => WINDOWS_USER_AGENTS,
         };
-        agents[rand::random::<usize>() % agents.len()]
+        Self::pick(rng, agents)
     }
 
     /// Generate random WebGL vendor
-    fn random_webgl_vendor() -> &'static str {
-        WEBGL_VENDORS[rand::random::<usize>() % WEBGL_VENDORS.len()]
+    fn random_webgl_vendor(rng: &mut impl Rng) -> &'static str {
+        Self::pick(rng, WEBGL_VENDORS)
     }
 
     /// Generate random WebGL renderer
-    fn random_webgl_renderer() -> &'static str {
-        WEBGL_RENDERERS[rand::random::<usize>() % WEBGL_RENDERERS.len()]
+    fn random_webgl_renderer(rng: &mut impl Rng) -> &'static str {
+        Self::pick(rng, WEBGL_RENDERERS)
     }
 
     /// Create base fingerprint with common options
@@ -147,12 +176,13 @@ impl FingerprintGeneratorImpl {
                 inject_webgl: true,
                 inject_canvas: true,
                 inject_audio: true,
+                use_isolated_world: false,
             },
         }
     }
 
     /// Generate screen resolution for platform
-    fn generate_screen_resolution(profile_type: services::ProfileType) -> (u32, u32) {
+    fn generate_screen_resolution(rng: &mut impl Rng, profile_type: services::ProfileType) -> (u32, u32) {
         // Use Vec to allow different sizes per platform
         let resolutions: Vec<(u32, u32)> = match profile_type {
             services::ProfileType::Windows => {
@@ -174,22 +204,20 @@ impl FingerprintGeneratorImpl {
                 vec![(1920, 1080)]
             }
         };
-        resolutions[rand::random::<usize>() % resolutions.len()]
+        Self::pick(rng, &resolutions)
     }
 
     /// Generate locale for platform
     #[allow(unused_variables)]
-    fn generate_locale(profile_type: services::ProfileType) -> String {
+    fn generate_locale(rng: &mut impl Rng, profile_type: services::ProfileType) -> String {
         // Common locales for desktop and mobile platforms
         let locales = ["en-US", "en-GB", "de-DE", "fr-FR", "es-ES", "ja-JP", "zh-CN"];
-        locales[rand::random::<usize>() % locales.len()].to_string()
+        Self::pick(rng, &locales).to_string()
     }
 
     /// Generate timezone
     #[allow(dead_code)]
-    fn generate_timezone(_profile_type: services::ProfileType) -> String {
-        let mut rng = rand::thread_rng();
-
+    fn generate_timezone(rng: &mut impl Rng, _profile_type: services::ProfileType) -> String {
         let timezones = vec![
             "America/New_York",
             "America/Chicago",
@@ -211,70 +239,74 @@ impl FingerprintGeneratorImpl {
 impl super::traits::FingerprintGenerator for FingerprintGeneratorImpl {
     /// Generate a Windows fingerprint
     async fn generate_windows(&self) -> Result<services::Fingerprint, Error> {
-        let screen = Self::generate_screen_resolution(services::ProfileType::Windows);
-        let locale = Self::generate_locale(services::ProfileType::Windows);
+        let mut rng = self.rng();
+        let screen = Self::generate_screen_resolution(&mut *rng, services::ProfileType::Windows);
+        let locale = Self::generate_locale(&mut *rng, services::ProfileType::Windows);
 
         Ok(Self::create_base_fingerprint(FingerprintConfig {
             platform: "Win32",
             vendor: "Google Inc.",
-            user_agent: Self::random_user_agent(services::ProfileType::Windows),
+            user_agent: Self::random_user_agent(&mut *rng, services::ProfileType::Windows),
             locale: &locale,
             screen,
-            webgl_vendor: Self::random_webgl_vendor(),
-            webgl_renderer: Self::random_webgl_renderer(),
-            hardware_concurrency: Self::generate_hardware_concurrency(),
-            device_memory: Self::generate_device_memory(),
+            webgl_vendor: Self::random_webgl_vendor(&mut *rng),
+            webgl_renderer: Self::random_webgl_renderer(&mut *rng),
+            hardware_concurrency: Self::generate_hardware_concurrency(&mut *rng),
+            device_memory: Self::generate_device_memory(&mut *rng),
             accept_encoding: "gzip, deflate, br",
         }))
     }
 
     /// Generate a macOS fingerprint
     async fn generate_macos(&self) -> Result<services::Fingerprint, Error> {
-        let screen = Self::generate_screen_resolution(services::ProfileType::MacOS);
-        let locale = Self::generate_locale(services::ProfileType::MacOS);
+        let mut rng = self.rng();
+        let screen = Self::generate_screen_resolution(&mut *rng, services::ProfileType::MacOS);
+        let locale = Self::generate_locale(&mut *rng, services::ProfileType::MacOS);
 
         Ok(Self::create_base_fingerprint(FingerprintConfig {
             platform: "MacIntel",
             vendor: "Google Inc.",
-            user_agent: Self::random_user_agent(services::ProfileType::MacOS),
+            user_agent: Self::random_user_agent(&mut *rng, services::ProfileType::MacOS),
             locale: &locale,
             screen,
-            webgl_vendor: Self::random_webgl_vendor(),
-            webgl_renderer: Self::random_webgl_renderer(),
-            hardware_concurrency: Self::generate_hardware_concurrency(),
-            device_memory: Self::generate_device_memory(),
+            webgl_vendor: Self::random_webgl_vendor(&mut *rng),
+            webgl_renderer: Self::random_webgl_renderer(&mut *rng),
+            hardware_concurrency: Self::generate_hardware_concurrency(&mut *rng),
+            device_memory: Self::generate_device_memory(&mut *rng),
             accept_encoding: "gzip, deflate, br",
         }))
     }
 
     /// Generate a Linux fingerprint
     async fn generate_linux(&self) -> Result<services::Fingerprint, Error> {
-        let screen = Self::generate_screen_resolution(services::ProfileType::Linux);
-        let locale = Self::generate_locale(services::ProfileType::Linux);
+        let mut rng = self.rng();
+        let screen = Self::generate_screen_resolution(&mut *rng, services::ProfileType::Linux);
+        let locale = Self::generate_locale(&mut *rng, services::ProfileType::Linux);
 
         Ok(Self::create_base_fingerprint(FingerprintConfig {
             platform: "Linux x86_64",
             vendor: "",
-            user_agent: Self::random_user_agent(services::ProfileType::Linux),
+            user_agent: Self::random_user_agent(&mut *rng, services::ProfileType::Linux),
             locale: &locale,
             screen,
-            webgl_vendor: Self::random_webgl_vendor(),
-            webgl_renderer: Self::random_webgl_renderer(),
-            hardware_concurrency: Self::generate_hardware_concurrency(),
-            device_memory: Self::generate_device_memory(),
+            webgl_vendor: Self::random_webgl_vendor(&mut *rng),
+            webgl_renderer: Self::random_webgl_renderer(&mut *rng),
+            hardware_concurrency: Self::generate_hardware_concurrency(&mut *rng),
+            device_memory: Self::generate_device_memory(&mut *rng),
             accept_encoding: "gzip, deflate",
         }))
     }
 
     /// Generate an Android fingerprint
     async fn generate_android(&self) -> Result<services::Fingerprint, Error> {
-        let screen = Self::generate_screen_resolution(services::ProfileType::Android);
-        let locale = Self::generate_locale(services::ProfileType::Android);
+        let mut rng = self.rng();
+        let screen = Self::generate_screen_resolution(&mut *rng, services::ProfileType::Android);
+        let locale = Self::generate_locale(&mut *rng, services::ProfileType::Android);
 
         Ok(Self::create_base_fingerprint(FingerprintConfig {
             platform: "Linux armv8l",
             vendor: "Google Inc.",
-            user_agent: Self::random_user_agent(services::ProfileType::Android),
+            user_agent: Self::random_user_agent(&mut *rng, services::ProfileType::Android),
             locale: &locale,
             screen,
             webgl_vendor: "Qualcomm",
@@ -287,13 +319,14 @@ impl super::traits::FingerprintGenerator for FingerprintGeneratorImpl {
 
     /// Generate an iOS fingerprint
     async fn generate_ios(&self) -> Result<services::Fingerprint, Error> {
-        let screen = Self::generate_screen_resolution(services::ProfileType::IOS);
-        let locale = Self::generate_locale(services::ProfileType::IOS);
+        let mut rng = self.rng();
+        let screen = Self::generate_screen_resolution(&mut *rng, services::ProfileType::IOS);
+        let locale = Self::generate_locale(&mut *rng, services::ProfileType::IOS);
 
         Ok(Self::create_base_fingerprint(FingerprintConfig {
             platform: "iPhone",
             vendor: "Apple Computer, Inc.",
-            user_agent: Self::random_user_agent(services::ProfileType::IOS),
+            user_agent: Self::random_user_agent(&mut *rng, services::ProfileType::IOS),
             locale: &locale,
             screen,
             webgl_vendor: "Apple Inc.",
@@ -350,6 +383,7 @@ impl super::traits::FingerprintGenerator for FingerprintGeneratorImpl {
                 inject_webgl: true,
                 inject_canvas: true,
                 inject_audio: true,
+                use_isolated_world: false,
             },
         })
     }
@@ -358,14 +392,26 @@ impl super::traits::FingerprintGenerator for FingerprintGeneratorImpl {
     async fn randomize(
         &self,
         fingerprint: &services::Fingerprint,
+        seed: Option<u64>,
     ) -> Result<services::Fingerprint, Error> {
-        let mut rng = rand::thread_rng();
+        let mut seeded;
+        let mut shared;
+        let rng: &mut StdRng = match seed {
+            Some(seed) => {
+                seeded = StdRng::seed_from_u64(seed);
+                &mut seeded
+            }
+            None => {
+                shared = self.rng();
+                &mut shared
+            }
+        };
 
         // Randomize hardware concurrency
-        let new_concurrency = Self::generate_hardware_concurrency();
+        let new_concurrency = Self::generate_hardware_concurrency(rng);
 
         // Randomize device memory
-        let new_memory = Self::generate_device_memory();
+        let new_memory = Self::generate_device_memory(rng);
 
         // Small randomization of screen dimensions
         let width_variation = rng.gen_range(-5..5);
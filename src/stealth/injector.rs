@@ -34,11 +34,19 @@ impl ScriptInjectorImpl {
     }
 
     /// Track injected script
-    async fn track_script(&self, page_id: &str, script_id: String, script_type: ScriptType, content: String) {
+    async fn track_script(
+        &self,
+        page_id: &str,
+        script_id: String,
+        script_type: ScriptType,
+        content: String,
+        identifier: Option<String>,
+    ) {
         let injected = InjectedScript {
             script_id,
             script_type,
             content,
+            identifier,
         };
 
         let mut tracker = self.injected_scripts.write().await;
@@ -48,48 +56,72 @@ impl ScriptInjectorImpl {
             .push(injected);
     }
 
-    /// Get CDP client for page
-    async fn get_cdp_client(&self, page_id: &str) -> Result<Arc<dyn crate::cdp::CdpClient>, Error> {
-        let page = self.session_manager.get_page(page_id).await?;
-        Ok(page.get_cdp_client())
-    }
-}
-
-#[async_trait]
-impl ScriptInjector for ScriptInjectorImpl {
-    /// Inject JavaScript before page load
-    async fn inject_init_script(&self, page_id: &str, script: &str) -> Result<(), Error> {
+    /// Register `script` for new documents and run it in the current one
+    ///
+    /// With `isolated` set the script runs in the page's isolated world
+    /// instead of the main world.
+    async fn add_init_script(&self, page_id: &str, script: &str, isolated: bool) -> Result<String, Error> {
         let script_id = self.generate_script_id();
         tracing::debug!("[P5-DEBUG] Injecting script for page {}: {} bytes", page_id, script.len());
 
-        let cdp_client = self.get_cdp_client(page_id).await?;
+        let page = self.session_manager.get_page(page_id).await?;
+        let cdp_client = page.get_cdp_client();
 
         // Add script to evaluate on new document
-        let params = serde_json::json!({ "source": script });
-        match cdp_client.call_method("Page.addScriptToEvaluateOnNewDocument", params).await {
+        let mut params = serde_json::json!({ "source": script });
+        if isolated {
+            params["worldName"] = serde_json::json!(crate::session::page::ISOLATED_WORLD_NAME);
+        }
+        let identifier = match cdp_client.call_method("Page.addScriptToEvaluateOnNewDocument", params).await {
             Ok(result) => {
-                if let Some(identifier) = result.get("identifier") {
+                let identifier = result.get("identifier").and_then(|v| v.as_str()).map(str::to_string);
+                if let Some(identifier) = &identifier {
                     tracing::debug!("[P5-DEBUG] Script added with identifier: {}", identifier);
                 }
+                identifier
             }
             Err(e) => {
                 tracing::error!("[P5-DEBUG] Failed to add script: {}", e);
                 return Err(Error::internal(format!("Page.addScriptToEvaluateOnNewDocument failed: {}", e)));
             }
-        }
+        };
 
         // Evaluate immediately for current page
-        let eval_params = serde_json::json!({
-            "expression": script,
-            "awaitPromise": true
-        });
-
-        if let Err(e) = cdp_client.call_method("Runtime.evaluate", eval_params).await {
+        let evaluated = if isolated {
+            page.evaluate_isolated(script, true).await.map(|_| ())
+        } else {
+            let eval_params = serde_json::json!({
+                "expression": script,
+                "awaitPromise": true
+            });
+            cdp_client.call_method("Runtime.evaluate", eval_params).await.map(|_| ())
+        };
+        if let Err(e) = evaluated {
             tracing::warn!("[P5-DEBUG] Runtime.evaluate failed (non-critical): {}", e);
         }
 
-        self.track_script(page_id, script_id, ScriptType::InitScript, script.to_string()).await;
-        Ok(())
+        self.track_script(page_id, script_id.clone(), ScriptType::InitScript, script.to_string(), identifier)
+            .await;
+        Ok(script_id)
+    }
+
+    /// Get CDP client for page
+    async fn get_cdp_client(&self, page_id: &str) -> Result<Arc<dyn crate::cdp::CdpClient>, Error> {
+        let page = self.session_manager.get_page(page_id).await?;
+        Ok(page.get_cdp_client())
+    }
+}
+
+#[async_trait]
+impl ScriptInjector for ScriptInjectorImpl {
+    /// Inject JavaScript before page load
+    async fn inject_init_script(&self, page_id: &str, script: &str) -> Result<String, Error> {
+        self.add_init_script(page_id, script, false).await
+    }
+
+    /// Inject JavaScript before page load into the page's isolated world
+    async fn inject_isolated_init_script(&self, page_id: &str, script: &str) -> Result<String, Error> {
+        self.add_init_script(page_id, script, true).await
     }
 
     /// Evaluate JavaScript in the page
@@ -124,7 +156,8 @@ impl ScriptInjector for ScriptInjectorImpl {
         );
 
         self.evaluate(page_id, &script).await?;
-        self.track_script(page_id, self.generate_script_id(), ScriptType::Style, css.to_string()).await;
+        self.track_script(page_id, self.generate_script_id(), ScriptType::Style, css.to_string(), None)
+            .await;
         Ok(())
     }
 
@@ -138,6 +171,12 @@ impl ScriptInjector for ScriptInjectorImpl {
         Ok(())
     }
 
+    /// Set the screen size at CDP protocol level
+    async fn set_screen_size(&self, page_id: &str, width: u32, height: u32) -> Result<(), Error> {
+        let page = self.session_manager.get_page(page_id).await?;
+        page.set_screen_size(width, height).await
+    }
+
     /// Get all injected scripts
     async fn get_injected_scripts(&self, page_id: &str) -> Result<Vec<InjectedScript>, Error> {
         let tracker = self.injected_scripts.read().await;
@@ -148,7 +187,26 @@ impl ScriptInjector for ScriptInjectorImpl {
     }
 
     /// Remove injected script
+    ///
+    /// Overrides the script already applied to the current document stay in
+    /// effect until the page is reloaded.
     async fn remove_script(&self, page_id: &str, script_id: &str) -> Result<(), Error> {
+        let identifier = self
+            .injected_scripts
+            .read()
+            .await
+            .get(page_id)
+            .and_then(|scripts| scripts.iter().find(|s| s.script_id == script_id))
+            .and_then(|s| s.identifier.clone());
+
+        if let Some(identifier) = identifier {
+            let cdp_client = self.get_cdp_client(page_id).await?;
+            let params = serde_json::json!({ "identifier": identifier });
+            cdp_client
+                .call_method("Page.removeScriptToEvaluateOnNewDocument", params)
+                .await?;
+        }
+
         let mut tracker = self.injected_scripts.write().await;
 
         if let Some(scripts) = tracker.get_mut(page_id) {
@@ -159,9 +217,27 @@ impl ScriptInjector for ScriptInjectorImpl {
     }
 
     /// Clear all injected scripts
+    ///
+    /// The page is forgotten even if unregistering fails, e.g. because it is
+    /// closing.
     async fn clear_all(&self, page_id: &str) -> Result<(), Error> {
-        let mut tracker = self.injected_scripts.write().await;
-        tracker.remove(page_id);
+        let identifiers: Vec<String> = self
+            .injected_scripts
+            .write()
+            .await
+            .remove(page_id)
+            .map(|scripts| scripts.into_iter().filter_map(|s| s.identifier).collect())
+            .unwrap_or_default();
+
+        if !identifiers.is_empty() {
+            let cdp_client = self.get_cdp_client(page_id).await?;
+            for identifier in identifiers {
+                let params = serde_json::json!({ "identifier": identifier });
+                cdp_client
+                    .call_method("Page.removeScriptToEvaluateOnNewDocument", params)
+                    .await?;
+            }
+        }
 
         Ok(())
     }
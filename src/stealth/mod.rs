@@ -45,8 +45,9 @@ mod tests;
 
 pub use traits::{
     StealthEngine, ScriptInjector, BehaviorSimulator, FingerprintGenerator, ProfileManager,
-    AppliedFeatures, InjectedScript, ScriptType,
-    MouseMoveOptions, TypingOptions, ClickOptions, ScrollOptions,
+    AppliedFeatures, ActiveProfile, InjectedScript, ScriptType,
+    MouseMoveOptions, TypingOptions, ClickOptions, ScrollOptions, MouseButton, TracePoint,
+    MAX_MOUSE_TRACE_DURATION_MS, MAX_IDLE_DURATION_MS,
 };
 
 pub use engine::StealthEngineImpl;
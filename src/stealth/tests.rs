@@ -11,7 +11,7 @@ mod tests {
     use super::super::*;
     use super::super::traits::*;
     use super::super::super::services::traits as services;
-    use super::super::super::session::mock::MockSessionManager;
+    use super::super::super::session::mock::{MockPage, MockSessionManager};
     use super::super::super::cdp::mock::MockCdpClient;
     use std::sync::Arc;
 
@@ -163,8 +163,8 @@ mod tests {
         let base = generator.generate_windows().await.unwrap();
 
         // Generate multiple randomizations
-        let randomized1 = generator.randomize(&base).await.unwrap();
-        let randomized2 = generator.randomize(&base).await.unwrap();
+        let randomized1 = generator.randomize(&base, None).await.unwrap();
+        let randomized2 = generator.randomize(&base, None).await.unwrap();
 
         // Verify hardware concurrency changed
         assert_ne!(
@@ -189,6 +189,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_fingerprint_seeded_generators_match() {
+        let first = FingerprintGeneratorImpl::with_seed(42);
+        let second = FingerprintGeneratorImpl::with_seed(42);
+
+        for _ in 0..3 {
+            let a = first.generate_windows().await.unwrap();
+            let b = second.generate_windows().await.unwrap();
+            assert_eq!(format!("{:?}", a), format!("{:?}", b));
+
+            let a = first.generate_android().await.unwrap();
+            let b = second.generate_android().await.unwrap();
+            assert_eq!(format!("{:?}", a), format!("{:?}", b));
+        }
+
+        // Seeded randomization depends only on the seed
+        let base = first.generate_macos().await.unwrap();
+        let a = first.randomize(&base, Some(7)).await.unwrap();
+        let b = second.randomize(&base, Some(7)).await.unwrap();
+        assert_eq!(format!("{:?}", a), format!("{:?}", b));
+    }
+
     #[tokio::test]
     async fn test_fingerprint_user_agent_variety() {
         let generator = FingerprintGeneratorImpl::new();
@@ -510,6 +532,7 @@ mod tests {
                     inject_webgl: true,
                     inject_canvas: true,
                     inject_audio: true,
+                    use_isolated_world: false,
                 },
             },
         };
@@ -571,6 +594,7 @@ mod tests {
                     inject_webgl: false,
                     inject_canvas: false,
                     inject_audio: false,
+                    use_isolated_world: false,
                 },
             },
         };
@@ -632,6 +656,7 @@ mod tests {
                     inject_webgl: false,
                     inject_canvas: false,
                     inject_audio: false,
+                    use_isolated_world: false,
                 },
             },
         };
@@ -660,6 +685,192 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_stealth_engine_get_active_profile() {
+        let mock_session = Arc::new(MockSessionManager::new());
+        let mock_cdp = Arc::new(MockCdpClient::new());
+        let injector = Arc::new(ScriptInjectorImpl::new(mock_session.clone())) as Arc<dyn ScriptInjector>;
+        let simulator =
+            Arc::new(BehaviorSimulatorImpl::new(mock_cdp.clone())) as Arc<dyn BehaviorSimulator>;
+
+        let engine = StealthEngineImpl::new(injector.clone(), simulator);
+
+        let page_id = mock_session
+            .register_page(Arc::new(MockPage::new("browser_1".to_string(), Default::default())))
+            .await;
+        let page_id = page_id.as_str();
+        assert!(engine.get_active_profile(page_id).await.unwrap().is_none());
+
+        // Apply a profile with only navigator and WebGL enabled
+        let profile = services::Profile {
+            profile_id: "partial_profile".to_string(),
+            profile_type: services::ProfileType::Windows,
+            fingerprint: services::Fingerprint {
+                headers: services::HeadersFingerprint {
+                    user_agent: "Mozilla/5.0".to_string(),
+                    accept_language: "en-US".to_string(),
+                    accept_encoding: "gzip".to_string(),
+                },
+                navigator: services::NavigatorFingerprint {
+                    platform: "Win32".to_string(),
+                    vendor: "Google Inc.".to_string(),
+                    hardware_concurrency: 8,
+                    device_memory: Some(8),
+                    language: "en-US".to_string(),
+                },
+                screen: services::ScreenFingerprint {
+                    width: 1920,
+                    height: 1080,
+                    color_depth: 24,
+                    pixel_depth: 24,
+                },
+                webgl: services::WebGLFingerprint {
+                    vendor: "Google Inc.".to_string(),
+                    renderer: "ANGLE".to_string(),
+                },
+                options: services::ProfileOptions {
+                    inject_navigator: true,
+                    inject_screen: false,
+                    inject_webgl: true,
+                    inject_canvas: false,
+                    inject_audio: false,
+                    use_isolated_world: false,
+                },
+            },
+        };
+
+        engine.apply_profile(page_id, &profile).await.unwrap();
+
+        let active = engine.get_active_profile(page_id).await.unwrap().unwrap();
+        assert_eq!(active.profile_id, "partial_profile");
+        assert_eq!(active.features.features, vec!["user_agent", "navigator", "webgl"]);
+        assert!(active.script_ids["user_agent"].is_empty());
+        assert!(!active.script_ids.contains_key("screen"));
+
+        // Reported script ids are the ones the injector tracked
+        let injected: Vec<String> = injector
+            .get_injected_scripts(page_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|script| script.script_id)
+            .collect();
+        assert_eq!(
+            vec![active.script_ids["navigator"][0].clone(), active.script_ids["webgl"][0].clone()],
+            injected
+        );
+
+        engine.remove_all(page_id).await.unwrap();
+        assert!(engine.get_active_profile(page_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stealth_engine_apply_profile_sets_screen_size() {
+        let mock_session = Arc::new(MockSessionManager::new());
+        let mock_cdp = Arc::new(MockCdpClient::new());
+        let injector = Arc::new(ScriptInjectorImpl::new(mock_session.clone())) as Arc<dyn ScriptInjector>;
+        let simulator =
+            Arc::new(BehaviorSimulatorImpl::new(mock_cdp.clone())) as Arc<dyn BehaviorSimulator>;
+        let engine = StealthEngineImpl::new(injector, simulator);
+
+        let page = Arc::new(MockPage::new("browser_1".to_string(), Default::default()));
+        let page_id = mock_session.register_page(page.clone()).await;
+
+        let mut fingerprint = FingerprintGeneratorImpl::new().generate_windows().await.unwrap();
+        fingerprint.screen.width = 2560;
+        fingerprint.screen.height = 1440;
+        let profile = services::Profile {
+            profile_id: "screen_profile".to_string(),
+            profile_type: services::ProfileType::Windows,
+            fingerprint,
+        };
+
+        engine.apply_profile(&page_id, &profile).await.unwrap();
+
+        assert_eq!(page.screen_size().await, (2560, 1440));
+    }
+
+    #[tokio::test]
+    async fn test_stealth_engine_remove_feature() {
+        let mock_session = Arc::new(MockSessionManager::new());
+        let mock_cdp = Arc::new(MockCdpClient::new());
+        let page_id = mock_session
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser_1".to_string(),
+                Default::default(),
+                mock_cdp.clone(),
+            )))
+            .await;
+        let page_id = page_id.as_str();
+        let injector = Arc::new(ScriptInjectorImpl::new(mock_session.clone())) as Arc<dyn ScriptInjector>;
+        let simulator =
+            Arc::new(BehaviorSimulatorImpl::new(mock_cdp.clone())) as Arc<dyn BehaviorSimulator>;
+
+        let engine = StealthEngineImpl::new(injector.clone(), simulator);
+
+        let profile = services::Profile {
+            profile_id: "test_profile".to_string(),
+            profile_type: services::ProfileType::Windows,
+            fingerprint: services::Fingerprint {
+                headers: services::HeadersFingerprint {
+                    user_agent: "Mozilla/5.0".to_string(),
+                    accept_language: "en-US".to_string(),
+                    accept_encoding: "gzip".to_string(),
+                },
+                navigator: services::NavigatorFingerprint {
+                    platform: "Win32".to_string(),
+                    vendor: "Google Inc.".to_string(),
+                    hardware_concurrency: 8,
+                    device_memory: Some(8),
+                    language: "en-US".to_string(),
+                },
+                screen: services::ScreenFingerprint {
+                    width: 1920,
+                    height: 1080,
+                    color_depth: 24,
+                    pixel_depth: 24,
+                },
+                webgl: services::WebGLFingerprint {
+                    vendor: "Google Inc.".to_string(),
+                    renderer: "ANGLE".to_string(),
+                },
+                options: services::ProfileOptions {
+                    inject_navigator: true,
+                    inject_screen: false,
+                    inject_webgl: true,
+                    inject_canvas: false,
+                    inject_audio: false,
+                    use_isolated_world: false,
+                },
+            },
+        };
+
+        engine.apply_profile(page_id, &profile).await.unwrap();
+        let webgl_script = injector.get_injected_scripts(page_id).await.unwrap()[1].clone();
+
+        let remaining = engine.remove_feature(page_id, "webgl").await.unwrap();
+        assert_eq!(remaining.features, vec!["user_agent", "navigator"]);
+
+        // Only the WebGL script is unregistered from new documents
+        let removed = mock_cdp.recorded_calls("Page.removeScriptToEvaluateOnNewDocument").await;
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0]["identifier"], webgl_script.identifier.unwrap());
+
+        let scripts = injector.get_injected_scripts(page_id).await.unwrap();
+        assert_eq!(scripts.len(), 1);
+        assert!(scripts[0].content.contains("navigator"));
+
+        let active = engine.get_active_profile(page_id).await.unwrap().unwrap();
+        assert!(active.script_ids.contains_key("navigator"));
+        assert!(!active.script_ids.contains_key("webgl"));
+
+        // A feature that is no longer applied cannot be removed again
+        assert!(matches!(
+            engine.remove_feature(page_id, "webgl").await,
+            Err(crate::Error::Configuration(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_stealth_engine_remove_all() {
         let mock_session = Arc::new(MockSessionManager::new());
@@ -705,6 +916,7 @@ mod tests {
                     inject_webgl: false,
                     inject_canvas: false,
                     inject_audio: false,
+                    use_isolated_world: false,
                 },
             },
         };
@@ -756,6 +968,48 @@ mod tests {
         assert_eq!(options.hold_duration_ms, 50);
     }
 
+    #[tokio::test]
+    async fn test_behavior_simulator_dispatches_to_page_client() {
+        let mock_session = Arc::new(MockSessionManager::new());
+        let first_cdp = Arc::new(MockCdpClient::new());
+        let second_cdp = Arc::new(MockCdpClient::new());
+        mock_session
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser_1".to_string(),
+                Default::default(),
+                first_cdp.clone(),
+            )))
+            .await;
+        let second_page = mock_session
+            .register_page(Arc::new(MockPage::with_cdp_client(
+                "browser_1".to_string(),
+                Default::default(),
+                second_cdp.clone(),
+            )))
+            .await;
+
+        let simulator = BehaviorSimulatorImpl::with_session_manager(mock_session.clone());
+        let options = ClickOptions {
+            delay_before_ms: 0,
+            movement_duration_ms: 0,
+            hold_duration_ms: 0,
+        };
+        simulator
+            .simulate_click_at(&second_page, (40.0, 60.0), MouseButton::Left, 1, options)
+            .await
+            .unwrap();
+
+        let events = second_cdp.recorded_calls("Input.dispatchMouseEvent").await;
+        assert!(events.iter().any(|e| e["type"] == "mousePressed" && e["x"] == 40.0));
+        assert!(first_cdp.recorded_calls("Input.dispatchMouseEvent").await.is_empty());
+
+        // Unknown pages are reported instead of silently going elsewhere
+        let result = simulator
+            .simulate_click_at("missing_page", (1.0, 1.0), MouseButton::Left, 1, ClickOptions::default())
+            .await;
+        assert!(matches!(result, Err(crate::Error::PageNotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_behavior_simulator_scroll_options() {
         let options = ScrollOptions::default();
@@ -788,6 +1042,7 @@ mod tests {
             duration_ms: 100,
             deviation: 20.0,
             points: 10,
+            ..Default::default()
         };
 
         // Simulate mouse movement (will use mock CDP)
@@ -796,6 +1051,40 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_behavior_simulator_overshoot_passes_target() {
+        let mock_cdp = Arc::new(MockCdpClient::new());
+        let simulator = BehaviorSimulatorImpl::new(mock_cdp.clone());
+
+        let options = MouseMoveOptions {
+            duration_ms: 0,
+            deviation: 0.0,
+            points: 20,
+            curvature: 0.2,
+            overshoot_probability: 1.0,
+            overshoot_magnitude: 0.2,
+        };
+        simulator
+            .simulate_mouse_move("test_page", (0.0, 0.0), (100.0, 0.0), options)
+            .await
+            .unwrap();
+
+        let path: Vec<(f64, f64)> = mock_cdp
+            .recorded_calls("Input.dispatchMouseEvent")
+            .await
+            .iter()
+            .map(|e| (e["x"].as_f64().unwrap(), e["y"].as_f64().unwrap()))
+            .collect();
+
+        // The cursor goes past the target, then settles exactly on it
+        let farthest = path.iter().map(|p| p.0).fold(f64::MIN, f64::max);
+        assert!(farthest > 110.0, "path never overshot: {:?}", path);
+        assert_eq!(*path.last().unwrap(), (100.0, 0.0));
+
+        // Curvature bends the path off the straight line
+        assert!(path.iter().any(|p| p.1.abs() > 5.0));
+    }
+
     // ============================================================================
     // End-to-End Tests
     // ============================================================================
@@ -905,9 +1194,9 @@ mod tests {
         let base = generator.generate_windows().await.unwrap();
 
         // Create multiple randomizations
-        let rand1 = generator.randomize(&base).await.unwrap();
-        let rand2 = generator.randomize(&rand1).await.unwrap();
-        let rand3 = generator.randomize(&rand2).await.unwrap();
+        let rand1 = generator.randomize(&base, None).await.unwrap();
+        let rand2 = generator.randomize(&rand1, None).await.unwrap();
+        let rand3 = generator.randomize(&rand2, None).await.unwrap();
 
         // Create profiles
         let profile1 = services::Profile {
@@ -991,6 +1280,7 @@ mod tests {
                     inject_webgl: false,
                     inject_canvas: false,
                     inject_audio: false,
+                    use_isolated_world: false,
                 },
             },
         };
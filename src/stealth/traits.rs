@@ -4,6 +4,7 @@
 //! and human behavior simulation.
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use super::super::services::traits as services;
 
 // ============================================================================
@@ -25,36 +26,45 @@ pub trait StealthEngine: Send + Sync {
     /// Set User-Agent at CDP protocol level
     async fn set_user_agent(&self, page_id: &str, user_agent: &str) -> Result<(), crate::Error>;
 
-    /// Inject navigator overrides
+    /// Inject navigator overrides, returning the injected script id
     async fn inject_navigator(
         &self,
         page_id: &str,
         fingerprint: &services::NavigatorFingerprint,
-    ) -> Result<(), crate::Error>;
+    ) -> Result<String, crate::Error>;
 
-    /// Inject screen overrides
+    /// Inject screen overrides, returning the injected script id
     async fn inject_screen(
         &self,
         page_id: &str,
         fingerprint: &services::ScreenFingerprint,
-    ) -> Result<(), crate::Error>;
+    ) -> Result<String, crate::Error>;
 
-    /// Inject WebGL protection
+    /// Inject WebGL protection, returning the injected script id
     async fn inject_webgl(
         &self,
         page_id: &str,
         fingerprint: &services::WebGLFingerprint,
-    ) -> Result<(), crate::Error>;
+    ) -> Result<String, crate::Error>;
 
-    /// Inject canvas protection
-    async fn inject_canvas(&self, page_id: &str) -> Result<(), crate::Error>;
+    /// Inject canvas protection, returning the injected script id
+    async fn inject_canvas(&self, page_id: &str) -> Result<String, crate::Error>;
 
-    /// Inject audio protection
-    async fn inject_audio(&self, page_id: &str) -> Result<(), crate::Error>;
+    /// Inject audio protection, returning the injected script id
+    async fn inject_audio(&self, page_id: &str) -> Result<String, crate::Error>;
 
     /// Get applied features
     async fn get_applied_features(&self, page_id: &str) -> Result<AppliedFeatures, crate::Error>;
 
+    /// Remove the injected scripts of one applied feature, returning the remaining features
+    async fn remove_feature(&self, page_id: &str, feature: &str) -> Result<AppliedFeatures, crate::Error>;
+
+    /// Get the profile applied to a page, if any
+    async fn get_active_profile(&self, page_id: &str) -> Result<Option<ActiveProfile>, crate::Error>;
+
+    /// Get the scripts injected into a page (empty if none)
+    async fn get_injected_scripts(&self, page_id: &str) -> Result<Vec<InjectedScript>, crate::Error>;
+
     /// Remove all injections
     async fn remove_all(&self, page_id: &str) -> Result<(), crate::Error>;
 }
@@ -65,6 +75,15 @@ pub struct AppliedFeatures {
     pub features: Vec<String>,
 }
 
+/// Profile applied to a page and the scripts it injected
+#[derive(Debug, Clone)]
+pub struct ActiveProfile {
+    pub profile_id: String,
+    pub features: AppliedFeatures,
+    /// Injected script ids per feature; features applied over CDP (e.g. `user_agent`) have none
+    pub script_ids: HashMap<String, Vec<String>>,
+}
+
 // ============================================================================
 // Script Injector
 // ============================================================================
@@ -74,8 +93,11 @@ pub struct AppliedFeatures {
 /// Handles injection of JavaScript code for fingerprint modification.
 #[async_trait]
 pub trait ScriptInjector: Send + Sync {
-    /// Inject JavaScript before page load
-    async fn inject_init_script(&self, page_id: &str, script: &str) -> Result<(), crate::Error>;
+    /// Inject JavaScript before page load, returning the tracked script id
+    async fn inject_init_script(&self, page_id: &str, script: &str) -> Result<String, crate::Error>;
+
+    /// Inject JavaScript before page load into the page's isolated world, returning the tracked script id
+    async fn inject_isolated_init_script(&self, page_id: &str, script: &str) -> Result<String, crate::Error>;
 
     /// Evaluate JavaScript in the page
     async fn evaluate(&self, page_id: &str, script: &str) -> Result<String, crate::Error>;
@@ -86,13 +108,16 @@ pub trait ScriptInjector: Send + Sync {
     /// Set User-Agent at CDP protocol level
     async fn set_user_agent(&self, page_id: &str, user_agent: &str) -> Result<(), crate::Error>;
 
+    /// Set the screen size at CDP protocol level, keeping `window.screen` coherent with the viewport
+    async fn set_screen_size(&self, page_id: &str, width: u32, height: u32) -> Result<(), crate::Error>;
+
     /// Get all injected scripts
     async fn get_injected_scripts(&self, page_id: &str) -> Result<Vec<InjectedScript>, crate::Error>;
 
-    /// Remove injected script
+    /// Remove injected script so it no longer runs on new documents
     async fn remove_script(&self, page_id: &str, script_id: &str) -> Result<(), crate::Error>;
 
-    /// Clear all injected scripts
+    /// Clear all injected scripts, unregistering them from new documents
     async fn clear_all(&self, page_id: &str) -> Result<(), crate::Error>;
 }
 
@@ -102,6 +127,8 @@ pub struct InjectedScript {
     pub script_id: String,
     pub script_type: ScriptType,
     pub content: String,
+    /// CDP identifier from `Page.addScriptToEvaluateOnNewDocument`
+    pub identifier: Option<String>,
 }
 
 /// Script type
@@ -147,6 +174,19 @@ pub trait BehaviorSimulator: Send + Sync {
         options: ClickOptions,
     ) -> Result<(), crate::Error>;
 
+    /// Simulate a human-like click at viewport coordinates
+    ///
+    /// Approaches the point along a Bezier path, then presses and releases
+    /// `button` `click_count` times.
+    async fn simulate_click_at(
+        &self,
+        page_id: &str,
+        point: (f64, f64),
+        button: MouseButton,
+        click_count: u32,
+        options: ClickOptions,
+    ) -> Result<(), crate::Error>;
+
     /// Simulate scroll behavior
     async fn simulate_scroll(
         &self,
@@ -155,6 +195,19 @@ pub trait BehaviorSimulator: Send + Sync {
         options: ScrollOptions,
     ) -> Result<(), crate::Error>;
 
+    /// Replay a recorded mouse trace as `mouseMoved` events
+    ///
+    /// Points are dispatched at their recorded offsets from the first point.
+    /// Timestamps must not decrease and the trace may span at most
+    /// [`MAX_MOUSE_TRACE_DURATION_MS`].
+    async fn replay_mouse_trace(&self, page_id: &str, trace: &[TracePoint]) -> Result<(), crate::Error>;
+
+    /// Fidget like a reader for `duration_ms`: small mouse moves and the odd
+    /// scroll, kept inside the viewport
+    ///
+    /// At most [`MAX_IDLE_DURATION_MS`].
+    async fn simulate_idle(&self, page_id: &str, duration_ms: u64) -> Result<(), crate::Error>;
+
     /// Add random delay
     async fn random_delay(&self, min_ms: u64, max_ms: u64) -> Result<(), crate::Error>;
 }
@@ -168,6 +221,13 @@ pub struct MouseMoveOptions {
     pub deviation: f64,
     /// Number of intermediate points
     pub points: u32,
+    /// Bend of the path as a fraction of the distance, perpendicular to the
+    /// straight line (0 keeps the control points on the line; the sign picks the side)
+    pub curvature: f64,
+    /// Probability of overshooting the target and then correcting back to it
+    pub overshoot_probability: f64,
+    /// How far past the target an overshoot goes, as a fraction of the distance
+    pub overshoot_magnitude: f64,
 }
 
 impl Default for MouseMoveOptions {
@@ -176,10 +236,28 @@ impl Default for MouseMoveOptions {
             duration_ms: 500,
             deviation: 50.0,
             points: 20,
+            curvature: 0.0,
+            overshoot_probability: 0.0,
+            overshoot_magnitude: 0.1,
         }
     }
 }
 
+/// Longest mouse trace that can be replayed, in milliseconds
+pub const MAX_MOUSE_TRACE_DURATION_MS: u64 = 60_000;
+
+/// Longest idle period that can be simulated, in milliseconds
+pub const MAX_IDLE_DURATION_MS: u64 = 300_000;
+
+/// A recorded mouse position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TracePoint {
+    pub x: f64,
+    pub y: f64,
+    /// Milliseconds since the start of the recording
+    pub t_ms: u64,
+}
+
 /// Typing options
 #[derive(Debug, Clone)]
 pub struct TypingOptions {
@@ -204,6 +282,30 @@ impl Default for TypingOptions {
     }
 }
 
+/// Mouse button
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MouseButton {
+    #[default]
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+}
+
+impl MouseButton {
+    /// Button name used by `Input.dispatchMouseEvent`
+    pub fn as_cdp(&self) -> &'static str {
+        match self {
+            MouseButton::Left => "left",
+            MouseButton::Right => "right",
+            MouseButton::Middle => "middle",
+            MouseButton::Back => "back",
+            MouseButton::Forward => "forward",
+        }
+    }
+}
+
 /// Click options
 #[derive(Debug, Clone)]
 pub struct ClickOptions {
@@ -277,9 +379,13 @@ pub trait FingerprintGenerator: Send + Sync {
     ) -> Result<services::Fingerprint, crate::Error>;
 
     /// Randomize an existing fingerprint
+    ///
+    /// With `seed`, the result depends only on the seed and `fingerprint`;
+    /// otherwise the generator's own RNG is used.
     async fn randomize(
         &self,
         fingerprint: &services::Fingerprint,
+        seed: Option<u64>,
     ) -> Result<services::Fingerprint, crate::Error>;
 }
 
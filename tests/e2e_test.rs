@@ -5,7 +5,7 @@
 mod common;
 mod mock_chrome;
 
-use chaser_oxide::session::{SessionManager, SessionManagerImpl, BrowserOptions, PageOptions};
+use chaser_oxide::session::{SessionManager, SessionManagerImpl, BrowserOptions, NavigationOptions, PageOptions};
 use common::{setup_test_browser, setup_test_page, teardown_test_browser};
 use std::sync::Arc;
 
@@ -223,7 +223,7 @@ async fn test_page_reload() {
     page.set_content(html).await.unwrap();
 
     // Reload
-    let reload_result = page.reload(false).await;
+    let reload_result = page.reload(false, NavigationOptions::default()).await;
     assert!(reload_result.is_ok());
 
     // Verify page still active